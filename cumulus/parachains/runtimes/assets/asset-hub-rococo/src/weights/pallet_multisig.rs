@@ -177,4 +177,162 @@ impl<T: frame_system::Config> pallet_multisig::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Multisig::NextStatefulMultisigIndex` (r:1 w:1)
+	/// Proof: `Multisig::NextStatefulMultisigIndex` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulMultisigs` (r:0 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn create_stateful_multisig(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `1489`
+		// Minimum execution time: 27_247_000 picoseconds.
+		Weight::from_parts(28_988_608, 0)
+			.saturating_add(Weight::from_parts(0, 1489))
+			// Standard Error: 786
+			.saturating_add(Weight::from_parts(70_780, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_create(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (37 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 44_255_000 picoseconds.
+		Weight::from_parts(33_698_736, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_682
+			.saturating_add(Weight::from_parts(151_403, 0).saturating_mul(s.into()))
+			// Standard Error: 20
+			.saturating_add(Weight::from_parts(1_813, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[3, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_approve(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 30_192_000 picoseconds.
+		Weight::from_parts(18_493_745, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_954
+			.saturating_add(Weight::from_parts(175_861, 0).saturating_mul(s.into()))
+			// Standard Error: 21
+			.saturating_add(Weight::from_parts(1_949, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_complete(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `468 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 55_398_000 picoseconds.
+		Weight::from_parts(38_685_311, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_370
+			.saturating_add(Weight::from_parts(213_372, 0).saturating_mul(s.into()))
+			// Standard Error: 22
+			.saturating_add(Weight::from_parts(2_036, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_multi_stateful_create(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (37 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 28_954_000 picoseconds.
+		Weight::from_parts(30_627_112, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_640
+			.saturating_add(Weight::from_parts(147_659, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_multi_stateful_approve(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 16_348_000 picoseconds.
+		Weight::from_parts(17_378_333, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_882
+			.saturating_add(Weight::from_parts(169_391, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	fn cancel_as_multi_stateful() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3618`
+		//  Estimated: `7163`
+		// Minimum execution time: 34_416_000 picoseconds.
+		Weight::from_parts(36_090_075, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn set_stateful_multisig_members(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3710`
+		//  Estimated: `7163`
+		// Minimum execution time: 46_599_000 picoseconds.
+		Weight::from_parts(47_235_216, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 906
+			.saturating_add(Weight::from_parts(81_622, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn dissolve_stateful_multisig() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3745`
+		//  Estimated: `7163`
+		// Minimum execution time: 48_688_000 picoseconds.
+		Weight::from_parts(50_550_367, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
 }
@@ -56,6 +56,10 @@ impl pallet_conviction_voting::Config for Runtime {
 	type Polls = Referenda;
 	type BlockNumberProvider = RelaychainDataProvider<Runtime>;
 	type VotingHooks = (); // TODO review
+	type PersonhoodOrigin =
+		frame_support::traits::NeverEnsureOrigin<frame_support::traits::reality::Alias>;
+	type PersonhoodClasses = frame_support::traits::Nothing;
+	type VotesPerPerson = frame_support::traits::ConstU128<1>;
 }
 
 parameter_types! {
@@ -177,4 +177,162 @@ impl<T: frame_system::Config> pallet_multisig::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Multisig::NextStatefulMultisigIndex` (r:1 w:1)
+	/// Proof: `Multisig::NextStatefulMultisigIndex` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulMultisigs` (r:0 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn create_stateful_multisig(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `1489`
+		// Minimum execution time: 30_756_000 picoseconds.
+		Weight::from_parts(32_721_547, 0)
+			.saturating_add(Weight::from_parts(0, 1489))
+			// Standard Error: 887
+			.saturating_add(Weight::from_parts(79_895, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_create(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (37 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 49_901_000 picoseconds.
+		Weight::from_parts(37_986_893, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_898
+			.saturating_add(Weight::from_parts(170_899, 0).saturating_mul(s.into()))
+			// Standard Error: 22
+			.saturating_add(Weight::from_parts(2_046, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[3, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_approve(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 34_027_000 picoseconds.
+		Weight::from_parts(20_823_915, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_205
+			.saturating_add(Weight::from_parts(198_507, 0).saturating_mul(s.into()))
+			// Standard Error: 24
+			.saturating_add(Weight::from_parts(2_200, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_complete(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `468 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 62_478_000 picoseconds.
+		Weight::from_parts(43_615_603, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_676
+			.saturating_add(Weight::from_parts(240_848, 0).saturating_mul(s.into()))
+			// Standard Error: 25
+			.saturating_add(Weight::from_parts(2_298, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_multi_stateful_create(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (37 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 32_630_000 picoseconds.
+		Weight::from_parts(34_519_728, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_851
+			.saturating_add(Weight::from_parts(166_674, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_multi_stateful_approve(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 18_401_000 picoseconds.
+		Weight::from_parts(19_564_868, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_124
+			.saturating_add(Weight::from_parts(191_204, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	fn cancel_as_multi_stateful() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3618`
+		//  Estimated: `7163`
+		// Minimum execution time: 38_795_000 picoseconds.
+		Weight::from_parts(40_686_171, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn set_stateful_multisig_members(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3710`
+		//  Estimated: `7163`
+		// Minimum execution time: 52_600_000 picoseconds.
+		Weight::from_parts(53_317_820, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_023
+			.saturating_add(Weight::from_parts(92_133, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn dissolve_stateful_multisig() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3745`
+		//  Estimated: `7163`
+		// Minimum execution time: 54_958_000 picoseconds.
+		Weight::from_parts(57_059_871, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
 }
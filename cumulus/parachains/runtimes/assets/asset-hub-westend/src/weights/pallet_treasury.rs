@@ -175,4 +175,76 @@ impl<T: frame_system::Config> pallet_treasury::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: AssetRate ConversionRateToNative (r:1 w:0)
+	/// Proof: AssetRate ConversionRateToNative (max_values: None, max_size: Some(1237), added: 3712, mode: MaxEncodedLen)
+	/// Storage: Treasury StreamCount (r:1 w:1)
+	/// Proof: Treasury StreamCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Treasury Streams (r:0 w:1)
+	/// Proof: Treasury Streams (max_values: None, max_size: Some(1889), added: 4364, mode: MaxEncodedLen)
+	fn spend_stream() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `155`
+		//  Estimated: `4702`
+		// Minimum execution time: 225_680_000 picoseconds.
+		Weight::from_parts(240_870_000, 0)
+			.saturating_add(Weight::from_parts(0, 4702))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Treasury Streams (r:1 w:1)
+	/// Proof: Treasury Streams (max_values: None, max_size: Some(1889), added: 4364, mode: MaxEncodedLen)
+	/// Storage: XcmPallet QueryCounter (r:1 w:1)
+	/// Proof Skipped: XcmPallet QueryCounter (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Configuration ActiveConfig (r:1 w:0)
+	/// Proof Skipped: Configuration ActiveConfig (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Dmp DeliveryFeeFactor (r:1 w:0)
+	/// Proof Skipped: Dmp DeliveryFeeFactor (max_values: None, max_size: None, mode: Measured)
+	/// Storage: XcmPallet SupportedVersion (r:1 w:0)
+	/// Proof Skipped: XcmPallet SupportedVersion (max_values: None, max_size: None, mode: Measured)
+	/// Storage: XcmPallet VersionDiscoveryQueue (r:1 w:1)
+	/// Proof Skipped: XcmPallet VersionDiscoveryQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: XcmPallet SafeXcmVersion (r:1 w:0)
+	/// Proof Skipped: XcmPallet SafeXcmVersion (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Dmp DownwardMessageQueues (r:1 w:1)
+	/// Proof Skipped: Dmp DownwardMessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Dmp DownwardMessageQueueHeads (r:1 w:1)
+	/// Proof Skipped: Dmp DownwardMessageQueueHeads (max_values: None, max_size: None, mode: Measured)
+	/// Storage: XcmPallet Queries (r:0 w:1)
+	/// Proof Skipped: XcmPallet Queries (max_values: None, max_size: None, mode: Measured)
+	fn claim_stream() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `778`
+		//  Estimated: `5354`
+		// Minimum execution time: 597_835_000 picoseconds.
+		Weight::from_parts(617_365_000, 0)
+			.saturating_add(Weight::from_parts(0, 5354))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	/// Storage: Treasury Streams (r:1 w:1)
+	/// Proof: Treasury Streams (max_values: None, max_size: Some(1889), added: 4364, mode: MaxEncodedLen)
+	/// Storage: XcmPallet Queries (r:1 w:1)
+	/// Proof Skipped: XcmPallet Queries (max_values: None, max_size: None, mode: Measured)
+	fn check_stream_status() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `483`
+		//  Estimated: `5354`
+		// Minimum execution time: 265_825_000 picoseconds.
+		Weight::from_parts(304_885_000, 0)
+			.saturating_add(Weight::from_parts(0, 5354))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Treasury Streams (r:1 w:1)
+	/// Proof: Treasury Streams (max_values: None, max_size: Some(1889), added: 4364, mode: MaxEncodedLen)
+	fn cancel_stream() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `213`
+		//  Estimated: `5354`
+		// Minimum execution time: 159_495_000 picoseconds.
+		Weight::from_parts(173_600_000, 0)
+			.saturating_add(Weight::from_parts(0, 5354))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
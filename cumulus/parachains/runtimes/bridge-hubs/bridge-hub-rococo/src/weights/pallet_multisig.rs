@@ -177,4 +177,162 @@ impl<T: frame_system::Config> pallet_multisig::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Multisig::NextStatefulMultisigIndex` (r:1 w:1)
+	/// Proof: `Multisig::NextStatefulMultisigIndex` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulMultisigs` (r:0 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn create_stateful_multisig(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `1489`
+		// Minimum execution time: 25_986_000 picoseconds.
+		Weight::from_parts(27_647_083, 0)
+			.saturating_add(Weight::from_parts(0, 1489))
+			// Standard Error: 750
+			.saturating_add(Weight::from_parts(67_505, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_create(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (37 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 42_226_000 picoseconds.
+		Weight::from_parts(32_157_680, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_604
+			.saturating_add(Weight::from_parts(144_396, 0).saturating_mul(s.into()))
+			// Standard Error: 19
+			.saturating_add(Weight::from_parts(1_729, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[3, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_approve(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 28_814_000 picoseconds.
+		Weight::from_parts(17_656_340, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_863
+			.saturating_add(Weight::from_parts(167_723, 0).saturating_mul(s.into()))
+			// Standard Error: 20
+			.saturating_add(Weight::from_parts(1_859, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_complete(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `468 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 52_853_000 picoseconds.
+		Weight::from_parts(36_913_488, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_261
+			.saturating_add(Weight::from_parts(203_497, 0).saturating_mul(s.into()))
+			// Standard Error: 21
+			.saturating_add(Weight::from_parts(1_942, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_multi_stateful_create(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (37 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 27_633_000 picoseconds.
+		Weight::from_parts(29_228_203, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_564
+			.saturating_add(Weight::from_parts(140_826, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_multi_stateful_approve(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 15_611_000 picoseconds.
+		Weight::from_parts(16_592_546, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_795
+			.saturating_add(Weight::from_parts(161_552, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	fn cancel_as_multi_stateful() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3618`
+		//  Estimated: `7163`
+		// Minimum execution time: 32_843_000 picoseconds.
+		Weight::from_parts(34_438_353, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn set_stateful_multisig_members(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3710`
+		//  Estimated: `7163`
+		// Minimum execution time: 44_443_000 picoseconds.
+		Weight::from_parts(45_049_281, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 864
+			.saturating_add(Weight::from_parts(77_845, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn dissolve_stateful_multisig() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3745`
+		//  Estimated: `7163`
+		// Minimum execution time: 46_434_000 picoseconds.
+		Weight::from_parts(48_211_014, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
 }
@@ -177,4 +177,162 @@ impl<T: frame_system::Config> pallet_multisig::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Multisig::NextStatefulMultisigIndex` (r:1 w:1)
+	/// Proof: `Multisig::NextStatefulMultisigIndex` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulMultisigs` (r:0 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn create_stateful_multisig(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `1489`
+		// Minimum execution time: 30_070_000 picoseconds.
+		Weight::from_parts(31_992_458, 0)
+			.saturating_add(Weight::from_parts(0, 1489))
+			// Standard Error: 867
+			.saturating_add(Weight::from_parts(78_115, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_create(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (37 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 48_798_000 picoseconds.
+		Weight::from_parts(37_149_363, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_856
+			.saturating_add(Weight::from_parts(167_091, 0).saturating_mul(s.into()))
+			// Standard Error: 22
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[3, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_approve(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 33_278_000 picoseconds.
+		Weight::from_parts(20_368_804, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_156
+			.saturating_add(Weight::from_parts(194_084, 0).saturating_mul(s.into()))
+			// Standard Error: 23
+			.saturating_add(Weight::from_parts(2_151, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_complete(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `468 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 61_095_000 picoseconds.
+		Weight::from_parts(42_652_655, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_616
+			.saturating_add(Weight::from_parts(235_482, 0).saturating_mul(s.into()))
+			// Standard Error: 24
+			.saturating_add(Weight::from_parts(2_247, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_multi_stateful_create(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (37 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 31_912_000 picoseconds.
+		Weight::from_parts(33_759_452, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_810
+			.saturating_add(Weight::from_parts(162_960, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_multi_stateful_approve(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(19_137_811, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_077
+			.saturating_add(Weight::from_parts(186_944, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	fn cancel_as_multi_stateful() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3618`
+		//  Estimated: `7163`
+		// Minimum execution time: 37_940_000 picoseconds.
+		Weight::from_parts(39_788_496, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn set_stateful_multisig_members(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3710`
+		//  Estimated: `7163`
+		// Minimum execution time: 51_428_000 picoseconds.
+		Weight::from_parts(52_129_811, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_parts(90_080, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn dissolve_stateful_multisig() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3745`
+		//  Estimated: `7163`
+		// Minimum execution time: 53_733_000 picoseconds.
+		Weight::from_parts(55_788_483, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
 }
@@ -263,6 +263,7 @@ impl pallet_multisig::Config for Runtime {
 	type MaxSignatories = ConstU32<100>;
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type ProposalLifetime = ConstU32<{ 30 * DAYS }>;
 }
 
 impl pallet_utility::Config for Runtime {
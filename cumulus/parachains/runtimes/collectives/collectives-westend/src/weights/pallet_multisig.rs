@@ -177,4 +177,162 @@ impl<T: frame_system::Config> pallet_multisig::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Multisig::NextStatefulMultisigIndex` (r:1 w:1)
+	/// Proof: `Multisig::NextStatefulMultisigIndex` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulMultisigs` (r:0 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn create_stateful_multisig(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `1489`
+		// Minimum execution time: 29_385_000 picoseconds.
+		Weight::from_parts(31_263_368, 0)
+			.saturating_add(Weight::from_parts(0, 1489))
+			// Standard Error: 848
+			.saturating_add(Weight::from_parts(76_334, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_create(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (37 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 47_695_000 picoseconds.
+		Weight::from_parts(36_311_832, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_814
+			.saturating_add(Weight::from_parts(163_283, 0).saturating_mul(s.into()))
+			// Standard Error: 21
+			.saturating_add(Weight::from_parts(1_955, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[3, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_approve(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 32_529_000 picoseconds.
+		Weight::from_parts(19_913_692, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_107
+			.saturating_add(Weight::from_parts(189_661, 0).saturating_mul(s.into()))
+			// Standard Error: 23
+			.saturating_add(Weight::from_parts(2_102, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_multi_stateful_complete(s: u32, z: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `468 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 59_712_000 picoseconds.
+		Weight::from_parts(41_689_707, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_556
+			.saturating_add(Weight::from_parts(230_115, 0).saturating_mul(s.into()))
+			// Standard Error: 24
+			.saturating_add(Weight::from_parts(2_196, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_multi_stateful_create(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + s * (37 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 31_194_000 picoseconds.
+		Weight::from_parts(32_999_175, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 1_769
+			.saturating_add(Weight::from_parts(159_246, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_multi_stateful_approve(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402 + s * (69 ±0)`
+		//  Estimated: `7163`
+		// Minimum execution time: 17_599_000 picoseconds.
+		Weight::from_parts(18_710_753, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 2_029
+			.saturating_add(Weight::from_parts(182_683, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::StatefulProposals` (r:1 w:1)
+	/// Proof: `Multisig::StatefulProposals` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+	fn cancel_as_multi_stateful() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3618`
+		//  Estimated: `7163`
+		// Minimum execution time: 37_085_000 picoseconds.
+		Weight::from_parts(38_890_821, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 100]`.
+	fn set_stateful_multisig_members(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3710`
+		//  Estimated: `7163`
+		// Minimum execution time: 50_256_000 picoseconds.
+		Weight::from_parts(50_941_803, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			// Standard Error: 978
+			.saturating_add(Weight::from_parts(88_027, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Multisig::StatefulMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StatefulMultisigs` (`max_values`: None, `max_size`: Some(3698), added: 6173, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn dissolve_stateful_multisig() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3745`
+		//  Estimated: `7163`
+		// Minimum execution time: 52_509_000 picoseconds.
+		Weight::from_parts(54_517_096, 0)
+			.saturating_add(Weight::from_parts(0, 7163))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
 }
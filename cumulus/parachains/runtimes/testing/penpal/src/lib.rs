@@ -654,6 +654,11 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type MaxSwapPathLength = ConstU32<3>;
+	type AmplificationOrigin = EnsureRoot<AccountId>;
+	type MaxAmplification = ConstU32<10_000>;
+	type MinAmplificationRampDuration = ConstU32<DAYS>;
+	type MaxObservations = ConstU32<48>;
+	type ObservationPeriod = ConstU32<HOURS>;
 	type MintMinLiquidity = ConstU128<100>;
 	type WeightInfo = ();
 	#[cfg(feature = "runtime-benchmarks")]
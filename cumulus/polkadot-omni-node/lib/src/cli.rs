@@ -100,6 +100,9 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export a state snapshot of a finalized block for checkpoint sync.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
@@ -205,6 +208,17 @@ pub struct Cli<Config: CliConfig> {
 	#[arg(long)]
 	pub enable_statement_store: bool,
 
+	/// Serve the Ethereum JSON-RPC (`eth_*`, `net_*`, `web3_*` and `debug_*` methods) on the node
+	/// RPC server.
+	///
+	/// This is only supported for runtimes that include `pallet-revive` and expose the
+	/// `ReviveApi` runtime API. Transactions and receipts can be looked up by hash for the
+	/// latest 256 best blocks only, use the standalone `pallet-revive-eth-rpc` server with a
+	/// database to look up older ones.
+	#[cfg(feature = "eth-rpc")]
+	#[arg(long)]
+	pub enable_eth_rpc: bool,
+
 	#[arg(skip)]
 	pub(crate) _phantom: PhantomData<Config>,
 }
@@ -240,6 +254,10 @@ impl<Config: CliConfig> Cli<Config> {
 			export_pov: self.export_pov_to_path.clone(),
 			max_pov_percentage: self.run.experimental_max_pov_percentage,
 			enable_statement_store: self.enable_statement_store,
+			#[cfg(feature = "eth-rpc")]
+			enable_eth_rpc: self.enable_eth_rpc,
+			#[cfg(not(feature = "eth-rpc"))]
+			enable_eth_rpc: false,
 		}
 	}
 }
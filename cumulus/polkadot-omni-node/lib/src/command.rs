@@ -23,7 +23,7 @@ use crate::{
 			RuntimeResolver,
 		},
 		types::Block,
-		NodeBlock, NodeExtraArgs,
+		NodeExtraArgs,
 	},
 	extra_subcommand::DefaultExtraSubcommands,
 	fake_runtime_api,
@@ -60,25 +60,23 @@ impl RunConfig {
 	}
 }
 
-pub fn new_aura_node_spec<Block>(
-	aura_id: AuraConsensusId,
-	extra_args: &NodeExtraArgs,
-) -> Box<dyn DynNodeSpecExt>
-where
-	Block: NodeBlock,
-{
-	match aura_id {
-		AuraConsensusId::Sr25519 => crate::nodes::aura::new_aura_node_spec::<
-			Block,
-			fake_runtime_api::aura_sr25519::RuntimeApi,
-			sp_consensus_aura::sr25519::AuthorityId,
-		>(extra_args),
-		AuraConsensusId::Ed25519 => crate::nodes::aura::new_aura_node_spec::<
-			Block,
-			fake_runtime_api::aura_ed25519::RuntimeApi,
-			sp_consensus_aura::ed25519::AuthorityId,
-		>(extra_args),
-	}
+/// Creates the Aura node spec for blocks using the given block number type, backed by the fake
+/// runtime declared for that block number.
+macro_rules! new_aura_node_spec {
+	($block_number: ty, $fake_runtime: ident, $aura_id: expr, $extra_args: expr) => {
+		match $aura_id {
+			AuraConsensusId::Sr25519 => crate::nodes::aura::new_aura_node_spec::<
+				Block<$block_number>,
+				fake_runtime_api::aura_sr25519::$fake_runtime::RuntimeApi,
+				sp_consensus_aura::sr25519::AuthorityId,
+			>($extra_args),
+			AuraConsensusId::Ed25519 => crate::nodes::aura::new_aura_node_spec::<
+				Block<$block_number>,
+				fake_runtime_api::aura_ed25519::$fake_runtime::RuntimeApi,
+				sp_consensus_aura::ed25519::AuthorityId,
+			>($extra_args),
+		}
+	};
 }
 
 fn new_node_spec(
@@ -91,9 +89,9 @@ fn new_node_spec(
 	Ok(match runtime {
 		Runtime::Omni(block_number, consensus) => match (block_number, consensus) {
 			(BlockNumber::U32, Consensus::Aura(aura_id)) =>
-				new_aura_node_spec::<Block<u32>>(aura_id, extra_args),
+				new_aura_node_spec!(u32, block_number_u32, aura_id, extra_args),
 			(BlockNumber::U64, Consensus::Aura(aura_id)) =>
-				new_aura_node_spec::<Block<u64>>(aura_id, extra_args),
+				new_aura_node_spec!(u64, block_number_u64, aura_id, extra_args),
 		},
 	})
 }
@@ -174,6 +172,14 @@ where
 				node.prepare_export_state_cmd(config, cmd)
 			})
 		},
+		Some(Subcommand::ExportSnapshot(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let node =
+					new_node_spec(&config, &cmd_config.runtime_resolver, &cli.node_extra_args())?;
+				node.prepare_export_snapshot_cmd(config, cmd)
+			})
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -277,6 +283,7 @@ where
 					let runner = cli.create_runner(cmd)?;
 					runner.sync_run(|config| cmd.run(&config, SUBSTRATE_REFERENCE_HARDWARE.clone()))
 				},
+				BenchmarkCmd::Compare(cmd) => cmd.run(),
 				#[allow(unreachable_patterns)]
 				_ => Err("Benchmarking sub-command unsupported or compilation feature missing. \
 					Make sure to compile omni-node with --features=runtime-benchmarks \
@@ -308,13 +315,13 @@ where
 					// TODO: take block time from AURA config if set.
 					let dev_block_time = cli.dev_block_time.unwrap_or(DEFAULT_DEV_BLOCK_TIME_MS);
 					return node_spec
-						.start_manual_seal_node(config, dev_block_time)
+						.start_manual_seal_node(config, dev_block_time, cli.node_extra_args())
 						.map_err(Into::into);
 				}
 
 				if let Some(dev_block_time) = cli.dev_block_time {
 					return node_spec
-						.start_manual_seal_node(config, dev_block_time)
+						.start_manual_seal_node(config, dev_block_time, cli.node_extra_args())
 						.map_err(Into::into);
 				}
 
@@ -19,7 +19,9 @@ use cumulus_client_cli::ExportGenesisHeadCommand;
 use frame_benchmarking_cli::BlockCmd;
 #[cfg(any(feature = "runtime-benchmarks"))]
 use frame_benchmarking_cli::StorageCmd;
-use sc_cli::{CheckBlockCmd, ExportBlocksCmd, ExportStateCmd, ImportBlocksCmd, RevertCmd};
+use sc_cli::{
+	CheckBlockCmd, ExportBlocksCmd, ExportSnapshotCmd, ExportStateCmd, ImportBlocksCmd, RevertCmd,
+};
 use sc_service::{Configuration, TaskManager};
 use std::{future::Future, pin::Pin};
 
@@ -47,6 +49,12 @@ pub trait NodeCommandRunner {
 		cmd: &ExportStateCmd,
 	) -> AsyncCmdResult<'_>;
 
+	fn prepare_export_snapshot_cmd(
+		self: Box<Self>,
+		config: Configuration,
+		cmd: &ExportSnapshotCmd,
+	) -> AsyncCmdResult<'_>;
+
 	fn prepare_import_blocks_cmd(
 		self: Box<Self>,
 		config: Configuration,
@@ -110,6 +118,15 @@ where
 		Ok((Box::pin(cmd.run(partial.client, config.chain_spec)), partial.task_manager))
 	}
 
+	fn prepare_export_snapshot_cmd(
+		self: Box<Self>,
+		config: Configuration,
+		cmd: &ExportSnapshotCmd,
+	) -> AsyncCmdResult<'_> {
+		let partial = T::new_partial(&config).map_err(sc_cli::Error::Service)?;
+		Ok((Box::pin(cmd.run(partial.client)), partial.task_manager))
+	}
+
 	fn prepare_import_blocks_cmd(
 		self: Box<Self>,
 		config: Configuration,
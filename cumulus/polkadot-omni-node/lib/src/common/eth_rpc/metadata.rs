@@ -0,0 +1,127 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The parts of the runtime metadata that the embedded Ethereum JSON-RPC depends on.
+
+use crate::common::runtime::{
+	MetadataInspector, DEFAULT_FRAME_SYSTEM_PALLET_NAME, DEFAULT_REVIVE_PALLET_NAME,
+	DEFAULT_REVIVE_RUNTIME_API_NAME, DEFAULT_TIMESTAMP_PALLET_NAME,
+};
+use codec::{Compact, Decode, Encode};
+use sp_runtime::OpaqueExtrinsic;
+use sp_storage::StorageKey;
+
+/// Extrinsic version byte of a bare (unsigned) extrinsic, as used by `eth_transact`.
+const BARE_EXTRINSIC_VERSIONS: [u8; 2] = [4, 5];
+
+/// The `pallet_revive::Call::eth_transact` call of the runtime.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct EthTransactCall {
+	/// Index of `pallet-revive` in the runtime.
+	pub pallet_index: u8,
+	/// Index of `eth_transact` in the calls of `pallet-revive`.
+	pub call_index: u8,
+}
+
+impl EthTransactCall {
+	/// Encode `payload` as a bare `eth_transact` extrinsic.
+	pub fn extrinsic(&self, payload: Vec<u8>) -> OpaqueExtrinsic {
+		let extrinsic =
+			(BARE_EXTRINSIC_VERSIONS[0], self.pallet_index, self.call_index, payload).encode();
+		OpaqueExtrinsic::from_bytes(&extrinsic.encode())
+			.expect("`Vec<u8>` encoding is a valid `OpaqueExtrinsic`; qed")
+	}
+
+	/// Returns the signed Ethereum transaction payload if `extrinsic` is an `eth_transact` call.
+	pub fn payload(&self, extrinsic: &OpaqueExtrinsic) -> Option<Vec<u8>> {
+		let encoded = extrinsic.encode();
+		let mut input = &encoded[..];
+		let _length = Compact::<u32>::decode(&mut input).ok()?;
+		let (version, pallet_index, call_index) = <(u8, u8, u8)>::decode(&mut input).ok()?;
+		if !BARE_EXTRINSIC_VERSIONS.contains(&version) ||
+			(pallet_index, call_index) != (self.pallet_index, self.call_index)
+		{
+			return None
+		}
+
+		Vec::<u8>::decode(&mut input).ok()
+	}
+}
+
+/// The parts of the runtime metadata that the embedded Ethereum JSON-RPC depends on.
+pub(crate) struct ReviveMetadata {
+	/// The `pallet_revive::Config::ChainId` constant.
+	pub chain_id: u64,
+	/// The `pallet_revive::Config::NativeToEthRatio` constant.
+	pub native_to_eth_ratio: u32,
+	/// The `pallet_revive::Call::eth_transact` call.
+	pub eth_transact: EthTransactCall,
+	/// The storage key of `frame_system::Events`.
+	pub events_key: StorageKey,
+	/// The storage key of `pallet_timestamp::Now`, if the runtime includes the pallet.
+	pub timestamp_key: Option<StorageKey>,
+	/// The full metadata, used to decode the events of a block.
+	pub metadata: subxt::Metadata,
+}
+
+impl ReviveMetadata {
+	/// Returns `None` if the runtime does not include `pallet-revive` or `frame-system`.
+	pub fn from_inspector(inspector: MetadataInspector) -> Option<Self> {
+		if !inspector.runtime_api_exists(DEFAULT_REVIVE_RUNTIME_API_NAME) {
+			return None
+		}
+
+		let (pallet_index, call_index) =
+			inspector.call_index(DEFAULT_REVIVE_PALLET_NAME, "eth_transact")?;
+		Some(Self {
+			chain_id: inspector.constant(DEFAULT_REVIVE_PALLET_NAME, "ChainId")?,
+			native_to_eth_ratio: inspector
+				.constant(DEFAULT_REVIVE_PALLET_NAME, "NativeToEthRatio")?,
+			eth_transact: EthTransactCall { pallet_index, call_index },
+			events_key: StorageKey(
+				inspector.storage_value_key(DEFAULT_FRAME_SYSTEM_PALLET_NAME, "Events")?,
+			),
+			timestamp_key: inspector
+				.storage_value_key(DEFAULT_TIMESTAMP_PALLET_NAME, "Now")
+				.map(StorageKey),
+			metadata: inspector.into_inner().into(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CALL: EthTransactCall = EthTransactCall { pallet_index: 60, call_index: 0 };
+
+	#[test]
+	fn eth_transact_extrinsic_roundtrip() {
+		let payload = vec![1, 2, 3, 4];
+		let extrinsic = CALL.extrinsic(payload.clone());
+		assert_eq!(CALL.payload(&extrinsic), Some(payload));
+	}
+
+	#[test]
+	fn other_calls_are_not_eth_transactions() {
+		let other_call = EthTransactCall { call_index: 1, ..CALL }.extrinsic(vec![1, 2, 3, 4]);
+		assert_eq!(CALL.payload(&other_call), None);
+
+		let signed =
+			OpaqueExtrinsic::from_bytes(&(0b1000_0100u8, 60u8, 0u8).encode().encode()).unwrap();
+		assert_eq!(CALL.payload(&signed), None);
+	}
+}
@@ -0,0 +1,91 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ethereum JSON-RPC served in-process for runtimes that include `pallet-revive`.
+//!
+//! This is an embedded alternative to the standalone `pallet-revive-eth-rpc` server. Instead of
+//! talking to the node over a WebSocket connection, the methods are answered by calling the
+//! `ReviveApi` runtime API through the node client and by submitting transactions straight to
+//! the transaction pool.
+//!
+//! Receipts and logs are extracted on demand from the block extrinsics and the events stored in
+//! its state. Lookups by transaction hash are served from an in-memory index of the latest best
+//! blocks, which is also used to compute the fee history.
+//!
+//! The server is only available when the crate is built with the `eth-rpc` feature.
+
+#[cfg(feature = "eth-rpc")]
+mod metadata;
+#[cfg(feature = "eth-rpc")]
+mod receipts;
+#[cfg(feature = "eth-rpc")]
+mod server;
+
+#[cfg(feature = "eth-rpc")]
+pub(crate) use server::EthRpc;
+
+#[cfg(feature = "eth-rpc")]
+use crate::common::types::{AccountId, Balance, Nonce};
+#[cfg(feature = "eth-rpc")]
+use pallet_revive::ReviveApi;
+use sc_service::SpawnTaskHandle;
+#[cfg(feature = "eth-rpc")]
+use sp_api::{ApiExt, Metadata};
+use sp_consensus::SyncOracle;
+use sp_runtime::traits::Block as BlockT;
+#[cfg(feature = "eth-rpc")]
+use sp_runtime::traits::NumberFor;
+use std::sync::Arc;
+
+/// Convenience trait that defines the bounds of a parachain runtime that includes
+/// `pallet-revive`.
+#[cfg(feature = "eth-rpc")]
+pub trait ReviveRuntimeApi<Block: BlockT>:
+	ApiExt<Block>
+	+ Metadata<Block>
+	+ ReviveApi<Block, AccountId, Balance, Nonce, NumberFor<Block>>
+	+ Sized
+{
+	/// Check if the runtime has the `ReviveApi`.
+	fn has_revive_api(&self, at: Block::Hash) -> bool {
+		self.has_api::<dyn ReviveApi<Block, AccountId, Balance, Nonce, NumberFor<Block>>>(at)
+			.unwrap_or(false)
+	}
+}
+
+#[cfg(feature = "eth-rpc")]
+impl<T, Block: BlockT> ReviveRuntimeApi<Block> for T where
+	T: ApiExt<Block>
+		+ Metadata<Block>
+		+ ReviveApi<Block, AccountId, Balance, Nonce, NumberFor<Block>>
+{
+}
+
+/// Without the `eth-rpc` feature the runtime is not required to expose the `ReviveApi`.
+#[cfg(not(feature = "eth-rpc"))]
+pub trait ReviveRuntimeApi<Block: BlockT> {}
+
+#[cfg(not(feature = "eth-rpc"))]
+impl<T, Block: BlockT> ReviveRuntimeApi<Block> for T {}
+
+/// The node services the embedded Ethereum JSON-RPC depends on.
+#[derive(Clone)]
+pub struct EthRpcDeps {
+	/// Used to report the syncing status through `eth_syncing` and `net_listening`.
+	pub sync_oracle: Arc<dyn SyncOracle + Send + Sync>,
+	/// Used to spawn the task that indexes the Ethereum transactions of new best blocks.
+	pub spawn_handle: SpawnTaskHandle,
+}
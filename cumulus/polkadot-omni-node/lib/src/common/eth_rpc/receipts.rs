@@ -0,0 +1,295 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Extraction of the Ethereum receipts of a block, and the in-memory index of the receipts of the
+//! latest best blocks.
+
+use pallet_revive::{
+	create1,
+	evm::{
+		AddressOrAddresses, Filter, FilterTopic, GenericTransaction, Log, ReceiptInfo,
+		TransactionSigned, H256, U256,
+	},
+};
+use pallet_revive_eth_rpc::{
+	client::ClientError,
+	subxt_client::{
+		revive::events::ContractEmitted, system::events::ExtrinsicSuccess,
+		transaction_payment::events::TransactionFeePaid, SrcChainConfig,
+	},
+};
+use sp_crypto_hashing::keccak_256;
+use std::collections::{HashMap, VecDeque};
+use subxt::events::{Events, Phase};
+
+/// The number of best blocks kept in the [`ReceiptsCache`], same default as
+/// `pallet-revive-eth-rpc`.
+pub(crate) const CACHE_SIZE: usize = 256;
+
+/// Extract the [`TransactionSigned`] and the [`ReceiptInfo`] of the `eth_transact` extrinsic at
+/// `transaction_index`, from its `payload` and the events of the block.
+pub(crate) fn extract_receipt(
+	events: &Events<SrcChainConfig>,
+	block_hash: H256,
+	block_number: U256,
+	transaction_index: u32,
+	payload: &[u8],
+	base_gas_price: U256,
+	native_to_eth_ratio: u32,
+) -> Result<(TransactionSigned, ReceiptInfo), ClientError> {
+	let extrinsic_events = events
+		.iter()
+		.filter(|event| match event {
+			Ok(event) =>
+				matches!(event.phase(), Phase::ApplyExtrinsic(index) if index == transaction_index),
+			Err(_) => true,
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut success = false;
+	let mut tx_fees = None;
+	for event in &extrinsic_events {
+		success |= event.as_event::<ExtrinsicSuccess>()?.is_some();
+		if tx_fees.is_none() {
+			tx_fees = event.as_event::<TransactionFeePaid>()?;
+		}
+	}
+	let tx_fees = tx_fees.ok_or(ClientError::TxFeeNotFound)?;
+
+	let transaction_hash = H256(keccak_256(payload));
+	let signed_tx =
+		TransactionSigned::decode(payload).map_err(|_| ClientError::TxDecodingFailed)?;
+	let from = signed_tx
+		.recover_eth_address()
+		.map_err(|_| ClientError::RecoverEthAddressFailed)?;
+
+	let tx_info = GenericTransaction::from_signed(signed_tx.clone(), base_gas_price, Some(from));
+	let gas_price = tx_info.gas_price.unwrap_or_default();
+	let gas_used = U256::from(tx_fees.tip.saturating_add(tx_fees.actual_fee))
+		.saturating_mul(native_to_eth_ratio.into())
+		.checked_div(gas_price)
+		.unwrap_or_default();
+
+	let mut logs = Vec::new();
+	for event in &extrinsic_events {
+		if let Some(emitted) = event.as_event::<ContractEmitted>()? {
+			logs.push(Log {
+				address: emitted.contract,
+				topics: emitted.topics,
+				data: Some(emitted.data.into()),
+				block_number,
+				transaction_hash,
+				transaction_index: transaction_index.into(),
+				block_hash,
+				log_index: event.index().into(),
+				..Default::default()
+			});
+		}
+	}
+
+	let contract_address = if tx_info.to.is_none() {
+		Some(create1(
+			&from,
+			tx_info
+				.nonce
+				.unwrap_or_default()
+				.try_into()
+				.map_err(|_| ClientError::ConversionFailed)?,
+		))
+	} else {
+		None
+	};
+
+	let receipt = ReceiptInfo::new(
+		block_hash,
+		block_number,
+		contract_address,
+		from,
+		logs,
+		tx_info.to,
+		gas_price,
+		gas_used,
+		success,
+		transaction_hash,
+		transaction_index.into(),
+		tx_info.r#type.unwrap_or_default(),
+	);
+	Ok((signed_tx, receipt))
+}
+
+/// Returns `true` if `log` matches the address and topics of `filter`.
+///
+/// The block range of the filter is not checked.
+pub(crate) fn filter_matches(filter: &Filter, log: &Log) -> bool {
+	let address_matches = match &filter.address {
+		None => true,
+		Some(AddressOrAddresses::Address(address)) => *address == log.address,
+		Some(AddressOrAddresses::Addresses(addresses)) => addresses.contains(&log.address),
+	};
+
+	let topics_match = filter.topics.iter().flatten().enumerate().all(|(i, topic)| {
+		let Some(log_topic) = log.topics.get(i) else { return false };
+		match topic {
+			FilterTopic::Single(hash) => hash == log_topic,
+			FilterTopic::Multiple(hashes) => hashes.contains(log_topic),
+		}
+	});
+
+	address_matches && topics_match
+}
+
+/// In-memory index of the Ethereum transactions included in the latest best blocks.
+#[derive(Default)]
+pub(crate) struct ReceiptsCache {
+	/// The indexed blocks, oldest first, with the hashes of their transactions.
+	blocks: VecDeque<(H256, Vec<H256>)>,
+	/// The indexed transactions, by transaction hash.
+	transactions: HashMap<H256, (TransactionSigned, ReceiptInfo)>,
+}
+
+impl ReceiptsCache {
+	/// Returns `true` if the given block is already indexed.
+	pub fn contains_block(&self, block_hash: &H256) -> bool {
+		self.blocks.iter().any(|(hash, _)| hash == block_hash)
+	}
+
+	/// Index the receipts of the given block, evicting the oldest block if the cache is full.
+	pub fn insert(&mut self, block_hash: H256, receipts: Vec<(TransactionSigned, ReceiptInfo)>) {
+		if self.contains_block(&block_hash) {
+			return
+		}
+
+		if self.blocks.len() >= CACHE_SIZE {
+			if let Some((evicted, hashes)) = self.blocks.pop_front() {
+				for hash in hashes {
+					// The transaction might have been re-included in a more recent block.
+					if self.transactions.get(&hash).is_some_and(|(_, r)| r.block_hash == evicted) {
+						self.transactions.remove(&hash);
+					}
+				}
+			}
+		}
+
+		let hashes = receipts.iter().map(|(_, receipt)| receipt.transaction_hash).collect();
+		self.blocks.push_back((block_hash, hashes));
+		for (signed_tx, receipt) in receipts {
+			self.transactions.insert(receipt.transaction_hash, (signed_tx, receipt));
+		}
+	}
+
+	/// Returns the transaction and the receipt of the given transaction hash.
+	pub fn get(&self, transaction_hash: &H256) -> Option<&(TransactionSigned, ReceiptInfo)> {
+		self.transactions.get(transaction_hash)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_revive::evm::H160;
+
+	fn hash(n: u64) -> H256 {
+		H256::from_low_u64_be(n)
+	}
+
+	fn receipt(block: u64, transaction: u64) -> (TransactionSigned, ReceiptInfo) {
+		let receipt = ReceiptInfo {
+			block_hash: hash(block),
+			transaction_hash: hash(transaction),
+			..Default::default()
+		};
+		(TransactionSigned::default(), receipt)
+	}
+
+	#[test]
+	fn cache_evicts_oldest_block() {
+		let mut cache = ReceiptsCache::default();
+		for block in 0..CACHE_SIZE as u64 {
+			cache.insert(hash(block), vec![receipt(block, block)]);
+		}
+		assert!(cache.get(&hash(0)).is_some());
+
+		let newest = CACHE_SIZE as u64;
+		cache.insert(hash(newest), vec![receipt(newest, newest)]);
+		assert!(!cache.contains_block(&hash(0)));
+		assert!(cache.get(&hash(0)).is_none());
+		assert!(cache.get(&hash(1)).is_some());
+		assert!(cache.get(&hash(newest)).is_some());
+	}
+
+	#[test]
+	fn cache_keeps_reincluded_transactions() {
+		let mut cache = ReceiptsCache::default();
+		let transaction = u64::MAX;
+		cache.insert(hash(0), vec![receipt(0, transaction)]);
+		for block in 1..CACHE_SIZE as u64 {
+			cache.insert(hash(block), vec![]);
+		}
+		// The transaction is re-included after a re-org, while its first block is evicted.
+		let newest = CACHE_SIZE as u64;
+		cache.insert(hash(newest), vec![receipt(newest, transaction)]);
+
+		let (_, receipt) = cache.get(&hash(transaction)).unwrap();
+		assert_eq!(receipt.block_hash, hash(newest));
+	}
+
+	#[test]
+	fn filter_matches_address_and_topics() {
+		let log = Log {
+			address: H160::repeat_byte(1),
+			topics: vec![H256::repeat_byte(1), H256::repeat_byte(2)],
+			..Default::default()
+		};
+
+		assert!(filter_matches(&Filter::default(), &log));
+		assert!(filter_matches(
+			&Filter {
+				address: Some(AddressOrAddresses::Addresses(vec![
+					H160::repeat_byte(1),
+					H160::repeat_byte(2)
+				])),
+				topics: Some(vec![
+					FilterTopic::Single(H256::repeat_byte(1)),
+					FilterTopic::Multiple(vec![H256::repeat_byte(2), H256::repeat_byte(3)]),
+				]),
+				..Default::default()
+			},
+			&log
+		));
+		assert!(!filter_matches(
+			&Filter { address: Some(H160::repeat_byte(2).into()), ..Default::default() },
+			&log
+		));
+		assert!(!filter_matches(
+			&Filter {
+				topics: Some(vec![FilterTopic::Single(H256::repeat_byte(2))]),
+				..Default::default()
+			},
+			&log
+		));
+		assert!(!filter_matches(
+			&Filter {
+				topics: Some(vec![
+					FilterTopic::Single(H256::repeat_byte(1)),
+					FilterTopic::Single(H256::repeat_byte(2)),
+					FilterTopic::Single(H256::repeat_byte(3)),
+				]),
+				..Default::default()
+			},
+			&log
+		));
+	}
+}
@@ -0,0 +1,797 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The embedded Ethereum JSON-RPC server.
+
+use super::{
+	metadata::ReviveMetadata,
+	receipts::{extract_receipt, filter_matches, ReceiptsCache},
+	EthRpcDeps, ReviveRuntimeApi,
+};
+use crate::common::{
+	runtime::{MetadataInspector, DEFAULT_REVIVE_PALLET_NAME, DEFAULT_REVIVE_RUNTIME_API_NAME},
+	types::{Balance, ParachainClient},
+	ConstructNodeRuntimeApi, NodeBlock,
+};
+use codec::Decode;
+use futures::StreamExt;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	types::{
+		error::{CALL_EXECUTION_FAILED_CODE, INVALID_PARAMS_CODE},
+		ErrorObjectOwned,
+	},
+};
+use pallet_revive::{
+	evm::{
+		Block as EthBlock, BlockNumberOrTag, BlockNumberOrTagOrHash, BlockTag, Bytes,
+		FeeHistoryResult, Filter, FilterResults, GenericTransaction, Log, ReceiptInfo,
+		SyncingProgress, SyncingStatus, Trace, TracerConfig, TransactionInfo, TransactionSigned,
+		TransactionTrace, H160, H256, U256,
+	},
+	ReviveApi,
+};
+use pallet_revive_eth_rpc::{
+	client::ClientError, subxt_client::SrcChainConfig, DebugRpcServer, EthRpcError, EthRpcServer,
+	FeeHistoryProvider,
+};
+use sc_client_api::{BlockBackend, BlockchainEvents, HeaderBackend, StorageProvider};
+use sc_transaction_pool::TransactionPoolHandle;
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_crypto_hashing::keccak_256;
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, NumberFor, One},
+	Permill,
+};
+use std::sync::{Arc, Mutex};
+use subxt::events::Events;
+
+/// Metadata version requested from the runtime.
+const METADATA_VERSION: u32 = 15;
+
+/// Maximum number of blocks that `eth_getLogs` scans in a single request.
+const MAX_LOGS_BLOCK_RANGE: u32 = 1024;
+
+/// Maximum number of logs returned by `eth_getLogs`, same as `pallet-revive-eth-rpc`.
+const MAX_LOGS: usize = 10_000;
+
+/// Ethereum JSON-RPC implementation backed by the node client.
+pub struct EthRpc<Block: BlockT, RuntimeApi> {
+	client: Arc<ParachainClient<Block, RuntimeApi>>,
+	pool: Arc<TransactionPoolHandle<Block, ParachainClient<Block, RuntimeApi>>>,
+	deps: EthRpcDeps,
+	/// Revive metadata of the latest runtime we have seen, keyed by its `spec_version`.
+	metadata: Arc<Mutex<Option<(u32, Arc<ReviveMetadata>)>>>,
+	/// Receipts of the latest best blocks, used to look up transactions by hash.
+	receipts_cache: Arc<Mutex<ReceiptsCache>>,
+	/// Fee history of the latest best blocks.
+	fee_history: FeeHistoryProvider,
+}
+
+impl<Block: BlockT, RuntimeApi> Clone for EthRpc<Block, RuntimeApi> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			pool: self.pool.clone(),
+			deps: self.deps.clone(),
+			metadata: self.metadata.clone(),
+			receipts_cache: self.receipts_cache.clone(),
+			fee_history: self.fee_history.clone(),
+		}
+	}
+}
+
+impl<Block, RuntimeApi> EthRpc<Block, RuntimeApi>
+where
+	Block: NodeBlock,
+	RuntimeApi: ConstructNodeRuntimeApi<Block, ParachainClient<Block, RuntimeApi>>,
+	RuntimeApi::RuntimeApi: ReviveRuntimeApi<Block>,
+{
+	/// Creates a new [`EthRpc`] and spawns the task indexing the receipts of new best blocks.
+	///
+	/// Returns `None` if the runtime at the best block does not include `pallet-revive`.
+	pub fn new(
+		client: Arc<ParachainClient<Block, RuntimeApi>>,
+		pool: Arc<TransactionPoolHandle<Block, ParachainClient<Block, RuntimeApi>>>,
+		deps: EthRpcDeps,
+	) -> Option<Self> {
+		let rpc = Self {
+			client,
+			pool,
+			deps,
+			metadata: Default::default(),
+			receipts_cache: Default::default(),
+			fee_history: Default::default(),
+		};
+		let best_hash = rpc.client.chain_info().best_hash;
+		if !rpc.client.runtime_api().has_revive_api(best_hash) {
+			log::warn!(
+				"⚠️  The runtime does not expose the `{DEFAULT_REVIVE_RUNTIME_API_NAME}` runtime API. The Ethereum JSON-RPC will not be served."
+			);
+			return None
+		}
+
+		if let Err(err) = rpc.revive_metadata(best_hash) {
+			log::warn!(
+				"⚠️  Failed to read the revive metadata: {}. Not serving the Ethereum JSON-RPC.",
+				err.message()
+			);
+			return None
+		}
+
+		rpc.spawn_indexer();
+		log::info!("🧾 Serving the Ethereum JSON-RPC from the node");
+		Some(rpc)
+	}
+
+	/// Spawn the task that indexes the receipts of the new best blocks, including the blocks
+	/// enacted by a re-org.
+	fn spawn_indexer(&self) {
+		let rpc = self.clone();
+		let mut notifications = self.client.import_notification_stream();
+		self.deps.spawn_handle.spawn("eth-rpc-indexer", None, async move {
+			while let Some(notification) = notifications.next().await {
+				if !notification.is_new_best {
+					continue
+				}
+
+				let enacted = notification
+					.tree_route
+					.iter()
+					.flat_map(|route| route.enacted().iter().map(|block| block.hash))
+					.collect::<Vec<_>>();
+				for hash in enacted.into_iter().chain(std::iter::once(notification.hash)) {
+					rpc.index_block(hash).await;
+				}
+			}
+		});
+	}
+
+	async fn index_block(&self, hash: Block::Hash) {
+		if self
+			.receipts_cache
+			.lock()
+			.expect("Lock is never poisoned; qed")
+			.contains_block(&hash)
+		{
+			return
+		}
+
+		let indexed = self.receipts(hash).and_then(|receipts| {
+			let Some((block, receipts)) = receipts else { return Ok(None) };
+			let evm_block = self.evm_block_from_receipts(hash, &block, &receipts, false)?;
+			Ok(Some((evm_block, receipts)))
+		});
+		let (evm_block, receipts) = match indexed {
+			Ok(Some(indexed)) => indexed,
+			Ok(None) => return,
+			Err(err) => {
+				log::debug!(
+					"Failed to extract the Ethereum receipts of block {hash:?}: {}",
+					err.message()
+				);
+				return
+			},
+		};
+
+		let receipt_infos = receipts.iter().map(|(_, receipt)| receipt.clone()).collect::<Vec<_>>();
+		self.fee_history.update_fee_history(&evm_block, &receipt_infos).await;
+		self.receipts_cache
+			.lock()
+			.expect("Lock is never poisoned; qed")
+			.insert(hash, receipts);
+	}
+
+	fn revive_metadata(&self, at: Block::Hash) -> RpcResult<Arc<ReviveMetadata>> {
+		let spec_version = self.client.runtime_version_at(at).map_err(internal_error)?.spec_version;
+		let mut cache = self.metadata.lock().expect("Lock is never poisoned; qed");
+		match &*cache {
+			Some((cached_version, metadata)) if *cached_version == spec_version =>
+				Ok(metadata.clone()),
+			_ => {
+				let runtime_api = self.client.runtime_api();
+				let opaque_metadata = match runtime_api
+					.metadata_at_version(at, METADATA_VERSION)
+					.map_err(internal_error)?
+				{
+					Some(metadata) => metadata,
+					None => runtime_api.metadata(at).map_err(internal_error)?,
+				};
+				let inspector = MetadataInspector::from_opaque_metadata(&opaque_metadata)
+					.map_err(internal_error)?;
+				let metadata =
+					Arc::new(ReviveMetadata::from_inspector(inspector).ok_or_else(|| {
+						internal_error(format!(
+							"`{DEFAULT_REVIVE_PALLET_NAME}` pallet not found in the runtime metadata"
+						))
+					})?);
+				*cache = Some((spec_version, metadata.clone()));
+				Ok(metadata)
+			},
+		}
+	}
+
+	fn block_hash_for_tag(&self, at: BlockNumberOrTagOrHash) -> RpcResult<Block::Hash> {
+		let info = self.client.chain_info();
+		match at {
+			BlockNumberOrTagOrHash::BlockHash(hash) => Ok(hash),
+			BlockNumberOrTagOrHash::BlockNumber(number) => {
+				let number = NumberFor::<Block>::try_from(number)
+					.map_err(|_| EthRpcError::ConversionError)?;
+				self.client
+					.hash(number)
+					.map_err(internal_error)?
+					.ok_or_else(|| ClientError::BlockNotFound.into())
+			},
+			BlockNumberOrTagOrHash::BlockTag(BlockTag::Earliest) => Ok(info.genesis_hash),
+			BlockNumberOrTagOrHash::BlockTag(BlockTag::Finalized | BlockTag::Safe) =>
+				Ok(info.finalized_hash),
+			BlockNumberOrTagOrHash::BlockTag(BlockTag::Latest | BlockTag::Pending) =>
+				Ok(info.best_hash),
+		}
+	}
+
+	fn block_number_for_tag(&self, at: BlockNumberOrTag) -> RpcResult<NumberFor<Block>> {
+		let hash = self.block_hash_for_tag(at.into())?;
+		self.client
+			.number(hash)
+			.map_err(internal_error)?
+			.ok_or_else(|| ClientError::BlockNotFound.into())
+	}
+
+	fn dry_run(
+		&self,
+		transaction: GenericTransaction,
+		at: Block::Hash,
+	) -> RpcResult<pallet_revive::EthTransactInfo<Balance>> {
+		self.client
+			.runtime_api()
+			.eth_transact(at, transaction)
+			.map_err(internal_error)?
+			.map_err(|err| ClientError::TransactError(err).into())
+	}
+
+	/// Returns the extrinsic index and the payload of the Ethereum transactions included in the
+	/// given block.
+	fn eth_transactions(
+		&self,
+		hash: Block::Hash,
+	) -> RpcResult<Option<(Block, Vec<(u32, Vec<u8>)>)>> {
+		let Some(signed_block) = self.client.block(hash).map_err(internal_error)? else {
+			return Ok(None)
+		};
+		let metadata = self.revive_metadata(hash)?;
+		let transactions = signed_block
+			.block
+			.extrinsics()
+			.iter()
+			.enumerate()
+			.filter_map(|(index, extrinsic)| {
+				Some((index as u32, metadata.eth_transact.payload(extrinsic)?))
+			})
+			.collect();
+
+		Ok(Some((signed_block.block, transactions)))
+	}
+
+	/// Returns the Ethereum transactions included in the given block, with their receipts.
+	///
+	/// The receipts are built from the `frame_system::Events` stored in the state of the block.
+	fn receipts(
+		&self,
+		hash: Block::Hash,
+	) -> RpcResult<Option<(Block, Vec<(TransactionSigned, ReceiptInfo)>)>> {
+		let Some((block, transactions)) = self.eth_transactions(hash)? else { return Ok(None) };
+		if transactions.is_empty() {
+			return Ok(Some((block, Vec::new())))
+		}
+
+		let metadata = self.revive_metadata(hash)?;
+		let events = self
+			.client
+			.storage(hash, &metadata.events_key)
+			.map_err(internal_error)?
+			.map(|data| data.0)
+			.unwrap_or_default();
+		let events = Events::<SrcChainConfig>::decode_from(events, metadata.metadata.clone());
+		let base_gas_price = self.client.runtime_api().gas_price(hash).map_err(internal_error)?;
+		let block_number = (*block.header().number()).into();
+
+		let receipts = transactions
+			.into_iter()
+			.map(|(index, payload)| {
+				extract_receipt(
+					&events,
+					hash,
+					block_number,
+					index,
+					&payload,
+					base_gas_price,
+					metadata.native_to_eth_ratio,
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(Some((block, receipts)))
+	}
+
+	/// Returns the indexed transaction and receipt of `transaction_hash`, if the block that
+	/// includes it is still part of the canonical chain.
+	fn indexed_transaction(
+		&self,
+		transaction_hash: &H256,
+	) -> RpcResult<Option<(TransactionSigned, ReceiptInfo)>> {
+		let Some((signed_tx, receipt)) = self
+			.receipts_cache
+			.lock()
+			.expect("Lock is never poisoned; qed")
+			.get(transaction_hash)
+			.cloned()
+		else {
+			return Ok(None)
+		};
+
+		let number = NumberFor::<Block>::try_from(receipt.block_number)
+			.map_err(|_| EthRpcError::ConversionError)?;
+		let canonical_hash = self.client.hash(number).map_err(internal_error)?;
+		Ok((canonical_hash == Some(receipt.block_hash)).then_some((signed_tx, receipt)))
+	}
+
+	/// Returns the transaction at `transaction_index` in the given block.
+	fn transaction_by_block_and_index(
+		&self,
+		hash: Block::Hash,
+		transaction_index: U256,
+	) -> RpcResult<Option<TransactionInfo>> {
+		let Some((_, receipts)) = self.receipts(hash)? else { return Ok(None) };
+		Ok(receipts
+			.into_iter()
+			.find(|(_, receipt)| receipt.transaction_index == transaction_index)
+			.map(|(signed_tx, receipt)| TransactionInfo::new(&receipt, signed_tx)))
+	}
+
+	/// Read the `pallet-timestamp` `Now` storage item, in seconds.
+	fn timestamp(&self, hash: Block::Hash, metadata: &ReviveMetadata) -> u64 {
+		let Some(key) = &metadata.timestamp_key else { return 0 };
+		self.client
+			.storage(hash, key)
+			.ok()
+			.flatten()
+			.and_then(|data| u64::decode(&mut &data.0[..]).ok())
+			.map(|now| now / 1000)
+			.unwrap_or_default()
+	}
+
+	fn evm_block(
+		&self,
+		hash: Block::Hash,
+		hydrated_transactions: bool,
+	) -> RpcResult<Option<EthBlock>> {
+		let Some((block, receipts)) = self.receipts(hash)? else { return Ok(None) };
+		self.evm_block_from_receipts(hash, &block, &receipts, hydrated_transactions)
+			.map(Some)
+	}
+
+	fn evm_block_from_receipts(
+		&self,
+		hash: Block::Hash,
+		block: &Block,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+		hydrated_transactions: bool,
+	) -> RpcResult<EthBlock> {
+		let metadata = self.revive_metadata(hash)?;
+		let runtime_api = self.client.runtime_api();
+		let header = block.header();
+
+		let gas_used =
+			receipts.iter().fold(U256::zero(), |acc, (_, receipt)| acc + receipt.gas_used);
+		let transactions = if hydrated_transactions {
+			receipts
+				.iter()
+				.map(|(signed_tx, receipt)| TransactionInfo::new(receipt, signed_tx.clone()))
+				.collect::<Vec<_>>()
+				.into()
+		} else {
+			receipts
+				.iter()
+				.map(|(_, receipt)| receipt.transaction_hash)
+				.collect::<Vec<_>>()
+				.into()
+		};
+
+		Ok(EthBlock {
+			hash,
+			parent_hash: *header.parent_hash(),
+			state_root: *header.state_root(),
+			miner: runtime_api.block_author(hash).ok().flatten().unwrap_or_default(),
+			transactions_root: *header.extrinsics_root(),
+			number: (*header.number()).into(),
+			timestamp: self.timestamp(hash, &metadata).into(),
+			difficulty: Some(0u32.into()),
+			base_fee_per_gas: runtime_api.gas_price(hash).ok(),
+			gas_limit: runtime_api.block_gas_limit(hash).unwrap_or_default(),
+			gas_used,
+			// The receipts are not committed to in an Ethereum receipts trie, so report the root
+			// of an empty trie, i.e. `keccak256(rlp(""))`, rather than an unrelated root.
+			receipts_root: H256(keccak_256(&[0x80])),
+			transactions,
+			..Default::default()
+		})
+	}
+
+	/// Returns the logs matching `filter`, extracted from the receipts of the requested blocks.
+	fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>> {
+		let hashes = match (filter.from_block.clone(), filter.to_block.clone(), filter.block_hash) {
+			(None, None, Some(hash)) => vec![hash],
+			(_, _, Some(_)) =>
+				return Err(invalid_params("block number and block hash cannot be used together")),
+			(from_block, to_block, None) => {
+				let from = from_block.map_or(Ok(self.client.chain_info().best_number), |at| {
+					self.block_number_for_tag(at)
+				})?;
+				let to = to_block.map_or(Ok(self.client.chain_info().best_number), |at| {
+					self.block_number_for_tag(at)
+				})?;
+				if from > to {
+					return Err(invalid_params("invalid block range params"))
+				}
+				if to - from >= MAX_LOGS_BLOCK_RANGE.into() {
+					return Err(invalid_params(format!(
+						"block range exceeds the maximum of {MAX_LOGS_BLOCK_RANGE} blocks"
+					)))
+				}
+
+				let mut hashes = Vec::new();
+				let mut number = from;
+				while number <= to {
+					hashes.push(
+						self.client
+							.hash(number)
+							.map_err(internal_error)?
+							.ok_or(ClientError::BlockNotFound)?,
+					);
+					number += One::one();
+				}
+				hashes
+			},
+		};
+
+		let mut logs = Vec::new();
+		for hash in hashes {
+			let Some((_, receipts)) = self.receipts(hash)? else {
+				return Err(ClientError::BlockNotFound.into())
+			};
+			logs.extend(
+				receipts
+					.into_iter()
+					.flat_map(|(_, receipt)| receipt.logs)
+					.filter(|log| filter_matches(&filter, log)),
+			);
+			if logs.len() >= MAX_LOGS {
+				logs.truncate(MAX_LOGS);
+				break
+			}
+		}
+
+		Ok(logs)
+	}
+}
+
+/// Wrap an error raised while talking to the client into a JSON-RPC error.
+fn internal_error(err: impl std::fmt::Display) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(CALL_EXECUTION_FAILED_CODE, err.to_string(), None::<()>)
+}
+
+/// Error returned for invalid request parameters.
+fn invalid_params(message: impl Into<String>) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(INVALID_PARAMS_CODE, message.into(), None::<()>)
+}
+
+#[async_trait]
+impl<Block, RuntimeApi> EthRpcServer for EthRpc<Block, RuntimeApi>
+where
+	Block: NodeBlock,
+	RuntimeApi: ConstructNodeRuntimeApi<Block, ParachainClient<Block, RuntimeApi>>,
+	RuntimeApi::RuntimeApi: ReviveRuntimeApi<Block>,
+{
+	async fn accounts(&self) -> RpcResult<Vec<H160>> {
+		Ok(vec![])
+	}
+
+	async fn block_number(&self) -> RpcResult<U256> {
+		Ok(self.client.chain_info().best_number.into())
+	}
+
+	async fn call(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<Bytes> {
+		let hash = self.block_hash_for_tag(block.unwrap_or_default())?;
+		Ok(self.dry_run(transaction, hash)?.data.into())
+	}
+
+	async fn chain_id(&self) -> RpcResult<U256> {
+		let best_hash = self.client.chain_info().best_hash;
+		Ok(self.revive_metadata(best_hash)?.chain_id.into())
+	}
+
+	async fn estimate_gas(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTag>,
+	) -> RpcResult<U256> {
+		let hash = self.block_hash_for_tag(block.unwrap_or_default().into())?;
+		Ok(self.dry_run(transaction, hash)?.eth_gas)
+	}
+
+	async fn gas_price(&self) -> RpcResult<U256> {
+		let best_hash = self.client.chain_info().best_hash;
+		self.client.runtime_api().gas_price(best_hash).map_err(internal_error)
+	}
+
+	async fn get_balance(&self, address: H160, block: BlockNumberOrTagOrHash) -> RpcResult<U256> {
+		let hash = self.block_hash_for_tag(block)?;
+		self.client.runtime_api().balance(hash, address).map_err(internal_error)
+	}
+
+	async fn get_block_by_hash(
+		&self,
+		block_hash: H256,
+		hydrated_transactions: bool,
+	) -> RpcResult<Option<EthBlock>> {
+		self.evm_block(block_hash, hydrated_transactions)
+	}
+
+	async fn get_block_by_number(
+		&self,
+		block: BlockNumberOrTag,
+		hydrated_transactions: bool,
+	) -> RpcResult<Option<EthBlock>> {
+		let hash = match self.block_hash_for_tag(block.into()) {
+			Ok(hash) => hash,
+			Err(_) => return Ok(None),
+		};
+		self.evm_block(hash, hydrated_transactions)
+	}
+
+	async fn get_block_transaction_count_by_hash(
+		&self,
+		block_hash: Option<H256>,
+	) -> RpcResult<Option<U256>> {
+		let hash = block_hash.unwrap_or_else(|| self.client.chain_info().best_hash);
+		Ok(self.eth_transactions(hash)?.map(|(_, transactions)| transactions.len().into()))
+	}
+
+	async fn get_block_transaction_count_by_number(
+		&self,
+		block: Option<BlockNumberOrTag>,
+	) -> RpcResult<Option<U256>> {
+		let hash = match self.block_hash_for_tag(block.unwrap_or_default().into()) {
+			Ok(hash) => hash,
+			Err(_) => return Ok(None),
+		};
+		Ok(self.eth_transactions(hash)?.map(|(_, transactions)| transactions.len().into()))
+	}
+
+	async fn get_code(&self, address: H160, block: BlockNumberOrTagOrHash) -> RpcResult<Bytes> {
+		let hash = self.block_hash_for_tag(block)?;
+		Ok(self.client.runtime_api().code(hash, address).map_err(internal_error)?.into())
+	}
+
+	async fn get_logs(&self, filter: Option<Filter>) -> RpcResult<FilterResults> {
+		Ok(FilterResults::Logs(self.logs(filter.unwrap_or_default())?))
+	}
+
+	async fn get_storage_at(
+		&self,
+		address: H160,
+		storage_slot: U256,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<Bytes> {
+		let hash = self.block_hash_for_tag(block)?;
+		let bytes = self
+			.client
+			.runtime_api()
+			.get_storage(hash, address, storage_slot.to_big_endian())
+			.map_err(internal_error)?
+			.map_err(|_| ClientError::ContractNotFound)?;
+		Ok(bytes.unwrap_or_default().into())
+	}
+
+	async fn get_transaction_by_block_hash_and_index(
+		&self,
+		block_hash: H256,
+		transaction_index: U256,
+	) -> RpcResult<Option<TransactionInfo>> {
+		self.transaction_by_block_and_index(block_hash, transaction_index)
+	}
+
+	async fn get_transaction_by_block_number_and_index(
+		&self,
+		block: BlockNumberOrTag,
+		transaction_index: U256,
+	) -> RpcResult<Option<TransactionInfo>> {
+		let hash = match self.block_hash_for_tag(block.into()) {
+			Ok(hash) => hash,
+			Err(_) => return Ok(None),
+		};
+		self.transaction_by_block_and_index(hash, transaction_index)
+	}
+
+	async fn get_transaction_by_hash(
+		&self,
+		transaction_hash: H256,
+	) -> RpcResult<Option<TransactionInfo>> {
+		Ok(self
+			.indexed_transaction(&transaction_hash)?
+			.map(|(signed_tx, receipt)| TransactionInfo::new(&receipt, signed_tx)))
+	}
+
+	async fn get_transaction_count(
+		&self,
+		address: H160,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<U256> {
+		let hash = self.block_hash_for_tag(block)?;
+		Ok(self.client.runtime_api().nonce(hash, address).map_err(internal_error)?.into())
+	}
+
+	async fn get_transaction_receipt(
+		&self,
+		transaction_hash: H256,
+	) -> RpcResult<Option<ReceiptInfo>> {
+		Ok(self.indexed_transaction(&transaction_hash)?.map(|(_, receipt)| receipt))
+	}
+
+	async fn max_priority_fee_per_gas(&self) -> RpcResult<U256> {
+		// Same estimation as `pallet-revive-eth-rpc`.
+		let gas_price = self.gas_price().await?;
+		Ok(Permill::from_percent(20).mul_ceil(gas_price))
+	}
+
+	async fn send_raw_transaction(&self, transaction: Bytes) -> RpcResult<H256> {
+		let hash = H256(keccak_256(&transaction.0));
+		let best_hash = self.client.chain_info().best_hash;
+		let extrinsic = self.revive_metadata(best_hash)?.eth_transact.extrinsic(transaction.0);
+
+		self.pool
+			.submit_one(best_hash, TransactionSource::External, extrinsic)
+			.await
+			.map_err(|err| {
+				log::debug!("eth_sendRawTransaction {hash:?} rejected by the pool: {err}");
+				internal_error(err)
+			})?;
+
+		Ok(hash)
+	}
+
+	async fn send_transaction(&self, transaction: GenericTransaction) -> RpcResult<H256> {
+		// The embedded server does not manage any account.
+		Err(EthRpcError::AccountNotFound(transaction.from.unwrap_or_default()).into())
+	}
+
+	async fn syncing(&self) -> RpcResult<SyncingStatus> {
+		if !self.deps.sync_oracle.is_major_syncing() {
+			return Ok(SyncingStatus::Bool(false))
+		}
+
+		Ok(SyncingProgress {
+			current_block: Some(self.client.chain_info().best_number.into()),
+			..Default::default()
+		}
+		.into())
+	}
+
+	async fn net_listening(&self) -> RpcResult<bool> {
+		Ok(!self.deps.sync_oracle.is_offline() && !self.deps.sync_oracle.is_major_syncing())
+	}
+
+	async fn net_version(&self) -> RpcResult<String> {
+		Ok(self.chain_id().await?.to_string())
+	}
+
+	async fn web3_client_version(&self) -> RpcResult<String> {
+		Ok(format!("polkadot-omni-node/v{}", crate::NODE_VERSION))
+	}
+
+	async fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> RpcResult<FeeHistoryResult> {
+		let block_count: u32 = block_count.try_into().map_err(|_| EthRpcError::ConversionError)?;
+		let newest_block: U256 = self.block_number_for_tag(newest_block)?.into();
+		let newest_block = newest_block.try_into().map_err(|_| EthRpcError::ConversionError)?;
+		Ok(self
+			.fee_history
+			.fee_history(block_count, newest_block, reward_percentiles)
+			.await?)
+	}
+}
+
+#[async_trait]
+impl<Block, RuntimeApi> DebugRpcServer for EthRpc<Block, RuntimeApi>
+where
+	Block: NodeBlock,
+	RuntimeApi: ConstructNodeRuntimeApi<Block, ParachainClient<Block, RuntimeApi>>,
+	RuntimeApi::RuntimeApi: ReviveRuntimeApi<Block>,
+{
+	async fn trace_block_by_number(
+		&self,
+		block: BlockNumberOrTag,
+		tracer_config: TracerConfig,
+	) -> RpcResult<Vec<TransactionTrace>> {
+		let hash = self.block_hash_for_tag(block.into())?;
+		let (block, transactions) =
+			self.eth_transactions(hash)?.ok_or(ClientError::BlockNotFound)?;
+		let parent_hash = *block.header().parent_hash();
+		let traces = self
+			.client
+			.runtime_api()
+			.trace_block(parent_hash, block, tracer_config.config)
+			.map_err(internal_error)?;
+
+		Ok(traces
+			.into_iter()
+			.filter_map(|(index, trace)| {
+				let (_, payload) = transactions.iter().find(|(i, _)| *i == index)?;
+				Some(TransactionTrace { tx_hash: H256(keccak_256(payload)), trace })
+			})
+			.collect())
+	}
+
+	async fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+		tracer_config: TracerConfig,
+	) -> RpcResult<Trace> {
+		let (_, receipt) = self
+			.indexed_transaction(&transaction_hash)?
+			.ok_or(ClientError::EthExtrinsicNotFound)?;
+		let transaction_index: u32 =
+			receipt.transaction_index.try_into().map_err(|_| EthRpcError::ConversionError)?;
+		let block = self
+			.client
+			.block(receipt.block_hash)
+			.map_err(internal_error)?
+			.ok_or(ClientError::BlockNotFound)?
+			.block;
+		let parent_hash = *block.header().parent_hash();
+
+		self.client
+			.runtime_api()
+			.trace_tx(parent_hash, block, transaction_index, tracer_config.config)
+			.map_err(internal_error)?
+			.ok_or_else(|| ClientError::EthExtrinsicNotFound.into())
+	}
+
+	async fn trace_call(
+		&self,
+		transaction: GenericTransaction,
+		block: BlockNumberOrTagOrHash,
+		tracer_config: TracerConfig,
+	) -> RpcResult<Trace> {
+		let hash = self.block_hash_for_tag(block)?;
+		self.client
+			.runtime_api()
+			.trace_call(hash, transaction, tracer_config.config)
+			.map_err(internal_error)?
+			.map_err(|err| ClientError::TransactError(err).into())
+	}
+}
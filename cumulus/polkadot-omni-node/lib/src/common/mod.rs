@@ -21,6 +21,7 @@
 pub(crate) mod aura;
 pub mod chain_spec;
 pub mod command;
+pub(crate) mod eth_rpc;
 pub mod rpc;
 pub mod runtime;
 pub mod spec;
@@ -130,4 +131,8 @@ pub struct NodeExtraArgs {
 
 	/// If true then the statement store will be enabled.
 	pub enable_statement_store: bool,
+
+	/// If true then the Ethereum JSON-RPC will be served for runtimes that include
+	/// `pallet-revive`.
+	pub enable_eth_rpc: bool,
 }
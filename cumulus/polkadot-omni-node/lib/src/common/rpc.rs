@@ -18,16 +18,20 @@
 
 #![warn(missing_docs)]
 
+#[cfg(feature = "eth-rpc")]
+use crate::common::eth_rpc::EthRpc;
 use crate::common::{
+	eth_rpc::{EthRpcDeps, ReviveRuntimeApi},
 	types::{AccountId, Balance, Nonce, ParachainBackend, ParachainClient},
-	ConstructNodeRuntimeApi,
+	ConstructNodeRuntimeApi, NodeBlock,
 };
+#[cfg(feature = "eth-rpc")]
+use pallet_revive_eth_rpc::{DebugRpcServer, EthRpcServer};
 use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 use sc_rpc::{
 	dev::{Dev, DevApiServer},
 	statement::{StatementApiServer, StatementStore},
 };
-use sp_runtime::traits::Block as BlockT;
 use std::{marker::PhantomData, sync::Arc};
 use substrate_frame_rpc_system::{System, SystemApiServer};
 use substrate_state_trie_migration_rpc::{StateMigration, StateMigrationApiServer};
@@ -41,12 +45,13 @@ pub(crate) trait BuildRpcExtensions<Client, Backend, Pool, StatementStore> {
 		backend: Arc<Backend>,
 		pool: Arc<Pool>,
 		statement_store: Option<Arc<StatementStore>>,
+		eth_rpc_deps: Option<EthRpcDeps>,
 	) -> sc_service::error::Result<RpcExtension>;
 }
 
 pub(crate) struct BuildParachainRpcExtensions<Block, RuntimeApi>(PhantomData<(Block, RuntimeApi)>);
 
-impl<Block: BlockT, RuntimeApi>
+impl<Block: NodeBlock, RuntimeApi>
 	BuildRpcExtensions<
 		ParachainClient<Block, RuntimeApi>,
 		ParachainBackend<Block>,
@@ -57,7 +62,8 @@ where
 	RuntimeApi:
 		ConstructNodeRuntimeApi<Block, ParachainClient<Block, RuntimeApi>> + Send + Sync + 'static,
 	RuntimeApi::RuntimeApi: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
-		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ ReviveRuntimeApi<Block>,
 {
 	fn build_rpc_extensions(
 		client: Arc<ParachainClient<Block, RuntimeApi>>,
@@ -66,16 +72,26 @@ where
 			sc_transaction_pool::TransactionPoolHandle<Block, ParachainClient<Block, RuntimeApi>>,
 		>,
 		statement_store: Option<Arc<sc_statement_store::Store>>,
+		eth_rpc_deps: Option<EthRpcDeps>,
 	) -> sc_service::error::Result<RpcExtension> {
 		let build = || -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>> {
 			let mut module = RpcExtension::new(());
 
-			module.merge(System::new(client.clone(), pool).into_rpc())?;
+			module.merge(System::new(client.clone(), pool.clone()).into_rpc())?;
 			module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 			module.merge(StateMigration::new(client.clone(), backend).into_rpc())?;
 			if let Some(statement_store) = statement_store {
 				module.merge(StatementStore::new(statement_store).into_rpc())?;
 			}
+			#[cfg(feature = "eth-rpc")]
+			if let Some(eth_rpc) =
+				eth_rpc_deps.and_then(|deps| EthRpc::new(client.clone(), pool, deps))
+			{
+				module.merge(DebugRpcServer::into_rpc(eth_rpc.clone()))?;
+				module.merge(EthRpcServer::into_rpc(eth_rpc))?;
+			}
+			#[cfg(not(feature = "eth-rpc"))]
+			let _ = eth_rpc_deps;
 			module.merge(Dev::new(client).into_rpc())?;
 
 			Ok(module)
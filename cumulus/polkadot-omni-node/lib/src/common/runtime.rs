@@ -22,6 +22,7 @@ use sc_chain_spec::ChainSpec;
 use sc_executor::WasmExecutor;
 use sc_runtime_utilities::fetch_latest_metadata_from_code_blob;
 use scale_info::{form::PortableForm, TypeDef, TypeDefPrimitive};
+use sp_crypto_hashing::twox_128;
 use std::fmt::Display;
 use subxt_metadata::{Metadata, StorageEntryType};
 
@@ -29,6 +30,12 @@ use subxt_metadata::{Metadata, StorageEntryType};
 pub const DEFAULT_PARACHAIN_SYSTEM_PALLET_NAME: &str = "ParachainSystem";
 /// Expected frame system pallet runtime type name.
 pub const DEFAULT_FRAME_SYSTEM_PALLET_NAME: &str = "System";
+/// Expected revive pallet runtime type name.
+pub const DEFAULT_REVIVE_PALLET_NAME: &str = "Revive";
+/// Expected revive runtime API name.
+pub const DEFAULT_REVIVE_RUNTIME_API_NAME: &str = "ReviveApi";
+/// Expected timestamp pallet runtime type name.
+pub const DEFAULT_TIMESTAMP_PALLET_NAME: &str = "Timestamp";
 
 /// The Aura ID used by the Aura consensus
 #[derive(PartialEq)]
@@ -129,11 +136,18 @@ impl RuntimeResolver for DefaultRuntimeResolver {
 			);
 		}
 
+		#[cfg(feature = "eth-rpc")]
+		if metadata_inspector.runtime_api_exists(DEFAULT_REVIVE_RUNTIME_API_NAME) {
+			log::info!(
+				"🧾 The runtime exposes the `{DEFAULT_REVIVE_RUNTIME_API_NAME}` runtime API. Start the node with `--enable-eth-rpc` to serve the Ethereum JSON-RPC."
+			);
+		}
+
 		Ok(Runtime::Omni(block_number, Consensus::Aura(AuraConsensusId::Sr25519)))
 	}
 }
 
-struct MetadataInspector(Metadata);
+pub(crate) struct MetadataInspector(Metadata);
 
 impl MetadataInspector {
 	fn new(chain_spec: &dyn ChainSpec) -> Result<MetadataInspector, sc_cli::Error> {
@@ -175,16 +189,63 @@ impl MetadataInspector {
 	}
 }
 
+/// Lookups used by the embedded Ethereum JSON-RPC.
+#[cfg_attr(not(feature = "eth-rpc"), allow(dead_code))]
+impl MetadataInspector {
+	/// Decode the metadata returned by the `Metadata` runtime API.
+	pub(crate) fn from_opaque_metadata(mut opaque_metadata: &[u8]) -> Result<Self, codec::Error> {
+		Metadata::decode(&mut opaque_metadata).map(MetadataInspector)
+	}
+
+	pub(crate) fn runtime_api_exists(&self, name: &str) -> bool {
+		self.0.runtime_api_trait_by_name(name).is_some()
+	}
+
+	/// Returns the inner metadata.
+	pub(crate) fn into_inner(self) -> Metadata {
+		self.0
+	}
+
+	/// Returns the `(pallet index, call index)` pair of the given call.
+	pub(crate) fn call_index(&self, pallet_name: &str, call_name: &str) -> Option<(u8, u8)> {
+		let pallet_metadata = self.0.pallet_by_name(pallet_name)?;
+		let call_variant = pallet_metadata.call_variant_by_name(call_name)?;
+		Some((pallet_metadata.index(), call_variant.index))
+	}
+
+	/// Returns the decoded value of the given pallet constant.
+	pub(crate) fn constant<T: Decode>(&self, pallet_name: &str, constant_name: &str) -> Option<T> {
+		self.0
+			.pallet_by_name(pallet_name)
+			.and_then(|inner| inner.constant_by_name(constant_name))
+			.and_then(|constant| T::decode(&mut constant.value()).ok())
+	}
+
+	/// Returns the storage key of the given plain (i.e. not a map) storage item.
+	pub(crate) fn storage_value_key(&self, pallet_name: &str, entry_name: &str) -> Option<Vec<u8>> {
+		let storage = self.0.pallet_by_name(pallet_name)?.storage()?;
+		let entry = storage.entry_by_name(entry_name)?;
+		match entry.entry_type() {
+			StorageEntryType::Plain(_) => Some(
+				[twox_128(storage.prefix().as_bytes()), twox_128(entry.name().as_bytes())].concat(),
+			),
+			_ => None,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::runtime::{
 		BlockNumber, MetadataInspector, DEFAULT_FRAME_SYSTEM_PALLET_NAME,
-		DEFAULT_PARACHAIN_SYSTEM_PALLET_NAME,
+		DEFAULT_PARACHAIN_SYSTEM_PALLET_NAME, DEFAULT_REVIVE_RUNTIME_API_NAME,
+		DEFAULT_TIMESTAMP_PALLET_NAME,
 	};
 	use codec::Decode;
 	use cumulus_client_service::ParachainHostFunctions;
 	use sc_executor::WasmExecutor;
 	use sc_runtime_utilities::fetch_latest_metadata_from_code_blob;
+	use sp_crypto_hashing::twox_128;
 
 	fn cumulus_test_runtime_metadata() -> subxt_metadata::Metadata {
 		let opaque_metadata = fetch_latest_metadata_from_code_blob(
@@ -210,4 +271,37 @@ mod tests {
 		let metadata_inspector = MetadataInspector(cumulus_test_runtime_metadata());
 		assert_eq!(metadata_inspector.block_number().unwrap(), BlockNumber::U32);
 	}
+
+	#[test]
+	fn test_runtime_api_exists() {
+		let metadata_inspector = MetadataInspector(cumulus_test_runtime_metadata());
+		assert!(metadata_inspector.runtime_api_exists("Core"));
+		assert!(!metadata_inspector.runtime_api_exists(DEFAULT_REVIVE_RUNTIME_API_NAME));
+	}
+
+	#[test]
+	fn test_call_index() {
+		let metadata_inspector = MetadataInspector(cumulus_test_runtime_metadata());
+		assert!(metadata_inspector
+			.call_index(DEFAULT_FRAME_SYSTEM_PALLET_NAME, "remark")
+			.is_some());
+		assert!(metadata_inspector
+			.call_index(DEFAULT_FRAME_SYSTEM_PALLET_NAME, "unknown")
+			.is_none());
+	}
+
+	#[test]
+	fn test_storage_value_key() {
+		let metadata_inspector = MetadataInspector(cumulus_test_runtime_metadata());
+		assert_eq!(
+			metadata_inspector.storage_value_key(DEFAULT_TIMESTAMP_PALLET_NAME, "Now"),
+			Some([twox_128(b"Timestamp"), twox_128(b"Now")].concat())
+		);
+		assert!(metadata_inspector
+			.storage_value_key(DEFAULT_FRAME_SYSTEM_PALLET_NAME, "Account")
+			.is_none());
+		assert!(metadata_inspector
+			.storage_value_key(DEFAULT_TIMESTAMP_PALLET_NAME, "unknown")
+			.is_none());
+	}
 }
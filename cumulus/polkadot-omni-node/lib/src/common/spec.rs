@@ -18,6 +18,7 @@ use crate::{
 	chain_spec::DeprecatedExtensions,
 	common::{
 		command::NodeCommandRunner,
+		eth_rpc::EthRpcDeps,
 		rpc::BuildRpcExtensions,
 		statement_store::{build_statement_store, new_statement_handler_proto},
 		types::{
@@ -402,6 +403,8 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 						network_provider: Arc::new(network.clone()),
 						is_validator: parachain_config.role.is_authority(),
 						enable_http_requests: true,
+						http_policy: parachain_config.offchain_worker.http_policy.clone(),
+						prometheus_registry: parachain_config.prometheus_registry().cloned(),
 						custom_extensions,
 					})?;
 				task_manager.spawn_handle().spawn(
@@ -416,6 +419,10 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 				let transaction_pool = transaction_pool.clone();
 				let backend_for_rpc = backend.clone();
 				let statement_store = statement_store.clone();
+				let eth_rpc_deps = node_extra_args.enable_eth_rpc.then(|| EthRpcDeps {
+					sync_oracle: sync_service.clone(),
+					spawn_handle: task_manager.spawn_handle(),
+				});
 
 				Box::new(move |_| {
 					Self::BuildRpcExtensions::build_rpc_extensions(
@@ -423,6 +430,7 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 						backend_for_rpc.clone(),
 						transaction_pool.clone(),
 						statement_store.clone(),
+						eth_rpc_deps.clone(),
 					)
 				})
 			};
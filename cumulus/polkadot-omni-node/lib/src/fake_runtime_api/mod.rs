@@ -21,17 +21,41 @@ mod utils;
 
 use utils::{impl_node_runtime_apis, imports::*};
 
-type CustomBlock = crate::common::types::Block<u32>;
+/// Declares a fake runtime whose blocks use the given block number type.
+///
+/// The runtime APIs are generic over the block, but some of them (e.g. `ReviveApi`) are also
+/// generic over the block number, hence the need for one fake runtime per block number type.
+macro_rules! fake_runtime {
+	($block_number: ty, $aura_id: ty) => {
+		use super::*;
+
+		type CustomBlock = crate::common::types::Block<$block_number>;
+		#[allow(dead_code)]
+		struct FakeRuntime;
+		impl_node_runtime_apis!(FakeRuntime, CustomBlock, $aura_id);
+	};
+}
+
 pub mod aura_sr25519 {
 	use super::*;
-	#[allow(dead_code)]
-	struct FakeRuntime;
-	impl_node_runtime_apis!(FakeRuntime, CustomBlock, sp_consensus_aura::sr25519::AuthorityId);
+
+	pub mod block_number_u32 {
+		fake_runtime!(u32, sp_consensus_aura::sr25519::AuthorityId);
+	}
+
+	pub mod block_number_u64 {
+		fake_runtime!(u64, sp_consensus_aura::sr25519::AuthorityId);
+	}
 }
 
 pub mod aura_ed25519 {
 	use super::*;
-	#[allow(dead_code)]
-	struct FakeRuntime;
-	impl_node_runtime_apis!(FakeRuntime, CustomBlock, sp_consensus_aura::ed25519::AuthorityId);
+
+	pub mod block_number_u32 {
+		fake_runtime!(u32, sp_consensus_aura::ed25519::AuthorityId);
+	}
+
+	pub mod block_number_u64 {
+		fake_runtime!(u64, sp_consensus_aura::ed25519::AuthorityId);
+	}
 }
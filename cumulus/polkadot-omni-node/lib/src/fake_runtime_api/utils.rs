@@ -17,7 +17,7 @@
 pub(crate) mod imports {
 	pub use cumulus_primitives_core::{ClaimQueueOffset, CoreSelector, ParaId};
 	pub use parachains_common::{AccountId, Balance, Nonce};
-	pub use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+	pub use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H160, U256};
 	pub use sp_runtime::{
 		traits::Block as BlockT,
 		transaction_validity::{TransactionSource, TransactionValidity},
@@ -238,6 +238,108 @@ macro_rules! impl_node_runtime_apis {
 				}
 			}
 
+			#[cfg(feature = "eth-rpc")]
+			impl pallet_revive::ReviveApi<$block, AccountId, Balance, Nonce, sp_runtime::traits::NumberFor<$block>> for $runtime {
+				fn block_gas_limit() -> U256 {
+					unimplemented!()
+				}
+
+				fn balance(_: H160) -> U256 {
+					unimplemented!()
+				}
+
+				fn gas_price() -> U256 {
+					unimplemented!()
+				}
+
+				fn nonce(_: H160) -> Nonce {
+					unimplemented!()
+				}
+
+				fn call(
+					_: AccountId,
+					_: H160,
+					_: Balance,
+					_: Option<Weight>,
+					_: Option<Balance>,
+					_: Vec<u8>,
+				) -> pallet_revive::ContractResult<pallet_revive::ExecReturnValue, Balance> {
+					unimplemented!()
+				}
+
+				fn instantiate(
+					_: AccountId,
+					_: Balance,
+					_: Option<Weight>,
+					_: Option<Balance>,
+					_: pallet_revive::Code,
+					_: Vec<u8>,
+					_: Option<[u8; 32]>,
+				) -> pallet_revive::ContractResult<pallet_revive::InstantiateReturnValue, Balance> {
+					unimplemented!()
+				}
+
+				fn eth_transact(
+					_: pallet_revive::evm::GenericTransaction,
+				) -> Result<pallet_revive::EthTransactInfo<Balance>, pallet_revive::EthTransactError> {
+					unimplemented!()
+				}
+
+				fn upload_code(
+					_: AccountId,
+					_: Vec<u8>,
+					_: Option<Balance>,
+				) -> pallet_revive::CodeUploadResult<Balance> {
+					unimplemented!()
+				}
+
+				fn get_storage(_: H160, _: [u8; 32]) -> pallet_revive::GetStorageResult {
+					unimplemented!()
+				}
+
+				fn get_storage_var_key(_: H160, _: Vec<u8>) -> pallet_revive::GetStorageResult {
+					unimplemented!()
+				}
+
+				fn trace_block(
+					_: $block,
+					_: pallet_revive::evm::TracerType,
+				) -> Vec<(u32, pallet_revive::evm::Trace)> {
+					unimplemented!()
+				}
+
+				fn trace_tx(
+					_: $block,
+					_: u32,
+					_: pallet_revive::evm::TracerType,
+				) -> Option<pallet_revive::evm::Trace> {
+					unimplemented!()
+				}
+
+				fn trace_call(
+					_: pallet_revive::evm::GenericTransaction,
+					_: pallet_revive::evm::TracerType,
+				) -> Result<pallet_revive::evm::Trace, pallet_revive::EthTransactError> {
+					unimplemented!()
+				}
+
+				fn block_author() -> Option<H160> {
+					unimplemented!()
+				}
+
+				fn address(_: AccountId) -> H160 {
+					unimplemented!()
+				}
+
+				fn runtime_pallets_address() -> H160 {
+					unimplemented!()
+				}
+
+				fn code(_: H160) -> Vec<u8> {
+					unimplemented!()
+				}
+			}
+
 			impl sp_statement_store::runtime_api::ValidateStatement<$block> for $runtime {
 				fn validate_statement(
 					_source: sp_statement_store::runtime_api::StatementSource,
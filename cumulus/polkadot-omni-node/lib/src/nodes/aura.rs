@@ -18,6 +18,7 @@ use crate::{
 	cli::AuthoringPolicy,
 	common::{
 		aura::{AuraIdT, AuraRuntimeApi},
+		eth_rpc::ReviveRuntimeApi,
 		rpc::BuildParachainRpcExtensions,
 		spec::{
 			BaseNodeSpec, BuildImportQueue, ClientBlockImport, InitBlockImport, NodeSpec,
@@ -170,7 +171,8 @@ where
 	RuntimeApi: ConstructNodeRuntimeApi<Block, ParachainClient<Block, RuntimeApi>>,
 	RuntimeApi::RuntimeApi: AuraRuntimeApi<Block, AuraId>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
-		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ ReviveRuntimeApi<Block>,
 	AuraId: AuraIdT + Sync,
 	InitBlockImport: self::InitBlockImport<Block, RuntimeApi> + Send,
 	InitBlockImport::BlockImport:
@@ -190,7 +192,8 @@ where
 	RuntimeApi: ConstructNodeRuntimeApi<Block, ParachainClient<Block, RuntimeApi>>,
 	RuntimeApi::RuntimeApi: AuraRuntimeApi<Block, AuraId>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
-		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ ReviveRuntimeApi<Block>,
 	AuraId: AuraIdT + Sync,
 	StartConsensus: self::StartConsensus<
 			Block,
@@ -216,6 +219,7 @@ where
 	RuntimeApi::RuntimeApi: AuraRuntimeApi<Block, AuraId>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ ReviveRuntimeApi<Block>
 		+ GetParachainInfo<Block>,
 	AuraId: AuraIdT + Sync,
 {
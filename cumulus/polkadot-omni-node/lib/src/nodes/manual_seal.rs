@@ -15,9 +15,11 @@
 // limitations under the License.
 
 use crate::common::{
+	eth_rpc::EthRpcDeps,
 	rpc::BuildRpcExtensions as BuildRpcExtensionsT,
 	spec::{BaseNodeSpec, BuildImportQueue, ClientBlockImport, NodeSpec as NodeSpecT},
 	types::{Hash, ParachainBlockImport, ParachainClient},
+	NodeExtraArgs,
 };
 use codec::Encode;
 use cumulus_client_parachain_inherent::{MockValidationDataInherentDataProvider, MockXcmConfig};
@@ -79,6 +81,7 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 		&self,
 		mut config: Configuration,
 		block_time: u64,
+		node_extra_args: NodeExtraArgs,
 	) -> sc_service::error::Result<TaskManager>
 	where
 		Net: NetworkBackend<NodeSpec::Block, Hash>,
@@ -135,6 +138,8 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 					network_provider: Arc::new(network.clone()),
 					is_validator: config.role.is_authority(),
 					enable_http_requests: true,
+					http_policy: config.offchain_worker.http_policy.clone(),
+					prometheus_registry: config.prometheus_registry().cloned(),
 					custom_extensions: move |_| vec![],
 				})?;
 			task_manager.spawn_handle().spawn(
@@ -255,6 +260,10 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 			let client = client.clone();
 			let transaction_pool = transaction_pool.clone();
 			let backend_for_rpc = backend.clone();
+			let eth_rpc_deps = node_extra_args.enable_eth_rpc.then(|| EthRpcDeps {
+				sync_oracle: sync_service.clone(),
+				spawn_handle: task_manager.spawn_handle(),
+			});
 
 			Box::new(move |_| {
 				let mut module = NodeSpec::BuildRpcExtensions::build_rpc_extensions(
@@ -262,6 +271,7 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 					backend_for_rpc.clone(),
 					transaction_pool.clone(),
 					None,
+					eth_rpc_deps.clone(),
 				)?;
 				module
 					.merge(ManualSeal::new(manual_seal_sink.clone()).into_rpc())
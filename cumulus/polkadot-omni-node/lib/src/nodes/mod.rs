@@ -17,7 +17,10 @@
 pub mod aura;
 mod manual_seal;
 
-use crate::common::spec::{DynNodeSpec, NodeSpec as NodeSpecT};
+use crate::common::{
+	spec::{DynNodeSpec, NodeSpec as NodeSpecT},
+	NodeExtraArgs,
+};
 use manual_seal::ManualSealNode;
 use sc_service::{Configuration, TaskManager};
 
@@ -35,6 +38,7 @@ pub trait DynNodeSpecExt: DynNodeSpec {
 		&self,
 		config: Configuration,
 		block_time: u64,
+		node_extra_args: NodeExtraArgs,
 	) -> sc_service::error::Result<TaskManager>;
 }
 
@@ -47,13 +51,18 @@ where
 		&self,
 		config: Configuration,
 		block_time: u64,
+		node_extra_args: NodeExtraArgs,
 	) -> sc_service::error::Result<TaskManager> {
 		let node = ManualSealNode::<T>::new();
 		match config.network.network_backend {
-			sc_network::config::NetworkBackendType::Libp2p =>
-				node.start_node::<sc_network::NetworkWorker<_, _>>(config, block_time),
-			sc_network::config::NetworkBackendType::Litep2p =>
-				node.start_node::<sc_network::Litep2pNetworkBackend>(config, block_time),
+			sc_network::config::NetworkBackendType::Libp2p => node
+				.start_node::<sc_network::NetworkWorker<_, _>>(config, block_time, node_extra_args),
+			sc_network::config::NetworkBackendType::Litep2p => node
+				.start_node::<sc_network::Litep2pNetworkBackend>(
+				config,
+				block_time,
+				node_extra_args,
+			),
 		}
 	}
 }
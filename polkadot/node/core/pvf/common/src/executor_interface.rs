@@ -24,7 +24,7 @@ use polkadot_primitives::{
 use sc_executor_common::{
 	error::WasmError,
 	runtime_blob::RuntimeBlob,
-	wasm_runtime::{HeapAllocStrategy, WasmModule as _},
+	wasm_runtime::{HeapAllocStrategy, WasmModule},
 };
 use sc_executor_wasmtime::{Config, DeterministicStackLimit, Semantics, WasmtimeRuntime};
 use sp_core::storage::{ChildInfo, TrackedStorageKey};
@@ -46,6 +46,12 @@ use std::any::{Any, TypeId};
 const DEFAULT_HEAP_PAGES_ESTIMATE: u32 = 32;
 const EXTRA_HEAP_PAGES: u32 = 2048;
 
+/// The size of a WASM page, in bytes.
+const WASM_PAGE_SIZE: u32 = 64 * 1024;
+
+/// PolkaVM program blobs, and thus their artifacts, start with these bytes.
+const POLKAVM_MAGIC: &[u8] = b"PVM\0";
+
 // VALUES OF THE DEFAULT CONFIGURATION SHOULD NEVER BE CHANGED
 // They are used as base values for the execution environment parametrization.
 // To overwrite them, add new ones to `EXECUTOR_PARAMS` in the `session_info` pallet and perform
@@ -138,16 +144,85 @@ pub unsafe fn execute_artifact(
 pub unsafe fn create_runtime_from_artifact_bytes(
 	compiled_artifact_blob: &[u8],
 	executor_params: &ExecutorParams,
-) -> Result<WasmtimeRuntime, WasmError> {
+) -> Result<Box<dyn WasmModule>, WasmError> {
+	if compiled_artifact_blob.starts_with(POLKAVM_MAGIC) {
+		return create_polkavm_runtime(compiled_artifact_blob, executor_params)
+	}
+
 	let mut config = DEFAULT_CONFIG.clone();
 	config.semantics = params_to_wasmtime_semantics(executor_params).0;
 
-	sc_executor_wasmtime::create_runtime_from_artifact_bytes::<HostFunctions>(
-		compiled_artifact_blob,
-		config,
+	let runtime: WasmtimeRuntime = sc_executor_wasmtime::create_runtime_from_artifact_bytes::<
+		HostFunctions,
+	>(compiled_artifact_blob, config)?;
+	Ok(Box::new(runtime))
+}
+
+/// Constructs the runtime for the given PolkaVM program.
+///
+/// PolkaVM artifacts are the program blobs themselves, which are verified again when loaded.
+fn create_polkavm_runtime(
+	program_blob: &[u8],
+	executor_params: &ExecutorParams,
+) -> Result<Box<dyn WasmModule>, WasmError> {
+	let blob = RuntimeBlob::new_allow_polkavm(program_blob)?;
+	let program = blob
+		.as_polkavm_blob()
+		.ok_or_else(|| WasmError::Other("expected a PolkaVM program blob".into()))?;
+	let max_heap_size = polkavm_max_heap_size(program, executor_params)?;
+
+	sc_executor_polkavm::create_runtime_with_config::<HostFunctions>(
+		program,
+		&polkavm_config(),
+		Some(max_heap_size),
 	)
 }
 
+/// The PolkaVM configuration of the workers.
+///
+/// Programs are always interpreted: the compiler backend runs them in sandboxed processes of its
+/// own, which the security restrictions of the workers do not allow. The configuration is not
+/// read from the environment, so all validators execute the same way.
+fn polkavm_config() -> polkavm::Config {
+	let mut config = polkavm::Config::new();
+	config.set_backend(Some(polkavm::BackendKind::Interpreter));
+	config
+}
+
+/// Whether the executor parameters accept PolkaVM validation code.
+pub fn polkavm_enabled(executor_params: &ExecutorParams) -> bool {
+	executor_params.iter().any(|param| matches!(param, ExecutorParam::PolkaVM))
+}
+
+/// The maximum size of the heap of a PolkaVM program, in bytes.
+///
+/// PolkaVM programs get as much memory as the linear memory of WASM programs, which includes their
+/// data and stack. Fails if the program declares more memory than that.
+fn polkavm_max_heap_size(
+	program: &polkavm::ProgramBlob,
+	executor_params: &ExecutorParams,
+) -> Result<u32, WasmError> {
+	let max_pages = match params_to_wasmtime_semantics(executor_params).0.heap_alloc_strategy {
+		HeapAllocStrategy::Dynamic { maximum_pages } =>
+			maximum_pages.unwrap_or(DEFAULT_HEAP_PAGES_ESTIMATE + EXTRA_HEAP_PAGES),
+		HeapAllocStrategy::Static { extra_pages } => extra_pages,
+	};
+	let max_memory = max_pages.saturating_mul(WASM_PAGE_SIZE);
+
+	let declared_memory = program
+		.ro_data_size()
+		.checked_add(program.rw_data_size())
+		.and_then(|size| size.checked_add(program.stack_size()))
+		.filter(|size| *size <= max_memory)
+		.ok_or_else(|| {
+			WasmError::Other(format!(
+				"PolkaVM program declares more memory than the limit of {max_memory} bytes"
+			))
+		})?;
+
+	Ok(max_memory - declared_memory)
+}
+
 /// Takes the default config and overwrites any settings with existing executor parameters.
 ///
 /// Returns the semantics as well as the stack limit (since we are guaranteed to have it).
@@ -167,6 +242,7 @@ pub fn params_to_wasmtime_semantics(par: &ExecutorParams) -> (Semantics, Determi
 			ExecutorParam::StackLogicalMax(slm) => stack_limit.logical_max = *slm,
 			ExecutorParam::StackNativeMax(snm) => stack_limit.native_stack_max = *snm,
 			ExecutorParam::WasmExtBulkMemory => sem.wasm_bulk_memory = true,
+			ExecutorParam::PolkaVM |
 			ExecutorParam::PrecheckingMaxMemory(_) |
 			ExecutorParam::PvfPrepTimeout(_, _) |
 			ExecutorParam::PvfExecTimeout(_, _) => (), /* Not used here */
@@ -177,19 +253,33 @@ pub fn params_to_wasmtime_semantics(par: &ExecutorParams) -> (Semantics, Determi
 }
 
 /// Runs the prevalidation on the given code. Returns a [`RuntimeBlob`] if it succeeds.
+///
+/// Accepts both WASM and PolkaVM programs, [`prepare`] checks whether the latter are enabled.
 pub fn prevalidate(code: &[u8]) -> Result<RuntimeBlob, sc_executor_common::error::WasmError> {
 	// Construct the runtime blob and do some basic checks for consistency.
-	let blob = RuntimeBlob::new(code)?;
+	let blob = RuntimeBlob::new_allow_polkavm(code)?;
 	// In the future this function should take care of any further prevalidation logic.
 	Ok(blob)
 }
 
 /// Runs preparation on the given runtime blob. If successful, it returns a serialized compiled
 /// artifact which can then be used to pass into `Executor::execute` after writing it to the disk.
+///
+/// PolkaVM programs are not compiled ahead of time, their artifact is the program blob itself.
 pub fn prepare(
 	blob: RuntimeBlob,
 	executor_params: &ExecutorParams,
 ) -> Result<Vec<u8>, sc_executor_common::error::WasmError> {
+	if let Some(program) = blob.as_polkavm_blob() {
+		if !polkavm_enabled(executor_params) {
+			return Err(WasmError::Other(
+				"PolkaVM validation code is not enabled by the executor parameters".into(),
+			))
+		}
+		polkavm_max_heap_size(program, executor_params)?;
+		return Ok(blob.serialize())
+	}
+
 	let (semantics, _) = params_to_wasmtime_semantics(executor_params);
 	sc_executor_wasmtime::prepare_runtime_artifact(blob, &semantics)
 }
@@ -379,3 +469,94 @@ impl sp_core::traits::ReadRuntimeVersion for ReadRuntimeVersion {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+
+	/// A PolkaVM PVF whose `validate_block` returns its input.
+	fn echo_program(stack_size: u32) -> Vec<u8> {
+		polkavm_common::assembler::assemble(&format!(
+			"
+			%stack_size = {stack_size}
+			pub @validate_block:
+			ret
+			"
+		))
+		.unwrap()
+	}
+
+	fn polkavm_params(max_memory_pages: u32) -> ExecutorParams {
+		ExecutorParams::from(
+			&[ExecutorParam::PolkaVM, ExecutorParam::MaxMemoryPages(max_memory_pages)][..],
+		)
+	}
+
+	#[test]
+	fn polkavm_is_enabled_by_executor_param() {
+		assert!(!polkavm_enabled(&ExecutorParams::default()));
+		assert!(polkavm_enabled(&ExecutorParams::from(&[ExecutorParam::PolkaVM][..])));
+	}
+
+	#[test]
+	fn prevalidate_rejects_invalid_polkavm_blob() {
+		assert!(prevalidate(b"PVM\0\x01invalid").is_err());
+	}
+
+	#[test]
+	fn polkavm_program_is_prepared_and_executed() {
+		let params = polkavm_params(16);
+		let blob = prevalidate(&echo_program(4096)).unwrap();
+		let artifact = prepare(blob, &params).unwrap();
+		assert!(artifact.starts_with(POLKAVM_MAGIC));
+
+		let output = unsafe { execute_artifact(&artifact, &params, b"validation params") };
+		assert_matches!(output, Ok(output) if output == b"validation params");
+	}
+
+	#[test]
+	fn polkavm_program_is_rejected_unless_enabled() {
+		let blob = prevalidate(&echo_program(4096)).unwrap();
+		assert_matches!(
+			prepare(blob, &ExecutorParams::default()),
+			Err(WasmError::Other(e)) if e.contains("not enabled")
+		);
+	}
+
+	#[test]
+	fn polkavm_program_over_the_memory_limit_is_rejected() {
+		// `DEFAULT_HEAP_PAGES_ESTIMATE` pages of 64 KiB, less than the declared stack.
+		let params = polkavm_params(0);
+		let program = echo_program(4 * 1024 * 1024);
+
+		let blob = prevalidate(&program).unwrap();
+		assert_matches!(
+			prepare(blob, &params),
+			Err(WasmError::Other(e)) if e.contains("more memory than the limit")
+		);
+
+		// an artifact prepared under a higher limit is rejected when loaded under a lower one.
+		let artifact = prepare(prevalidate(&program).unwrap(), &polkavm_params(1024)).unwrap();
+		assert!(unsafe { create_runtime_from_artifact_bytes(&artifact, &params) }.is_err());
+	}
+
+	#[test]
+	fn polkavm_input_over_the_heap_limit_is_rejected() {
+		let params = polkavm_params(0);
+		let artifact = prepare(prevalidate(&echo_program(4096)).unwrap(), &params).unwrap();
+
+		let params_len = (DEFAULT_HEAP_PAGES_ESTIMATE * WASM_PAGE_SIZE) as usize;
+		let output = unsafe { execute_artifact(&artifact, &params, &vec![0; params_len]) };
+		assert_matches!(output, Err(e) if e.to_string().contains("exceeds the heap limit"));
+	}
+
+	#[test]
+	fn invalid_polkavm_artifact_is_rejected() {
+		let params = polkavm_params(16);
+		let mut artifact = prepare(prevalidate(&echo_program(4096)).unwrap(), &params).unwrap();
+		artifact.truncate(artifact.len() / 2);
+
+		assert!(unsafe { execute_artifact(&artifact, &params, b"validation params") }.is_err());
+	}
+}
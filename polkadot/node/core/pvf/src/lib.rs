@@ -71,6 +71,10 @@
 //! Preparation workers handle preparation requests by prevalidating and instrumenting PVF wasm
 //! code, and then passing it into the compiler, to prepare the artifact.
 //!
+//! PVFs may also be PolkaVM programs, if enabled by the `PolkaVM` executor parameter of the
+//! session. These are verified but not compiled ahead of time, and are interpreted by the execute
+//! workers, under the same memory limit as wasm code.
+//!
 //! ## Artifacts
 //!
 //! An artifact is the final product of preparation. If the preparation succeeded, then the artifact
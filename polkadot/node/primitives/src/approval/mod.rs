@@ -187,7 +187,7 @@ pub mod v1 {
 			let inout = self
 				.vrf_pre_output
 				.0
-				.attach_input_hash(&pubkey, transcript.0)
+				.attach_input_hash(&pubkey, transcript.transcript().clone())
 				.map_err(ApprovalError::SchnorrkelSignature)?;
 			Ok(RelayVRFStory(inout.make_bytes(super::v1::RELAY_VRF_STORY_CONTEXT)))
 		}
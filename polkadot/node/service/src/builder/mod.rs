@@ -51,7 +51,7 @@ use polkadot_primitives::Block;
 use sc_client_api::Backend;
 use sc_network::config::FullNetworkConfiguration;
 use sc_network_sync::WarpSyncConfig;
-use sc_service::{Configuration, RpcHandlers, TaskManager};
+use sc_service::{config::KeystoreConfig, Configuration, RpcHandlers, TaskManager};
 use sc_sysinfo::Metric;
 use sc_telemetry::TelemetryWorkerHandle;
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
@@ -125,6 +125,12 @@ where
 		mut config: Configuration,
 		params: NewFullParams<OverseerGenerator>,
 	) -> Result<PolkadotServiceBuilder<OverseerGenerator, Network>, Error> {
+		// Approval voting, disputes and the other parachain subsystems need the key pairs of the
+		// validator, which a remote signer never hands out, see `local_keystore`.
+		if config.role.is_authority() && matches!(config.keystore, KeystoreConfig::Remote { .. }) {
+			return Err(Error::RemoteSignerNotSupported)
+		}
+
 		let basics = new_partial_basics(&mut config, params.telemetry_worker_handle.clone())?;
 
 		let prometheus_registry = config.prometheus_registry().cloned();
@@ -360,6 +366,19 @@ where
 			import_setup.1.shared_authority_set().clone(),
 			grandpa_hard_forks,
 		));
+		// BEEFY warp proofs are preferred when BEEFY is enabled, with GRANDPA proofs used as
+		// fallback. Serving BEEFY warp proofs additionally requires offchain indexing of the MMR.
+		let warp_sync_config = if enable_beefy {
+			WarpSyncConfig::WithBeefyProvider {
+				beefy: Arc::new(sc_consensus_beefy::warp_proof::BeefyWarpSyncProofProvider::new(
+					backend.clone(),
+					client.clone(),
+				)),
+				grandpa: warp_sync,
+			}
+		} else {
+			WarpSyncConfig::WithProvider(warp_sync)
+		};
 
 		let ext_overseer_args = if is_parachain_node.is_running_alongside_parachain_node() {
 			None
@@ -452,7 +471,7 @@ where
 				spawn_handle: task_manager.spawn_handle(),
 				import_queue,
 				block_announce_validator_builder: None,
-				warp_sync_config: Some(WarpSyncConfig::WithProvider(warp_sync)),
+				warp_sync_config: Some(warp_sync_config),
 				block_relay: None,
 				metrics,
 			})?;
@@ -473,6 +492,8 @@ where
 					network_provider: Arc::new(network.clone()),
 					is_validator: role.is_authority(),
 					enable_http_requests: false,
+					http_policy: config.offchain_worker.http_policy.clone(),
+					prometheus_registry: config.prometheus_registry().cloned(),
 					custom_extensions: move |_| vec![],
 				})?
 				.run(client.clone(), task_manager.spawn_handle())
@@ -216,6 +216,12 @@ pub enum Error {
 	#[error("Creating a custom database is required for validators")]
 	DatabasePathRequired,
 
+	#[cfg(feature = "full-node")]
+	#[error(
+		"Validators cannot use a remote signer, the parachain subsystems sign with local keys"
+	)]
+	RemoteSignerNotSupported,
+
 	#[cfg(feature = "full-node")]
 	#[error("Expected at least one of polkadot, kusama, westend or rococo runtime feature")]
 	NoRuntime,
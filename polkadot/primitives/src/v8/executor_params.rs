@@ -120,6 +120,9 @@ pub enum ExecutorParam {
 	/// Enables WASM bulk memory proposal
 	#[codec(index = 7)]
 	WasmExtBulkMemory,
+	/// Accepts PolkaVM validation code next to WASM
+	#[codec(index = 8)]
+	PolkaVM,
 }
 
 /// Possible inconsistencies of executor params.
@@ -242,6 +245,7 @@ impl ExecutorParams {
 				PvfPrepTimeout(..) => Some(param),
 				PvfExecTimeout(..) => None,
 				WasmExtBulkMemory => Some(param),
+				PolkaVM => Some(param),
 			})
 			.for_each(|p| enc.extend(p.encode()));
 
@@ -325,6 +329,7 @@ impl ExecutorParams {
 					PvfExecKind::Approval => "PvfExecKind::Approval",
 				},
 				WasmExtBulkMemory => "WasmExtBulkMemory",
+				PolkaVM => "PolkaVM",
 			};
 
 			match *param {
@@ -359,6 +364,10 @@ impl ExecutorParams {
 				WasmExtBulkMemory => {
 					check!(param_ident, 1);
 				},
+
+				PolkaVM => {
+					check!(param_ident, 1);
+				},
 			}
 		}
 
@@ -429,6 +438,7 @@ fn ensure_prep_hash_changes() {
 			PvfExecTimeout(PvfExecKind::Backing, 0),
 			PvfExecTimeout(PvfExecKind::Approval, 0),
 			WasmExtBulkMemory,
+			PolkaVM,
 		][..],
 	);
 
@@ -452,6 +462,7 @@ fn ensure_prep_hash_changes() {
 			PvfExecTimeout(_, _) => continue,
 			WasmExtBulkMemory =>
 				(ExecutorParams::default(), ExecutorParams::from(&[WasmExtBulkMemory][..])),
+			PolkaVM => (ExecutorParams::default(), ExecutorParams::from(&[PolkaVM][..])),
 		};
 
 		assert_ne!(ep1.prep_hash(), ep2.prep_hash());
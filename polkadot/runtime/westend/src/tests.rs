@@ -110,7 +110,10 @@ fn check_treasury_pallet_id() {
 mod remote_tests {
 	use super::*;
 	use frame_support::traits::{TryState, TryStateSelect::All};
-	use frame_try_runtime::{runtime_decl_for_try_runtime::TryRuntime, UpgradeCheckSelect};
+	use frame_try_runtime::{
+		multi_block::run_multi_block_migrations, runtime_decl_for_try_runtime::TryRuntimeV2,
+		UpgradeCheckSelect,
+	};
 	use remote_externalities::{
 		Builder, Mode, OfflineConfig, OnlineConfig, SnapshotConfig, Transport,
 	};
@@ -145,6 +148,57 @@ mod remote_tests {
 		ext.execute_with(|| Runtime::on_runtime_upgrade(UpgradeCheckSelect::PreAndPost));
 	}
 
+	#[tokio::test]
+	async fn step_multi_block_migrations() {
+		// Intended to be run only manually.
+		if var("RUN_MIGRATION_TESTS").is_err() {
+			return;
+		}
+
+		sp_tracing::try_init_simple();
+		let transport: Transport =
+			var("WS").unwrap_or("wss://westend-rpc.polkadot.io:443".to_string()).into();
+		let maybe_state_snapshot: Option<SnapshotConfig> = var("SNAP").map(|s| s.into()).ok();
+		let mut ext = Builder::<Block>::default()
+			.mode(if let Some(state_snapshot) = maybe_state_snapshot {
+				Mode::OfflineOrElseOnline(
+					OfflineConfig { state_snapshot: state_snapshot.clone() },
+					OnlineConfig {
+						transport,
+						state_snapshot: Some(state_snapshot),
+						..Default::default()
+					},
+				)
+			} else {
+				Mode::Online(OnlineConfig { transport, ..Default::default() })
+			})
+			.build()
+			.await
+			.unwrap();
+
+		// Empty blocks carry no BABE pre-digest, so the slot is not advanced.
+		let parent = ext.header.clone();
+		let report = run_multi_block_migrations::<Block, Runtime>(
+			&mut ext,
+			parent,
+			UpgradeCheckSelect::PreAndPost,
+			1_000,
+			|_| Default::default(),
+		);
+
+		for (i, step) in report.steps.iter().enumerate() {
+			log::info!(
+				target: "remote_test",
+				"Step {}: weight {:?} of {:?}, proof size {} bytes",
+				i,
+				step.weight,
+				report.max_block_weight,
+				step.proof_size,
+			);
+		}
+		assert!(report.is_completed(), "{:?}", report.outcome);
+	}
+
 	#[tokio::test]
 	async fn delegate_stake_migration() {
 		// Intended to be run only manually.
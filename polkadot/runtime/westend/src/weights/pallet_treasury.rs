@@ -166,4 +166,72 @@ impl<T: frame_system::Config> pallet_treasury::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `AssetRate::ConversionRateToNative` (r:1 w:0)
+	/// Proof: `AssetRate::ConversionRateToNative` (`max_values`: None, `max_size`: Some(1238), added: 3713, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::StreamCount` (r:1 w:1)
+	/// Proof: `Treasury::StreamCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Treasury::Streams` (r:0 w:1)
+	/// Proof: `Treasury::Streams` (`max_values`: None, `max_size`: Some(1894), added: 4369, mode: `MaxEncodedLen`)
+	fn spend_stream() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `255`
+		//  Estimated: `4703`
+		// Minimum execution time: 25_580_000 picoseconds.
+		Weight::from_parts(26_652_475, 0)
+			.saturating_add(Weight::from_parts(0, 4703))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Treasury::Streams` (r:1 w:1)
+	/// Proof: `Treasury::Streams` (`max_values`: None, `max_size`: Some(1894), added: 4369, mode: `MaxEncodedLen`)
+	/// Storage: `XcmPallet::QueryCounter` (r:1 w:1)
+	/// Proof: `XcmPallet::QueryCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Dmp::DeliveryFeeFactor` (r:1 w:0)
+	/// Proof: `Dmp::DeliveryFeeFactor` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmPallet::SupportedVersion` (r:1 w:0)
+	/// Proof: `XcmPallet::SupportedVersion` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Dmp::DownwardMessageQueues` (r:1 w:1)
+	/// Proof: `Dmp::DownwardMessageQueues` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Paras::Heads` (r:1 w:0)
+	/// Proof: `Paras::Heads` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Dmp::DownwardMessageQueueHeads` (r:1 w:1)
+	/// Proof: `Dmp::DownwardMessageQueueHeads` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmPallet::Queries` (r:0 w:1)
+	/// Proof: `XcmPallet::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn claim_stream() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `530`
+		//  Estimated: `5359`
+		// Minimum execution time: 65_104_000 picoseconds.
+		Weight::from_parts(67_582_025, 0)
+			.saturating_add(Weight::from_parts(0, 5359))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `Treasury::Streams` (r:1 w:1)
+	/// Proof: `Treasury::Streams` (`max_values`: None, `max_size`: Some(1894), added: 4369, mode: `MaxEncodedLen`)
+	/// Storage: `XcmPallet::Queries` (r:1 w:1)
+	/// Proof: `XcmPallet::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn check_stream_status() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `346`
+		//  Estimated: `5359`
+		// Minimum execution time: 30_738_000 picoseconds.
+		Weight::from_parts(31_725_400, 0)
+			.saturating_add(Weight::from_parts(0, 5359))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Treasury::Streams` (r:1 w:1)
+	/// Proof: `Treasury::Streams` (`max_values`: None, `max_size`: Some(1894), added: 4369, mode: `MaxEncodedLen`)
+	fn cancel_stream() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `318`
+		//  Estimated: `5359`
+		// Minimum execution time: 19_814_000 picoseconds.
+		Weight::from_parts(20_452_950, 0)
+			.saturating_add(Weight::from_parts(0, 5359))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
@@ -28,7 +28,7 @@ use frame_support::{
 	},
 	PalletId,
 };
-use sp_core::{ConstU128, ConstU32, Get};
+use sp_core::{ConstU128, ConstU32, ConstU64, Get};
 use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup, MaybeEquivalence, TryConvert, TryConvertInto},
 	BuildStorage, Permill,
@@ -145,6 +145,11 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type MaxSwapPathLength = ConstU32<3>;
+	type AmplificationOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxAmplification = ConstU32<10_000>;
+	type MinAmplificationRampDuration = ConstU64<10>;
+	type MaxObservations = ConstU32<4>;
+	type ObservationPeriod = ConstU64<10>;
 	type MintMinLiquidity = ConstU128<100>;
 	type WeightInfo = ();
 	#[cfg(feature = "runtime-benchmarks")]
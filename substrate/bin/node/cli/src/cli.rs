@@ -46,6 +46,44 @@ pub struct Cli {
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub storage_monitor: sc_storage_monitor::StorageMonitorParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub indexer: IndexerParams,
+}
+
+/// Which blocks are indexed by the block indexer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum IndexerMode {
+	/// Index finalized blocks only.
+	Finalized,
+	/// Index every imported block, and prune the ones that end up on abandoned forks.
+	Imported,
+}
+
+/// Parameters of the block indexer.
+#[derive(Debug, Clone, clap::Args)]
+pub struct IndexerParams {
+	/// Index blocks, extrinsics and events into a SQLite database, and serve them over the
+	/// `indexer_*` RPC methods.
+	#[arg(long)]
+	pub indexer: bool,
+
+	/// Path of the indexer database.
+	///
+	/// Defaults to `indexer.sqlite` in the base path of the chain.
+	#[arg(long, value_name = "PATH", requires = "indexer")]
+	pub indexer_path: Option<std::path::PathBuf>,
+
+	/// Which blocks are indexed.
+	#[arg(long, value_enum, default_value_t = IndexerMode::Finalized, requires = "indexer")]
+	pub indexer_mode: IndexerMode,
+
+	/// Only keep this many of the latest finalized blocks in the indexer database.
+	///
+	/// All indexed blocks are kept by default.
+	#[arg(long, value_name = "COUNT", requires = "indexer")]
+	pub indexer_keep_blocks: Option<u32>,
 }
 
 /// Possible subcommands of the main binary.
@@ -97,6 +135,12 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export a state snapshot of a finalized block for checkpoint sync.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
+	/// Re-execute a block and export its trace in the Chrome trace event format.
+	TraceBlock(sc_cli::TraceBlockCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
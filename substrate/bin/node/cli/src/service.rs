@@ -25,7 +25,7 @@ use polkadot_sdk::{
 	sp_consensus_beefy as beefy_primitives, *,
 };
 
-use crate::Cli;
+use crate::{cli::IndexerMode, Cli};
 use codec::Encode;
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
 use frame_system_rpc_runtime_api::AccountNonceApi;
@@ -123,6 +123,7 @@ pub fn create_extrinsic(
 	let tx_ext: kitchensink_runtime::TxExtension =
 		(
 			frame_system::AuthorizeCall::<kitchensink_runtime::Runtime>::new(),
+			pallet_people::extension::AsPerson::<kitchensink_runtime::Runtime>::new(None),
 			frame_system::CheckNonZeroSender::<kitchensink_runtime::Runtime>::new(),
 			frame_system::CheckSpecVersion::<kitchensink_runtime::Runtime>::new(),
 			frame_system::CheckTxVersion::<kitchensink_runtime::Runtime>::new(),
@@ -146,6 +147,7 @@ pub fn create_extrinsic(
 		function.clone(),
 		tx_ext.clone(),
 		(
+			(),
 			(),
 			(),
 			kitchensink_runtime::VERSION.spec_version,
@@ -399,9 +401,12 @@ pub struct NewFullBase {
 }
 
 /// Creates a full service from the configuration.
+///
+/// The block indexer is spawned, and its RPC methods are served, when `indexer_config` is `Some`.
 pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	config: Configuration,
 	mixnet_config: Option<sc_mixnet::Config>,
+	indexer_config: Option<sc_service::indexer::IndexerConfig>,
 	disable_hardware_benchmarks: bool,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<
@@ -412,7 +417,10 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		&sc_consensus_babe::BabeLink<Block>,
 	),
 ) -> Result<NewFullBase, ServiceError> {
-	let is_offchain_indexing_enabled = config.offchain_worker.indexing_enabled;
+	// Light clients fetch the state of their blocks from full peers, and can't run the workers
+	// that need local state.
+	let is_light = config.network.sync_mode.is_light();
+	let is_offchain_indexing_enabled = config.offchain_worker.indexing_enabled && !is_light;
 	let role = config.role;
 	let force_authoring = config.force_authoring;
 	let backoff_authoring_blocks =
@@ -420,7 +428,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	let name = config.network.node_name.clone();
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
-	let enable_offchain_worker = config.offchain_worker.enabled;
+	let enable_offchain_worker = config.offchain_worker.enabled && !is_light;
 
 	let hwbench = (!disable_hardware_benchmarks)
 		.then(|| {
@@ -531,6 +539,17 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 			metrics,
 		})?;
 
+	if is_light {
+		sc_service::build_light_client(
+			client.clone(),
+			sc_service::new_wasm_executor(&config.executor),
+			network.clone(),
+			sync_service.clone(),
+			config.chain_spec.fork_id(),
+			&task_manager.spawn_handle(),
+		)?;
+	}
+
 	if let Some(mixnet_config) = mixnet_config {
 		let mixnet = sc_mixnet::run(
 			mixnet_config,
@@ -547,6 +566,21 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		task_manager.spawn_handle().spawn("mixnet", None, mixnet);
 	}
 
+	let indexer_rpc = indexer_config.map(|indexer_config| {
+		sc_service::indexer::spawn_indexer::<_, FullBackend, _>(
+			indexer_config,
+			client.clone(),
+			&task_manager.spawn_handle(),
+		)
+	});
+	let rpc_builder = move |subscription_executor: node_rpc::SubscriptionTaskExecutor| {
+		let mut io = rpc_builder(subscription_executor)?;
+		if let Some(indexer_rpc) = indexer_rpc.clone() {
+			io.merge(indexer_rpc).map_err(|e| ServiceError::Application(e.into()))?;
+		}
+		Ok(io)
+	};
+
 	let rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		config,
 		backend: backend.clone(),
@@ -794,6 +828,8 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 				network_provider: Arc::new(network.clone()),
 				is_validator: role.is_authority(),
 				enable_http_requests: true,
+				http_policy: config.offchain_worker.http_policy.clone(),
+				prometheus_registry: config.prometheus_registry().cloned(),
 				custom_extensions: move |_| {
 					vec![Box::new(statement_store.clone().as_statement_store_ext()) as Box<_>]
 				},
@@ -818,6 +854,18 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 /// Builds a new service for a full client.
 pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceError> {
 	let mixnet_config = cli.mixnet_params.config(config.role.is_authority());
+	let indexer_config = cli.indexer.indexer.then(|| sc_service::indexer::IndexerConfig {
+		path: cli
+			.indexer
+			.indexer_path
+			.clone()
+			.unwrap_or_else(|| config.data_path.join("indexer.sqlite")),
+		mode: match cli.indexer.indexer_mode {
+			IndexerMode::Finalized => sc_service::indexer::IndexingMode::Finalized,
+			IndexerMode::Imported => sc_service::indexer::IndexingMode::Imported,
+		},
+		keep_blocks: cli.indexer.indexer_keep_blocks,
+	});
 	let database_path = config.database.path().map(Path::to_path_buf);
 
 	let task_manager = match config.network.network_backend {
@@ -825,6 +873,7 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 			let task_manager = new_full_base::<sc_network::NetworkWorker<_, _>>(
 				config,
 				mixnet_config,
+				indexer_config,
 				cli.no_hardware_benchmarks,
 				|_, _| (),
 			)
@@ -835,6 +884,7 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 			let task_manager = new_full_base::<sc_network::Litep2pNetworkBackend>(
 				config,
 				mixnet_config,
+				indexer_config,
 				cli.no_hardware_benchmarks,
 				|_, _| (),
 			)
@@ -922,6 +972,7 @@ mod tests {
 					new_full_base::<sc_network::NetworkWorker<_, _>>(
 						config,
 						None,
+						None,
 						false,
 						|block_import: &sc_consensus_babe::BabeBlockImport<Block, _, _>,
 						 babe_link: &sc_consensus_babe::BabeLink<Block>| {
@@ -1055,6 +1106,7 @@ mod tests {
 				});
 
 				let authorize_call = frame_system::AuthorizeCall::new();
+				let as_person = pallet_people::extension::AsPerson::new(None);
 				let check_non_zero_sender = frame_system::CheckNonZeroSender::new();
 				let check_spec_version = frame_system::CheckSpecVersion::new();
 				let check_tx_version = frame_system::CheckTxVersion::new();
@@ -1069,6 +1121,7 @@ mod tests {
 				let metadata_hash = frame_metadata_hash_extension::CheckMetadataHash::new(false);
 				let tx_ext: TxExtension = (
 					authorize_call,
+					as_person,
 					check_non_zero_sender,
 					check_spec_version,
 					check_tx_version,
@@ -1084,6 +1137,7 @@ mod tests {
 					function,
 					tx_ext,
 					(
+						(),
 						(),
 						(),
 						spec_version,
@@ -1126,6 +1180,7 @@ mod tests {
 					new_full_base::<sc_network::NetworkWorker<_, _>>(
 						config,
 						None,
+						None,
 						false,
 						|_, _| (),
 					)?;
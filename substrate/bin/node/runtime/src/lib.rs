@@ -59,7 +59,7 @@ use frame_support::{
 		},
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU16, ConstU32, ConstU64,
 		ConstantStoragePrice, Contains, Currency, EitherOfDiverse, EnsureOriginWithArg,
-		EqualPrivilegeOnly, Imbalance, InsideBoth, InstanceFilter, KeyOwnerProofSystem,
+		EqualPrivilegeOnly, Equals, Imbalance, InsideBoth, InstanceFilter, KeyOwnerProofSystem,
 		LinearStoragePrice, LockIdentifier, Nothing, OnUnbalanced, VariantCountOf, WithdrawReasons,
 	},
 	weights::{
@@ -184,7 +184,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_version: 268,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 3,
 	system_version: 1,
 };
 
@@ -398,6 +398,7 @@ impl pallet_multisig::Config for Runtime {
 	type MaxSignatories = ConstU32<100>;
 	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type ProposalLifetime = ConstU32<{ 30 * DAYS }>;
 }
 
 parameter_types! {
@@ -1006,8 +1007,23 @@ impl pallet_nomination_pools::Config for Runtime {
 	type Filter = Nothing;
 }
 
+impl pallet_people::Config for Runtime {
+	type WeightInfo = pallet_people::weights::SubstrateWeight<Runtime>;
+	type RuntimeEvent = RuntimeEvent;
+	type Crypto = verifiable::ring_vrf_impl::BandersnatchVrfVerifiable;
+	type AccountContexts = Nothing;
+	type ChunkPageSize = ConstU32<16>;
+	type MaxRingSize = ConstU32<255>;
+	type OnboardingQueuePageSize = ConstU32<512>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
 parameter_types! {
 	pub const VoteLockingPeriod: BlockNumber = 30 * DAYS;
+	/// Referenda on this track are decided by one person, one vote. It can be set up through the
+	/// `referenda` dynamic parameters.
+	pub const PersonhoodTrack: u16 = 1;
 }
 
 impl pallet_conviction_voting::Config for Runtime {
@@ -1020,6 +1036,9 @@ impl pallet_conviction_voting::Config for Runtime {
 	type Polls = Referenda;
 	type BlockNumberProvider = System;
 	type VotingHooks = ();
+	type PersonhoodOrigin = pallet_people::EnsurePersonalAliasInContext<Runtime>;
+	type PersonhoodClasses = Equals<PersonhoodTrack>;
+	type VotesPerPerson = ConstU128<1>;
 }
 
 parameter_types! {
@@ -1542,6 +1561,7 @@ where
 		let era = Era::mortal(period, current_block);
 		let tx_ext: TxExtension = (
 			frame_system::AuthorizeCall::<Runtime>::new(),
+			pallet_people::extension::AsPerson::<Runtime>::new(None),
 			frame_system::CheckNonZeroSender::<Runtime>::new(),
 			frame_system::CheckSpecVersion::<Runtime>::new(),
 			frame_system::CheckTxVersion::<Runtime>::new(),
@@ -1601,6 +1621,7 @@ where
 	fn create_extension() -> Self::Extension {
 		(
 			frame_system::AuthorizeCall::<Runtime>::new(),
+			pallet_people::extension::AsPerson::<Runtime>::new(None),
 			frame_system::CheckNonZeroSender::<Runtime>::new(),
 			frame_system::CheckSpecVersion::<Runtime>::new(),
 			frame_system::CheckTxVersion::<Runtime>::new(),
@@ -1755,8 +1776,9 @@ impl pallet_vesting::Config for Runtime {
 	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
 	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
 	type BlockNumberProvider = System;
-	// `VestingInfo` encode length is 36bytes. 28 schedules gets encoded as 1009 bytes, which is the
-	// highest number of schedules that encodes less than 2^10.
+	type RevokeOrigin = EnsureRoot<AccountId>;
+	// `VestingInfo` encode length is 45 bytes. 28 schedules gets encoded as 1261 bytes, which is
+	// less than 2^11.
 	const MAX_VESTING_SCHEDULES: u32 = 28;
 }
 
@@ -1903,6 +1925,11 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
 	type WeightInfo = pallet_asset_conversion::weights::SubstrateWeight<Runtime>;
 	type MaxSwapPathLength = ConstU32<4>;
+	type AmplificationOrigin = EnsureRoot<AccountId>;
+	type MaxAmplification = ConstU32<10_000>;
+	type MinAmplificationRampDuration = ConstU32<DAYS>;
+	type MaxObservations = ConstU32<48>;
+	type ObservationPeriod = ConstU32<HOURS>;
 	type MintMinLiquidity = MintMinLiquidity;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
@@ -2121,6 +2148,9 @@ impl pallet_nft_fractionalization::Config for Runtime {
 parameter_types! {
 	pub Features: PalletFeatures = PalletFeatures::all_enabled();
 	pub const MaxAttributesPerCall: u32 = 10;
+	pub const NftsMaxRoyalty: Permill = Permill::from_percent(50);
+	pub const NftsMinBidIncrement: Permill = Permill::from_percent(1);
+	pub const NftsAuctionExtension: BlockNumber = 10 * MINUTES;
 }
 
 impl pallet_nfts::Config for Runtime {
@@ -2142,6 +2172,9 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = MaxTips;
 	type MaxDeadlineDuration = MaxDeadlineDuration;
 	type MaxAttributesPerCall = MaxAttributesPerCall;
+	type MaxRoyalty = NftsMaxRoyalty;
+	type MinBidIncrement = NftsMinBidIncrement;
+	type AuctionExtension = NftsAuctionExtension;
 	type Features = Features;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as traits::Verify>::Signer;
@@ -2304,7 +2337,7 @@ parameter_types! {
 impl pallet_migrations::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	#[cfg(not(feature = "runtime-benchmarks"))]
-	type Migrations = ();
+	type Migrations = pallet_vesting::migrations::v2::LazyMigrationV1ToV2<Runtime>;
 	// Benchmarks need mocked migrations to guarantee that they succeed.
 	#[cfg(feature = "runtime-benchmarks")]
 	type Migrations = pallet_migrations::mock_helpers::MockedMigrations;
@@ -2797,6 +2830,9 @@ mod runtime {
 
 	#[runtime::pallet_index(89)]
 	pub type MetaTx = pallet_meta_tx::Pallet<Runtime>;
+
+	#[runtime::pallet_index(90)]
+	pub type People = pallet_people::Pallet<Runtime>;
 }
 
 /// The address format for describing accounts.
@@ -2816,6 +2852,7 @@ pub type BlockId = generic::BlockId<Block>;
 /// [`sign`]: <../../testing/src/keyring.rs.html>
 pub type TxExtension = (
 	frame_system::AuthorizeCall<Runtime>,
+	pallet_people::extension::AsPerson<Runtime>,
 	frame_system::CheckNonZeroSender<Runtime>,
 	frame_system::CheckSpecVersion<Runtime>,
 	frame_system::CheckTxVersion<Runtime>,
@@ -2841,6 +2878,7 @@ impl EthExtra for EthExtraImpl {
 	fn get_eth_extension(nonce: u32, tip: Balance) -> Self::Extension {
 		(
 			frame_system::AuthorizeCall::<Runtime>::new(),
+			pallet_people::extension::AsPerson::<Runtime>::new(None),
 			frame_system::CheckNonZeroSender::<Runtime>::new(),
 			frame_system::CheckSpecVersion::<Runtime>::new(),
 			frame_system::CheckTxVersion::<Runtime>::new(),
@@ -3053,6 +3091,7 @@ mod benches {
 		[pallet_asset_conversion_ops, AssetConversionMigration]
 		[pallet_verify_signature, VerifySignature]
 		[pallet_meta_tx, MetaTx]
+		[pallet_people, People]
 	);
 }
 
@@ -3414,6 +3453,17 @@ pallet_revive::impl_runtime_apis_plus_revive!(
 		}
 	}
 
+	impl pallet_asset_conversion::TimeWeightedPriceApi<
+		Block,
+		NativeOrWithId<u32>,
+		BlockNumber,
+	> for Runtime
+	{
+		fn time_weighted_price(asset1: NativeOrWithId<u32>, asset2: NativeOrWithId<u32>, period: BlockNumber) -> Option<sp_runtime::FixedU128> {
+			AssetConversion::time_weighted_price(asset1, asset2, period)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
 		for Runtime
 	{
@@ -3634,6 +3684,7 @@ pallet_revive::impl_runtime_apis_plus_revive!(
 	}
 
 	#[cfg(feature = "try-runtime")]
+	#[api_version(2)]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
 			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
@@ -3653,6 +3704,12 @@ pallet_revive::impl_runtime_apis_plus_revive!(
 			// have a backtrace here.
 			Executive::try_execute_block(block, state_root_check, signature_check, select).unwrap()
 		}
+
+		fn execute_empty_block(
+			header: <Block as BlockT>::Header,
+		) -> frame_try_runtime::EmptyBlockOutcome<<Block as BlockT>::Header> {
+			Executive::try_execute_empty_block(header)
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -76,6 +76,7 @@ pub fn session_keys_from_seed(seed: &str) -> SessionKeys {
 pub fn tx_ext(nonce: Nonce, extra_fee: Balance) -> TxExtension {
 	(
 		frame_system::AuthorizeCall::new(),
+		pallet_people::extension::AsPerson::new(None),
 		frame_system::CheckNonZeroSender::new(),
 		frame_system::CheckSpecVersion::new(),
 		frame_system::CheckTxVersion::new(),
@@ -0,0 +1,117 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reference remote signer, serving the keys of a local keystore to `RemoteKeystore`s.
+
+#[cfg(unix)]
+mod unix {
+	use clap::Parser;
+	use sc_keystore::{
+		remote::{read_secret_file, Signer, SlashingProtection},
+		LocalKeystore,
+	};
+	use sp_core::crypto::SecretString;
+	use std::{
+		fs,
+		os::unix::{fs::PermissionsExt, net::UnixListener},
+		path::PathBuf,
+		sync::Arc,
+	};
+
+	/// Sign on behalf of nodes connecting over a Unix socket.
+	#[derive(Debug, Parser)]
+	#[command(name = "remote-signer", version)]
+	pub struct Cli {
+		/// The Unix socket to listen on.
+		#[arg(long, value_name = "SOCKET")]
+		socket: PathBuf,
+
+		/// The path of the keystore holding the keys.
+		#[arg(long, value_name = "PATH")]
+		keystore_path: PathBuf,
+
+		/// File that contains the password used by the keystore.
+		#[arg(long, value_name = "PATH")]
+		password_filename: Option<PathBuf>,
+
+		/// File that contains the hex encoded secret shared with the nodes.
+		#[arg(long, value_name = "PATH")]
+		secret_file: PathBuf,
+
+		/// File to persist the slashing protection history in.
+		///
+		/// If not given, the history is lost when the signer restarts.
+		#[arg(long, value_name = "PATH")]
+		slashing_protection: Option<PathBuf>,
+
+		/// Allow nodes to insert secret keys.
+		#[arg(long)]
+		allow_insert: bool,
+	}
+
+	pub fn run() -> Result<(), String> {
+		let cli = Cli::parse();
+
+		let password = cli
+			.password_filename
+			.map(|file| fs::read_to_string(file).map(SecretString::new))
+			.transpose()
+			.map_err(|e| format!("Failed to read the password: {e}"))?;
+		let keystore = LocalKeystore::open(cli.keystore_path, password)
+			.map_err(|e| format!("Failed to open the keystore: {e}"))?;
+		let secret = read_secret_file(&cli.secret_file)
+			.map_err(|e| format!("Failed to read the secret: {e}"))?;
+		let protection = match cli.slashing_protection {
+			Some(path) => SlashingProtection::open(path),
+			None => {
+				log::warn!("Slashing protection history is not persisted");
+				Ok(SlashingProtection::in_memory())
+			},
+		}
+		.map_err(|e| format!("Failed to open the slashing protection history: {e}"))?;
+
+		// a stale socket of a previous run prevents binding.
+		if cli.socket.exists() {
+			fs::remove_file(&cli.socket)
+				.map_err(|e| format!("Failed to remove the socket: {e}"))?;
+		}
+		let listener = UnixListener::bind(&cli.socket)
+			.map_err(|e| format!("Failed to bind {}: {e}", cli.socket.display()))?;
+		fs::set_permissions(&cli.socket, fs::Permissions::from_mode(0o600))
+			.map_err(|e| format!("Failed to restrict the socket permissions: {e}"))?;
+
+		log::info!("Listening on {}", cli.socket.display());
+		let signer =
+			Signer::new(Arc::new(keystore), secret, protection).with_allow_insert(cli.allow_insert);
+		Arc::new(signer).serve(listener).map_err(|e| format!("Failed to serve: {e}"))
+	}
+}
+
+fn main() {
+	sp_tracing::try_init_simple();
+
+	#[cfg(unix)]
+	let result = unix::run();
+	#[cfg(not(unix))]
+	let result: Result<(), String> = Err("The remote signer is only supported on Unix".into());
+
+	if let Err(e) = result {
+		eprintln!("{e}");
+		std::process::exit(1);
+	}
+}
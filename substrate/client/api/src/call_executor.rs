@@ -23,9 +23,9 @@ use sp_core::traits::CallContext;
 use sp_externalities::Extensions;
 use sp_runtime::traits::{Block as BlockT, HashingFor};
 use sp_state_machine::{OverlayedChanges, StorageProof};
-use std::cell::RefCell;
+use std::{cell::RefCell, sync::Arc};
 
-use crate::execution_extensions::ExecutionExtensions;
+use crate::{execution_extensions::ExecutionExtensions, light::RemoteStateFetcher};
 use sp_api::ProofRecorder;
 
 /// Executor Provider
@@ -92,4 +92,11 @@ pub trait CallExecutor<B: BlockT>: RuntimeVersionOf {
 		method: &str,
 		call_data: &[u8],
 	) -> Result<(Vec<u8>, StorageProof), sp_blockchain::Error>;
+
+	/// The fetcher of the state of blocks that are only known by their headers.
+	///
+	/// Only set on light clients, which fall back to it when the state of a block is missing.
+	fn remote_state_fetcher(&self) -> Option<Arc<dyn RemoteStateFetcher<B>>> {
+		None
+	}
 }
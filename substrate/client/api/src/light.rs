@@ -0,0 +1,81 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Light client interfaces.
+//!
+//! A light client only imports the headers of blocks. Their state is fetched on demand from full
+//! nodes, and checked against the state roots of the headers.
+
+use futures::future::BoxFuture;
+use sp_runtime::traits::Block as BlockT;
+use sp_storage::PrefixedStorageKey;
+use std::{collections::HashMap, sync::Arc};
+
+/// Fetcher of the state of blocks that are only known by their headers.
+pub trait RemoteStateFetcher<Block: BlockT>: Send + Sync {
+	/// Read the storage values of `keys` at block `at`.
+	fn remote_read(
+		&self,
+		at: Block::Hash,
+		keys: Vec<Vec<u8>>,
+	) -> BoxFuture<'static, sp_blockchain::Result<HashMap<Vec<u8>, Option<Vec<u8>>>>>;
+
+	/// Read the child storage values of `keys` at block `at`.
+	fn remote_read_child(
+		&self,
+		at: Block::Hash,
+		storage_key: PrefixedStorageKey,
+		keys: Vec<Vec<u8>>,
+	) -> BoxFuture<'static, sp_blockchain::Result<HashMap<Vec<u8>, Option<Vec<u8>>>>>;
+
+	/// Call the runtime `method` with `call_data` at block `at`, returning the encoded result.
+	fn remote_call(
+		&self,
+		at: Block::Hash,
+		method: String,
+		call_data: Vec<u8>,
+	) -> BoxFuture<'static, sp_blockchain::Result<Vec<u8>>>;
+}
+
+impl<Block: BlockT, T: RemoteStateFetcher<Block> + ?Sized> RemoteStateFetcher<Block> for Arc<T> {
+	fn remote_read(
+		&self,
+		at: Block::Hash,
+		keys: Vec<Vec<u8>>,
+	) -> BoxFuture<'static, sp_blockchain::Result<HashMap<Vec<u8>, Option<Vec<u8>>>>> {
+		(**self).remote_read(at, keys)
+	}
+
+	fn remote_read_child(
+		&self,
+		at: Block::Hash,
+		storage_key: PrefixedStorageKey,
+		keys: Vec<Vec<u8>>,
+	) -> BoxFuture<'static, sp_blockchain::Result<HashMap<Vec<u8>, Option<Vec<u8>>>>> {
+		(**self).remote_read_child(at, storage_key, keys)
+	}
+
+	fn remote_call(
+		&self,
+		at: Block::Hash,
+		method: String,
+		call_data: Vec<u8>,
+	) -> BoxFuture<'static, sp_blockchain::Result<Vec<u8>>> {
+		(**self).remote_call(at, method, call_data)
+	}
+}
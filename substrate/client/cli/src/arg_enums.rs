@@ -291,6 +291,10 @@ pub enum SyncMode {
 	FastUnsafe,
 	/// Prove finality and download the latest state.
 	Warp,
+	/// Import the state of a trusted snapshot, see `--checkpoint-snapshot`.
+	Checkpoint,
+	/// Download and verify headers only. State is requested from full peers on demand.
+	Light,
 }
 
 impl Into<sc_network::config::SyncMode> for SyncMode {
@@ -306,6 +310,8 @@ impl Into<sc_network::config::SyncMode> for SyncMode {
 				storage_chain_mode: false,
 			},
 			SyncMode::Warp => sc_network::config::SyncMode::Warp,
+			SyncMode::Checkpoint => sc_network::config::SyncMode::Checkpoint,
+			SyncMode::Light => sc_network::config::SyncMode::Light,
 		}
 	}
 }
@@ -72,7 +72,15 @@ impl InsertKeyCmd {
 				let keystore: KeystorePtr = LocalKeystore::open(path, password)?.into();
 				(keystore, public)
 			},
-			_ => unreachable!("keystore_config always returns path and password; qed"),
+			#[cfg(unix)]
+			KeystoreConfig::Remote { password, socket, secret_file, .. } => {
+				let public = with_crypto_scheme!(self.scheme, to_vec(&suri, password))?;
+				let secret = sc_keystore::remote::read_secret_file(&secret_file)?;
+				let keystore: KeystorePtr =
+					std::sync::Arc::new(sc_keystore::RemoteKeystore::open(socket, secret)?);
+				(keystore, public)
+			},
+			_ => unreachable!("keystore_config always returns a local or remote keystore; qed"),
 		};
 
 		let key_type =
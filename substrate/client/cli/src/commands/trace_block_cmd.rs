@@ -0,0 +1,128 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::info;
+use sc_client_api::{BlockBackend, HeaderBackend};
+use sc_tracing::block::BlockExecutor;
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_rpc::tracing::{TraceBlockResponse, TraceFormat};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{fmt::Debug, fs, io, path::PathBuf, str::FromStr, sync::Arc};
+
+/// The `trace-block` command used to export the execution timeline of a block.
+///
+/// The block is re-executed and the trace is written in the Chrome trace event format, which can
+/// be opened in <https://ui.perfetto.dev> or `chrome://tracing`. Spans emitted by the runtime are
+/// only recorded by runtimes built with the `with-tracing` feature.
+#[derive(Debug, Clone, Parser)]
+pub struct TraceBlockCmd {
+	/// Hash or number of the block to trace.
+	#[arg(value_name = "HASH or NUMBER")]
+	pub block: BlockNumberOrHash,
+
+	/// Output file name or stdout if unspecified.
+	#[arg(long, short)]
+	pub output: Option<PathBuf>,
+
+	/// Comma separated tracing targets to record, matched by prefix.
+	///
+	/// `sp_io` records the host functions called by the runtime.
+	#[arg(long, default_value = "pallet,frame,state,sp_io")]
+	pub targets: String,
+
+	/// Comma separated hex encoded storage key prefixes of the storage events to record.
+	///
+	/// All storage events are recorded if empty.
+	#[arg(long, default_value = "")]
+	pub storage_keys: String,
+
+	/// Comma separated methods of the storage events to record, e.g. `Get,Put`.
+	///
+	/// All storage events are recorded if empty.
+	#[arg(long, default_value = "")]
+	pub methods: String,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl TraceBlockCmd {
+	/// Run the `trace-block` command
+	pub async fn run<B, C>(&self, client: Arc<C>) -> error::Result<()>
+	where
+		B: BlockT + 'static,
+		C: HeaderBackend<B> + BlockBackend<B> + ProvideRuntimeApi<B> + Send + Sync + 'static,
+		C::Api: Metadata<B>,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let hash = client.expect_block_hash_from_id(&self.block.parse()?)?;
+		info!("Tracing block {hash}...");
+
+		let executor = BlockExecutor::new(
+			client,
+			hash,
+			Some(self.targets.clone()),
+			Some(self.storage_keys.clone()),
+			Some(self.methods.clone()),
+		);
+		let trace = match executor
+			.trace_block_as(TraceFormat::Chrome)
+			.map_err(|e| error::Error::Application(Box::new(e)))?
+		{
+			TraceBlockResponse::ChromeTrace(trace) => trace,
+			_ => return Err("Unexpected block trace format".into()),
+		};
+		info!("Captured {} trace events", trace.trace_events.len());
+
+		let output: Box<dyn io::Write> = match &self.output {
+			Some(filename) => Box::new(io::BufWriter::new(fs::File::create(filename)?)),
+			None => Box::new(io::BufWriter::new(io::stdout())),
+		};
+		serde_json::to_writer(output, &trace).map_err(|e| error::Error::Application(Box::new(e)))
+	}
+}
+
+impl CliConfiguration for TraceBlockCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -35,6 +35,7 @@ use sc_service::{
 	BlocksPruning, ChainSpec, TracingReceiver,
 };
 use sc_tracing::logging::LoggerBuilder;
+use sc_transaction_pool::TRANSACTION_JOURNAL_FILE;
 use std::{num::NonZeroU32, path::PathBuf};
 
 /// The maximum number of characters for a node name.
@@ -523,7 +524,9 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			impl_name: C::impl_name(),
 			impl_version: C::impl_version(),
 			tokio_handle,
-			transaction_pool: self.transaction_pool(is_dev)?,
+			transaction_pool: self
+				.transaction_pool(is_dev)?
+				.with_journal_path(config_dir.join(TRANSACTION_JOURNAL_FILE)),
 			network: self.network_config(
 				&chain_spec,
 				is_dev,
@@ -56,6 +56,18 @@ pub struct KeystoreParams {
 		conflicts_with_all = &["password_interactive", "password"]
 	)]
 	pub password_filename: Option<PathBuf>,
+
+	/// Sign with the keys of a remote signer listening on the given Unix socket.
+	///
+	/// The keystore at `--keystore-path` is still used by components that require direct access
+	/// to key pairs. Polkadot validators need those for their parachain keys, and cannot use a
+	/// remote signer.
+	#[arg(long, value_name = "SOCKET", requires = "remote_signer_secret_file")]
+	pub remote_signer: Option<PathBuf>,
+
+	/// File that contains the hex encoded secret shared with the remote signer.
+	#[arg(long, value_name = "PATH", requires = "remote_signer")]
+	pub remote_signer_secret_file: Option<PathBuf>,
 }
 
 /// Parse a secret string, returning a displayable error.
@@ -80,7 +92,15 @@ impl KeystoreParams {
 			.clone()
 			.unwrap_or_else(|| config_dir.join(DEFAULT_KEYSTORE_CONFIG_PATH));
 
-		Ok(KeystoreConfig::Path { path, password })
+		Ok(match (&self.remote_signer, &self.remote_signer_secret_file) {
+			(Some(socket), Some(secret_file)) => KeystoreConfig::Remote {
+				path,
+				password,
+				socket: socket.clone(),
+				secret_file: secret_file.clone(),
+			},
+			_ => KeystoreConfig::Path { path, password },
+		})
 	}
 
 	/// helper method to fetch password from `KeyParams` or read from stdin
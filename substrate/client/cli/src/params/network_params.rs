@@ -152,6 +152,14 @@ pub struct NetworkParams {
 	#[arg(long)]
 	pub ipfs_server: bool,
 
+	/// Persist known peers, their addresses and reputations across restarts.
+	///
+	/// The peers are stored in the network configuration directory, and reloaded when the node
+	/// starts. This way, misbehaving peers stay banned and good peers are reconnected to after
+	/// a restart.
+	#[arg(long)]
+	pub persistent_peer_store: bool,
+
 	/// Blockchain syncing mode.
 	#[arg(
 		long,
@@ -163,6 +171,13 @@ pub struct NetworkParams {
 	)]
 	pub sync: SyncMode,
 
+	/// State snapshot to import with `--sync checkpoint`.
+	///
+	/// The snapshot is created with the `export-snapshot` subcommand. It is trusted: only its
+	/// state root is checked against the header it contains.
+	#[arg(long, value_name = "PATH", required_if_eq("sync", "checkpoint"))]
+	pub checkpoint_snapshot: Option<PathBuf>,
+
 	/// Maximum number of blocks per request.
 	///
 	/// Try reducing this number from the default value if you have a slow network connection
@@ -282,7 +297,9 @@ impl NetworkParams {
 			kademlia_replication_factor: self.kademlia_replication_factor,
 			ipfs_server: self.ipfs_server,
 			sync_mode: self.sync.into(),
+			checkpoint_snapshot: self.checkpoint_snapshot.clone(),
 			network_backend: self.network_backend.into(),
+			persistent_peer_store: self.persistent_peer_store,
 		}
 	}
 }
@@ -337,4 +354,20 @@ mod tests {
 
 		assert_eq!(SyncMode::Warp, params.network_params.sync);
 	}
+
+	#[test]
+	fn checkpoint_sync_requires_snapshot() {
+		assert!(Cli::try_parse_from(["", "--sync", "checkpoint"]).is_err());
+
+		let params = Cli::try_parse_from([
+			"",
+			"--sync",
+			"checkpoint",
+			"--checkpoint-snapshot",
+			"state.snap",
+		])
+		.expect("Parses network params");
+		assert_eq!(SyncMode::Checkpoint, params.network_params.sync);
+		assert_eq!(Some(PathBuf::from("state.snap")), params.network_params.checkpoint_snapshot);
+	}
 }
@@ -25,7 +25,8 @@
 
 use clap::{ArgAction, Args};
 use sc_network::config::Role;
-use sc_service::config::OffchainWorkerConfig;
+use sc_service::config::{OffchainHttpPolicy, OffchainUrlPattern, OffchainWorkerConfig};
+use std::time::Duration;
 
 use crate::{error, OffchainWorkerEnabled};
 
@@ -47,6 +48,28 @@ pub struct OffchainWorkerParams {
 	/// Allows the runtime to write directly to offchain workers DB during block import.
 	#[arg(long = "enable-offchain-indexing", value_name = "ENABLE_OFFCHAIN_INDEXING", default_value_t = false, action = ArgAction::Set)]
 	pub indexing_enabled: bool,
+
+	/// Only allow offchain workers to make HTTP requests to URLs matching these patterns.
+	///
+	/// Patterns containing `://` match the scheme, host, port and path of the URL, others the host
+	/// only. `*` matches any sequence of characters, e.g. `*.example.com` or
+	/// `https://example.com/api/*`. All URLs are allowed if unspecified. Other requests fail with a
+	/// `Denied` error.
+	#[arg(long, value_name = "PATTERN", num_args = 1..)]
+	pub offchain_http_allow: Vec<OffchainUrlPattern>,
+
+	/// Maximum number of HTTP requests an offchain worker can start for a block.
+	#[arg(long, value_name = "COUNT")]
+	pub offchain_http_max_requests: Option<u32>,
+
+	/// Maximum number of HTTP body bytes an offchain worker can send and receive for a block.
+	#[arg(long, value_name = "BYTES")]
+	pub offchain_http_max_bytes: Option<u64>,
+
+	/// Cache successful HTTP `GET` responses for this many seconds, sharing them between
+	/// offchain workers.
+	#[arg(long, value_name = "SECONDS")]
+	pub offchain_http_cache_ttl: Option<u64>,
 }
 
 impl OffchainWorkerParams {
@@ -60,6 +83,12 @@ impl OffchainWorkerParams {
 		};
 
 		let indexing_enabled = self.indexing_enabled;
-		Ok(OffchainWorkerConfig { enabled, indexing_enabled })
+		let http_policy = OffchainHttpPolicy {
+			allowed: self.offchain_http_allow.clone(),
+			max_requests_per_block: self.offchain_http_max_requests,
+			max_bytes_per_block: self.offchain_http_max_bytes,
+			response_cache_ttl: self.offchain_http_cache_ttl.map(Duration::from_secs),
+		};
+		Ok(OffchainWorkerConfig { enabled, indexing_enabled, http_policy })
 	}
 }
@@ -39,6 +39,26 @@ impl Into<sc_transaction_pool::TransactionPoolType> for TransactionPoolType {
 	}
 }
 
+/// Transactions to journal across restarts.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum TransactionJournalMode {
+	/// Journal locally submitted transactions, e.g. by offchain workers.
+	Local,
+	/// Journal locally submitted transactions, and transactions submitted over RPC.
+	LocalAndExternal,
+}
+
+impl Into<sc_transaction_pool::TransactionJournalMode> for TransactionJournalMode {
+	fn into(self) -> sc_transaction_pool::TransactionJournalMode {
+		match self {
+			TransactionJournalMode::Local => sc_transaction_pool::TransactionJournalMode::Local,
+			TransactionJournalMode::LocalAndExternal =>
+				sc_transaction_pool::TransactionJournalMode::LocalAndExternal,
+		}
+	}
+}
+
 /// Parameters used to create the pool configuration.
 #[derive(Debug, Clone, Args)]
 pub struct TransactionPoolParams {
@@ -59,17 +79,28 @@ pub struct TransactionPoolParams {
 	/// The type of transaction pool to be instantiated.
 	#[arg(long, value_enum, default_value_t = TransactionPoolType::ForkAware)]
 	pub pool_type: TransactionPoolType,
+
+	/// Journal pending transactions in the node's base path, and re-import them after a restart.
+	///
+	/// Journaled transactions are revalidated against the best block when re-imported.
+	#[arg(long, value_enum, value_name = "TRANSACTIONS")]
+	pub pool_journal: Option<TransactionJournalMode>,
 }
 
 impl TransactionPoolParams {
 	/// Fill the given `PoolConfiguration` by looking at the cli parameters.
 	pub fn transaction_pool(&self, is_dev: bool) -> TransactionPoolOptions {
-		TransactionPoolOptions::new_with_params(
+		let options = TransactionPoolOptions::new_with_params(
 			self.pool_limit,
 			self.pool_kbytes * 1024,
 			self.tx_ban_seconds,
 			self.pool_type.into(),
 			is_dev,
-		)
+		);
+
+		match self.pool_journal {
+			Some(mode) => options.with_journal(mode.into()),
+			None => options,
+		}
 	}
 }
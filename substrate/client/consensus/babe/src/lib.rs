@@ -821,9 +821,24 @@ where
 		(_, public): Self::Claim,
 		epoch_descriptor: Self::AuxData,
 	) -> Result<BlockImportParams<B>, ConsensusError> {
-		let signature = self
-			.keystore
-			.sr25519_sign(<AuthorityId as AppCrypto>::ID, public.as_ref(), header_hash.as_ref())
+		// keystores protecting against equivocation, e.g. a remote signer, read the slot from the
+		// header, which they can only do if they are handed the header itself.
+		let encoded_header = header.encode();
+		let signature =
+			if header_hash.as_ref() == sp_crypto_hashing::blake2_256(&encoded_header).as_slice() {
+				self.keystore.sr25519_sign_with_blake2_256(
+					<AuthorityId as AppCrypto>::ID,
+					public.as_ref(),
+					&encoded_header,
+				)
+			} else {
+				self.keystore.sr25519_sign(
+					<AuthorityId as AppCrypto>::ID,
+					public.as_ref(),
+					header_hash.as_ref(),
+				)
+			};
+		let signature = signature
 			.map_err(|e| ConsensusError::CannotSign(format!("{}. Key: {:?}", e, public)))?
 			.ok_or_else(|| {
 				ConsensusError::CannotSign(format!(
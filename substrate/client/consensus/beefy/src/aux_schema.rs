@@ -23,11 +23,12 @@ use codec::{Decode, Encode};
 use log::{debug, trace, warn};
 use sc_client_api::{backend::AuxStore, Backend};
 use sp_blockchain::{Error as ClientError, Result as ClientResult};
-use sp_consensus_beefy::AuthorityIdBound;
-use sp_runtime::traits::Block as BlockT;
+use sp_consensus_beefy::{AuthorityIdBound, ValidatorSetId};
+use sp_runtime::traits::{Block as BlockT, NumberFor};
 
 const VERSION_KEY: &[u8] = b"beefy_auxschema_version";
 const WORKER_STATE_KEY: &[u8] = b"beefy_voter_state";
+const SET_CHANGES_KEY: &[u8] = b"beefy_set_changes";
 
 const CURRENT_VERSION: u32 = 4;
 
@@ -46,6 +47,57 @@ pub(crate) fn write_voter_state<B: BlockT, BE: AuxStore, AuthorityId: AuthorityI
 	AuxStore::insert_aux(backend, &[(WORKER_STATE_KEY, state.encode().as_slice())], &[])
 }
 
+/// Note the first block of the session of validator set `set_id`.
+///
+/// The index of set changes is used to serve BEEFY warp proofs. Set ids are expected to grow, so
+/// an already known or older set is ignored.
+pub(crate) fn write_set_change<B: BlockT, BE: AuxStore>(
+	backend: &BE,
+	set_id: ValidatorSetId,
+	session_start: NumberFor<B>,
+) -> ClientResult<()> {
+	let mut set_changes = load_set_changes::<B, _>(backend)?;
+	if set_changes.last().map_or(false, |(last_id, _)| *last_id >= set_id) {
+		return Ok(())
+	}
+
+	trace!(target: LOG_TARGET, "🥩 persisting set change {:?} at #{:?}", set_id, session_start);
+	set_changes.push((set_id, session_start));
+	AuxStore::insert_aux(backend, &[(SET_CHANGES_KEY, set_changes.encode().as_slice())], &[])
+}
+
+/// Note set changes that happened before the first indexed one, e.g. before the voter first
+/// started.
+///
+/// `set_changes` are expected in ascending order, the ones not preceding the index are ignored.
+pub(crate) fn prepend_set_changes<B: BlockT, BE: AuxStore>(
+	backend: &BE,
+	set_changes: Vec<(ValidatorSetId, NumberFor<B>)>,
+) -> ClientResult<()> {
+	let known = load_set_changes::<B, _>(backend)?;
+	let mut set_changes: Vec<_> = match known.first() {
+		Some((first_id, first_start)) => set_changes
+			.into_iter()
+			.filter(|(set_id, session_start)| set_id < first_id && session_start < first_start)
+			.collect(),
+		None => set_changes,
+	};
+	if set_changes.is_empty() {
+		return Ok(())
+	}
+
+	trace!(target: LOG_TARGET, "🥩 persisting {} earlier set changes", set_changes.len());
+	set_changes.extend(known);
+	AuxStore::insert_aux(backend, &[(SET_CHANGES_KEY, set_changes.encode().as_slice())], &[])
+}
+
+/// Load the validator set ids along with the first block of their sessions, in ascending order.
+pub(crate) fn load_set_changes<B: BlockT, BE: AuxStore>(
+	backend: &BE,
+) -> ClientResult<Vec<(ValidatorSetId, NumberFor<B>)>> {
+	Ok(load_decode(backend, SET_CHANGES_KEY)?.unwrap_or_default())
+}
+
 fn load_decode<BE: AuxStore, T: Decode>(backend: &BE, key: &[u8]) -> ClientResult<Option<T>> {
 	match backend.get_aux(key)? {
 		None => Ok(None),
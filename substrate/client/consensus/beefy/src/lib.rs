@@ -67,6 +67,7 @@ mod worker;
 pub mod communication;
 pub mod import;
 pub mod justification;
+pub mod warp_proof;
 
 use crate::{
 	communication::gossip::GossipValidator,
@@ -289,7 +290,7 @@ where
 
 		let persisted_state = Self::load_or_init_state(
 			beefy_genesis,
-			best_grandpa,
+			best_grandpa.clone(),
 			min_block_delta,
 			backend.clone(),
 			runtime.clone(),
@@ -298,6 +299,16 @@ where
 			is_authority,
 		)
 		.await?;
+		// Set changes are only indexed by the voter as it goes, so make sure the index of the set
+		// changes goes back to the BEEFY activation, for serving warp proofs.
+		if let Err(e) = backfill_set_changes::<B, _, _, AuthorityId>(
+			beefy_genesis,
+			&best_grandpa,
+			backend.as_ref(),
+			runtime.as_ref(),
+		) {
+			warn!(target: LOG_TARGET, "🥩 Failed to backfill BEEFY set changes: {:?}", e);
+		}
 		// Update the gossip validator with the right starting round and set id.
 		persisted_state
 			.gossip_filter_config()
@@ -795,6 +806,77 @@ where
 	}
 }
 
+/// Index the validator set changes that happened between the BEEFY activation at `beefy_genesis`
+/// and the first indexed one (or `best_grandpa` if none is indexed yet).
+///
+/// The set BEEFY was activated with is read from the state of the activation block, or from its
+/// digest if the state is pruned. The later changes are read from the BEEFY digests of the
+/// finalized headers. Backfilling stops at the first missing header, e.g. when the headers
+/// haven't been gap synced yet, and is resumed on the next start.
+fn backfill_set_changes<B, BE, R, AuthorityId>(
+	beefy_genesis: NumberFor<B>,
+	best_grandpa: &B::Header,
+	backend: &BE,
+	runtime: &R,
+) -> Result<(), Error>
+where
+	B: Block,
+	BE: Backend<B>,
+	R: ProvideRuntimeApi<B>,
+	R::Api: BeefyApi<B, AuthorityId>,
+	AuthorityId: AuthorityIdBound,
+{
+	let blockchain = backend.blockchain();
+	let known = aux_schema::load_set_changes::<B, _>(backend)?;
+	let until = match known.first() {
+		Some((_, session_start)) if *session_start <= beefy_genesis => return Ok(()),
+		Some((_, session_start)) => *session_start,
+		None => *best_grandpa.number(),
+	};
+	let Some(mut header) =
+		blockchain.hash(until)?.and_then(|hash| blockchain.header(hash).ok().flatten())
+	else {
+		return Ok(())
+	};
+
+	debug!(
+		target: LOG_TARGET,
+		"🥩 Backfilling BEEFY set changes from block {:?} back to {:?}.", until, beefy_genesis
+	);
+	let mut set_changes = VecDeque::new();
+	loop {
+		if *header.number() == beefy_genesis {
+			let active = runtime
+				.runtime_api()
+				.validator_set(header.hash())
+				.ok()
+				.flatten()
+				.or_else(|| find_authorities_change::<B, AuthorityId>(&header));
+			if let Some(active) = active {
+				set_changes.push_front((active.id(), beefy_genesis));
+			}
+			break
+		}
+		if let Some(active) = find_authorities_change::<B, AuthorityId>(&header) {
+			set_changes.push_front((active.id(), *header.number()));
+		}
+		match blockchain.header(*header.parent_hash())? {
+			Some(parent) => header = parent,
+			None => {
+				debug!(
+					target: LOG_TARGET,
+					"🥩 Parent of header {:?} is missing, BEEFY set changes are partially backfilled.",
+					header.number(),
+				);
+				break
+			},
+		}
+	}
+
+	aux_schema::prepend_set_changes::<B, _>(backend, set_changes.into())?;
+	Ok(())
+}
+
 /// Scan the `header` digest log for a BEEFY validator set change. Return either the new
 /// validator set or `None` in case no validator set change has been signaled.
 pub(crate) fn find_authorities_change<B, AuthorityId>(
@@ -19,8 +19,10 @@
 //! Tests and test helpers for BEEFY.
 
 use crate::{
-	aux_schema::{load_persistent, tests::verify_persisted_version},
-	beefy_block_import_and_links,
+	aux_schema::{
+		load_persistent, load_set_changes, tests::verify_persisted_version, write_set_change,
+	},
+	backfill_set_changes, beefy_block_import_and_links,
 	communication::{
 		gossip::{
 			proofs_topic,
@@ -1304,6 +1306,44 @@ async fn should_initialize_voter_at_custom_genesis_when_state_unavailable() {
 	assert_eq!(state, persisted_state);
 }
 
+#[tokio::test]
+async fn should_backfill_set_changes_from_beefy_activation() {
+	let keys = &[BeefyKeyring::Alice];
+	let mut net = BeefyTestNet::new(1);
+	let backend = net.peer(0).client().as_backend();
+	let client = net.peer(0).client().as_client();
+	// BEEFY activated by a runtime upgrade at block 7, with validator set 3
+	let activation = 7;
+	let activation_set = ValidatorSet::new(make_beefy_ids(keys), 3).unwrap();
+	let api = TestApi::new(activation, &activation_set, GOOD_MMR_ROOT);
+
+	// push 30 blocks with `AuthorityChange` digests of the next validator set every 10 blocks
+	let mut block_num = 0;
+	let hashes = net.peer(0).generate_blocks(30, BlockOrigin::File, |mut builder| {
+		block_num += 1;
+		if block_num % 10 == 0 {
+			let next_set = ValidatorSet::new(make_beefy_ids(keys), 3 + block_num / 10).unwrap();
+			add_auth_change_digest(&mut builder, next_set);
+		}
+		builder.build().unwrap().block
+	});
+	net.run_until_sync().await;
+	let header = |number: usize| client.header(hashes[number - 1]).unwrap().unwrap();
+
+	// voter started at block 20, set changes before that are unknown
+	write_set_change::<Block, _>(&*backend, 5, 20).unwrap();
+	backfill_set_changes::<_, _, _, AuthorityId>(activation, &header(25), &*backend, &api).unwrap();
+	assert_eq!(load_set_changes::<Block, _>(&*backend).unwrap(), vec![(3, 7), (4, 10), (5, 20)]);
+
+	// the voter keeps indexing new set changes, the index is not backfilled again
+	write_set_change::<Block, _>(&*backend, 6, 30).unwrap();
+	backfill_set_changes::<_, _, _, AuthorityId>(activation, &header(30), &*backend, &api).unwrap();
+	assert_eq!(
+		load_set_changes::<Block, _>(&*backend).unwrap(),
+		vec![(3, 7), (4, 10), (5, 20), (6, 30)]
+	);
+}
+
 #[tokio::test]
 async fn should_catch_up_when_loading_saved_voter_state() {
 	let keys = &[BeefyKeyring::Alice];
@@ -0,0 +1,682 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Utilities for generating and verifying BEEFY warp sync proofs.
+//!
+//! A BEEFY warp proof chains validator set handoffs: a commitment signed by the current validator
+//! set carries the MMR root, and the MMR leaf of the committed block proves the keyset commitment
+//! of the next validator set. The proof ends with a commitment of the latest validator set for a
+//! recent block, whose parent header becomes the warp sync target.
+
+use codec::{Decode, DecodeAll, Encode};
+
+use crate::{
+	aux_schema::load_set_changes,
+	justification::{verify_with_validator_set, BeefyVersionedFinalityProof},
+};
+use sc_client_api::Backend as ClientBackend;
+use sc_network_sync::strategy::warp::{
+	BeefyValidatorSet, BeefyVerificationResult, BeefyWarpSyncProvider, EncodedProof,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::{Backend as BlockchainBackend, HeaderBackend};
+use sp_consensus_beefy::{
+	ecdsa_crypto::AuthorityId, known_payloads, mmr::MmrLeaf, BeefyApi, MmrRootHash, ValidatorSetId,
+	VersionedFinalityProof, BEEFY_ENGINE_ID,
+};
+use sp_core::H256;
+use sp_mmr_primitives::{mmr_lib, utils::NodesUtils, EncodableOpaqueLeaf, LeafProof, MmrApi};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Hash as HashT, Header as HeaderT, Keccak256, NumberFor, One, Zero},
+};
+
+use std::{marker::PhantomData, sync::Arc};
+
+/// Warp proof processing error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// Decoding error.
+	#[error("Failed to decode block hash: {0}.")]
+	DecodeScale(#[from] codec::Error),
+	/// Client backend error.
+	#[error("{0}")]
+	Client(#[from] sp_blockchain::Error),
+	/// Runtime API error.
+	#[error("{0}")]
+	RuntimeApi(#[from] sp_api::ApiError),
+	/// MMR proof generation error.
+	#[error("{0}")]
+	Mmr(#[from] sp_mmr_primitives::Error),
+	/// Invalid request data.
+	#[error("{0}")]
+	InvalidRequest(String),
+	/// Invalid warp proof.
+	#[error("{0}")]
+	InvalidProof(String),
+	/// Missing justification or validator set change data.
+	#[error("Missing required data to be able to answer request.")]
+	MissingData,
+}
+
+/// The maximum size in bytes of the `BeefyWarpSyncProof`.
+pub(crate) const MAX_WARP_SYNC_PROOF_SIZE: usize = 8 * 1024 * 1024;
+
+/// A commitment of a validator set along with the MMR leaf of the committed block.
+#[derive(Decode, Encode, Debug)]
+pub struct MmrCommitment<Block: BlockT> {
+	/// BEEFY justification of the committed block, signed by the current validator set.
+	pub justification: BeefyVersionedFinalityProof<Block, AuthorityId>,
+	/// MMR leaf added by the committed block.
+	pub leaf: EncodableOpaqueLeaf,
+	/// Proof of the leaf against the MMR root of the commitment.
+	pub leaf_proof: LeafProof<MmrRootHash>,
+}
+
+/// A proof of a validator set handoff.
+#[derive(Decode, Encode, Debug)]
+pub struct BeefyWarpSyncFragment<Block: BlockT> {
+	/// Commitment whose MMR leaf proves the keyset commitment of the next validator set.
+	pub commitment: MmrCommitment<Block>,
+	/// Validators of the next validator set.
+	pub next_validators: Vec<AuthorityId>,
+}
+
+/// A proof of finality of the warp sync target.
+#[derive(Decode, Encode, Debug)]
+pub struct BeefyWarpSyncTarget<Block: BlockT> {
+	/// Commitment of the latest validator set.
+	pub commitment: MmrCommitment<Block>,
+	/// The parent header of the committed block, proved by the MMR leaf.
+	pub header: Block::Header,
+}
+
+/// An accumulated proof of multiple validator set handoffs.
+#[derive(Decode, Encode)]
+pub struct BeefyWarpSyncProof<Block: BlockT> {
+	handoffs: Vec<BeefyWarpSyncFragment<Block>>,
+	target: Option<BeefyWarpSyncTarget<Block>>,
+}
+
+/// Leaf type of the MMR, ignoring the leaf extra data.
+type Leaf<Block> = MmrLeaf<NumberFor<Block>, <Block as BlockT>::Hash, MmrRootHash, ()>;
+
+/// MMR node merging as done by `pallet-mmr` with the `Keccak256` hashing.
+struct MmrHasher;
+
+impl mmr_lib::Merge for MmrHasher {
+	type Item = H256;
+
+	fn merge(left: &Self::Item, right: &Self::Item) -> mmr_lib::Result<Self::Item> {
+		let mut concat = left.as_bytes().to_vec();
+		concat.extend_from_slice(right.as_bytes());
+
+		Ok(Keccak256::hash(&concat))
+	}
+}
+
+/// Compute the keyset commitment of the validators, as done by `pallet-beefy-mmr`.
+fn keyset_commitment(validators: &[AuthorityId]) -> H256 {
+	let addresses = validators.iter().map(|id| {
+		sp_core::ecdsa::Public::from(id.clone())
+			.to_eth_address()
+			.map(|address| address.to_vec())
+			.unwrap_or_default()
+	});
+	binary_merkle_tree::merkle_root::<Keccak256, _>(addresses)
+}
+
+impl<Block: BlockT> MmrCommitment<Block> {
+	/// Load the justification of block `number` and prove its MMR leaf at the state of `at`.
+	fn generate<Backend, Runtime>(
+		backend: &Backend,
+		runtime: &Runtime,
+		at: Block::Hash,
+		number: NumberFor<Block>,
+	) -> Result<Self, Error>
+	where
+		Backend: ClientBackend<Block>,
+		Runtime: ProvideRuntimeApi<Block>,
+		Runtime::Api: MmrApi<Block, MmrRootHash, NumberFor<Block>>,
+	{
+		let blockchain = backend.blockchain();
+		let hash = blockchain.hash(number)?.ok_or(Error::MissingData)?;
+		let justification = blockchain
+			.justifications(hash)?
+			.and_then(|just| just.into_justification(BEEFY_ENGINE_ID))
+			.ok_or(Error::MissingData)?;
+		let justification =
+			BeefyVersionedFinalityProof::<Block, AuthorityId>::decode_all(&mut &justification[..])?;
+
+		let (mut leaves, leaf_proof) =
+			runtime.runtime_api().generate_proof(at, vec![number], Some(number))??;
+		let leaf = leaves.pop().ok_or(Error::MissingData)?;
+
+		Ok(MmrCommitment { justification, leaf, leaf_proof })
+	}
+
+	/// Verify the commitment against the validator set, returning the committed block number and
+	/// the MMR leaf.
+	fn verify(&self, validator_set: &BeefyValidatorSet) -> Result<Leaf<Block>, Error> {
+		let VersionedFinalityProof::V1(signed_commitment) = &self.justification;
+		let commitment = &signed_commitment.commitment;
+		verify_with_validator_set::<Block, AuthorityId>(
+			commitment.block_number,
+			validator_set,
+			&self.justification,
+		)
+		.map_err(|(err, _)| Error::InvalidProof(err.to_string()))?;
+
+		let mmr_root = commitment
+			.payload
+			.get_decoded::<MmrRootHash>(&known_payloads::MMR_ROOT_ID)
+			.ok_or_else(|| Error::InvalidProof("Commitment is missing the MMR root".to_string()))?;
+
+		let [leaf_index] = self.leaf_proof.leaf_indices[..] else {
+			return Err(Error::InvalidProof("Expected a proof of a single MMR leaf".to_string()))
+		};
+		let proof = mmr_lib::MerkleProof::<H256, MmrHasher>::new(
+			NodesUtils::new(self.leaf_proof.leaf_count).size(),
+			self.leaf_proof.items.clone(),
+		);
+		let leaf_hash = Keccak256::hash(&self.leaf.0);
+		let is_valid = proof
+			.verify(mmr_root, vec![(mmr_lib::leaf_index_to_pos(leaf_index), leaf_hash)])
+			.map_err(|err| Error::InvalidProof(format!("Invalid MMR proof: {err:?}")))?;
+		if !is_valid {
+			return Err(Error::InvalidProof("MMR leaf is not part of the committed MMR".to_string()))
+		}
+
+		let leaf = Leaf::<Block>::decode(&mut &self.leaf.0[..])?;
+		if leaf.parent_number_and_hash.0 + One::one() != commitment.block_number {
+			return Err(Error::InvalidProof("Mismatch between MMR leaf and commitment".to_string()))
+		}
+
+		Ok(leaf)
+	}
+}
+
+/// Find the index of the set change of the validator set active at `begin_number`.
+///
+/// Before the BEEFY activation at `beefy_genesis` no set is active, so proofs start with the set
+/// BEEFY was activated with, provided the index of set changes goes back to the activation.
+fn first_set_change<N: PartialOrd>(
+	set_changes: &[(ValidatorSetId, N)],
+	begin_number: N,
+	beefy_genesis: N,
+) -> Option<usize> {
+	match set_changes
+		.iter()
+		.rposition(|(_, session_start)| *session_start <= begin_number)
+	{
+		Some(first) => Some(first),
+		None if set_changes.first().map_or(false, |(_, start)| *start <= beefy_genesis) => Some(0),
+		None => None,
+	}
+}
+
+impl<Block: BlockT> BeefyWarpSyncProof<Block> {
+	/// Generates a warp sync proof starting at the given block. It will generate validator set
+	/// handoff proofs for all handoffs that happened from `begin` until the current validator set
+	/// (capped by MAX_WARP_SYNC_PROOF_SIZE).
+	fn generate<Backend, Runtime>(
+		backend: &Backend,
+		runtime: &Runtime,
+		begin: Block::Hash,
+		beefy_genesis: NumberFor<Block>,
+		set_changes: &[(ValidatorSetId, NumberFor<Block>)],
+	) -> Result<BeefyWarpSyncProof<Block>, Error>
+	where
+		Backend: ClientBackend<Block>,
+		Runtime: ProvideRuntimeApi<Block>,
+		Runtime::Api: MmrApi<Block, MmrRootHash, NumberFor<Block>>,
+	{
+		let blockchain = backend.blockchain();
+		let info = blockchain.info();
+
+		let begin_number = blockchain
+			.block_number_from_id(&BlockId::Hash(begin))?
+			.ok_or_else(|| Error::InvalidRequest("Missing start block".to_string()))?;
+
+		if begin_number > info.finalized_number {
+			return Err(Error::InvalidRequest("Start block is not finalized".to_string()))
+		}
+
+		if blockchain.hash(begin_number)? != Some(begin) {
+			return Err(Error::InvalidRequest(
+				"Start block is not in the finalized chain".to_string(),
+			))
+		}
+
+		// The set active at `begin` is the first one to hand off.
+		let first =
+			first_set_change(set_changes, begin_number, beefy_genesis).ok_or(Error::MissingData)?;
+		let set_changes = &set_changes[first..];
+
+		let mut handoffs = Vec::new();
+		let mut proofs_encoded_len = 0;
+		let mut proof_limit_reached = false;
+
+		for window in set_changes.windows(2) {
+			let [(set_id, session_start), (next_set_id, next_session_start)] = window else {
+				unreachable!("windows of size 2 are requested; qed");
+			};
+			if *next_set_id != set_id + 1 {
+				// The chain of handoffs is broken if a set change is unknown to us.
+				return Err(Error::MissingData)
+			}
+
+			let commitment = MmrCommitment::<Block>::generate(
+				backend,
+				runtime,
+				info.finalized_hash,
+				*session_start,
+			)?;
+			let next_session_start_hash =
+				blockchain.hash(*next_session_start)?.ok_or(Error::MissingData)?;
+			let next_validators = runtime
+				.runtime_api()
+				.validator_set(next_session_start_hash)?
+				.ok_or(Error::MissingData)?
+				.validators()
+				.to_vec();
+
+			let handoff = BeefyWarpSyncFragment { commitment, next_validators };
+			let handoff_size = handoff.encoded_size();
+
+			// Check for the limit. We remove some bytes from the maximum size, because we're only
+			// counting the size of the `BeefyWarpSyncFragment`s. The extra margin is here to leave
+			// room for rest of the data (the size of the `Vec` and the target).
+			if proofs_encoded_len + handoff_size >= MAX_WARP_SYNC_PROOF_SIZE - 50 {
+				proof_limit_reached = true;
+				break
+			}
+
+			proofs_encoded_len += handoff_size;
+			handoffs.push(handoff);
+		}
+
+		let target = if proof_limit_reached {
+			None
+		} else {
+			// The latest set is guaranteed to have a justification for its mandatory block.
+			let (_, session_start) =
+				set_changes.last().expect("`first` is an index of `set_changes`; qed");
+			let mut number = info.finalized_number;
+			let target_number = loop {
+				if number <= *session_start {
+					break *session_start
+				}
+				let hash = blockchain.hash(number)?.ok_or(Error::MissingData)?;
+				if blockchain
+					.justifications(hash)?
+					.map_or(false, |just| just.get(BEEFY_ENGINE_ID).is_some())
+				{
+					break number
+				}
+				number -= One::one();
+			};
+
+			if target_number.is_zero() {
+				return Err(Error::MissingData)
+			}
+
+			let commitment = MmrCommitment::<Block>::generate(
+				backend,
+				runtime,
+				info.finalized_hash,
+				target_number,
+			)?;
+			let parent_hash =
+				blockchain.hash(target_number - One::one())?.ok_or(Error::MissingData)?;
+			let header = blockchain.header(parent_hash)?.ok_or(Error::MissingData)?;
+			let target = BeefyWarpSyncTarget { commitment, header };
+
+			(proofs_encoded_len + target.encoded_size() < MAX_WARP_SYNC_PROOF_SIZE - 50)
+				.then_some(target)
+		};
+
+		let final_outcome = BeefyWarpSyncProof { handoffs, target };
+		debug_assert!(final_outcome.encoded_size() <= MAX_WARP_SYNC_PROOF_SIZE);
+		Ok(final_outcome)
+	}
+
+	/// Verifies the warp sync proof starting at the given validator set. Verification stops when
+	/// either the proof is exhausted or finality for the target header can be proven.
+	fn verify(
+		&self,
+		validator_set: BeefyValidatorSet,
+	) -> Result<BeefyVerificationResult<Block>, Error> {
+		let mut current = validator_set;
+		let mut last_hash = None;
+
+		for handoff in &self.handoffs {
+			let VersionedFinalityProof::V1(signed_commitment) = &handoff.commitment.justification;
+			if signed_commitment.commitment.validator_set_id < current.id() {
+				// Handoffs we already know about are skipped.
+				continue
+			}
+
+			let leaf = handoff.commitment.verify(&current)?;
+			let next_authority_set = leaf.beefy_next_authority_set;
+			if next_authority_set.id != current.id() + 1 {
+				return Err(Error::InvalidProof("MMR leaf is missing the next validator set".into()))
+			}
+			if next_authority_set.len as usize != handoff.next_validators.len() ||
+				next_authority_set.keyset_commitment !=
+					keyset_commitment(&handoff.next_validators)
+			{
+				return Err(Error::InvalidProof(
+					"Mismatch between next validators and MMR leaf".to_string(),
+				))
+			}
+
+			current =
+				BeefyValidatorSet::new(handoff.next_validators.clone(), next_authority_set.id)
+					.ok_or_else(|| Error::InvalidProof("Empty next validator set".to_string()))?;
+			last_hash = Some(leaf.parent_number_and_hash.1);
+		}
+
+		match &self.target {
+			Some(target) => {
+				let leaf = target.commitment.verify(&current)?;
+				if leaf.parent_number_and_hash.1 != target.header.hash() {
+					return Err(Error::InvalidProof(
+						"Mismatch between target header and MMR leaf".to_string(),
+					))
+				}
+				Ok(BeefyVerificationResult::Complete(current, target.header.clone()))
+			},
+			None => {
+				let last_hash =
+					last_hash.ok_or_else(|| Error::InvalidProof("Empty proof".to_string()))?;
+				Ok(BeefyVerificationResult::Partial(current, last_hash))
+			},
+		}
+	}
+}
+
+/// Implements network API for BEEFY warp sync.
+///
+/// Serving proofs requires the MMR to be available through offchain indexing.
+pub struct BeefyWarpSyncProofProvider<Block, Backend, Runtime> {
+	backend: Arc<Backend>,
+	runtime: Arc<Runtime>,
+	activation_validator_set: Option<BeefyValidatorSet>,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block, Backend, Runtime> BeefyWarpSyncProofProvider<Block, Backend, Runtime> {
+	/// Create a new instance for a given backend and runtime.
+	pub fn new(backend: Arc<Backend>, runtime: Arc<Runtime>) -> Self {
+		BeefyWarpSyncProofProvider {
+			backend,
+			runtime,
+			activation_validator_set: None,
+			_phantom: PhantomData,
+		}
+	}
+
+	/// Verify proofs starting with the given validator set, for chains where BEEFY was activated
+	/// after genesis.
+	///
+	/// The validator set is trusted, so it must come from a finalized BEEFY activation block.
+	/// Without it, the genesis validator set is used and warp sync falls back to GRANDPA proofs
+	/// if BEEFY is not active at genesis.
+	pub fn with_activation_validator_set(mut self, validator_set: BeefyValidatorSet) -> Self {
+		self.activation_validator_set = Some(validator_set);
+		self
+	}
+}
+
+impl<Block, Backend, Runtime> BeefyWarpSyncProvider<Block>
+	for BeefyWarpSyncProofProvider<Block, Backend, Runtime>
+where
+	Block: BlockT,
+	Backend: ClientBackend<Block>,
+	Runtime: ProvideRuntimeApi<Block> + Send + Sync,
+	Runtime::Api: BeefyApi<Block, AuthorityId> + MmrApi<Block, MmrRootHash, NumberFor<Block>>,
+{
+	fn generate(
+		&self,
+		start: Block::Hash,
+	) -> Result<EncodedProof, Box<dyn std::error::Error + Send + Sync>> {
+		let set_changes = load_set_changes::<Block, _>(&*self.backend).map_err(Error::Client)?;
+		let finalized_hash = self.backend.blockchain().info().finalized_hash;
+		let beefy_genesis = self
+			.runtime
+			.runtime_api()
+			.beefy_genesis(finalized_hash)
+			.map_err(Error::RuntimeApi)?
+			.ok_or(Error::MissingData)?;
+		let proof = BeefyWarpSyncProof::<Block>::generate(
+			&*self.backend,
+			&*self.runtime,
+			start,
+			beefy_genesis,
+			&set_changes,
+		)
+		.map_err(Box::new)?;
+		Ok(EncodedProof(proof.encode()))
+	}
+
+	fn verify(
+		&self,
+		proof: &EncodedProof,
+		validator_set: BeefyValidatorSet,
+	) -> Result<BeefyVerificationResult<Block>, Box<dyn std::error::Error + Send + Sync>> {
+		let EncodedProof(proof) = proof;
+		let proof = BeefyWarpSyncProof::<Block>::decode_all(&mut proof.as_slice())
+			.map_err(|e| format!("Proof decoding error: {:?}", e))?;
+		Ok(proof.verify(validator_set).map_err(Box::new)?)
+	}
+
+	fn initial_validator_set(&self) -> Option<BeefyValidatorSet> {
+		let genesis_hash = self.backend.blockchain().info().genesis_hash;
+		self.runtime
+			.runtime_api()
+			.validator_set(genesis_hash)
+			.ok()
+			.flatten()
+			.or_else(|| self.activation_validator_set.clone())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::make_beefy_ids;
+	use sp_consensus_beefy::{
+		mmr::{BeefyAuthoritySet, MmrLeafVersion},
+		test_utils::Keyring,
+		Commitment, Payload, SignedCommitment,
+	};
+	use substrate_test_runtime_client::runtime::{Block, Header};
+
+	fn header(number: u64) -> Header {
+		Header::new(
+			number,
+			Default::default(),
+			Default::default(),
+			H256::random(),
+			Default::default(),
+		)
+	}
+
+	/// Commit block `number` by `keys` of `validator_set`, in a single leaf MMR whose leaf
+	/// announces `next` as the next validator set.
+	fn mmr_commitment(
+		number: u64,
+		parent_hash: H256,
+		validator_set: &BeefyValidatorSet,
+		keys: &[Keyring<AuthorityId>],
+		next: &BeefyValidatorSet,
+	) -> MmrCommitment<Block> {
+		let leaf = MmrLeaf {
+			version: MmrLeafVersion::new(0, 0),
+			parent_number_and_hash: (number - 1, parent_hash),
+			beefy_next_authority_set: BeefyAuthoritySet {
+				id: next.id(),
+				len: next.len() as u32,
+				keyset_commitment: keyset_commitment(next.validators()),
+			},
+			leaf_extra: vec![1, 2, 3],
+		};
+		let leaf = EncodableOpaqueLeaf(leaf.encode());
+		// The root of a single leaf MMR is the leaf hash.
+		let mmr_root = Keccak256::hash(&leaf.0);
+
+		let commitment = Commitment {
+			payload: Payload::from_single_entry(known_payloads::MMR_ROOT_ID, mmr_root.encode()),
+			block_number: number,
+			validator_set_id: validator_set.id(),
+		};
+		let message = commitment.encode();
+		let signatures = keys.iter().map(|key| Some(key.sign(&message))).collect();
+		MmrCommitment {
+			justification: VersionedFinalityProof::V1(SignedCommitment { commitment, signatures }),
+			leaf,
+			leaf_proof: LeafProof { leaf_indices: vec![0], leaf_count: 1, items: Vec::new() },
+		}
+	}
+
+	/// Three validator sets, the first one being `first_id`.
+	fn validator_sets(first_id: u64) -> Vec<(Vec<Keyring<AuthorityId>>, BeefyValidatorSet)> {
+		[
+			vec![Keyring::Alice, Keyring::Bob],
+			vec![Keyring::Charlie, Keyring::Dave],
+			vec![Keyring::Eve, Keyring::Ferdie],
+		]
+		.into_iter()
+		.enumerate()
+		.map(|(id, keys)| {
+			let validator_set =
+				BeefyValidatorSet::new(make_beefy_ids(&keys), first_id + id as u64).unwrap();
+			(keys, validator_set)
+		})
+		.collect()
+	}
+
+	fn warp_sync_proof(target: &Header, first_id: u64) -> BeefyWarpSyncProof<Block> {
+		let sets = validator_sets(first_id);
+		let handoffs = (0..2)
+			.map(|i| {
+				let (keys, validator_set) = &sets[i];
+				let next = &sets[i + 1].1;
+				BeefyWarpSyncFragment {
+					commitment: mmr_commitment(
+						10 * (i as u64 + 1),
+						H256::random(),
+						validator_set,
+						keys,
+						next,
+					),
+					next_validators: next.validators().to_vec(),
+				}
+			})
+			.collect();
+		let (keys, validator_set) = &sets[2];
+		let commitment =
+			mmr_commitment(target.number + 1, target.hash(), validator_set, keys, validator_set);
+
+		BeefyWarpSyncProof {
+			handoffs,
+			target: Some(BeefyWarpSyncTarget { commitment, header: target.clone() }),
+		}
+	}
+
+	#[test]
+	fn beefy_warp_sync_proof_verify() {
+		let sets = validator_sets(0);
+		let target = header(24);
+		let proof = warp_sync_proof(&target, 0);
+
+		// Proof is verified starting at the genesis set.
+		match proof.verify(sets[0].1.clone()).unwrap() {
+			BeefyVerificationResult::Complete(validator_set, header) => {
+				assert_eq!(validator_set, sets[2].1);
+				assert_eq!(header, target);
+			},
+			BeefyVerificationResult::Partial(..) => panic!("Proof is complete."),
+		}
+
+		// Handoffs of already known sets are skipped.
+		assert!(matches!(
+			proof.verify(sets[1].1.clone()),
+			Ok(BeefyVerificationResult::Complete(validator_set, _)) if validator_set == sets[2].1
+		));
+
+		// Proof of future sets is rejected.
+		let unknown_set = BeefyValidatorSet::new(sets[0].1.validators().to_vec(), 5).unwrap();
+		assert!(proof.verify(unknown_set).is_err());
+	}
+
+	#[test]
+	fn partial_beefy_warp_sync_proof_ends_at_last_handoff() {
+		let sets = validator_sets(0);
+		let mut proof = warp_sync_proof(&header(24), 0);
+		proof.target = None;
+		let leaf = Leaf::<Block>::decode(&mut &proof.handoffs[1].commitment.leaf.0[..]).unwrap();
+
+		assert!(matches!(
+			proof.verify(sets[0].1.clone()),
+			Ok(BeefyVerificationResult::Partial(validator_set, last_hash))
+				if validator_set == sets[2].1 && last_hash == leaf.parent_number_and_hash.1
+		));
+	}
+
+	#[test]
+	fn beefy_warp_sync_proof_with_wrong_next_validators_is_rejected() {
+		let sets = validator_sets(0);
+		let mut proof = warp_sync_proof(&header(24), 0);
+		proof.handoffs[1].next_validators = sets[0].1.validators().to_vec();
+
+		assert!(matches!(proof.verify(sets[0].1.clone()), Err(Error::InvalidProof(_))));
+	}
+
+	#[test]
+	fn beefy_warp_sync_proof_with_wrong_target_header_is_rejected() {
+		let sets = validator_sets(0);
+		let mut proof = warp_sync_proof(&header(24), 0);
+		proof.target.as_mut().unwrap().header = header(24);
+
+		assert!(matches!(proof.verify(sets[0].1.clone()), Err(Error::InvalidProof(_))));
+	}
+
+	#[test]
+	fn beefy_warp_sync_proof_starts_at_activation_set() {
+		// BEEFY activated at block 7 with set 3, handed off at blocks 10 and 20.
+		let set_changes = [(3, 7u64), (4, 10), (5, 20)];
+		assert_eq!(first_set_change(&set_changes, 0, 7), Some(0));
+		assert_eq!(first_set_change(&set_changes, 7, 7), Some(0));
+		assert_eq!(first_set_change(&set_changes, 15, 7), Some(1));
+		assert_eq!(first_set_change(&set_changes, 25, 7), Some(2));
+		// The activation set is unknown if the index doesn't go back to the activation.
+		assert_eq!(first_set_change(&set_changes[1..], 7, 7), None);
+
+		// Proof is verified starting at the activation set.
+		let sets = validator_sets(3);
+		let target = header(24);
+		let proof = warp_sync_proof(&target, 3);
+		assert!(matches!(
+			proof.verify(sets[0].1.clone()),
+			Ok(BeefyVerificationResult::Complete(validator_set, header))
+				if validator_set == sets[2].1 && header == target
+		));
+	}
+}
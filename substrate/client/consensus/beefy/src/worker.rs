@@ -436,6 +436,13 @@ where
 		validator_set: ValidatorSet<AuthorityId>,
 		new_session_start: NumberFor<B>,
 	) {
+		if let Err(e) = crate::aux_schema::write_set_change::<B, _>(
+			&*self.backend,
+			validator_set.id(),
+			new_session_start,
+		) {
+			warn!(target: LOG_TARGET, "🥩 Failed to persist BEEFY set change: {:?}", e);
+		}
 		self.persisted_state.init_session_at(
 			new_session_start,
 			validator_set,
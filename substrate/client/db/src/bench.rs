@@ -134,6 +134,59 @@ impl<Hasher: Hash> BenchmarkingState<Hasher> {
 		enable_tracking: bool,
 	) -> Result<Self, String> {
 		let state_version = sp_runtime::StateVersion::default();
+		let mut state = Self::empty(record_proof, enable_tracking)?;
+		let child_delta = genesis.children_default.values().map(|child_content| {
+			(
+				&child_content.child_info,
+				child_content.data.iter().map(|(k, v)| (k.as_ref(), Some(v.as_ref()))),
+			)
+		});
+		let (root, transaction): (Hasher::Output, _) =
+			state.state.borrow().as_ref().unwrap().full_storage_root(
+				genesis.top.iter().map(|(k, v)| (k.as_ref(), Some(v.as_ref()))),
+				child_delta,
+				state_version,
+			);
+		state.genesis = transaction.clone().drain();
+		state.genesis_root = root;
+		state.commit(root, transaction, Vec::new(), Vec::new())?;
+		state.record.take();
+		Ok(state)
+	}
+
+	/// Create a new instance on top of the raw trie of a state snapshot.
+	///
+	/// The trie nodes are written to the database as they are. In contrast to [`Self::new`], the
+	/// trie therefore keeps the exact shape and depth of the chain that the snapshot was taken
+	/// from, which is what the storage reads and proof sizes of a benchmark are measured against.
+	pub fn from_raw_snapshot(
+		raw_storage: Vec<(Vec<u8>, (Vec<u8>, i32))>,
+		storage_root: Hasher::Output,
+		_cache_size_mb: Option<usize>,
+		record_proof: bool,
+		enable_tracking: bool,
+	) -> Result<Self, String> {
+		let mut state = Self::empty(record_proof, enable_tracking)?;
+		let db = state.db.take().ok_or_else(|| String::from("Database was not opened"))?;
+		let mut db_transaction = DBTransaction::new();
+		for (key, (val, rc)) in raw_storage {
+			if rc > 0 {
+				db_transaction.put(0, &key, &val);
+				state.genesis.insert(key, (val, rc));
+			}
+		}
+		db.write(db_transaction)
+			.map_err(|_| String::from("Error committing transaction"))?;
+		state.db.set(Some(db));
+
+		state.genesis_root = storage_root;
+		state.root.set(storage_root);
+		state.reopen()?;
+		Ok(state)
+	}
+
+	/// Create a new instance with an empty trie in a temporary database.
+	fn empty(record_proof: bool, enable_tracking: bool) -> Result<Self, String> {
 		let mut root = Default::default();
 		let mut mdb = MemoryDB::<Hasher>::default();
 		sp_trie::trie_types::TrieDBMutBuilderV1::<Hasher>::new(&mut mdb, &mut root).build();
@@ -160,22 +213,6 @@ impl<Hasher: Hash> BenchmarkingState<Hasher> {
 		state.add_whitelist_to_tracker();
 
 		state.reopen()?;
-		let child_delta = genesis.children_default.values().map(|child_content| {
-			(
-				&child_content.child_info,
-				child_content.data.iter().map(|(k, v)| (k.as_ref(), Some(v.as_ref()))),
-			)
-		});
-		let (root, transaction): (Hasher::Output, _) =
-			state.state.borrow().as_ref().unwrap().full_storage_root(
-				genesis.top.iter().map(|(k, v)| (k.as_ref(), Some(v.as_ref()))),
-				child_delta,
-				state_version,
-			);
-		state.genesis = transaction.clone().drain();
-		state.genesis_root = root;
-		state.commit(root, transaction, Vec::new(), Vec::new())?;
-		state.record.take();
 		Ok(state)
 	}
 
@@ -730,4 +767,45 @@ mod test {
 			bench_state.wipe().unwrap();
 		}
 	}
+
+	#[test]
+	fn raw_snapshot_keeps_trie_and_is_restored_on_wipe() {
+		let mut ext = sp_state_machine::TestExternalities::<HashingFor<crate::tests::Block>>::new(
+			sp_runtime::Storage {
+				top: vec![(b"foo".to_vec(), b"bar".to_vec()), (b"baz".to_vec(), b"qux".to_vec())]
+					.into_iter()
+					.collect(),
+				..sp_runtime::Storage::default()
+			},
+		);
+		ext.commit_all().unwrap();
+		let (raw_storage, storage_root) = ext.into_raw_snapshot();
+
+		let bench_state = BenchmarkingState::<HashingFor<crate::tests::Block>>::from_raw_snapshot(
+			raw_storage,
+			storage_root,
+			None,
+			false,
+			true,
+		)
+		.unwrap();
+
+		assert_eq!(
+			bench_state.storage_root(std::iter::empty(), Default::default()).0,
+			storage_root
+		);
+		assert_eq!(bench_state.storage(b"foo").unwrap(), Some(b"bar".to_vec()));
+		assert_eq!(bench_state.read_write_count(), (1, 0, 0, 0));
+
+		let (root, transaction) =
+			bench_state.storage_root(std::iter::once((&b"foo"[..], None)), Default::default());
+		bench_state
+			.commit(root, transaction, vec![(b"foo".to_vec(), None)], Vec::new())
+			.unwrap();
+		assert_eq!(bench_state.storage(b"foo").unwrap(), None);
+
+		bench_state.wipe().unwrap();
+		assert_eq!(bench_state.storage(b"foo").unwrap(), Some(b"bar".to_vec()));
+		assert_eq!(bench_state.storage(b"baz").unwrap(), Some(b"qux".to_vec()));
+	}
 }
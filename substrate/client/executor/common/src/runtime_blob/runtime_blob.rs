@@ -51,14 +51,22 @@ impl RuntimeBlob {
 	/// Will only accept a PolkaVM program if the `SUBSTRATE_ENABLE_POLKAVM` environment
 	/// variable is set to `1`.
 	pub fn new(raw_blob: &[u8]) -> Result<Self, WasmError> {
+		if raw_blob.starts_with(b"PVM\0") && !crate::is_polkavm_enabled() {
+			return Err(WasmError::Other("expected a WASM runtime blob, found a PolkaVM runtime blob; set the 'SUBSTRATE_ENABLE_POLKAVM' environment variable to enable the experimental PolkaVM-based executor".to_string()));
+		}
+
+		Self::new_allow_polkavm(raw_blob)
+	}
+
+	/// Create `RuntimeBlob` from the given WASM or PolkaVM program blob.
+	///
+	/// Unlike [`Self::new`], accepts PolkaVM programs regardless of the environment. For callers
+	/// which decide themselves whether PolkaVM programs are allowed.
+	pub fn new_allow_polkavm(raw_blob: &[u8]) -> Result<Self, WasmError> {
 		if raw_blob.starts_with(b"PVM\0") {
-			if crate::is_polkavm_enabled() {
-				let raw = ArcBytes::from(raw_blob);
-				let blob = polkavm::ProgramBlob::parse(raw.clone())?;
-				return Ok(Self(BlobKind::PolkaVM((blob, raw))));
-			} else {
-				return Err(WasmError::Other("expected a WASM runtime blob, found a PolkaVM runtime blob; set the 'SUBSTRATE_ENABLE_POLKAVM' environment variable to enable the experimental PolkaVM-based executor".to_string()));
-			}
+			let raw = ArcBytes::from(raw_blob);
+			let blob = polkavm::ProgramBlob::parse(raw.clone())?;
+			return Ok(Self(BlobKind::PolkaVM((blob, raw))));
 		}
 
 		let raw_module: Module = deserialize_buffer(raw_blob)
@@ -25,15 +25,29 @@ use sp_wasm_interface::{
 	Function, FunctionContext, HostFunctions, Pointer, Value, ValueType, WordSize,
 };
 
-#[repr(transparent)]
-pub struct InstancePre(polkavm::InstancePre<(), String>);
+/// The state of a single call into the runtime.
+#[derive(Default)]
+struct CallState {
+	/// The heap may not grow beyond this address, if set.
+	heap_end: Option<u32>,
+}
+
+pub struct InstancePre {
+	instance_pre: polkavm::InstancePre<CallState, String>,
+	max_heap_size: Option<u32>,
+}
 
-#[repr(transparent)]
-pub struct Instance(polkavm::Instance<(), String>);
+pub struct Instance {
+	instance: polkavm::Instance<CallState, String>,
+	max_heap_size: Option<u32>,
+}
 
 impl WasmModule for InstancePre {
 	fn new_instance(&self) -> Result<Box<dyn WasmInstance>, Error> {
-		Ok(Box::new(Instance(self.0.instantiate()?)))
+		Ok(Box::new(Instance {
+			instance: self.instance_pre.instantiate()?,
+			max_heap_size: self.max_heap_size,
+		}))
 	}
 }
 
@@ -43,7 +57,7 @@ impl WasmInstance for Instance {
 		name: &str,
 		raw_data: &[u8],
 	) -> (Result<Vec<u8>, Error>, Option<AllocationStats>) {
-		let pc = match self.0.module().exports().find(|e| e.symbol() == name) {
+		let pc = match self.instance.module().exports().find(|e| e.symbol() == name) {
 			Some(export) => export.program_counter(),
 			None =>
 				return (
@@ -62,7 +76,7 @@ impl WasmInstance for Instance {
 		// TODO: This will leak guest memory; find a better solution.
 
 		// Make sure that the memory is cleared...
-		if let Err(err) = self.0.reset_memory() {
+		if let Err(err) = self.instance.reset_memory() {
 			return (
 				Err(format!(
 					"call into the runtime method '{name}' failed: reset memory failed: {err}"
@@ -72,8 +86,21 @@ impl WasmInstance for Instance {
 			);
 		}
 
+		if self
+			.max_heap_size
+			.map_or(false, |max_heap_size| raw_data_length > max_heap_size)
+		{
+			return (
+				Err(format!(
+					"cannot call runtime method '{name}': input payload exceeds the heap limit"
+				)
+				.into()),
+				None,
+			);
+		}
+
 		// ... and allocate space for the input payload.
-		if let Err(err) = self.0.sbrk(raw_data_length) {
+		if let Err(err) = self.instance.sbrk(raw_data_length) {
 			return (
 				Err(format!(
 					"call into the runtime method '{name}' failed: reset memory failed: {err}"
@@ -85,13 +112,18 @@ impl WasmInstance for Instance {
 
 		// Grab the address of where the guest's heap starts; that's where we've just allocated
 		// the memory for the input payload.
-		let data_pointer = self.0.module().memory_map().heap_base();
+		let data_pointer = self.instance.module().memory_map().heap_base();
 
-		if let Err(err) = self.0.write_memory(data_pointer, raw_data) {
+		if let Err(err) = self.instance.write_memory(data_pointer, raw_data) {
 			return (Err(format!("call into the runtime method '{name}': failed to write the input payload into guest memory: {err}").into()), None);
 		}
 
-		match self.0.call_typed(&mut (), pc, (data_pointer, raw_data_length)) {
+		let mut state = CallState {
+			heap_end: self
+				.max_heap_size
+				.map(|max_heap_size| data_pointer.saturating_add(max_heap_size)),
+		};
+		match self.instance.call_typed(&mut state, pc, (data_pointer, raw_data_length)) {
 			Ok(()) => {},
 			Err(CallError::Trap) =>
 				return (
@@ -112,9 +144,9 @@ impl WasmInstance for Instance {
 			Err(CallError::Step) => unreachable!("stepping is never enabled"),
 		};
 
-		let result_pointer = self.0.reg(Reg::A0);
-		let result_length = self.0.reg(Reg::A1);
-		let output = match self.0.read_memory(result_pointer as u32, result_length as u32) {
+		let result_pointer = self.instance.reg(Reg::A0);
+		let result_length = self.instance.reg(Reg::A1);
+		let output = match self.instance.read_memory(result_pointer as u32, result_length as u32) {
 			Ok(output) => output,
 			Err(error) => {
 				return (Err(format!("call into the runtime method '{name}' failed: failed to read the return payload: {error}").into()), None)
@@ -125,7 +157,7 @@ impl WasmInstance for Instance {
 	}
 }
 
-struct Context<'r, 'a>(&'r mut polkavm::Caller<'a, ()>);
+struct Context<'r, 'a>(&'r mut polkavm::Caller<'a, CallState>);
 
 impl<'r, 'a> FunctionContext for Context<'r, 'a> {
 	fn read_memory_into(
@@ -153,6 +185,12 @@ impl<'r, 'a> FunctionContext for Context<'r, 'a> {
 			Err(err) => return Err(format!("sbrk failed: {err}")),
 		};
 
+		if let Some(heap_end) = self.0.user_data.heap_end {
+			if pointer.checked_add(size).map_or(true, |end| end > heap_end) {
+				return Err(String::from("allocation error: heap limit exceeded"))
+			}
+		}
+
 		// TODO: This will leak guest memory; find a better solution.
 		match self.0.instance.sbrk(size) {
 			Ok(Some(_)) => (),
@@ -173,7 +211,10 @@ impl<'r, 'a> FunctionContext for Context<'r, 'a> {
 	}
 }
 
-fn call_host_function(caller: &mut Caller<()>, function: &dyn Function) -> Result<(), String> {
+fn call_host_function(
+	caller: &mut Caller<CallState>,
+	function: &dyn Function,
+) -> Result<(), String> {
 	let mut args = [Value::I64(0); Reg::ARG_REGS.len()];
 	let mut nth_reg = 0;
 	for (nth_arg, kind) in function.signature().args.iter().enumerate() {
@@ -279,16 +320,43 @@ where
 		},
 	};
 
+	instantiate_pre::<H>(engine, blob, None)
+}
+
+/// Create a runtime with its own engine, configured by `config` rather than the environment.
+///
+/// Allocations of the runtime through the host fail once they would grow the heap beyond
+/// `max_heap_size` bytes, the input payload of a call included.
+pub fn create_runtime_with_config<H>(
+	blob: &polkavm::ProgramBlob,
+	config: &polkavm::Config,
+	max_heap_size: Option<u32>,
+) -> Result<Box<dyn WasmModule>, WasmError>
+where
+	H: HostFunctions,
+{
+	let engine = polkavm::Engine::new(config)?;
+	instantiate_pre::<H>(&engine, blob, max_heap_size)
+}
+
+fn instantiate_pre<H>(
+	engine: &polkavm::Engine,
+	blob: &polkavm::ProgramBlob,
+	max_heap_size: Option<u32>,
+) -> Result<Box<dyn WasmModule>, WasmError>
+where
+	H: HostFunctions,
+{
 	let module =
-		polkavm::Module::from_blob(&engine, &polkavm::ModuleConfig::default(), blob.clone())?;
+		polkavm::Module::from_blob(engine, &polkavm::ModuleConfig::default(), blob.clone())?;
 
 	let mut linker = polkavm::Linker::new();
 
 	for function in H::host_functions() {
-		linker.define_untyped(function.name(), |mut caller: Caller<()>| {
+		linker.define_untyped(function.name(), |mut caller: Caller<CallState>| {
 			call_host_function(&mut caller, function)
 		})?;
 	}
 	let instance_pre = linker.instantiate_pre(&module)?;
-	Ok(Box::new(InstancePre(instance_pre)))
+	Ok(Box::new(InstancePre { instance_pre, max_heap_size }))
 }
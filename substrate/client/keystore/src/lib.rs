@@ -28,6 +28,12 @@ mod local;
 pub use local::LocalKeystore;
 pub use sp_keystore::Keystore;
 
+/// Remote keystore implementation
+#[cfg(unix)]
+pub mod remote;
+#[cfg(unix)]
+pub use remote::RemoteKeystore;
+
 /// Keystore error.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -55,6 +61,9 @@ pub enum Error {
 	/// Keystore unavailable
 	#[error("Keystore unavailable")]
 	Unavailable,
+	/// Invalid shared secret of a remote signer
+	#[error("Invalid remote signer secret, expected 32 hex encoded bytes")]
+	InvalidSecret,
 }
 
 /// Keystore Result
@@ -64,8 +73,10 @@ impl From<Error> for TraitError {
 	fn from(error: Error) -> Self {
 		match error {
 			Error::KeyNotSupported(id) => TraitError::KeyNotSupported(id),
-			Error::InvalidSeed | Error::InvalidPhrase | Error::PublicKeyMismatch =>
-				TraitError::ValidationError(error.to_string()),
+			Error::InvalidSeed |
+			Error::InvalidPhrase |
+			Error::PublicKeyMismatch |
+			Error::InvalidSecret => TraitError::ValidationError(error.to_string()),
 			Error::Unavailable => TraitError::Unavailable,
 			Error::Io(e) => TraitError::Other(e.to_string()),
 			Error::Json(e) => TraitError::Other(e.to_string()),
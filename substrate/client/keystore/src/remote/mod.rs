@@ -0,0 +1,670 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A keystore whose keys are held by a separate signer process.
+//!
+//! The [`RemoteKeystore`] forwards all operations over a Unix socket to a [`Signer`], e.g. the
+//! `remote-signer` binary, which holds the secret keys in a [`LocalKeystore`](crate::LocalKeystore)
+//! of its own. Both sides authenticate each other with a shared secret when connecting, see
+//! [`protocol`].
+//!
+//! The signer protects its keys against equivocation with [`SlashingProtection`].
+//!
+//! Only available on Unix.
+
+pub mod protocol;
+mod signer;
+mod slashing;
+
+pub use signer::Signer;
+pub use slashing::SlashingProtection;
+
+use crate::{Error, Result};
+use codec::Decode;
+use parking_lot::Mutex;
+use protocol::{RemoteError, Request, Response, SECRET_LEN};
+use sp_core::{
+	crypto::{ByteArray, CryptoTypeId, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{Error as TraitError, Keystore};
+use std::{
+	fs,
+	os::unix::net::UnixStream,
+	path::{Path, PathBuf},
+	time::Duration,
+};
+
+sp_keystore::bandersnatch_experimental_enabled! {
+use sp_core::bandersnatch;
+}
+
+sp_keystore::bls_experimental_enabled! {
+use sp_core::{bls381, ecdsa_bls381};
+}
+
+const LOG_TARGET: &str = "remote-signer";
+
+/// How long to wait for a response of the signer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Read a shared secret from `path`, as 32 hex encoded bytes.
+pub fn read_secret_file(path: &Path) -> Result<[u8; SECRET_LEN]> {
+	let secret = fs::read_to_string(path)?;
+	array_bytes::hex2array(secret.trim()).map_err(|_| Error::InvalidSecret)
+}
+
+/// A [`Keystore`] forwarding all operations to a remote [`Signer`].
+pub struct RemoteKeystore {
+	socket: PathBuf,
+	secret: [u8; SECRET_LEN],
+	connection: Mutex<Option<UnixStream>>,
+}
+
+impl RemoteKeystore {
+	/// Connect to the signer listening at `socket`, authenticating with the shared `secret`.
+	///
+	/// Fails if the signer cannot be reached. Later connection failures are retried on the next
+	/// request.
+	pub fn open<T: Into<PathBuf>>(socket: T, secret: [u8; SECRET_LEN]) -> Result<Self> {
+		let keystore = Self { socket: socket.into(), secret, connection: Mutex::new(None) };
+		*keystore.connection.lock() = Some(keystore.connect()?);
+		Ok(keystore)
+	}
+
+	/// Open a new authenticated connection to the signer.
+	fn connect(&self) -> std::io::Result<UnixStream> {
+		let mut stream = UnixStream::connect(&self.socket)?;
+		stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+		stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+		protocol::authenticate_signer(&mut stream, &self.secret)?;
+		Ok(stream)
+	}
+
+	/// Send `request` to the signer, reconnecting once if the connection was lost.
+	///
+	/// The idle connection is taken out of the lock for the round trip, concurrent requests open
+	/// their own connection instead of waiting for the signer to answer this one.
+	fn request(&self, request: Request) -> std::result::Result<Response, TraitError> {
+		let exchange = |stream: Option<UnixStream>| -> std::io::Result<(UnixStream, Response)> {
+			let mut stream = match stream {
+				Some(stream) => stream,
+				None => self.connect()?,
+			};
+			protocol::write_message(&mut stream, &request)?;
+			let response = protocol::read_message(&mut stream)?;
+			Ok((stream, response))
+		};
+
+		let idle = self.connection.lock().take();
+		let result = exchange(idle).or_else(|e| {
+			log::debug!(target: LOG_TARGET, "Request failed, reconnecting: {e}");
+			exchange(None)
+		});
+		match result {
+			Ok((stream, response)) => {
+				self.connection.lock().get_or_insert(stream);
+				match response {
+					Response::Error(e) => Err(e.into()),
+					response => Ok(response),
+				}
+			},
+			Err(e) => {
+				log::warn!(target: LOG_TARGET, "Remote signer at {} is unavailable: {e}", self.socket.display());
+				Err(TraitError::Unavailable)
+			},
+		}
+	}
+
+	fn public_keys<P: ByteArray>(&self, key_type: KeyTypeId, crypto_id: CryptoTypeId) -> Vec<P> {
+		match self.request(Request::PublicKeys { key_type, crypto_id }) {
+			Ok(Response::PublicKeys(keys)) =>
+				keys.iter().filter_map(|key| P::from_slice(key).ok()).collect(),
+			Ok(_) => {
+				log::warn!(target: LOG_TARGET, "Unexpected response of the remote signer");
+				Vec::new()
+			},
+			Err(_) => Vec::new(),
+		}
+	}
+
+	fn generate_new<P: ByteArray>(
+		&self,
+		key_type: KeyTypeId,
+		crypto_id: CryptoTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<P, TraitError> {
+		let seed = seed.map(Into::into);
+		match self.request(Request::GenerateNew { key_type, crypto_id, seed })? {
+			Response::Public(public) => P::from_slice(&public).map_err(|_| unexpected_response()),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	/// Decode the SCALE encoded signature of a `Signature` response to `request`.
+	fn signature<S: Decode>(&self, request: Request) -> std::result::Result<Option<S>, TraitError> {
+		match self.request(request)? {
+			Response::Signature(signature) => signature
+				.map(|signature| S::decode(&mut &signature[..]).map_err(|_| unexpected_response()))
+				.transpose(),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	fn sign<S: Decode>(
+		&self,
+		key_type: KeyTypeId,
+		crypto_id: CryptoTypeId,
+		public: &[u8],
+		msg: &[u8],
+	) -> std::result::Result<Option<S>, TraitError> {
+		self.signature(Request::Sign {
+			key_type,
+			crypto_id,
+			public: public.to_vec(),
+			msg: msg.to_vec(),
+		})
+	}
+}
+
+fn unexpected_response() -> TraitError {
+	TraitError::Other("Unexpected response of the remote signer".into())
+}
+
+sp_keystore::bandersnatch_experimental_enabled! {
+fn not_supported() -> TraitError {
+	TraitError::Other("Not supported by the remote signer".into())
+}
+}
+
+impl Keystore for RemoteKeystore {
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys(key_type, sr25519::CRYPTO_ID)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		self.generate_new(key_type, sr25519::CRYPTO_ID, seed)
+	}
+
+	fn sr25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<sr25519::Signature>, TraitError> {
+		self.sign(key_type, sr25519::CRYPTO_ID, public.as_slice(), msg)
+	}
+
+	fn sr25519_sign_with_blake2_256(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<sr25519::Signature>, TraitError> {
+		self.signature(Request::Sr25519SignWithBlake2_256 {
+			key_type,
+			public: public.to_raw_vec(),
+			msg: msg.to_vec(),
+		})
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		data: &sr25519::vrf::VrfSignData,
+	) -> std::result::Result<Option<sr25519::vrf::VrfSignature>, TraitError> {
+		let request = Request::Sr25519VrfSign {
+			key_type,
+			public: public.to_raw_vec(),
+			data: data.try_into()?,
+		};
+		match self.request(request)? {
+			Response::VrfSignature(signature) => Ok(signature),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	fn sr25519_vrf_pre_output(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		input: &sr25519::vrf::VrfInput,
+	) -> std::result::Result<Option<sr25519::vrf::VrfPreOutput>, TraitError> {
+		let request = Request::Sr25519VrfPreOutput {
+			key_type,
+			public: public.to_raw_vec(),
+			input: input.try_into()?,
+		};
+		match self.request(request)? {
+			Response::VrfPreOutput(pre_output) => Ok(pre_output),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys(key_type, ed25519::CRYPTO_ID)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		self.generate_new(key_type, ed25519::CRYPTO_ID, seed)
+	}
+
+	fn ed25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ed25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ed25519::Signature>, TraitError> {
+		self.sign(key_type, ed25519::CRYPTO_ID, public.as_slice(), msg)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys(key_type, ecdsa::CRYPTO_ID)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		self.generate_new(key_type, ecdsa::CRYPTO_ID, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.sign(key_type, ecdsa::CRYPTO_ID, public.as_slice(), msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.signature(Request::EcdsaSignPrehashed {
+			key_type,
+			public: public.to_raw_vec(),
+			msg: *msg,
+		})
+	}
+
+	fn ecdsa_sign_with_keccak256(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.signature(Request::EcdsaSignWithKeccak256 {
+			key_type,
+			public: public.to_raw_vec(),
+			msg: msg.to_vec(),
+		})
+	}
+
+	sp_keystore::bandersnatch_experimental_enabled! {
+		fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public> {
+			self.public_keys(key_type, bandersnatch::CRYPTO_ID)
+		}
+
+		fn bandersnatch_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bandersnatch::Public, TraitError> {
+			self.generate_new(key_type, bandersnatch::CRYPTO_ID, seed)
+		}
+
+		fn bandersnatch_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bandersnatch::Signature>, TraitError> {
+			self.sign(key_type, bandersnatch::CRYPTO_ID, public.as_slice(), msg)
+		}
+
+		fn bandersnatch_vrf_sign(
+			&self,
+			_: KeyTypeId,
+			_: &bandersnatch::Public,
+			_: &bandersnatch::vrf::VrfSignData,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfSignature>, TraitError> {
+			Err(not_supported())
+		}
+
+		fn bandersnatch_vrf_pre_output(
+			&self,
+			_: KeyTypeId,
+			_: &bandersnatch::Public,
+			_: &bandersnatch::vrf::VrfInput,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfPreOutput>, TraitError> {
+			Err(not_supported())
+		}
+
+		fn bandersnatch_ring_vrf_sign(
+			&self,
+			_: KeyTypeId,
+			_: &bandersnatch::Public,
+			_: &bandersnatch::vrf::VrfSignData,
+			_: &bandersnatch::ring_vrf::RingProver,
+		) -> std::result::Result<Option<bandersnatch::ring_vrf::RingVrfSignature>, TraitError> {
+			Err(not_supported())
+		}
+	}
+
+	sp_keystore::bls_experimental_enabled! {
+		fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
+			self.public_keys(key_type, bls381::CRYPTO_ID)
+		}
+
+		fn ecdsa_bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa_bls381::Public> {
+			self.public_keys(key_type, ecdsa_bls381::CRYPTO_ID)
+		}
+
+		fn bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bls381::Public, TraitError> {
+			self.generate_new(key_type, bls381::CRYPTO_ID, seed)
+		}
+
+		fn ecdsa_bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<ecdsa_bls381::Public, TraitError> {
+			self.generate_new(key_type, ecdsa_bls381::CRYPTO_ID, seed)
+		}
+
+		fn bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bls381::Signature>, TraitError> {
+			self.sign(key_type, bls381::CRYPTO_ID, public.as_slice(), msg)
+		}
+
+		fn bls381_generate_proof_of_possession(
+			&self,
+			key_type: KeyTypeId,
+			public: &bls381::Public,
+		) -> std::result::Result<Option<bls381::Signature>, TraitError> {
+			self.signature(Request::Bls381ProofOfPossession { key_type, public: public.to_raw_vec() })
+		}
+
+		fn ecdsa_bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls381::Signature>, TraitError> {
+			self.sign(key_type, ecdsa_bls381::CRYPTO_ID, public.as_slice(), msg)
+		}
+
+		fn ecdsa_bls381_sign_with_keccak256(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls381::Signature>, TraitError> {
+			self.signature(Request::EcdsaBls381SignWithKeccak256 {
+				key_type,
+				public: public.to_raw_vec(),
+				msg: msg.to_vec(),
+			})
+		}
+	}
+
+	fn insert(
+		&self,
+		key_type: KeyTypeId,
+		suri: &str,
+		public: &[u8],
+	) -> std::result::Result<(), ()> {
+		let request = Request::Insert { key_type, suri: suri.into(), public: public.to_vec() };
+		match self.request(request) {
+			Ok(Response::Done) => Ok(()),
+			_ => Err(()),
+		}
+	}
+
+	fn keys(&self, key_type: KeyTypeId) -> std::result::Result<Vec<Vec<u8>>, TraitError> {
+		match self.request(Request::Keys { key_type })? {
+			Response::PublicKeys(keys) => Ok(keys),
+			_ => Err(unexpected_response()),
+		}
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		matches!(self.request(Request::HasKeys(public_keys.to_vec())), Ok(Response::Bool(true)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::LocalKeystore;
+	use codec::Encode;
+	use sp_core::{crypto::key_types, Pair};
+	use std::{os::unix::net::UnixListener, sync::Arc, thread};
+	use tempfile::TempDir;
+
+	const SECRET: [u8; SECRET_LEN] = [7; SECRET_LEN];
+
+	fn spawn_signer(dir: &TempDir, allow_insert: bool) -> (Arc<LocalKeystore>, PathBuf) {
+		let keystore = Arc::new(LocalKeystore::in_memory());
+		let signer = Arc::new(
+			Signer::new(keystore.clone(), SECRET, SlashingProtection::in_memory())
+				.with_allow_insert(allow_insert),
+		);
+		let socket = dir.path().join("signer.sock");
+		let listener = UnixListener::bind(&socket).unwrap();
+		thread::spawn(move || signer.serve(listener));
+		(keystore, socket)
+	}
+
+	fn grandpa_vote(kind: u8, target: u8, round: u64, set_id: u64) -> Vec<u8> {
+		(kind, [target; 32], 1u32, round, set_id).encode()
+	}
+
+	#[test]
+	fn sign_with_remote_keys() {
+		let dir = TempDir::new().unwrap();
+		let (local, socket) = spawn_signer(&dir, false);
+		let remote = RemoteKeystore::open(socket, SECRET).unwrap();
+
+		let public = remote.sr25519_generate_new(key_types::AUTHORITY_DISCOVERY, None).unwrap();
+		assert_eq!(local.sr25519_public_keys(key_types::AUTHORITY_DISCOVERY), vec![public]);
+		assert_eq!(remote.sr25519_public_keys(key_types::AUTHORITY_DISCOVERY), vec![public]);
+		assert!(remote.has_keys(&[(public.to_raw_vec(), key_types::AUTHORITY_DISCOVERY)]));
+
+		let msg = b"Hello remote signer";
+		let signature = remote
+			.sr25519_sign(key_types::AUTHORITY_DISCOVERY, &public, msg)
+			.unwrap()
+			.unwrap();
+		assert!(sr25519::Pair::verify(&signature, msg, &public));
+
+		let unknown = sr25519::Pair::generate().0.public();
+		assert_eq!(
+			remote.sr25519_sign(key_types::AUTHORITY_DISCOVERY, &unknown, msg).unwrap(),
+			None
+		);
+	}
+
+	#[test]
+	fn sr25519_vrf_sign_with_remote_keys() {
+		let dir = TempDir::new().unwrap();
+		let (_, socket) = spawn_signer(&dir, false);
+		let remote = RemoteKeystore::open(socket, SECRET).unwrap();
+		let public = remote.sr25519_generate_new(key_types::BABE, None).unwrap();
+
+		let input = sr25519::vrf::VrfTranscript::new(b"label", &[(b"domain", b"data")]);
+		let extra = sr25519::vrf::VrfTranscript::new(b"extra", &[(b"domain", b"more data")]);
+		let data = sr25519::vrf::VrfSignData::new(input.clone()).with_extra(extra);
+
+		let signature = remote.sr25519_vrf_sign(key_types::BABE, &public, &data).unwrap().unwrap();
+		assert!(sp_core::crypto::VrfPublic::vrf_verify(&public, &data, &signature));
+
+		let pre_output = remote
+			.sr25519_vrf_pre_output(key_types::BABE, &public, &input)
+			.unwrap()
+			.unwrap();
+		assert_eq!(pre_output, signature.pre_output);
+	}
+
+	#[test]
+	fn grandpa_equivocation_is_refused() {
+		let dir = TempDir::new().unwrap();
+		let (_, socket) = spawn_signer(&dir, false);
+		let remote = RemoteKeystore::open(socket, SECRET).unwrap();
+		let public = remote.ed25519_generate_new(key_types::GRANDPA, None).unwrap();
+
+		let prevote = grandpa_vote(0, 1, 10, 0);
+		assert!(remote.ed25519_sign(key_types::GRANDPA, &public, &prevote).unwrap().is_some());
+		// signing the same vote again is fine.
+		assert!(remote.ed25519_sign(key_types::GRANDPA, &public, &prevote).unwrap().is_some());
+		assert!(matches!(
+			remote.ed25519_sign(key_types::GRANDPA, &public, &grandpa_vote(0, 2, 10, 0)),
+			Err(TraitError::Other(_))
+		));
+		// a precommit in the same round is not an equivocation.
+		let precommit = grandpa_vote(1, 1, 10, 0);
+		assert!(remote.ed25519_sign(key_types::GRANDPA, &public, &precommit).unwrap().is_some());
+	}
+
+	#[test]
+	fn beefy_equivocation_is_refused() {
+		let dir = TempDir::new().unwrap();
+		let (_, socket) = spawn_signer(&dir, false);
+		let remote = RemoteKeystore::open(socket, SECRET).unwrap();
+		let public = remote.ecdsa_generate_new(key_types::BEEFY, None).unwrap();
+		let commitment =
+			|root: u8, number: u32| (vec![(*b"mh", [root; 32].encode())], number, 0u64).encode();
+
+		let vote = commitment(1, 10);
+		let signature = remote
+			.ecdsa_sign_with_keccak256(key_types::BEEFY, &public, &vote)
+			.unwrap()
+			.unwrap();
+		assert_eq!(
+			signature.recover_prehashed(&sp_crypto_hashing::keccak_256(&vote)),
+			Some(public)
+		);
+		assert!(matches!(
+			remote.ecdsa_sign_with_keccak256(key_types::BEEFY, &public, &commitment(2, 10)),
+			Err(TraitError::Other(_))
+		));
+		// the message of pre-hashed votes cannot be checked.
+		assert!(matches!(
+			remote.ecdsa_sign_prehashed(key_types::BEEFY, &public, &[0; 32]),
+			Err(TraitError::Other(_))
+		));
+		assert!(remote
+			.ecdsa_sign_with_keccak256(key_types::BEEFY, &public, &commitment(2, 11))
+			.unwrap()
+			.is_some());
+	}
+
+	#[test]
+	fn babe_equivocation_is_refused() {
+		let dir = TempDir::new().unwrap();
+		let (_, socket) = spawn_signer(&dir, false);
+		let remote = RemoteKeystore::open(socket, SECRET).unwrap();
+		let public = remote.sr25519_generate_new(key_types::BABE, None).unwrap();
+		// a header with a plain secondary BABE pre-digest, which is claimed without a VRF.
+		let header = |slot: u64, state_root: u8| {
+			let pre_digest = (2u8, 0u32, slot).encode();
+			let digest = vec![(6u8, *b"BABE", pre_digest)];
+			([0u8; 32], codec::Compact(1u32), [state_root; 32], [0u8; 32], digest).encode()
+		};
+
+		let signature = remote
+			.sr25519_sign_with_blake2_256(key_types::BABE, &public, &header(5, 1))
+			.unwrap()
+			.unwrap();
+		assert!(sr25519::Pair::verify(
+			&signature,
+			sp_crypto_hashing::blake2_256(&header(5, 1)),
+			&public
+		));
+		assert!(matches!(
+			remote.sr25519_sign_with_blake2_256(key_types::BABE, &public, &header(5, 2)),
+			Err(TraitError::Other(_))
+		));
+		// the slot of header hashes cannot be checked.
+		assert!(matches!(
+			remote.sr25519_sign(key_types::BABE, &public, &[2; 32]),
+			Err(TraitError::Other(_))
+		));
+		assert!(remote
+			.sr25519_sign_with_blake2_256(key_types::BABE, &public, &header(6, 2))
+			.unwrap()
+			.is_some());
+	}
+
+	#[test]
+	fn wrong_secret_is_rejected() {
+		let dir = TempDir::new().unwrap();
+		let (_, socket) = spawn_signer(&dir, false);
+		assert!(RemoteKeystore::open(socket, [8; SECRET_LEN]).is_err());
+	}
+
+	#[test]
+	fn insert_is_refused_unless_allowed() {
+		let suri = "//Alice";
+		let public = sr25519::Pair::from_string(suri, None).unwrap().public();
+
+		let dir = TempDir::new().unwrap();
+		let (_, socket) = spawn_signer(&dir, false);
+		let remote = RemoteKeystore::open(socket, SECRET).unwrap();
+		assert!(remote.insert(key_types::BABE, suri, public.as_slice()).is_err());
+
+		let dir = TempDir::new().unwrap();
+		let (local, socket) = spawn_signer(&dir, true);
+		let remote = RemoteKeystore::open(socket, SECRET).unwrap();
+		assert!(remote.insert(key_types::BABE, suri, public.as_slice()).is_ok());
+		assert_eq!(local.sr25519_public_keys(key_types::BABE), vec![public]);
+	}
+
+	#[test]
+	fn read_secret_file_works() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("secret");
+		fs::write(&path, format!("0x{}\n", "07".repeat(SECRET_LEN))).unwrap();
+		assert_eq!(read_secret_file(&path).unwrap(), SECRET);
+
+		fs::write(&path, "0x0707").unwrap();
+		assert!(matches!(read_secret_file(&path), Err(Error::InvalidSecret)));
+	}
+}
@@ -0,0 +1,336 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The wire protocol between the [`RemoteKeystore`](super::RemoteKeystore) and the
+//! [`Signer`](super::Signer).
+//!
+//! Every connection starts with a mutual challenge-response authentication based on a shared
+//! secret, after which the keystore sends [`Request`]s and the signer answers each of them with a
+//! [`Response`]. Messages are SCALE encoded and prefixed by their length as a little endian `u32`.
+
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sp_core::{
+	crypto::{CryptoTypeId, KeyTypeId},
+	sr25519::vrf::{VrfPreOutput, VrfSignData, VrfSignature, VrfTranscript, VrfTranscriptParts},
+};
+use std::{
+	collections::BTreeSet,
+	io::{self, Read, Write},
+	sync::OnceLock,
+};
+
+/// The maximum length of a single message.
+const MAX_MESSAGE_LEN: u32 = 16 * 1024 * 1024;
+
+/// The length of the shared secret, and of the nonces and MACs of the handshake.
+pub const SECRET_LEN: usize = 32;
+
+/// The maximum length of a VRF transcript label or domain.
+const MAX_LABEL_LEN: usize = 64;
+
+/// The maximum number of distinct VRF transcript labels and domains the signer accepts.
+const MAX_LABELS: usize = 256;
+
+/// A request of the keystore to the signer.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum Request {
+	/// All public keys of the given key and crypto type.
+	#[codec(index = 0)]
+	PublicKeys { key_type: KeyTypeId, crypto_id: CryptoTypeId },
+	/// Generate a new key pair of the given key and crypto type.
+	#[codec(index = 1)]
+	GenerateNew { key_type: KeyTypeId, crypto_id: CryptoTypeId, seed: Option<String> },
+	/// Sign `msg` with the given key, see [`sp_keystore::Keystore::sign_with`].
+	#[codec(index = 2)]
+	Sign { key_type: KeyTypeId, crypto_id: CryptoTypeId, public: Vec<u8>, msg: Vec<u8> },
+	/// Sign the pre-hashed `msg` with the given ecdsa key.
+	#[codec(index = 3)]
+	EcdsaSignPrehashed { key_type: KeyTypeId, public: Vec<u8>, msg: [u8; 32] },
+	/// Generate an sr25519 VRF signature.
+	#[codec(index = 4)]
+	Sr25519VrfSign { key_type: KeyTypeId, public: Vec<u8>, data: SignData },
+	/// Generate an sr25519 VRF pre-output.
+	#[codec(index = 5)]
+	Sr25519VrfPreOutput { key_type: KeyTypeId, public: Vec<u8>, input: Transcript },
+	/// Generate a bls381 proof of possession.
+	#[codec(index = 6)]
+	Bls381ProofOfPossession { key_type: KeyTypeId, public: Vec<u8> },
+	/// Sign the keccak256 hash of `msg` with the given (ecdsa,bls381) key.
+	#[codec(index = 7)]
+	EcdsaBls381SignWithKeccak256 { key_type: KeyTypeId, public: Vec<u8>, msg: Vec<u8> },
+	/// Insert a secret key.
+	#[codec(index = 8)]
+	Insert { key_type: KeyTypeId, suri: String, public: Vec<u8> },
+	/// All public keys of the given key type, of any crypto type.
+	#[codec(index = 9)]
+	Keys { key_type: KeyTypeId },
+	/// Whether all the given keys are known.
+	#[codec(index = 10)]
+	HasKeys(Vec<(Vec<u8>, KeyTypeId)>),
+	/// Sign the keccak256 hash of `msg` with the given ecdsa key.
+	#[codec(index = 11)]
+	EcdsaSignWithKeccak256 { key_type: KeyTypeId, public: Vec<u8>, msg: Vec<u8> },
+	/// Sign the blake2_256 hash of `msg` with the given sr25519 key.
+	#[codec(index = 12)]
+	Sr25519SignWithBlake2_256 { key_type: KeyTypeId, public: Vec<u8>, msg: Vec<u8> },
+}
+
+/// The response of the signer to a [`Request`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum Response {
+	/// A list of raw public keys.
+	#[codec(index = 0)]
+	PublicKeys(Vec<Vec<u8>>),
+	/// A raw public key.
+	#[codec(index = 1)]
+	Public(Vec<u8>),
+	/// A SCALE encoded signature, `None` if the key is unknown.
+	#[codec(index = 2)]
+	Signature(Option<Vec<u8>>),
+	/// An sr25519 VRF signature, `None` if the key is unknown.
+	#[codec(index = 3)]
+	VrfSignature(Option<VrfSignature>),
+	/// An sr25519 VRF pre-output, `None` if the key is unknown.
+	#[codec(index = 4)]
+	VrfPreOutput(Option<VrfPreOutput>),
+	/// A boolean answer.
+	#[codec(index = 5)]
+	Bool(bool),
+	/// The request succeeded without a result.
+	#[codec(index = 6)]
+	Done,
+	/// The request failed.
+	#[codec(index = 7)]
+	Error(RemoteError),
+}
+
+/// An error of the signer.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum RemoteError {
+	/// The key type is not supported.
+	#[codec(index = 0)]
+	KeyNotSupported(KeyTypeId),
+	/// The request is invalid.
+	#[codec(index = 1)]
+	Validation(String),
+	/// The keystore of the signer is unavailable.
+	#[codec(index = 2)]
+	Unavailable,
+	/// Signing was refused by the slashing protection of the signer.
+	#[codec(index = 3)]
+	Refused(String),
+	/// The request is not allowed by the signer.
+	#[codec(index = 4)]
+	NotAllowed,
+	/// Any other error.
+	#[codec(index = 5)]
+	Other(String),
+}
+
+impl From<sp_keystore::Error> for RemoteError {
+	fn from(error: sp_keystore::Error) -> Self {
+		match error {
+			sp_keystore::Error::KeyNotSupported(id) => Self::KeyNotSupported(id),
+			sp_keystore::Error::ValidationError(e) => Self::Validation(e),
+			sp_keystore::Error::Unavailable => Self::Unavailable,
+			sp_keystore::Error::Other(e) => Self::Other(e),
+		}
+	}
+}
+
+impl From<RemoteError> for sp_keystore::Error {
+	fn from(error: RemoteError) -> Self {
+		match error {
+			RemoteError::KeyNotSupported(id) => Self::KeyNotSupported(id),
+			RemoteError::Validation(e) => Self::ValidationError(e),
+			RemoteError::Unavailable => Self::Unavailable,
+			RemoteError::Refused(e) => Self::Other(format!("Refused by the remote signer: {e}")),
+			RemoteError::NotAllowed => Self::Other("Not allowed by the remote signer".into()),
+			RemoteError::Other(e) => Self::Other(e),
+		}
+	}
+}
+
+/// A VRF transcript, as the label and the `(domain, message)` elements it was built from.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Transcript {
+	label: Vec<u8>,
+	data: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl TryFrom<&VrfTranscript> for Transcript {
+	type Error = RemoteError;
+
+	fn try_from(transcript: &VrfTranscript) -> Result<Self, RemoteError> {
+		let parts = transcript.parts().ok_or_else(|| {
+			RemoteError::Validation("VRF transcript built without its parts".into())
+		})?;
+		Ok(Self {
+			label: parts.label.to_vec(),
+			data: parts
+				.data
+				.iter()
+				.map(|(domain, message)| (domain.to_vec(), message.clone()))
+				.collect(),
+		})
+	}
+}
+
+impl TryFrom<Transcript> for VrfTranscript {
+	type Error = RemoteError;
+
+	fn try_from(transcript: Transcript) -> Result<Self, RemoteError> {
+		let label = intern(transcript.label)?;
+		let data = transcript
+			.data
+			.into_iter()
+			.map(|(domain, message)| Ok((intern(domain)?, message)))
+			.collect::<Result<_, RemoteError>>()?;
+		Ok(VrfTranscript::from_parts(VrfTranscriptParts { label, data }))
+	}
+}
+
+/// The data of an sr25519 VRF signature.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct SignData {
+	input: Transcript,
+	extra: Option<Transcript>,
+}
+
+impl TryFrom<&VrfSignData> for SignData {
+	type Error = RemoteError;
+
+	fn try_from(data: &VrfSignData) -> Result<Self, RemoteError> {
+		Ok(Self {
+			input: data.as_ref().try_into()?,
+			extra: data.extra().map(TryInto::try_into).transpose()?,
+		})
+	}
+}
+
+impl TryFrom<SignData> for VrfSignData {
+	type Error = RemoteError;
+
+	fn try_from(data: SignData) -> Result<Self, RemoteError> {
+		let sign_data = VrfSignData::new(data.input.try_into()?);
+		Ok(match data.extra {
+			Some(extra) => sign_data.with_extra(extra.try_into()?),
+			None => sign_data,
+		})
+	}
+}
+
+/// Get a static reference to `label`.
+///
+/// Transcripts require static labels, which are leaked once per distinct label. The number and
+/// length of the labels is bounded, legitimate transcripts only use a handful of constant ones.
+fn intern(label: Vec<u8>) -> Result<&'static [u8], RemoteError> {
+	static LABELS: OnceLock<Mutex<BTreeSet<&'static [u8]>>> = OnceLock::new();
+
+	let mut labels = LABELS.get_or_init(Default::default).lock();
+	if let Some(label) = labels.get(&label[..]) {
+		return Ok(label)
+	}
+	if label.len() > MAX_LABEL_LEN || labels.len() >= MAX_LABELS {
+		return Err(RemoteError::Validation("Unsupported VRF transcript label".into()))
+	}
+	let label: &'static [u8] = Box::leak(label.into_boxed_slice());
+	labels.insert(label);
+	Ok(label)
+}
+
+/// Write the SCALE encoded `message`, prefixed by its length.
+pub fn write_message<T: Encode>(stream: &mut impl Write, message: &T) -> io::Result<()> {
+	let encoded = message.encode();
+	let len = u32::try_from(encoded.len())
+		.ok()
+		.filter(|len| *len <= MAX_MESSAGE_LEN)
+		.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Message too long"))?;
+	stream.write_all(&len.to_le_bytes())?;
+	stream.write_all(&encoded)?;
+	stream.flush()
+}
+
+/// Read a message written by [`write_message`].
+pub fn read_message<T: Decode>(stream: &mut impl Read) -> io::Result<T> {
+	let mut len = [0u8; 4];
+	stream.read_exact(&mut len)?;
+	let len = u32::from_le_bytes(len);
+	if len > MAX_MESSAGE_LEN {
+		return Err(io::Error::new(io::ErrorKind::InvalidData, "Message too long"))
+	}
+	let mut encoded = vec![0u8; len as usize];
+	stream.read_exact(&mut encoded)?;
+	T::decode(&mut &encoded[..]).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// The MAC of a handshake, proving knowledge of the shared `secret` to the other side.
+fn mac(
+	secret: &[u8; SECRET_LEN],
+	role: &[u8],
+	signer_nonce: &[u8; SECRET_LEN],
+	keystore_nonce: &[u8; SECRET_LEN],
+) -> [u8; SECRET_LEN] {
+	sp_crypto_hashing::blake2_256(&[&secret[..], role, signer_nonce, keystore_nonce].concat())
+}
+
+/// Compare two MACs in constant time.
+fn mac_eq(a: &[u8; SECRET_LEN], b: &[u8; SECRET_LEN]) -> bool {
+	a.iter().zip(b.iter()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn authentication_failed() -> io::Error {
+	io::Error::new(io::ErrorKind::PermissionDenied, "Authentication failed")
+}
+
+/// Authenticate the connection on the side of the signer.
+pub fn authenticate_keystore(
+	stream: &mut (impl Read + Write),
+	secret: &[u8; SECRET_LEN],
+) -> io::Result<()> {
+	let signer_nonce: [u8; SECRET_LEN] = rand::random();
+	write_message(stream, &signer_nonce)?;
+
+	let (keystore_nonce, keystore_mac): ([u8; SECRET_LEN], [u8; SECRET_LEN]) =
+		read_message(stream)?;
+	if !mac_eq(&keystore_mac, &mac(secret, b"keystore", &signer_nonce, &keystore_nonce)) {
+		return Err(authentication_failed())
+	}
+
+	write_message(stream, &mac(secret, b"signer", &signer_nonce, &keystore_nonce))
+}
+
+/// Authenticate the connection on the side of the keystore.
+pub fn authenticate_signer(
+	stream: &mut (impl Read + Write),
+	secret: &[u8; SECRET_LEN],
+) -> io::Result<()> {
+	let signer_nonce: [u8; SECRET_LEN] = read_message(stream)?;
+	let keystore_nonce: [u8; SECRET_LEN] = rand::random();
+	write_message(
+		stream,
+		&(keystore_nonce, mac(secret, b"keystore", &signer_nonce, &keystore_nonce)),
+	)?;
+
+	let signer_mac: [u8; SECRET_LEN] = read_message(stream)?;
+	if !mac_eq(&signer_mac, &mac(secret, b"signer", &signer_nonce, &keystore_nonce)) {
+		return Err(authentication_failed())
+	}
+	Ok(())
+}
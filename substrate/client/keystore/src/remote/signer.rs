@@ -0,0 +1,290 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The signer, serving the requests of [`RemoteKeystore`](super::RemoteKeystore)s.
+
+use super::{
+	protocol::{self, RemoteError, Request, Response, SECRET_LEN},
+	SlashingProtection, LOG_TARGET,
+};
+use crate::LocalKeystore;
+use parking_lot::Mutex;
+use sp_core::{
+	crypto::{ByteArray, CryptoTypeId, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::Keystore;
+use std::{
+	io,
+	os::unix::net::{UnixListener, UnixStream},
+	sync::Arc,
+	thread,
+};
+
+sp_keystore::bandersnatch_experimental_enabled! {
+use sp_core::bandersnatch;
+}
+
+sp_keystore::bls_experimental_enabled! {
+use sp_core::{bls381, ecdsa_bls381};
+}
+
+/// Signs on behalf of [`RemoteKeystore`](super::RemoteKeystore)s with the keys of a
+/// [`LocalKeystore`], guarded by [`SlashingProtection`].
+pub struct Signer {
+	keystore: Arc<LocalKeystore>,
+	secret: [u8; SECRET_LEN],
+	protection: Mutex<SlashingProtection>,
+	allow_insert: bool,
+}
+
+impl Signer {
+	/// Create a new signer, authenticating keystores with the shared `secret`.
+	pub fn new(
+		keystore: Arc<LocalKeystore>,
+		secret: [u8; SECRET_LEN],
+		protection: SlashingProtection,
+	) -> Self {
+		Self { keystore, secret, protection: Mutex::new(protection), allow_insert: false }
+	}
+
+	/// Whether keystores may insert secret keys into the signer, `false` by default.
+	///
+	/// Generating new keys is always allowed, since their secrets never leave the signer.
+	pub fn with_allow_insert(mut self, allow_insert: bool) -> Self {
+		self.allow_insert = allow_insert;
+		self
+	}
+
+	/// Serve the connections of `listener`, each in its own thread.
+	///
+	/// Only returns if accepting connections fails.
+	pub fn serve(self: Arc<Self>, listener: UnixListener) -> io::Result<()> {
+		loop {
+			let (stream, _) = listener.accept()?;
+			let signer = self.clone();
+			thread::Builder::new().name("remote-signer-connection".into()).spawn(move || {
+				if let Err(e) = signer.serve_connection(stream) {
+					log::debug!(target: LOG_TARGET, "Connection closed: {e}");
+				}
+			})?;
+		}
+	}
+
+	/// Authenticate the keystore of `stream`, then answer its requests until it disconnects.
+	fn serve_connection(&self, mut stream: UnixStream) -> io::Result<()> {
+		if let Err(e) = protocol::authenticate_keystore(&mut stream, &self.secret) {
+			log::warn!(target: LOG_TARGET, "Failed to authenticate a keystore: {e}");
+			return Err(e)
+		}
+		log::debug!(target: LOG_TARGET, "Keystore connected");
+
+		loop {
+			let request = match protocol::read_message::<Request>(&mut stream) {
+				Ok(request) => request,
+				Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+				Err(e) => return Err(e),
+			};
+			let response = self.handle(request).unwrap_or_else(Response::Error);
+			protocol::write_message(&mut stream, &response)?;
+		}
+	}
+
+	/// Answer a single request.
+	pub(super) fn handle(&self, request: Request) -> Result<Response, RemoteError> {
+		Ok(match request {
+			Request::PublicKeys { key_type, crypto_id } =>
+				Response::PublicKeys(self.public_keys(key_type, crypto_id)?),
+			Request::GenerateNew { key_type, crypto_id, seed } =>
+				Response::Public(self.generate_new(key_type, crypto_id, seed.as_deref())?),
+			Request::Sign { key_type, crypto_id, public, msg } =>
+				Response::Signature(self.protected(key_type, crypto_id, &public, &msg, || {
+					Ok(self.keystore.sign_with(key_type, crypto_id, &public, &msg)?)
+				})?),
+			Request::EcdsaSignPrehashed { key_type, public, msg } => {
+				if let Err(e) = self.protection.lock().check_prehashed(key_type) {
+					log::warn!(target: LOG_TARGET, "Refusing to sign with {key_type:?} key: {e}");
+					return Err(RemoteError::Refused(e))
+				}
+				let public = decode_public::<ecdsa::Public>(&public)?;
+				let signature = self.keystore.ecdsa_sign_prehashed(key_type, &public, &msg)?;
+				Response::Signature(signature.map(|s| codec::Encode::encode(&s)))
+			},
+			Request::EcdsaSignWithKeccak256 { key_type, public, msg } => {
+				let ecdsa_public = decode_public::<ecdsa::Public>(&public)?;
+				let signature =
+					self.protected(key_type, ecdsa::CRYPTO_ID, &public, &msg, || {
+						Ok(self.keystore.ecdsa_sign_with_keccak256(
+							key_type,
+							&ecdsa_public,
+							&msg,
+						)?)
+					})?;
+				Response::Signature(signature.map(|s| codec::Encode::encode(&s)))
+			},
+			Request::Sr25519SignWithBlake2_256 { key_type, public, msg } => {
+				let sr25519_public = decode_public::<sr25519::Public>(&public)?;
+				let signature =
+					self.protected(key_type, sr25519::CRYPTO_ID, &public, &msg, || {
+						Ok(self.keystore.sr25519_sign_with_blake2_256(
+							key_type,
+							&sr25519_public,
+							&msg,
+						)?)
+					})?;
+				Response::Signature(signature.map(|s| codec::Encode::encode(&s)))
+			},
+			Request::Sr25519VrfSign { key_type, public, data } => {
+				let public = decode_public::<sr25519::Public>(&public)?;
+				let data: sr25519::vrf::VrfSignData = data.try_into()?;
+				Response::VrfSignature(self.keystore.sr25519_vrf_sign(key_type, &public, &data)?)
+			},
+			Request::Sr25519VrfPreOutput { key_type, public, input } => {
+				let public = decode_public::<sr25519::Public>(&public)?;
+				let input = input.try_into()?;
+				Response::VrfPreOutput(
+					self.keystore.sr25519_vrf_pre_output(key_type, &public, &input)?,
+				)
+			},
+			Request::Bls381ProofOfPossession { key_type, public } =>
+				Response::Signature(self.bls381_proof_of_possession(key_type, &public)?),
+			Request::EcdsaBls381SignWithKeccak256 { key_type, public, msg } =>
+				Response::Signature(self.ecdsa_bls381_sign_with_keccak256(key_type, &public, &msg)?),
+			Request::Insert { key_type, suri, public } => {
+				if !self.allow_insert {
+					return Err(RemoteError::NotAllowed)
+				}
+				self.keystore
+					.insert(key_type, &suri, &public)
+					.map_err(|()| RemoteError::Other("Failed to insert the key".into()))?;
+				Response::Done
+			},
+			Request::Keys { key_type } => Response::PublicKeys(self.keystore.keys(key_type)?),
+			Request::HasKeys(public_keys) => Response::Bool(self.keystore.has_keys(&public_keys)),
+		})
+	}
+
+	/// Sign with `sign` if signing `msg` with the given key is safe, see [`SlashingProtection`].
+	fn protected<R>(
+		&self,
+		key_type: KeyTypeId,
+		crypto_id: CryptoTypeId,
+		public: &[u8],
+		msg: &[u8],
+		sign: impl FnOnce() -> Result<R, RemoteError>,
+	) -> Result<R, RemoteError> {
+		// hold the lock while signing, so conflicting messages are not signed concurrently.
+		let mut protection = self.protection.lock();
+		if let Err(e) = protection.check_and_record(key_type, crypto_id, public, msg) {
+			log::warn!(target: LOG_TARGET, "Refusing to sign with {key_type:?} key: {e}");
+			return Err(RemoteError::Refused(e))
+		}
+		sign()
+	}
+
+	fn public_keys(
+		&self,
+		key_type: KeyTypeId,
+		crypto_id: CryptoTypeId,
+	) -> Result<Vec<Vec<u8>>, RemoteError> {
+		fn to_raw<P: ByteArray>(keys: Vec<P>) -> Vec<Vec<u8>> {
+			keys.into_iter().map(|key| key.to_raw_vec()).collect()
+		}
+
+		Ok(match crypto_id {
+			sr25519::CRYPTO_ID => to_raw(self.keystore.sr25519_public_keys(key_type)),
+			ed25519::CRYPTO_ID => to_raw(self.keystore.ed25519_public_keys(key_type)),
+			ecdsa::CRYPTO_ID => to_raw(self.keystore.ecdsa_public_keys(key_type)),
+			#[cfg(feature = "bandersnatch-experimental")]
+			bandersnatch::CRYPTO_ID => to_raw(self.keystore.bandersnatch_public_keys(key_type)),
+			#[cfg(feature = "bls-experimental")]
+			bls381::CRYPTO_ID => to_raw(self.keystore.bls381_public_keys(key_type)),
+			#[cfg(feature = "bls-experimental")]
+			ecdsa_bls381::CRYPTO_ID => to_raw(self.keystore.ecdsa_bls381_public_keys(key_type)),
+			_ => return Err(RemoteError::KeyNotSupported(key_type)),
+		})
+	}
+
+	fn generate_new(
+		&self,
+		key_type: KeyTypeId,
+		crypto_id: CryptoTypeId,
+		seed: Option<&str>,
+	) -> Result<Vec<u8>, RemoteError> {
+		Ok(match crypto_id {
+			sr25519::CRYPTO_ID => self.keystore.sr25519_generate_new(key_type, seed)?.to_raw_vec(),
+			ed25519::CRYPTO_ID => self.keystore.ed25519_generate_new(key_type, seed)?.to_raw_vec(),
+			ecdsa::CRYPTO_ID => self.keystore.ecdsa_generate_new(key_type, seed)?.to_raw_vec(),
+			#[cfg(feature = "bandersnatch-experimental")]
+			bandersnatch::CRYPTO_ID => self.keystore.bandersnatch_generate_new(key_type, seed)?.to_raw_vec(),
+			#[cfg(feature = "bls-experimental")]
+			bls381::CRYPTO_ID => self.keystore.bls381_generate_new(key_type, seed)?.to_raw_vec(),
+			#[cfg(feature = "bls-experimental")]
+			ecdsa_bls381::CRYPTO_ID => self.keystore.ecdsa_bls381_generate_new(key_type, seed)?.to_raw_vec(),
+			_ => return Err(RemoteError::KeyNotSupported(key_type)),
+		})
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_proof_of_possession(
+		&self,
+		key_type: KeyTypeId,
+		public: &[u8],
+	) -> Result<Option<Vec<u8>>, RemoteError> {
+		let public = decode_public::<bls381::Public>(public)?;
+		let proof = self.keystore.bls381_generate_proof_of_possession(key_type, &public)?;
+		Ok(proof.map(|p| codec::Encode::encode(&p)))
+	}
+
+	#[cfg(not(feature = "bls-experimental"))]
+	fn bls381_proof_of_possession(
+		&self,
+		key_type: KeyTypeId,
+		_: &[u8],
+	) -> Result<Option<Vec<u8>>, RemoteError> {
+		Err(RemoteError::KeyNotSupported(key_type))
+	}
+
+	#[cfg(feature = "bls-experimental")]
+	fn ecdsa_bls381_sign_with_keccak256(
+		&self,
+		key_type: KeyTypeId,
+		public: &[u8],
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, RemoteError> {
+		let pair_public = decode_public::<ecdsa_bls381::Public>(public)?;
+		let signature = self.protected(key_type, ecdsa_bls381::CRYPTO_ID, public, msg, || {
+			Ok(self.keystore.ecdsa_bls381_sign_with_keccak256(key_type, &pair_public, msg)?)
+		})?;
+		Ok(signature.map(|s| codec::Encode::encode(&s)))
+	}
+
+	#[cfg(not(feature = "bls-experimental"))]
+	fn ecdsa_bls381_sign_with_keccak256(
+		&self,
+		key_type: KeyTypeId,
+		_: &[u8],
+		_: &[u8],
+	) -> Result<Option<Vec<u8>>, RemoteError> {
+		Err(RemoteError::KeyNotSupported(key_type))
+	}
+}
+
+fn decode_public<P: ByteArray>(public: &[u8]) -> Result<P, RemoteError> {
+	P::from_slice(public).map_err(|_| RemoteError::Validation("Invalid public key format".into()))
+}
@@ -0,0 +1,564 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Slashing protection of the [`Signer`](super::Signer).
+//!
+//! The signer refuses to sign messages that could get its validator slashed for equivocating:
+//!
+//! - GRANDPA votes: for every key, set id, round and vote kind (prevote, precommit or primary
+//!   proposal) only a single message is ever signed.
+//! - BEEFY votes: for every key, validator set id and block number only a single commitment is ever
+//!   signed. ECDSA votes must be signed with their message through
+//!   [`Keystore::ecdsa_sign_with_keccak256`](sp_keystore::Keystore::ecdsa_sign_with_keccak256),
+//!   signing pre-hashed messages with BEEFY keys is refused.
+//! - BABE blocks: for every key and slot only a single header is ever sealed, whatever the kind of
+//!   the slot. Headers must be sealed with their encoding through
+//!   [`Keystore::sr25519_sign_with_blake2_256`](sp_keystore::Keystore::sr25519_sign_with_blake2_256),
+//!   so that the slot can be read from their BABE pre-runtime digest. Sealing header hashes with
+//!   BABE keys is refused.
+//!
+//! Messages older than the ones recorded are refused altogether, so that the history can be
+//! pruned. The record of signed messages is persisted as JSON, and written before a signature is
+//! returned.
+
+use codec::{Compact, Decode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_core::{
+	crypto::{key_types, CryptoTypeId, KeyTypeId},
+	ed25519, sr25519,
+};
+use std::{
+	collections::{BTreeMap, HashMap},
+	fs, io,
+	path::PathBuf,
+};
+
+/// The number of GRANDPA votes per key that are kept in the history, three per round.
+const GRANDPA_VOTES_KEPT: usize = 256 * 3;
+
+/// The number of BEEFY votes per key that are kept in the history.
+const BEEFY_VOTES_KEPT: usize = 256;
+
+/// The number of BABE slots per key that are kept in the history.
+const BABE_SLOTS_KEPT: usize = 256;
+
+/// The length of the round number and set id at the end of a GRANDPA vote.
+const GRANDPA_ROUND_AND_SET_ID_LEN: usize = 16;
+
+/// The length of the validator set id at the end of a BEEFY commitment.
+const BEEFY_SET_ID_LEN: usize = 8;
+
+/// The length of the hashes in a block header.
+const HEADER_HASH_LEN: usize = 32;
+
+/// The consensus engine id of BABE.
+const BABE_ENGINE_ID: [u8; 4] = *b"BABE";
+
+/// A GRANDPA vote, as `(set id, round, message kind)`.
+type GrandpaVote = (u64, u64, u8);
+
+/// A BEEFY vote, as `(validator set id, block number)`.
+type BeefyVote = (u64, u64);
+
+/// The messages signed by a single key, by the vote or slot they were signed for.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct History<K> {
+	/// Messages before this one are refused.
+	low_watermark: K,
+	/// The hashes of the signed messages.
+	signed: BTreeMap<K, [u8; 32]>,
+}
+
+/// Why signing a message was refused by a [`History`].
+enum Conflict {
+	/// The message is older than the history.
+	Older,
+	/// Another message was already signed in its place.
+	Signed,
+}
+
+impl<K: Ord + Copy> History<K> {
+	/// Record that the message with `hash` is signed as `key`, unless it conflicts.
+	///
+	/// Returns whether it was not recorded yet. Only the latest `kept` messages are kept, older
+	/// ones are pruned, moving the low watermark to `after` the oldest pruned one.
+	fn record(
+		&mut self,
+		key: K,
+		hash: [u8; 32],
+		kept: usize,
+		after: impl Fn(&K) -> K,
+	) -> Result<bool, Conflict> {
+		if key < self.low_watermark {
+			return Err(Conflict::Older)
+		}
+		match self.signed.get(&key) {
+			Some(signed) if *signed == hash => return Ok(false),
+			Some(_) => return Err(Conflict::Signed),
+			None => {},
+		}
+
+		self.signed.insert(key, hash);
+		while self.signed.len() > kept {
+			let (oldest, _) = self.signed.pop_first().expect("history is not empty; qed");
+			self.low_watermark = after(&oldest);
+			self.signed = self.signed.split_off(&self.low_watermark);
+		}
+		Ok(true)
+	}
+}
+
+/// A message newly recorded by the [`SlashingProtection`].
+enum Recorded {
+	Grandpa(GrandpaVote),
+	Beefy(BeefyVote),
+	Babe(u64),
+}
+
+/// The persisted history of a single key, as `(hex encoded public key, low watermark, signed)`.
+type PersistedKey<K> = (String, K, Vec<(K, [u8; 32])>);
+
+/// The persisted state of the slashing protection.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedHistory {
+	/// The history of the GRANDPA votes.
+	#[serde(default)]
+	grandpa: Vec<PersistedKey<GrandpaVote>>,
+	/// The history of the BEEFY votes.
+	#[serde(default)]
+	beefy: Vec<PersistedKey<BeefyVote>>,
+	/// The history of the sealed BABE blocks, by slot.
+	#[serde(default)]
+	babe: Vec<PersistedKey<u64>>,
+}
+
+/// Slashing protection for the signer.
+#[derive(Debug, Default)]
+pub struct SlashingProtection {
+	/// Where the history is persisted, if at all.
+	path: Option<PathBuf>,
+	/// The GRANDPA votes, per public key.
+	grandpa: HashMap<Vec<u8>, History<GrandpaVote>>,
+	/// The BEEFY votes, per public key.
+	beefy: HashMap<Vec<u8>, History<BeefyVote>>,
+	/// The sealed BABE blocks, per public key.
+	babe: HashMap<Vec<u8>, History<u64>>,
+}
+
+impl SlashingProtection {
+	/// Slashing protection that only keeps its history in memory, e.g. for tests.
+	pub fn in_memory() -> Self {
+		Self::default()
+	}
+
+	/// Open the slashing protection history at `path`, creating it if it does not exist.
+	pub fn open(path: PathBuf) -> io::Result<Self> {
+		let persisted: PersistedHistory = match fs::read(&path) {
+			Ok(data) => serde_json::from_slice(&data)
+				.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Default::default(),
+			Err(e) => return Err(e),
+		};
+
+		Ok(Self {
+			path: Some(path),
+			grandpa: restore(persisted.grandpa)?,
+			beefy: restore(persisted.beefy)?,
+			babe: restore(persisted.babe)?,
+		})
+	}
+
+	/// Check whether signing `msg` with the given key is safe, and record it if so.
+	///
+	/// Returns an error describing the conflict if it is not.
+	pub fn check_and_record(
+		&mut self,
+		key_type: KeyTypeId,
+		crypto_id: CryptoTypeId,
+		public: &[u8],
+		msg: &[u8],
+	) -> Result<(), String> {
+		let hash = sp_crypto_hashing::blake2_256(msg);
+
+		let recorded = if key_type == key_types::GRANDPA && crypto_id == ed25519::CRYPTO_ID {
+			let Some(vote) = grandpa_vote(msg) else { return Err("Not a GRANDPA vote".into()) };
+			let (set_id, round, kind) = vote;
+			let history = self.grandpa.entry(public.to_vec()).or_default();
+			let new = history
+				.record(vote, hash, GRANDPA_VOTES_KEPT, |(set_id, round, _)| {
+					(*set_id, round + 1, 0)
+				})
+				.map_err(|conflict| match conflict {
+					Conflict::Older =>
+						format!("Round {round} of set {set_id} is older than the last signed votes"),
+					Conflict::Signed => format!(
+						"Another vote of kind {kind} was already signed in round {round} of set {set_id}"
+					),
+				})?;
+			new.then_some(Recorded::Grandpa(vote))
+		} else if key_type == key_types::BEEFY {
+			let Some(vote) = beefy_vote(msg) else { return Err("Not a BEEFY vote".into()) };
+			let (set_id, number) = vote;
+			let history = self.beefy.entry(public.to_vec()).or_default();
+			let new = history
+				.record(vote, hash, BEEFY_VOTES_KEPT, |(set_id, number)| (*set_id, number + 1))
+				.map_err(|conflict| match conflict {
+					Conflict::Older => format!(
+						"Block {number} of set {set_id} is older than the last signed votes"
+					),
+					Conflict::Signed => format!(
+						"Another commitment was already signed for block {number} of set {set_id}"
+					),
+				})?;
+			new.then_some(Recorded::Beefy(vote))
+		} else if key_type == key_types::BABE && crypto_id == sr25519::CRYPTO_ID {
+			let Some(slot) = babe_slot(msg) else {
+				return Err(
+					"Not a BABE header, headers must be sealed along with their encoding".into()
+				)
+			};
+			let history = self.babe.entry(public.to_vec()).or_default();
+			let new = history.record(slot, hash, BABE_SLOTS_KEPT, |slot| slot + 1).map_err(
+				|conflict| match conflict {
+					Conflict::Older => format!("Slot {slot} is older than the last sealed blocks"),
+					Conflict::Signed => format!("Another block was already sealed in slot {slot}"),
+				},
+			)?;
+			new.then_some(Recorded::Babe(slot))
+		} else {
+			None
+		};
+
+		let Some(recorded) = recorded else { return Ok(()) };
+		self.persist().map_err(|e| {
+			// a message that is not persisted must not be signed, the signer could sign a
+			// conflicting one after a restart.
+			self.forget(public, recorded);
+			format!("Failed to persist the slashing protection history: {e}")
+		})
+	}
+
+	/// Check whether signing a pre-hashed message with the given key is safe.
+	///
+	/// Pre-hashed messages cannot be inspected, so this is refused for keys of protected
+	/// protocols.
+	pub fn check_prehashed(&self, key_type: KeyTypeId) -> Result<(), String> {
+		if key_type == key_types::BEEFY {
+			return Err("BEEFY votes must be signed along with their message".into())
+		}
+		Ok(())
+	}
+
+	/// Remove a message that was just recorded.
+	fn forget(&mut self, public: &[u8], recorded: Recorded) {
+		match recorded {
+			Recorded::Grandpa(vote) =>
+				self.grandpa.get_mut(public).map(|history| history.signed.remove(&vote)),
+			Recorded::Beefy(vote) =>
+				self.beefy.get_mut(public).map(|history| history.signed.remove(&vote)),
+			Recorded::Babe(slot) =>
+				self.babe.get_mut(public).map(|history| history.signed.remove(&slot)),
+		};
+	}
+
+	/// Write the history to disk, if persisted.
+	fn persist(&self) -> io::Result<()> {
+		let Some(path) = &self.path else { return Ok(()) };
+		let persisted = PersistedHistory {
+			grandpa: to_persisted(&self.grandpa),
+			beefy: to_persisted(&self.beefy),
+			babe: to_persisted(&self.babe),
+		};
+		let data = serde_json::to_vec(&persisted)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+		// write atomically, a torn history would lose the record of signed messages.
+		let tmp = path.with_extension("tmp");
+		fs::write(&tmp, data)?;
+		fs::rename(&tmp, path)
+	}
+}
+
+/// Restore the persisted histories of all keys.
+fn restore<K: Ord + DeserializeOwned>(
+	persisted: Vec<PersistedKey<K>>,
+) -> io::Result<HashMap<Vec<u8>, History<K>>> {
+	persisted
+		.into_iter()
+		.map(|(public, low_watermark, signed)| {
+			let public = array_bytes::hex2bytes(&public)
+				.map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid public key"))?;
+			Ok((public, History { low_watermark, signed: signed.into_iter().collect() }))
+		})
+		.collect()
+}
+
+/// The histories of all keys, ready to be persisted.
+fn to_persisted<K: Copy + Serialize>(
+	histories: &HashMap<Vec<u8>, History<K>>,
+) -> Vec<PersistedKey<K>> {
+	histories
+		.iter()
+		.map(|(public, history)| {
+			(
+				array_bytes::bytes2hex("", public),
+				history.low_watermark,
+				history.signed.iter().map(|(key, hash)| (*key, *hash)).collect(),
+			)
+		})
+		.collect()
+}
+
+/// Parse a GRANDPA vote `(message, round, set_id)` into `(set id, round, message kind)`.
+///
+/// The message starts with the index of its variant: prevote, precommit or primary proposal.
+/// The target block in between is not parsed, since its encoding depends on the chain.
+fn grandpa_vote(msg: &[u8]) -> Option<GrandpaVote> {
+	let kind = *msg.first()?;
+	if kind > 2 || msg.len() < 1 + GRANDPA_ROUND_AND_SET_ID_LEN {
+		return None
+	}
+	let tail = &msg[msg.len() - GRANDPA_ROUND_AND_SET_ID_LEN..];
+	let round = u64::from_le_bytes(tail[..8].try_into().expect("8 bytes; qed"));
+	let set_id = u64::from_le_bytes(tail[8..].try_into().expect("8 bytes; qed"));
+	Some((set_id, round, kind))
+}
+
+/// Parse the slot of a BABE header `(parent_hash, number, state_root, extrinsics_root, digest)`.
+///
+/// The hashes are assumed to be 32 bytes long and the number compact encoded. The slot is the one
+/// of the BABE pre-runtime digest, which for every kind of slot follows the variant and the
+/// authority index.
+fn babe_slot(header: &[u8]) -> Option<u64> {
+	let mut rest = header.get(HEADER_HASH_LEN..)?;
+	Compact::<u64>::decode(&mut rest).ok()?;
+	let mut rest = rest.get(2 * HEADER_HASH_LEN..)?;
+
+	let items = Compact::<u32>::decode(&mut rest).ok()?.0;
+	for _ in 0..items {
+		// the variants of `sp_runtime::DigestItem`.
+		match u8::decode(&mut rest).ok()? {
+			// `PreRuntime`
+			6 => {
+				let (engine_id, data) = <([u8; 4], Vec<u8>)>::decode(&mut rest).ok()?;
+				if engine_id == BABE_ENGINE_ID {
+					let slot = data.get(5..13)?;
+					return Some(u64::from_le_bytes(slot.try_into().expect("8 bytes; qed")))
+				}
+			},
+			// `Consensus` and `Seal`
+			4 | 5 => {
+				<([u8; 4], Vec<u8>)>::decode(&mut rest).ok()?;
+			},
+			// `Other`
+			0 => {
+				<Vec<u8>>::decode(&mut rest).ok()?;
+			},
+			// `RuntimeEnvironmentUpdated`
+			8 => {},
+			_ => return None,
+		}
+	}
+	None
+}
+
+/// Parse a BEEFY commitment `(payload, block_number, validator_set_id)` into
+/// `(validator set id, block number)`.
+///
+/// The block number is the little endian integer between the payload and the set id, of any
+/// width up to 64 bits.
+fn beefy_vote(msg: &[u8]) -> Option<BeefyVote> {
+	let mut rest = msg;
+	<Vec<([u8; 2], Vec<u8>)>>::decode(&mut rest).ok()?;
+	if rest.len() <= BEEFY_SET_ID_LEN || rest.len() > BEEFY_SET_ID_LEN + 8 {
+		return None
+	}
+	let (number, set_id) = rest.split_at(rest.len() - BEEFY_SET_ID_LEN);
+	let mut padded = [0u8; 8];
+	padded[..number.len()].copy_from_slice(number);
+	let set_id = u64::from_le_bytes(set_id.try_into().expect("8 bytes; qed"));
+	Some((set_id, u64::from_le_bytes(padded)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use sp_core::ecdsa;
+	use tempfile::TempDir;
+
+	const PUBLIC: [u8; 32] = [1; 32];
+
+	fn vote(kind: u8, target: u8, round: u64, set_id: u64) -> Vec<u8> {
+		// `(message, round, set_id)` with a `(hash, number)` target.
+		((kind, [target; 32], 10u32), round, set_id).encode()
+	}
+
+	fn commitment(root: u8, number: u32, set_id: u64) -> Vec<u8> {
+		// `(payload, block_number, validator_set_id)` with an MMR root payload.
+		(vec![(*b"mh", [root; 32].encode())], number, set_id).encode()
+	}
+
+	fn header(kind: u8, slot: u64, state_root: u8) -> Vec<u8> {
+		// `(parent_hash, number, state_root, extrinsics_root, digest)` with a BABE pre-digest of
+		// the given kind, after another digest item.
+		let pre_digest = (kind, 0u32, slot).encode();
+		let digest = (Compact(2u32), (0u8, b"other".to_vec()), (6u8, BABE_ENGINE_ID, pre_digest));
+		([0u8; 32], Compact(10u32), [state_root; 32], [0u8; 32], digest).encode()
+	}
+
+	fn check(protection: &mut SlashingProtection, msg: &[u8]) -> Result<(), String> {
+		protection.check_and_record(key_types::GRANDPA, ed25519::CRYPTO_ID, &PUBLIC, msg)
+	}
+
+	fn check_beefy(protection: &mut SlashingProtection, msg: &[u8]) -> Result<(), String> {
+		protection.check_and_record(key_types::BEEFY, ecdsa::CRYPTO_ID, &PUBLIC, msg)
+	}
+
+	fn check_babe(protection: &mut SlashingProtection, header: &[u8]) -> Result<(), String> {
+		protection.check_and_record(key_types::BABE, sr25519::CRYPTO_ID, &PUBLIC, header)
+	}
+
+	#[test]
+	fn refuses_conflicting_grandpa_votes() {
+		let mut protection = SlashingProtection::in_memory();
+
+		assert!(check(&mut protection, &vote(0, 1, 5, 0)).is_ok());
+		// signing the same vote again is fine.
+		assert!(check(&mut protection, &vote(0, 1, 5, 0)).is_ok());
+		// a different prevote in the same round is not.
+		assert!(check(&mut protection, &vote(0, 2, 5, 0)).is_err());
+		// a precommit, or a prevote in another round or set, is.
+		assert!(check(&mut protection, &vote(1, 2, 5, 0)).is_ok());
+		assert!(check(&mut protection, &vote(0, 2, 6, 0)).is_ok());
+		assert!(check(&mut protection, &vote(0, 2, 5, 1)).is_ok());
+	}
+
+	#[test]
+	fn ignores_other_keys() {
+		let mut protection = SlashingProtection::in_memory();
+
+		for msg in [b"heartbeat".to_vec(), b"another heartbeat".to_vec()] {
+			assert!(protection
+				.check_and_record(key_types::IM_ONLINE, sr25519::CRYPTO_ID, &PUBLIC, &msg)
+				.is_ok());
+		}
+		assert!(protection.check_prehashed(key_types::IM_ONLINE).is_ok());
+	}
+
+	#[test]
+	fn refuses_votes_older_than_the_history() {
+		let mut protection = SlashingProtection::in_memory();
+
+		for round in 0..GRANDPA_VOTES_KEPT as u64 + 1 {
+			assert!(check(&mut protection, &vote(0, 1, round, 0)).is_ok());
+		}
+		assert!(check(&mut protection, &vote(1, 1, 0, 0)).is_err());
+		assert!(check(&mut protection, &vote(1, 1, GRANDPA_VOTES_KEPT as u64, 0)).is_ok());
+	}
+
+	#[test]
+	fn refuses_conflicting_beefy_votes() {
+		let mut protection = SlashingProtection::in_memory();
+
+		assert!(check_beefy(&mut protection, &commitment(1, 10, 0)).is_ok());
+		// signing the same commitment again is fine.
+		assert!(check_beefy(&mut protection, &commitment(1, 10, 0)).is_ok());
+		// a different commitment for the same block is not, whatever the crypto of the key.
+		assert!(check_beefy(&mut protection, &commitment(2, 10, 0)).is_err());
+		assert!(protection
+			.check_and_record(key_types::BEEFY, sr25519::CRYPTO_ID, &PUBLIC, &commitment(2, 10, 0))
+			.is_err());
+		// a commitment for another block or set is.
+		assert!(check_beefy(&mut protection, &commitment(2, 11, 0)).is_ok());
+		assert!(check_beefy(&mut protection, &commitment(2, 10, 1)).is_ok());
+		// anything else is refused.
+		assert!(check_beefy(&mut protection, b"not a commitment").is_err());
+	}
+
+	#[test]
+	fn refuses_beefy_votes_older_than_the_history() {
+		let mut protection = SlashingProtection::in_memory();
+
+		for number in 0..BEEFY_VOTES_KEPT as u32 + 1 {
+			assert!(check_beefy(&mut protection, &commitment(1, number, 0)).is_ok());
+		}
+		assert!(check_beefy(&mut protection, &commitment(1, 0, 0)).is_err());
+		assert!(check_beefy(&mut protection, &commitment(1, 1, 0)).is_ok());
+	}
+
+	#[test]
+	fn refuses_prehashed_beefy_votes() {
+		let protection = SlashingProtection::in_memory();
+
+		assert!(protection.check_prehashed(key_types::BEEFY).is_err());
+	}
+
+	#[test]
+	fn refuses_conflicting_babe_blocks() {
+		let mut protection = SlashingProtection::in_memory();
+
+		// header hashes cannot be checked.
+		assert!(check_babe(&mut protection, &[1; 32]).is_err());
+
+		assert!(check_babe(&mut protection, &header(1, 5, 1)).is_ok());
+		// sealing the same header again is fine.
+		assert!(check_babe(&mut protection, &header(1, 5, 1)).is_ok());
+		// another header in the same slot is not, whatever the kind of the slot.
+		assert!(check_babe(&mut protection, &header(1, 5, 2)).is_err());
+		assert!(check_babe(&mut protection, &header(2, 5, 2)).is_err());
+		assert!(check_babe(&mut protection, &header(3, 5, 2)).is_err());
+		// plain secondary slots are protected as well.
+		assert!(check_babe(&mut protection, &header(2, 6, 1)).is_ok());
+		assert!(check_babe(&mut protection, &header(2, 6, 2)).is_err());
+		// headers are per key.
+		assert!(protection
+			.check_and_record(key_types::BABE, sr25519::CRYPTO_ID, &[2; 32], &header(2, 6, 2))
+			.is_ok());
+	}
+
+	#[test]
+	fn refuses_babe_blocks_older_than_the_history() {
+		let mut protection = SlashingProtection::in_memory();
+
+		for slot in 0..BABE_SLOTS_KEPT as u64 + 1 {
+			assert!(check_babe(&mut protection, &header(1, slot, 1)).is_ok());
+		}
+		assert!(check_babe(&mut protection, &header(1, 0, 1)).is_err());
+		assert!(check_babe(&mut protection, &header(1, 1, 1)).is_ok());
+	}
+
+	#[test]
+	fn history_is_persisted() {
+		let temp_dir = TempDir::new().unwrap();
+		let path = temp_dir.path().join("slashing_protection.json");
+
+		let mut protection = SlashingProtection::open(path.clone()).unwrap();
+		assert!(check(&mut protection, &vote(0, 1, 5, 0)).is_ok());
+		assert!(check_beefy(&mut protection, &commitment(1, 10, 0)).is_ok());
+		assert!(check_babe(&mut protection, &header(1, 5, 1)).is_ok());
+		drop(protection);
+
+		let mut protection = SlashingProtection::open(path).unwrap();
+		assert!(check(&mut protection, &vote(0, 1, 5, 0)).is_ok());
+		assert!(check(&mut protection, &vote(0, 2, 5, 0)).is_err());
+		assert!(check_beefy(&mut protection, &commitment(2, 10, 0)).is_err());
+		assert!(check_babe(&mut protection, &header(1, 5, 1)).is_ok());
+		assert!(check_babe(&mut protection, &header(2, 5, 2)).is_err());
+	}
+}
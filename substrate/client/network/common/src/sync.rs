@@ -34,6 +34,13 @@ pub enum SyncMode {
 	},
 	/// Warp sync - verify authority set transitions and the latest state.
 	Warp,
+	/// Import the state of a trusted local snapshot, then continue with full sync.
+	Checkpoint,
+	/// Download and verify headers only. State is fetched on demand from full peers with the
+	/// light client request protocol.
+	///
+	/// Not supported by `sc-service` nodes yet, whose runtime API calls need local state.
+	Light,
 }
 
 impl SyncMode {
@@ -46,6 +53,16 @@ impl SyncMode {
 	pub fn light_state(&self) -> bool {
 		matches!(self, Self::LightState { .. })
 	}
+
+	/// Returns `true` if `self` is [`Self::Checkpoint`].
+	pub fn is_checkpoint(&self) -> bool {
+		matches!(self, Self::Checkpoint)
+	}
+
+	/// Returns `true` if `self` is [`Self::Light`].
+	pub fn is_light(&self) -> bool {
+		matches!(self, Self::Light)
+	}
 }
 
 impl Default for SyncMode {
@@ -0,0 +1,447 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Helper for outgoing light client requests.
+//!
+//! Fetch state of blocks only known by their headers from full peers with
+//! [`LightClientRequestSender`], checking the returned proofs against the state roots of the
+//! headers.
+
+use crate::schema;
+use codec::{Decode, Encode};
+use futures::{future::BoxFuture, prelude::*};
+use log::{debug, trace};
+use parking_lot::Mutex;
+use prost::Message;
+use sc_client_api::light::RemoteStateFetcher;
+use sc_network::{IfDisconnected, NetworkRequest, ProtocolName};
+use sc_network_sync::{
+	service::syncing_service::SyncingService,
+	types::{SyncEvent, SyncEventStream},
+};
+use sc_network_types::PeerId;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	storage::{ChildInfo, ChildType, PrefixedStorageKey},
+	traits::CodeExecutor,
+};
+use sp_runtime::traits::{Block, HashingFor, Header};
+use sp_state_machine::{backend::BackendRuntimeCode, OverlayedChanges, StorageProof};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+};
+
+const LOG_TARGET: &str = "light-client-request-sender";
+
+/// Maximum number of peers asked to answer a single request.
+const MAX_ATTEMPTS: usize = 3;
+
+/// Light client request error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// Header of the block is not known, so proofs can't be checked.
+	#[error("Header of block {0} is not known.")]
+	UnknownBlock(String),
+	/// Client backend error.
+	#[error(transparent)]
+	Client(#[from] sp_blockchain::Error),
+	/// Child storage key is not a default child trie key.
+	#[error("Invalid child storage key.")]
+	InvalidChildStorageKey,
+	/// None of the peers answered the request with a valid proof.
+	#[error("No peer answered the request with a valid proof after {0} attempt(s).")]
+	Unanswered(usize),
+}
+
+impl From<Error> for sp_blockchain::Error {
+	fn from(error: Error) -> Self {
+		match error {
+			Error::UnknownBlock(block) => sp_blockchain::Error::UnknownBlock(block),
+			Error::Client(error) => error,
+			error => sp_blockchain::Error::Application(Box::new(error)),
+		}
+	}
+}
+
+/// Failure of a single peer to answer a request.
+#[derive(Debug, thiserror::Error)]
+enum AttemptError {
+	#[error("Request failed: {0}.")]
+	Request(#[from] sc_network::request_responses::RequestFailure),
+	#[error("Failed to decode response: {0}.")]
+	DecodeProto(#[from] prost::DecodeError),
+	#[error("Failed to decode proof: {0}.")]
+	Codec(#[from] codec::Error),
+	#[error("Unexpected response.")]
+	UnexpectedResponse,
+	#[error("Remote couldn't answer the request.")]
+	Unavailable,
+	#[error("Invalid proof: {0}.")]
+	InvalidProof(String),
+}
+
+/// On-demand fetcher of the state of blocks known only by their headers.
+///
+/// Requests are sent to the peers reported by the syncing service, see [`Self::track_peers`].
+pub struct LightClientRequestSender<B, Client, Executor> {
+	client: Arc<Client>,
+	executor: Executor,
+	network: Arc<dyn NetworkRequest + Send + Sync>,
+	protocol_name: ProtocolName,
+	peers: Arc<Mutex<HashSet<PeerId>>>,
+	_block: std::marker::PhantomData<B>,
+}
+
+impl<B, Client, Executor: Clone> Clone for LightClientRequestSender<B, Client, Executor> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			executor: self.executor.clone(),
+			network: self.network.clone(),
+			protocol_name: self.protocol_name.clone(),
+			peers: self.peers.clone(),
+			_block: std::marker::PhantomData,
+		}
+	}
+}
+
+impl<B, Client, Executor> LightClientRequestSender<B, Client, Executor>
+where
+	B: Block,
+	Client: HeaderBackend<B> + 'static,
+	Executor: CodeExecutor + Clone + 'static,
+{
+	/// Create a new [`LightClientRequestSender`].
+	pub fn new<Hash: AsRef<[u8]>>(
+		client: Arc<Client>,
+		executor: Executor,
+		network: Arc<dyn NetworkRequest + Send + Sync>,
+		genesis_hash: Hash,
+		fork_id: Option<&str>,
+	) -> Self {
+		Self {
+			client,
+			executor,
+			network,
+			protocol_name: super::generate_protocol_name(genesis_hash, fork_id).into(),
+			peers: Default::default(),
+			_block: std::marker::PhantomData,
+		}
+	}
+
+	/// Keep track of the connected full peers, reported by the syncing service.
+	///
+	/// Must be spawned for requests to be sent out.
+	pub async fn track_peers(self, sync_service: Arc<SyncingService<B>>) {
+		let mut events = sync_service.event_stream("light-client-request-sender");
+		while let Some(event) = events.next().await {
+			match event {
+				SyncEvent::PeerConnected(peer) => {
+					// Light peers don't have the state to answer requests.
+					let is_full = sync_service.peers_info().await.map_or(false, |peers| {
+						peers.iter().any(|(peer_id, info)| *peer_id == peer && info.roles.is_full())
+					});
+					if is_full {
+						self.peers.lock().insert(peer);
+					}
+				},
+				SyncEvent::PeerDisconnected(peer) => {
+					self.peers.lock().remove(&peer);
+				},
+			}
+		}
+	}
+
+	/// Read the storage values of `keys` at block `at`.
+	pub async fn remote_read(
+		&self,
+		at: B::Hash,
+		keys: Vec<Vec<u8>>,
+	) -> Result<HashMap<Vec<u8>, Option<Vec<u8>>>, Error> {
+		let state_root = self.state_root(at)?;
+		let request = schema::v1::light::request::Request::RemoteReadRequest(
+			schema::v1::light::RemoteReadRequest { block: at.encode(), keys: keys.clone() },
+		);
+
+		self.send(request, |response| {
+			let proof = read_response_proof(response)?;
+			check_read_proof::<B>(state_root, proof, &keys)
+		})
+		.await
+	}
+
+	/// Read the child storage values of `keys` at block `at`.
+	pub async fn remote_read_child(
+		&self,
+		at: B::Hash,
+		storage_key: PrefixedStorageKey,
+		keys: Vec<Vec<u8>>,
+	) -> Result<HashMap<Vec<u8>, Option<Vec<u8>>>, Error> {
+		let child_info = match ChildType::from_prefixed_key(&storage_key) {
+			Some((ChildType::ParentKeyId, storage_key)) => ChildInfo::new_default(storage_key),
+			None => return Err(Error::InvalidChildStorageKey),
+		};
+		let state_root = self.state_root(at)?;
+		let request = schema::v1::light::request::Request::RemoteReadChildRequest(
+			schema::v1::light::RemoteReadChildRequest {
+				block: at.encode(),
+				storage_key: storage_key.into_inner(),
+				keys: keys.clone(),
+			},
+		);
+
+		self.send(request, |response| {
+			let proof = read_response_proof(response)?;
+			sp_state_machine::read_child_proof_check::<HashingFor<B>, _>(
+				state_root,
+				proof,
+				&child_info,
+				&keys,
+			)
+			.map_err(|e| AttemptError::InvalidProof(e.to_string()))
+		})
+		.await
+	}
+
+	/// Call the runtime `method` with `call_data` at block `at`, returning the encoded result.
+	pub async fn remote_call(
+		&self,
+		at: B::Hash,
+		method: &str,
+		call_data: Vec<u8>,
+	) -> Result<Vec<u8>, Error> {
+		let state_root = self.state_root(at)?;
+		let request = schema::v1::light::request::Request::RemoteCallRequest(
+			schema::v1::light::RemoteCallRequest {
+				block: at.encode(),
+				method: method.to_string(),
+				data: call_data.clone(),
+			},
+		);
+
+		self.send(request, |response| {
+			let proof = match response {
+				schema::v1::light::response::Response::RemoteCallResponse(response) =>
+					response.proof.ok_or(AttemptError::Unavailable)?,
+				_ => return Err(AttemptError::UnexpectedResponse),
+			};
+			let proof = StorageProof::decode(&mut &proof[..])?;
+			check_execution_proof::<B, _>(&self.executor, state_root, proof, method, &call_data)
+		})
+		.await
+	}
+
+	fn state_root(&self, at: B::Hash) -> Result<B::Hash, Error> {
+		self.client
+			.header(at)?
+			.map(|header| *header.state_root())
+			.ok_or_else(|| Error::UnknownBlock(at.to_string()))
+	}
+
+	/// Send the request to up to [`MAX_ATTEMPTS`] peers until one of them answers with a response
+	/// passing `check`.
+	async fn send<T>(
+		&self,
+		request: schema::v1::light::request::Request,
+		check: impl Fn(schema::v1::light::response::Response) -> Result<T, AttemptError>,
+	) -> Result<T, Error> {
+		let payload = schema::v1::light::Request { request: Some(request) }.encode_to_vec();
+		let peers = self.peers.lock().iter().take(MAX_ATTEMPTS).copied().collect::<Vec<_>>();
+
+		for peer in &peers {
+			let result = self
+				.network
+				.request(
+					*peer,
+					self.protocol_name.clone(),
+					payload.clone(),
+					None,
+					IfDisconnected::ImmediateError,
+				)
+				.await
+				.map_err(AttemptError::from)
+				.and_then(|(response, _)| {
+					schema::v1::light::Response::decode(&response[..])?
+						.response
+						.ok_or(AttemptError::UnexpectedResponse)
+				})
+				.and_then(&check);
+
+			match result {
+				Ok(result) => {
+					trace!(target: LOG_TARGET, "Light client request answered by {}.", peer);
+					return Ok(result)
+				},
+				Err(e) => {
+					debug!(target: LOG_TARGET, "Light client request to {} failed: {}", peer, e)
+				},
+			}
+		}
+
+		Err(Error::Unanswered(peers.len()))
+	}
+}
+
+impl<B, Client, Executor> RemoteStateFetcher<B> for LightClientRequestSender<B, Client, Executor>
+where
+	B: Block,
+	Client: HeaderBackend<B> + Send + Sync + 'static,
+	Executor: CodeExecutor + Clone + Send + Sync + 'static,
+{
+	fn remote_read(
+		&self,
+		at: B::Hash,
+		keys: Vec<Vec<u8>>,
+	) -> BoxFuture<'static, sp_blockchain::Result<HashMap<Vec<u8>, Option<Vec<u8>>>>> {
+		let this = self.clone();
+		async move { this.remote_read(at, keys).await.map_err(Into::into) }.boxed()
+	}
+
+	fn remote_read_child(
+		&self,
+		at: B::Hash,
+		storage_key: PrefixedStorageKey,
+		keys: Vec<Vec<u8>>,
+	) -> BoxFuture<'static, sp_blockchain::Result<HashMap<Vec<u8>, Option<Vec<u8>>>>> {
+		let this = self.clone();
+		async move { this.remote_read_child(at, storage_key, keys).await.map_err(Into::into) }
+			.boxed()
+	}
+
+	fn remote_call(
+		&self,
+		at: B::Hash,
+		method: String,
+		call_data: Vec<u8>,
+	) -> BoxFuture<'static, sp_blockchain::Result<Vec<u8>>> {
+		let this = self.clone();
+		async move { this.remote_call(at, &method, call_data).await.map_err(Into::into) }.boxed()
+	}
+}
+
+fn read_response_proof(
+	response: schema::v1::light::response::Response,
+) -> Result<StorageProof, AttemptError> {
+	match response {
+		schema::v1::light::response::Response::RemoteReadResponse(response) => {
+			let proof = response.proof.ok_or(AttemptError::Unavailable)?;
+			Ok(StorageProof::decode(&mut &proof[..])?)
+		},
+		_ => Err(AttemptError::UnexpectedResponse),
+	}
+}
+
+fn check_read_proof<B: Block>(
+	state_root: B::Hash,
+	proof: StorageProof,
+	keys: &[Vec<u8>],
+) -> Result<HashMap<Vec<u8>, Option<Vec<u8>>>, AttemptError> {
+	sp_state_machine::read_proof_check::<HashingFor<B>, _>(state_root, proof, keys)
+		.map_err(|e| AttemptError::InvalidProof(e.to_string()))
+}
+
+fn check_execution_proof<B: Block, Executor: CodeExecutor + Clone + 'static>(
+	executor: &Executor,
+	state_root: B::Hash,
+	proof: StorageProof,
+	method: &str,
+	call_data: &[u8],
+) -> Result<Vec<u8>, AttemptError> {
+	let trie_backend =
+		sp_state_machine::create_proof_check_backend::<HashingFor<B>>(state_root, proof)
+			.map_err(|e| AttemptError::InvalidProof(e.to_string()))?;
+	let runtime_code = BackendRuntimeCode::new(&trie_backend)
+		.runtime_code()
+		.map_err(|e| AttemptError::InvalidProof(e.to_string()))?;
+
+	sp_state_machine::execution_proof_check_on_trie_backend(
+		&trie_backend,
+		&mut OverlayedChanges::default(),
+		executor,
+		method,
+		call_data,
+		&runtime_code,
+	)
+	.map_err(|e| AttemptError::InvalidProof(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_client_api::ProofProvider;
+	use sp_core::storage::well_known_keys::CODE;
+	use substrate_test_runtime_client::{
+		runtime::Block, sc_executor::WasmExecutor, DefaultTestClientBuilderExt, TestClientBuilder,
+		TestClientBuilderExt,
+	};
+
+	#[test]
+	fn read_proof_is_checked_against_state_root() {
+		let client = TestClientBuilder::new().build();
+		let info = client.info();
+		let header = client.header(info.best_hash).unwrap().unwrap();
+		let keys = vec![CODE.to_vec(), b"missing".to_vec()];
+
+		let proof = client.read_proof(info.best_hash, &mut keys.iter().map(AsRef::as_ref)).unwrap();
+
+		// Values are read from a proof for the header state root.
+		let values = check_read_proof::<Block>(*header.state_root(), proof.clone(), &keys).unwrap();
+		assert!(values[CODE].is_some());
+		assert_eq!(values[&b"missing"[..]], None);
+
+		// The same proof doesn't match another state root.
+		assert!(matches!(
+			check_read_proof::<Block>(Default::default(), proof, &keys),
+			Err(AttemptError::InvalidProof(_))
+		));
+	}
+
+	#[test]
+	fn execution_proof_is_checked_against_state_root() {
+		let client = TestClientBuilder::new().build();
+		let info = client.info();
+		let header = client.header(info.best_hash).unwrap().unwrap();
+		let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder().build();
+
+		let (result, proof) = client.execution_proof(info.best_hash, "Core_version", &[]).unwrap();
+
+		// The call is re-executed on top of the proof.
+		assert_eq!(
+			check_execution_proof::<Block, _>(
+				&executor,
+				*header.state_root(),
+				proof.clone(),
+				"Core_version",
+				&[],
+			)
+			.unwrap(),
+			result,
+		);
+
+		// The same proof doesn't match another state root.
+		assert!(check_execution_proof::<Block, _>(
+			&executor,
+			Default::default(),
+			proof,
+			"Core_version",
+			&[],
+		)
+		.is_err());
+	}
+}
@@ -23,7 +23,10 @@
 
 pub use crate::{
 	discovery::DEFAULT_KADEMLIA_REPLICATION_FACTOR,
-	peer_store::PeerStoreProvider,
+	peer_store::{
+		persistence::{PeerStorePersistence, PEER_STORE_FILE},
+		PeerStoreProvider,
+	},
 	protocol::{notification_service, NotificationsSink, ProtocolHandlePair},
 	request_responses::{
 		IncomingRequest, OutgoingResponse, ProtocolConfig as RequestResponseConfig,
@@ -632,6 +635,9 @@ pub struct NetworkConfiguration {
 	/// Initial syncing mode.
 	pub sync_mode: SyncMode,
 
+	/// State snapshot imported when `sync_mode` is [`SyncMode::Checkpoint`].
+	pub checkpoint_snapshot: Option<PathBuf>,
+
 	/// True if Kademlia random discovery should be enabled.
 	///
 	/// If true, the node will automatically randomly walk the DHT in order to find new peers.
@@ -655,6 +661,12 @@ pub struct NetworkConfiguration {
 
 	/// Networking backend used for P2P communication.
 	pub network_backend: NetworkBackendType,
+
+	/// Persist known peers, their addresses and reputations in `net_config_path`, and reload them
+	/// when the node restarts.
+	///
+	/// Ignored if `net_config_path` is `None`.
+	pub persistent_peer_store: bool,
 }
 
 impl NetworkConfiguration {
@@ -681,6 +693,7 @@ impl NetworkConfiguration {
 			max_blocks_per_request: 64,
 			min_peers_to_start_warp_sync: None,
 			sync_mode: SyncMode::Full,
+			checkpoint_snapshot: None,
 			enable_dht_random_walk: true,
 			allow_non_globals_in_dht: false,
 			kademlia_disjoint_query_paths: false,
@@ -688,6 +701,7 @@ impl NetworkConfiguration {
 				.expect("value is a constant; constant is non-zero; qed."),
 			ipfs_server: false,
 			network_backend: NetworkBackendType::Litep2p,
+			persistent_peer_store: false,
 		}
 	}
 
@@ -781,7 +795,12 @@ impl<B: BlockT + 'static, H: ExHashT, N: NetworkBackend<B, H>> FullNetworkConfig
 	/// Create new [`FullNetworkConfiguration`].
 	pub fn new(network_config: &NetworkConfiguration, metrics_registry: Option<Registry>) -> Self {
 		let bootnodes = network_config.boot_nodes.iter().map(|bootnode| bootnode.peer_id).collect();
-		let peer_store = N::peer_store(bootnodes, metrics_registry.clone());
+		let persistence = network_config
+			.net_config_path
+			.as_ref()
+			.filter(|_| network_config.persistent_peer_store)
+			.map(|path| PeerStorePersistence::new(path.join(PEER_STORE_FILE)));
+		let peer_store = N::peer_store(bootnodes, metrics_registry.clone(), persistence);
 		let peer_store_handle = peer_store.handle();
 
 		Self {
@@ -38,7 +38,7 @@ use crate::{
 			request_response::{RequestResponseConfig, RequestResponseProtocol},
 		},
 	},
-	peer_store::PeerStoreProvider,
+	peer_store::{persistence::PeerStorePersistence, PeerStoreProvider},
 	service::{
 		metrics::{register_without_sources, MetricSources, Metrics, NotificationMetrics},
 		out_events,
@@ -479,9 +479,15 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 			},
 		);
 
-		// collect known addresses
-		let known_addresses: HashMap<litep2p::PeerId, Vec<Multiaddr>> =
-			known_addresses.into_iter().fold(HashMap::new(), |mut acc, (peer, address)| {
+		// collect known addresses, including the addresses of peers known from a previous run
+		let persisted_addresses =
+			peer_store_handle.peer_addresses().into_iter().flat_map(|(peer, addresses)| {
+				addresses.into_iter().map(move |address| (peer, address))
+			});
+		let known_addresses: HashMap<litep2p::PeerId, Vec<Multiaddr>> = known_addresses
+			.into_iter()
+			.chain(persisted_addresses)
+			.fold(HashMap::new(), |mut acc, (peer, address)| {
 				use sc_network_types::multiaddr::Protocol;
 
 				let address = match address.iter().last() {
@@ -595,8 +601,14 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 	fn peer_store(
 		bootnodes: Vec<sc_network_types::PeerId>,
 		metrics_registry: Option<Registry>,
+		persistence: Option<PeerStorePersistence>,
 	) -> Self::PeerStore {
-		Peerstore::new(bootnodes, metrics_registry)
+		let peerstore = Peerstore::new(bootnodes, metrics_registry);
+
+		match persistence {
+			Some(persistence) => peerstore.with_persistence(persistence),
+			None => peerstore,
+		}
 	}
 
 	fn register_notification_metrics(registry: Option<&Registry>) -> NotificationMetrics {
@@ -1107,6 +1119,11 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkBackend<B, H> for Litep2pNetworkBac
 				},
 				event = self.litep2p.next_event() => match event {
 					Some(Litep2pEvent::ConnectionEstablished { peer, endpoint }) => {
+						// remember the addresses we could dial the peer at
+						if let Endpoint::Dialer { address, .. } = &endpoint {
+							self.peerstore_handle.add_peer_address(peer.into(), address.clone().into());
+						}
+
 						let Some(metrics) = &self.metrics else {
 							continue;
 						};
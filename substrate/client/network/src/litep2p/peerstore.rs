@@ -22,7 +22,10 @@
 //! such as their addresses, reputations, supported protocols etc.
 
 use crate::{
-	peer_store::{PeerStoreProvider, ProtocolHandle},
+	peer_store::{
+		persistence::{PeerStorePersistence, PersistedPeer, MAX_PEER_ADDRESSES, PERSIST_INTERVAL},
+		PeerStoreProvider, ProtocolHandle,
+	},
 	service::{metrics::PeerStoreMetrics, traits::PeerStore},
 	ObservedRole, ReputationChange,
 };
@@ -31,7 +34,7 @@ use parking_lot::Mutex;
 use prometheus_endpoint::Registry;
 use wasm_timer::Delay;
 
-use sc_network_types::PeerId;
+use sc_network_types::{multiaddr::Multiaddr, PeerId};
 
 use std::{
 	collections::{HashMap, HashSet},
@@ -63,7 +66,7 @@ const INVERSE_DECREMENT: i32 = 200;
 const FORGET_AFTER: Duration = Duration::from_secs(3600);
 
 /// Peer information.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct PeerInfo {
 	/// Reputation of the peer.
 	reputation: i32,
@@ -73,11 +76,14 @@ struct PeerInfo {
 
 	/// Role of the peer, if known.
 	role: Option<ObservedRole>,
+
+	/// Addresses the peer was reachable at, oldest first.
+	addresses: Vec<Multiaddr>,
 }
 
 impl Default for PeerInfo {
 	fn default() -> Self {
-		Self { reputation: 0i32, last_updated: Instant::now(), role: None }
+		Self { reputation: 0i32, last_updated: Instant::now(), role: None, addresses: Vec::new() }
 	}
 }
 
@@ -113,6 +119,17 @@ impl PeerInfo {
 	fn bump_last_updated(&mut self) {
 		self.last_updated = Instant::now();
 	}
+
+	fn add_address(&mut self, address: Multiaddr) {
+		if self.addresses.contains(&address) {
+			return
+		}
+
+		if self.addresses.len() >= MAX_PEER_ADDRESSES {
+			self.addresses.remove(0);
+		}
+		self.addresses.push(address);
+	}
 }
 
 #[derive(Debug, Default)]
@@ -137,10 +154,7 @@ impl PeerstoreHandle {
 
 	/// Add known peer to [`Peerstore`].
 	pub fn add_known_peer(&self, peer: PeerId) {
-		self.0
-			.lock()
-			.peers
-			.insert(peer, PeerInfo { reputation: 0i32, last_updated: Instant::now(), role: None });
+		self.0.lock().peers.insert(peer, PeerInfo::default());
 	}
 
 	pub fn peer_count(&self) -> usize {
@@ -174,6 +188,34 @@ impl PeerstoreHandle {
 			metrics.num_banned_peers.set(num_banned_peers);
 		}
 	}
+
+	/// Add the persisted `peers`, decaying their reputation by `seconds_passed`.
+	fn load_peers(&self, peers: Vec<PersistedPeer>, seconds_passed: u64) {
+		let mut lock = self.0.lock();
+
+		for peer in peers {
+			let info = lock.peers.entry(peer.peer_id).or_default();
+			info.reputation = peer.reputation;
+			info.decay_reputation(seconds_passed);
+			info.role = info.role.or(peer.role);
+			peer.addresses.into_iter().for_each(|address| info.add_address(address));
+		}
+	}
+
+	/// Get the peers to persist.
+	fn persisted_peers(&self) -> Vec<PersistedPeer> {
+		self.0
+			.lock()
+			.peers
+			.iter()
+			.map(|(peer, info)| PersistedPeer {
+				peer_id: *peer,
+				reputation: info.reputation,
+				role: info.role,
+				addresses: info.addresses.clone(),
+			})
+			.collect()
+	}
 }
 
 impl PeerStoreProvider for PeerstoreHandle {
@@ -290,6 +332,22 @@ impl PeerStoreProvider for PeerstoreHandle {
 	fn add_known_peer(&self, peer: PeerId) {
 		self.0.lock().peers.entry(peer).or_default().last_updated = Instant::now();
 	}
+
+	/// Add an address the peer was reachable at, to be persisted across restarts.
+	fn add_peer_address(&self, peer: PeerId, address: Multiaddr) {
+		self.0.lock().peers.entry(peer).or_default().add_address(address);
+	}
+
+	/// Get the addresses of known peers, e.g. loaded from the persisted peer store.
+	fn peer_addresses(&self) -> Vec<(PeerId, Vec<Multiaddr>)> {
+		self.0
+			.lock()
+			.peers
+			.iter()
+			.filter(|(_, info)| !info.addresses.is_empty())
+			.map(|(peer, info)| (*peer, info.addresses.clone()))
+			.collect()
+	}
 }
 
 /// `Peerstore` handle for testing.
@@ -304,6 +362,9 @@ pub fn peerstore_handle_test() -> PeerstoreHandle {
 pub struct Peerstore {
 	/// Handle to `Peerstore`.
 	peerstore_handle: PeerstoreHandle,
+
+	/// Persistence of `Peerstore`, if enabled.
+	persistence: Option<PeerStorePersistence>,
 }
 
 impl Peerstore {
@@ -326,7 +387,36 @@ impl Peerstore {
 			metrics,
 		);
 
-		Self { peerstore_handle }
+		Self { peerstore_handle, persistence: None }
+	}
+
+	/// Load the peers persisted by `persistence`, and persist [`Peerstore`] periodically and
+	/// when it is dropped.
+	pub fn with_persistence(mut self, persistence: PeerStorePersistence) -> Self {
+		let (peers, seconds_passed) = persistence.load();
+		log::info!(
+			target: LOG_TARGET,
+			"Loaded {} peers from {}",
+			peers.len(),
+			persistence.path().display(),
+		);
+
+		self.peerstore_handle.load_peers(peers, seconds_passed);
+		self.persistence = Some(persistence);
+		self
+	}
+
+	/// Persist [`Peerstore`], if enabled.
+	fn persist(&self) {
+		let Some(persistence) = &self.persistence else { return };
+
+		if let Err(err) = persistence.save(self.peerstore_handle.persisted_peers()) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to persist peer store to {}: {err}",
+				persistence.path().display(),
+			);
+		}
 	}
 
 	/// Get mutable reference to the underlying [`PeerstoreHandle`].
@@ -343,6 +433,7 @@ impl Peerstore {
 	async fn run(self) {
 		let started = Instant::now();
 		let mut latest_time_update = started;
+		let mut latest_persist = started;
 
 		loop {
 			let now = Instant::now();
@@ -356,11 +447,24 @@ impl Peerstore {
 			};
 
 			self.peerstore_handle.progress_time(seconds_passed);
+
+			if now - latest_persist >= PERSIST_INTERVAL {
+				latest_persist = now;
+				self.persist();
+			}
+
 			let _ = Delay::new(Duration::from_secs(1)).await;
 		}
 	}
 }
 
+impl Drop for Peerstore {
+	fn drop(&mut self) {
+		// `Peerstore` task is dropped when the node shuts down.
+		self.persist();
+	}
+}
+
 #[async_trait::async_trait]
 impl PeerStore for Peerstore {
 	/// Get handle to `PeerStore`.
@@ -23,14 +23,20 @@ use crate::{
 	ReputationChange,
 };
 
+use parking_lot::Mutex;
 use sc_network_common::role::ObservedRole;
-use sc_network_types::PeerId;
+use sc_network_types::{multiaddr::Multiaddr, PeerId};
 
-use std::{collections::HashSet, sync::Arc};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+};
 
-/// No-op `PeerStore`.
-#[derive(Debug)]
-pub struct MockPeerStore {}
+/// No-op `PeerStore` that only records the addresses of peers.
+#[derive(Debug, Default)]
+pub struct MockPeerStore {
+	addresses: Mutex<HashMap<PeerId, Vec<Multiaddr>>>,
+}
 
 impl PeerStoreProvider for MockPeerStore {
 	fn is_banned(&self, _peer_id: &PeerId) -> bool {
@@ -70,4 +76,20 @@ impl PeerStoreProvider for MockPeerStore {
 	fn add_known_peer(&self, _peer_id: PeerId) {
 		unimplemented!()
 	}
+
+	fn add_peer_address(&self, peer_id: PeerId, address: Multiaddr) {
+		let mut addresses = self.addresses.lock();
+		let addresses = addresses.entry(peer_id).or_default();
+		if !addresses.contains(&address) {
+			addresses.push(address);
+		}
+	}
+
+	fn peer_addresses(&self) -> Vec<(PeerId, Vec<Multiaddr>)> {
+		self.addresses
+			.lock()
+			.iter()
+			.map(|(peer_id, addresses)| (*peer_id, addresses.clone()))
+			.collect()
+	}
 }
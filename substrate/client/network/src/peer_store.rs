@@ -19,7 +19,10 @@
 //! [`PeerStore`] manages peer reputations and provides connection candidates to
 //! [`crate::protocol_controller::ProtocolController`].
 
+pub mod persistence;
+
 use crate::service::{metrics::PeerStoreMetrics, traits::PeerStore as PeerStoreT};
+use persistence::{PeerStorePersistence, PersistedPeer, MAX_PEER_ADDRESSES, PERSIST_INTERVAL};
 
 use libp2p::PeerId;
 use log::trace;
@@ -27,6 +30,7 @@ use parking_lot::Mutex;
 use partial_sort::PartialSort;
 use prometheus_endpoint::Registry;
 use sc_network_common::{role::ObservedRole, types::ReputationChange};
+use sc_network_types::multiaddr::Multiaddr;
 use std::{
 	cmp::{Ord, Ordering, PartialOrd},
 	collections::{hash_map::Entry, HashMap, HashSet},
@@ -98,6 +102,18 @@ pub trait PeerStoreProvider: Debug + Send + Sync {
 
 	/// Add known peer.
 	fn add_known_peer(&self, peer_id: sc_network_types::PeerId);
+
+	/// Add an address the peer was reachable at, to be persisted across restarts.
+	///
+	/// Peer stores that do not keep addresses ignore it.
+	fn add_peer_address(&self, _peer_id: sc_network_types::PeerId, _address: Multiaddr) {}
+
+	/// Get the addresses of known peers, e.g. loaded from the persisted peer store.
+	///
+	/// Peer stores that do not keep addresses return none.
+	fn peer_addresses(&self) -> Vec<(sc_network_types::PeerId, Vec<Multiaddr>)> {
+		Vec::new()
+	}
 }
 
 /// Actual implementation of peer reputations and connection candidates provider.
@@ -154,9 +170,23 @@ impl PeerStoreProvider for PeerStoreHandle {
 	fn add_known_peer(&self, peer_id: sc_network_types::PeerId) {
 		self.inner.lock().add_known_peer(peer_id.into());
 	}
+
+	fn add_peer_address(&self, peer_id: sc_network_types::PeerId, address: Multiaddr) {
+		self.inner.lock().add_peer_address(peer_id.into(), address);
+	}
+
+	fn peer_addresses(&self) -> Vec<(sc_network_types::PeerId, Vec<Multiaddr>)> {
+		self.inner
+			.lock()
+			.peers
+			.iter()
+			.filter(|(_, info)| !info.addresses.is_empty())
+			.map(|(peer_id, info)| (peer_id.into(), info.addresses.clone()))
+			.collect()
+	}
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct PeerInfo {
 	/// Reputation of the peer.
 	reputation: i32,
@@ -166,11 +196,14 @@ struct PeerInfo {
 
 	/// Role of the peer, if known.
 	role: Option<ObservedRole>,
+
+	/// Addresses the peer was reachable at, oldest first.
+	addresses: Vec<Multiaddr>,
 }
 
 impl Default for PeerInfo {
 	fn default() -> Self {
-		Self { reputation: 0, last_updated: Instant::now(), role: None }
+		Self { reputation: 0, last_updated: Instant::now(), role: None, addresses: Vec::new() }
 	}
 }
 
@@ -227,6 +260,17 @@ impl PeerInfo {
 	fn bump_last_updated(&mut self) {
 		self.last_updated = Instant::now();
 	}
+
+	fn add_address(&mut self, address: Multiaddr) {
+		if self.addresses.contains(&address) {
+			return
+		}
+
+		if self.addresses.len() >= MAX_PEER_ADDRESSES {
+			self.addresses.remove(0);
+		}
+		self.addresses.push(address);
+	}
 }
 
 #[derive(Debug)]
@@ -342,7 +386,7 @@ impl PeerStoreInner {
 			.peers
 			.iter()
 			.filter_map(|(peer_id, info)| {
-				(!info.is_banned() && !ignored.contains(peer_id)).then_some((*peer_id, *info))
+				(!info.is_banned() && !ignored.contains(peer_id)).then_some((*peer_id, info))
 			})
 			.collect::<Vec<_>>();
 		let count = std::cmp::min(count, candidates.len());
@@ -394,12 +438,40 @@ impl PeerStoreInner {
 			},
 		}
 	}
+
+	fn add_peer_address(&mut self, peer_id: PeerId, address: Multiaddr) {
+		trace!(target: LOG_TARGET, "Add address {address} of {peer_id}.");
+		self.peers.entry(peer_id).or_default().add_address(address);
+	}
+
+	fn load_peers(&mut self, peers: Vec<PersistedPeer>, seconds_passed: u64) {
+		for peer in peers {
+			let info = self.peers.entry(peer.peer_id.into()).or_default();
+			info.reputation = peer.reputation;
+			info.decay_reputation(seconds_passed);
+			info.role = info.role.or(peer.role);
+			peer.addresses.into_iter().for_each(|address| info.add_address(address));
+		}
+	}
+
+	fn persisted_peers(&self) -> Vec<PersistedPeer> {
+		self.peers
+			.iter()
+			.map(|(peer_id, info)| PersistedPeer {
+				peer_id: peer_id.into(),
+				reputation: info.reputation,
+				role: info.role,
+				addresses: info.addresses.clone(),
+			})
+			.collect()
+	}
 }
 
 /// Worker part of [`PeerStoreHandle`]
 #[derive(Debug)]
 pub struct PeerStore {
 	inner: Arc<Mutex<PeerStoreInner>>,
+	persistence: Option<PeerStorePersistence>,
 }
 
 impl PeerStore {
@@ -425,6 +497,37 @@ impl PeerStore {
 				protocols: Vec::new(),
 				metrics,
 			})),
+			persistence: None,
+		}
+	}
+
+	/// Load the peers persisted by `persistence`, and persist the peer store periodically and
+	/// when it is dropped.
+	pub fn with_persistence(mut self, persistence: PeerStorePersistence) -> Self {
+		let (peers, seconds_passed) = persistence.load();
+		log::info!(
+			target: LOG_TARGET,
+			"Loaded {} peers from {}",
+			peers.len(),
+			persistence.path().display(),
+		);
+
+		self.inner.lock().load_peers(peers, seconds_passed);
+		self.persistence = Some(persistence);
+		self
+	}
+
+	/// Persist the peer store, if enabled.
+	fn persist(&self) {
+		let Some(persistence) = &self.persistence else { return };
+
+		let peers = self.inner.lock().persisted_peers();
+		if let Err(err) = persistence.save(peers) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to persist peer store to {}: {err}",
+				persistence.path().display(),
+			);
 		}
 	}
 
@@ -437,6 +540,7 @@ impl PeerStore {
 	pub async fn run(self) {
 		let started = Instant::now();
 		let mut latest_time_update = started;
+		let mut latest_persist = started;
 
 		loop {
 			let now = Instant::now();
@@ -450,11 +554,24 @@ impl PeerStore {
 			};
 
 			self.inner.lock().progress_time(seconds_passed);
+
+			if now - latest_persist >= PERSIST_INTERVAL {
+				latest_persist = now;
+				self.persist();
+			}
+
 			let _ = Delay::new(Duration::from_secs(1)).await;
 		}
 	}
 }
 
+impl Drop for PeerStore {
+	fn drop(&mut self) {
+		// The peer store task is dropped when the node shuts down.
+		self.persist();
+	}
+}
+
 #[async_trait::async_trait]
 impl PeerStoreT for PeerStore {
 	fn handle(&self) -> Arc<dyn PeerStoreProvider> {
@@ -468,7 +585,12 @@ impl PeerStoreT for PeerStore {
 
 #[cfg(test)]
 mod tests {
-	use super::{PeerInfo, PeerStore, PeerStoreProvider};
+	use super::{
+		persistence::{PeerStorePersistence, PEER_STORE_FILE},
+		PeerInfo, PeerStore, PeerStoreProvider,
+	};
+	use sc_network_common::{role::ObservedRole, types::ReputationChange};
+	use sc_network_types::multiaddr::Multiaddr;
 
 	#[test]
 	fn decaying_zero_reputation_yields_zero() {
@@ -570,4 +692,33 @@ mod tests {
 		assert_eq!(metrics.num_discovered.get(), 3);
 		assert_eq!(metrics.num_banned_peers.get(), 2);
 	}
+
+	#[test]
+	fn peer_store_is_persisted_across_restarts() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(PEER_STORE_FILE);
+		let banned = sc_network_types::PeerId::random();
+		let good = sc_network_types::PeerId::random();
+		let address: Multiaddr = "/ip4/127.0.0.1/tcp/30333".parse().unwrap();
+
+		{
+			let peerstore = PeerStore::new(vec![], None)
+				.with_persistence(PeerStorePersistence::new(path.clone()));
+			let handle = peerstore.handle();
+			handle.report_peer(banned, ReputationChange { value: i32::MIN, reason: "test".into() });
+			handle.report_peer(good, ReputationChange { value: 1000, reason: "test".into() });
+			handle.set_peer_role(&good, ObservedRole::Authority);
+			handle.add_peer_address(good, address.clone());
+
+			// The peer store is persisted when dropped.
+		}
+
+		let peerstore =
+			PeerStore::new(vec![], None).with_persistence(PeerStorePersistence::new(path));
+		let handle = peerstore.handle();
+		assert!(handle.is_banned(&banned));
+		assert!((1..=1000).contains(&handle.peer_reputation(&good)));
+		assert_eq!(handle.peer_role(&good), Some(ObservedRole::Authority));
+		assert_eq!(handle.peer_addresses(), vec![(good, vec![address])]);
+	}
 }
@@ -0,0 +1,335 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Persistence of the peer store across node restarts.
+//!
+//! When enabled, the peer store is periodically written to a JSON file in the network
+//! configuration directory, and loaded again when the node starts. This way, peers banned for
+//! misbehaviour stay banned, and good peers and their addresses are not forgotten across restarts.
+//!
+//! The file is bounded to [`MAX_PERSISTED_PEERS`] peers with the highest absolute reputation, so
+//! that both banned and well-behaved peers are kept. Loading is tolerant to corruption: an
+//! unreadable file is ignored, and invalid entries are skipped.
+
+use crate::peer_store::LOG_TARGET;
+
+use sc_network_common::role::ObservedRole;
+use sc_network_types::{multiaddr::Multiaddr, PeerId};
+use serde::{Deserialize, Serialize};
+
+use std::{
+	cmp::Reverse,
+	fs, io,
+	path::{Path, PathBuf},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the file the peer store is persisted to, in the network configuration directory.
+pub const PEER_STORE_FILE: &str = "peer_store.json";
+
+/// Maximum number of persisted peers.
+pub const MAX_PERSISTED_PEERS: usize = 2048;
+
+/// Maximum number of addresses that are kept per peer.
+pub const MAX_PEER_ADDRESSES: usize = 4;
+
+/// Interval at which the peer store is persisted.
+pub const PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Version of the format of the persisted peer store.
+const VERSION: u32 = 1;
+
+/// A peer as persisted by the peer store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersistedPeer {
+	/// Peer ID.
+	pub peer_id: PeerId,
+
+	/// Reputation of the peer, at the time it was persisted.
+	pub reputation: i32,
+
+	/// Role of the peer, if known.
+	pub role: Option<ObservedRole>,
+
+	/// Addresses the peer was reachable at, oldest first.
+	pub addresses: Vec<Multiaddr>,
+}
+
+/// The persisted peer store.
+#[derive(Serialize, Deserialize)]
+struct PeerStoreFile<P> {
+	/// Version of the format.
+	version: u32,
+
+	/// Seconds since the UNIX epoch at which the peer store was persisted.
+	saved_at: u64,
+
+	/// The persisted peers.
+	peers: Vec<P>,
+}
+
+/// A persisted peer, as stored in [`PeerStoreFile`].
+#[derive(Serialize, Deserialize)]
+struct PeerEntry {
+	peer_id: String,
+	#[serde(default)]
+	reputation: i32,
+	#[serde(default)]
+	role: Option<String>,
+	#[serde(default)]
+	addresses: Vec<String>,
+}
+
+impl From<PersistedPeer> for PeerEntry {
+	fn from(peer: PersistedPeer) -> Self {
+		PeerEntry {
+			peer_id: peer.peer_id.to_base58(),
+			reputation: peer.reputation,
+			role: peer.role.map(|role| {
+				match role {
+					ObservedRole::Full => "full",
+					ObservedRole::Light => "light",
+					ObservedRole::Authority => "authority",
+				}
+				.to_string()
+			}),
+			addresses: peer.addresses.iter().map(ToString::to_string).collect(),
+		}
+	}
+}
+
+impl TryFrom<PeerEntry> for PersistedPeer {
+	type Error = ();
+
+	fn try_from(entry: PeerEntry) -> Result<Self, ()> {
+		let peer_id = entry.peer_id.parse().map_err(|_| ())?;
+		let role = match entry.role.as_deref() {
+			None => None,
+			Some("full") => Some(ObservedRole::Full),
+			Some("light") => Some(ObservedRole::Light),
+			Some("authority") => Some(ObservedRole::Authority),
+			Some(_) => return Err(()),
+		};
+		// Invalid addresses are skipped rather than invalidating the whole entry.
+		let addresses = entry
+			.addresses
+			.iter()
+			.filter_map(|address| address.parse().ok())
+			.take(MAX_PEER_ADDRESSES)
+			.collect();
+
+		Ok(PersistedPeer { peer_id, reputation: entry.reputation, role, addresses })
+	}
+}
+
+/// Seconds since the UNIX epoch.
+fn now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs())
+}
+
+/// Loads and saves the peer store from and to a file.
+#[derive(Debug, Clone)]
+pub struct PeerStorePersistence {
+	path: PathBuf,
+}
+
+impl PeerStorePersistence {
+	/// Create new [`PeerStorePersistence`] persisting to `path`.
+	pub fn new(path: PathBuf) -> Self {
+		Self { path }
+	}
+
+	/// Path of the persisted peer store.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	/// Load the persisted peers, along with the number of seconds since they were persisted.
+	///
+	/// Returns no peers if the file does not exist or is corrupt.
+	pub fn load(&self) -> (Vec<PersistedPeer>, u64) {
+		let data = match fs::read(&self.path) {
+			Ok(data) => data,
+			Err(err) if err.kind() == io::ErrorKind::NotFound => return (Vec::new(), 0),
+			Err(err) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to read peer store from {}: {err}",
+					self.path.display(),
+				);
+				return (Vec::new(), 0)
+			},
+		};
+
+		// Entries are decoded one by one below, so that an invalid one doesn't invalidate the
+		// others.
+		let file: PeerStoreFile<serde_json::Value> = match serde_json::from_slice(&data) {
+			Ok(file) => file,
+			Err(err) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Ignoring corrupt peer store {}: {err}",
+					self.path.display(),
+				);
+				return (Vec::new(), 0)
+			},
+		};
+		if file.version != VERSION {
+			log::warn!(
+				target: LOG_TARGET,
+				"Ignoring peer store {} of unsupported version {}",
+				self.path.display(),
+				file.version,
+			);
+			return (Vec::new(), 0)
+		}
+
+		let total = file.peers.len();
+		let peers = file
+			.peers
+			.into_iter()
+			.filter_map(|entry| serde_json::from_value::<PeerEntry>(entry).ok()?.try_into().ok())
+			.take(MAX_PERSISTED_PEERS)
+			.collect::<Vec<_>>();
+		if peers.len() < total.min(MAX_PERSISTED_PEERS) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Skipped {} invalid entries of peer store {}",
+				total.min(MAX_PERSISTED_PEERS) - peers.len(),
+				self.path.display(),
+			);
+		}
+
+		log::debug!(
+			target: LOG_TARGET,
+			"Loaded {} peers from {}",
+			peers.len(),
+			self.path.display(),
+		);
+
+		(peers, now().saturating_sub(file.saved_at))
+	}
+
+	/// Persist the [`MAX_PERSISTED_PEERS`] of `peers` with the highest absolute reputation.
+	///
+	/// The file is replaced atomically, so that a crash while saving doesn't corrupt it.
+	pub fn save(&self, mut peers: Vec<PersistedPeer>) -> io::Result<()> {
+		peers.sort_by_key(|peer| {
+			(Reverse(peer.reputation.unsigned_abs()), peer.addresses.is_empty())
+		});
+		peers.truncate(MAX_PERSISTED_PEERS);
+
+		let file = PeerStoreFile {
+			version: VERSION,
+			saved_at: now(),
+			peers: peers.into_iter().map(PeerEntry::from).collect(),
+		};
+		let data = serde_json::to_vec(&file)?;
+
+		if let Some(parent) = self.path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		let tmp_path = self.path.with_extension("json.tmp");
+		fs::write(&tmp_path, data)?;
+		fs::rename(&tmp_path, &self.path)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn peer(reputation: i32) -> PersistedPeer {
+		PersistedPeer {
+			peer_id: PeerId::random(),
+			reputation,
+			role: Some(ObservedRole::Authority),
+			addresses: vec!["/ip4/127.0.0.1/tcp/30333".parse().unwrap()],
+		}
+	}
+
+	#[test]
+	fn save_and_load_works() {
+		let dir = tempfile::tempdir().unwrap();
+		let persistence = PeerStorePersistence::new(dir.path().join(PEER_STORE_FILE));
+		assert_eq!(persistence.load(), (Vec::new(), 0));
+
+		let peers = vec![peer(i32::MIN), peer(100)];
+		persistence.save(peers.clone()).unwrap();
+
+		let (loaded, elapsed) = persistence.load();
+		assert_eq!(loaded, peers);
+		assert!(elapsed <= 1);
+	}
+
+	#[test]
+	fn save_keeps_peers_with_highest_absolute_reputation() {
+		let dir = tempfile::tempdir().unwrap();
+		let persistence = PeerStorePersistence::new(dir.path().join(PEER_STORE_FILE));
+
+		let banned = peer(i32::MIN);
+		let good = peer(i32::MAX);
+		let mut peers = vec![banned.clone(), good.clone()];
+		peers.extend((0..MAX_PERSISTED_PEERS).map(|_| peer(0)));
+		persistence.save(peers).unwrap();
+
+		let (loaded, _) = persistence.load();
+		assert_eq!(loaded.len(), MAX_PERSISTED_PEERS);
+		assert!(loaded.contains(&banned));
+		assert!(loaded.contains(&good));
+	}
+
+	#[test]
+	fn corrupt_peer_store_is_ignored() {
+		let dir = tempfile::tempdir().unwrap();
+		let persistence = PeerStorePersistence::new(dir.path().join(PEER_STORE_FILE));
+
+		fs::write(persistence.path(), b"{\"version\":1,\"saved_at\":").unwrap();
+		assert_eq!(persistence.load(), (Vec::new(), 0));
+
+		fs::write(persistence.path(), b"{\"version\":2,\"saved_at\":0,\"peers\":[]}").unwrap();
+		assert_eq!(persistence.load(), (Vec::new(), 0));
+	}
+
+	#[test]
+	fn invalid_entries_are_skipped() {
+		let dir = tempfile::tempdir().unwrap();
+		let persistence = PeerStorePersistence::new(dir.path().join(PEER_STORE_FILE));
+
+		let valid = peer(-100);
+		let file = serde_json::json!({
+			"version": VERSION,
+			"saved_at": 0,
+			"peers": [
+				{ "peer_id": "not a peer id", "reputation": 10 },
+				{ "peer_id": PeerId::random().to_base58(), "reputation": "not a number" },
+				{
+					"peer_id": valid.peer_id.to_base58(),
+					"reputation": -100,
+					"role": "authority",
+					"addresses": ["not an address", "/ip4/127.0.0.1/tcp/30333"],
+				},
+			],
+		});
+		fs::write(persistence.path(), serde_json::to_vec(&file).unwrap()).unwrap();
+
+		let (loaded, elapsed) = persistence.load();
+		assert_eq!(loaded, vec![valid]);
+		assert!(elapsed > 0);
+	}
+}
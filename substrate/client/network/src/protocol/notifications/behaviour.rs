@@ -2441,7 +2441,7 @@ mod tests {
 				reserved_only: false,
 			},
 			to_notifications,
-			Arc::new(MockPeerStore {}),
+			Arc::new(MockPeerStore::default()),
 		);
 
 		let (notif_handle, command_stream) = protocol_handle_pair.split();
@@ -879,6 +879,8 @@ mod tests {
 			fn peer_role(&self, peer_id: &sc_network_types::PeerId) -> Option<ObservedRole>;
 			fn outgoing_candidates(&self, count: usize, ignored: HashSet<sc_network_types::PeerId>) -> Vec<sc_network_types::PeerId>;
 			fn add_known_peer(&self, peer_id: sc_network_types::PeerId);
+			fn add_peer_address(&self, peer_id: sc_network_types::PeerId, address: sc_network_types::multiaddr::Multiaddr);
+			fn peer_addresses(&self) -> Vec<(sc_network_types::PeerId, Vec<sc_network_types::multiaddr::Multiaddr>)>;
 		}
 	}
 
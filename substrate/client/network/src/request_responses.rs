@@ -1190,7 +1190,8 @@ mod tests {
 			.multiplex(libp2p::yamux::Config::default())
 			.boxed();
 
-		let behaviour = RequestResponsesBehaviour::new(list, Arc::new(MockPeerStore {})).unwrap();
+		let behaviour =
+			RequestResponsesBehaviour::new(list, Arc::new(MockPeerStore::default())).unwrap();
 
 		let mut swarm = Swarm::new(
 			transport,
@@ -40,7 +40,7 @@ use crate::{
 	network_state::{
 		NetworkState, NotConnectedPeer as NetworkStateNotConnectedPeer, Peer as NetworkStatePeer,
 	},
-	peer_store::{PeerStore, PeerStoreProvider},
+	peer_store::{persistence::PeerStorePersistence, PeerStore, PeerStoreProvider},
 	protocol::{self, Protocol, Ready},
 	protocol_controller::{self, ProtoSetConfig, ProtocolController, SetId},
 	request_responses::{IfDisconnected, ProtocolConfig as RequestResponseConfig, RequestFailure},
@@ -189,8 +189,15 @@ where
 	fn peer_store(
 		bootnodes: Vec<sc_network_types::PeerId>,
 		metrics_registry: Option<Registry>,
+		persistence: Option<PeerStorePersistence>,
 	) -> Self::PeerStore {
-		PeerStore::new(bootnodes.into_iter().map(From::from).collect(), metrics_registry)
+		let peer_store =
+			PeerStore::new(bootnodes.into_iter().map(From::from).collect(), metrics_registry);
+
+		match persistence {
+			Some(persistence) => peer_store.with_persistence(persistence),
+			None => peer_store,
+		}
 	}
 
 	fn register_notification_metrics(registry: Option<&Registry>) -> NotificationMetrics {
@@ -581,6 +588,13 @@ where
 			Swarm::<Behaviour<B>>::add_external_address(&mut swarm, addr.clone().into());
 		}
 
+		// Add addresses of peers known from a previous run.
+		for (peer_id, addresses) in peer_store_handle.peer_addresses() {
+			for addr in addresses {
+				swarm.behaviour_mut().add_known_address(peer_id.into(), addr.into());
+			}
+		}
+
 		let listen_addresses_set = Arc::new(Mutex::new(HashSet::new()));
 
 		let service = Arc::new(NetworkService {
@@ -1709,6 +1723,11 @@ where
 					debug!(target: LOG_TARGET, "Libp2p => Connected({:?})", peer_id);
 				}
 
+				// Remember the addresses we could dial the peer at.
+				if let ConnectedPoint::Dialer { address, .. } = &endpoint {
+					self.peer_store_handle.add_peer_address(peer_id.into(), address.clone().into());
+				}
+
 				if let Some(metrics) = self.metrics.as_ref() {
 					let direction = match endpoint {
 						ConnectedPoint::Dialer { .. } => "out",
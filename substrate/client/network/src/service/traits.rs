@@ -25,6 +25,7 @@ use crate::{
 	error::{self, Error},
 	event::Event,
 	network_state::NetworkState,
+	peer_store::persistence::PeerStorePersistence,
 	request_responses::{IfDisconnected, RequestFailure},
 	service::{metrics::NotificationMetrics, signature::Signature, PeerStoreProvider},
 	types::ProtocolName,
@@ -137,8 +138,12 @@ pub trait NetworkBackend<B: BlockT + 'static, H: ExHashT>: Send + 'static {
 	/// Get handle to `NetworkService` of the `NetworkBackend`.
 	fn network_service(&self) -> Arc<dyn NetworkService>;
 
-	/// Create [`PeerStore`].
-	fn peer_store(bootnodes: Vec<PeerId>, metrics_registry: Option<Registry>) -> Self::PeerStore;
+	/// Create [`PeerStore`], persisted by `persistence` if it's `Some`.
+	fn peer_store(
+		bootnodes: Vec<PeerId>,
+		metrics_registry: Option<Registry>,
+		persistence: Option<PeerStorePersistence>,
+	) -> Self::PeerStore;
 
 	/// Register metrics that are used by the notification protocols.
 	fn register_notification_metrics(registry: Option<&Registry>) -> NotificationMetrics;
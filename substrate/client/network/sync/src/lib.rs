@@ -36,6 +36,7 @@ pub mod blocks;
 pub mod engine;
 pub mod mock;
 pub mod service;
+pub mod snapshot;
 pub mod state_request_handler;
 pub mod strategy;
 pub mod warp_request_handler;
@@ -0,0 +1,193 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Trusted state snapshots, imported in
+//! [`SyncMode::Checkpoint`](sc_network_common::sync::SyncMode).
+//!
+//! A snapshot holds the header and justifications of a finalized block together with its full
+//! state, including child tries. The file starts with [`MAGIC`], the format [`VERSION`] and a
+//! [`SnapshotHeader`], followed by the records of the state: first the key-values of the top trie,
+//! then each default child trie introduced by its prefixed storage key. An end marker detects
+//! truncated files. Everything is SCALE encoded.
+
+use codec::{Decode, Encode, IoReader};
+use sc_client_api::KeyValueStates;
+use sp_runtime::{traits::Block as BlockT, Justifications};
+use std::io::{self, Read, Write};
+
+/// The bytes every snapshot starts with.
+pub const MAGIC: &[u8; 8] = b"SUBSNAP\0";
+
+/// The version of the snapshot format.
+pub const VERSION: u32 = 1;
+
+/// The block a snapshot was taken at.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct SnapshotHeader<B: BlockT> {
+	/// The genesis hash of the chain.
+	pub genesis_hash: B::Hash,
+	/// The header of the block.
+	pub header: B::Header,
+	/// The justifications of the block, if any.
+	pub justifications: Option<Justifications>,
+}
+
+/// A record of the state in a snapshot.
+#[derive(Encode, Decode)]
+enum Record {
+	/// A key-value of the current trie.
+	#[codec(index = 0)]
+	KeyValue(Vec<u8>, Vec<u8>),
+	/// The following key-values belong to the child trie with the given prefixed storage key.
+	#[codec(index = 1)]
+	ChildTrie(Vec<u8>),
+	/// The end of the snapshot.
+	#[codec(index = 2)]
+	End,
+}
+
+/// Writes a snapshot, see the [module docs](self) for the format.
+pub struct SnapshotWriter<W> {
+	output: W,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+	/// Start a snapshot of the block described by `header`.
+	pub fn new<B: BlockT>(mut output: W, header: &SnapshotHeader<B>) -> io::Result<Self> {
+		output.write_all(MAGIC)?;
+		output.write_all(&VERSION.encode())?;
+		output.write_all(&header.encode())?;
+		Ok(Self { output })
+	}
+
+	/// Write a key-value of the current trie.
+	///
+	/// Key-values of the top trie must be written before any child trie is started.
+	pub fn key_value(&mut self, key: Vec<u8>, value: Vec<u8>) -> io::Result<()> {
+		self.write(Record::KeyValue(key, value))
+	}
+
+	/// Start the default child trie with the given prefixed storage key.
+	pub fn child_trie(&mut self, prefixed_storage_key: Vec<u8>) -> io::Result<()> {
+		self.write(Record::ChildTrie(prefixed_storage_key))
+	}
+
+	/// Complete the snapshot, returning the output.
+	pub fn finish(mut self) -> io::Result<W> {
+		self.write(Record::End)?;
+		self.output.flush()?;
+		Ok(self.output)
+	}
+
+	fn write(&mut self, record: Record) -> io::Result<()> {
+		self.output.write_all(&record.encode())
+	}
+}
+
+/// Read a snapshot, returning its header and state.
+pub fn read_snapshot<B: BlockT>(
+	input: impl Read,
+) -> io::Result<(SnapshotHeader<B>, KeyValueStates)> {
+	let mut input = IoReader(input);
+	let invalid = |e: codec::Error| io::Error::new(io::ErrorKind::InvalidData, e);
+
+	let magic = <[u8; 8]>::decode(&mut input).map_err(invalid)?;
+	if &magic != MAGIC {
+		return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a state snapshot"))
+	}
+	let version = u32::decode(&mut input).map_err(invalid)?;
+	if version != VERSION {
+		return Err(io::Error::new(
+			io::ErrorKind::InvalidData,
+			format!("Unsupported snapshot version {version}, expected {VERSION}"),
+		))
+	}
+	let header = SnapshotHeader::<B>::decode(&mut input).map_err(invalid)?;
+
+	// `(prefixed storage key, key-values)` of the top trie and the child tries.
+	let mut levels: Vec<(Option<Vec<u8>>, Vec<(Vec<u8>, Vec<u8>)>)> = vec![(None, Vec::new())];
+	loop {
+		match Record::decode(&mut input).map_err(invalid)? {
+			Record::KeyValue(key, value) => levels
+				.last_mut()
+				.expect("there is always the top trie; qed")
+				.1
+				.push((key, value)),
+			Record::ChildTrie(prefixed_storage_key) =>
+				levels.push((Some(prefixed_storage_key), Vec::new())),
+			Record::End => break,
+		}
+	}
+
+	let state: KeyValueStates = levels
+		.into_iter()
+		.map(|(prefixed_storage_key, key_values)| {
+			(Vec::new(), (key_values, prefixed_storage_key.into_iter().collect()))
+		})
+		.into();
+	Ok((header, state))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::traits::Header as _;
+	use substrate_test_runtime_client::runtime::{Block, Header};
+
+	fn header() -> SnapshotHeader<Block> {
+		SnapshotHeader {
+			genesis_hash: Default::default(),
+			header: Header::new(
+				42,
+				Default::default(),
+				Default::default(),
+				Default::default(),
+				Default::default(),
+			),
+			justifications: Some(Justifications::from((*b"FRNK", vec![1, 2, 3]))),
+		}
+	}
+
+	#[test]
+	fn snapshot_roundtrip() {
+		let mut writer = SnapshotWriter::new(Vec::new(), &header()).unwrap();
+		writer.key_value(b"top".to_vec(), b"value".to_vec()).unwrap();
+		writer.child_trie(b":child_storage:default:child".to_vec()).unwrap();
+		writer.key_value(b"child".to_vec(), b"child value".to_vec()).unwrap();
+		let snapshot = writer.finish().unwrap();
+
+		let (read_header, state) = read_snapshot::<Block>(&snapshot[..]).unwrap();
+		assert_eq!(read_header, header());
+		assert_eq!(state.0.len(), 2);
+		assert!(state.0[0].parent_storage_keys.is_empty());
+		assert_eq!(state.0[0].key_values, vec![(b"top".to_vec(), b"value".to_vec())]);
+		assert_eq!(state.0[1].parent_storage_keys, vec![b":child_storage:default:child".to_vec()]);
+		assert_eq!(state.0[1].key_values, vec![(b"child".to_vec(), b"child value".to_vec())]);
+	}
+
+	#[test]
+	fn truncated_snapshot_is_rejected() {
+		let mut writer = SnapshotWriter::new(Vec::new(), &header()).unwrap();
+		writer.key_value(b"top".to_vec(), b"value".to_vec()).unwrap();
+		let mut snapshot = writer.finish().unwrap();
+		snapshot.pop();
+
+		assert!(read_snapshot::<Block>(&snapshot[..]).is_err());
+		assert!(read_snapshot::<Block>(&b"not a snapshot"[..]).is_err());
+	}
+}
@@ -218,6 +218,8 @@ pub enum ChainSyncMode {
 		/// Download indexed transactions for recent blocks.
 		storage_chain_mode: bool,
 	},
+	/// Download and import headers only, without ever downloading the state.
+	Light,
 }
 
 /// All the data we have about a Peer that we are trying to sync with
@@ -1531,13 +1533,14 @@ where
 				BlockAttributes::HEADER |
 					BlockAttributes::JUSTIFICATION |
 					BlockAttributes::INDEXED_BODY,
+			ChainSyncMode::Light => BlockAttributes::HEADER | BlockAttributes::JUSTIFICATION,
 		}
 	}
 
 	fn skip_execution(&self) -> bool {
 		match self.mode {
 			ChainSyncMode::Full => false,
-			ChainSyncMode::LightState { .. } => true,
+			ChainSyncMode::LightState { .. } | ChainSyncMode::Light => true,
 		}
 	}
 
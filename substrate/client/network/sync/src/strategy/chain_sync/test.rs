@@ -1341,3 +1341,40 @@ fn sync_gap_filled_regardless_of_blocks_origin() {
 		assert!(sync.gap_sync.is_none());
 	}
 }
+
+#[test]
+fn light_mode_requests_headers_only() {
+	let client = Arc::new(TestClientBuilder::new().build());
+	let peer_id = PeerId::random();
+
+	let mut sync = ChainSync::new(
+		ChainSyncMode::Light,
+		client.clone(),
+		1,
+		64,
+		ProtocolName::Static(""),
+		Arc::new(MockBlockDownloader::new()),
+		None,
+		std::iter::empty(),
+	)
+	.unwrap();
+
+	// The peer knows a block we haven't imported yet.
+	let block = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().best_hash)
+		.with_parent_block_number(client.chain_info().best_number)
+		.build()
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	sync.add_peer(peer_id, block.hash(), *block.header().number());
+
+	// Bodies are never requested.
+	let requests = sync.block_requests();
+	assert_eq!(requests.len(), 1);
+	assert_eq!(requests[0].1.fields, BlockAttributes::HEADER | BlockAttributes::JUSTIFICATION);
+
+	// Headers are imported without execution.
+	assert!(sync.skip_execution());
+}
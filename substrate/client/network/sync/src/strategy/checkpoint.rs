@@ -0,0 +1,287 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checkpoint sync strategy, importing the state of a trusted local snapshot.
+
+use crate::{
+	snapshot::{read_snapshot, SnapshotHeader},
+	strategy::SyncingAction,
+	types::{SyncState, SyncStatus},
+	LOG_TARGET,
+};
+use log::{error, info, trace, warn};
+use sc_client_api::KeyValueStates;
+use sc_consensus::{BlockImportError, BlockImportStatus, ImportedState, IncomingBlock};
+use sc_network_common::sync::message::BlockAnnounce;
+use sc_network_types::PeerId;
+use sp_blockchain::{Error as ClientError, HeaderBackend};
+use sp_consensus::BlockOrigin;
+use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
+use std::{fs::File, io::BufReader, path::Path};
+
+/// Syncing strategy that imports the state of a trusted snapshot, see [`crate::snapshot`].
+///
+/// The snapshot is not verified beyond its state matching the state root of its header, and the
+/// chain matching its genesis hash.
+pub struct CheckpointStrategy<B: BlockT> {
+	target: Option<(B::Hash, NumberFor<B>)>,
+	actions: Vec<SyncingAction<B>>,
+	succeeded: bool,
+}
+
+impl<B: BlockT> CheckpointStrategy<B> {
+	/// Create a new instance, reading the snapshot at `path`.
+	///
+	/// Finishes right away if the database already holds a finalized state.
+	pub fn new<Client>(client: &Client, path: &Path) -> Result<Self, ClientError>
+	where
+		Client: HeaderBackend<B>,
+	{
+		let info = client.info();
+		if info.finalized_state.is_some() {
+			warn!(
+				target: LOG_TARGET,
+				"Database already holds a finalized state, not importing the checkpoint snapshot.",
+			);
+			return Ok(Self {
+				target: None,
+				actions: vec![SyncingAction::Finished],
+				succeeded: false,
+			})
+		}
+
+		info!(target: LOG_TARGET, "Reading checkpoint snapshot {}", path.display());
+		let file = File::open(path).map_err(|e| {
+			ClientError::Backend(format!("Failed to open snapshot {}: {e}", path.display()))
+		})?;
+		let (header, state) = read_snapshot::<B>(BufReader::new(file)).map_err(|e| {
+			ClientError::Backend(format!("Failed to read snapshot {}: {e}", path.display()))
+		})?;
+		if header.genesis_hash != info.genesis_hash {
+			return Err(ClientError::Backend(format!(
+				"Snapshot {} belongs to the chain with genesis {}, expected {}",
+				path.display(),
+				header.genesis_hash,
+				info.genesis_hash,
+			)))
+		}
+
+		Ok(Self::from_snapshot(header, state))
+	}
+
+	/// Create a new instance importing the given snapshot.
+	pub fn from_snapshot(snapshot: SnapshotHeader<B>, state: KeyValueStates) -> Self {
+		let SnapshotHeader { header, justifications, .. } = snapshot;
+		let hash = header.hash();
+		let number = *header.number();
+		info!(
+			target: LOG_TARGET,
+			"Importing checkpoint #{number} ({hash}) with {} keys",
+			state.len(),
+		);
+
+		let block = IncomingBlock {
+			hash,
+			header: Some(header),
+			body: None,
+			indexed_body: None,
+			justifications,
+			origin: None,
+			allow_missing_state: true,
+			import_existing: true,
+			skip_execution: true,
+			state: Some(ImportedState { block: hash, state }),
+		};
+		Self {
+			target: Some((hash, number)),
+			actions: vec![SyncingAction::ImportBlocks {
+				origin: BlockOrigin::NetworkInitialSync,
+				blocks: vec![block],
+			}],
+			succeeded: false,
+		}
+	}
+
+	/// Notify that a block announce was validated.
+	///
+	/// Returns the new best block of the peer if the announce is for a best block.
+	pub fn on_validated_block_announce(
+		&mut self,
+		is_best: bool,
+		_peer_id: PeerId,
+		announce: &BlockAnnounce<B::Header>,
+	) -> Option<(B::Hash, NumberFor<B>)> {
+		// Peers are not used while importing the snapshot, let `SyncingEngine` keep track of them.
+		is_best.then(|| (announce.header.hash(), *announce.header.number()))
+	}
+
+	/// A batch of blocks have been processed, with or without errors.
+	///
+	/// Finishes once the checkpoint block was processed.
+	pub fn on_blocks_processed(
+		&mut self,
+		imported: usize,
+		count: usize,
+		results: Vec<(Result<BlockImportStatus<NumberFor<B>>, BlockImportError>, B::Hash)>,
+	) {
+		trace!(target: LOG_TARGET, "Checkpoint sync: imported {imported} of {count}.");
+
+		let Some((target_hash, _)) = self.target else { return };
+		let results = results
+			.into_iter()
+			.filter_map(|(result, hash)| (hash == target_hash).then_some(result))
+			.collect::<Vec<_>>();
+
+		if !results.is_empty() {
+			results.iter().filter_map(|result| result.as_ref().err()).for_each(|e| {
+				error!(target: LOG_TARGET, "Failed to import checkpoint: {e:?}.");
+			});
+			self.succeeded |= results.into_iter().any(|result| result.is_ok());
+			self.actions.push(SyncingAction::Finished);
+		}
+	}
+
+	/// Returns the current sync status.
+	pub fn status(&self) -> SyncStatus<B> {
+		SyncStatus {
+			state: match self.target {
+				Some((_, number)) if !self.succeeded => SyncState::Importing { target: number },
+				_ => SyncState::Idle,
+			},
+			best_seen_block: self.target.map(|(_, number)| number),
+			num_peers: 0,
+			queued_blocks: 0,
+			state_sync: None,
+			warp_sync: None,
+		}
+	}
+
+	/// Get actions that should be performed.
+	#[must_use]
+	pub fn actions(&mut self) -> impl Iterator<Item = SyncingAction<B>> {
+		std::mem::take(&mut self.actions).into_iter()
+	}
+
+	/// Check if the checkpoint was imported.
+	#[must_use]
+	pub fn is_succeeded(&self) -> bool {
+		self.succeeded
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use sc_consensus::ImportedAux;
+	use substrate_test_runtime_client::{
+		runtime::{Block, Header},
+		DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+	};
+
+	fn snapshot() -> (SnapshotHeader<Block>, KeyValueStates) {
+		let header = Header::new(
+			42,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let state = vec![(Vec::new(), (vec![(b"key".to_vec(), b"value".to_vec())], Vec::new()))];
+		(
+			SnapshotHeader { genesis_hash: Default::default(), header, justifications: None },
+			state.into(),
+		)
+	}
+
+	#[test]
+	fn checkpoint_is_imported() {
+		let (snapshot, state) = snapshot();
+		let target_hash = snapshot.header.hash();
+		let mut checkpoint = CheckpointStrategy::from_snapshot(snapshot, state);
+
+		let actions = checkpoint.actions().collect::<Vec<_>>();
+		assert_eq!(actions.len(), 1);
+		let SyncingAction::ImportBlocks { origin, blocks } = &actions[0] else {
+			panic!("Unexpected action: {:?}", actions[0]);
+		};
+		assert_eq!(*origin, BlockOrigin::NetworkInitialSync);
+		assert_eq!(blocks.len(), 1);
+		assert_eq!(blocks[0].hash, target_hash);
+		assert!(blocks[0].skip_execution);
+		assert_eq!(blocks[0].state.as_ref().map(|state| state.block), Some(target_hash));
+		assert!(checkpoint.status().state.is_major_syncing());
+
+		// Unrelated blocks are ignored.
+		checkpoint.on_blocks_processed(
+			1,
+			1,
+			vec![(
+				Ok(BlockImportStatus::ImportedUnknown(1, ImportedAux::default(), None)),
+				Default::default(),
+			)],
+		);
+		assert_eq!(checkpoint.actions().count(), 0);
+
+		checkpoint.on_blocks_processed(
+			1,
+			1,
+			vec![(
+				Ok(BlockImportStatus::ImportedUnknown(42, ImportedAux::default(), None)),
+				target_hash,
+			)],
+		);
+		assert!(checkpoint.is_succeeded());
+		assert!(checkpoint.actions().all(|action| action.is_finished()));
+		assert!(!checkpoint.status().state.is_major_syncing());
+	}
+
+	#[test]
+	fn failed_import_finishes_unsuccessfully() {
+		let (snapshot, state) = snapshot();
+		let target_hash = snapshot.header.hash();
+		let mut checkpoint = CheckpointStrategy::from_snapshot(snapshot, state);
+		let _ = checkpoint.actions().count();
+
+		checkpoint.on_blocks_processed(
+			0,
+			1,
+			vec![(Err(BlockImportError::MissingState), target_hash)],
+		);
+		assert!(!checkpoint.is_succeeded());
+		assert!(checkpoint.actions().all(|action| action.is_finished()));
+	}
+
+	#[test]
+	fn snapshot_of_another_chain_is_rejected() {
+		let client = TestClientBuilder::new().build();
+		let (snapshot, state) = snapshot();
+
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("state.snap");
+		let mut writer =
+			crate::snapshot::SnapshotWriter::new(File::create(&path).unwrap(), &snapshot).unwrap();
+		for state in state.0 {
+			for (key, value) in state.key_values {
+				writer.key_value(key, value).unwrap();
+			}
+		}
+		writer.finish().unwrap();
+
+		assert!(CheckpointStrategy::<Block>::new(&client, &path).is_err());
+	}
+}
@@ -25,6 +25,7 @@ use crate::{
 	service::network::NetworkServiceHandle,
 	strategy::{
 		chain_sync::{ChainSync, ChainSyncMode},
+		checkpoint::CheckpointStrategy,
 		state::StateStrategy,
 		warp::{WarpSync, WarpSyncConfig},
 		StrategyKey, SyncingAction, SyncingStrategy,
@@ -41,7 +42,7 @@ use sc_network_common::sync::{message::BlockAnnounce, SyncMode};
 use sc_network_types::PeerId;
 use sp_blockchain::{Error as ClientError, HeaderBackend, HeaderMetadata};
 use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
-use std::{any::Any, collections::HashMap, sync::Arc};
+use std::{any::Any, collections::HashMap, path::PathBuf, sync::Arc};
 
 /// Corresponding `ChainSync` mode.
 fn chain_sync_mode(sync_mode: SyncMode) -> ChainSyncMode {
@@ -50,6 +51,8 @@ fn chain_sync_mode(sync_mode: SyncMode) -> ChainSyncMode {
 		SyncMode::LightState { skip_proofs, storage_chain_mode } =>
 			ChainSyncMode::LightState { skip_proofs, storage_chain_mode },
 		SyncMode::Warp => ChainSyncMode::Full,
+		SyncMode::Checkpoint => ChainSyncMode::Full,
+		SyncMode::Light => ChainSyncMode::Light,
 	}
 }
 
@@ -71,8 +74,12 @@ where
 	pub metrics_registry: Option<Registry>,
 	/// Protocol name used to send out state requests
 	pub state_request_protocol_name: ProtocolName,
+	/// Protocol name used to send out BEEFY warp proof requests, if supported.
+	pub beefy_warp_sync_protocol_name: Option<ProtocolName>,
 	/// Block downloader
 	pub block_downloader: Arc<dyn BlockDownloader<Block>>,
+	/// Snapshot imported in [`SyncMode::Checkpoint`].
+	pub checkpoint_snapshot: Option<PathBuf>,
 }
 
 /// Proxy to specific syncing strategies used in Polkadot.
@@ -81,6 +88,8 @@ pub struct PolkadotSyncingStrategy<B: BlockT, Client> {
 	config: PolkadotSyncingStrategyConfig<B>,
 	/// Client used by syncing strategies.
 	client: Arc<Client>,
+	/// Checkpoint strategy.
+	checkpoint: Option<CheckpointStrategy<B>>,
 	/// Warp strategy.
 	warp: Option<WarpSync<B, Client>>,
 	/// State strategy.
@@ -125,7 +134,9 @@ where
 		peer_id: PeerId,
 		announce: &BlockAnnounce<B::Header>,
 	) -> Option<(B::Hash, NumberFor<B>)> {
-		let new_best = if let Some(ref mut warp) = self.warp {
+		let new_best = if let Some(ref mut checkpoint) = self.checkpoint {
+			checkpoint.on_validated_block_announce(is_best, peer_id, announce)
+		} else if let Some(ref mut warp) = self.warp {
 			warp.on_validated_block_announce(is_best, peer_id, announce)
 		} else if let Some(ref mut state) = self.state {
 			state.on_validated_block_announce(is_best, peer_id, announce)
@@ -245,8 +256,11 @@ where
 		count: usize,
 		results: Vec<(Result<BlockImportStatus<NumberFor<B>>, BlockImportError>, B::Hash)>,
 	) {
-		// Only `StateStrategy` and `ChainSync` are interested in block processing notifications.
-		if let Some(ref mut state) = self.state {
+		// Only `CheckpointStrategy`, `StateStrategy` and `ChainSync` are interested in block
+		// processing notifications.
+		if let Some(ref mut checkpoint) = self.checkpoint {
+			checkpoint.on_blocks_processed(imported, count, results);
+		} else if let Some(ref mut state) = self.state {
 			state.on_blocks_processed(imported, count, results);
 		} else if let Some(ref mut chain_sync) = self.chain_sync {
 			chain_sync.on_blocks_processed(imported, count, results);
@@ -268,7 +282,8 @@ where
 	}
 
 	fn is_major_syncing(&self) -> bool {
-		self.warp.is_some() ||
+		self.checkpoint.is_some() ||
+			self.warp.is_some() ||
 			self.state.is_some() ||
 			match self.chain_sync {
 				Some(ref s) => s.status().state.is_major_syncing(),
@@ -283,7 +298,9 @@ where
 	fn status(&self) -> SyncStatus<B> {
 		// This function presumes that strategies are executed serially and must be refactored
 		// once we have parallel strategies.
-		if let Some(ref warp) = self.warp {
+		if let Some(ref checkpoint) = self.checkpoint {
+			checkpoint.status()
+		} else if let Some(ref warp) = self.warp {
 			warp.status()
 		} else if let Some(ref state) = self.state {
 			state.status()
@@ -310,7 +327,9 @@ where
 	) -> Result<Vec<SyncingAction<B>>, ClientError> {
 		// This function presumes that strategies are executed serially and must be refactored once
 		// we have parallel strategies.
-		let actions: Vec<_> = if let Some(ref mut warp) = self.warp {
+		let actions: Vec<_> = if let Some(ref mut checkpoint) = self.checkpoint {
+			checkpoint.actions().collect()
+		} else if let Some(ref mut warp) = self.warp {
 			warp.actions(network_service).map(Into::into).collect()
 		} else if let Some(ref mut state) = self.state {
 			state.actions(network_service).map(Into::into).collect()
@@ -354,19 +373,37 @@ where
 			config.max_blocks_per_request = MAX_BLOCKS_IN_RESPONSE as u32;
 		}
 
-		if let SyncMode::Warp = config.mode {
+		if let SyncMode::Checkpoint = config.mode {
+			let path = config.checkpoint_snapshot.clone().ok_or_else(|| {
+				ClientError::Backend("Checkpoint sync requires a snapshot to import.".into())
+			})?;
+			let checkpoint = CheckpointStrategy::new(&*client, &path)?;
+			Ok(Self {
+				config,
+				client,
+				checkpoint: Some(checkpoint),
+				warp: None,
+				state: None,
+				chain_sync: None,
+				peer_best_blocks: Default::default(),
+			})
+		} else if let SyncMode::Warp = config.mode {
 			let warp_sync_config = warp_sync_config
 				.expect("Warp sync configuration must be supplied in warp sync mode.");
-			let warp_sync = WarpSync::new(
+			let mut warp_sync = WarpSync::new(
 				client.clone(),
 				warp_sync_config,
 				warp_sync_protocol_name,
 				config.block_downloader.clone(),
 				config.min_peers_to_start_warp_sync,
 			);
+			if let Some(protocol_name) = config.beefy_warp_sync_protocol_name.clone() {
+				warp_sync = warp_sync.with_beefy_protocol_name(protocol_name);
+			}
 			Ok(Self {
 				config,
 				client,
+				checkpoint: None,
 				warp: Some(warp_sync),
 				state: None,
 				chain_sync: None,
@@ -386,6 +423,7 @@ where
 			Ok(Self {
 				config,
 				client,
+				checkpoint: None,
 				warp: None,
 				state: None,
 				chain_sync: Some(chain_sync),
@@ -396,8 +434,20 @@ where
 
 	/// Proceed with the next strategy if the active one finished.
 	pub fn proceed_to_next(&mut self) -> Result<(), ClientError> {
-		// The strategies are switched as `WarpSync` -> `StateStrategy` -> `ChainSync`.
-		if let Some(ref mut warp) = self.warp {
+		// The strategies are switched as `WarpSync` -> `StateStrategy` -> `ChainSync`, or
+		// `CheckpointStrategy` -> `ChainSync`.
+		if let Some(checkpoint) = &self.checkpoint {
+			if checkpoint.is_succeeded() {
+				info!(target: LOG_TARGET, "Checkpoint imported, continuing with block sync.");
+			} else {
+				error!(target: LOG_TARGET, "Checkpoint import failed. Falling back to full sync.");
+			}
+			let chain_sync = self.new_chain_sync()?;
+
+			self.checkpoint = None;
+			self.chain_sync = Some(chain_sync);
+			Ok(())
+		} else if let Some(ref mut warp) = self.warp {
 			match warp.take_result() {
 				Some(res) => {
 					info!(
@@ -478,7 +528,24 @@ where
 			self.chain_sync = Some(chain_sync);
 			Ok(())
 		} else {
-			unreachable!("Only warp & state strategies can finish; qed")
+			unreachable!("Only checkpoint, warp & state strategies can finish; qed")
 		}
 	}
+
+	/// Create `ChainSync` seeded with the currently known peers.
+	fn new_chain_sync(&self) -> Result<ChainSync<B, Client>, ClientError> {
+		ChainSync::new(
+			chain_sync_mode(self.config.mode),
+			self.client.clone(),
+			self.config.max_parallel_downloads,
+			self.config.max_blocks_per_request,
+			self.config.state_request_protocol_name.clone(),
+			self.config.block_downloader.clone(),
+			self.config.metrics_registry.as_ref(),
+			self.peer_best_blocks
+				.iter()
+				.map(|(peer_id, (best_hash, best_number))| (*peer_id, *best_hash, *best_number)),
+		)
+		.inspect_err(|_| error!(target: LOG_TARGET, "Failed to start `ChainSync`."))
+	}
 }
@@ -20,6 +20,10 @@
 
 pub use sp_consensus_grandpa::{AuthorityList, SetId};
 
+/// BEEFY validator set, the verifier state of BEEFY warp proofs.
+pub type BeefyValidatorSet =
+	sp_consensus_beefy::ValidatorSet<sp_consensus_beefy::ecdsa_crypto::AuthorityId>;
+
 use crate::{
 	block_relay_protocol::{BlockDownloader, BlockResponseError},
 	service::network::NetworkServiceHandle,
@@ -33,7 +37,10 @@ use crate::{
 use codec::{Decode, Encode};
 use futures::{channel::oneshot, FutureExt};
 use log::{debug, error, trace, warn};
-use sc_network::{IfDisconnected, ProtocolName};
+use sc_network::{
+	request_responses::{OutboundFailure, RequestFailure},
+	IfDisconnected, ProtocolName,
+};
 use sc_network_common::sync::message::{
 	BlockAnnounce, BlockAttributes, BlockData, BlockRequest, Direction, FromBlock,
 };
@@ -43,7 +50,12 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor, Zero},
 	Justifications, SaturatedConversion,
 };
-use std::{any::Any, collections::HashMap, fmt, sync::Arc};
+use std::{
+	any::Any,
+	collections::{HashMap, HashSet},
+	fmt,
+	sync::Arc,
+};
 
 /// Number of peers that need to be connected before warp sync is started.
 const MIN_PEERS_TO_START_WARP_SYNC: usize = 3;
@@ -86,6 +98,36 @@ pub trait WarpSyncProvider<Block: BlockT>: Send + Sync {
 	fn current_authorities(&self) -> AuthorityList;
 }
 
+/// BEEFY warp proof verification result.
+pub enum BeefyVerificationResult<Block: BlockT> {
+	/// Proof is valid, but the target was not reached.
+	Partial(BeefyValidatorSet, Block::Hash),
+	/// Target finality is proved.
+	Complete(BeefyValidatorSet, Block::Header),
+}
+
+/// Warp sync backend proving finality with BEEFY signed commitments and MMR proofs.
+///
+/// A BEEFY warp proof only needs a single commitment per validator set to reach a recent
+/// finalized header, so it is usually much smaller than the GRANDPA one.
+pub trait BeefyWarpSyncProvider<Block: BlockT>: Send + Sync {
+	/// Generate proof starting at given block hash. The proof is accumulated until maximum proof
+	/// size is reached.
+	fn generate(
+		&self,
+		start: Block::Hash,
+	) -> Result<EncodedProof, Box<dyn std::error::Error + Send + Sync>>;
+	/// Verify warp proof against the given BEEFY validator set.
+	fn verify(
+		&self,
+		proof: &EncodedProof,
+		validator_set: BeefyValidatorSet,
+	) -> Result<BeefyVerificationResult<Block>, Box<dyn std::error::Error + Send + Sync>>;
+	/// Get the BEEFY validator set to start verifying proofs with: the genesis set, or a trusted
+	/// set BEEFY was activated with after genesis. `None` if neither is known.
+	fn initial_validator_set(&self) -> Option<BeefyValidatorSet>;
+}
+
 mod rep {
 	use sc_network::ReputationChange as Rep;
 
@@ -155,6 +197,13 @@ pub struct WarpSyncProgress<Block: BlockT> {
 pub enum WarpSyncConfig<Block: BlockT> {
 	/// Standard warp sync for the chain.
 	WithProvider(Arc<dyn WarpSyncProvider<Block>>),
+	/// Warp sync with BEEFY proofs, falling back to GRANDPA proofs if no peer can serve them.
+	WithBeefyProvider {
+		/// Provider of the BEEFY warp proofs.
+		beefy: Arc<dyn BeefyWarpSyncProvider<Block>>,
+		/// Provider of the GRANDPA warp proofs used as fallback.
+		grandpa: Arc<dyn WarpSyncProvider<Block>>,
+	},
 	/// Skip downloading proofs and use provided header of the state that should be downloaded.
 	///
 	/// It is expected that the header provider ensures that the header is trusted.
@@ -164,7 +213,10 @@ pub enum WarpSyncConfig<Block: BlockT> {
 /// Warp sync phase used by warp sync state machine.
 enum Phase<B: BlockT> {
 	/// Waiting for enough peers to connect.
-	WaitingForPeers { warp_sync_provider: Arc<dyn WarpSyncProvider<B>> },
+	WaitingForPeers {
+		warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+		beefy_warp_sync_provider: Option<Arc<dyn BeefyWarpSyncProvider<B>>>,
+	},
 	/// Downloading warp proofs.
 	WarpProof {
 		set_id: SetId,
@@ -172,6 +224,16 @@ enum Phase<B: BlockT> {
 		last_hash: B::Hash,
 		warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
 	},
+	/// Downloading BEEFY warp proofs.
+	BeefyWarpProof {
+		validator_set: BeefyValidatorSet,
+		last_hash: B::Hash,
+		beefy_warp_sync_provider: Arc<dyn BeefyWarpSyncProvider<B>>,
+		/// Used when none of the peers serves BEEFY warp proofs.
+		warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+		/// Peers that don't serve BEEFY warp proofs.
+		unsupported_peers: HashSet<PeerId>,
+	},
 	/// Downloading target block.
 	TargetBlock(B::Header),
 	/// Warp sync is complete.
@@ -210,6 +272,7 @@ pub struct WarpSync<B: BlockT, Client> {
 	peers: HashMap<PeerId, Peer<B>>,
 	disconnected_peers: DisconnectedPeers,
 	protocol_name: Option<ProtocolName>,
+	beefy_protocol_name: Option<ProtocolName>,
 	block_downloader: Arc<dyn BlockDownloader<B>>,
 	actions: Vec<SyncingAction<B>>,
 	result: Option<WarpSyncResult<B>>,
@@ -250,6 +313,7 @@ where
 				peers: HashMap::new(),
 				disconnected_peers: DisconnectedPeers::new(),
 				protocol_name,
+				beefy_protocol_name: None,
 				block_downloader,
 				actions: vec![SyncingAction::Finished],
 				result: None,
@@ -259,7 +323,11 @@ where
 
 		let phase = match warp_sync_config {
 			WarpSyncConfig::WithProvider(warp_sync_provider) =>
-				Phase::WaitingForPeers { warp_sync_provider },
+				Phase::WaitingForPeers { warp_sync_provider, beefy_warp_sync_provider: None },
+			WarpSyncConfig::WithBeefyProvider { beefy, grandpa } => Phase::WaitingForPeers {
+				warp_sync_provider: grandpa,
+				beefy_warp_sync_provider: Some(beefy),
+			},
 			WarpSyncConfig::WithTarget(target_header) => Phase::TargetBlock(target_header),
 		};
 
@@ -271,6 +339,7 @@ where
 			peers: HashMap::new(),
 			disconnected_peers: DisconnectedPeers::new(),
 			protocol_name,
+			beefy_protocol_name: None,
 			block_downloader,
 			actions: Vec::new(),
 			result: None,
@@ -278,6 +347,14 @@ where
 		}
 	}
 
+	/// Set the name of the BEEFY warp sync request protocol.
+	///
+	/// BEEFY warp proofs are only requested if the protocol is known.
+	pub fn with_beefy_protocol_name(mut self, protocol_name: ProtocolName) -> Self {
+		self.beefy_protocol_name = Some(protocol_name);
+		self
+	}
+
 	/// Notify that a new peer has connected.
 	pub fn add_peer(&mut self, peer_id: PeerId, _best_hash: B::Hash, best_number: NumberFor<B>) {
 		self.peers.insert(peer_id, Peer { best_number, state: PeerState::Available });
@@ -296,6 +373,8 @@ where
 				}
 			}
 		}
+
+		self.fall_back_to_grandpa_if_unsupported();
 	}
 
 	/// Submit a validated block announcement.
@@ -321,19 +400,80 @@ where
 
 	/// Start warp sync as soon as we have enough peers.
 	fn try_to_start_warp_sync(&mut self) {
-		let Phase::WaitingForPeers { warp_sync_provider } = &self.phase else { return };
+		let Phase::WaitingForPeers { warp_sync_provider, beefy_warp_sync_provider } = &self.phase
+		else {
+			return
+		};
 
 		if self.peers.len() < self.min_peers_to_start_warp_sync {
 			return
 		}
 
+		let genesis_hash = self.client.info().genesis_hash;
+		let beefy = beefy_warp_sync_provider
+			.as_ref()
+			.filter(|_| self.beefy_protocol_name.is_some())
+			.and_then(|provider| match provider.initial_validator_set() {
+				Some(validator_set) => Some((provider, validator_set)),
+				None => {
+					warn!(
+						target: LOG_TARGET,
+						"BEEFY is not active at genesis and its activation validator set is unknown, \
+						warp syncing with GRANDPA proofs.",
+					);
+					None
+				},
+			});
+		self.phase = match beefy {
+			Some((beefy_warp_sync_provider, validator_set)) => Phase::BeefyWarpProof {
+				validator_set,
+				last_hash: genesis_hash,
+				beefy_warp_sync_provider: Arc::clone(beefy_warp_sync_provider),
+				warp_sync_provider: Arc::clone(warp_sync_provider),
+				unsupported_peers: HashSet::new(),
+			},
+			None => Phase::WarpProof {
+				set_id: 0,
+				authorities: warp_sync_provider.current_authorities(),
+				last_hash: genesis_hash,
+				warp_sync_provider: Arc::clone(warp_sync_provider),
+			},
+		};
+		trace!(target: LOG_TARGET, "Started warp sync with {} peers.", self.peers.len());
+	}
+
+	/// Fall back to GRANDPA warp proofs if none of the connected peers serves BEEFY ones.
+	fn fall_back_to_grandpa_if_unsupported(&mut self) {
+		let Phase::BeefyWarpProof { warp_sync_provider, unsupported_peers, .. } = &self.phase
+		else {
+			return
+		};
+
+		if self.peers.is_empty() || self.peers.len() < self.min_peers_to_start_warp_sync {
+			return
+		}
+
+		// Only peers synced as much as the peer majority are ever asked for proofs.
+		let mut targets: Vec<_> = self.peers.values().map(|p| p.best_number).collect();
+		targets.sort();
+		let median = targets[targets.len() / 2];
+		if self.peers.iter().any(|(peer_id, peer)| {
+			peer.best_number >= median && !unsupported_peers.contains(peer_id)
+		}) {
+			return
+		}
+
+		debug!(
+			target: LOG_TARGET,
+			"None of the {} peers serves BEEFY warp proofs, falling back to GRANDPA warp proofs.",
+			self.peers.len(),
+		);
 		self.phase = Phase::WarpProof {
 			set_id: 0,
 			authorities: warp_sync_provider.current_authorities(),
 			last_hash: self.client.info().genesis_hash,
 			warp_sync_provider: Arc::clone(warp_sync_provider),
 		};
-		trace!(target: LOG_TARGET, "Started warp sync with {} peers.", self.peers.len());
 	}
 
 	pub fn on_generic_response(
@@ -377,6 +517,14 @@ where
 			};
 
 			self.on_block_response(*peer_id, request, blocks);
+		} else if self.beefy_protocol_name.as_ref() == Some(&protocol_name) {
+			let Ok(response) = response.downcast::<Vec<u8>>() else {
+				warn!(target: LOG_TARGET, "Failed to downcast BEEFY warp sync response");
+				debug_assert!(false);
+				return;
+			};
+
+			self.on_beefy_warp_proof_response(peer_id, EncodedProof(*response));
 		} else {
 			let Ok(response) = response.downcast::<Vec<u8>>() else {
 				warn!(target: LOG_TARGET, "Failed to downcast warp sync response");
@@ -430,6 +578,65 @@ where
 		}
 	}
 
+	/// Process BEEFY warp proof response.
+	///
+	/// An empty response means that the peer doesn't serve BEEFY warp proofs.
+	pub fn on_beefy_warp_proof_response(&mut self, peer_id: &PeerId, response: EncodedProof) {
+		if let Some(peer) = self.peers.get_mut(peer_id) {
+			peer.state = PeerState::Available;
+		}
+
+		let Phase::BeefyWarpProof {
+			validator_set,
+			last_hash,
+			beefy_warp_sync_provider,
+			unsupported_peers,
+			..
+		} = &mut self.phase
+		else {
+			debug!(target: LOG_TARGET, "Unexpected BEEFY warp proof response");
+			self.actions
+				.push(SyncingAction::DropPeer(BadPeer(*peer_id, rep::UNEXPECTED_RESPONSE)));
+			return
+		};
+
+		if response.0.is_empty() {
+			trace!(target: LOG_TARGET, "Peer {peer_id} doesn't serve BEEFY warp proofs.");
+			unsupported_peers.insert(*peer_id);
+			self.fall_back_to_grandpa_if_unsupported();
+			return
+		}
+
+		match beefy_warp_sync_provider.verify(&response, validator_set.clone()) {
+			Err(e) => {
+				debug!(target: LOG_TARGET, "Bad BEEFY warp proof response: {}", e);
+				self.actions
+					.push(SyncingAction::DropPeer(BadPeer(*peer_id, rep::BAD_WARP_PROOF)))
+			},
+			Ok(BeefyVerificationResult::Partial(new_validator_set, new_last_hash)) => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Verified partial BEEFY proof, validator_set_id={:?}",
+					new_validator_set.id(),
+				);
+				*validator_set = new_validator_set;
+				*last_hash = new_last_hash;
+				self.total_proof_bytes += response.0.len() as u64;
+			},
+			Ok(BeefyVerificationResult::Complete(new_validator_set, header)) => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Verified complete BEEFY proof, validator_set_id={:?}. Continuing with target block download: {} ({}).",
+					new_validator_set.id(),
+					header.hash(),
+					header.number(),
+				);
+				self.total_proof_bytes += response.0.len() as u64;
+				self.phase = Phase::TargetBlock(header);
+			},
+		}
+	}
+
 	/// Process (target) block response.
 	pub fn on_block_response(
 		&mut self,
@@ -517,6 +724,16 @@ where
 		&mut self,
 		new_state: PeerState,
 		min_best_number: Option<NumberFor<B>>,
+	) -> Option<PeerId> {
+		self.schedule_next_peer_except(new_state, min_best_number, &HashSet::new())
+	}
+
+	/// Same as [`Self::schedule_next_peer`], but never picks one of the `excluded` peers.
+	fn schedule_next_peer_except(
+		&mut self,
+		new_state: PeerState,
+		min_best_number: Option<NumberFor<B>>,
+		excluded: &HashSet<PeerId>,
 	) -> Option<PeerId> {
 		let mut targets: Vec<_> = self.peers.values().map(|p| p.best_number).collect();
 		if targets.is_empty() {
@@ -530,6 +747,7 @@ where
 		for (peer_id, peer) in self.peers.iter_mut() {
 			if peer.state.is_available() &&
 				peer.best_number >= threshold &&
+				!excluded.contains(peer_id) &&
 				self.disconnected_peers.is_peer_available(peer_id)
 			{
 				peer.state = new_state;
@@ -571,6 +789,34 @@ where
 		Some((peer_id, protocol_name, request))
 	}
 
+	/// Produce BEEFY warp proof request.
+	fn beefy_warp_proof_request(&mut self) -> Option<(PeerId, ProtocolName, WarpProofRequest<B>)> {
+		let Phase::BeefyWarpProof { last_hash, unsupported_peers, .. } = &self.phase else {
+			return None
+		};
+
+		// Copy early to cut the borrowing tie.
+		let begin = *last_hash;
+		let unsupported_peers = unsupported_peers.clone();
+
+		if self
+			.peers
+			.values()
+			.any(|peer| matches!(peer.state, PeerState::DownloadingProofs))
+		{
+			// Only one warp proof request at a time is possible.
+			return None
+		}
+
+		let peer_id =
+			self.schedule_next_peer_except(PeerState::DownloadingProofs, None, &unsupported_peers)?;
+		trace!(target: LOG_TARGET, "New BEEFY WarpProofRequest to {peer_id}, begin hash: {begin}.");
+
+		let protocol_name = self.beefy_protocol_name.clone()?;
+
+		Some((peer_id, protocol_name, WarpProofRequest { begin }))
+	}
+
 	/// Produce target block request.
 	fn target_block_request(&mut self) -> Option<(PeerId, BlockRequest<B>)> {
 		let Phase::TargetBlock(target_header) = &self.phase else { return None };
@@ -621,7 +867,7 @@ where
 				},
 				total_bytes: self.total_proof_bytes,
 			},
-			Phase::WarpProof { .. } => WarpSyncProgress {
+			Phase::WarpProof { .. } | Phase::BeefyWarpProof { .. } => WarpSyncProgress {
 				phase: WarpSyncPhase::DownloadingWarpProofs,
 				total_bytes: self.total_proof_bytes,
 			},
@@ -646,13 +892,14 @@ where
 		SyncStatus {
 			state: match &self.phase {
 				Phase::WaitingForPeers { .. } => SyncState::Downloading { target: Zero::zero() },
-				Phase::WarpProof { .. } => SyncState::Downloading { target: Zero::zero() },
+				Phase::WarpProof { .. } | Phase::BeefyWarpProof { .. } =>
+					SyncState::Downloading { target: Zero::zero() },
 				Phase::TargetBlock(header) => SyncState::Downloading { target: *header.number() },
 				Phase::Complete => SyncState::Idle,
 			},
 			best_seen_block: match &self.phase {
 				Phase::WaitingForPeers { .. } => None,
-				Phase::WarpProof { .. } => None,
+				Phase::WarpProof { .. } | Phase::BeefyWarpProof { .. } => None,
 				Phase::TargetBlock(header) => Some(*header.number()),
 				Phase::Complete => None,
 			},
@@ -702,6 +949,49 @@ where
 			});
 		self.actions.extend(warp_proof_request);
 
+		let beefy_warp_proof_request =
+			self.beefy_warp_proof_request()
+				.into_iter()
+				.map(|(peer_id, protocol_name, request)| {
+					trace!(
+						target: LOG_TARGET,
+						"Created BEEFY `WarpProofRequest` to {}, request: {:?}.",
+						peer_id,
+						request,
+					);
+
+					let (tx, rx) = oneshot::channel();
+
+					network_service.start_request(
+						peer_id,
+						protocol_name.clone(),
+						request.encode(),
+						tx,
+						IfDisconnected::ImmediateError,
+					);
+
+					SyncingAction::StartRequest {
+						peer_id,
+						key: Self::STRATEGY_KEY,
+						request: async move {
+							let response = match rx.await? {
+								// Peers not knowing the protocol are not penalized, they are only
+								// excluded from BEEFY warp proof requests.
+								Err(RequestFailure::Network(
+									OutboundFailure::UnsupportedProtocols,
+								)) => Ok((Vec::new(), protocol_name)),
+								response => response,
+							};
+							Ok(response.map(|(response, protocol_name)| {
+								(Box::new(response) as Box<dyn Any + Send>, protocol_name)
+							}))
+						}
+						.boxed(),
+						remove_obsolete: false,
+					}
+				});
+		self.actions.extend(beefy_warp_proof_request);
+
 		let target_block_request =
 			self.target_block_request().into_iter().map(|(peer_id, request)| {
 				let downloader = self.block_downloader.clone();
@@ -786,6 +1076,31 @@ mod test {
 		}
 	}
 
+	mockall::mock! {
+		pub BeefyWarpSyncProvider<B: BlockT> {}
+
+		impl<B: BlockT> super::BeefyWarpSyncProvider<B> for BeefyWarpSyncProvider<B> {
+			fn generate(
+				&self,
+				start: B::Hash,
+			) -> Result<EncodedProof, Box<dyn std::error::Error + Send + Sync>>;
+			fn verify(
+				&self,
+				proof: &EncodedProof,
+				validator_set: BeefyValidatorSet,
+			) -> Result<BeefyVerificationResult<B>, Box<dyn std::error::Error + Send + Sync>>;
+			fn initial_validator_set(&self) -> Option<BeefyValidatorSet>;
+		}
+	}
+
+	fn genesis_beefy_validator_set() -> BeefyValidatorSet {
+		let alice = sp_consensus_beefy::test_utils::Keyring::<
+			sp_consensus_beefy::ecdsa_crypto::AuthorityId,
+		>::Alice
+			.public();
+		BeefyValidatorSet::new(vec![alice], 0).unwrap()
+	}
+
 	fn mock_client_with_state() -> MockClient<Block> {
 		let mut client = MockClient::<Block>::new();
 		let genesis_hash = Hash::random();
@@ -1718,4 +2033,124 @@ mod test {
 		assert_eq!(result.target_body, body);
 		assert_eq!(result.target_justifications, justifications);
 	}
+
+	#[test]
+	fn beefy_warp_sync_falls_back_to_grandpa_if_no_peer_serves_it() {
+		let client = mock_client_without_state();
+		let mut provider = MockWarpSyncProvider::<Block>::new();
+		provider
+			.expect_current_authorities()
+			.once()
+			.return_const(AuthorityList::default());
+		let mut beefy_provider = MockBeefyWarpSyncProvider::<Block>::new();
+		beefy_provider
+			.expect_initial_validator_set()
+			.once()
+			.returning(|| Some(genesis_beefy_validator_set()));
+		let config = WarpSyncConfig::WithBeefyProvider {
+			beefy: Arc::new(beefy_provider),
+			grandpa: Arc::new(provider),
+		};
+		let mut warp_sync = WarpSync::new(
+			Arc::new(client),
+			config,
+			Some(ProtocolName::Static("grandpa")),
+			Arc::new(MockBlockDownloader::new()),
+			None,
+		)
+		.with_beefy_protocol_name(ProtocolName::Static("beefy"));
+
+		// Make sure we have enough peers to make a request.
+		for best_number in 1..11 {
+			warp_sync.add_peer(PeerId::random(), Hash::random(), best_number);
+		}
+		assert!(matches!(warp_sync.phase, Phase::BeefyWarpProof { .. }));
+
+		let network_provider = NetworkServiceProvider::new();
+		let network_handle = network_provider.handle();
+
+		// Every peer synced as much as the peer majority is asked once and responds it doesn't
+		// know the protocol.
+		let mut asked_peers = HashSet::new();
+		while matches!(warp_sync.phase, Phase::BeefyWarpProof { .. }) {
+			let actions = warp_sync.actions(&network_handle).collect::<Vec<_>>();
+			assert_eq!(actions.len(), 1);
+			let SyncingAction::StartRequest { peer_id, .. } = actions[0] else {
+				panic!("Invalid action.");
+			};
+			assert!(asked_peers.insert(peer_id), "Unsupported peer is not asked again.");
+
+			warp_sync.on_beefy_warp_proof_response(&peer_id, EncodedProof(Vec::new()));
+			assert!(warp_sync.actions.is_empty(), "Unsupported peer is not dropped.");
+		}
+		assert_eq!(asked_peers.len(), 5);
+
+		// GRANDPA warp sync starts from genesis.
+		let Phase::WarpProof { set_id, last_hash, .. } = &warp_sync.phase else {
+			panic!("Invalid phase.");
+		};
+		assert_eq!(*set_id, 0);
+		assert_eq!(*last_hash, warp_sync.client.info().genesis_hash);
+		let (_, protocol_name, _) = warp_sync.warp_proof_request().unwrap();
+		assert_eq!(protocol_name, ProtocolName::Static("grandpa"));
+	}
+
+	#[test]
+	fn complete_beefy_warp_proof_advances_phase() {
+		let client = Arc::new(TestClientBuilder::new().set_no_genesis().build());
+		let provider = MockWarpSyncProvider::<Block>::new();
+		let mut beefy_provider = MockBeefyWarpSyncProvider::<Block>::new();
+		beefy_provider
+			.expect_initial_validator_set()
+			.once()
+			.returning(|| Some(genesis_beefy_validator_set()));
+		let target_block = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(client.chain_info().best_hash)
+			.with_parent_block_number(client.chain_info().best_number)
+			.build()
+			.unwrap()
+			.build()
+			.unwrap()
+			.block;
+		let target_header = target_block.header().clone();
+		// BEEFY warp proof is complete.
+		beefy_provider.expect_verify().return_once(move |_proof, validator_set| {
+			Ok(BeefyVerificationResult::Complete(validator_set, target_header))
+		});
+		let config = WarpSyncConfig::WithBeefyProvider {
+			beefy: Arc::new(beefy_provider),
+			grandpa: Arc::new(provider),
+		};
+		let mut warp_sync = WarpSync::new(
+			client,
+			config,
+			Some(ProtocolName::Static("grandpa")),
+			Arc::new(MockBlockDownloader::new()),
+			None,
+		)
+		.with_beefy_protocol_name(ProtocolName::Static("beefy"));
+
+		// Make sure we have enough peers to make a request.
+		for best_number in 1..11 {
+			warp_sync.add_peer(PeerId::random(), Hash::random(), best_number);
+		}
+		assert!(matches!(warp_sync.phase, Phase::BeefyWarpProof { .. }));
+
+		let network_provider = NetworkServiceProvider::new();
+		let network_handle = network_provider.handle();
+
+		// Consume `SendWarpProofRequest` action.
+		let actions = warp_sync.actions(&network_handle).collect::<Vec<_>>();
+		assert_eq!(actions.len(), 1);
+		let SyncingAction::StartRequest { peer_id: request_peer_id, .. } = actions[0] else {
+			panic!("Invalid action.");
+		};
+
+		warp_sync.on_beefy_warp_proof_response(&request_peer_id, EncodedProof(vec![1, 2, 3]));
+
+		assert!(warp_sync.actions.is_empty(), "No extra actions generated.");
+		assert!(
+			matches!(warp_sync.phase, Phase::TargetBlock(header) if header == *target_block.header())
+		);
+	}
 }
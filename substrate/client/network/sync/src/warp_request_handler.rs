@@ -15,14 +15,14 @@
 // You should have received a copy of the GNU General Public License
 // along with Substrate. If not, see <https://www.gnu.org/licenses/>.
 
-//! Helper for handling (i.e. answering) grandpa warp sync requests from a remote peer.
+//! Helper for handling (i.e. answering) grandpa and BEEFY warp sync requests from a remote peer.
 
 use codec::Decode;
 use futures::{channel::oneshot, stream::StreamExt};
 use log::debug;
 
 use crate::{
-	strategy::warp::{EncodedProof, WarpProofRequest, WarpSyncProvider},
+	strategy::warp::{BeefyWarpSyncProvider, EncodedProof, WarpProofRequest, WarpSyncProvider},
 	LOG_TARGET,
 };
 use sc_network::{
@@ -69,6 +69,35 @@ fn generate_protocol_name<Hash: AsRef<[u8]>>(genesis_hash: Hash, fork_id: Option
 	}
 }
 
+/// Generates a `RequestResponseProtocolConfig` for the BEEFY warp sync request protocol, refusing
+/// incoming requests.
+pub fn generate_beefy_request_response_config<
+	Hash: AsRef<[u8]>,
+	B: BlockT,
+	N: NetworkBackend<B, <B as BlockT>::Hash>,
+>(
+	genesis_hash: Hash,
+	fork_id: Option<&str>,
+	inbound_queue: async_channel::Sender<IncomingRequest>,
+) -> N::RequestResponseProtocolConfig {
+	N::request_response_config(
+		generate_beefy_protocol_name(genesis_hash, fork_id).into(),
+		Vec::new(),
+		32,
+		MAX_RESPONSE_SIZE,
+		Duration::from_secs(10),
+		Some(inbound_queue),
+	)
+}
+
+/// Generate the BEEFY warp sync protocol name from the genesis hash and fork id.
+fn generate_beefy_protocol_name<Hash: AsRef<[u8]>>(
+	genesis_hash: Hash,
+	fork_id: Option<&str>,
+) -> String {
+	format!("{}/beefy", generate_protocol_name(genesis_hash, fork_id))
+}
+
 /// Generate the legacy grandpa warp sync protocol name from chain specific protocol identifier.
 fn generate_legacy_protocol_name(protocol_id: ProtocolId) -> String {
 	format!("/{}/sync/warp", protocol_id.as_ref())
@@ -140,6 +169,76 @@ impl<TBlock: BlockT> RequestHandler<TBlock> {
 	}
 }
 
+/// Handler for incoming BEEFY warp sync requests from a remote peer.
+///
+/// If no proof can be generated (e.g. because BEEFY or MMR offchain indexing is not available
+/// on this node), an empty response is sent, making the remote fall back to grandpa warp sync.
+pub struct BeefyRequestHandler<TBlock: BlockT> {
+	backend: Arc<dyn BeefyWarpSyncProvider<TBlock>>,
+	request_receiver: async_channel::Receiver<IncomingRequest>,
+}
+
+impl<TBlock: BlockT> BeefyRequestHandler<TBlock> {
+	/// Create a new [`BeefyRequestHandler`].
+	pub fn new<Hash: AsRef<[u8]>, N: NetworkBackend<TBlock, <TBlock as BlockT>::Hash>>(
+		genesis_hash: Hash,
+		fork_id: Option<&str>,
+		backend: Arc<dyn BeefyWarpSyncProvider<TBlock>>,
+	) -> (Self, N::RequestResponseProtocolConfig) {
+		let (tx, request_receiver) = async_channel::bounded(MAX_WARP_REQUEST_QUEUE);
+
+		let request_response_config =
+			generate_beefy_request_response_config::<_, TBlock, N>(genesis_hash, fork_id, tx);
+
+		(Self { backend, request_receiver }, request_response_config)
+	}
+
+	fn handle_request(
+		&self,
+		payload: Vec<u8>,
+		pending_response: oneshot::Sender<OutgoingResponse>,
+	) -> Result<(), HandleRequestError> {
+		let request = WarpProofRequest::<TBlock>::decode(&mut &payload[..])?;
+
+		let proof = match self.backend.generate(request.begin) {
+			Ok(EncodedProof(proof)) => proof,
+			Err(e) => {
+				debug!(
+					target: LOG_TARGET,
+					"Failed to generate BEEFY warp proof, responding with an empty one: {}", e,
+				);
+				Vec::new()
+			},
+		};
+
+		pending_response
+			.send(OutgoingResponse {
+				result: Ok(proof),
+				reputation_changes: Vec::new(),
+				sent_feedback: None,
+			})
+			.map_err(|_| HandleRequestError::SendResponse)
+	}
+
+	/// Run [`BeefyRequestHandler`].
+	pub async fn run(mut self) {
+		while let Some(request) = self.request_receiver.next().await {
+			let IncomingRequest { peer, payload, pending_response } = request;
+
+			match self.handle_request(payload, pending_response) {
+				Ok(()) => {
+					debug!(target: LOG_TARGET, "Handled BEEFY warp sync request from {}.", peer)
+				},
+				Err(e) => debug!(
+					target: LOG_TARGET,
+					"Failed to handle BEEFY warp sync request from {}: {}",
+					peer, e,
+				),
+			}
+		}
+	}
+}
+
 #[derive(Debug, thiserror::Error)]
 enum HandleRequestError {
 	#[error("Failed to decode request: {0}.")]
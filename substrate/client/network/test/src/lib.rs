@@ -30,6 +30,7 @@ mod sync;
 
 use std::{
 	collections::HashMap,
+	path::PathBuf,
 	pin::Pin,
 	sync::Arc,
 	task::{Context as FutureContext, Poll},
@@ -709,6 +710,8 @@ pub struct FullPeerConfig {
 	pub target_header: Option<<Block as BlockT>::Header>,
 	/// Force genesis even in case of warp & light state sync.
 	pub force_genesis: bool,
+	/// Snapshot imported in checkpoint sync.
+	pub checkpoint_snapshot: Option<PathBuf>,
 }
 
 #[async_trait::async_trait]
@@ -769,8 +772,10 @@ pub trait TestNetFactory: Default + Sized + Send {
 		}
 
 		if !config.force_genesis &&
-			matches!(config.sync_mode, SyncMode::LightState { .. } | SyncMode::Warp)
-		{
+			matches!(
+				config.sync_mode,
+				SyncMode::LightState { .. } | SyncMode::Warp | SyncMode::Checkpoint
+			) {
 			test_client_builder = test_client_builder.set_no_genesis();
 		}
 		let backend = test_client_builder.backend();
@@ -797,6 +802,7 @@ pub trait TestNetFactory: Default + Sized + Send {
 		let mut network_config =
 			NetworkConfiguration::new("test-node", "test-client", Default::default(), None);
 		network_config.sync_mode = config.sync_mode;
+		network_config.checkpoint_snapshot = config.checkpoint_snapshot.clone();
 		network_config.transport = TransportConfig::MemoryOnly;
 		network_config.listen_addresses = vec![listen_addr.clone()];
 		network_config.allow_non_globals_in_dht = true;
@@ -916,8 +922,10 @@ pub trait TestNetFactory: Default + Sized + Send {
 			max_blocks_per_request: network_config.max_blocks_per_request,
 			metrics_registry: None,
 			state_request_protocol_name: state_request_protocol_config.name.clone(),
+			beefy_warp_sync_protocol_name: None,
 			block_downloader: block_relay_params.downloader,
 			min_peers_to_start_warp_sync: None,
+			checkpoint_snapshot: network_config.checkpoint_snapshot.clone(),
 		};
 		// Initialize syncing strategy.
 		let syncing_strategy = Box::new(
@@ -210,8 +210,10 @@ impl TestNetworkBuilder {
 			max_blocks_per_request: network_config.max_blocks_per_request,
 			metrics_registry: None,
 			state_request_protocol_name: state_request_protocol_config.name.clone(),
+			beefy_warp_sync_protocol_name: None,
 			block_downloader: block_relay_params.downloader,
 			min_peers_to_start_warp_sync: None,
+			checkpoint_snapshot: None,
 		};
 		// Initialize syncing strategy.
 		let syncing_strategy = Box::new(
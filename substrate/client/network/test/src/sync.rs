@@ -1146,6 +1146,168 @@ async fn syncs_state() {
 	}
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn syncs_from_exported_checkpoint_snapshot() {
+	use sc_client_api::StorageProvider;
+	use sp_core::storage::StorageKey;
+	sp_tracing::try_init_simple();
+	let mut net = TestNet::new(1);
+	let hashes = net.peer(0).push_blocks(64, false);
+	let hashof60 = hashes[59];
+	net.peer(0).client().finalize_block(hashof60, None, true).unwrap();
+
+	let dir = tempfile::tempdir().unwrap();
+	let snapshot = dir.path().join("state.snap");
+	sc_service::chain_ops::export_snapshot(
+		net.peer(0).client().as_client(),
+		hashof60,
+		std::fs::File::create(&snapshot).unwrap(),
+	)
+	.unwrap();
+
+	net.add_full_peer_with_config(FullPeerConfig {
+		sync_mode: SyncMode::Checkpoint,
+		checkpoint_snapshot: Some(snapshot),
+		..Default::default()
+	});
+	assert!(!net.peer(1).client().has_state_at(&BlockId::Number(0)));
+
+	// The state of the checkpoint is imported without downloading it ...
+	futures::future::poll_fn::<(), _>(|cx| {
+		net.poll(cx);
+		if net.peer(1).client().has_state_at(&BlockId::Hash(hashof60)) {
+			Poll::Ready(())
+		} else {
+			Poll::Pending
+		}
+	})
+	.await;
+	let code = StorageKey(b":code".to_vec());
+	assert_eq!(
+		net.peer(1).client().as_client().storage(hashof60, &code).unwrap(),
+		net.peer(0).client().as_client().storage(hashof60, &code).unwrap(),
+	);
+
+	// ... then the chain is synced from there and its history is backfilled.
+	futures::future::poll_fn::<(), _>(|cx| {
+		net.poll(cx);
+		let info = net.peer(1).client().info();
+		if info.best_number == 64 && info.block_gap.is_none() {
+			Poll::Ready(())
+		} else {
+			Poll::Pending
+		}
+	})
+	.await;
+	assert!(net.peer(1).client().has_state_at(&BlockId::Number(64)));
+	assert!(net.peer(1).client().header(hashes[0]).unwrap().is_some());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn light_peer_syncs_headers_and_calls_runtime_of_full_peers() {
+	use sc_client_api::{
+		CallExecutor, ExecutorProvider, ProofProvider, StorageKey, StorageProvider,
+	};
+	use sc_network_light::light_client_requests::sender::{Error, LightClientRequestSender};
+	use sp_core::traits::CallContext;
+	use substrate_test_runtime_client::WasmExecutor;
+	sp_tracing::try_init_simple();
+	let mut net = TestNet::new(1);
+	let hashes = net.peer(0).push_blocks(16, false);
+	let best_hash = hashes[15];
+	net.add_full_peer_with_config(FullPeerConfig {
+		sync_mode: SyncMode::Light,
+		..Default::default()
+	});
+
+	// Only the headers are synced ...
+	futures::future::poll_fn::<(), _>(|cx| {
+		net.poll(cx);
+		if net.peer(1).client().info().best_hash == best_hash {
+			Poll::Ready(())
+		} else {
+			Poll::Pending
+		}
+	})
+	.await;
+	assert!(!net.peer(1).has_body(best_hash));
+	assert!(!net.peer(1).client().has_state_at(&BlockId::Hash(best_hash)));
+
+	// ... and runtime calls are answered by the full peer.
+	let light = net.peer(1);
+	let sender = LightClientRequestSender::new(
+		light.client().as_client(),
+		WasmExecutor::<sp_io::SubstrateHostFunctions>::builder().build(),
+		light.network_service().clone(),
+		light.client().info().genesis_hash,
+		None,
+	);
+	tokio::spawn(sender.clone().track_peers(light.sync_service().clone()));
+
+	let version = loop {
+		let request = sender.remote_call(best_hash, "Core_version", Vec::new());
+		pin_mut!(request);
+		match futures::future::poll_fn(|cx| {
+			net.poll(cx);
+			request.as_mut().poll(cx)
+		})
+		.await
+		{
+			Ok(version) => break version,
+			// The full peer is not tracked yet.
+			Err(Error::Unanswered(0)) => tokio::time::sleep(Duration::from_millis(100)).await,
+			Err(e) => panic!("Runtime call failed: {e}"),
+		}
+	};
+	let (expected, _) = net
+		.peer(0)
+		.client()
+		.as_client()
+		.execution_proof(best_hash, "Core_version", &[])
+		.unwrap();
+	assert_eq!(version, expected);
+
+	// The client of the light peer falls back to the full peer for the state it doesn't have.
+	let client = net.peer(1).client().as_client();
+	assert!(client.executor().set_remote_state_fetcher(Arc::new(sender)));
+	let code = StorageKey(b":code".to_vec());
+	let (tx, rx) = futures::channel::oneshot::channel();
+	let thread_code = code.clone();
+	std::thread::spawn(move || {
+		let _ = tx
+			.send((client.runtime_version_at(best_hash), client.storage(best_hash, &thread_code)));
+	});
+	pin_mut!(rx);
+	let (version, storage) = futures::future::poll_fn(|cx| {
+		net.poll(cx);
+		rx.as_mut().poll(cx)
+	})
+	.await
+	.unwrap();
+	let full = net.peer(0).client().as_client();
+	assert_eq!(version.unwrap(), full.runtime_version_at(best_hash).unwrap());
+	assert_eq!(storage.unwrap(), full.storage(best_hash, &code).unwrap());
+
+	// Calls recording a proof are not sent to the full peer, as the proof can't be recorded.
+	let client = net.peer(1).client().as_client();
+	let result = client.executor().contextual_call(
+		best_hash,
+		"Core_version",
+		&[],
+		&Default::default(),
+		&Some(Default::default()),
+		CallContext::Offchain,
+		&Default::default(),
+	);
+	assert!(matches!(
+		result,
+		Err(e) if !matches!(
+			e,
+			sp_blockchain::Error::RemoteFetchFailed | sp_blockchain::Error::RemoteFetchCancelled
+		)
+	));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn syncs_indexed_blocks() {
 	use sp_runtime::traits::Hash;
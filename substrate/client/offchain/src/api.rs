@@ -18,7 +18,7 @@
 
 use std::{collections::HashSet, str::FromStr, sync::Arc, thread::sleep};
 
-use crate::NetworkProvider;
+use crate::{http_policy::SharedHttpPolicy, NetworkProvider};
 use codec::{Decode, Encode};
 use futures::Future;
 pub use http::SharedClient;
@@ -201,8 +201,9 @@ impl AsyncApi {
 		network_provider: Arc<dyn NetworkProvider + Send + Sync>,
 		is_validator: bool,
 		shared_http_client: SharedClient,
+		http_policy: Arc<SharedHttpPolicy>,
 	) -> (Api, Self) {
-		let (http_api, http_worker) = http::http(shared_http_client);
+		let (http_api, http_worker) = http::http(shared_http_client, http_policy);
 
 		let api = Api { network_provider, is_validator, http: http_api };
 
@@ -328,7 +329,7 @@ mod tests {
 		let mock = Arc::new(TestNetwork());
 		let shared_client = SharedClient::new().unwrap();
 
-		AsyncApi::new(mock, false, shared_client)
+		AsyncApi::new(mock, false, shared_client, Default::default())
 	}
 
 	fn offchain_db() -> OffchainDb<LocalStorage> {
@@ -27,7 +27,10 @@
 //! (i.e.: the socket should continue being processed) in the background even if the runtime isn't
 //! actively calling any function.
 
-use crate::api::timestamp;
+use crate::{
+	api::timestamp,
+	http_policy::{CacheKey, CachedResponse, Outcome, SharedHttpPolicy, MAX_CACHED_BODY_SIZE},
+};
 use bytes::buf::{Buf, Reader};
 use fnv::FnvHashMap;
 use futures::{channel::mpsc, future, prelude::*};
@@ -72,7 +75,7 @@ impl SharedClient {
 }
 
 /// Creates a pair of [`HttpApi`] and [`HttpWorker`].
-pub fn http(shared_client: SharedClient) -> (HttpApi, HttpWorker) {
+pub fn http(shared_client: SharedClient, policy: Arc<SharedHttpPolicy>) -> (HttpApi, HttpWorker) {
 	let (to_worker, from_api) = tracing_unbounded("mpsc_ocw_to_worker", 100_000);
 	let (to_api, from_worker) = tracing_unbounded("mpsc_ocw_to_api", 100_000);
 
@@ -83,6 +86,11 @@ pub fn http(shared_client: SharedClient) -> (HttpApi, HttpWorker) {
 		// writing runtime code with hardcoded IDs.
 		next_id: HttpRequestId(rand::random::<u16>() % 2000),
 		requests: FnvHashMap::default(),
+		policy,
+		request_hosts: FnvHashMap::default(),
+		cache_keys: FnvHashMap::default(),
+		requests_started: 0,
+		bytes_transferred: 0,
 	};
 
 	let engine =
@@ -105,6 +113,16 @@ pub struct HttpApi {
 	next_id: HttpRequestId,
 	/// List of HTTP requests in preparation or in progress.
 	requests: FnvHashMap<HttpRequestId, HttpApiRequest>,
+	/// Policy of the node, shared with the other offchain workers.
+	policy: Arc<SharedHttpPolicy>,
+	/// Host label of every request started, for the metrics, see [`SharedHttpPolicy::host_label`].
+	request_hosts: FnvHashMap<HttpRequestId, String>,
+	/// Keys to cache the responses of the dispatched requests with.
+	cache_keys: FnvHashMap<HttpRequestId, CacheKey>,
+	/// Number of requests started, counted against the request quota.
+	requests_started: u32,
+	/// Number of body bytes sent and received, counted against the bandwidth quota.
+	bytes_transferred: u64,
 }
 
 /// One active request within `HttpApi`.
@@ -120,6 +138,11 @@ enum HttpApiRequest {
 	/// failure to the user as an `IoError` and remove the request from the list as soon as
 	/// possible.
 	Fail(client::Error),
+	/// The request was denied by the HTTP policy, either when starting it or because it exceeded
+	/// the bandwidth quota. We report this to the user as `Denied`, or as an `IoError` when
+	/// waiting for the response. The first version of the HTTP host functions reports `Denied` as
+	/// an `IoError` as well, since older runtimes cannot decode it.
+	Denied,
 }
 
 /// A request within `HttpApi` that has received a response.
@@ -141,6 +164,28 @@ struct HttpApiRequestRp {
 	/// Chunk that has been extracted from the channel and that is currently being read.
 	/// Reading data from the response should read from this field in priority.
 	current_read_chunk: Option<Reader<hyper::body::Bytes>>,
+	/// Body read so far, to be cached under the key once complete.
+	recording: Option<(CacheKey, Vec<u8>)>,
+}
+
+impl HttpApiRequestRp {
+	/// A response answered from the cache.
+	fn cached(response: CachedResponse) -> Self {
+		let (mut body_tx, body_rx) = mpsc::channel(0);
+		if !response.body.is_empty() {
+			// A channel always has room for one item per sender.
+			let _ = body_tx.try_send(Ok(hyper::body::Frame::data(response.body)));
+		}
+
+		HttpApiRequestRp {
+			sending_body: None,
+			status_code: response.status_code,
+			headers: response.headers,
+			body: body_rx.fuse(),
+			current_read_chunk: None,
+			recording: None,
+		}
+	}
 }
 
 impl HttpApi {
@@ -168,8 +213,25 @@ impl HttpApi {
 				return Err(());
 			},
 		};
-		self.requests
-			.insert(new_id, HttpApiRequest::NotDispatched(request, body_sender));
+
+		// Denied requests are still started, so that the user is told about it with a distinct
+		// error on its next call.
+		let host = self.policy.host_label(request.uri());
+		let policy = &self.policy.policy;
+		if !policy.is_allowed(request.uri()) {
+			tracing::debug!(target: LOG_TARGET, id = %new_id.0, %uri, "Request denied by policy");
+			self.policy.report_request(&host, Outcome::Denied);
+			self.requests.insert(new_id, HttpApiRequest::Denied);
+		} else if policy.max_requests_per_block.is_some_and(|max| self.requests_started >= max) {
+			tracing::debug!(target: LOG_TARGET, id = %new_id.0, %uri, "Request quota exceeded");
+			self.policy.report_request(&host, Outcome::Denied);
+			self.requests.insert(new_id, HttpApiRequest::Denied);
+		} else {
+			self.requests_started += 1;
+			self.requests
+				.insert(new_id, HttpApiRequest::NotDispatched(request, body_sender));
+		}
+		self.request_hosts.insert(new_id, host);
 
 		tracing::trace!(
 			target: LOG_TARGET,
@@ -191,6 +253,8 @@ impl HttpApi {
 	) -> Result<(), ()> {
 		let request = match self.requests.get_mut(&request_id) {
 			Some(&mut HttpApiRequest::NotDispatched(ref mut rq, _)) => rq,
+			// The denial is reported when writing the body, which `()` can't express.
+			Some(&mut HttpApiRequest::Denied) => return Ok(()),
 			_ => return Err(()),
 		};
 
@@ -247,16 +311,28 @@ impl HttpApi {
 		loop {
 			request = match request {
 				HttpApiRequest::NotDispatched(request, sender) => {
+					// A request without a body might be answered from the cache.
+					if chunk.is_empty() {
+						if let Some(response) = self.cached_response(request_id, &request) {
+							tracing::debug!(target: LOG_TARGET, id = %request_id.0, "Finished writing body");
+							self.requests.insert(request_id, response);
+							return Ok(());
+						}
+					}
+
 					tracing::debug!(target: LOG_TARGET, id = %request_id.0, "Added new body chunk");
 					// If the request is not dispatched yet, dispatch it and loop again.
-					let _ = self
-						.to_worker
-						.unbounded_send(ApiToWorker::Dispatch { id: request_id, request });
+					self.dispatch(request_id, request);
 					HttpApiRequest::Dispatched(Some(sender))
 				},
 
 				HttpApiRequest::Dispatched(Some(mut sender)) => {
 					if !chunk.is_empty() {
+						if !self.consume_bandwidth(request_id, chunk.len()) {
+							self.requests.insert(request_id, HttpApiRequest::Denied);
+							return Err(HttpError::Denied);
+						}
+						self.policy.report_sent(self.host(request_id), chunk.len());
 						match poll_sender(&mut sender) {
 							Err(HttpError::IoError) => {
 								tracing::debug!(target: LOG_TARGET, id = %request_id.0, "Encountered io error while trying to add new chunk to body");
@@ -283,6 +359,11 @@ impl HttpApi {
 					mut response @ HttpApiRequestRp { sending_body: Some(_), .. },
 				) => {
 					if !chunk.is_empty() {
+						if !self.consume_bandwidth(request_id, chunk.len()) {
+							self.requests.insert(request_id, HttpApiRequest::Denied);
+							return Err(HttpError::Denied);
+						}
+						self.policy.report_sent(self.host(request_id), chunk.len());
 						match poll_sender(
 							response
 								.sending_body
@@ -324,6 +405,11 @@ impl HttpApi {
 					return Err(HttpError::IoError);
 				},
 
+				HttpApiRequest::Denied => {
+					self.requests.insert(request_id, HttpApiRequest::Denied);
+					return Err(HttpError::Denied);
+				},
+
 				v @ HttpApiRequest::Dispatched(None) |
 				v @ HttpApiRequest::Response(HttpApiRequestRp { sending_body: None, .. }) => {
 					tracing::debug!(target: LOG_TARGET, id = %request_id.0, "Body sending already finished");
@@ -360,7 +446,12 @@ impl HttpApi {
 				_ => unreachable!("we checked for NotDispatched above; qed"),
 			};
 
-			let _ = self.to_worker.unbounded_send(ApiToWorker::Dispatch { id: *id, request });
+			if let Some(response) = self.cached_response(*id, &request) {
+				self.requests.insert(*id, response);
+				continue;
+			}
+
+			self.dispatch(*id, request);
 
 			// We also destroy the sender in order to forbid writing more data.
 			self.requests.insert(*id, HttpApiRequest::Dispatched(None));
@@ -384,7 +475,8 @@ impl HttpApi {
 							must_wait_more = true;
 							HttpRequestStatus::DeadlineReached
 						},
-						Some(HttpApiRequest::Fail(_)) => HttpRequestStatus::IoError,
+						Some(HttpApiRequest::Fail(_)) | Some(HttpApiRequest::Denied) =>
+							HttpRequestStatus::IoError,
 						Some(HttpApiRequest::Response(HttpApiRequestRp {
 							status_code, ..
 						})) => HttpRequestStatus::Finished(status_code.as_u16()),
@@ -401,8 +493,14 @@ impl HttpApi {
 					debug_assert_eq!(output.len(), ids.len());
 					for n in (0..ids.len()).rev() {
 						match output[n] {
+							// Denied requests are kept to report `Denied` when reading their body.
 							HttpRequestStatus::IoError => {
-								self.requests.remove(&ids[n]);
+								if !matches!(
+									self.requests.get(&ids[n]),
+									Some(HttpApiRequest::Denied)
+								) {
+									self.requests.remove(&ids[n]);
+								}
 							},
 							HttpRequestStatus::Invalid => {
 								tracing::debug!(target: LOG_TARGET, id = %ids[n].0, "Unknown request");
@@ -437,6 +535,8 @@ impl HttpApi {
 				Some(WorkerToApi::Response { id, status_code, headers, body }) => {
 					match self.requests.remove(&id) {
 						Some(HttpApiRequest::Dispatched(sending_body)) => {
+							let recording =
+								self.cache_keys.remove(&id).map(|key| (key, Vec::new()));
 							self.requests.insert(
 								id,
 								HttpApiRequest::Response(HttpApiRequestRp {
@@ -445,10 +545,15 @@ impl HttpApi {
 									headers,
 									body: body.fuse(),
 									current_read_chunk: None,
+									recording,
 								}),
 							);
 						},
 						None => {}, // can happen if we detected an IO error when sending the body
+						// The request exceeded the bandwidth quota while sending the body.
+						Some(HttpApiRequest::Denied) => {
+							self.requests.insert(id, HttpApiRequest::Denied);
+						},
 						_ => {
 							tracing::error!(target: "offchain-worker::http", "State mismatch between the API and worker")
 						},
@@ -461,6 +566,9 @@ impl HttpApi {
 						self.requests.insert(id, HttpApiRequest::Fail(error));
 					},
 					None => {}, // can happen if we detected an IO error when sending the body
+					Some(HttpApiRequest::Denied) => {
+						self.requests.insert(id, HttpApiRequest::Denied);
+					},
 					_ => {
 						tracing::error!(target: "offchain-worker::http", "State mismatch between the API and worker")
 					},
@@ -512,6 +620,11 @@ impl HttpApi {
 			},
 			// The request has failed.
 			Some(HttpApiRequest::Fail { .. }) => return Err(HttpError::IoError),
+			// The request was denied by the policy.
+			Some(HttpApiRequest::Denied) => {
+				self.requests.insert(request_id, HttpApiRequest::Denied);
+				return Err(HttpError::Denied);
+			},
 			// Request hasn't been dispatched yet; reading the body is invalid.
 			Some(rq @ HttpApiRequest::NotDispatched(_, _)) => {
 				self.requests.insert(request_id, rq);
@@ -529,6 +642,21 @@ impl HttpApi {
 				match current_read_chunk.read(buffer) {
 					Ok(0) => {},
 					Ok(n) => {
+						if !self.consume_bandwidth(request_id, n) {
+							self.requests.insert(request_id, HttpApiRequest::Denied);
+							return Err(HttpError::Denied);
+						}
+						self.policy.report_received(self.host(request_id), n);
+						if let Some((_, recorded)) = &mut response.recording {
+							recorded.extend_from_slice(&buffer[..n]);
+						}
+						if response
+							.recording
+							.as_ref()
+							.is_some_and(|(_, recorded)| recorded.len() > MAX_CACHED_BODY_SIZE)
+						{
+							response.recording = None;
+						}
 						self.requests.insert(
 							request_id,
 							HttpApiRequest::Response(HttpApiRequestRp {
@@ -559,7 +687,19 @@ impl HttpApi {
 							response.current_read_chunk = Some(chunk.reader());
 						},
 					Some(Err(_)) => return Err(HttpError::IoError),
-					None => return Ok(0), // eof
+					None => {
+						if let Some((key, body)) = response.recording.take() {
+							self.policy.cache(
+								key,
+								CachedResponse {
+									status_code: response.status_code,
+									headers: response.headers,
+									body: body.into(),
+								},
+							);
+						}
+						return Ok(0) // eof
+					},
 				}
 			}
 
@@ -571,6 +711,59 @@ impl HttpApi {
 	}
 }
 
+impl HttpApi {
+	/// Sends a request to the worker.
+	fn dispatch(&mut self, id: HttpRequestId, request: hyper::Request<Body>) {
+		self.policy.report_request(self.host(id), Outcome::Sent);
+		let _ = self.to_worker.unbounded_send(ApiToWorker::Dispatch { id, request });
+	}
+
+	/// Returns the cached response to a request that is about to be dispatched, if any.
+	///
+	/// If the response of the request can be cached, it is recorded while being read.
+	fn cached_response(
+		&mut self,
+		id: HttpRequestId,
+		request: &hyper::Request<Body>,
+	) -> Option<HttpApiRequest> {
+		let key = self.policy.cache_key(request)?;
+		match self.policy.cached(&key) {
+			Some(response) => {
+				tracing::debug!(target: LOG_TARGET, id = %id.0, "Answered from cache");
+				self.policy.report_request(self.host(id), Outcome::Cached);
+				Some(HttpApiRequest::Response(HttpApiRequestRp::cached(response)))
+			},
+			None => {
+				self.cache_keys.insert(id, key);
+				None
+			},
+		}
+	}
+
+	/// Counts `bytes` of body against the bandwidth quota.
+	///
+	/// Returns `false` if the quota is exceeded.
+	fn consume_bandwidth(&mut self, id: HttpRequestId, bytes: usize) -> bool {
+		self.bytes_transferred = self.bytes_transferred.saturating_add(bytes as u64);
+		if self
+			.policy
+			.policy
+			.max_bytes_per_block
+			.is_some_and(|max| self.bytes_transferred > max)
+		{
+			tracing::debug!(target: LOG_TARGET, id = %id.0, "Bandwidth quota exceeded");
+			self.policy.report_request(self.host(id), Outcome::Denied);
+			return false
+		}
+		true
+	}
+
+	/// Returns the host label of a request, for the metrics.
+	fn host(&self, id: HttpRequestId) -> &str {
+		self.request_hosts.get(&id).map_or("", String::as_str)
+	}
+}
+
 impl fmt::Debug for HttpApi {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		f.debug_list().entries(self.requests.iter()).finish()
@@ -589,6 +782,7 @@ impl fmt::Debug for HttpApiRequest {
 				.field(headers)
 				.finish(),
 			HttpApiRequest::Fail(err) => f.debug_tuple("HttpApiRequest::Fail").field(err).finish(),
+			HttpApiRequest::Denied => f.debug_tuple("HttpApiRequest::Denied").finish(),
 		}
 	}
 }
@@ -775,7 +969,7 @@ mod tests {
 		super::{tests::TestNetwork, AsyncApi},
 		*,
 	};
-	use crate::api::timestamp;
+	use crate::{api::timestamp, http_policy::HttpPolicy};
 	use core::convert::Infallible;
 	use futures::future;
 	use http_body_util::BodyExt;
@@ -794,9 +988,12 @@ mod tests {
 				hyper::body::Bytes::from("Hello World!")
 			)))
 		};
-		( $response:expr ) => {{
+		( $response:expr ) => {
+			build_api_server!(Default::default(), $response)
+		};
+		( $policy:expr, $response:expr ) => {{
 			let hyper_client = SHARED_CLIENT.clone();
-			let (api, worker) = http(hyper_client.clone());
+			let (api, worker) = http(hyper_client.clone(), $policy);
 
 			let (addr_tx, addr_rx) = std::sync::mpsc::channel();
 			std::thread::spawn(move || {
@@ -887,6 +1084,113 @@ mod tests {
 		assert_eq!(&buf[..n], b"Hello World!");
 	}
 
+	fn policy(policy: HttpPolicy) -> Arc<SharedHttpPolicy> {
+		Arc::new(SharedHttpPolicy::new(policy, None).unwrap())
+	}
+
+	#[test]
+	fn denied_request_reports_denied() {
+		let deadline = timestamp::now().add(Duration::from_millis(10_000));
+		let allowed = vec!["example.com".parse().unwrap()];
+		let (mut api, addr) = build_api_server!(
+			policy(HttpPolicy { allowed, ..Default::default() }),
+			hyper::Response::new(http_body_util::Full::new(hyper::body::Bytes::from("")))
+		);
+
+		let id = api.request_start("GET", &format!("http://{}", addr)).unwrap();
+		api.request_add_header(id, "Foo", "Bar").unwrap();
+		assert_eq!(api.request_write_body(id, &[], Some(deadline)), Err(HttpError::Denied));
+		assert_eq!(api.response_wait(&[id], Some(deadline)), vec![HttpRequestStatus::IoError]);
+		assert!(api.response_headers(id).is_empty());
+		assert_eq!(
+			api.response_read_body(id, &mut [0; 16], Some(deadline)),
+			Err(HttpError::Denied)
+		);
+	}
+
+	#[test]
+	fn request_quota_is_enforced() {
+		let deadline = timestamp::now().add(Duration::from_millis(10_000));
+		let (mut api, addr) = build_api_server!(
+			policy(HttpPolicy { max_requests_per_block: Some(1), ..Default::default() }),
+			hyper::Response::new(http_body_util::Full::new(hyper::body::Bytes::from("")))
+		);
+
+		let id = api.request_start("POST", &format!("http://{}", addr)).unwrap();
+		api.request_write_body(id, &[], Some(deadline)).unwrap();
+		assert_eq!(
+			api.response_wait(&[id], Some(deadline)),
+			vec![HttpRequestStatus::Finished(200)]
+		);
+
+		let id = api.request_start("POST", &format!("http://{}", addr)).unwrap();
+		assert_eq!(api.request_write_body(id, &[], Some(deadline)), Err(HttpError::Denied));
+	}
+
+	#[test]
+	fn bandwidth_quota_is_enforced() {
+		let deadline = timestamp::now().add(Duration::from_millis(10_000));
+		let (mut api, addr) = build_api_server!(
+			policy(HttpPolicy { max_bytes_per_block: Some(8), ..Default::default() }),
+			hyper::Response::new(http_body_util::Full::new(hyper::body::Bytes::from(
+				"Hello World!"
+			)))
+		);
+
+		let id = api.request_start("POST", &format!("http://{}", addr)).unwrap();
+		api.request_write_body(id, &[1, 2, 3, 4], Some(deadline)).unwrap();
+		api.request_write_body(id, &[], Some(deadline)).unwrap();
+		assert_eq!(
+			api.response_wait(&[id], Some(deadline)),
+			vec![HttpRequestStatus::Finished(200)]
+		);
+
+		// 4 bytes were sent, so the 12 bytes of the body exceed the quota.
+		let mut buf = vec![0; 2048];
+		assert_eq!(api.response_read_body(id, &mut buf, Some(deadline)), Err(HttpError::Denied));
+		assert_eq!(api.response_read_body(id, &mut buf, Some(deadline)), Err(HttpError::Denied));
+	}
+
+	#[test]
+	fn responses_are_cached() {
+		let deadline = timestamp::now().add(Duration::from_millis(10_000));
+		let policy = policy(HttpPolicy {
+			response_cache_ttl: Some(std::time::Duration::from_secs(60)),
+			..Default::default()
+		});
+		let (mut api, addr) = build_api_server!(policy.clone(), {
+			static CALLS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+			let calls = CALLS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+			hyper::Response::new(http_body_util::Full::new(hyper::body::Bytes::from(format!(
+				"Call {calls}"
+			))))
+		});
+
+		let read = |api: &mut HttpApi| {
+			let id = api.request_start("GET", &format!("http://{}", addr)).unwrap();
+			api.request_write_body(id, &[], Some(deadline)).unwrap();
+			assert_eq!(
+				api.response_wait(&[id], Some(deadline)),
+				vec![HttpRequestStatus::Finished(200)]
+			);
+			let mut body = Vec::new();
+			let mut buf = vec![0; 2048];
+			loop {
+				match api.response_read_body(id, &mut buf, Some(deadline)).unwrap() {
+					0 => return body,
+					n => body.extend_from_slice(&buf[..n]),
+				}
+			}
+		};
+
+		assert_eq!(read(&mut api), b"Call 0");
+		assert_eq!(read(&mut api), b"Call 0");
+
+		// The cache is shared with the other workers.
+		let (mut other_api, _worker) = http(SHARED_CLIENT.clone(), policy);
+		assert_eq!(read(&mut other_api), b"Call 0");
+	}
+
 	#[test]
 	fn request_start_invalid_call() {
 		let (mut api, addr) = build_api_server!();
@@ -1130,7 +1434,8 @@ mod tests {
 
 		{
 			let mock = Arc::new(TestNetwork());
-			let (mut api, async_api) = AsyncApi::new(mock, false, shared_client.clone());
+			let (mut api, async_api) =
+				AsyncApi::new(mock, false, shared_client.clone(), Default::default());
 			api.timestamp();
 
 			futures::executor::block_on(async move {
@@ -1145,7 +1450,8 @@ mod tests {
 
 		{
 			let mock = Arc::new(TestNetwork());
-			let (mut api, async_api) = AsyncApi::new(mock, false, shared_client.clone());
+			let (mut api, async_api) =
+				AsyncApi::new(mock, false, shared_client.clone(), Default::default());
 			let id = api.http_request_start("lol", "nope", &[]).unwrap();
 			api.http_request_write_body(id, &[], None).unwrap();
 			futures::executor::block_on(async move {
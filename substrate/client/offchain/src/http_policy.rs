@@ -0,0 +1,513 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Operator policy for the HTTP requests of offchain workers.
+//!
+//! Nodes may run runtimes they don't control, so operators can restrict the hosts and URLs
+//! offchain workers are allowed to reach with [`HttpPolicy::allowed`], limit how much each worker
+//! may use the network for a block, and share responses between workers with a TTL-based cache.
+//! Requests violating the policy fail with [`HttpError::Denied`](sp_core::offchain::HttpError),
+//! or with `HttpError::IoError` for runtimes that use the first version of the HTTP host
+//! functions.
+
+use parking_lot::Mutex;
+use prometheus_endpoint::{register, CounterVec, Opts, PrometheusError, Registry, U64};
+use std::{
+	collections::{HashMap, HashSet},
+	fmt,
+	str::FromStr,
+	time::{Duration, Instant},
+};
+
+/// Maximum number of responses in the cache.
+const MAX_CACHED_RESPONSES: usize = 1024;
+
+/// Responses with a larger body are not cached.
+pub(crate) const MAX_CACHED_BODY_SIZE: usize = 1024 * 1024;
+
+/// Maximum number of hosts that get their own `host` label in the metrics.
+const MAX_HOST_LABELS: usize = 64;
+
+/// The `host` label of the metrics of requests to all other hosts.
+const OTHER_HOST: &str = "other";
+
+/// Policy applied to the HTTP requests of offchain workers.
+///
+/// The default policy allows everything and doesn't cache responses.
+#[derive(Debug, Clone, Default)]
+pub struct HttpPolicy {
+	/// Requests are only allowed to URLs matching one of these patterns.
+	///
+	/// Everything is allowed if empty.
+	pub allowed: Vec<UrlPattern>,
+	/// Maximum number of requests a worker can start for a block.
+	pub max_requests_per_block: Option<u32>,
+	/// Maximum number of body bytes a worker can send and receive for a block.
+	pub max_bytes_per_block: Option<u64>,
+	/// Cache successful `GET` responses for this long, sharing them between workers.
+	pub response_cache_ttl: Option<Duration>,
+}
+
+impl HttpPolicy {
+	/// Returns `true` if requests to `uri` are allowed.
+	pub fn is_allowed(&self, uri: &hyper::Uri) -> bool {
+		self.allowed.is_empty() || self.allowed.iter().any(|pattern| pattern.matches(uri))
+	}
+}
+
+/// A pattern of the URLs offchain workers are allowed to request.
+///
+/// Patterns containing `://` are matched against the scheme, host, port and path of the URL
+/// separately, others against its host only. `*` matches any sequence of characters, so
+/// `*.example.com` allows all subdomains of `example.com` and `https://example.com/api/*` all URLs
+/// under `/api/` over HTTPS. A URL pattern without a path allows all paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlPattern {
+	/// Pattern of the host.
+	Host(String),
+	/// Pattern of the scheme, host, port and path of the URL.
+	Url {
+		/// The scheme.
+		scheme: String,
+		/// Pattern of the host.
+		host: String,
+		/// The port, `None` for the default port of the scheme.
+		port: Option<u16>,
+		/// Pattern of the path, including the query.
+		path: String,
+	},
+}
+
+impl UrlPattern {
+	/// Returns `true` if `uri` matches the pattern.
+	pub fn matches(&self, uri: &hyper::Uri) -> bool {
+		match self {
+			Self::Host(pattern) => host_matches(pattern, uri),
+			Self::Url { scheme, host, port, path } =>
+				uri.scheme_str().is_some_and(|s| s.eq_ignore_ascii_case(scheme)) &&
+					host_matches(host, uri) &&
+					uri.port_u16().or_else(|| default_port(scheme)) ==
+						port.or_else(|| default_port(scheme)) &&
+					glob_matches(path, uri.path_and_query().map_or("/", |p| p.as_str())),
+		}
+	}
+}
+
+/// Returns `true` if the host of `uri` matches `pattern`.
+fn host_matches(pattern: &str, uri: &hyper::Uri) -> bool {
+	uri.host().is_some_and(|host| glob_matches(pattern, &host.to_ascii_lowercase()))
+}
+
+/// The default port of `scheme`.
+fn default_port(scheme: &str) -> Option<u16> {
+	match scheme {
+		"http" => Some(80),
+		"https" => Some(443),
+		_ => None,
+	}
+}
+
+impl FromStr for UrlPattern {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if s.is_empty() {
+			return Err("Empty URL pattern".into())
+		}
+		if s.contains(char::is_whitespace) {
+			return Err(format!("URL pattern `{s}` contains whitespace"))
+		}
+
+		let Some((scheme, rest)) = s.split_once("://") else {
+			return Ok(Self::Host(s.to_ascii_lowercase()))
+		};
+		let (authority, path) = match rest.find('/') {
+			Some(index) => rest.split_at(index),
+			None => (rest, "/*"),
+		};
+		let (host, port) = match authority.rsplit_once(':') {
+			Some((host, port)) => {
+				let port =
+					port.parse().map_err(|_| format!("Invalid port in URL pattern `{s}`"))?;
+				(host, Some(port))
+			},
+			None => (authority, None),
+		};
+		if scheme.is_empty() || scheme.contains('*') {
+			return Err(format!("Invalid scheme in URL pattern `{s}`"))
+		}
+		if host.is_empty() || host.contains(['@', '[', ']']) {
+			return Err(format!("Invalid host in URL pattern `{s}`"))
+		}
+
+		Ok(Self::Url {
+			scheme: scheme.to_ascii_lowercase(),
+			host: host.to_ascii_lowercase(),
+			port,
+			path: path.into(),
+		})
+	}
+}
+
+impl fmt::Display for UrlPattern {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Host(pattern) => f.write_str(pattern),
+			Self::Url { scheme, host, port, path } => {
+				write!(f, "{scheme}://{host}")?;
+				if let Some(port) = port {
+					write!(f, ":{port}")?;
+				}
+				f.write_str(path)
+			},
+		}
+	}
+}
+
+/// Matches `text` against `pattern`, where `*` matches any sequence of characters.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+	let (pattern, text) = (pattern.as_bytes(), text.as_bytes());
+	let (mut p, mut t) = (0, 0);
+	// Position of the last `*` in `pattern` and of the text it was tried at.
+	let mut backtrack = None;
+
+	while t < text.len() {
+		if p < pattern.len() && pattern[p] == b'*' {
+			backtrack = Some((p, t));
+			p += 1;
+		} else if p < pattern.len() && pattern[p] == text[t] {
+			p += 1;
+			t += 1;
+		} else if let Some((star, start)) = backtrack {
+			// Let the last `*` match one more character.
+			p = star + 1;
+			t = start + 1;
+			backtrack = Some((star, start + 1));
+		} else {
+			return false
+		}
+	}
+
+	pattern[p..].iter().all(|c| *c == b'*')
+}
+
+/// Identifies requests answered by the same cached response.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+	uri: String,
+	headers: Vec<(String, Vec<u8>)>,
+}
+
+/// A response in the cache.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedResponse {
+	pub status_code: hyper::StatusCode,
+	pub headers: hyper::HeaderMap,
+	pub body: hyper::body::Bytes,
+}
+
+/// Prometheus metrics of the HTTP requests, per host.
+#[derive(Clone)]
+struct Metrics {
+	requests: CounterVec<U64>,
+	bytes: CounterVec<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			requests: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_offchain_http_requests_total",
+						"HTTP requests of offchain workers",
+					),
+					&["host", "outcome"],
+				)?,
+				registry,
+			)?,
+			bytes: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_offchain_http_bytes_total",
+						"Body bytes sent and received by offchain workers",
+					),
+					&["host", "direction"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// Outcome of an HTTP request, reported in the metrics.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Outcome {
+	/// Denied by the policy.
+	Denied,
+	/// Answered from the cache.
+	Cached,
+	/// Sent to the host.
+	Sent,
+}
+
+impl Outcome {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::Denied => "denied",
+			Self::Cached => "cached",
+			Self::Sent => "sent",
+		}
+	}
+}
+
+/// The [`HttpPolicy`] together with the state shared by the HTTP APIs of all offchain workers.
+#[derive(Default)]
+pub(crate) struct SharedHttpPolicy {
+	pub policy: HttpPolicy,
+	cache: Mutex<HashMap<CacheKey, (Instant, CachedResponse)>>,
+	/// Hosts not on the allowlist that got their own `host` label.
+	labelled_hosts: Mutex<HashSet<String>>,
+	metrics: Option<Metrics>,
+}
+
+impl SharedHttpPolicy {
+	pub fn new(policy: HttpPolicy, registry: Option<&Registry>) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			policy,
+			cache: Default::default(),
+			labelled_hosts: Default::default(),
+			metrics: registry.map(Metrics::register).transpose()?,
+		})
+	}
+
+	/// The `host` label of the metrics of requests to `uri`.
+	///
+	/// Requests matching the allowlist are reported with the matching pattern, so wildcard
+	/// patterns are reported as is rather than the hosts they match. Other requests are reported
+	/// with their host, but only the first [`MAX_HOST_LABELS`] distinct hosts get their own label
+	/// and all later ones are reported as `other`, so that runtimes cannot create an unbounded
+	/// number of time series.
+	pub fn host_label(&self, uri: &hyper::Uri) -> String {
+		if let Some(pattern) = self.policy.allowed.iter().find(|pattern| pattern.matches(uri)) {
+			return pattern.to_string()
+		}
+		let Some(host) = uri.host().map(|host| host.to_ascii_lowercase()) else {
+			return OTHER_HOST.into()
+		};
+
+		let mut labelled_hosts = self.labelled_hosts.lock();
+		if labelled_hosts.contains(&host) {
+			return host
+		}
+		if labelled_hosts.len() < MAX_HOST_LABELS && host != OTHER_HOST {
+			labelled_hosts.insert(host.clone());
+			return host
+		}
+		OTHER_HOST.into()
+	}
+
+	/// Returns the key to cache the response of `request` with, if it may be cached.
+	pub fn cache_key<T>(&self, request: &hyper::Request<T>) -> Option<CacheKey> {
+		if self.policy.response_cache_ttl.is_none() || request.method() != hyper::Method::GET {
+			return None
+		}
+
+		let mut headers = request
+			.headers()
+			.iter()
+			.map(|(name, value)| (name.as_str().to_owned(), value.as_bytes().to_owned()))
+			.collect::<Vec<_>>();
+		headers.sort();
+		Some(CacheKey { uri: request.uri().to_string(), headers })
+	}
+
+	/// Returns the cached response for `key`, if it didn't expire.
+	pub fn cached(&self, key: &CacheKey) -> Option<CachedResponse> {
+		let ttl = self.policy.response_cache_ttl?;
+		let mut cache = self.cache.lock();
+		match cache.get(key) {
+			Some((inserted, response)) if inserted.elapsed() < ttl => Some(response.clone()),
+			Some(_) => {
+				cache.remove(key);
+				None
+			},
+			None => None,
+		}
+	}
+
+	/// Cache `response` for `key`.
+	pub fn cache(&self, key: CacheKey, response: CachedResponse) {
+		let Some(ttl) = self.policy.response_cache_ttl else { return };
+		if !response.status_code.is_success() || response.body.len() > MAX_CACHED_BODY_SIZE {
+			return
+		}
+
+		let mut cache = self.cache.lock();
+		if cache.len() >= MAX_CACHED_RESPONSES {
+			cache.retain(|_, (inserted, _)| inserted.elapsed() < ttl);
+		}
+		if cache.len() < MAX_CACHED_RESPONSES || cache.contains_key(&key) {
+			cache.insert(key, (Instant::now(), response));
+		}
+	}
+
+	/// Report a request to `host` in the metrics.
+	pub fn report_request(&self, host: &str, outcome: Outcome) {
+		if let Some(metrics) = &self.metrics {
+			metrics.requests.with_label_values(&[host, outcome.as_str()]).inc();
+		}
+	}
+
+	/// Report body bytes sent to `host` in the metrics.
+	pub fn report_sent(&self, host: &str, bytes: usize) {
+		if let Some(metrics) = &self.metrics {
+			metrics.bytes.with_label_values(&[host, "sent"]).inc_by(bytes as u64);
+		}
+	}
+
+	/// Report body bytes received from `host` in the metrics.
+	pub fn report_received(&self, host: &str, bytes: usize) {
+		if let Some(metrics) = &self.metrics {
+			metrics.bytes.with_label_values(&[host, "received"]).inc_by(bytes as u64);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn uri(s: &str) -> hyper::Uri {
+		s.parse().unwrap()
+	}
+
+	#[test]
+	fn glob_matching_works() {
+		assert!(glob_matches("example.com", "example.com"));
+		assert!(!glob_matches("example.com", "example.co"));
+		assert!(!glob_matches("example.com", "example.com.evil"));
+		assert!(glob_matches("*.example.com", "api.example.com"));
+		assert!(glob_matches("*.example.com", "a.b.example.com"));
+		assert!(!glob_matches("*.example.com", "example.com"));
+		assert!(!glob_matches("*.example.com", "evilexample.com"));
+		assert!(glob_matches("a*b*c", "aXbYbZc"));
+		assert!(glob_matches("*", ""));
+		assert!(!glob_matches("a*", ""));
+	}
+
+	#[test]
+	fn url_patterns_work() {
+		let host: UrlPattern = "*.Example.com".parse().unwrap();
+		assert_eq!(host, UrlPattern::Host("*.example.com".into()));
+		assert!(host.matches(&uri("https://API.example.com/prices")));
+		assert!(!host.matches(&uri("https://example.org/")));
+
+		let url: UrlPattern = "https://example.com/api/*".parse().unwrap();
+		assert!(url.matches(&uri("https://example.com/api/prices?asset=dot")));
+		assert!(url.matches(&uri("https://example.com:443/api/prices")));
+		assert!(!url.matches(&uri("http://example.com/api/prices")));
+		assert!(!url.matches(&uri("https://example.com:8443/api/prices")));
+		assert!(!url.matches(&uri("https://example.com/admin")));
+
+		let url: UrlPattern = "http://localhost:9933".parse().unwrap();
+		assert_eq!(url.to_string(), "http://localhost:9933/*");
+		assert!(url.matches(&uri("http://localhost:9933/health")));
+		assert!(!url.matches(&uri("http://localhost/health")));
+
+		assert!("".parse::<UrlPattern>().is_err());
+		assert!("example .com".parse::<UrlPattern>().is_err());
+		assert!("https://example.com:*/".parse::<UrlPattern>().is_err());
+		assert!("https://user@example.com/".parse::<UrlPattern>().is_err());
+	}
+
+	#[test]
+	fn url_pattern_wildcards_do_not_leave_the_host() {
+		let url: UrlPattern = "https://*.example.com/*".parse().unwrap();
+		assert!(url.matches(&uri("https://api.example.com/prices")));
+		assert!(!url.matches(&uri("https://attacker.net/x.example.com/")));
+		assert!(!url.matches(&uri("https://attacker.net/?x.example.com/")));
+		assert!(!url.matches(&uri("https://api.example.com.attacker.net/")));
+		assert!(!url.matches(&uri("https://attacker.net:443/.example.com/")));
+	}
+
+	#[test]
+	fn empty_allowlist_allows_everything() {
+		let mut policy = HttpPolicy::default();
+		assert!(policy.is_allowed(&uri("http://localhost:9933/")));
+
+		policy.allowed = vec!["example.com".parse().unwrap()];
+		assert!(policy.is_allowed(&uri("https://example.com/")));
+		assert!(!policy.is_allowed(&uri("http://localhost:9933/")));
+	}
+
+	#[test]
+	fn host_label_uses_allowlist_patterns() {
+		let policy = HttpPolicy {
+			allowed: vec!["Example.com".parse().unwrap(), "*.example.org".parse().unwrap()],
+			..Default::default()
+		};
+		let policy = SharedHttpPolicy::new(policy, None).unwrap();
+		assert_eq!(policy.host_label(&uri("https://EXAMPLE.com/prices")), "example.com");
+		assert_eq!(policy.host_label(&uri("https://api.example.org/")), "*.example.org");
+		assert_eq!(policy.host_label(&uri("http://localhost:9933/")), "localhost");
+	}
+
+	#[test]
+	fn host_label_is_bounded() {
+		let policy = SharedHttpPolicy::new(HttpPolicy::default(), None).unwrap();
+		assert_eq!(policy.host_label(&uri("https://API.example.com/")), "api.example.com");
+
+		for i in 1..MAX_HOST_LABELS {
+			let host = format!("host{i}.example.com");
+			assert_eq!(policy.host_label(&uri(&format!("https://{host}/"))), host);
+		}
+		assert_eq!(policy.host_label(&uri("https://one-too-many.example.com/")), OTHER_HOST);
+		assert_eq!(policy.host_label(&uri("https://api.example.com/prices")), "api.example.com");
+	}
+
+	#[test]
+	fn cache_respects_ttl_and_method() {
+		let policy = SharedHttpPolicy::new(
+			HttpPolicy {
+				response_cache_ttl: Some(Duration::from_millis(50)),
+				..Default::default()
+			},
+			None,
+		)
+		.unwrap();
+
+		let post = hyper::Request::post("https://example.com/").body(()).unwrap();
+		assert!(policy.cache_key(&post).is_none());
+
+		let get = hyper::Request::get("https://example.com/").body(()).unwrap();
+		let key = policy.cache_key(&get).unwrap();
+		assert!(policy.cached(&key).is_none());
+
+		let response = CachedResponse {
+			status_code: hyper::StatusCode::OK,
+			headers: Default::default(),
+			body: "Hello World!".into(),
+		};
+		policy.cache(key.clone(), response);
+		assert_eq!(policy.cached(&key).unwrap().body, "Hello World!");
+
+		std::thread::sleep(Duration::from_millis(60));
+		assert!(policy.cached(&key).is_none());
+	}
+}
@@ -42,6 +42,7 @@ use futures::{
 	prelude::*,
 };
 use parking_lot::Mutex;
+use prometheus_endpoint::Registry;
 use sc_client_api::BlockchainEvents;
 use sc_network::{NetworkPeers, NetworkStateInfo};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
@@ -53,7 +54,9 @@ use sp_runtime::traits::{self, Header};
 use threadpool::ThreadPool;
 
 mod api;
+mod http_policy;
 
+pub use http_policy::{HttpPolicy, UrlPattern};
 pub use sp_core::offchain::storage::OffchainDb;
 pub use sp_offchain::{OffchainWorkerApi, STORAGE_PREFIX};
 
@@ -110,6 +113,10 @@ pub struct OffchainWorkerOptions<RA, Block: traits::Block, Storage, CE> {
 	///
 	/// If not enabled, any http request will panic.
 	pub enable_http_requests: bool,
+	/// Policy applied to the http requests.
+	pub http_policy: HttpPolicy,
+	/// Registry for the metrics of the http requests.
+	pub prometheus_registry: Option<Registry>,
 	/// Callback to create custom [`Extension`]s that should be registered for the
 	/// `offchain_worker` runtime call.
 	///
@@ -132,6 +139,7 @@ pub struct OffchainWorkers<RA, Block: traits::Block, Storage> {
 	thread_pool: Mutex<ThreadPool>,
 	shared_http_client: api::SharedClient,
 	enable_http_requests: bool,
+	http_policy: Arc<http_policy::SharedHttpPolicy>,
 	keystore: Option<KeystorePtr>,
 	offchain_db: Option<OffchainDb<Storage>>,
 	transaction_pool: Option<OffchainTransactionPoolFactory<Block>>,
@@ -151,9 +159,15 @@ impl<RA, Block: traits::Block, Storage> OffchainWorkers<RA, Block, Storage> {
 			network_provider,
 			is_validator,
 			enable_http_requests,
+			http_policy,
+			prometheus_registry,
 			custom_extensions,
 		}: OffchainWorkerOptions<RA, Block, Storage, CE>,
 	) -> std::io::Result<Self> {
+		let http_policy =
+			http_policy::SharedHttpPolicy::new(http_policy, prometheus_registry.as_ref())
+				.map_err(std::io::Error::other)?;
+
 		Ok(Self {
 			runtime_api_provider,
 			thread_pool: Mutex::new(ThreadPool::with_name(
@@ -162,6 +176,7 @@ impl<RA, Block: traits::Block, Storage> OffchainWorkers<RA, Block, Storage> {
 			)),
 			shared_http_client: api::SharedClient::new()?,
 			enable_http_requests,
+			http_policy: Arc::new(http_policy),
 			keystore,
 			offchain_db: offchain_db.map(OffchainDb::new),
 			transaction_pool,
@@ -247,6 +262,7 @@ where
 				self.network_provider.clone(),
 				self.is_validator,
 				self.shared_http_client.clone(),
+				self.http_policy.clone(),
 			);
 			tracing::debug!(target: LOG_TARGET, "Spawning offchain workers at {hash:?}");
 			let header = header.clone();
@@ -465,6 +481,8 @@ mod tests {
 			network_provider: network,
 			is_validator: false,
 			enable_http_requests: false,
+			http_policy: Default::default(),
+			prometheus_registry: None,
 			custom_extensions: |_| Vec::new(),
 		})
 		.unwrap();
@@ -0,0 +1,48 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Indexer RPC errors.
+
+use jsonrpsee::types::error::{ErrorObject, ErrorObjectOwned};
+
+/// Indexer RPC Result type.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Indexer RPC errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The indexer database could not be queried.
+	#[error("Indexer database error: {0}")]
+	Database(String),
+	/// The indexed data could not be decoded.
+	#[error("Indexed data is corrupt: {0}")]
+	Corrupt(String),
+}
+
+/// Base error code for all indexer errors.
+const BASE_ERROR: i32 = crate::error::base::INDEXER;
+
+impl From<Error> for ErrorObjectOwned {
+	fn from(e: Error) -> Self {
+		let code = match e {
+			Error::Database(_) => BASE_ERROR + 1,
+			Error::Corrupt(_) => BASE_ERROR + 2,
+		};
+		ErrorObject::owned(code, e.to_string(), None::<()>)
+	}
+}
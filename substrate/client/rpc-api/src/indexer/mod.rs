@@ -0,0 +1,156 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate indexer API.
+//!
+//! Read-only access to the blocks, extrinsics, events and transfers that the node indexed into its
+//! local database.
+
+pub mod error;
+
+use error::Error;
+use jsonrpsee::proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+
+/// The maximum number of transfers returned by a single call.
+pub const MAX_TRANSFERS: u32 = 1000;
+
+/// An indexed block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedBlock<Hash> {
+	/// The hash of the block.
+	pub hash: Hash,
+	/// The hash of the parent of the block.
+	pub parent_hash: Hash,
+	/// The number of the block.
+	pub number: u64,
+	/// The spec version of the runtime the block was decoded with.
+	pub spec_version: u32,
+	/// Whether the block is finalized.
+	///
+	/// Blocks that are not finalized might still be removed from the index by a re-org.
+	pub finalized: bool,
+}
+
+/// An indexed extrinsic.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedExtrinsic<Hash> {
+	/// The hash of the block that includes the extrinsic.
+	pub block_hash: Hash,
+	/// The index of the extrinsic in the block.
+	pub index: u32,
+	/// The hash of the extrinsic.
+	pub hash: Hash,
+	/// The name of the pallet of the call, empty if the extrinsic could not be decoded.
+	pub pallet: String,
+	/// The name of the call, empty if the extrinsic could not be decoded.
+	pub call: String,
+	/// The SCALE encoded address of the signer, if the extrinsic is signed.
+	pub signer: Option<Bytes>,
+	/// Whether the extrinsic was dispatched successfully, if known.
+	pub success: Option<bool>,
+	/// The decoded arguments of the call.
+	pub args: String,
+	/// The SCALE encoded extrinsic, if it could not be decoded.
+	pub raw: Option<Bytes>,
+}
+
+/// An indexed event.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEvent<Hash> {
+	/// The hash of the block that emitted the event.
+	pub block_hash: Hash,
+	/// The index of the event in the block.
+	pub index: u32,
+	/// The index of the extrinsic that emitted the event, if any.
+	pub extrinsic_index: Option<u32>,
+	/// The name of the pallet of the event.
+	pub pallet: String,
+	/// The name of the event.
+	pub variant: String,
+	/// The decoded fields of the event.
+	pub fields: String,
+	/// The SCALE encoded event, if it could not be decoded.
+	///
+	/// Events that follow an event that could not be split off are not indexed separately, their
+	/// bytes are part of the bytes of that event.
+	pub raw: Option<Bytes>,
+}
+
+/// An indexed transfer of the native balance.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedTransfer<Hash> {
+	/// The hash of the block of the transfer.
+	pub block_hash: Hash,
+	/// The number of the block of the transfer.
+	pub block_number: u64,
+	/// The index of the `Transfer` event in the block.
+	pub event_index: u32,
+	/// The index of the extrinsic that made the transfer, if any.
+	pub extrinsic_index: Option<u32>,
+	/// The sender of the transfer.
+	pub from: Bytes,
+	/// The recipient of the transfer.
+	pub to: Bytes,
+	/// The transferred amount, as a decimal string.
+	pub amount: String,
+}
+
+/// Substrate indexer RPC API.
+#[rpc(client, server)]
+pub trait IndexerApi<Hash> {
+	/// Get an indexed block by its hash.
+	#[method(name = "indexer_block")]
+	async fn block(&self, hash: Hash) -> Result<Option<IndexedBlock<Hash>>, Error>;
+
+	/// Get all indexed blocks with the given number, one per fork.
+	#[method(name = "indexer_blocksByNumber")]
+	async fn blocks_by_number(&self, number: u64) -> Result<Vec<IndexedBlock<Hash>>, Error>;
+
+	/// Get the extrinsics of an indexed block.
+	#[method(name = "indexer_extrinsics")]
+	async fn extrinsics(&self, block: Hash) -> Result<Vec<IndexedExtrinsic<Hash>>, Error>;
+
+	/// Get all indexed extrinsics with the given hash.
+	#[method(name = "indexer_extrinsicsByHash")]
+	async fn extrinsics_by_hash(&self, hash: Hash) -> Result<Vec<IndexedExtrinsic<Hash>>, Error>;
+
+	/// Get the events of an indexed block, optionally only those of `pallet`.
+	#[method(name = "indexer_events")]
+	async fn events(
+		&self,
+		block: Hash,
+		pallet: Option<String>,
+	) -> Result<Vec<IndexedEvent<Hash>>, Error>;
+
+	/// Get the latest transfers from or to `account`, newest first.
+	///
+	/// At most `limit` transfers are returned, which defaults to and is capped at
+	/// [`MAX_TRANSFERS`].
+	#[method(name = "indexer_transfers")]
+	async fn transfers(
+		&self,
+		account: Bytes,
+		limit: Option<u32>,
+	) -> Result<Vec<IndexedTransfer<Hash>>, Error>;
+}
@@ -31,8 +31,10 @@ pub mod author;
 pub mod chain;
 pub mod child_state;
 pub mod dev;
+pub mod indexer;
 pub mod mixnet;
 pub mod offchain;
 pub mod state;
 pub mod statement;
 pub mod system;
+pub mod txpool;
@@ -247,6 +247,15 @@ pub trait StateApi<Hash> {
 	/// 	http://localhost:9933/
 	/// ```
 	///
+	/// - Get a timeline of spans and storage events that can be opened in a trace viewer
+	/// ```text
+	/// curl \
+	/// 	-H "Content-Type: application/json" \
+	/// 	-d '{"id":1, "jsonrpc":"2.0", "method": "state_traceBlock", \
+	/// 		"params": ["0xb246acf1adea1f801ce15c77a5fa7d8f2eb8fed466978bcee172cc02cf64e264", "pallet,frame,state,sp_io", "", "", "chrome"]}' \
+	/// 	http://localhost:9933/ | jq .result.chromeTrace > trace.json
+	/// ```
+	///
 	/// ### Params
 	///
 	/// - `block` (param index 0): Hash of the block to trace.
@@ -272,6 +281,11 @@ pub trait StateApi<Hash> {
 	/// If an empty string is specified no events will be filtered out. If anything other than
 	/// an empty string is specified, events will be filtered by method (so non-method events will
 	/// **not** show up).
+	/// - `format` (param index 4, optional): Either `"spans"` (default) for the flat lists of
+	/// spans and events, or `"chrome"` for a timeline in the Chrome trace event format. The
+	/// timeline nests the host function (`sp_io`), extrinsic, and dispatch spans, marks the storage
+	/// events as instants, and has a `proof_size` counter. It can be opened in
+	/// <https://ui.perfetto.dev> or `chrome://tracing`.
 	///
 	/// Additionally you would want to track the extrinsic index, which is under the
 	/// `:extrinsic_index` key. The key for this would be the aforementioned string as bytes
@@ -299,5 +313,6 @@ pub trait StateApi<Hash> {
 		targets: Option<String>,
 		storage_keys: Option<String>,
 		methods: Option<String>,
+		format: Option<sp_rpc::tracing::TraceFormat>,
 	) -> Result<sp_rpc::tracing::TraceBlockResponse, Error>;
 }
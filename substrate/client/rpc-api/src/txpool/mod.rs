@@ -0,0 +1,165 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate transaction pool introspection API.
+//!
+//! Exposes the structure of the transaction pool: which transactions are ready or waiting in the
+//! future queue, the tags that keep them there, and why transactions were recently dropped.
+
+pub mod error;
+
+use error::Error;
+use jsonrpsee::proc_macros::rpc;
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+use std::collections::BTreeMap;
+
+/// The queue of the pool a transaction is in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PoolQueue {
+	/// The transaction is ready to be included in a block.
+	Ready,
+	/// The transaction waits for the tags it requires to be provided by other transactions.
+	Future,
+}
+
+/// The call of a transaction, decoded with the metadata of the runtime.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedCall {
+	/// The name of the pallet of the call.
+	pub pallet: String,
+	/// The name of the call.
+	pub call: String,
+	/// The decoded arguments of the call.
+	pub args: String,
+}
+
+/// A transaction in the pool.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolTransaction<Hash, BlockHash> {
+	/// The hash of the transaction.
+	pub hash: Hash,
+	/// The queue the transaction is in.
+	pub queue: PoolQueue,
+	/// The source of the transaction (`inBlock`, `local` or `external`), if known.
+	pub source: Option<String>,
+	/// The priority of the transaction.
+	pub priority: u64,
+	/// The number of the block up to which the transaction is valid.
+	pub longevity: u64,
+	/// The tags the transaction requires.
+	pub requires: Vec<Bytes>,
+	/// The tags the transaction provides.
+	pub provides: Vec<Bytes>,
+	/// The subset of [`Self::requires`] that is not provided by any transaction in the pool.
+	///
+	/// These tags keep a transaction in the future queue until provided by an included or a new
+	/// transaction.
+	pub missing: Vec<Bytes>,
+	/// Whether the transaction is propagated to other peers.
+	pub propagate: bool,
+	/// The hashes of the blocks whose views contain the transaction.
+	///
+	/// `None` if the pool does not maintain views per block.
+	pub views: Option<Vec<BlockHash>>,
+	/// The decoded call, if the transaction could be decoded with the runtime metadata.
+	pub call: Option<DecodedCall>,
+	/// The SCALE encoded transaction.
+	pub encoded: Bytes,
+}
+
+/// The transactions of a single sender.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderTransactions<Hash, BlockHash> {
+	/// The SCALE encoded address of the sender.
+	///
+	/// `None` for unsigned transactions and transactions that could not be decoded.
+	pub sender: Option<Bytes>,
+	/// The transactions of the sender, in the order of the pool.
+	pub transactions: Vec<PoolTransaction<Hash, BlockHash>>,
+}
+
+/// The content of the pool, grouped by sender.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolContent<Hash, BlockHash> {
+	/// The transactions of the ready queue.
+	pub ready: Vec<SenderTransactions<Hash, BlockHash>>,
+	/// The transactions of the future queue.
+	pub future: Vec<SenderTransactions<Hash, BlockHash>>,
+}
+
+/// A short summary of the content of the pool, one line per transaction, grouped by sender.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolSummary {
+	/// The ready transactions, keyed by the hex encoded sender, or `unsigned`.
+	pub ready: BTreeMap<String, Vec<String>>,
+	/// The future transactions, keyed by the hex encoded sender, or `unsigned`.
+	pub future: BTreeMap<String, Vec<String>>,
+}
+
+/// The reason of a transaction being dropped from the pool.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DropReason<Hash> {
+	/// The transaction was replaced by the given transaction.
+	Usurped(Hash),
+	/// The transaction was dropped to enforce the limits of the pool.
+	LimitsEnforced,
+	/// The transaction was invalid.
+	Invalid,
+}
+
+/// A transaction that was recently dropped from the pool.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroppedTransaction<Hash> {
+	/// The hash of the transaction.
+	pub hash: Hash,
+	/// Why the transaction was dropped.
+	pub reason: DropReason<Hash>,
+	/// Seconds since the UNIX epoch at which the transaction was dropped.
+	pub dropped_at: u64,
+}
+
+/// Substrate transaction pool introspection RPC API.
+#[rpc(client, server)]
+pub trait TxPoolApi<Hash, BlockHash> {
+	/// Get all transactions of the pool, grouped by queue and sender.
+	#[method(name = "txpool_content", with_extensions)]
+	fn content(&self) -> Result<PoolContent<Hash, BlockHash>, Error>;
+
+	/// Get a one line summary of every transaction of the pool, grouped by queue and sender.
+	#[method(name = "txpool_inspect", with_extensions)]
+	fn inspect(&self) -> Result<PoolSummary, Error>;
+
+	/// Get a single transaction of the pool by its hash.
+	#[method(name = "txpool_transaction", with_extensions)]
+	fn transaction(&self, hash: Hash) -> Result<Option<PoolTransaction<Hash, BlockHash>>, Error>;
+
+	/// Get the transactions that were most recently dropped from the pool, oldest first.
+	///
+	/// Only tracked by pools maintaining views per block, returns an empty list otherwise.
+	#[method(name = "txpool_recentlyDropped", with_extensions)]
+	fn recently_dropped(&self) -> Result<Vec<DroppedTransaction<Hash>>, Error>;
+}
@@ -146,6 +146,7 @@ where
 		targets: Option<String>,
 		storage_keys: Option<String>,
 		methods: Option<String>,
+		format: Option<sp_rpc::tracing::TraceFormat>,
 	) -> Result<sp_rpc::tracing::TraceBlockResponse, Error>;
 
 	/// New runtime version subscription
@@ -318,10 +319,11 @@ where
 		targets: Option<String>,
 		storage_keys: Option<String>,
 		methods: Option<String>,
+		format: Option<sp_rpc::tracing::TraceFormat>,
 	) -> Result<sp_rpc::tracing::TraceBlockResponse, Error> {
 		check_if_safe(ext)?;
 		self.backend
-			.trace_block(block, targets, storage_keys, methods)
+			.trace_block(block, targets, storage_keys, methods, format)
 			.map_err(Into::into)
 	}
 
@@ -472,6 +472,7 @@ where
 		targets: Option<String>,
 		storage_keys: Option<String>,
 		methods: Option<String>,
+		format: Option<sp_rpc::tracing::TraceFormat>,
 	) -> std::result::Result<sp_rpc::tracing::TraceBlockResponse, Error> {
 		sc_tracing::block::BlockExecutor::new(
 			self.client.clone(),
@@ -480,7 +481,7 @@ where
 			storage_keys,
 			methods,
 		)
-		.trace_block()
+		.trace_block_as(format.unwrap_or_default())
 		.map_err(|e| invalid_block::<Block>(block, None, e.to_string()))
 	}
 }
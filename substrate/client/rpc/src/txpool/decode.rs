@@ -0,0 +1,101 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Decoding of transactions with the metadata of the runtime.
+
+use super::DecodedCall;
+use log::debug;
+use sp_api::{CallApiAt, Metadata as _, ProvideRuntimeApi};
+use sp_runtime::traits::Block as BlockT;
+use subxt_core::{blocks::Extrinsics, config::SubstrateConfig, Metadata};
+
+/// The latest metadata version that is requested from the runtime.
+const METADATA_VERSION: u32 = 15;
+
+const LOG_TARGET: &str = "rpc::txpool";
+
+/// A decoded transaction.
+pub(super) struct Decoded {
+	/// The SCALE encoded address of the signer, if the transaction is signed.
+	pub sender: Option<Vec<u8>>,
+	/// The decoded call.
+	pub call: DecodedCall,
+}
+
+/// Decodes transactions, caching the metadata of the latest runtime.
+#[derive(Default)]
+pub(super) struct Decoder {
+	metadata: Option<(u32, Metadata)>,
+}
+
+impl Decoder {
+	/// Use the metadata of the runtime of block `at`.
+	///
+	/// Transactions are not decoded if the metadata is not available.
+	pub(super) fn update<B, C>(&mut self, client: &C, at: B::Hash)
+	where
+		B: BlockT,
+		C: ProvideRuntimeApi<B> + CallApiAt<B>,
+		C::Api: sp_api::Metadata<B>,
+	{
+		let spec_version = match client.runtime_version_at(at) {
+			Ok(version) => version.spec_version,
+			Err(e) => {
+				debug!(target: LOG_TARGET, "Failed to fetch the runtime version at {at}: {e}");
+				self.metadata = None;
+				return
+			},
+		};
+		if self.metadata.as_ref().map_or(false, |(version, _)| *version == spec_version) {
+			return
+		}
+
+		let api = client.runtime_api();
+		// runtimes that do not support `metadata_at_version` only provide the V14 metadata.
+		let opaque = match api.metadata_at_version(at, METADATA_VERSION) {
+			Ok(Some(opaque)) => Ok(opaque),
+			_ => api.metadata(at),
+		};
+		self.metadata = match opaque.map_err(|e| e.to_string()).and_then(|opaque| {
+			subxt_core::metadata::decode_from(&opaque[..]).map_err(|e| e.to_string())
+		}) {
+			Ok(metadata) => Some((spec_version, metadata)),
+			Err(e) => {
+				debug!(target: LOG_TARGET, "Failed to fetch the metadata at {at}: {e}");
+				None
+			},
+		};
+	}
+
+	/// Decode the SCALE encoded transaction, if possible.
+	pub(super) fn decode(&self, encoded: &[u8]) -> Option<Decoded> {
+		let (_, metadata) = self.metadata.as_ref()?;
+		let extrinsics =
+			Extrinsics::<SubstrateConfig>::decode_from(vec![encoded.to_vec()], metadata.clone())
+				.ok()?;
+		let extrinsic = extrinsics.iter().next()?;
+		Some(Decoded {
+			sender: extrinsic.address_bytes().map(|address| address.to_vec()),
+			call: DecodedCall {
+				pallet: extrinsic.pallet_name().ok()?.into(),
+				call: extrinsic.variant_name().ok()?.into(),
+				args: extrinsic.field_values().ok()?.to_string(),
+			},
+		})
+	}
+}
@@ -0,0 +1,245 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate transaction pool introspection API.
+
+mod decode;
+#[cfg(test)]
+mod tests;
+
+use self::{decode::Decoder, error::Result};
+use codec::Encode;
+use jsonrpsee::Extensions;
+use parking_lot::Mutex;
+use sc_rpc_api::check_if_safe;
+use sc_transaction_pool_api::{
+	BlockHash, DropReason as PoolDropReason, InPoolTransaction, TransactionPool, TransactionSource,
+	TransactionTag, TxHash,
+};
+use sp_api::{CallApiAt, Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{bytes::to_hex, Bytes};
+use std::{
+	collections::{hash_map::Entry, HashMap, HashSet},
+	sync::Arc,
+};
+
+/// Re-export the API for backward compatibility.
+pub use sc_rpc_api::txpool::*;
+
+/// The key of unsigned transactions in [`PoolSummary`].
+const UNSIGNED: &str = "unsigned";
+
+/// Transaction pool introspection API.
+pub struct TxPool<P, Client> {
+	/// Substrate client.
+	client: Arc<Client>,
+	/// Transactions pool.
+	pool: Arc<P>,
+	/// Decodes transactions with the metadata of the runtime of the best block.
+	decoder: Mutex<Decoder>,
+}
+
+impl<P, Client> TxPool<P, Client> {
+	/// Create new instance of the transaction pool introspection API.
+	pub fn new(client: Arc<Client>, pool: Arc<P>) -> Self {
+		TxPool { client, pool, decoder: Default::default() }
+	}
+}
+
+impl<P, Client> TxPool<P, Client>
+where
+	P: TransactionPool + 'static,
+	Client: HeaderBackend<P::Block>
+		+ ProvideRuntimeApi<P::Block>
+		+ CallApiAt<P::Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: Metadata<P::Block>,
+{
+	/// All transactions of the pool, grouped by queue and sender.
+	fn collect(&self, filter: impl Fn(&TxHash<P>) -> bool) -> PoolContent<TxHash<P>, BlockHash<P>> {
+		let ready = self.pool.ready().collect::<Vec<_>>();
+		let future = self.pool.futures();
+
+		// tags provided by ready transactions, future transactions wait for the other ones.
+		let provided = ready
+			.iter()
+			.flat_map(|tx| tx.provides().iter().cloned())
+			.collect::<HashSet<TransactionTag>>();
+
+		let best_hash = self.client.info().best_hash;
+		let mut decoder = self.decoder.lock();
+		decoder.update::<P::Block, _>(&*self.client, best_hash);
+
+		let to_rpc = |tx: &P::InPoolTransaction, queue| {
+			let encoded = tx.data().encode();
+			let decoded = decoder.decode(&encoded);
+			let missing = match queue {
+				PoolQueue::Ready => Vec::new(),
+				PoolQueue::Future => tx
+					.requires()
+					.iter()
+					.filter(|tag| !provided.contains(*tag))
+					.map(|tag| tag.clone().into())
+					.collect(),
+			};
+			let transaction = PoolTransaction {
+				hash: tx.hash().clone(),
+				queue,
+				source: tx.source().map(|source| source_name(source).into()),
+				priority: *tx.priority(),
+				longevity: *tx.longevity(),
+				requires: tx.requires().iter().map(|tag| tag.clone().into()).collect(),
+				provides: tx.provides().iter().map(|tag| tag.clone().into()).collect(),
+				missing,
+				propagate: tx.is_propagable(),
+				views: self.pool.transaction_views(tx.hash()),
+				call: decoded.as_ref().map(|decoded| decoded.call.clone()),
+				encoded: encoded.into(),
+			};
+			(decoded.and_then(|decoded| decoded.sender), transaction)
+		};
+
+		let ready = ready
+			.iter()
+			.filter(|tx| filter(tx.hash()))
+			.map(|tx| to_rpc(&**tx, PoolQueue::Ready))
+			.collect::<Vec<_>>();
+		let future = future
+			.iter()
+			.filter(|tx| filter(tx.hash()))
+			.map(|tx| to_rpc(tx, PoolQueue::Future))
+			.collect::<Vec<_>>();
+
+		PoolContent { ready: group_by_sender(ready), future: group_by_sender(future) }
+	}
+}
+
+impl<P, Client> TxPoolApiServer<TxHash<P>, BlockHash<P>> for TxPool<P, Client>
+where
+	P: TransactionPool + Sync + Send + 'static,
+	Client: HeaderBackend<P::Block>
+		+ ProvideRuntimeApi<P::Block>
+		+ CallApiAt<P::Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: Metadata<P::Block>,
+{
+	fn content(&self, ext: &Extensions) -> Result<PoolContent<TxHash<P>, BlockHash<P>>> {
+		check_if_safe(ext)?;
+		Ok(self.collect(|_| true))
+	}
+
+	fn inspect(&self, ext: &Extensions) -> Result<PoolSummary> {
+		check_if_safe(ext)?;
+		let content = self.collect(|_| true);
+		Ok(PoolSummary { ready: summarize(content.ready), future: summarize(content.future) })
+	}
+
+	fn transaction(
+		&self,
+		ext: &Extensions,
+		hash: TxHash<P>,
+	) -> Result<Option<PoolTransaction<TxHash<P>, BlockHash<P>>>> {
+		check_if_safe(ext)?;
+		let content = self.collect(|tx_hash| *tx_hash == hash);
+		Ok(content
+			.ready
+			.into_iter()
+			.chain(content.future)
+			.flat_map(|sender| sender.transactions)
+			.next())
+	}
+
+	fn recently_dropped(&self, ext: &Extensions) -> Result<Vec<DroppedTransaction<TxHash<P>>>> {
+		check_if_safe(ext)?;
+		Ok(self
+			.pool
+			.recently_dropped()
+			.into_iter()
+			.map(|dropped| DroppedTransaction {
+				hash: dropped.hash,
+				reason: match dropped.reason {
+					PoolDropReason::Usurped(by) => DropReason::Usurped(by),
+					PoolDropReason::LimitsEnforced => DropReason::LimitsEnforced,
+					PoolDropReason::Invalid => DropReason::Invalid,
+				},
+				dropped_at: dropped.dropped_at,
+			})
+			.collect())
+	}
+}
+
+/// The name of `source` in the RPC.
+fn source_name(source: TransactionSource) -> &'static str {
+	match source {
+		TransactionSource::InBlock => "inBlock",
+		TransactionSource::Local => "local",
+		TransactionSource::External => "external",
+	}
+}
+
+/// Group the transactions by their sender, keeping the order of the pool.
+fn group_by_sender<Hash, BlockHash>(
+	transactions: Vec<(Option<Vec<u8>>, PoolTransaction<Hash, BlockHash>)>,
+) -> Vec<SenderTransactions<Hash, BlockHash>> {
+	let mut groups = Vec::<SenderTransactions<Hash, BlockHash>>::new();
+	let mut indices = HashMap::new();
+	for (sender, transaction) in transactions {
+		match indices.entry(sender.clone()) {
+			Entry::Occupied(entry) => groups[*entry.get()].transactions.push(transaction),
+			Entry::Vacant(entry) => {
+				entry.insert(groups.len());
+				groups.push(SenderTransactions {
+					sender: sender.map(Bytes),
+					transactions: vec![transaction],
+				});
+			},
+		}
+	}
+	groups
+}
+
+/// One line per transaction, keyed by the hex encoded sender.
+fn summarize<Hash: std::fmt::Debug, BlockHash>(
+	groups: Vec<SenderTransactions<Hash, BlockHash>>,
+) -> std::collections::BTreeMap<String, Vec<String>> {
+	groups
+		.into_iter()
+		.map(|group| {
+			let sender = group
+				.sender
+				.map_or_else(|| UNSIGNED.to_string(), |sender| to_hex(&sender, false));
+			let lines = group
+				.transactions
+				.into_iter()
+				.map(|tx| {
+					let call = tx.call.map_or_else(
+						|| "<unknown call>".to_string(),
+						|c| format!("{}.{}", c.pallet, c.call),
+					);
+					format!("{:?}: {call}, priority {}", tx.hash, tx.priority)
+				})
+				.collect();
+			(sender, lines)
+		})
+		.collect()
+}
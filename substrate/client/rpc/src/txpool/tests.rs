@@ -0,0 +1,164 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::testing::{allow_unsafe, deny_unsafe};
+use assert_matches::assert_matches;
+use sc_transaction_pool::{BasicPool, ForkAwareTxPool, FullChainApi};
+use sc_transaction_pool_api::{ChainEvent, MaintainedTransactionPool};
+use sp_core::H256;
+use substrate_test_runtime_client::{
+	runtime::{Block, Extrinsic, ExtrinsicBuilder, Transfer},
+	Backend, Client, DefaultTestClientBuilderExt, Sr25519Keyring, TestClientBuilderExt,
+};
+
+type FullTransactionPool = BasicPool<FullChainApi<Client<Backend>, Block>, Block>;
+type ForkAwareTransactionPool = ForkAwareTxPool<FullChainApi<Client<Backend>, Block>, Block>;
+
+fn uxt(sender: Sr25519Keyring, nonce: u64) -> Extrinsic {
+	let tx = Transfer {
+		amount: Default::default(),
+		nonce,
+		from: sender.into(),
+		to: Sr25519Keyring::Bob.into(),
+	};
+	ExtrinsicBuilder::new_transfer(tx).build()
+}
+
+/// A pool with a ready transaction of Alice, and a future one that misses its predecessor.
+async fn setup() -> (TxPool<FullTransactionPool, Client<Backend>>, H256, H256) {
+	let client = Arc::new(substrate_test_runtime_client::TestClientBuilder::new().build());
+	let pool = Arc::from(BasicPool::new_full(
+		Default::default(),
+		true.into(),
+		None,
+		sp_core::testing::TaskExecutor::new(),
+		client.clone(),
+	));
+
+	let best_hash = client.info().best_hash;
+	let ready = pool
+		.submit_one(best_hash, TransactionSource::External, uxt(Sr25519Keyring::Alice, 0))
+		.await
+		.unwrap();
+	let future = pool
+		.submit_one(best_hash, TransactionSource::Local, uxt(Sr25519Keyring::Alice, 2))
+		.await
+		.unwrap();
+	assert_eq!(pool.status().ready, 1);
+	assert_eq!(pool.status().future, 1);
+
+	(TxPool::new(client, pool), ready, future)
+}
+
+#[tokio::test]
+async fn content_should_list_ready_and_future_transactions() {
+	let (api, ready, future) = setup().await;
+
+	let content = api.content(&allow_unsafe()).unwrap();
+	let ready_txs = content.ready.iter().flat_map(|s| &s.transactions).collect::<Vec<_>>();
+	let future_txs = content.future.iter().flat_map(|s| &s.transactions).collect::<Vec<_>>();
+
+	assert_eq!(ready_txs.len(), 1);
+	assert_eq!(ready_txs[0].hash, ready);
+	assert_eq!(ready_txs[0].queue, PoolQueue::Ready);
+	assert_eq!(ready_txs[0].source.as_deref(), Some("external"));
+	assert!(ready_txs[0].missing.is_empty());
+	assert!(!ready_txs[0].provides.is_empty());
+
+	assert_eq!(future_txs.len(), 1);
+	assert_eq!(future_txs[0].hash, future);
+	assert_eq!(future_txs[0].queue, PoolQueue::Future);
+	assert_eq!(future_txs[0].source.as_deref(), Some("local"));
+	// the transaction with nonce 1 is neither in the pool nor on chain.
+	assert_eq!(future_txs[0].missing.len(), 1);
+	assert!(future_txs[0].requires.contains(&future_txs[0].missing[0]));
+
+	// the single-state pool does not maintain views.
+	assert_eq!(ready_txs[0].views, None);
+}
+
+#[tokio::test]
+async fn content_should_list_views_of_fork_aware_pool() {
+	let client = Arc::new(substrate_test_runtime_client::TestClientBuilder::new().build());
+	let pool = Arc::new(ForkAwareTransactionPool::new_full(
+		Default::default(),
+		true.into(),
+		None,
+		sp_core::testing::TaskExecutor::new(),
+		client.clone(),
+	));
+
+	// the pool only creates a view once notified of a new best block.
+	let best_hash = client.info().best_hash;
+	pool.maintain(ChainEvent::NewBestBlock { hash: best_hash, tree_route: None })
+		.await;
+	let ready = pool
+		.submit_one(best_hash, TransactionSource::External, uxt(Sr25519Keyring::Alice, 0))
+		.await
+		.unwrap();
+
+	let api = TxPool::new(client, pool);
+	let content = api.content(&allow_unsafe()).unwrap();
+	let ready_txs = content.ready.iter().flat_map(|s| &s.transactions).collect::<Vec<_>>();
+
+	assert_matches!(&ready_txs[..], [tx] if tx.hash == ready);
+	assert_eq!(ready_txs[0].views, Some(vec![best_hash]));
+	assert_matches!(api.transaction(&allow_unsafe(), ready), Ok(Some(tx)) if tx.views == Some(vec![best_hash]));
+}
+
+#[tokio::test]
+async fn inspect_should_summarize_transactions() {
+	let (api, ready, future) = setup().await;
+
+	let summary = api.inspect(&allow_unsafe()).unwrap();
+	let lines = |groups: &std::collections::BTreeMap<String, Vec<String>>| {
+		groups.values().flatten().cloned().collect::<Vec<_>>()
+	};
+
+	assert_matches!(&lines(&summary.ready)[..], [line] if line.starts_with(&format!("{ready:?}")));
+	assert_matches!(&lines(&summary.future)[..], [line] if line.starts_with(&format!("{future:?}")));
+}
+
+#[tokio::test]
+async fn transaction_should_be_found_by_hash() {
+	let (api, ready, future) = setup().await;
+	let ext = allow_unsafe();
+
+	assert_matches!(api.transaction(&ext, ready), Ok(Some(tx)) if tx.queue == PoolQueue::Ready);
+	assert_matches!(api.transaction(&ext, future), Ok(Some(tx)) if tx.queue == PoolQueue::Future);
+	assert_matches!(api.transaction(&ext, H256::repeat_byte(0x42)), Ok(None));
+}
+
+#[tokio::test]
+async fn recently_dropped_should_be_empty_without_views() {
+	let (api, _, _) = setup().await;
+
+	assert_matches!(api.recently_dropped(&allow_unsafe()), Ok(dropped) if dropped.is_empty());
+}
+
+#[tokio::test]
+async fn txpool_calls_considered_unsafe() {
+	let (api, ready, _) = setup().await;
+	let ext = deny_unsafe();
+
+	assert_matches!(api.content(&ext), Err(error::Error::UnsafeRpcCalled(_)));
+	assert_matches!(api.inspect(&ext), Err(error::Error::UnsafeRpcCalled(_)));
+	assert_matches!(api.transaction(&ext, ready), Err(error::Error::UnsafeRpcCalled(_)));
+	assert_matches!(api.recently_dropped(&ext), Err(error::Error::UnsafeRpcCalled(_)));
+}
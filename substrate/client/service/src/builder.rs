@@ -52,7 +52,9 @@ use sc_network::{
 	NetworkBackend, NetworkStateInfo,
 };
 use sc_network_common::role::{Role, Roles};
-use sc_network_light::light_client_requests::handler::LightClientRequestHandler;
+use sc_network_light::light_client_requests::{
+	handler::LightClientRequestHandler, sender::LightClientRequestSender,
+};
 use sc_network_sync::{
 	block_relay_protocol::{BlockDownloader, BlockRelayParams},
 	block_request_handler::BlockRequestHandler,
@@ -63,7 +65,10 @@ use sc_network_sync::{
 		polkadot::{PolkadotSyncingStrategy, PolkadotSyncingStrategyConfig},
 		SyncingStrategy,
 	},
-	warp_request_handler::RequestHandler as WarpSyncRequestHandler,
+	warp_request_handler::{
+		BeefyRequestHandler as BeefyWarpSyncRequestHandler,
+		RequestHandler as WarpSyncRequestHandler,
+	},
 	SyncingService, WarpSyncConfig,
 };
 use sc_rpc::{
@@ -72,6 +77,7 @@ use sc_rpc::{
 	offchain::OffchainApiServer,
 	state::{ChildStateApiServer, StateApiServer},
 	system::SystemApiServer,
+	txpool::TxPoolApiServer,
 	DenyUnsafe, SubscriptionTaskExecutor,
 };
 use sc_rpc_spec_v2::{
@@ -93,6 +99,7 @@ use sp_keystore::KeystorePtr;
 use sp_runtime::traits::{Block as BlockT, BlockIdTo, NumberFor, Zero};
 use sp_storage::{ChildInfo, ChildType, PrefixedStorageKey};
 use std::{
+	path::Path,
 	str::FromStr,
 	sync::Arc,
 	time::{Duration, SystemTime},
@@ -112,31 +119,59 @@ type TFullParts<TBl, TRtApi, TExec> =
 	(TFullClient<TBl, TRtApi, TExec>, Arc<TFullBackend<TBl>>, KeystoreContainer, TaskManager);
 
 /// Construct a local keystore shareable container
-pub struct KeystoreContainer(Arc<LocalKeystore>);
+pub struct KeystoreContainer {
+	local: Arc<LocalKeystore>,
+	keystore: KeystorePtr,
+}
 
 impl KeystoreContainer {
 	/// Construct KeystoreContainer
 	pub fn new(config: &KeystoreConfig) -> Result<Self, Error> {
-		let keystore = Arc::new(match config {
-			KeystoreConfig::Path { path, password } =>
+		let local = Arc::new(match config {
+			KeystoreConfig::Path { path, password } |
+			KeystoreConfig::Remote { path, password, .. } =>
 				LocalKeystore::open(path.clone(), password.clone())?,
 			KeystoreConfig::InMemory => LocalKeystore::in_memory(),
 		});
 
-		Ok(Self(keystore))
+		let keystore = match config {
+			KeystoreConfig::Remote { socket, secret_file, .. } =>
+				remote_keystore(socket, secret_file)?,
+			_ => local.clone(),
+		};
+
+		Ok(Self { local, keystore })
 	}
 
 	/// Returns a shared reference to a dynamic `Keystore` trait implementation.
+	///
+	/// This is the remote keystore if one is configured.
 	pub fn keystore(&self) -> KeystorePtr {
-		self.0.clone()
+		self.keystore.clone()
 	}
 
 	/// Returns a shared reference to the local keystore .
+	///
+	/// Components using it sign with local keys, even if a remote keystore is configured. Polkadot
+	/// validators, whose parachain subsystems need key pairs, therefore refuse to start with one.
 	pub fn local_keystore(&self) -> Arc<LocalKeystore> {
-		self.0.clone()
+		self.local.clone()
 	}
 }
 
+#[cfg(unix)]
+fn remote_keystore(socket: &Path, secret_file: &Path) -> Result<KeystorePtr, Error> {
+	let secret = sc_keystore::remote::read_secret_file(secret_file)?;
+	let keystore = sc_keystore::RemoteKeystore::open(socket, secret)?;
+	info!("🔑 Using the remote signer at {}", socket.display());
+	Ok(Arc::new(keystore))
+}
+
+#[cfg(not(unix))]
+fn remote_keystore(_: &Path, _: &Path) -> Result<KeystorePtr, Error> {
+	Err(Error::Other("Remote signers are only supported on Unix".into()))
+}
+
 /// Creates a new full client for the given config.
 pub fn new_full_client<TBl, TRtApi, TExec>(
 	config: &Configuration,
@@ -859,6 +894,8 @@ where
 	)
 	.into_rpc();
 
+	let txpool = sc_rpc::txpool::TxPool::new(client.clone(), transaction_pool.clone()).into_rpc();
+
 	let author = sc_rpc::author::Author::new(
 		client.clone(),
 		transaction_pool,
@@ -886,6 +923,7 @@ where
 	// Part of the old RPC spec.
 	rpc_api.merge(chain).map_err(|e| Error::Application(e.into()))?;
 	rpc_api.merge(author).map_err(|e| Error::Application(e.into()))?;
+	rpc_api.merge(txpool).map_err(|e| Error::Application(e.into()))?;
 	rpc_api.merge(system).map_err(|e| Error::Application(e.into()))?;
 	rpc_api.merge(state).map_err(|e| Error::Application(e.into()))?;
 	rpc_api.merge(child_state).map_err(|e| Error::Application(e.into()))?;
@@ -967,6 +1005,11 @@ where
 		metrics,
 	} = params;
 
+	// Authoring and validating need the state of the best block, which light clients don't have.
+	if config.network.sync_mode.is_light() && config.role.is_authority() {
+		return Err("Light sync doesn't work for authorities".into())
+	}
+
 	let block_announce_validator = if let Some(f) = block_announce_validator_builder {
 		f(client.clone())
 	} else {
@@ -1415,12 +1458,21 @@ where
 		return Err("Warp sync enabled, but no warp sync provider configured.".into())
 	}
 
+	if net_config.network_config.sync_mode.is_checkpoint() &&
+		net_config.network_config.checkpoint_snapshot.is_none()
+	{
+		return Err("Checkpoint sync enabled, but no snapshot configured.".into())
+	}
+
 	if client.requires_full_sync() {
 		match net_config.network_config.sync_mode {
 			SyncMode::LightState { .. } =>
 				return Err("Fast sync doesn't work for archive nodes".into()),
 			SyncMode::Warp => return Err("Warp sync doesn't work for archive nodes".into()),
-			SyncMode::Full => {},
+			SyncMode::Light => return Err("Light sync doesn't work for archive nodes".into()),
+			// Archive nodes keep the state of all blocks from the checkpoint on, the history
+			// before it is backfilled without state by the gap sync.
+			SyncMode::Full | SyncMode::Checkpoint => {},
 		}
 	}
 
@@ -1439,26 +1491,45 @@ where
 	};
 	net_config.add_request_response_protocol(state_request_protocol_config);
 
-	let (warp_sync_protocol_config, warp_sync_protocol_name) = match warp_sync_config.as_ref() {
-		Some(WarpSyncConfig::WithProvider(warp_with_provider)) => {
+	let (warp_sync_provider, beefy_warp_sync_provider) = match warp_sync_config.as_ref() {
+		Some(WarpSyncConfig::WithProvider(warp_with_provider)) =>
+			(Some(warp_with_provider.clone()), None),
+		Some(WarpSyncConfig::WithBeefyProvider { beefy, grandpa }) =>
+			(Some(grandpa.clone()), Some(beefy.clone())),
+		_ => (None, None),
+	};
+
+	let (warp_sync_protocol_config, warp_sync_protocol_name) = match warp_sync_provider {
+		Some(warp_with_provider) => {
 			// Allow both outgoing and incoming requests.
 			let (handler, protocol_config) = WarpSyncRequestHandler::new::<_, Net>(
 				protocol_id,
 				genesis_hash,
 				fork_id,
-				warp_with_provider.clone(),
+				warp_with_provider,
 			);
 			let config_name = protocol_config.protocol_name().clone();
 
 			spawn_handle.spawn("warp-sync-request-handler", Some("networking"), handler.run());
 			(Some(protocol_config), Some(config_name))
 		},
-		_ => (None, None),
+		None => (None, None),
 	};
 	if let Some(config) = warp_sync_protocol_config {
 		net_config.add_request_response_protocol(config);
 	}
 
+	let beefy_warp_sync_protocol_name = beefy_warp_sync_provider.map(|beefy_with_provider| {
+		// Allow both outgoing and incoming requests.
+		let (handler, protocol_config) =
+			BeefyWarpSyncRequestHandler::new::<_, Net>(genesis_hash, fork_id, beefy_with_provider);
+		let config_name = protocol_config.protocol_name().clone();
+
+		spawn_handle.spawn("beefy-warp-sync-request-handler", Some("networking"), handler.run());
+		net_config.add_request_response_protocol(protocol_config);
+		config_name
+	});
+
 	let syncing_config = PolkadotSyncingStrategyConfig {
 		mode: net_config.network_config.sync_mode,
 		max_parallel_downloads: net_config.network_config.max_parallel_downloads,
@@ -1466,7 +1537,9 @@ where
 		min_peers_to_start_warp_sync: net_config.network_config.min_peers_to_start_warp_sync,
 		metrics_registry: metrics_registry.cloned(),
 		state_request_protocol_name,
+		beefy_warp_sync_protocol_name,
 		block_downloader,
+		checkpoint_snapshot: net_config.network_config.checkpoint_snapshot.clone(),
 	};
 	Ok(Box::new(PolkadotSyncingStrategy::new(
 		syncing_config,
@@ -1475,3 +1548,44 @@ where
 		warp_sync_protocol_name,
 	)?))
 }
+
+/// Turn `client` into a light client, whose node syncs in [`SyncMode::Light`].
+///
+/// A light client only imports headers. Its runtime calls and storage reads at blocks whose state
+/// is missing are answered by full peers, and checked against the state roots of the headers.
+/// This covers the runtime APIs used by the import queue and the transaction pool, as well as the
+/// state RPC. `executor` checks the execution proofs of the full peers.
+pub fn build_light_client<TBl, TRtApi, TExec>(
+	client: Arc<TFullClient<TBl, TRtApi, TExec>>,
+	executor: TExec,
+	network: Arc<dyn sc_network::service::traits::NetworkService>,
+	sync_service: Arc<SyncingService<TBl>>,
+	fork_id: Option<&str>,
+	spawn_handle: &SpawnTaskHandle,
+) -> Result<(), Error>
+where
+	TBl: BlockT,
+	TRtApi: Send + Sync + 'static,
+	TExec: CodeExecutor + RuntimeVersionOf + Clone + Send + Sync + 'static,
+{
+	let genesis_hash = client.info().genesis_hash;
+	let sender = LightClientRequestSender::new(
+		client.clone(),
+		executor,
+		Arc::new(network),
+		genesis_hash,
+		fork_id,
+	);
+
+	if !client.executor().set_remote_state_fetcher(Arc::new(sender.clone())) {
+		return Err("Light client already built".into())
+	}
+
+	spawn_handle.spawn(
+		"light-client-request-sender",
+		Some("networking"),
+		sender.track_peers(sync_service),
+	);
+
+	Ok(())
+}
@@ -0,0 +1,78 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::error::Error;
+use sc_client_api::{BlockBackend, HeaderBackend, StorageProvider};
+use sc_network_sync::snapshot::{SnapshotHeader, SnapshotWriter};
+use sp_core::storage::{well_known_keys, ChildInfo};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{io::Write, sync::Arc};
+
+/// Export a trusted state snapshot of the finalized block `hash` to `output`.
+///
+/// The snapshot can be imported with checkpoint sync.
+pub fn export_snapshot<B, BA, C>(
+	client: Arc<C>,
+	hash: B::Hash,
+	output: impl Write,
+) -> Result<(), Error>
+where
+	C: HeaderBackend<B> + BlockBackend<B> + StorageProvider<B, BA>,
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+{
+	let info = client.info();
+	let header = client
+		.header(hash)?
+		.ok_or_else(|| Error::Other(format!("Unknown block {hash}")))?;
+	// A block on a fork below the finalized number is not finalized either.
+	if *header.number() > info.finalized_number || client.hash(*header.number())? != Some(hash) {
+		return Err(Error::Other(format!("Block {hash} is not finalized")))
+	}
+	let header = SnapshotHeader::<B> {
+		genesis_hash: info.genesis_hash,
+		justifications: client.justifications(hash)?,
+		header,
+	};
+
+	let mut writer = SnapshotWriter::new(output, &header)?;
+	let mut child_roots = Vec::new();
+	for (key, value) in client.storage_pairs(hash, None, None)? {
+		// Child roots are recomputed when importing the child tries.
+		if key.0.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			child_roots.push(key.0);
+			continue
+		}
+		writer.key_value(key.0, value.0)?;
+	}
+
+	for prefixed_storage_key in child_roots {
+		let child_info = ChildInfo::new_default(
+			&prefixed_storage_key[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..],
+		);
+		writer.child_trie(prefixed_storage_key)?;
+		for child_key in client.child_storage_keys(hash, child_info.clone(), None, None)? {
+			if let Some(child_value) = client.child_storage(hash, &child_info, &child_key)? {
+				writer.key_value(child_key.0, child_value.0)?;
+			}
+		}
+	}
+	writer.finish()?;
+
+	Ok(())
+}
@@ -21,11 +21,13 @@
 mod check_block;
 mod export_blocks;
 mod export_raw_state;
+mod export_snapshot;
 mod import_blocks;
 mod revert_chain;
 
 pub use check_block::*;
 pub use export_blocks::*;
 pub use export_raw_state::*;
+pub use export_snapshot::*;
 pub use import_blocks::*;
 pub use revert_chain::*;
@@ -17,9 +17,11 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::{code_provider::CodeProvider, ClientConfig};
+use codec::Decode;
+use futures::{executor::ThreadPool, future::BoxFuture, FutureExt};
 use sc_client_api::{
-	backend, call_executor::CallExecutor, execution_extensions::ExecutionExtensions, HeaderBackend,
-	TrieCacheContext,
+	backend, call_executor::CallExecutor, execution_extensions::ExecutionExtensions,
+	light::RemoteStateFetcher, HeaderBackend, TrieCacheContext,
 };
 use sc_executor::{RuntimeVersion, RuntimeVersionOf};
 use sp_api::ProofRecorder;
@@ -30,15 +32,61 @@ use sp_runtime::{
 	traits::{Block as BlockT, HashingFor},
 };
 use sp_state_machine::{backend::AsTrieBackend, OverlayedChanges, StateMachine, StorageProof};
-use std::{cell::RefCell, sync::Arc};
+use std::{
+	cell::RefCell,
+	sync::{mpsc, Arc, OnceLock},
+	time::Duration,
+};
+
+/// How long to wait for the full peers to answer a request of a light client.
+const REMOTE_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Number of threads driving the requests of light clients.
+const REMOTE_REQUEST_THREADS: usize = 2;
+
+/// Wait for the answer to `request`, a request of a light client to its full peers.
+///
+/// The runtime calls and storage reads of the client are synchronous, so the request is driven
+/// to completion on a dedicated pool of threads rather than on the async runtime the caller may
+/// be running on, and fails with [`sp_blockchain::Error::RemoteFetchFailed`] if the peers don't
+/// answer within [`REMOTE_REQUEST_TIMEOUT`].
+pub(crate) fn wait_for_remote<T: Send + 'static>(
+	request: BoxFuture<'static, sp_blockchain::Result<T>>,
+) -> sp_blockchain::Result<T> {
+	static POOL: OnceLock<Option<ThreadPool>> = OnceLock::new();
+	let pool = POOL.get_or_init(|| {
+		ThreadPool::builder()
+			.name_prefix("light-client-request-")
+			.pool_size(REMOTE_REQUEST_THREADS)
+			.create()
+			.map_err(|e| log::error!("Failed to spawn the threads of light client requests: {e}"))
+			.ok()
+	});
+	let Some(pool) = pool else { return Err(sp_blockchain::Error::RemoteFetchFailed) };
+
+	let (tx, rx) = mpsc::sync_channel(1);
+	pool.spawn_ok(async move {
+		let timeout = futures_timer::Delay::new(REMOTE_REQUEST_TIMEOUT);
+		let result = futures::select! {
+			result = request.fuse() => result,
+			_ = timeout.fuse() => Err(sp_blockchain::Error::RemoteFetchFailed),
+		};
+		let _ = tx.send(result);
+	});
+	rx.recv().unwrap_or(Err(sp_blockchain::Error::RemoteFetchCancelled))
+}
 
 /// Call executor that executes methods locally, querying all required
 /// data from local backend.
+///
+/// On light clients, calls at blocks whose state is missing are executed by full peers instead,
+/// see [`LocalCallExecutor::set_remote_state_fetcher`].
 pub struct LocalCallExecutor<Block: BlockT, B, E> {
 	backend: Arc<B>,
 	executor: E,
 	code_provider: CodeProvider<Block, B, E>,
 	execution_extensions: Arc<ExecutionExtensions<Block>>,
+	remote: Arc<OnceLock<Arc<dyn RemoteStateFetcher<Block>>>>,
 }
 
 impl<Block: BlockT, B, E> LocalCallExecutor<Block, B, E>
@@ -60,8 +108,36 @@ where
 			executor,
 			code_provider,
 			execution_extensions: Arc::new(execution_extensions),
+			remote: Default::default(),
 		})
 	}
+
+	/// Execute the calls at blocks whose state is missing with `fetcher`, turning this into the
+	/// call executor of a light client.
+	///
+	/// The fetcher is shared by all clones of this executor. It can only be set once, usually
+	/// after the network it sends its requests over is built. Returns `false` if it was already
+	/// set.
+	///
+	/// Calls that record a storage proof, i.e. [`CallExecutor::contextual_call`] with a recorder,
+	/// are never executed by full peers, as their proof can't be recorded locally. They fail
+	/// with the error of the missing state instead.
+	pub fn set_remote_state_fetcher(&self, fetcher: Arc<dyn RemoteStateFetcher<Block>>) -> bool {
+		self.remote.set(fetcher).is_ok()
+	}
+
+	/// Execute `method` at `at_hash` with the remote state fetcher, or return `state_error` if
+	/// there is none.
+	fn remote_call(
+		&self,
+		at_hash: Block::Hash,
+		method: &str,
+		call_data: &[u8],
+		state_error: sp_blockchain::Error,
+	) -> sp_blockchain::Result<Vec<u8>> {
+		let Some(fetcher) = self.remote.get() else { return Err(state_error) };
+		wait_for_remote(fetcher.remote_call(at_hash, method.to_string(), call_data.to_vec()))
+	}
 }
 
 impl<Block: BlockT, B, E> Clone for LocalCallExecutor<Block, B, E>
@@ -74,6 +150,7 @@ where
 			executor: self.executor.clone(),
 			code_provider: self.code_provider.clone(),
 			execution_extensions: self.execution_extensions.clone(),
+			remote: self.remote.clone(),
 		}
 	}
 }
@@ -102,7 +179,10 @@ where
 		let mut changes = OverlayedChanges::default();
 		let at_number =
 			self.backend.blockchain().expect_block_number_from_id(&BlockId::Hash(at_hash))?;
-		let state = self.backend.state_at(at_hash, context.into())?;
+		let state = match self.backend.state_at(at_hash, context.into()) {
+			Ok(state) => state,
+			Err(e) => return self.remote_call(at_hash, method, call_data, e),
+		};
 
 		let state_runtime_code = sp_state_machine::backend::BackendRuntimeCode::new(&state);
 		let runtime_code =
@@ -137,7 +217,13 @@ where
 		call_context: CallContext,
 		extensions: &RefCell<Extensions>,
 	) -> Result<Vec<u8>, sp_blockchain::Error> {
-		let state = self.backend.state_at(at_hash, call_context.into())?;
+		let state = match self.backend.state_at(at_hash, call_context.into()) {
+			Ok(state) => state,
+			// Full peers execute the call on the state of `at_hash`, without the changes of
+			// earlier calls. The proof they return can't be recorded.
+			Err(e) if recorder.is_none() => return self.remote_call(at_hash, method, call_data, e),
+			Err(e) => return Err(e),
+		};
 
 		let changes = &mut *changes.borrow_mut();
 
@@ -191,7 +277,14 @@ where
 	}
 
 	fn runtime_version(&self, at_hash: Block::Hash) -> sp_blockchain::Result<RuntimeVersion> {
-		let state = self.backend.state_at(at_hash, backend::TrieCacheContext::Untrusted)?;
+		let state = match self.backend.state_at(at_hash, backend::TrieCacheContext::Untrusted) {
+			Ok(state) => state,
+			Err(e) => {
+				let version = self.remote_call(at_hash, "Core_version", &[], e)?;
+				return RuntimeVersion::decode(&mut &version[..])
+					.map_err(|e| sp_blockchain::Error::VersionInvalid(e.to_string()))
+			},
+		};
 		let state_runtime_code = sp_state_machine::backend::BackendRuntimeCode::new(&state);
 
 		let runtime_code =
@@ -229,6 +322,10 @@ where
 		)
 		.map_err(Into::into)
 	}
+
+	fn remote_state_fetcher(&self) -> Option<Arc<dyn RemoteStateFetcher<Block>>> {
+		self.remote.get().cloned()
+	}
 }
 
 impl<B, E, Block> RuntimeVersionOf for LocalCallExecutor<Block, B, E>
@@ -20,6 +20,7 @@
 
 use super::{
 	block_rules::{BlockRules, LookupResult as BlockLookupResult},
+	call_executor::wait_for_remote,
 	CodeProvider,
 };
 use crate::client::notification_pinning::NotificationPinningWorker;
@@ -66,7 +67,7 @@ use sp_core::{
 use sp_runtime::{
 	generic::{BlockId, SignedBlock},
 	traits::{
-		Block as BlockT, BlockIdTo, HashingFor, Header as HeaderT, NumberFor, One,
+		Block as BlockT, BlockIdTo, Hash as HashT, HashingFor, Header as HeaderT, NumberFor, One,
 		SaturatedConversion, Zero,
 	},
 	Justification, Justifications, StateVersion,
@@ -442,6 +443,25 @@ where
 		self.backend.state_at(hash, TrieCacheContext::Untrusted)
 	}
 
+	/// Read `key` from the storage at `hash`, or from the child storage of `child_info`, with the
+	/// remote state fetcher of a light client. Returns `state_error` if there is none.
+	fn remote_storage(
+		&self,
+		hash: Block::Hash,
+		child_info: Option<&ChildInfo>,
+		key: &StorageKey,
+		state_error: sp_blockchain::Error,
+	) -> sp_blockchain::Result<Option<StorageData>> {
+		let Some(fetcher) = self.executor.remote_state_fetcher() else { return Err(state_error) };
+		let keys = vec![key.0.clone()];
+		let mut values = wait_for_remote(match child_info {
+			Some(child_info) =>
+				fetcher.remote_read_child(hash, child_info.prefixed_storage_key(), keys),
+			None => fetcher.remote_read(hash, keys),
+		})?;
+		Ok(values.remove(&key.0).flatten().map(StorageData))
+	}
+
 	/// Get the code at a given block.
 	///
 	/// This takes any potential substitutes into account, but ignores overrides.
@@ -1450,8 +1470,11 @@ where
 		hash: Block::Hash,
 		key: &StorageKey,
 	) -> sp_blockchain::Result<Option<StorageData>> {
-		Ok(self
-			.state_at(hash)?
+		let state = match self.state_at(hash) {
+			Ok(state) => state,
+			Err(e) => return self.remote_storage(hash, None, key, e),
+		};
+		Ok(state
 			.storage(&key.0)
 			.map_err(|e| sp_blockchain::Error::from_state(Box::new(e)))?
 			.map(StorageData))
@@ -1462,7 +1485,14 @@ where
 		hash: <Block as BlockT>::Hash,
 		key: &StorageKey,
 	) -> sp_blockchain::Result<Option<Block::Hash>> {
-		self.state_at(hash)?
+		let state = match self.state_at(hash) {
+			Ok(state) => state,
+			Err(e) => {
+				let value = self.remote_storage(hash, None, key, e)?;
+				return Ok(value.map(|value| <HashingFor<Block> as HashT>::hash(&value.0)))
+			},
+		};
+		state
 			.storage_hash(&key.0)
 			.map_err(|e| sp_blockchain::Error::from_state(Box::new(e)))
 	}
@@ -1473,8 +1503,11 @@ where
 		child_info: &ChildInfo,
 		key: &StorageKey,
 	) -> sp_blockchain::Result<Option<StorageData>> {
-		Ok(self
-			.state_at(hash)?
+		let state = match self.state_at(hash) {
+			Ok(state) => state,
+			Err(e) => return self.remote_storage(hash, Some(child_info), key, e),
+		};
+		Ok(state
 			.child_storage(child_info, &key.0)
 			.map_err(|e| sp_blockchain::Error::from_state(Box::new(e)))?
 			.map(StorageData))
@@ -1486,7 +1519,14 @@ where
 		child_info: &ChildInfo,
 		key: &StorageKey,
 	) -> sp_blockchain::Result<Option<Block::Hash>> {
-		self.state_at(hash)?
+		let state = match self.state_at(hash) {
+			Ok(state) => state,
+			Err(e) => {
+				let value = self.remote_storage(hash, Some(child_info), key, e)?;
+				return Ok(value.map(|value| <HashingFor<Block> as HashT>::hash(&value.0)))
+			},
+		};
+		state
 			.child_storage_hash(child_info, &key.0)
 			.map_err(|e| sp_blockchain::Error::from_state(Box::new(e)))
 	}
@@ -33,6 +33,7 @@ pub use sc_network::{
 	},
 	Multiaddr,
 };
+pub use sc_offchain::{HttpPolicy as OffchainHttpPolicy, UrlPattern as OffchainUrlPattern};
 pub use sc_rpc_server::{
 	IpNetwork, RpcEndpoint, RpcMethods, SubscriptionIdProvider as RpcSubscriptionIdProvider,
 };
@@ -154,13 +155,27 @@ pub enum KeystoreConfig {
 	},
 	/// In-memory keystore. Recommended for in-browser nodes.
 	InMemory,
+	/// Keystore whose keys are held by a remote signer, reached over a Unix socket.
+	///
+	/// The on-disk keystore at `path` is still opened for the components that require direct
+	/// access to key pairs, see [`KeystoreContainer::local_keystore`](crate::KeystoreContainer).
+	Remote {
+		/// The path of the local keystore.
+		path: PathBuf,
+		/// Local keystore's password.
+		password: Option<SecretString>,
+		/// The Unix socket of the remote signer.
+		socket: PathBuf,
+		/// The file containing the secret shared with the remote signer.
+		secret_file: PathBuf,
+	},
 }
 
 impl KeystoreConfig {
 	/// Returns the path for the keystore.
 	pub fn path(&self) -> Option<&Path> {
 		match self {
-			Self::Path { path, .. } => Some(path),
+			Self::Path { path, .. } | Self::Remote { path, .. } => Some(path),
 			Self::InMemory => None,
 		}
 	}
@@ -172,6 +187,8 @@ pub struct OffchainWorkerConfig {
 	pub enabled: bool,
 	/// allow writes from the runtime to the offchain worker database.
 	pub indexing_enabled: bool,
+	/// Policy applied to the HTTP requests of offchain workers.
+	pub http_policy: OffchainHttpPolicy,
 }
 
 /// Configuration of the Prometheus endpoint.
@@ -225,7 +242,10 @@ impl Configuration {
 	/// Returns true if the genesis state writing will be skipped while initializing the genesis
 	/// block.
 	pub fn no_genesis(&self) -> bool {
-		matches!(self.network.sync_mode, SyncMode::LightState { .. } | SyncMode::Warp { .. })
+		matches!(
+			self.network.sync_mode,
+			SyncMode::LightState { .. } | SyncMode::Warp { .. } | SyncMode::Checkpoint
+		)
 	}
 
 	/// Returns the database config for creating the backend.
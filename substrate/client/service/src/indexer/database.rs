@@ -0,0 +1,457 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The SQLite database of the indexer.
+
+use super::{decode::BlockData, Error};
+use sqlx::{
+	sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+	FromRow, SqlitePool,
+};
+use std::path::Path;
+
+/// The columns of [`BlockRow`].
+const BLOCK_COLUMNS: &str = "hash, parent_hash, number, spec_version, finalized";
+
+/// The columns of [`ExtrinsicRow`].
+const EXTRINSIC_COLUMNS: &str =
+	"block_hash, extrinsic_index, hash, pallet, call, signer, success, args, raw";
+
+/// The columns of [`EventRow`].
+const EVENT_COLUMNS: &str =
+	"block_hash, event_index, extrinsic_index, pallet, variant, fields, raw";
+
+/// An indexed block.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub(super) struct BlockRow {
+	pub(super) hash: Vec<u8>,
+	pub(super) parent_hash: Vec<u8>,
+	pub(super) number: i64,
+	pub(super) spec_version: u32,
+	pub(super) finalized: bool,
+}
+
+/// An indexed extrinsic.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub(super) struct ExtrinsicRow {
+	pub(super) block_hash: Vec<u8>,
+	pub(super) extrinsic_index: u32,
+	pub(super) hash: Vec<u8>,
+	pub(super) pallet: String,
+	pub(super) call: String,
+	pub(super) signer: Option<Vec<u8>>,
+	pub(super) success: Option<bool>,
+	pub(super) args: String,
+	pub(super) raw: Option<Vec<u8>>,
+}
+
+/// An indexed event.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub(super) struct EventRow {
+	pub(super) block_hash: Vec<u8>,
+	pub(super) event_index: u32,
+	pub(super) extrinsic_index: Option<u32>,
+	pub(super) pallet: String,
+	pub(super) variant: String,
+	pub(super) fields: String,
+	pub(super) raw: Option<Vec<u8>>,
+}
+
+/// An indexed transfer, along with the number of its block.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub(super) struct TransferRow {
+	pub(super) block_hash: Vec<u8>,
+	pub(super) number: i64,
+	pub(super) event_index: u32,
+	pub(super) extrinsic_index: Option<u32>,
+	pub(super) sender: Vec<u8>,
+	pub(super) recipient: Vec<u8>,
+	pub(super) amount: String,
+}
+
+/// Block numbers are stored as SQLite integers, which are signed.
+fn to_i64(number: u64) -> i64 {
+	number.try_into().unwrap_or(i64::MAX)
+}
+
+/// The indexer database.
+#[derive(Clone)]
+pub struct Database {
+	pool: SqlitePool,
+}
+
+impl Database {
+	/// Open the database at `path`, creating it if it does not exist.
+	///
+	/// Connections are only established once the database is used.
+	pub fn open(path: &Path) -> Self {
+		let options = SqliteConnectOptions::new()
+			.filename(path)
+			.create_if_missing(true)
+			.journal_mode(SqliteJournalMode::Wal)
+			.foreign_keys(true);
+		Self { pool: SqlitePoolOptions::new().connect_lazy_with(options) }
+	}
+
+	/// Migrate the database to the latest schema.
+	pub async fn migrate(&self) -> Result<(), Error> {
+		sqlx::migrate!("src/indexer/migrations").run(&self.pool).await?;
+		Ok(())
+	}
+
+	/// Whether the block `hash` is indexed.
+	pub(super) async fn contains(&self, hash: &[u8]) -> Result<bool, Error> {
+		let row = sqlx::query("SELECT 1 FROM blocks WHERE hash = ?")
+			.bind(hash)
+			.fetch_optional(&self.pool)
+			.await?;
+		Ok(row.is_some())
+	}
+
+	/// The number of the latest finalized block.
+	pub(super) async fn last_finalized(&self) -> Result<Option<u64>, Error> {
+		let number: Option<i64> =
+			sqlx::query_scalar("SELECT MAX(number) FROM blocks WHERE finalized = 1")
+				.fetch_one(&self.pool)
+				.await?;
+		Ok(number.map(|number| number.try_into().unwrap_or_default()))
+	}
+
+	/// Insert `block` and all of its extrinsics, events and transfers.
+	pub(super) async fn insert(&self, block: &BlockData, finalized: bool) -> Result<(), Error> {
+		let mut tx = self.pool.begin().await?;
+
+		sqlx::query(
+			"INSERT OR IGNORE INTO blocks (hash, parent_hash, number, spec_version, finalized) \
+			 VALUES (?, ?, ?, ?, ?)",
+		)
+		.bind(&block.hash)
+		.bind(&block.parent_hash)
+		.bind(to_i64(block.number))
+		.bind(block.spec_version)
+		.bind(finalized)
+		.execute(&mut *tx)
+		.await?;
+
+		for extrinsic in &block.extrinsics {
+			sqlx::query(&format!(
+				"INSERT OR IGNORE INTO extrinsics ({EXTRINSIC_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+			))
+			.bind(&block.hash)
+			.bind(extrinsic.index)
+			.bind(&extrinsic.hash)
+			.bind(&extrinsic.pallet)
+			.bind(&extrinsic.call)
+			.bind(&extrinsic.signer)
+			.bind(extrinsic.success)
+			.bind(&extrinsic.args)
+			.bind(&extrinsic.raw)
+			.execute(&mut *tx)
+			.await?;
+		}
+
+		for event in &block.events {
+			sqlx::query(&format!(
+				"INSERT OR IGNORE INTO events ({EVENT_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?)"
+			))
+			.bind(&block.hash)
+			.bind(event.index)
+			.bind(event.extrinsic_index)
+			.bind(&event.pallet)
+			.bind(&event.variant)
+			.bind(&event.fields)
+			.bind(&event.raw)
+			.execute(&mut *tx)
+			.await?;
+		}
+
+		for transfer in &block.transfers {
+			sqlx::query(
+				"INSERT OR IGNORE INTO transfers \
+				 (block_hash, event_index, extrinsic_index, sender, recipient, amount) \
+				 VALUES (?, ?, ?, ?, ?, ?)",
+			)
+			.bind(&block.hash)
+			.bind(transfer.event_index)
+			.bind(transfer.extrinsic_index)
+			.bind(&transfer.from)
+			.bind(&transfer.to)
+			.bind(transfer.amount.to_string())
+			.execute(&mut *tx)
+			.await?;
+		}
+
+		tx.commit().await?;
+		Ok(())
+	}
+
+	/// Mark the blocks `hashes` as finalized, and remove all other blocks up to `number`.
+	pub(super) async fn finalize(&self, hashes: &[Vec<u8>], number: u64) -> Result<(), Error> {
+		let mut tx = self.pool.begin().await?;
+		for hash in hashes {
+			sqlx::query("UPDATE blocks SET finalized = 1 WHERE hash = ?")
+				.bind(hash)
+				.execute(&mut *tx)
+				.await?;
+		}
+		sqlx::query("DELETE FROM blocks WHERE finalized = 0 AND number <= ?")
+			.bind(to_i64(number))
+			.execute(&mut *tx)
+			.await?;
+		tx.commit().await?;
+		Ok(())
+	}
+
+	/// Remove the stale branch with the given `head`, down to the first finalized block.
+	pub(super) async fn remove_branch(&self, head: &[u8]) -> Result<(), Error> {
+		let mut tx = self.pool.begin().await?;
+		let mut hash = head.to_vec();
+		loop {
+			let parent: Option<Vec<u8>> = sqlx::query_scalar(
+				"SELECT parent_hash FROM blocks WHERE hash = ? AND finalized = 0",
+			)
+			.bind(&hash)
+			.fetch_optional(&mut *tx)
+			.await?;
+			let Some(parent) = parent else { break };
+			sqlx::query("DELETE FROM blocks WHERE hash = ?")
+				.bind(&hash)
+				.execute(&mut *tx)
+				.await?;
+			hash = parent;
+		}
+		tx.commit().await?;
+		Ok(())
+	}
+
+	/// Remove all blocks below `number`.
+	pub(super) async fn prune(&self, number: u64) -> Result<(), Error> {
+		sqlx::query("DELETE FROM blocks WHERE number < ?")
+			.bind(to_i64(number))
+			.execute(&self.pool)
+			.await?;
+		Ok(())
+	}
+
+	/// The block `hash`.
+	pub(super) async fn block(&self, hash: &[u8]) -> Result<Option<BlockRow>, Error> {
+		let block = sqlx::query_as(&format!("SELECT {BLOCK_COLUMNS} FROM blocks WHERE hash = ?"))
+			.bind(hash)
+			.fetch_optional(&self.pool)
+			.await?;
+		Ok(block)
+	}
+
+	/// All blocks with `number`, the finalized one first.
+	pub(super) async fn blocks_by_number(&self, number: u64) -> Result<Vec<BlockRow>, Error> {
+		let blocks = sqlx::query_as(&format!(
+			"SELECT {BLOCK_COLUMNS} FROM blocks WHERE number = ? ORDER BY finalized DESC"
+		))
+		.bind(to_i64(number))
+		.fetch_all(&self.pool)
+		.await?;
+		Ok(blocks)
+	}
+
+	/// The extrinsics of the block `hash`.
+	pub(super) async fn extrinsics(&self, block_hash: &[u8]) -> Result<Vec<ExtrinsicRow>, Error> {
+		let extrinsics = sqlx::query_as(&format!(
+			"SELECT {EXTRINSIC_COLUMNS} FROM extrinsics WHERE block_hash = ? \
+			 ORDER BY extrinsic_index"
+		))
+		.bind(block_hash)
+		.fetch_all(&self.pool)
+		.await?;
+		Ok(extrinsics)
+	}
+
+	/// All extrinsics with `hash`.
+	pub(super) async fn extrinsics_by_hash(&self, hash: &[u8]) -> Result<Vec<ExtrinsicRow>, Error> {
+		let extrinsics =
+			sqlx::query_as(&format!("SELECT {EXTRINSIC_COLUMNS} FROM extrinsics WHERE hash = ?"))
+				.bind(hash)
+				.fetch_all(&self.pool)
+				.await?;
+		Ok(extrinsics)
+	}
+
+	/// The events of the block `hash`, optionally only those of `pallet`.
+	pub(super) async fn events(
+		&self,
+		block_hash: &[u8],
+		pallet: Option<&str>,
+	) -> Result<Vec<EventRow>, Error> {
+		let events = sqlx::query_as(&format!(
+			"SELECT {EVENT_COLUMNS} FROM events WHERE block_hash = ? \
+			 AND (? IS NULL OR pallet = ?) ORDER BY event_index"
+		))
+		.bind(block_hash)
+		.bind(pallet)
+		.bind(pallet)
+		.fetch_all(&self.pool)
+		.await?;
+		Ok(events)
+	}
+
+	/// The latest `limit` transfers from or to `account`, newest first.
+	pub(super) async fn transfers(
+		&self,
+		account: &[u8],
+		limit: u32,
+	) -> Result<Vec<TransferRow>, Error> {
+		let transfers = sqlx::query_as(
+			"SELECT t.block_hash, b.number, t.event_index, t.extrinsic_index, t.sender, \
+			 t.recipient, t.amount FROM transfers t JOIN blocks b ON b.hash = t.block_hash \
+			 WHERE t.sender = ? OR t.recipient = ? ORDER BY b.number DESC, t.event_index DESC \
+			 LIMIT ?",
+		)
+		.bind(account)
+		.bind(account)
+		.bind(limit)
+		.fetch_all(&self.pool)
+		.await?;
+		Ok(transfers)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::indexer::decode::{EventData, ExtrinsicData, TransferData};
+	use std::str::FromStr;
+
+	async fn database() -> Database {
+		let options = SqliteConnectOptions::from_str("sqlite::memory:").unwrap().foreign_keys(true);
+		let pool = SqlitePoolOptions::new().max_connections(1).connect_with(options).await.unwrap();
+		let database = Database { pool };
+		database.migrate().await.unwrap();
+		database
+	}
+
+	fn block(hash: u8, parent: u8, number: u64) -> BlockData {
+		BlockData {
+			hash: vec![hash; 32],
+			parent_hash: vec![parent; 32],
+			number,
+			spec_version: 1,
+			extrinsics: vec![ExtrinsicData {
+				index: 0,
+				hash: vec![hash; 32],
+				pallet: "Balances".into(),
+				call: "transfer_keep_alive".into(),
+				signer: Some(vec![1; 33]),
+				success: Some(true),
+				args: "(dest: Id((0x02..)), value: 10)".into(),
+				raw: None,
+			}],
+			events: vec![EventData {
+				index: 0,
+				extrinsic_index: Some(0),
+				pallet: "Balances".into(),
+				variant: "Transfer".into(),
+				fields: "(from: 0x01.., to: 0x02.., amount: 10)".into(),
+				raw: None,
+			}],
+			transfers: vec![TransferData {
+				event_index: 0,
+				extrinsic_index: Some(0),
+				from: vec![1; 32],
+				to: vec![2; 32],
+				amount: u128::MAX,
+			}],
+		}
+	}
+
+	#[tokio::test]
+	async fn insert_and_query_works() {
+		let database = database().await;
+		database.insert(&block(1, 0, 1), true).await.unwrap();
+		database.insert(&block(2, 1, 2), false).await.unwrap();
+
+		assert!(database.contains(&[1; 32]).await.unwrap());
+		assert_eq!(database.last_finalized().await.unwrap(), Some(1));
+		assert_eq!(
+			database.block(&[2; 32]).await.unwrap(),
+			Some(BlockRow {
+				hash: vec![2; 32],
+				parent_hash: vec![1; 32],
+				number: 2,
+				spec_version: 1,
+				finalized: false,
+			})
+		);
+
+		let extrinsics = database.extrinsics(&[2; 32]).await.unwrap();
+		assert_eq!(extrinsics.len(), 1);
+		assert_eq!(extrinsics[0].success, Some(true));
+		assert_eq!(database.extrinsics_by_hash(&[1; 32]).await.unwrap().len(), 1);
+
+		assert_eq!(database.events(&[2; 32], None).await.unwrap().len(), 1);
+		assert_eq!(database.events(&[2; 32], Some("Balances")).await.unwrap().len(), 1);
+		assert!(database.events(&[2; 32], Some("System")).await.unwrap().is_empty());
+
+		// newest first, for both the sender and the recipient.
+		let transfers = database.transfers(&[2; 32], 10).await.unwrap();
+		assert_eq!(transfers.iter().map(|t| t.number).collect::<Vec<_>>(), vec![2, 1]);
+		assert_eq!(transfers[0].amount, u128::MAX.to_string());
+		assert_eq!(database.transfers(&[1; 32], 1).await.unwrap().len(), 1);
+		assert!(database.transfers(&[3; 32], 10).await.unwrap().is_empty());
+
+		// undecodable extrinsics are returned with their raw bytes.
+		let mut undecodable = block(3, 2, 3);
+		undecodable.extrinsics[0] = ExtrinsicData {
+			index: 0,
+			hash: vec![3; 32],
+			raw: Some(vec![0xff; 8]),
+			..Default::default()
+		};
+		database.insert(&undecodable, false).await.unwrap();
+		let extrinsics = database.extrinsics(&[3; 32]).await.unwrap();
+		assert_eq!(extrinsics[0].raw, Some(vec![0xff; 8]));
+		assert!(extrinsics[0].pallet.is_empty());
+	}
+
+	#[tokio::test]
+	async fn stale_and_pruned_blocks_are_removed() {
+		let database = database().await;
+		// 1 - 2 - 3
+		//   \ 4 - 5
+		database.insert(&block(1, 0, 1), true).await.unwrap();
+		database.insert(&block(2, 1, 2), false).await.unwrap();
+		database.insert(&block(3, 2, 3), false).await.unwrap();
+		database.insert(&block(4, 1, 2), false).await.unwrap();
+		database.insert(&block(5, 4, 3), false).await.unwrap();
+		assert_eq!(database.blocks_by_number(2).await.unwrap().len(), 2);
+
+		// finalizing 2 removes its sibling 4, and the branch of 5 once reported as stale.
+		database.finalize(&[vec![2; 32]], 2).await.unwrap();
+		assert!(!database.contains(&[4; 32]).await.unwrap());
+		assert!(database.contains(&[5; 32]).await.unwrap());
+		database.remove_branch(&[5; 32]).await.unwrap();
+		assert!(!database.contains(&[5; 32]).await.unwrap());
+		assert!(database.contains(&[3; 32]).await.unwrap());
+
+		// all data of removed blocks is removed as well.
+		assert!(database.extrinsics(&[4; 32]).await.unwrap().is_empty());
+		assert_eq!(database.transfers(&[1; 32], 10).await.unwrap().len(), 3);
+
+		database.prune(2).await.unwrap();
+		assert!(!database.contains(&[1; 32]).await.unwrap());
+		assert_eq!(database.last_finalized().await.unwrap(), Some(2));
+	}
+}
@@ -0,0 +1,404 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Decoding of blocks and their events with the metadata of their runtime.
+
+use super::{Error, LOG_TARGET};
+use codec::{Compact, CompactLen, Decode, Encode};
+use log::{debug, trace, warn};
+use sp_api::{Core, Metadata as _, ProvideRuntimeApi};
+use sp_core::storage::{StorageData, StorageKey};
+use sp_runtime::traits::{
+	Block as BlockT, Hash as HashT, HashingFor, Header as HeaderT, UniqueSaturatedInto, Zero,
+};
+use subxt_core::{
+	blocks::Extrinsics,
+	config::SubstrateConfig,
+	events::{Phase, StaticEvent},
+	ext::scale_decode,
+	utils::AccountId32,
+	Metadata,
+};
+
+/// The latest metadata version that is requested from the runtime.
+const METADATA_VERSION: u32 = 15;
+
+/// The storage key of `System::Events`.
+pub(super) fn events_key() -> StorageKey {
+	StorageKey(
+		[sp_crypto_hashing::twox_128(b"System"), sp_crypto_hashing::twox_128(b"Events")].concat(),
+	)
+}
+
+/// The `Transfer` event of `pallet-balances`.
+///
+/// Decoded by the type information of the metadata, thus independent of the exact integer type
+/// of the balance. Chains whose accounts are not 32 bytes long do not have their transfers
+/// indexed.
+#[derive(scale_decode::DecodeAsType)]
+#[decode_as_type(crate_path = "subxt_core::ext::scale_decode")]
+struct Transfer {
+	from: AccountId32,
+	to: AccountId32,
+	amount: u128,
+}
+
+impl StaticEvent for Transfer {
+	const PALLET: &'static str = "Balances";
+	const EVENT: &'static str = "Transfer";
+}
+
+/// A decoded block, ready to be written to the database.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockData {
+	pub hash: Vec<u8>,
+	pub parent_hash: Vec<u8>,
+	pub number: u64,
+	pub spec_version: u32,
+	pub extrinsics: Vec<ExtrinsicData>,
+	pub events: Vec<EventData>,
+	pub transfers: Vec<TransferData>,
+}
+
+/// A decoded extrinsic.
+///
+/// Extrinsics that cannot be decoded are kept with their SCALE encoded bytes in `raw`, and empty
+/// names and arguments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtrinsicData {
+	pub index: u32,
+	pub hash: Vec<u8>,
+	pub pallet: String,
+	pub call: String,
+	pub signer: Option<Vec<u8>>,
+	pub success: Option<bool>,
+	pub args: String,
+	pub raw: Option<Vec<u8>>,
+}
+
+/// A decoded event.
+///
+/// Events that cannot be decoded are kept with their SCALE encoded bytes in `raw`. As the events
+/// of a block are not length prefixed, the first event that cannot be split off holds the bytes of
+/// all remaining events.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventData {
+	pub index: u32,
+	pub extrinsic_index: Option<u32>,
+	pub pallet: String,
+	pub variant: String,
+	pub fields: String,
+	pub raw: Option<Vec<u8>>,
+}
+
+/// A decoded transfer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransferData {
+	pub event_index: u32,
+	pub extrinsic_index: Option<u32>,
+	pub from: Vec<u8>,
+	pub to: Vec<u8>,
+	pub amount: u128,
+}
+
+/// Decodes blocks, caching the metadata of the latest runtime.
+#[derive(Default)]
+pub(super) struct Decoder {
+	metadata: Option<(u32, Metadata)>,
+}
+
+impl Decoder {
+	/// Decode the block with `header` and `body`, whose `System::Events` are `events`.
+	///
+	/// Only fails if the metadata of the runtime is not available. Extrinsics and events that
+	/// cannot be decoded with it are indexed with their raw bytes instead.
+	pub(super) fn decode<B, C>(
+		&mut self,
+		client: &C,
+		header: &B::Header,
+		body: &[B::Extrinsic],
+		events: Option<StorageData>,
+	) -> Result<BlockData, Error>
+	where
+		B: BlockT,
+		C: ProvideRuntimeApi<B>,
+		C::Api: Core<B> + sp_api::Metadata<B>,
+	{
+		// A block is built by the runtime of its parent. Its own state may already contain the
+		// code of a runtime upgrade that was applied in this block. The genesis block has no
+		// parent state and is built by its own runtime.
+		let runtime_at =
+			if header.number().is_zero() { header.hash() } else { *header.parent_hash() };
+		let (spec_version, metadata) = self.metadata::<B, C>(client, runtime_at)?;
+
+		Ok(decode_block::<B>(
+			header,
+			spec_version,
+			metadata,
+			body.iter().map(Encode::encode).collect(),
+			events.map(|data| data.0).unwrap_or_default(),
+		))
+	}
+
+	/// The spec version and metadata of the runtime at the state of block `hash`.
+	fn metadata<B, C>(&mut self, client: &C, hash: B::Hash) -> Result<(u32, &Metadata), Error>
+	where
+		B: BlockT,
+		C: ProvideRuntimeApi<B>,
+		C::Api: Core<B> + sp_api::Metadata<B>,
+	{
+		let api = client.runtime_api();
+		let spec_version = api.version(hash)?.spec_version;
+		if self.metadata.as_ref().map_or(true, |(version, _)| *version != spec_version) {
+			debug!(target: LOG_TARGET, "Fetching the metadata of spec version {spec_version}");
+			// runtimes that do not support `metadata_at_version` only provide the V14 metadata.
+			let opaque = match api.metadata_at_version(hash, METADATA_VERSION) {
+				Ok(Some(opaque)) => opaque,
+				_ => api.metadata(hash)?,
+			};
+			let metadata = subxt_core::metadata::decode_from(&opaque[..])
+				.map_err(|e| Error::Metadata(e.to_string()))?;
+			self.metadata = Some((spec_version, metadata));
+		}
+
+		let (_, metadata) = self.metadata.as_ref().expect("metadata is set above; qed");
+		Ok((spec_version, metadata))
+	}
+}
+
+/// Decode a block from its SCALE encoded `extrinsics` and `events` with `metadata`.
+fn decode_block<B: BlockT>(
+	header: &B::Header,
+	spec_version: u32,
+	metadata: &Metadata,
+	extrinsics: Vec<Vec<u8>>,
+	events: Vec<u8>,
+) -> BlockData {
+	let hash = header.hash();
+	let mut block = BlockData {
+		hash: hash.as_ref().to_vec(),
+		parent_hash: header.parent_hash().as_ref().to_vec(),
+		number: (*header.number()).unique_saturated_into(),
+		spec_version,
+		..Default::default()
+	};
+
+	for (index, bytes) in extrinsics.into_iter().enumerate() {
+		let index = index as u32;
+		let extrinsic_hash = HashingFor::<B>::hash(&bytes).as_ref().to_vec();
+		let extrinsic = decode_extrinsic(&bytes, metadata).unwrap_or_else(|e| {
+			warn!(target: LOG_TARGET, "Storing extrinsic {index} of block {hash} undecoded: {e}");
+			ExtrinsicData { raw: Some(bytes), ..Default::default() }
+		});
+		block
+			.extrinsics
+			.push(ExtrinsicData { index, hash: extrinsic_hash, ..extrinsic });
+	}
+
+	// the events are decoded one after another, so the offset of the next one is tracked to keep
+	// the remaining bytes if an event cannot be decoded.
+	let mut offset = Compact::<u32>::decode(&mut &events[..])
+		.map_or(events.len(), |count| Compact::<u32>::compact_len(&count.0));
+	let decoded =
+		subxt_core::events::decode_from::<SubstrateConfig>(events.clone(), metadata.clone());
+	for (index, event) in decoded.iter().enumerate() {
+		let event = match event {
+			Ok(event) => event,
+			Err(e) => {
+				warn!(
+					target: LOG_TARGET,
+					"Storing the events of block {hash} from {index} on undecoded: {e}",
+				);
+				block.events.push(EventData {
+					index: index as u32,
+					raw: Some(events[offset.min(events.len())..].to_vec()),
+					..Default::default()
+				});
+				break
+			},
+		};
+		offset += event.bytes().len();
+
+		let extrinsic_index = match event.phase() {
+			Phase::ApplyExtrinsic(index) => Some(index),
+			Phase::Initialization | Phase::Finalization => None,
+		};
+
+		if let (Some(index), "System", variant @ ("ExtrinsicSuccess" | "ExtrinsicFailed")) =
+			(extrinsic_index, event.pallet_name(), event.variant_name())
+		{
+			if let Some(extrinsic) = block.extrinsics.get_mut(index as usize) {
+				extrinsic.success = Some(variant == "ExtrinsicSuccess");
+			}
+		}
+
+		match event.as_event::<Transfer>() {
+			Ok(Some(transfer)) => block.transfers.push(TransferData {
+				event_index: event.index(),
+				extrinsic_index,
+				from: transfer.from.0.to_vec(),
+				to: transfer.to.0.to_vec(),
+				amount: transfer.amount,
+			}),
+			Ok(None) => {},
+			Err(e) => trace!(target: LOG_TARGET, "Skipping transfer {}: {e}", event.index()),
+		}
+
+		let (fields, raw) = match event.field_values() {
+			Ok(fields) => (fields.to_string(), None),
+			Err(e) => {
+				warn!(target: LOG_TARGET, "Storing event {index} of block {hash} undecoded: {e}");
+				(String::new(), Some(event.bytes().to_vec()))
+			},
+		};
+		block.events.push(EventData {
+			index: event.index(),
+			extrinsic_index,
+			pallet: event.pallet_name().into(),
+			variant: event.variant_name().into(),
+			fields,
+			raw,
+		});
+	}
+
+	block
+}
+
+/// Decode the SCALE encoded extrinsic `bytes` with `metadata`.
+///
+/// The index and hash of the returned extrinsic are left to the caller.
+fn decode_extrinsic(bytes: &[u8], metadata: &Metadata) -> Result<ExtrinsicData, String> {
+	let extrinsics =
+		Extrinsics::<SubstrateConfig>::decode_from(vec![bytes.to_vec()], metadata.clone())
+			.map_err(|e| e.to_string())?;
+	let extrinsic = extrinsics.iter().next().ok_or("Missing extrinsic")?;
+
+	Ok(ExtrinsicData {
+		pallet: extrinsic.pallet_name().map_err(|e| e.to_string())?.into(),
+		call: extrinsic.variant_name().map_err(|e| e.to_string())?.into(),
+		signer: extrinsic.address_bytes().map(|address| address.to_vec()),
+		args: extrinsic.field_values().map_err(|e| e.to_string())?.to_string(),
+		..Default::default()
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_client_api::{BlockBackend, StorageProvider};
+	use sp_consensus::BlockOrigin;
+	use substrate_test_runtime_client::{
+		prelude::*,
+		runtime::{currency::DOLLARS, Block, Transfer as TransferCall},
+	};
+
+	/// A client with block #1, which contains a transfer from Alice to Ferdie.
+	fn client_with_transfer() -> (TestClient, <Block as BlockT>::Hash) {
+		let client = substrate_test_runtime_client::new();
+		let mut builder = BlockBuilderBuilder::new(&client)
+			.on_parent_block(client.chain_info().genesis_hash)
+			.with_parent_block_number(0)
+			.build()
+			.unwrap();
+		builder
+			.push_transfer(TransferCall {
+				from: Sr25519Keyring::Alice.into(),
+				to: Sr25519Keyring::Ferdie.into(),
+				amount: 42 * DOLLARS,
+				nonce: 0,
+			})
+			.unwrap();
+		let block = builder.build().unwrap().block;
+		let hash = block.header.hash();
+		block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		(client, hash)
+	}
+
+	#[test]
+	fn decoding_works() {
+		let (client, hash) = client_with_transfer();
+		let header = client.header(hash).unwrap().unwrap();
+		let body = client.block_body(hash).unwrap().unwrap();
+		let events = client.storage(hash, &events_key()).unwrap();
+
+		let block = Decoder::default().decode::<Block, _>(&client, &header, &body, events).unwrap();
+
+		assert_eq!(block.number, 1);
+		let [extrinsic] = &block.extrinsics[..] else { panic!("expected one extrinsic") };
+		assert_eq!(
+			(extrinsic.pallet.as_str(), extrinsic.call.as_str()),
+			("Balances", "transfer_allow_death")
+		);
+		assert_eq!(extrinsic.hash, HashingFor::<Block>::hash(&body[0].encode()).as_ref());
+		assert_eq!(extrinsic.success, Some(true));
+		assert_eq!(extrinsic.raw, None);
+
+		assert!(block.events.iter().all(|event| event.raw.is_none()));
+		assert!(block
+			.events
+			.iter()
+			.any(|event| event.pallet == "Balances" && event.variant == "Transfer"));
+		let [transfer] = &block.transfers[..] else { panic!("expected one transfer") };
+		assert_eq!(transfer.from, Sr25519Keyring::Alice.to_raw_public_vec());
+		assert_eq!(transfer.to, Sr25519Keyring::Ferdie.to_raw_public_vec());
+		assert_eq!(transfer.amount, u128::from(42 * DOLLARS));
+	}
+
+	#[test]
+	fn undecodable_entries_are_stored_raw() {
+		let (client, hash) = client_with_transfer();
+		let header = client.header(hash).unwrap().unwrap();
+		let body = client.block_body(hash).unwrap().unwrap();
+		let events = client.storage(hash, &events_key()).unwrap().unwrap().0;
+		let mut decoder = Decoder::default();
+		let (spec_version, metadata) =
+			decoder.metadata::<Block, _>(&client, *header.parent_hash()).unwrap();
+
+		// an extrinsic of an unknown version, and an event that claims to exist but is garbage.
+		let garbage = vec![0xff_u8; 8].encode();
+		let count = Compact::<u32>::decode(&mut &events[..]).unwrap().0;
+		let prefix = Compact::<u32>::compact_len(&count);
+		let corrupted = [&Compact(count + 1).encode()[..], &events[prefix..], &[0xff; 4]].concat();
+
+		let block = decode_block::<Block>(
+			&header,
+			spec_version,
+			metadata,
+			vec![garbage.clone(), body[0].encode()],
+			corrupted,
+		);
+
+		let [undecodable, decodable] = &block.extrinsics[..] else {
+			panic!("expected two extrinsics")
+		};
+		assert_eq!(undecodable.index, 0);
+		assert_eq!(undecodable.hash, HashingFor::<Block>::hash(&garbage).as_ref());
+		assert_eq!(undecodable.raw, Some(garbage));
+		assert!(undecodable.pallet.is_empty());
+		assert_eq!((decodable.index, decodable.pallet.as_str()), (1, "Balances"));
+		assert_eq!(decodable.raw, None);
+
+		// all valid events are still decoded, and the garbage is kept.
+		assert_eq!(block.events.len(), count as usize + 1);
+		let last = block.events.last().unwrap();
+		assert_eq!(last.index, count);
+		assert_eq!(last.raw, Some(vec![0xff; 4]));
+		assert!(block.events[..count as usize].iter().all(|event| event.raw.is_none()));
+		assert_eq!(block.transfers.len(), 1);
+	}
+}
@@ -0,0 +1,59 @@
+-- The schema of the indexer database.
+--
+-- All rows of a block are removed together with the block, e.g. when its branch becomes stale.
+CREATE TABLE IF NOT EXISTS blocks (
+  hash BLOB NOT NULL PRIMARY KEY,
+  parent_hash BLOB NOT NULL,
+  number INTEGER NOT NULL,
+  spec_version INTEGER NOT NULL,
+  finalized INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_blocks_number ON blocks (
+  number
+);
+
+CREATE TABLE IF NOT EXISTS extrinsics (
+  block_hash BLOB NOT NULL REFERENCES blocks (hash) ON DELETE CASCADE,
+  extrinsic_index INTEGER NOT NULL,
+  hash BLOB NOT NULL,
+  pallet TEXT NOT NULL,
+  call TEXT NOT NULL,
+  signer BLOB,
+  success INTEGER,
+  args TEXT NOT NULL,
+  PRIMARY KEY (block_hash, extrinsic_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_extrinsics_hash ON extrinsics (
+  hash
+);
+
+CREATE TABLE IF NOT EXISTS events (
+  block_hash BLOB NOT NULL REFERENCES blocks (hash) ON DELETE CASCADE,
+  event_index INTEGER NOT NULL,
+  extrinsic_index INTEGER,
+  pallet TEXT NOT NULL,
+  variant TEXT NOT NULL,
+  fields TEXT NOT NULL,
+  PRIMARY KEY (block_hash, event_index)
+);
+
+-- Amounts are stored as decimal strings, as SQLite integers are limited to 64 bits.
+CREATE TABLE IF NOT EXISTS transfers (
+  block_hash BLOB NOT NULL REFERENCES blocks (hash) ON DELETE CASCADE,
+  event_index INTEGER NOT NULL,
+  extrinsic_index INTEGER,
+  sender BLOB NOT NULL,
+  recipient BLOB NOT NULL,
+  amount TEXT NOT NULL,
+  PRIMARY KEY (block_hash, event_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transfers_sender ON transfers (
+  sender
+);
+
+CREATE INDEX IF NOT EXISTS idx_transfers_recipient ON transfers (
+  recipient
+);
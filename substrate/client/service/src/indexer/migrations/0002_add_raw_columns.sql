@@ -0,0 +1,5 @@
+-- Extrinsics and events that cannot be decoded with the metadata of their runtime are kept with
+-- their SCALE encoded bytes, instead of failing the whole block.
+ALTER TABLE extrinsics ADD COLUMN raw BLOB;
+
+ALTER TABLE events ADD COLUMN raw BLOB;
@@ -0,0 +1,282 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! An optional indexer of blocks, extrinsics, events and transfers.
+//!
+//! When enabled, the indexer follows the imported or finalized blocks of the node, decodes their
+//! extrinsics and events using the metadata of the runtime they were built with, and writes them
+//! to a local SQLite database. The database can then be queried through the read-only
+//! [`sc_rpc_api::indexer::IndexerApiServer`] RPC, without re-fetching and decoding the blocks
+//! over RPC like an external indexer would have to.
+//!
+//! Blocks that are not finalized yet are removed again once they are known to be stale, i.e. once
+//! a block of the same or a higher number on another branch is finalized. Finalized blocks are
+//! kept, unless [`IndexerConfig::keep_blocks`] is set.
+//!
+//! Only available with the `indexer` feature.
+
+mod database;
+mod decode;
+mod rpc;
+
+pub use database::Database;
+pub use rpc::Indexer;
+
+use crate::SpawnTaskHandle;
+use decode::Decoder;
+use futures::{stream, StreamExt};
+use jsonrpsee::RpcModule;
+use log::{debug, error, info, warn};
+use sc_client_api::{
+	Backend, BlockBackend, BlockchainEvents, FinalityNotification, StorageProvider,
+};
+use sc_rpc_api::indexer::IndexerApiServer;
+use sp_api::{Core, Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
+	SaturatedConversion,
+};
+use std::{marker::PhantomData, path::PathBuf, sync::Arc};
+
+const LOG_TARGET: &str = "indexer";
+
+/// Which blocks are indexed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IndexingMode {
+	/// Index blocks once they are finalized.
+	#[default]
+	Finalized,
+	/// Index blocks as soon as they are imported, including blocks of forks.
+	Imported,
+}
+
+/// The configuration of the indexer.
+#[derive(Debug, Clone)]
+pub struct IndexerConfig {
+	/// The path of the SQLite database. Created if it does not exist.
+	pub path: PathBuf,
+	/// Which blocks are indexed.
+	pub mode: IndexingMode,
+	/// When `Some`, only this many of the latest finalized blocks are kept.
+	pub keep_blocks: Option<u32>,
+}
+
+/// Indexer errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The database returned an error.
+	#[error(transparent)]
+	Database(#[from] sqlx::Error),
+	/// The database could not be migrated to the latest schema.
+	#[error(transparent)]
+	Migrate(#[from] sqlx::migrate::MigrateError),
+	/// The client returned an error.
+	#[error(transparent)]
+	Client(#[from] sp_blockchain::Error),
+	/// A runtime API call failed.
+	#[error(transparent)]
+	RuntimeApi(#[from] sp_api::ApiError),
+	/// The metadata of the runtime could not be fetched or decoded.
+	#[error("Invalid metadata: {0}")]
+	Metadata(String),
+	/// The block, or some of its data, is not available.
+	#[error("Block {0} is not available")]
+	UnknownBlock(String),
+}
+
+/// Spawn the indexer as a task of `spawn_handle`, returning the RPC module to query it.
+///
+/// The database is opened lazily, and migrated to the latest schema by the task before indexing
+/// any block. Errors of the task are logged, and do not stop the node.
+pub fn spawn_indexer<B, BE, C>(
+	config: IndexerConfig,
+	client: Arc<C>,
+	spawn_handle: &SpawnTaskHandle,
+) -> RpcModule<Indexer<B>>
+where
+	B: BlockT,
+	BE: Backend<B> + 'static,
+	C: BlockchainEvents<B>
+		+ BlockBackend<B>
+		+ HeaderBackend<B>
+		+ StorageProvider<B, BE>
+		+ ProvideRuntimeApi<B>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: Core<B> + Metadata<B>,
+{
+	let database = Database::open(&config.path);
+	info!(target: LOG_TARGET, "📇 Indexing {:?} blocks into {}", config.mode, config.path.display());
+
+	let worker = Worker {
+		client,
+		database: database.clone(),
+		decoder: Decoder::default(),
+		config,
+		_phantom: PhantomData,
+	};
+	spawn_handle.spawn("indexer", Some("indexer"), worker.run());
+
+	Indexer::new(database).into_rpc()
+}
+
+/// The task that indexes blocks.
+struct Worker<B, BE, C> {
+	client: Arc<C>,
+	database: Database,
+	decoder: Decoder,
+	config: IndexerConfig,
+	_phantom: PhantomData<(B, BE)>,
+}
+
+impl<B, BE, C> Worker<B, BE, C>
+where
+	B: BlockT,
+	BE: Backend<B> + 'static,
+	C: BlockchainEvents<B>
+		+ BlockBackend<B>
+		+ HeaderBackend<B>
+		+ StorageProvider<B, BE>
+		+ ProvideRuntimeApi<B>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: Core<B> + Metadata<B>,
+{
+	async fn run(mut self) {
+		if let Err(e) = self.database.migrate().await {
+			error!(target: LOG_TARGET, "Failed to migrate the indexer database: {e}");
+			return
+		}
+
+		let mut imported = match self.config.mode {
+			IndexingMode::Imported => self.client.import_notification_stream().boxed(),
+			IndexingMode::Finalized => stream::pending().boxed(),
+		}
+		.fuse();
+		let mut finalized = self.client.finality_notification_stream().fuse();
+
+		if let Err(e) = self.catch_up().await {
+			warn!(target: LOG_TARGET, "Failed to index the blocks finalized while offline: {e}");
+		}
+
+		loop {
+			futures::select! {
+				notification = imported.next() => {
+					let Some(notification) = notification else { break };
+					if let Err(e) = self.index(notification.hash, false).await {
+						warn!(target: LOG_TARGET, "Failed to index block {}: {e}", notification.hash);
+					}
+				},
+				notification = finalized.next() => {
+					let Some(notification) = notification else { break };
+					if let Err(e) = self.finalize(notification).await {
+						warn!(target: LOG_TARGET, "Failed to index finalized blocks: {e}");
+					}
+				},
+			}
+		}
+	}
+
+	/// Index the blocks that were finalized since the last finalized block in the database.
+	///
+	/// Does nothing if the database is empty, i.e. blocks are only indexed from the first
+	/// finalized block onwards.
+	async fn catch_up(&mut self) -> Result<(), Error> {
+		let Some(last) = self.database.last_finalized().await? else { return Ok(()) };
+		let finalized: u64 = self.client.info().finalized_number.unique_saturated_into();
+		if last < finalized {
+			info!(target: LOG_TARGET, "📇 Indexing finalized blocks #{} to #{finalized}", last + 1);
+		}
+
+		let mut hashes = Vec::new();
+		for number in last + 1..=finalized {
+			let hash = self
+				.client
+				.hash(number.saturated_into())?
+				.ok_or_else(|| Error::UnknownBlock(number.to_string()))?;
+			// a block that cannot be indexed must not keep the indexer from following the chain.
+			match self.index(hash, true).await {
+				Ok(()) => hashes.push(hash.as_ref().to_vec()),
+				Err(e) =>
+					warn!(target: LOG_TARGET, "Failed to index block #{number} ({hash}): {e}"),
+			}
+		}
+		self.database.finalize(&hashes, finalized).await?;
+		self.prune(finalized).await
+	}
+
+	/// Index the block `hash`, unless it is already indexed.
+	async fn index(&mut self, hash: B::Hash, finalized: bool) -> Result<(), Error> {
+		if self.database.contains(hash.as_ref()).await? {
+			return Ok(())
+		}
+
+		let header =
+			self.client.header(hash)?.ok_or_else(|| Error::UnknownBlock(hash.to_string()))?;
+		let body = self
+			.client
+			.block_body(hash)?
+			.ok_or_else(|| Error::UnknownBlock(hash.to_string()))?;
+		let events = self.client.storage(hash, &decode::events_key())?;
+
+		let block = self.decoder.decode::<B, C>(&*self.client, &header, &body, events)?;
+		debug!(
+			target: LOG_TARGET,
+			"Indexing block #{} ({hash}): {} extrinsics, {} events",
+			header.number(),
+			block.extrinsics.len(),
+			block.events.len(),
+		);
+		self.database.insert(&block, finalized).await
+	}
+
+	/// Mark the finalized blocks of `notification` as such, and remove all stale blocks.
+	async fn finalize(&mut self, notification: FinalityNotification<B>) -> Result<(), Error> {
+		let number: u64 = (*notification.header.number()).unique_saturated_into();
+
+		// the finalized blocks are already indexed in `IndexingMode::Imported`, unless the
+		// indexer lagged behind or was started in the meantime.
+		let finalized = notification.tree_route.iter().chain(std::iter::once(&notification.hash));
+		let mut hashes = Vec::new();
+		for hash in finalized {
+			// a block that cannot be indexed must not keep the others from being finalized.
+			match self.index(*hash, true).await {
+				Ok(()) => hashes.push(hash.as_ref().to_vec()),
+				Err(e) => warn!(target: LOG_TARGET, "Failed to index finalized block {hash}: {e}"),
+			}
+		}
+		self.database.finalize(&hashes, number).await?;
+
+		for head in notification.stale_heads.iter() {
+			self.database.remove_branch(head.as_ref()).await?;
+		}
+
+		self.prune(number).await
+	}
+
+	/// Remove the finalized blocks that are not to be kept anymore.
+	async fn prune(&self, finalized: u64) -> Result<(), Error> {
+		if let Some(keep) = self.config.keep_blocks {
+			self.database.prune(finalized.saturating_sub(keep.into())).await?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The RPC serving the indexer database.
+
+use super::{
+	database::{BlockRow, Database, EventRow, ExtrinsicRow, TransferRow},
+	Error,
+};
+use codec::Decode;
+use jsonrpsee::core::async_trait;
+use sc_rpc_api::indexer::{
+	error::Error as RpcError, IndexedBlock, IndexedEvent, IndexedExtrinsic, IndexedTransfer,
+	IndexerApiServer, MAX_TRANSFERS,
+};
+use sp_runtime::traits::Block as BlockT;
+use std::marker::PhantomData;
+
+impl From<Error> for RpcError {
+	fn from(e: Error) -> Self {
+		RpcError::Database(e.to_string())
+	}
+}
+
+/// Decode a hash stored in the database.
+fn hash<H: Decode>(bytes: &[u8]) -> Result<H, RpcError> {
+	H::decode(&mut &bytes[..]).map_err(|e| RpcError::Corrupt(format!("invalid hash: {e}")))
+}
+
+fn block<H: Decode>(row: BlockRow) -> Result<IndexedBlock<H>, RpcError> {
+	Ok(IndexedBlock {
+		hash: hash(&row.hash)?,
+		parent_hash: hash(&row.parent_hash)?,
+		number: row.number.try_into().unwrap_or_default(),
+		spec_version: row.spec_version,
+		finalized: row.finalized,
+	})
+}
+
+fn extrinsic<H: Decode>(row: ExtrinsicRow) -> Result<IndexedExtrinsic<H>, RpcError> {
+	Ok(IndexedExtrinsic {
+		block_hash: hash(&row.block_hash)?,
+		index: row.extrinsic_index,
+		hash: hash(&row.hash)?,
+		pallet: row.pallet,
+		call: row.call,
+		signer: row.signer.map(Into::into),
+		success: row.success,
+		args: row.args,
+		raw: row.raw.map(Into::into),
+	})
+}
+
+fn event<H: Decode>(row: EventRow) -> Result<IndexedEvent<H>, RpcError> {
+	Ok(IndexedEvent {
+		block_hash: hash(&row.block_hash)?,
+		index: row.event_index,
+		extrinsic_index: row.extrinsic_index,
+		pallet: row.pallet,
+		variant: row.variant,
+		fields: row.fields,
+		raw: row.raw.map(Into::into),
+	})
+}
+
+fn transfer<H: Decode>(row: TransferRow) -> Result<IndexedTransfer<H>, RpcError> {
+	Ok(IndexedTransfer {
+		block_hash: hash(&row.block_hash)?,
+		block_number: row.number.try_into().unwrap_or_default(),
+		event_index: row.event_index,
+		extrinsic_index: row.extrinsic_index,
+		from: row.sender.into(),
+		to: row.recipient.into(),
+		amount: row.amount,
+	})
+}
+
+/// Read-only RPC access to the indexer database.
+pub struct Indexer<Block> {
+	database: Database,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block> Indexer<Block> {
+	/// Create a new instance serving `database`.
+	pub fn new(database: Database) -> Self {
+		Self { database, _phantom: PhantomData }
+	}
+}
+
+#[async_trait]
+impl<Block: BlockT> IndexerApiServer<Block::Hash> for Indexer<Block> {
+	async fn block(
+		&self,
+		hash: Block::Hash,
+	) -> Result<Option<IndexedBlock<Block::Hash>>, RpcError> {
+		self.database.block(hash.as_ref()).await?.map(block).transpose()
+	}
+
+	async fn blocks_by_number(
+		&self,
+		number: u64,
+	) -> Result<Vec<IndexedBlock<Block::Hash>>, RpcError> {
+		self.database.blocks_by_number(number).await?.into_iter().map(block).collect()
+	}
+
+	async fn extrinsics(
+		&self,
+		block: Block::Hash,
+	) -> Result<Vec<IndexedExtrinsic<Block::Hash>>, RpcError> {
+		self.database
+			.extrinsics(block.as_ref())
+			.await?
+			.into_iter()
+			.map(extrinsic)
+			.collect()
+	}
+
+	async fn extrinsics_by_hash(
+		&self,
+		hash: Block::Hash,
+	) -> Result<Vec<IndexedExtrinsic<Block::Hash>>, RpcError> {
+		self.database
+			.extrinsics_by_hash(hash.as_ref())
+			.await?
+			.into_iter()
+			.map(extrinsic)
+			.collect()
+	}
+
+	async fn events(
+		&self,
+		block: Block::Hash,
+		pallet: Option<String>,
+	) -> Result<Vec<IndexedEvent<Block::Hash>>, RpcError> {
+		self.database
+			.events(block.as_ref(), pallet.as_deref())
+			.await?
+			.into_iter()
+			.map(event)
+			.collect()
+	}
+
+	async fn transfers(
+		&self,
+		account: sp_core::Bytes,
+		limit: Option<u32>,
+	) -> Result<Vec<IndexedTransfer<Block::Hash>>, RpcError> {
+		let limit = limit.unwrap_or(MAX_TRANSFERS).min(MAX_TRANSFERS);
+		self.database
+			.transfers(&account, limit)
+			.await?
+			.into_iter()
+			.map(transfer)
+			.collect()
+	}
+}
@@ -26,6 +26,8 @@ pub mod chain_ops;
 pub mod client;
 pub mod config;
 pub mod error;
+#[cfg(feature = "indexer")]
+pub mod indexer;
 
 mod builder;
 mod metrics;
@@ -56,10 +58,10 @@ use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 
 pub use self::{
 	builder::{
-		build_default_block_downloader, build_default_syncing_engine, build_network,
-		build_network_advanced, build_polkadot_syncing_strategy, gen_rpc_module, init_telemetry,
-		new_client, new_db_backend, new_full_client, new_full_parts, new_full_parts_record_import,
-		new_full_parts_with_genesis_builder, new_wasm_executor,
+		build_default_block_downloader, build_default_syncing_engine, build_light_client,
+		build_network, build_network_advanced, build_polkadot_syncing_strategy, gen_rpc_module,
+		init_telemetry, new_client, new_db_backend, new_full_client, new_full_parts,
+		new_full_parts_record_import, new_full_parts_with_genesis_builder, new_wasm_executor,
 		propagate_transaction_notifications, spawn_tasks, BuildNetworkAdvancedParams,
 		BuildNetworkParams, DefaultSyncingEngineConfig, KeystoreContainer, SpawnTasksParams,
 		TFullBackend, TFullCallExecutor, TFullClient,
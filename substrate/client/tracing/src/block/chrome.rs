@@ -0,0 +1,207 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Export of a traced block in the Chrome trace event format.
+//!
+//! See <https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU> for the
+//! format description.
+
+use std::collections::HashMap;
+
+use rustc_hash::FxHashMap;
+use sp_rpc::tracing::{ChromeArg, ChromeEvent, ChromePhase, ChromeTrace};
+use tracing::span::Id;
+
+use super::{patch_wasm_span, BlockSubscriber, Mark, REQUIRED_EVENT_FIELD};
+use crate::{SpanDatum, TraceEvent, Values};
+
+/// The whole block is executed on a single thread, so all entries share the same ids.
+const PID: u32 = 1;
+const TID: u32 = 1;
+/// Name of the proof size counter.
+const PROOF_SIZE: &str = "proof_size";
+
+/// Build the Chrome trace from the timeline recorded by `subscriber`.
+///
+/// Spans not matching `targets` and events rejected by `keep_event` are left out.
+pub(super) fn build(
+	subscriber: &BlockSubscriber,
+	targets: &str,
+	keep_event: impl Fn(&TraceEvent) -> bool,
+) -> ChromeTrace {
+	let spans: HashMap<Id, SpanDatum> = subscriber
+		.spans
+		.lock()
+		.drain()
+		.filter_map(|(id, s)| patch_wasm_span(s, targets).map(|s| (id, s)))
+		.collect();
+	let events = std::mem::take(&mut *subscriber.events.lock());
+	let timeline = subscriber
+		.timeline
+		.as_ref()
+		.map(|t| std::mem::take(&mut *t.lock()))
+		.unwrap_or_default();
+
+	let mut trace_events = Vec::with_capacity(timeline.len());
+	let mut last_proof_size = None;
+	for entry in timeline {
+		let ts = entry.elapsed.as_micros() as u64;
+		let event = match entry.mark {
+			Mark::Enter(id) => spans.get(&id).map(|span| ChromeEvent {
+				name: span.name.clone(),
+				cat: span.target.clone(),
+				ph: ChromePhase::Begin,
+				ts,
+				pid: PID,
+				tid: TID,
+				s: None,
+				args: args(&span.values),
+			}),
+			Mark::Exit(id) => spans.get(&id).map(|span| ChromeEvent {
+				name: span.name.clone(),
+				cat: span.target.clone(),
+				ph: ChromePhase::End,
+				ts,
+				pid: PID,
+				tid: TID,
+				s: None,
+				args: Default::default(),
+			}),
+			Mark::Event(index) =>
+				events.get(index).filter(|e| keep_event(e)).map(|event| ChromeEvent {
+					name: event
+						.values
+						.string_values
+						.get(REQUIRED_EVENT_FIELD)
+						.cloned()
+						.unwrap_or_else(|| event.name.clone()),
+					cat: event.target.clone(),
+					ph: ChromePhase::Instant,
+					ts,
+					pid: PID,
+					tid: TID,
+					s: Some("t".into()),
+					args: args(&event.values),
+				}),
+		};
+		trace_events.extend(event);
+
+		if let Some(proof_size) = entry.proof_size.filter(|size| Some(*size) != last_proof_size) {
+			last_proof_size = Some(proof_size);
+			trace_events.push(ChromeEvent {
+				name: PROOF_SIZE.into(),
+				cat: PROOF_SIZE.into(),
+				ph: ChromePhase::Counter,
+				ts,
+				pid: PID,
+				tid: TID,
+				s: None,
+				args: [("bytes".to_owned(), ChromeArg::U64(proof_size as u64))]
+					.into_iter()
+					.collect(),
+			});
+		}
+	}
+
+	ChromeTrace { display_time_unit: "ns".into(), trace_events }
+}
+
+fn args(values: &Values) -> FxHashMap<String, ChromeArg> {
+	let bools = values.bool_values.iter().map(|(k, v)| (k.clone(), ChromeArg::Bool(*v)));
+	let i64s = values.i64_values.iter().map(|(k, v)| (k.clone(), ChromeArg::I64(*v)));
+	let u64s = values.u64_values.iter().map(|(k, v)| (k.clone(), ChromeArg::U64(*v)));
+	let strings = values
+		.string_values
+		.iter()
+		.map(|(k, v)| (k.clone(), ChromeArg::String(v.clone())));
+	bools.chain(i64s).chain(u64s).chain(strings).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	};
+	use tracing::{dispatcher, Dispatch};
+
+	fn trace(proof_size: Arc<AtomicUsize>, targets: &str) -> ChromeTrace {
+		let sampler = proof_size.clone();
+		let subscriber = BlockSubscriber::new(targets)
+			.with_timeline(Some(Box::new(move || sampler.load(Ordering::Relaxed))));
+		let dispatch = Dispatch::new(subscriber);
+		dispatcher::with_default(&dispatch, || {
+			let extrinsic = tracing::info_span!(target: "frame", "apply_extrinsic", ext = "0x00");
+			let _enter = extrinsic.enter();
+			{
+				let call = tracing::trace_span!(target: "pallet_balances", "transfer");
+				let _enter = call.enter();
+				proof_size.store(42, Ordering::Relaxed);
+				tracing::trace!(target: "state", method = "Get", key = "0xaa", "storage read");
+			}
+			tracing::trace_span!(target: "other", "ignored").in_scope(|| ());
+		});
+		let subscriber = dispatch.downcast_ref::<BlockSubscriber>().unwrap();
+		build(subscriber, targets, |_| true)
+	}
+
+	#[test]
+	fn spans_are_nested_on_the_timeline() {
+		let trace = trace(Default::default(), "frame,pallet,state");
+		let timeline: Vec<_> = trace
+			.trace_events
+			.iter()
+			.filter(|e| e.ph != ChromePhase::Counter)
+			.map(|e| (e.ph, e.name.as_str()))
+			.collect();
+		assert_eq!(
+			timeline,
+			vec![
+				(ChromePhase::Begin, "apply_extrinsic"),
+				(ChromePhase::Begin, "transfer"),
+				(ChromePhase::Instant, "Get"),
+				(ChromePhase::End, "transfer"),
+				(ChromePhase::End, "apply_extrinsic"),
+			]
+		);
+		assert_eq!(trace.trace_events[0].args.get("ext"), Some(&ChromeArg::String("0x00".into())));
+		assert!(trace.trace_events.windows(2).all(|w| w[0].ts <= w[1].ts));
+	}
+
+	#[test]
+	fn proof_size_is_recorded_on_change() {
+		let trace = trace(Default::default(), "frame,pallet,state");
+		let counters: Vec<_> = trace
+			.trace_events
+			.iter()
+			.filter(|e| e.ph == ChromePhase::Counter)
+			.map(|e| e.args.get("bytes").cloned())
+			.collect();
+		assert_eq!(counters, vec![Some(ChromeArg::U64(0)), Some(ChromeArg::U64(42))]);
+	}
+
+	#[test]
+	fn serializes_to_trace_event_format() {
+		let trace = trace(Default::default(), "frame");
+		let json = serde_json::to_value(&trace).unwrap();
+		assert_eq!(json["displayTimeUnit"], "ns");
+		assert_eq!(json["traceEvents"][0]["ph"], "B");
+		assert_eq!(json["traceEvents"][0]["cat"], "frame");
+		assert!(json["traceEvents"][0].get("s").is_none());
+	}
+}
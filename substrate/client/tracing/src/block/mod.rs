@@ -17,13 +17,15 @@
 
 //! Utilities for tracing block execution
 
+mod chrome;
+
 use std::{
 	collections::HashMap,
 	sync::{
 		atomic::{AtomicU64, Ordering},
 		Arc,
 	},
-	time::Instant,
+	time::{Duration, Instant},
 };
 
 use codec::Encode;
@@ -36,10 +38,10 @@ use tracing::{
 
 use crate::{SpanDatum, TraceEvent, Values};
 use sc_client_api::BlockBackend;
-use sp_api::{Core, Metadata, ProvideRuntimeApi};
+use sp_api::{ApiExt, Core, Metadata, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::hexdisplay::HexDisplay;
-use sp_rpc::tracing::{BlockTrace, Span, TraceBlockResponse};
+use sp_rpc::tracing::{BlockTrace, Span, TraceBlockResponse, TraceFormat};
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, Header},
@@ -68,11 +70,37 @@ pub enum Error {
 	Dispatch(String),
 }
 
+/// Returns the current estimated proof size of the block execution.
+type ProofSizeSampler = Box<dyn Fn() -> usize + Send + Sync>;
+
+/// What happened at a point of the recorded timeline.
+enum Mark {
+	/// The span was entered.
+	Enter(Id),
+	/// The span was exited.
+	Exit(Id),
+	/// The event at the given index of [`BlockSubscriber::events`] was emitted.
+	Event(usize),
+}
+
+/// A point of the recorded timeline.
+struct TimelineEntry {
+	/// Time since the subscriber was created.
+	elapsed: Duration,
+	/// Estimated proof size at this point, if proof recording is enabled.
+	proof_size: Option<usize>,
+	mark: Mark,
+}
+
 struct BlockSubscriber {
 	targets: Vec<(String, Level)>,
 	next_id: AtomicU64,
 	spans: Mutex<HashMap<Id, SpanDatum>>,
 	events: Mutex<Vec<TraceEvent>>,
+	started: Instant,
+	/// Only recorded when the trace is exported as a timeline.
+	timeline: Option<Mutex<Vec<TimelineEntry>>>,
+	proof_size: Option<ProofSizeSampler>,
 }
 
 impl BlockSubscriber {
@@ -87,6 +115,28 @@ impl BlockSubscriber {
 			next_id,
 			spans: Mutex::new(HashMap::new()),
 			events: Mutex::new(Vec::new()),
+			started: Instant::now(),
+			timeline: None,
+			proof_size: None,
+		}
+	}
+
+	/// Also record when spans are entered and exited and when events are emitted, sampling the
+	/// proof size at each of these points if `proof_size` is given.
+	fn with_timeline(mut self, proof_size: Option<ProofSizeSampler>) -> Self {
+		self.timeline = Some(Mutex::new(Vec::new()));
+		self.proof_size = proof_size;
+		self
+	}
+
+	fn mark(&self, mark: Mark) {
+		if let Some(timeline) = &self.timeline {
+			let entry = TimelineEntry {
+				elapsed: self.started.elapsed(),
+				proof_size: self.proof_size.as_ref().map(|sample| sample()),
+				mark,
+			};
+			timeline.lock().push(entry);
 		}
 	}
 }
@@ -148,12 +198,21 @@ impl Subscriber for BlockSubscriber {
 			values,
 			parent_id,
 		};
-		self.events.lock().push(trace_event);
+		let index = {
+			let mut events = self.events.lock();
+			events.push(trace_event);
+			events.len() - 1
+		};
+		self.mark(Mark::Event(index));
 	}
 
-	fn enter(&self, _id: &Id) {}
+	fn enter(&self, id: &Id) {
+		self.mark(Mark::Enter(id.clone()));
+	}
 
-	fn exit(&self, _span: &Id) {}
+	fn exit(&self, span: &Id) {
+		self.mark(Mark::Exit(span.clone()));
+	}
 }
 
 /// Holds a reference to the client in order to execute the given block.
@@ -195,6 +254,14 @@ where
 	/// and filter out events which do not have keys starting with one of the
 	/// prefixes in `Self::storage_keys`.
 	pub fn trace_block(&self) -> TraceBlockResult<TraceBlockResponse> {
+		self.trace_block_as(TraceFormat::Spans)
+	}
+
+	/// Same as [`Self::trace_block`], but returns the trace in the given `format`.
+	///
+	/// With [`TraceFormat::Chrome`] the spans are exported as a timeline of the block
+	/// execution, together with the storage events and the estimated proof size.
+	pub fn trace_block_as(&self, format: TraceFormat) -> TraceBlockResult<TraceBlockResponse> {
 		tracing::debug!(target: "state_tracing", "Tracing block: {}", self.block);
 		// Prepare the block
 		let mut header = self
@@ -215,7 +282,17 @@ where
 		let block = Block::new(header, extrinsics);
 
 		let targets = if let Some(t) = &self.targets { t } else { DEFAULT_TARGETS };
-		let block_subscriber = BlockSubscriber::new(targets);
+		let mut runtime_api = self.client.runtime_api();
+		let block_subscriber = match format {
+			TraceFormat::Spans => BlockSubscriber::new(targets),
+			TraceFormat::Chrome => {
+				runtime_api.record_proof();
+				let proof_size = runtime_api.proof_recorder().map(|recorder| {
+					Box::new(move || recorder.estimate_encoded_size()) as ProofSizeSampler
+				});
+				BlockSubscriber::new(targets).with_timeline(proof_size)
+			},
+		};
 		let dispatch = Dispatch::new(block_subscriber);
 
 		{
@@ -228,7 +305,7 @@ where
 			if let Err(e) = dispatcher::with_default(&dispatch, || {
 				let span = tracing::info_span!(target: TRACE_TARGET, "trace_block");
 				let _enter = span.enter();
-				self.client.runtime_api().execute_block(parent_hash, block)
+				runtime_api.execute_block(parent_hash, block)
 			}) {
 				return Err(Error::Dispatch(format!(
 					"Failed to collect traces and execute block: {}",
@@ -242,6 +319,13 @@ where
 				"Cannot downcast Dispatch to BlockSubscriber after tracing block".to_string(),
 			)
 		})?;
+
+		if format == TraceFormat::Chrome {
+			let trace = chrome::build(block_subscriber, targets, |e| self.keep_event(e));
+			tracing::debug!(target: "state_tracing", "Captured {} timeline entries", trace.trace_events.len());
+			return Ok(TraceBlockResponse::ChromeTrace(trace))
+		}
+
 		let spans: Vec<_> = block_subscriber
 			.spans
 			.lock()
@@ -253,18 +337,7 @@ where
 			.events
 			.lock()
 			.drain(..)
-			.filter(|e| {
-				self.storage_keys
-					.as_ref()
-					.map(|keys| event_values_filter(e, "key", keys))
-					.unwrap_or(false)
-			})
-			.filter(|e| {
-				self.methods
-					.as_ref()
-					.map(|methods| event_values_filter(e, "method", methods))
-					.unwrap_or(false)
-			})
+			.filter(|e| self.keep_event(e))
 			.map(|s| s.into())
 			.collect();
 		tracing::debug!(target: "state_tracing", "Captured {} spans and {} events", spans.len(), events.len());
@@ -279,6 +352,18 @@ where
 			events,
 		}))
 	}
+
+	/// Whether the event matches both `Self::storage_keys` and `Self::methods`.
+	fn keep_event(&self, event: &TraceEvent) -> bool {
+		self.storage_keys
+			.as_ref()
+			.map(|keys| event_values_filter(event, "key", keys))
+			.unwrap_or(false) &&
+			self.methods
+				.as_ref()
+				.map(|methods| event_values_filter(event, "method", methods))
+				.unwrap_or(false)
+	}
 }
 
 fn event_values_filter(event: &TraceEvent, filter_kind: &str, values: &str) -> bool {
@@ -298,7 +383,12 @@ fn event_values_filter(event: &TraceEvent, filter_kind: &str, values: &str) -> b
 // (normally they would be in the static metadata assembled at compile time). Here, if a special
 // WASM `name` or `target` key is found in the `values` we remove it and put the key value pair in
 // the span's metadata, making it consistent with spans that come from native code.
-fn patch_and_filter(mut span: SpanDatum, targets: &str) -> Option<Span> {
+fn patch_and_filter(span: SpanDatum, targets: &str) -> Option<Span> {
+	patch_wasm_span(span, targets).map(Into::into)
+}
+
+/// Same as [`patch_and_filter`], but keeps the recorded values of the span.
+fn patch_wasm_span(mut span: SpanDatum, targets: &str) -> Option<SpanDatum> {
 	if span.name == WASM_TRACE_IDENTIFIER {
 		span.values.bool_values.insert("wasm".to_owned(), true);
 		if let Some(n) = span.values.string_values.remove(WASM_NAME_KEY) {
@@ -311,7 +401,7 @@ fn patch_and_filter(mut span: SpanDatum, targets: &str) -> Option<Span> {
 			return None
 		}
 	}
-	Some(span.into())
+	Some(span)
 }
 
 /// Check if a `target` matches any `targets` by prefix
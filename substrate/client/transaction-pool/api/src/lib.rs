@@ -236,6 +236,32 @@ pub trait InPoolTransaction {
 	fn provides(&self) -> &[TransactionTag];
 	/// Return a flag indicating if the transaction should be propagated to other peers.
 	fn is_propagable(&self) -> bool;
+	/// Get the source of the transaction, if known.
+	fn source(&self) -> Option<TransactionSource> {
+		None
+	}
+}
+
+/// The reason of a transaction being dropped from the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DropReason<Hash> {
+	/// Transaction was replaced by the given transaction (e.g. because of higher priority).
+	Usurped(Hash),
+	/// Transaction was dropped because of the pool limits being enforced.
+	LimitsEnforced,
+	/// Transaction was dropped because of being invalid.
+	Invalid,
+}
+
+/// A transaction that was recently dropped from the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DroppedTransactionInfo<Hash> {
+	/// Hash of the dropped transaction.
+	pub hash: Hash,
+	/// Reason of the transaction being dropped.
+	pub reason: DropReason<Hash>,
+	/// Seconds since the UNIX epoch at which the transaction was dropped.
+	pub dropped_at: u64,
 }
 
 /// Transaction pool interface.
@@ -347,6 +373,23 @@ pub trait TransactionPool: Send + Sync {
 		at: <Self::Block as BlockT>::Hash,
 		timeout: std::time::Duration,
 	) -> Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>;
+
+	// *** introspection
+	/// Returns the hashes of the blocks at which the pool keeps a view containing the given
+	/// transaction, either in its ready or future queue.
+	///
+	/// Returns `None` if the pool does not maintain per-block views.
+	fn transaction_views(&self, _hash: &TxHash<Self>) -> Option<Vec<BlockHash<Self>>> {
+		None
+	}
+
+	/// Returns the most recently dropped or invalidated transactions, oldest first.
+	///
+	/// The pool only keeps a bounded number of entries, and may not track dropped transactions at
+	/// all, in which case an empty list is returned.
+	fn recently_dropped(&self) -> Vec<DroppedTransactionInfo<TxHash<Self>>> {
+		Vec::new()
+	}
 }
 
 /// An iterator of ready transactions.
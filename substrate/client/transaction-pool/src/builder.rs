@@ -19,7 +19,10 @@
 //! Utility for building substrate transaction pool trait object.
 
 use crate::{
-	common::api::FullChainApi,
+	common::{
+		api::FullChainApi,
+		journal::{TransactionJournal, TransactionJournalMode},
+	},
 	fork_aware_txpool::ForkAwareTxPool as ForkAwareFullPool,
 	graph::{base_pool::Transaction, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options},
 	single_state_txpool::BasicPool as SingleStateFullPool,
@@ -29,7 +32,7 @@ use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{LocalTransactionPool, MaintainedTransactionPool};
 use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::traits::Block as BlockT;
-use std::{marker::PhantomData, sync::Arc, time::Duration};
+use std::{marker::PhantomData, path::PathBuf, sync::Arc, time::Duration};
 
 /// The type of transaction pool.
 #[derive(Debug, Clone)]
@@ -45,11 +48,18 @@ pub enum TransactionPoolType {
 pub struct TransactionPoolOptions {
 	txpool_type: TransactionPoolType,
 	options: Options,
+	journal_mode: Option<TransactionJournalMode>,
+	journal_path: Option<PathBuf>,
 }
 
 impl Default for TransactionPoolOptions {
 	fn default() -> Self {
-		Self { txpool_type: TransactionPoolType::SingleState, options: Default::default() }
+		Self {
+			txpool_type: TransactionPoolType::SingleState,
+			options: Default::default(),
+			journal_mode: None,
+			journal_path: None,
+		}
 	}
 }
 
@@ -81,7 +91,21 @@ impl TransactionPoolOptions {
 			Duration::from_secs(30 * 60)
 		};
 
-		TransactionPoolOptions { options, txpool_type }
+		TransactionPoolOptions { options, txpool_type, journal_mode: None, journal_path: None }
+	}
+
+	/// Journals the pending transactions of `mode`, to re-import them after a restart.
+	///
+	/// The journal is only written if its path is set with [`Self::with_journal_path`].
+	pub fn with_journal(mut self, mode: TransactionJournalMode) -> Self {
+		self.journal_mode = Some(mode);
+		self
+	}
+
+	/// Sets the path of the transaction journal.
+	pub fn with_journal_path(mut self, path: PathBuf) -> Self {
+		self.journal_path = Some(path);
+		self
 	}
 
 	/// Creates predefined options for benchmarking
@@ -100,6 +124,8 @@ impl TransactionPoolOptions {
 				ban_time: Duration::from_secs(30 * 60),
 			},
 			txpool_type: TransactionPoolType::SingleState,
+			journal_mode: None,
+			journal_path: None,
 		}
 	}
 }
@@ -231,7 +257,15 @@ where
 			future = ?self.options.options.future,
 			"Creating transaction pool"
 		);
-		TransactionPoolWrapper::<Block, Client>(match self.options.txpool_type {
+		let journal = match (self.options.journal_mode, self.options.journal_path) {
+			(Some(mode), Some(path)) => {
+				let limit = self.options.options.ready.count + self.options.options.future.count;
+				Some(TransactionJournal::open(path, mode, limit))
+			},
+			_ => None,
+		};
+		let pool: Box<dyn FullClientTransactionPool<Block, Client>> = match self.options.txpool_type
+		{
 			TransactionPoolType::SingleState => Box::new(SingleStateFullPool::new_full(
 				self.options.options,
 				self.is_validator,
@@ -246,6 +280,7 @@ where
 				self.spawner,
 				self.client,
 			)),
-		})
+		};
+		TransactionPoolWrapper::<Block, Client>::new(pool, journal)
 	}
 }
@@ -0,0 +1,378 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Journal of pending transactions, persisting them across node restarts.
+//!
+//! The journal is a snapshot of the transactions in the pool that were submitted from the
+//! selected [`TransactionJournalMode`] sources. It is written periodically on finalization, and
+//! when the pool is dropped on shutdown. On startup, the journaled transactions are submitted
+//! again at the first new best block, which revalidates them and applies the limits of the pool.
+//!
+//! The journal is written by a background thread, so that disk latency doesn't stall the
+//! maintenance of the pool. It is replaced atomically, and loading it is tolerant to corruption: an
+//! unreadable journal is ignored, and transactions that can't be decoded anymore (e.g. after a
+//! runtime upgrade) are skipped.
+
+use crate::LOG_TARGET;
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sp_runtime::{traits::Block as BlockT, transaction_validity::TransactionSource};
+use std::{
+	fs, io,
+	path::{Path, PathBuf},
+	sync::mpsc,
+	thread,
+	time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+/// Name of the journal file in the node's configuration directory.
+pub const TRANSACTION_JOURNAL_FILE: &str = "txpool_journal";
+
+/// Minimum interval between two writes of the journal on finalization.
+const JOURNAL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Version of the format of the journal.
+const VERSION: u8 = 1;
+
+/// Which transactions are journaled and re-imported after a restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionJournalMode {
+	/// Transactions with [`TransactionSource::Local`].
+	Local,
+	/// Transactions with [`TransactionSource::Local`] or [`TransactionSource::External`].
+	LocalAndExternal,
+}
+
+impl TransactionJournalMode {
+	/// Whether transactions from `source` are journaled.
+	fn includes(&self, source: TransactionSource) -> bool {
+		match source {
+			TransactionSource::Local => true,
+			TransactionSource::External => *self == Self::LocalAndExternal,
+			TransactionSource::InBlock => false,
+		}
+	}
+}
+
+/// A journaled transaction.
+#[derive(Encode, Decode)]
+struct JournalEntry {
+	/// Source the transaction was submitted from.
+	source: TransactionSource,
+	/// The encoded transaction.
+	///
+	/// Stored as bytes so that a transaction which can't be decoded anymore doesn't invalidate
+	/// the remaining ones.
+	extrinsic: Vec<u8>,
+}
+
+/// Journaled transactions loaded on startup, along with their source.
+pub(crate) type JournaledTransactions<Block> =
+	Vec<(TransactionSource, <Block as BlockT>::Extrinsic)>;
+
+/// An encoded journal, along with the number of transactions in it.
+type JournalWrite = (usize, Vec<u8>);
+
+/// Journal of pending transactions.
+pub(crate) struct TransactionJournal<Block: BlockT> {
+	path: PathBuf,
+	mode: TransactionJournalMode,
+	/// The journaled transactions that were not re-imported yet.
+	///
+	/// The journal is not written until they are, not to lose them.
+	pending: Mutex<Option<JournaledTransactions<Block>>>,
+	/// When the journal was last written.
+	last_write: Mutex<Instant>,
+	/// Sends the encoded journal to the writer thread.
+	///
+	/// `None` if the thread couldn't be spawned, in which case the journal is written in place.
+	writer: Option<mpsc::Sender<JournalWrite>>,
+	/// The writer thread, joined on drop to complete the last write before shutdown.
+	writer_thread: Option<thread::JoinHandle<()>>,
+}
+
+impl<Block: BlockT> TransactionJournal<Block> {
+	/// Open the journal at `path`, loading at most `limit` transactions to re-import.
+	pub fn open(path: PathBuf, mode: TransactionJournalMode, limit: usize) -> Self {
+		let pending = load::<Block>(&path, mode, limit);
+		info!(
+			target: LOG_TARGET,
+			count = pending.len(),
+			path = %path.display(),
+			"Loaded transaction journal"
+		);
+
+		let (writer, receiver) = mpsc::channel();
+		let writer_path = path.clone();
+		let (writer, writer_thread) = match thread::Builder::new()
+			.name("txpool-journal".into())
+			.spawn(move || run_writer(&writer_path, receiver))
+		{
+			Ok(writer_thread) => (Some(writer), Some(writer_thread)),
+			Err(error) => {
+				warn!(target: LOG_TARGET, %error, "Failed to spawn transaction journal writer");
+				(None, None)
+			},
+		};
+
+		Self {
+			path,
+			mode,
+			pending: Mutex::new(Some(pending)),
+			last_write: Mutex::new(Instant::now()),
+			writer,
+			writer_thread,
+		}
+	}
+
+	/// Take the journaled transactions to re-import, if not taken yet.
+	pub fn take_pending(&self) -> Option<JournaledTransactions<Block>> {
+		self.pending.lock().take()
+	}
+
+	/// Whether the journal is due to be written, i.e. the journaled transactions were re-imported
+	/// and the last write is older than [`JOURNAL_INTERVAL`].
+	pub fn is_due(&self) -> bool {
+		self.pending.lock().is_none() && self.last_write.lock().elapsed() >= JOURNAL_INTERVAL
+	}
+
+	/// Replace the journal with the given pool transactions, keeping those from the journaled
+	/// sources.
+	///
+	/// The transactions are encoded in place and written by the writer thread. Does nothing until
+	/// the journaled transactions were re-imported.
+	pub fn write<'a>(
+		&self,
+		transactions: impl Iterator<Item = (TransactionSource, &'a Block::Extrinsic)>,
+	) {
+		if self.pending.lock().is_some() {
+			return
+		}
+		*self.last_write.lock() = Instant::now();
+
+		let entries = transactions
+			.filter(|(source, _)| self.mode.includes(*source))
+			.map(|(source, extrinsic)| JournalEntry { source, extrinsic: extrinsic.encode() })
+			.collect::<Vec<_>>();
+		let write = (entries.len(), (VERSION, entries).encode());
+
+		match &self.writer {
+			Some(writer) =>
+				if let Err(mpsc::SendError(write)) = writer.send(write) {
+					write_journal(&self.path, write);
+				},
+			None => write_journal(&self.path, write),
+		}
+	}
+}
+
+impl<Block: BlockT> Drop for TransactionJournal<Block> {
+	fn drop(&mut self) {
+		// Closing the channel lets the writer thread finish the queued write and exit.
+		self.writer.take();
+		if let Some(writer_thread) = self.writer_thread.take() {
+			if writer_thread.join().is_err() {
+				warn!(target: LOG_TARGET, "Transaction journal writer panicked");
+			}
+		}
+	}
+}
+
+/// Write the journals received from `receiver` to `path`, until the channel is closed.
+fn run_writer(path: &Path, receiver: mpsc::Receiver<JournalWrite>) {
+	while let Ok(mut write) = receiver.recv() {
+		// Only the latest state of the pool is worth writing.
+		while let Ok(newer) = receiver.try_recv() {
+			write = newer;
+		}
+		write_journal(path, write);
+	}
+}
+
+/// Replace the journal at `path` with the encoded `write`.
+fn write_journal(path: &Path, (count, data): JournalWrite) {
+	match replace(path, &data) {
+		Ok(()) => debug!(target: LOG_TARGET, count, "Written transaction journal"),
+		Err(error) => warn!(
+			target: LOG_TARGET,
+			%error,
+			path = %path.display(),
+			"Failed to write transaction journal"
+		),
+	}
+}
+
+/// Load at most `limit` transactions of `mode` from the journal at `path`.
+fn load<Block: BlockT>(
+	path: &Path,
+	mode: TransactionJournalMode,
+	limit: usize,
+) -> JournaledTransactions<Block> {
+	let data = match fs::read(path) {
+		Ok(data) => data,
+		Err(error) if error.kind() == io::ErrorKind::NotFound => return Vec::new(),
+		Err(error) => {
+			warn!(target: LOG_TARGET, %error, path = %path.display(), "Failed to read transaction journal");
+			return Vec::new()
+		},
+	};
+
+	let entries = match <(u8, Vec<JournalEntry>)>::decode(&mut &data[..]) {
+		Ok((VERSION, entries)) => entries,
+		Ok((version, _)) => {
+			warn!(target: LOG_TARGET, version, "Ignoring transaction journal of unsupported version");
+			return Vec::new()
+		},
+		Err(error) => {
+			warn!(target: LOG_TARGET, %error, path = %path.display(), "Ignoring corrupt transaction journal");
+			return Vec::new()
+		},
+	};
+
+	let total = entries.len();
+	let transactions = entries
+		.into_iter()
+		.filter(|entry| mode.includes(entry.source))
+		.filter_map(|entry| {
+			Block::Extrinsic::decode(&mut &entry.extrinsic[..])
+				.ok()
+				.map(|extrinsic| (entry.source, extrinsic))
+		})
+		.take(limit)
+		.collect::<Vec<_>>();
+	if transactions.len() < total.min(limit) {
+		debug!(
+			target: LOG_TARGET,
+			skipped = total.min(limit) - transactions.len(),
+			"Skipped journaled transactions of other sources or that can't be decoded"
+		);
+	}
+
+	transactions
+}
+
+/// Atomically replace the file at `path` with `data`.
+fn replace(path: &Path, data: &[u8]) -> io::Result<()> {
+	if let Some(parent) = path.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	let tmp_path = path.with_extension("tmp");
+	fs::write(&tmp_path, data)?;
+	fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use substrate_test_runtime::{Block, Extrinsic, ExtrinsicBuilder};
+
+	fn journal(path: &Path, mode: TransactionJournalMode) -> TransactionJournal<Block> {
+		let journal = TransactionJournal::open(path.to_path_buf(), mode, 10);
+		assert!(journal.take_pending().is_some());
+		journal
+	}
+
+	fn remark(n: u8) -> Extrinsic {
+		ExtrinsicBuilder::new_include_data(vec![n]).build()
+	}
+
+	#[test]
+	fn journal_round_trip_works() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(TRANSACTION_JOURNAL_FILE);
+
+		let (local, external, in_block) = (remark(1), remark(2), remark(3));
+		journal(&path, TransactionJournalMode::Local).write(
+			[
+				(TransactionSource::Local, &local),
+				(TransactionSource::External, &external),
+				(TransactionSource::InBlock, &in_block),
+			]
+			.into_iter(),
+		);
+
+		let journal = TransactionJournal::<Block>::open(path, TransactionJournalMode::Local, 10);
+		assert_eq!(journal.take_pending(), Some(vec![(TransactionSource::Local, local)]));
+		assert_eq!(journal.take_pending(), None);
+	}
+
+	#[test]
+	fn journal_is_not_written_before_reimport() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(TRANSACTION_JOURNAL_FILE);
+
+		let mode = TransactionJournalMode::LocalAndExternal;
+		let transactions =
+			vec![(TransactionSource::Local, remark(1)), (TransactionSource::External, remark(2))];
+		journal(&path, mode).write(transactions.iter().map(|(source, xt)| (*source, xt)));
+
+		// The journaled transactions are not overwritten until taken.
+		let journal = TransactionJournal::<Block>::open(path.clone(), mode, 10);
+		journal.write(std::iter::empty());
+		assert!(!journal.is_due());
+		assert_eq!(journal.take_pending(), Some(transactions.clone()));
+
+		// The limit is applied on load.
+		let journal = TransactionJournal::<Block>::open(path, mode, 1);
+		assert_eq!(journal.take_pending(), Some(transactions[..1].to_vec()));
+	}
+
+	#[test]
+	fn latest_write_is_flushed_on_drop() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(TRANSACTION_JOURNAL_FILE);
+		let mode = TransactionJournalMode::Local;
+
+		let journal = journal(&path, mode);
+		for n in 0..10 {
+			let xt = remark(n);
+			journal.write(std::iter::once((TransactionSource::Local, &xt)));
+		}
+		drop(journal);
+
+		assert_eq!(
+			TransactionJournal::<Block>::open(path, mode, 10).take_pending(),
+			Some(vec![(TransactionSource::Local, remark(9))])
+		);
+	}
+
+	#[test]
+	fn corrupt_journal_is_ignored() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join(TRANSACTION_JOURNAL_FILE);
+		let mode = TransactionJournalMode::Local;
+
+		fs::write(&path, [VERSION, 0xff, 0xff]).unwrap();
+		assert_eq!(
+			TransactionJournal::<Block>::open(path.clone(), mode, 10).take_pending(),
+			Some(vec![])
+		);
+
+		// Transactions that can't be decoded are skipped.
+		let entries = vec![
+			JournalEntry { source: TransactionSource::Local, extrinsic: vec![0xff] },
+			JournalEntry { source: TransactionSource::Local, extrinsic: remark(1).encode() },
+		];
+		fs::write(&path, (VERSION, entries).encode()).unwrap();
+		assert_eq!(
+			TransactionJournal::<Block>::open(path, mode, 10).take_pending(),
+			Some(vec![(TransactionSource::Local, remark(1))])
+		);
+	}
+}
@@ -57,7 +57,7 @@ use futures::{
 use parking_lot::Mutex;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{
-	error::Error as TxPoolApiError, ChainEvent, ImportNotificationStream,
+	error::Error as TxPoolApiError, ChainEvent, DroppedTransactionInfo, ImportNotificationStream,
 	MaintainedTransactionPool, PoolStatus, TransactionFor, TransactionPool, TransactionSource,
 	TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
 };
@@ -1114,6 +1114,14 @@ where
 	) -> ReadyIteratorFor<ChainApi> {
 		self.ready_at_with_timeout_internal(at, timeout).await
 	}
+
+	fn transaction_views(&self, tx_hash: &TxHash<Self>) -> Option<Vec<Block::Hash>> {
+		Some(self.view_store.transaction_views(tx_hash))
+	}
+
+	fn recently_dropped(&self) -> Vec<DroppedTransactionInfo<TxHash<Self>>> {
+		self.view_store.listener.recently_dropped()
+	}
 }
 
 impl<ChainApi, Block> sc_transaction_pool_api::LocalTransactionPool
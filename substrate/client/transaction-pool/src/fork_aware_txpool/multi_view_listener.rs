@@ -27,14 +27,17 @@ use crate::{
 	LOG_TARGET,
 };
 use futures::{Future, FutureExt, Stream, StreamExt};
-use parking_lot::RwLock;
-use sc_transaction_pool_api::{TransactionStatus, TransactionStatusStream, TxIndex};
+use parking_lot::{Mutex, RwLock};
+use sc_transaction_pool_api::{
+	DropReason, DroppedTransactionInfo, TransactionStatus, TransactionStatusStream, TxIndex,
+};
 use sc_utils::mpsc;
 use sp_runtime::traits::Block as BlockT;
 use std::{
-	collections::{hash_map::Entry, HashMap, HashSet},
+	collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
 	pin::Pin,
 	sync::Arc,
+	time::{SystemTime, UNIX_EPOCH},
 };
 use tokio_stream::StreamMap;
 use tracing::trace;
@@ -255,8 +258,16 @@ pub struct MultiViewListener<ChainApi: graph::ChainApi> {
 	/// shared with listener's task.
 	external_controllers:
 		Arc<RwLock<HashMap<ExtrinsicHash<ChainApi>, Controller<ExternalWatcherCommand<ChainApi>>>>>,
+
+	/// The most recently dropped or invalidated transactions, oldest first.
+	///
+	/// Bounded by [`RECENTLY_DROPPED_LIMIT`], exposed for the introspection of the pool.
+	recently_dropped: Mutex<VecDeque<DroppedTransactionInfo<ExtrinsicHash<ChainApi>>>>,
 }
 
+/// The maximum number of entries kept in [`MultiViewListener::recently_dropped`].
+const RECENTLY_DROPPED_LIMIT: usize = 256;
+
 /// A type representing a `MultiViewListener` task. For more details refer to
 /// [`MultiViewListener::task`].
 pub type MultiViewListenerTask = Pin<Box<dyn Future<Output = ()> + Send>>;
@@ -551,7 +562,10 @@ where
 		);
 		let task = Self::task(external_controllers.clone(), rx, events_metrics_collector);
 
-		(Self { external_controllers, controller: tx }, task.boxed())
+		(
+			Self { external_controllers, controller: tx, recently_dropped: Default::default() },
+			task.boxed(),
+		)
 	}
 
 	/// Creates an external tstream of events for given transaction.
@@ -687,6 +701,7 @@ where
 	pub(crate) fn transactions_invalidated(&self, invalid_hashes: &[ExtrinsicHash<ChainApi>]) {
 		log_xt_trace!(target: LOG_TARGET, invalid_hashes, "transactions_invalidated");
 		for tx_hash in invalid_hashes {
+			self.record_dropped(*tx_hash, DropReason::Invalid);
 			if let Err(error) =
 				self.controller.unbounded_send(ControllerCommand::new_invalidated(*tx_hash))
 			{
@@ -730,6 +745,14 @@ where
 	pub(crate) fn transaction_dropped(&self, dropped: DroppedTransaction<ExtrinsicHash<ChainApi>>) {
 		let DroppedTransaction { tx_hash, reason } = dropped;
 		trace!(target: LOG_TARGET, ?tx_hash, ?reason, "transaction_dropped");
+		self.record_dropped(
+			tx_hash,
+			match &reason {
+				DroppedReason::Usurped(by) => DropReason::Usurped(*by),
+				DroppedReason::LimitsEnforced => DropReason::LimitsEnforced,
+				DroppedReason::Invalid => DropReason::Invalid,
+			},
+		);
 		if let Err(error) =
 			self.controller.unbounded_send(ControllerCommand::new_dropped(tx_hash, reason))
 		{
@@ -742,6 +765,28 @@ where
 		}
 	}
 
+	/// Records the transaction as recently dropped, evicting the oldest entry if needed.
+	fn record_dropped(
+		&self,
+		hash: ExtrinsicHash<ChainApi>,
+		reason: DropReason<ExtrinsicHash<ChainApi>>,
+	) {
+		let dropped_at = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_secs())
+			.unwrap_or_default();
+		let mut recently_dropped = self.recently_dropped.lock();
+		if recently_dropped.len() >= RECENTLY_DROPPED_LIMIT {
+			recently_dropped.pop_front();
+		}
+		recently_dropped.push_back(DroppedTransactionInfo { hash, reason, dropped_at });
+	}
+
+	/// Returns the most recently dropped or invalidated transactions, oldest first.
+	pub(crate) fn recently_dropped(&self) -> Vec<DroppedTransactionInfo<ExtrinsicHash<ChainApi>>> {
+		self.recently_dropped.lock().iter().cloned().collect()
+	}
+
 	/// Send `Finalized` event for given transaction at given block.
 	///
 	/// This will trigger `Finalized` event to the external watcher.
@@ -1101,4 +1146,33 @@ mod tests {
 		let _ = terminate_listener.send(());
 		let _ = listener_task.await.unwrap();
 	}
+
+	#[tokio::test]
+	async fn recently_dropped_is_bounded() {
+		sp_tracing::try_init_simple();
+		let (listener, terminate_listener, listener_task) = create_multi_view_listener();
+
+		let usurped = H256::repeat_byte(0x0a);
+		let by = H256::repeat_byte(0x0b);
+		listener.transaction_dropped(DroppedTransaction::new_usurped(usurped, by));
+		listener.transactions_invalidated(&[H256::repeat_byte(0x0c)]);
+
+		let dropped = listener.recently_dropped();
+		assert_eq!(dropped.len(), 2);
+		assert_eq!((dropped[0].hash, &dropped[0].reason), (usurped, &DropReason::Usurped(by)));
+		assert_eq!(dropped[1].reason, DropReason::Invalid);
+
+		for i in 0..RECENTLY_DROPPED_LIMIT as u64 {
+			listener.transaction_dropped(DroppedTransaction::new_enforced_by_limts(
+				H256::from_low_u64_be(i),
+			));
+		}
+		let dropped = listener.recently_dropped();
+		assert_eq!(dropped.len(), RECENTLY_DROPPED_LIMIT);
+		assert_eq!(dropped[0].hash, H256::from_low_u64_be(0));
+		assert!(dropped.iter().all(|d| d.reason == DropReason::LimitsEnforced));
+
+		let _ = terminate_listener.send(());
+		let _ = listener_task.await.unwrap();
+	}
 }
@@ -524,6 +524,19 @@ where
 			.and_then(|v| v.pool.validated_pool().ready_by_hash(tx_hash))
 	}
 
+	/// Returns the hashes of the blocks of all views (active and inactive) that contain the given
+	/// transaction, either in the ready or future queue.
+	pub(super) fn transaction_views(&self, tx_hash: &ExtrinsicHash<ChainApi>) -> Vec<Block::Hash> {
+		let active_views = self.active_views.read();
+		let inactive_views = self.inactive_views.read();
+		active_views
+			.iter()
+			.chain(inactive_views.iter())
+			.filter(|(_, view)| view.pool.validated_pool().pool.read().is_imported(tx_hash))
+			.map(|(at, _)| *at)
+			.collect()
+	}
+
 	/// Inserts new view into the view store.
 	///
 	/// All the views associated with the blocks which are on enacted path (including common
@@ -21,23 +21,26 @@
 
 use crate::{
 	builder::FullClientTransactionPool,
+	common::journal::TransactionJournal,
 	graph::{base_pool::Transaction, ExtrinsicFor, ExtrinsicHash},
-	ChainApi, FullChainApi, ReadyIteratorFor,
+	ChainApi, FullChainApi, ReadyIteratorFor, LOG_TARGET,
 };
 use async_trait::async_trait;
 use sc_transaction_pool_api::{
-	ChainEvent, ImportNotificationStream, LocalTransactionFor, LocalTransactionPool,
-	MaintainedTransactionPool, PoolStatus, ReadyTransactions, TransactionFor, TransactionPool,
-	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
+	ChainEvent, DroppedTransactionInfo, ImportNotificationStream, LocalTransactionFor,
+	LocalTransactionPool, MaintainedTransactionPool, PoolStatus, ReadyTransactions, TransactionFor,
+	TransactionPool, TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
 };
 use sp_runtime::traits::Block as BlockT;
 use std::{collections::HashMap, pin::Pin, sync::Arc};
 
 /// The wrapper for actual object providing implementation of TransactionPool.
 ///
-/// This wraps actual implementation of the TransactionPool, e.g. fork-aware or single-state.
+/// This wraps actual implementation of the TransactionPool, e.g. fork-aware or single-state, and
+/// keeps the transaction journal, if enabled.
 pub struct TransactionPoolWrapper<Block, Client>(
 	pub Box<dyn FullClientTransactionPool<Block, Client>>,
+	Option<TransactionJournal<Block>>,
 )
 where
 	Block: BlockT,
@@ -49,6 +52,96 @@ where
 		+ 'static,
 	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>;
 
+impl<Block, Client> TransactionPoolWrapper<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	/// Wraps `pool`, journaling its transactions if `journal` is `Some`.
+	pub(crate) fn new(
+		pool: Box<dyn FullClientTransactionPool<Block, Client>>,
+		journal: Option<TransactionJournal<Block>>,
+	) -> Self {
+		Self(pool, journal)
+	}
+
+	/// Re-import the journaled transactions at `at`, if not done yet.
+	async fn reimport_journal(&self, journal: &TransactionJournal<Block>, at: Block::Hash) {
+		let Some(transactions) = journal.take_pending() else { return };
+		if transactions.is_empty() {
+			return
+		}
+
+		let count = transactions.len();
+		let mut imported = 0;
+		for source in [TransactionSource::Local, TransactionSource::External] {
+			let xts = transactions
+				.iter()
+				.filter(|(tx_source, _)| *tx_source == source)
+				.map(|(_, xt)| xt.clone())
+				.collect::<Vec<_>>();
+			if xts.is_empty() {
+				continue
+			}
+
+			match self.0.submit_at(at, source, xts).await {
+				Ok(results) => imported += results.iter().filter(|result| result.is_ok()).count(),
+				Err(error) => tracing::warn!(
+					target: LOG_TARGET,
+					?error,
+					"Failed to re-import journaled transactions"
+				),
+			}
+		}
+
+		tracing::info!(
+			target: LOG_TARGET,
+			count,
+			imported,
+			?at,
+			"Re-imported journaled transactions"
+		);
+	}
+
+	/// Write the ready and future transactions of the pool to `journal`.
+	fn write_journal(&self, journal: &TransactionJournal<Block>) {
+		let ready = self.0.ready().collect::<Vec<_>>();
+		let futures = self.0.futures();
+		journal.write(
+			ready
+				.iter()
+				.map(|tx| &**tx)
+				.chain(futures.iter())
+				.map(|tx| (tx.source.source, &*tx.data)),
+		);
+	}
+}
+
+impl<Block, Client> Drop for TransactionPoolWrapper<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	fn drop(&mut self) {
+		// The pool is dropped when the node shuts down.
+		if let Some(journal) = &self.1 {
+			self.write_journal(journal);
+		}
+	}
+}
+
 #[async_trait]
 impl<Block, Client> TransactionPool for TransactionPoolWrapper<Block, Client>
 where
@@ -146,6 +239,14 @@ where
 	) -> ReadyIteratorFor<FullChainApi<Client, Block>> {
 		self.0.ready_at_with_timeout(at, timeout).await
 	}
+
+	fn transaction_views(&self, hash: &TxHash<Self>) -> Option<Vec<Block::Hash>> {
+		self.0.transaction_views(hash)
+	}
+
+	fn recently_dropped(&self) -> Vec<DroppedTransactionInfo<TxHash<Self>>> {
+		self.0.recently_dropped()
+	}
 }
 
 #[async_trait]
@@ -161,7 +262,21 @@ where
 	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
 {
 	async fn maintain(&self, event: ChainEvent<Self::Block>) {
+		let best_block = match event {
+			ChainEvent::NewBestBlock { hash, .. } => Some(hash),
+			ChainEvent::Finalized { .. } => None,
+		};
+		let is_finalized = best_block.is_none();
+
 		self.0.maintain(event).await;
+
+		let Some(journal) = &self.1 else { return };
+		if let Some(at) = best_block {
+			self.reimport_journal(journal, at).await;
+		}
+		if is_finalized && journal.is_due() {
+			self.write_journal(journal);
+		}
 	}
 }
 
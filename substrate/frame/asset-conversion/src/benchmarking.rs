@@ -28,8 +28,9 @@ use frame_support::{
 		fungible::NativeOrWithId,
 		fungibles::{Create, Inspect, Mutate, Refund},
 	},
+	BoundedVec,
 };
-use frame_system::RawOrigin as SystemOrigin;
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin as SystemOrigin};
 use sp_core::Get;
 
 /// Benchmark Helper
@@ -134,7 +135,7 @@ fn mint_setup_fee_asset<T: Config>(
 	));
 }
 
-/// Creates a pool for a given asset pair.
+/// Creates a pool priced along `curve` for a given asset pair.
 ///
 /// This action mints the necessary amounts of the given assets for the `caller` to provide initial
 /// liquidity. It returns the LP token ID along with a pair of amounts sufficient for the pool's
@@ -143,6 +144,7 @@ fn create_asset_and_pool<T: Config>(
 	caller: &T::AccountId,
 	asset1: &T::AssetKind,
 	asset2: &T::AssetKind,
+	curve: CurveKind,
 ) -> (T::PoolAssetId, T::Balance, T::Balance)
 where
 	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
@@ -157,15 +159,50 @@ where
 
 	mint_setup_fee_asset::<T>(caller, asset1, asset2, &lp_token);
 
-	assert_ok!(AssetConversion::<T>::create_pool(
+	assert_ok!(AssetConversion::<T>::create_pool_with_curve(
 		SystemOrigin::Signed(caller.clone()).into(),
 		Box::new(asset1.clone()),
-		Box::new(asset2.clone())
+		Box::new(asset2.clone()),
+		curve,
 	));
 
 	(lp_token, liquidity1, liquidity2)
 }
 
+/// Fills the price history of the pool of `asset1` and `asset2` from the current block on.
+///
+/// Returns the block from which the next interaction with the pool advances its accumulator and
+/// records an observation, evicting the oldest one.
+fn fill_price_observations<T: Config>(
+	asset1: &T::AssetKind,
+	asset2: &T::AssetKind,
+) -> BlockNumberFor<T> {
+	let pool_id = T::PoolLocator::pool_id(asset1, asset2).unwrap();
+	let period = T::ObservationPeriod::get();
+	let mut block = frame_system::Pallet::<T>::block_number();
+	let mut observations = BoundedVec::<_, T::MaxObservations>::new();
+	while !observations.is_full() {
+		assert_ok!(observations.try_push(Observation {
+			block,
+			price1_cumulative: 0,
+			price2_cumulative: 0
+		}));
+		block += period;
+	}
+	PriceObservations::<T>::insert(&pool_id, observations);
+	PriceAccumulators::<T>::insert(
+		&pool_id,
+		PriceAccumulator {
+			block,
+			price1_cumulative: 0,
+			price2_cumulative: 0,
+			reserve1: T::Balance::one(),
+			reserve2: T::Balance::one(),
+		},
+	);
+	block + period
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -206,7 +243,9 @@ mod benchmarks {
 
 		create_fee_asset::<T>(&caller);
 		let (lp_token, liquidity1, liquidity2) =
-			create_asset_and_pool::<T>(&caller, &asset1, &asset2);
+			create_asset_and_pool::<T>(&caller, &asset1, &asset2, CurveKind::ConstantProduct);
+		let due = fill_price_observations::<T>(&asset1, &asset2);
+		frame_system::Pallet::<T>::set_block_number(due);
 
 		#[extrinsic_call]
 		_(
@@ -235,7 +274,7 @@ mod benchmarks {
 
 		create_fee_asset::<T>(&caller);
 		let (lp_token, liquidity1, liquidity2) =
-			create_asset_and_pool::<T>(&caller, &asset1, &asset2);
+			create_asset_and_pool::<T>(&caller, &asset1, &asset2, CurveKind::ConstantProduct);
 
 		let remove_lp_amount = T::Balance::one();
 
@@ -251,6 +290,8 @@ mod benchmarks {
 		));
 		let total_supply =
 			<T::PoolAssets as Inspect<T::AccountId>>::total_issuance(lp_token.clone());
+		let due = fill_price_observations::<T>(&asset1, &asset2);
+		frame_system::Pallet::<T>::set_block_number(due);
 
 		#[extrinsic_call]
 		_(
@@ -274,6 +315,7 @@ mod benchmarks {
 
 		let caller: T::AccountId = whitelisted_caller();
 		create_fee_asset::<T>(&caller);
+		let mut due = frame_system::Pallet::<T>::block_number();
 		for n in 1..n {
 			let (asset1, asset2) = T::BenchmarkHelper::create_pair(n - 1, n);
 			swap_amount = swap_amount + T::Balance::one();
@@ -283,7 +325,8 @@ mod benchmarks {
 				path.push(Box::new(asset2.clone()));
 			}
 
-			let (_, liquidity1, liquidity2) = create_asset_and_pool::<T>(&caller, &asset1, &asset2);
+			let (_, liquidity1, liquidity2) =
+				create_asset_and_pool::<T>(&caller, &asset1, &asset2, CurveKind::ConstantProduct);
 
 			assert_ok!(AssetConversion::<T>::add_liquidity(
 				SystemOrigin::Signed(caller.clone()).into(),
@@ -295,7 +338,9 @@ mod benchmarks {
 				T::Balance::zero(),
 				caller.clone(),
 			));
+			due = fill_price_observations::<T>(&asset1, &asset2);
 		}
+		frame_system::Pallet::<T>::set_block_number(due);
 
 		let asset_in = *path.first().unwrap().clone();
 		assert_ok!(T::Assets::mint_into(
@@ -326,6 +371,7 @@ mod benchmarks {
 
 		let caller: T::AccountId = whitelisted_caller();
 		create_fee_asset::<T>(&caller);
+		let mut due = frame_system::Pallet::<T>::block_number();
 		for n in 1..n {
 			let (asset1, asset2) = T::BenchmarkHelper::create_pair(n - 1, n);
 			max_swap_amount = max_swap_amount + T::Balance::one() + T::Balance::one();
@@ -335,7 +381,8 @@ mod benchmarks {
 				path.push(Box::new(asset2.clone()));
 			}
 
-			let (_, liquidity1, liquidity2) = create_asset_and_pool::<T>(&caller, &asset1, &asset2);
+			let (_, liquidity1, liquidity2) =
+				create_asset_and_pool::<T>(&caller, &asset1, &asset2, CurveKind::ConstantProduct);
 
 			assert_ok!(AssetConversion::<T>::add_liquidity(
 				SystemOrigin::Signed(caller.clone()).into(),
@@ -347,7 +394,9 @@ mod benchmarks {
 				T::Balance::zero(),
 				caller.clone(),
 			));
+			due = fill_price_observations::<T>(&asset1, &asset2);
 		}
+		frame_system::Pallet::<T>::set_block_number(due);
 
 		let asset_in = *path.first().unwrap().clone();
 		let asset_out = *path.last().unwrap().clone();
@@ -410,5 +459,106 @@ mod benchmarks {
 		assert_last_event::<T>(Event::Touched { pool_id, who: caller }.into());
 	}
 
+	#[benchmark]
+	fn create_pool_with_curve() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		create_asset::<T>(&caller, &asset1, T::Assets::minimum_balance(asset1.clone()), true);
+		create_asset::<T>(&caller, &asset2, T::Assets::minimum_balance(asset2.clone()), true);
+
+		let lp_token = AssetConversion::<T>::get_next_pool_asset_id();
+		create_fee_asset::<T>(&caller);
+		mint_setup_fee_asset::<T>(&caller, &asset1, &asset2, &lp_token);
+		let curve =
+			CurveKind::StableSwap { amplification: T::MaxAmplification::get(), rates: (1, 1) };
+
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			curve,
+		);
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_last_event::<T>(Event::CurveSet { pool_id, curve }.into());
+	}
+
+	#[benchmark]
+	fn ramp_amplification() -> Result<(), BenchmarkError> {
+		let origin = T::AmplificationOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+
+		create_fee_asset::<T>(&caller);
+		create_asset_and_pool::<T>(
+			&caller,
+			&asset1,
+			&asset2,
+			CurveKind::StableSwap { amplification: 1, rates: (1, 1) },
+		);
+		let future = MAX_AMPLIFICATION_CHANGE.min(T::MaxAmplification::get());
+		let now = frame_system::Pallet::<T>::block_number();
+		let future_block = now + T::MinAmplificationRampDuration::get();
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			future,
+			future_block,
+		);
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_last_event::<T>(
+			Event::AmplificationRampStarted {
+				pool_id,
+				initial: 1,
+				future,
+				initial_block: now,
+				future_block,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn stop_amplification_ramp() -> Result<(), BenchmarkError> {
+		let origin = T::AmplificationOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+
+		create_fee_asset::<T>(&caller);
+		create_asset_and_pool::<T>(
+			&caller,
+			&asset1,
+			&asset2,
+			CurveKind::StableSwap { amplification: 1, rates: (1, 1) },
+		);
+		let future = MAX_AMPLIFICATION_CHANGE.min(T::MaxAmplification::get());
+		let now = frame_system::Pallet::<T>::block_number();
+		assert_ok!(AssetConversion::<T>::ramp_amplification(
+			origin.clone(),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			future,
+			now + T::MinAmplificationRampDuration::get(),
+		));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(asset1.clone()), Box::new(asset2.clone()));
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_eq!(
+			PoolCurves::<T>::get(pool_id),
+			Curve::StableSwap { amplification: Amplification::constant(1, now), rates: (1, 1) }
+		);
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(AssetConversion, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -18,12 +18,16 @@
 //! # Substrate Asset Conversion pallet
 //!
 //! Substrate Asset Conversion pallet based on the [Uniswap V2](https://github.com/Uniswap/v2-core) logic.
+//! Pools may alternatively price their swaps along the [Curve StableSwap](https://curve.fi/files/stableswap-paper.pdf)
+//! invariant, which offers much lower slippage for pairs of assets expected to trade near parity.
 //!
 //! ## Overview
 //!
 //! This pallet allows you to:
 //!
 //!  - [create a liquidity pool](`Pallet::create_pool()`) for 2 assets
+//!  - [create a liquidity pool with a chosen curve](`Pallet::create_pool_with_curve()`), such as
+//!    StableSwap, and [ramp its amplification](`Pallet::ramp_amplification()`) through governance
 //!  - [provide the liquidity](`Pallet::add_liquidity()`) and receive back an LP token
 //!  - [exchange the LP token back to assets](`Pallet::remove_liquidity()`)
 //!  - [swap a specific amount of assets for another](`Pallet::swap_exact_tokens_for_tokens()`) if
@@ -34,6 +38,9 @@
 //!    a runtime call endpoint
 //!  - [query the size of a liquidity pool](`AssetConversionApi::get_reserves`) via a runtime api
 //!    endpoint.
+//!  - [query the time weighted average price](`TimeWeightedPriceApi::time_weighted_price`) of a
+//!    pool, which other pallets may consume through [`TimeWeightedPrice`] or the [`TwapConversion`]
+//!    balance converter.
 //!
 //! The `quote_price_exact_tokens_for_tokens` and `quote_price_tokens_for_exact_tokens` functions
 //! both take a path parameter of the route to take. If you want to swap from native asset to
@@ -58,6 +65,7 @@
 mod benchmarking;
 #[cfg(test)]
 mod mock;
+mod oracle;
 mod swap;
 #[cfg(test)]
 mod tests;
@@ -65,6 +73,7 @@ mod types;
 pub mod weights;
 #[cfg(feature = "runtime-benchmarks")]
 pub use benchmarking::{BenchmarkHelper, NativeOrWithIdFactory};
+pub use oracle::*;
 pub use pallet::*;
 pub use swap::*;
 pub use types::*;
@@ -94,9 +103,15 @@ use sp_runtime::{
 		CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Ensure, IntegerSquareRoot, MaybeDisplay,
 		One, TrailingZeroInput, Zero,
 	},
-	DispatchError, Saturating, TokenError, TransactionOutcome,
+	DispatchError, FixedU128, Saturating, TokenError, TransactionOutcome,
 };
 
+/// The maximum factor by which a single ramp may change the amplification coefficient of a pool.
+const MAX_AMPLIFICATION_CHANGE: u32 = 10;
+
+/// The maximum number of Newton iterations when solving the StableSwap invariant.
+const MAX_STABLE_SWAP_ITERATIONS: u32 = 255;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -121,6 +136,7 @@ pub mod pallet {
 
 		/// A type used for calculations concerning the `Balance` type to avoid possible overflows.
 		type HigherPrecisionBalance: IntegerSquareRoot
+			+ Clone
 			+ One
 			+ Ensure
 			+ Unsigned
@@ -186,6 +202,28 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSwapPathLength: Get<u32>;
 
+		/// Origin allowed to ramp the amplification coefficient of StableSwap pools.
+		type AmplificationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum amplification coefficient of a StableSwap pool.
+		#[pallet::constant]
+		type MaxAmplification: Get<u32>;
+
+		/// The minimum number of blocks a ramp of the amplification coefficient must span.
+		#[pallet::constant]
+		type MinAmplificationRampDuration: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of price observations kept for each pool.
+		#[pallet::constant]
+		type MaxObservations: Get<u32>;
+
+		/// The minimum number of blocks between two price observations of a pool.
+		///
+		/// Together with `MaxObservations`, bounds the longest window a time weighted average
+		/// price can be queried over.
+		#[pallet::constant]
+		type ObservationPeriod: Get<BlockNumberFor<Self>>;
+
 		/// The pallet's id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -204,6 +242,32 @@ pub mod pallet {
 	pub type Pools<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::PoolId, PoolInfo<T::PoolAssetId>, OptionQuery>;
 
+	/// The pricing curve of each pool. Pools without an entry use the constant product curve.
+	#[pallet::storage]
+	pub type PoolCurves<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, Curve<BlockNumberFor<T>>, ValueQuery>;
+
+	/// The cumulative prices of each pool as of the last block the pool was interacted with,
+	/// along with the reserves the pool was left with.
+	#[pallet::storage]
+	pub type PriceAccumulators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		PriceAccumulator<BlockNumberFor<T>, T::Balance>,
+		OptionQuery,
+	>;
+
+	/// Snapshots of the cumulative prices of each pool, oldest first.
+	#[pallet::storage]
+	pub type PriceObservations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		BoundedVec<Observation<BlockNumberFor<T>>, T::MaxObservations>,
+		ValueQuery,
+	>;
+
 	/// Stores the `PoolAssetId` that is going to be used for the next lp token.
 	/// This gets incremented whenever a new lp pool is created.
 	#[pallet::storage]
@@ -296,6 +360,33 @@ pub mod pallet {
 			/// The account initiating the touch.
 			who: T::AccountId,
 		},
+		/// A pool has been created with the given pricing curve.
+		CurveSet {
+			/// The ID of the pool.
+			pool_id: T::PoolId,
+			/// The pricing curve of the pool.
+			curve: CurveKind,
+		},
+		/// The amplification coefficient of a StableSwap pool started ramping.
+		AmplificationRampStarted {
+			/// The ID of the pool.
+			pool_id: T::PoolId,
+			/// The coefficient at the start of the ramp.
+			initial: u32,
+			/// The coefficient at the end of the ramp.
+			future: u32,
+			/// The block the ramp starts at.
+			initial_block: BlockNumberFor<T>,
+			/// The block the ramp ends at.
+			future_block: BlockNumberFor<T>,
+		},
+		/// The amplification coefficient ramp of a StableSwap pool has been stopped.
+		AmplificationRampStopped {
+			/// The ID of the pool.
+			pool_id: T::PoolId,
+			/// The coefficient the pool is left with.
+			amplification: u32,
+		},
 	}
 
 	#[pallet::error]
@@ -349,6 +440,18 @@ pub mod pallet {
 		IncorrectPoolAssetId,
 		/// The destination account cannot exist with the swapped funds.
 		BelowMinimum,
+		/// The amplification coefficient must be non-zero and not above `MaxAmplification`.
+		InvalidAmplification,
+		/// The pool does not use the StableSwap curve.
+		NotStableSwapPool,
+		/// The amplification ramp ends before `MinAmplificationRampDuration` has elapsed.
+		AmplificationRampTooShort,
+		/// The amplification coefficient can't change by more than a factor of ten in one ramp.
+		AmplificationChangeTooLarge,
+		/// The StableSwap invariant could not be solved for the given reserves.
+		InvariantNotConverged,
+		/// The rates of a StableSwap pool must be non-zero and fit the balance type.
+		InvalidRate,
 	}
 
 	#[pallet::hooks]
@@ -358,6 +461,10 @@ pub mod pallet {
 				T::MaxSwapPathLength::get() > 1,
 				"the `MaxSwapPathLength` should be greater than 1",
 			);
+			assert!(
+				T::MaxAmplification::get() > 0,
+				"the `MaxAmplification` should be greater than 0",
+			);
 		}
 	}
 
@@ -376,51 +483,7 @@ pub mod pallet {
 			asset2: Box<T::AssetKind>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
-
-			// prepare pool_id
-			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
-				.map_err(|_| Error::<T>::InvalidAssetPair)?;
-			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
-
-			let pool_account =
-				T::PoolLocator::address(&pool_id).map_err(|_| Error::<T>::InvalidAssetPair)?;
-
-			// pay the setup fee
-			let fee =
-				Self::withdraw(T::PoolSetupFeeAsset::get(), &sender, T::PoolSetupFee::get(), true)?;
-			T::PoolSetupFeeTarget::on_unbalanced(fee);
-
-			if T::Assets::should_touch(*asset1.clone(), &pool_account) {
-				T::Assets::touch(*asset1, &pool_account, &sender)?
-			};
-
-			if T::Assets::should_touch(*asset2.clone(), &pool_account) {
-				T::Assets::touch(*asset2, &pool_account, &sender)?
-			};
-
-			let lp_token = NextPoolAssetId::<T>::get()
-				.or(T::PoolAssetId::initial_value())
-				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
-
-			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
-			if T::PoolAssets::should_touch(lp_token.clone(), &pool_account) {
-				T::PoolAssets::touch(lp_token.clone(), &pool_account, &sender)?
-			};
-
-			let pool_info = PoolInfo { lp_token: lp_token.clone() };
-			Pools::<T>::insert(pool_id.clone(), pool_info);
-
-			Self::deposit_event(Event::PoolCreated {
-				creator: sender,
-				pool_id,
-				pool_account,
-				lp_token,
-			});
-
-			Ok(())
+			Self::do_create_pool(sender, asset1, asset2, CurveKind::ConstantProduct)
 		}
 
 		/// Provide liquidity into the pool of `asset1` and `asset2`.
@@ -505,8 +568,9 @@ pub mod pallet {
 				Error::<T>::AmountTwoLessThanMinimal
 			);
 
-			T::Assets::transfer(*asset1, &sender, &pool_account, amount1, Preserve)?;
-			T::Assets::transfer(*asset2, &sender, &pool_account, amount2, Preserve)?;
+			T::Assets::transfer(*asset1.clone(), &sender, &pool_account, amount1, Preserve)?;
+			T::Assets::transfer(*asset2.clone(), &sender, &pool_account, amount2, Preserve)?;
+			Self::update_price_oracle(&asset1, &asset2);
 
 			let total_supply = T::PoolAssets::total_issuance(pool.lp_token.clone());
 
@@ -608,8 +672,9 @@ pub mod pallet {
 				Polite,
 			)?;
 
-			T::Assets::transfer(*asset1, &pool_account, &withdraw_to, amount1, Expendable)?;
-			T::Assets::transfer(*asset2, &pool_account, &withdraw_to, amount2, Expendable)?;
+			T::Assets::transfer(*asset1.clone(), &pool_account, &withdraw_to, amount1, Expendable)?;
+			T::Assets::transfer(*asset2.clone(), &pool_account, &withdraw_to, amount2, Expendable)?;
+			Self::update_price_oracle(&asset1, &asset2);
 
 			Self::deposit_event(Event::LiquidityRemoved {
 				who: sender,
@@ -723,9 +788,203 @@ pub mod pallet {
 			Self::deposit_event(Event::Touched { pool_id, who });
 			Ok(Some(T::WeightInfo::touch(refunds_number)).into())
 		}
+
+		/// Creates an empty liquidity pool that prices its swaps along the given `curve`, and an
+		/// associated new `lp_token` asset.
+		///
+		/// Behaves like [`Pallet::create_pool`] otherwise. A StableSwap pool's amplification
+		/// coefficient must be non-zero and not above [`Config::MaxAmplification`], and the rates
+		/// of its assets must be non-zero.
+		///
+		/// Emits `PoolCreated` event when successful, followed by `CurveSet` for any curve other
+		/// than the constant product one.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::create_pool_with_curve())]
+		pub fn create_pool_with_curve(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			curve: CurveKind,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_create_pool(sender, asset1, asset2, curve)
+		}
+
+		/// Ramp the amplification coefficient of a StableSwap pool linearly from its current value
+		/// to `future_amplification`, reached at `future_block`.
+		///
+		/// The origin must be [`Config::AmplificationOrigin`]. The ramp must span at least
+		/// [`Config::MinAmplificationRampDuration`] blocks and may change the coefficient by at
+		/// most a factor of ten. Starting a new ramp replaces any ramp in progress.
+		///
+		/// Emits `AmplificationRampStarted` event when successful.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::ramp_amplification())]
+		pub fn ramp_amplification(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			future_amplification: u32,
+			future_block: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::AmplificationOrigin::ensure_origin(origin)?;
+			ensure!(
+				future_amplification > 0 && future_amplification <= T::MaxAmplification::get(),
+				Error::<T>::InvalidAmplification
+			);
+
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(Pools::<T>::contains_key(&pool_id), Error::<T>::PoolNotFound);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				future_block >= now.saturating_add(T::MinAmplificationRampDuration::get()),
+				Error::<T>::AmplificationRampTooShort
+			);
+
+			Self::close_price_accumulator(&asset1, &asset2);
+			let initial = PoolCurves::<T>::try_mutate(&pool_id, |curve| {
+				let Curve::StableSwap { amplification, .. } = curve else {
+					return Err(Error::<T>::NotStableSwapPool)
+				};
+				let initial = amplification.at(now);
+				ensure!(
+					future_amplification <= initial.saturating_mul(MAX_AMPLIFICATION_CHANGE) &&
+						initial <= future_amplification.saturating_mul(MAX_AMPLIFICATION_CHANGE),
+					Error::<T>::AmplificationChangeTooLarge
+				);
+				*amplification = Amplification {
+					initial,
+					future: future_amplification,
+					initial_block: now,
+					future_block,
+				};
+				Ok(initial)
+			})?;
+
+			Self::deposit_event(Event::AmplificationRampStarted {
+				pool_id,
+				initial,
+				future: future_amplification,
+				initial_block: now,
+				future_block,
+			});
+			Ok(())
+		}
+
+		/// Stop the amplification coefficient ramp of a StableSwap pool, fixing the coefficient at
+		/// its current value.
+		///
+		/// The origin must be [`Config::AmplificationOrigin`].
+		///
+		/// Emits `AmplificationRampStopped` event when successful.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::stop_amplification_ramp())]
+		pub fn stop_amplification_ramp(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+		) -> DispatchResult {
+			T::AmplificationOrigin::ensure_origin(origin)?;
+
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(Pools::<T>::contains_key(&pool_id), Error::<T>::PoolNotFound);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			Self::close_price_accumulator(&asset1, &asset2);
+			let amplification = PoolCurves::<T>::try_mutate(&pool_id, |curve| {
+				let Curve::StableSwap { amplification, .. } = curve else {
+					return Err(Error::<T>::NotStableSwapPool)
+				};
+				let current = amplification.at(now);
+				*amplification = Amplification::constant(current, now);
+				Ok(current)
+			})?;
+
+			Self::deposit_event(Event::AmplificationRampStopped { pool_id, amplification });
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Create an empty liquidity pool of `asset1` and `asset2` priced along `curve`, paying
+		/// the setup fee from `sender`.
+		pub(crate) fn do_create_pool(
+			sender: T::AccountId,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			curve: CurveKind,
+		) -> DispatchResult {
+			ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
+			if let CurveKind::StableSwap { amplification, rates: (rate1, rate2) } = curve {
+				ensure!(
+					amplification > 0 && amplification <= T::MaxAmplification::get(),
+					Error::<T>::InvalidAmplification
+				);
+				Self::stable_swap_rate(rate1)?;
+				Self::stable_swap_rate(rate2)?;
+			}
+			let rates_order = Self::is_encoding_order(&asset1, &asset2);
+			// prepare pool_id
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
+
+			let pool_account =
+				T::PoolLocator::address(&pool_id).map_err(|_| Error::<T>::InvalidAssetPair)?;
+
+			// pay the setup fee
+			let fee =
+				Self::withdraw(T::PoolSetupFeeAsset::get(), &sender, T::PoolSetupFee::get(), true)?;
+			T::PoolSetupFeeTarget::on_unbalanced(fee);
+
+			if T::Assets::should_touch(*asset1.clone(), &pool_account) {
+				T::Assets::touch(*asset1, &pool_account, &sender)?
+			};
+
+			if T::Assets::should_touch(*asset2.clone(), &pool_account) {
+				T::Assets::touch(*asset2, &pool_account, &sender)?
+			};
+
+			let lp_token = NextPoolAssetId::<T>::get()
+				.or(T::PoolAssetId::initial_value())
+				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
+
+			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
+			if T::PoolAssets::should_touch(lp_token.clone(), &pool_account) {
+				T::PoolAssets::touch(lp_token.clone(), &pool_account, &sender)?
+			};
+
+			let pool_info = PoolInfo { lp_token: lp_token.clone() };
+			Pools::<T>::insert(pool_id.clone(), pool_info);
+			if let CurveKind::StableSwap { amplification, rates: (rate1, rate2) } = curve {
+				let now = frame_system::Pallet::<T>::block_number();
+				PoolCurves::<T>::insert(
+					pool_id.clone(),
+					Curve::StableSwap {
+						amplification: Amplification::constant(amplification, now),
+						rates: if rates_order { (rate1, rate2) } else { (rate2, rate1) },
+					},
+				);
+			}
+
+			Self::deposit_event(Event::PoolCreated {
+				creator: sender,
+				pool_id: pool_id.clone(),
+				pool_account,
+				lp_token,
+			});
+			if curve != CurveKind::ConstantProduct {
+				Self::deposit_event(Event::CurveSet { pool_id, curve });
+			}
+
+			Ok(())
+		}
+
 		/// Swap exactly `amount_in` of asset `path[0]` for asset `path[1]`.
 		/// If an `amount_out_min` is specified, it will return an error if it is unable to acquire
 		/// the amount desired.
@@ -1000,6 +1259,10 @@ pub mod pallet {
 			T::Assets::resolve(&pool_to, credit_in)
 				.map_err(|c| (c, Error::<T>::BelowMinimum.into()))?;
 
+			for pair in path.windows(2) {
+				Self::update_price_oracle(&pair[0].0, &pair[1].0);
+			}
+
 			Ok(credit_out)
 		}
 
@@ -1066,8 +1329,10 @@ pub mod pallet {
 					},
 				};
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let curve = Self::get_curve(asset1, &asset2)?;
 				balance_path.push((asset2, amount_in));
-				amount_in = Self::get_amount_in(&amount_in, &reserve_in, &reserve_out)?;
+				amount_in =
+					Self::get_curve_amount_in(&curve, &amount_in, &reserve_in, &reserve_out)?;
 			}
 			balance_path.reverse();
 
@@ -1092,8 +1357,10 @@ pub mod pallet {
 					},
 				};
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let curve = Self::get_curve(&asset1, asset2)?;
 				balance_path.push((asset1, amount_out));
-				amount_out = Self::get_amount_out(&amount_out, &reserve_in, &reserve_out)?;
+				amount_out =
+					Self::get_curve_amount_out(&curve, &amount_out, &reserve_in, &reserve_out)?;
 			}
 			Ok(balance_path)
 		}
@@ -1106,14 +1373,26 @@ pub mod pallet {
 			include_fee: bool,
 		) -> Option<T::Balance> {
 			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let curve = Self::get_curve(&asset1, &asset2).ok()?;
 
 			let balance1 = Self::get_balance(&pool_account, asset1);
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_out(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance1, &balance2).ok()
+				match (include_fee, curve) {
+					(true, curve) =>
+						Self::get_curve_amount_out(&curve, &amount, &balance1, &balance2).ok(),
+					(false, Curve::ConstantProduct) =>
+						Self::quote(&amount, &balance1, &balance2).ok(),
+					(false, Curve::StableSwap { amplification, rates }) => {
+						let (price, unit) = Self::stable_swap_marginal_price(
+							&balance1,
+							&balance2,
+							amplification.at(frame_system::Pallet::<T>::block_number()),
+							rates,
+						)
+						.ok()?;
+						Self::mul_div(&amount, &price, &unit).ok()
+					},
 				}
 			} else {
 				None
@@ -1128,14 +1407,26 @@ pub mod pallet {
 			include_fee: bool,
 		) -> Option<T::Balance> {
 			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let curve = Self::get_curve(&asset1, &asset2).ok()?;
 
 			let balance1 = Self::get_balance(&pool_account, asset1);
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_in(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance2, &balance1).ok()
+				match (include_fee, curve) {
+					(true, curve) =>
+						Self::get_curve_amount_in(&curve, &amount, &balance1, &balance2).ok(),
+					(false, Curve::ConstantProduct) =>
+						Self::quote(&amount, &balance2, &balance1).ok(),
+					(false, Curve::StableSwap { amplification, rates }) => {
+						let (price, unit) = Self::stable_swap_marginal_price(
+							&balance1,
+							&balance2,
+							amplification.at(frame_system::Pallet::<T>::block_number()),
+							rates,
+						)
+						.ok()?;
+						Self::mul_div(&amount, &unit, &price).ok()
+					},
 				}
 			} else {
 				None
@@ -1260,6 +1551,317 @@ pub mod pallet {
 			result.try_into().map_err(|_| Error::<T>::Overflow)
 		}
 
+		/// Returns the pricing curve of the pool of `asset1` and `asset2`.
+		///
+		/// The rates of a StableSwap curve are returned in the order of `asset1` and `asset2`.
+		pub fn get_curve(
+			asset1: &T::AssetKind,
+			asset2: &T::AssetKind,
+		) -> Result<Curve<BlockNumberFor<T>>, Error<T>> {
+			let pool_id = T::PoolLocator::pool_id(asset1, asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			Ok(match PoolCurves::<T>::get(pool_id) {
+				Curve::StableSwap { amplification, rates: (rate1, rate2) }
+					if !Self::is_encoding_order(asset1, asset2) =>
+					Curve::StableSwap { amplification, rates: (rate2, rate1) },
+				curve => curve,
+			})
+		}
+
+		/// Whether `asset1` comes first in the order of the assets' encodings.
+		///
+		/// The order doesn't depend on the order a pool is interacted with, so it is the one the
+		/// rates of a StableSwap pool and the prices of the oracle are kept in.
+		pub(crate) fn is_encoding_order(asset1: &T::AssetKind, asset2: &T::AssetKind) -> bool {
+			asset1.using_encoded(|a| asset2.using_encoded(|b| a <= b))
+		}
+
+		/// Calculates amount out along the given pool `curve`, including the LP fee.
+		pub fn get_curve_amount_out(
+			curve: &Curve<BlockNumberFor<T>>,
+			amount_in: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, Error<T>> {
+			match curve {
+				Curve::ConstantProduct => Self::get_amount_out(amount_in, reserve_in, reserve_out),
+				Curve::StableSwap { amplification, rates } => Self::get_stable_swap_amount_out(
+					amount_in,
+					reserve_in,
+					reserve_out,
+					amplification.at(frame_system::Pallet::<T>::block_number()),
+					*rates,
+					T::LPFee::get(),
+				),
+			}
+		}
+
+		/// Calculates amount in along the given pool `curve`, including the LP fee.
+		pub fn get_curve_amount_in(
+			curve: &Curve<BlockNumberFor<T>>,
+			amount_out: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, Error<T>> {
+			match curve {
+				Curve::ConstantProduct => Self::get_amount_in(amount_out, reserve_in, reserve_out),
+				Curve::StableSwap { amplification, rates } => Self::get_stable_swap_amount_in(
+					amount_out,
+					reserve_in,
+					reserve_out,
+					amplification.at(frame_system::Pallet::<T>::block_number()),
+					*rates,
+					T::LPFee::get(),
+				),
+			}
+		}
+
+		/// Calculates amount out of a StableSwap pool.
+		///
+		/// Given an input amount of an asset, pair reserves, the amplification coefficient, the
+		/// rates of the input and the output asset and the LP fee in 10ths of a percent, returns
+		/// the maximum output amount of the other asset.
+		pub fn get_stable_swap_amount_out(
+			amount_in: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+			amplification: u32,
+			(rate_in, rate_out): (u128, u128),
+			lp_fee: u32,
+		) -> Result<T::Balance, Error<T>> {
+			let amount_in = T::HigherPrecisionBalance::from(*amount_in);
+			let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
+			let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
+
+			if reserve_in.is_zero() || reserve_out.is_zero() {
+				return Err(Error::<T>::ZeroLiquidity)
+			}
+
+			let rate_in = Self::stable_swap_rate(rate_in)?;
+			let rate_out = Self::stable_swap_rate(rate_out)?;
+			let reserve_in = reserve_in.checked_mul(&rate_in).ok_or(Error::<T>::Overflow)?;
+			let reserve_out = reserve_out.checked_mul(&rate_out).ok_or(Error::<T>::Overflow)?;
+
+			let ann = Self::stable_swap_ann(amplification)?;
+			let invariant = Self::stable_swap_invariant(&reserve_in, &reserve_out, &ann)?;
+
+			let amount_in_with_fee = amount_in
+				.checked_mul(&(T::HigherPrecisionBalance::from(1000u32) - lp_fee.into()))
+				.ok_or(Error::<T>::Overflow)?
+				.checked_div(&1000u32.into())
+				.ok_or(Error::<T>::Overflow)?
+				.checked_mul(&rate_in)
+				.ok_or(Error::<T>::Overflow)?;
+
+			let new_reserve_in =
+				reserve_in.checked_add(&amount_in_with_fee).ok_or(Error::<T>::Overflow)?;
+			let new_reserve_out = Self::stable_swap_reserve(&new_reserve_in, &invariant, &ann)?;
+
+			// one unit is kept back to make up for the rounding of the invariant.
+			let result = reserve_out
+				.checked_sub(&new_reserve_out)
+				.and_then(|r| r.checked_sub(&One::one()))
+				.unwrap_or_else(Zero::zero)
+				.checked_div(&rate_out)
+				.ok_or(Error::<T>::Overflow)?;
+
+			result.try_into().map_err(|_| Error::<T>::Overflow)
+		}
+
+		/// Calculates amount in of a StableSwap pool.
+		///
+		/// Given an output amount of an asset, pair reserves, the amplification coefficient, the
+		/// rates of the input and the output asset and the LP fee in 10ths of a percent, returns
+		/// a required input amount of the other asset.
+		pub fn get_stable_swap_amount_in(
+			amount_out: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+			amplification: u32,
+			(rate_in, rate_out): (u128, u128),
+			lp_fee: u32,
+		) -> Result<T::Balance, Error<T>> {
+			let amount_out = T::HigherPrecisionBalance::from(*amount_out);
+			let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
+			let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
+
+			if reserve_in.is_zero() || reserve_out.is_zero() {
+				Err(Error::<T>::ZeroLiquidity)?
+			}
+
+			if amount_out >= reserve_out {
+				Err(Error::<T>::AmountOutTooHigh)?
+			}
+
+			let rate_in = Self::stable_swap_rate(rate_in)?;
+			let rate_out = Self::stable_swap_rate(rate_out)?;
+			let reserve_in = reserve_in.checked_mul(&rate_in).ok_or(Error::<T>::Overflow)?;
+			let reserve_out = reserve_out.checked_mul(&rate_out).ok_or(Error::<T>::Overflow)?;
+			let amount_out = amount_out.checked_mul(&rate_out).ok_or(Error::<T>::Overflow)?;
+
+			let ann = Self::stable_swap_ann(amplification)?;
+			let invariant = Self::stable_swap_invariant(&reserve_in, &reserve_out, &ann)?;
+
+			let new_reserve_out =
+				reserve_out.checked_sub(&amount_out).ok_or(Error::<T>::Overflow)?;
+			let new_reserve_in = Self::stable_swap_reserve(&new_reserve_out, &invariant, &ann)?;
+
+			// one unit is added to make up for the rounding of the invariant, and the amount is
+			// rounded up when scaled back by the rate of the input asset.
+			let numerator = new_reserve_in
+				.checked_sub(&reserve_in)
+				.unwrap_or_else(Zero::zero)
+				.checked_add(&rate_in)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_div(&rate_in)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_mul(&1000u32.into())
+				.ok_or(Error::<T>::Overflow)?;
+
+			let denominator = T::HigherPrecisionBalance::from(1000u32) - lp_fee.into();
+
+			let result = numerator
+				.checked_div(&denominator)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_add(&One::one())
+				.ok_or(Error::<T>::Overflow)?;
+
+			result.try_into().map_err(|_| Error::<T>::Overflow)
+		}
+
+		/// Returns the rate of an asset of a StableSwap pool, which must be non-zero and fit the
+		/// balance type.
+		pub(crate) fn stable_swap_rate(rate: u128) -> Result<T::HigherPrecisionBalance, Error<T>> {
+			let rate = T::Balance::try_from(rate).map_err(|_| Error::<T>::InvalidRate)?;
+			ensure!(!rate.is_zero(), Error::<T>::InvalidRate);
+			Ok(rate.into())
+		}
+
+		/// Returns `A * n^n` for a pool of `n = 2` assets with the amplification coefficient `A`.
+		pub(crate) fn stable_swap_ann(
+			amplification: u32,
+		) -> Result<T::HigherPrecisionBalance, Error<T>> {
+			ensure!(amplification > 0, Error::<T>::InvalidAmplification);
+			T::HigherPrecisionBalance::from(amplification)
+				.checked_mul(&4u32.into())
+				.ok_or(Error::<T>::Overflow)
+		}
+
+		/// Solves the StableSwap invariant `D` for the reserves `x` and `y`, i.e.
+		/// `Ann * (x + y) + D = Ann * D + D^3 / (4 * x * y)`.
+		pub(crate) fn stable_swap_invariant(
+			x: &T::HigherPrecisionBalance,
+			y: &T::HigherPrecisionBalance,
+			ann: &T::HigherPrecisionBalance,
+		) -> Result<T::HigherPrecisionBalance, Error<T>> {
+			let sum = x.checked_add(y).ok_or(Error::<T>::Overflow)?;
+			if sum.is_zero() {
+				return Ok(sum)
+			}
+
+			let two = T::HigherPrecisionBalance::from(2u32);
+			let ann_sum = ann.checked_mul(&sum).ok_or(Error::<T>::Overflow)?;
+			let ann_minus_one = ann.checked_sub(&One::one()).ok_or(Error::<T>::Overflow)?;
+			let double_x = x.checked_mul(&two).ok_or(Error::<T>::Overflow)?;
+			let double_y = y.checked_mul(&two).ok_or(Error::<T>::Overflow)?;
+
+			let mut d = sum;
+			for _ in 0..MAX_STABLE_SWAP_ITERATIONS {
+				// d_p = D^3 / (4 * x * y)
+				let d_p = d
+					.checked_mul(&d)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_div(&double_x)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_mul(&d)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_div(&double_y)
+					.ok_or(Error::<T>::Overflow)?;
+
+				// D = (Ann * S + 2 * d_p) * D / ((Ann - 1) * D + 3 * d_p)
+				let numerator = d_p
+					.checked_mul(&two)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_add(&ann_sum)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_mul(&d)
+					.ok_or(Error::<T>::Overflow)?;
+				let denominator = ann_minus_one
+					.checked_mul(&d)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_add(&d_p.checked_mul(&3u32.into()).ok_or(Error::<T>::Overflow)?)
+					.ok_or(Error::<T>::Overflow)?;
+
+				let next = numerator.checked_div(&denominator).ok_or(Error::<T>::Overflow)?;
+				if Self::differ_by_at_most_one(&next, &d) {
+					return Ok(next)
+				}
+				d = next;
+			}
+
+			Err(Error::<T>::InvariantNotConverged)
+		}
+
+		/// Solves the StableSwap invariant `d` for the reserve `y` of one asset, given the reserve
+		/// `x` of the other one.
+		fn stable_swap_reserve(
+			x: &T::HigherPrecisionBalance,
+			d: &T::HigherPrecisionBalance,
+			ann: &T::HigherPrecisionBalance,
+		) -> Result<T::HigherPrecisionBalance, Error<T>> {
+			let two = T::HigherPrecisionBalance::from(2u32);
+
+			// c = D^3 / (4 * x * Ann)
+			let c = d
+				.checked_mul(d)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_div(&x.checked_mul(&two).ok_or(Error::<T>::Overflow)?)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_mul(d)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_div(&ann.checked_mul(&two).ok_or(Error::<T>::Overflow)?)
+				.ok_or(Error::<T>::Overflow)?;
+			// b = x + D / Ann
+			let b = d
+				.checked_div(ann)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_add(x)
+				.ok_or(Error::<T>::Overflow)?;
+
+			let mut y = d.clone();
+			for _ in 0..MAX_STABLE_SWAP_ITERATIONS {
+				// y = (y^2 + c) / (2 * y + b - D)
+				let numerator = y
+					.checked_mul(&y)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_add(&c)
+					.ok_or(Error::<T>::Overflow)?;
+				let denominator = y
+					.checked_mul(&two)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_add(&b)
+					.ok_or(Error::<T>::Overflow)?
+					.checked_sub(d)
+					.ok_or(Error::<T>::InvariantNotConverged)?;
+
+				let next = numerator.checked_div(&denominator).ok_or(Error::<T>::Overflow)?;
+				if Self::differ_by_at_most_one(&next, &y) {
+					return Ok(next)
+				}
+				y = next;
+			}
+
+			Err(Error::<T>::InvariantNotConverged)
+		}
+
+		fn differ_by_at_most_one(
+			a: &T::HigherPrecisionBalance,
+			b: &T::HigherPrecisionBalance,
+		) -> bool {
+			let difference = if a > b { a.checked_sub(b) } else { b.checked_sub(a) };
+			difference.map_or(false, |d| d <= One::one())
+		}
+
 		/// Ensure that a path is valid.
 		fn validate_swap_path(path: &Vec<T::AssetKind>) -> Result<(), DispatchError> {
 			ensure!(path.len() >= 2, Error::<T>::InvalidPath);
@@ -1324,6 +1926,21 @@ sp_api::decl_runtime_apis! {
 		/// Returns the size of the liquidity pool for the given asset pair.
 		fn get_reserves(asset1: AssetId, asset2: AssetId) -> Option<(Balance, Balance)>;
 	}
+
+	/// This runtime api allows querying the time weighted average prices of the liquidity pools.
+	pub trait TimeWeightedPriceApi<AssetId, BlockNumber>
+	where
+		AssetId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the average price of `asset1` in units of `asset2` over at least the last
+		/// `period` blocks, or `None` if the pool doesn't have that much history.
+		fn time_weighted_price(
+			asset1: AssetId,
+			asset2: AssetId,
+			period: BlockNumber,
+		) -> Option<FixedU128>;
+	}
 }
 
 sp_core::generate_feature_enabled_macro!(runtime_benchmarks_enabled, feature = "runtime-benchmarks", $);
@@ -0,0 +1,372 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time weighted average price oracle built on the pools' price accumulators.
+//!
+//! Every pool keeps the running sum of its spot prices weighted by the number of blocks each of
+//! them held for, along with the reserves it was left with by its last interaction. The sum is
+//! advanced at the spot prices of the stored reserves, from the block they were stored at up to
+//! the current one, so a price moved within a block only counts once it is held across blocks.
+//! Snapshots of the sums, at least [`Config::ObservationPeriod`] blocks apart, are kept in a ring
+//! buffer of [`Config::MaxObservations`] entries, and the average price over a window is the
+//! difference between the current sum and the snapshot preceding the window, divided by the window
+//! length.
+
+use super::*;
+use core::marker::PhantomData;
+use frame_support::{
+	traits::tokens::{ConversionFromAssetBalance, ConversionToAssetBalance},
+	BoundedVec,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::{
+	traits::{Bounded, UniqueSaturatedInto},
+	FixedPointNumber,
+};
+
+/// Trait providing time weighted average prices of asset pairs, which can't be moved within a
+/// single block.
+pub trait TimeWeightedPrice {
+	/// Type representing the kind of assets for which the price is being provided.
+	type AssetKind;
+	/// Type used to measure the averaging window.
+	type BlockNumber;
+
+	/// Returns the average price of `asset1` in units of `asset2` over at least the last
+	/// `period` blocks.
+	///
+	/// Returns `None` if there is no pool for the pair or it hasn't recorded history going back
+	/// `period` blocks.
+	fn time_weighted_price(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		period: Self::BlockNumber,
+	) -> Option<FixedU128>;
+}
+
+impl<T: Config> TimeWeightedPrice for Pallet<T> {
+	type AssetKind = T::AssetKind;
+	type BlockNumber = BlockNumberFor<T>;
+
+	fn time_weighted_price(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		period: Self::BlockNumber,
+	) -> Option<FixedU128> {
+		Self::time_weighted_price(asset1, asset2, period)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the average price of `asset1` in units of `asset2` over at least the last `period`
+	/// blocks.
+	pub fn time_weighted_price(
+		asset1: T::AssetKind,
+		asset2: T::AssetKind,
+		period: BlockNumberFor<T>,
+	) -> Option<FixedU128> {
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).ok()?;
+		let now = frame_system::Pallet::<T>::block_number();
+		let since = now.checked_sub(&period)?;
+
+		// extrapolated at the prices of the stored reserves, which don't account for the moves
+		// made within the current block.
+		let latest = PriceAccumulators::<T>::get(&pool_id)?;
+		let current = Self::accumulate(&asset1, &asset2, &latest, now);
+		let start = PriceObservations::<T>::get(&pool_id)
+			.into_iter()
+			.rev()
+			.find(|observation| observation.block <= since)?;
+
+		let elapsed: u128 = (now - start.block).unique_saturated_into();
+		if elapsed.is_zero() {
+			return None
+		}
+		let cumulative = if Self::is_encoding_order(&asset1, &asset2) {
+			current.price1_cumulative.wrapping_sub(start.price1_cumulative)
+		} else {
+			current.price2_cumulative.wrapping_sub(start.price2_cumulative)
+		};
+		Some(FixedU128::from_inner(cumulative / elapsed))
+	}
+
+	/// Advances the price accumulator of the pool of `asset1` and `asset2` up to the current
+	/// block, stores the reserves the pool is left with, and records an observation if the last
+	/// one is at least [`Config::ObservationPeriod`] blocks old.
+	///
+	/// Must be called after the reserves of the pool change. Does nothing for an invalid pair.
+	pub(crate) fn update_price_oracle(asset1: &T::AssetKind, asset2: &T::AssetKind) {
+		let Ok(pool_id) = T::PoolLocator::pool_id(asset1, asset2) else { return };
+		let now = frame_system::Pallet::<T>::block_number();
+
+		let current = match PriceAccumulators::<T>::get(&pool_id) {
+			Some(latest) => Self::accumulate(asset1, asset2, &latest, now),
+			None => Observation { block: now, price1_cumulative: 0, price2_cumulative: 0 },
+		};
+		let (first, second) = if Self::is_encoding_order(asset1, asset2) {
+			(asset1, asset2)
+		} else {
+			(asset2, asset1)
+		};
+		// an emptied pool is left without reserves, and so without a price.
+		let (reserve1, reserve2) =
+			Self::get_reserves(first.clone(), second.clone()).unwrap_or_default();
+		PriceAccumulators::<T>::insert(
+			&pool_id,
+			PriceAccumulator {
+				block: now,
+				price1_cumulative: current.price1_cumulative,
+				price2_cumulative: current.price2_cumulative,
+				reserve1,
+				reserve2,
+			},
+		);
+
+		PriceObservations::<T>::mutate(&pool_id, |observations| {
+			let due = observations.last().map_or(true, |last| {
+				last.block < now && now - last.block >= T::ObservationPeriod::get()
+			});
+			if due {
+				if observations.is_full() {
+					observations.remove(0);
+				}
+				// can't fail, room was made above.
+				let _ = observations.try_push(current);
+			}
+		});
+	}
+
+	/// Advances the price accumulator of the pool of `asset1` and `asset2`, if any, up to the
+	/// current block, at the prices it held since its last update.
+	///
+	/// Must be called before the amplification ramp of the pool changes, so that the prices held
+	/// so far are accumulated with the ramp they were held under.
+	pub(crate) fn close_price_accumulator(asset1: &T::AssetKind, asset2: &T::AssetKind) {
+		let Ok(pool_id) = T::PoolLocator::pool_id(asset1, asset2) else { return };
+		if PriceAccumulators::<T>::contains_key(&pool_id) {
+			Self::update_price_oracle(asset1, asset2);
+		}
+	}
+
+	/// Advances the `latest` accumulator of the pool of `asset1` and `asset2` to `now`, at the
+	/// spot prices of the reserves it stores, which held since its block.
+	///
+	/// StableSwap pools are priced with the amplification coefficient of the accumulator's block,
+	/// so changing the ramp of a pool must close out its accumulator first, see
+	/// [`Self::close_price_accumulator`].
+	fn accumulate(
+		asset1: &T::AssetKind,
+		asset2: &T::AssetKind,
+		latest: &PriceAccumulator<BlockNumberFor<T>, T::Balance>,
+		now: BlockNumberFor<T>,
+	) -> Observation<BlockNumberFor<T>> {
+		let elapsed: u128 = now.saturating_sub(latest.block).unique_saturated_into();
+		if elapsed.is_zero() {
+			return Observation { block: now, ..latest.observation() }
+		}
+		let (first, second) = if Self::is_encoding_order(asset1, asset2) {
+			(asset1, asset2)
+		} else {
+			(asset2, asset1)
+		};
+		// an empty pool has no price, so nothing is accumulated over the time it stays empty.
+		let (price1, price2) =
+			Self::spot_prices(first, second, &latest.reserve1, &latest.reserve2, latest.block)
+				.unwrap_or_default();
+
+		Observation {
+			block: now,
+			price1_cumulative: latest
+				.price1_cumulative
+				.wrapping_add(price1.into_inner().wrapping_mul(elapsed)),
+			price2_cumulative: latest
+				.price2_cumulative
+				.wrapping_add(price2.into_inner().wrapping_mul(elapsed)),
+		}
+	}
+
+	/// Returns the marginal prices of `asset1` in units of `asset2` and of `asset2` in units of
+	/// `asset1` at the given reserves of their pool, excluding the LP fee.
+	///
+	/// StableSwap pools are priced with the amplification coefficient in effect at block `at`.
+	fn spot_prices(
+		asset1: &T::AssetKind,
+		asset2: &T::AssetKind,
+		reserve1: &T::Balance,
+		reserve2: &T::Balance,
+		at: BlockNumberFor<T>,
+	) -> Option<(FixedU128, FixedU128)> {
+		if reserve1.is_zero() || reserve2.is_zero() {
+			return None
+		}
+		let (numerator, denominator) = match Self::get_curve(asset1, asset2).ok()? {
+			Curve::ConstantProduct => (*reserve2, *reserve1),
+			Curve::StableSwap { amplification, rates } =>
+				Self::stable_swap_marginal_price(reserve1, reserve2, amplification.at(at), rates)
+					.ok()?,
+		};
+		Some((
+			FixedU128::checked_from_rational(numerator, denominator)?,
+			FixedU128::checked_from_rational(denominator, numerator)?,
+		))
+	}
+
+	/// Returns the marginal price of `x` in units of `y` on the StableSwap curve as a fraction.
+	///
+	/// Differentiating the invariant gives `(t + y) / (t + x)`, with `t = 4 * Ann * x^2 * y^2 /
+	/// D^3`, for the reserves scaled by the rates of their assets. The price is scaled back by
+	/// `rate_x / rate_y`. Both terms are scaled down together when they don't fit the balance
+	/// type.
+	pub(crate) fn stable_swap_marginal_price(
+		x: &T::Balance,
+		y: &T::Balance,
+		amplification: u32,
+		(rate_x, rate_y): (u128, u128),
+	) -> Result<(T::Balance, T::Balance), Error<T>> {
+		let rate_x = Self::stable_swap_rate(rate_x)?;
+		let rate_y = Self::stable_swap_rate(rate_y)?;
+		let x = T::HigherPrecisionBalance::from(*x)
+			.checked_mul(&rate_x)
+			.ok_or(Error::<T>::Overflow)?;
+		let y = T::HigherPrecisionBalance::from(*y)
+			.checked_mul(&rate_y)
+			.ok_or(Error::<T>::Overflow)?;
+		let ann = Self::stable_swap_ann(amplification)?;
+		let d = Self::stable_swap_invariant(&x, &y, &ann)?;
+
+		let xy_over_d = x
+			.checked_mul(&y)
+			.ok_or(Error::<T>::Overflow)?
+			.checked_div(&d)
+			.ok_or(Error::<T>::Overflow)?;
+		let t = ann
+			.checked_mul(&4u32.into())
+			.ok_or(Error::<T>::Overflow)?
+			.checked_mul(&xy_over_d)
+			.ok_or(Error::<T>::Overflow)?
+			.checked_mul(&xy_over_d)
+			.ok_or(Error::<T>::Overflow)?
+			.checked_div(&d)
+			.ok_or(Error::<T>::Overflow)?;
+
+		let numerator = t
+			.checked_add(&y)
+			.ok_or(Error::<T>::Overflow)?
+			.checked_mul(&rate_x)
+			.ok_or(Error::<T>::Overflow)?;
+		let denominator = t
+			.checked_add(&x)
+			.ok_or(Error::<T>::Overflow)?
+			.checked_mul(&rate_y)
+			.ok_or(Error::<T>::Overflow)?;
+		let larger = if numerator > denominator { numerator.clone() } else { denominator.clone() };
+		let scale = larger
+			.checked_div(&T::HigherPrecisionBalance::from(T::Balance::max_value()))
+			.ok_or(Error::<T>::Overflow)?
+			.checked_add(&One::one())
+			.ok_or(Error::<T>::Overflow)?;
+
+		let scaled = |value: T::HigherPrecisionBalance| -> Result<T::Balance, Error<T>> {
+			value
+				.checked_div(&scale)
+				.ok_or(Error::<T>::Overflow)?
+				.try_into()
+				.map_err(|_| Error::<T>::Overflow)
+		};
+		Ok((scaled(numerator)?, scaled(denominator)?))
+	}
+}
+
+/// Converts balances between an asset and `Target` at the time weighted average price over the
+/// last `Period` blocks of the pool of the two.
+///
+/// Can serve, for instance, as the balance converter of `pallet-treasury` or the fee asset
+/// converter of an XCM trader, in place of governance-set rates.
+pub struct TwapConversion<T, Target, Period>(PhantomData<(T, Target, Period)>);
+
+impl<T, Target, Period> ConversionFromAssetBalance<T::Balance, T::AssetKind, T::Balance>
+	for TwapConversion<T, Target, Period>
+where
+	T: Config,
+	Target: Get<T::AssetKind>,
+	Period: Get<BlockNumberFor<T>>,
+{
+	type Error = ();
+
+	fn from_asset_balance(
+		balance: T::Balance,
+		asset_id: T::AssetKind,
+	) -> Result<T::Balance, Self::Error> {
+		let target = Target::get();
+		if asset_id == target {
+			return Ok(balance)
+		}
+		let price = Pallet::<T>::time_weighted_price(asset_id, target, Period::get()).ok_or(())?;
+		Ok(price.saturating_mul_int(balance))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(asset_id: T::AssetKind) {
+		let Ok(pool_id) = T::PoolLocator::pool_id(&asset_id, &Target::get()) else { return };
+		// seed a history at a price of one over the whole window.
+		let now = frame_system::Pallet::<T>::block_number().max(Period::get() + One::one());
+		frame_system::Pallet::<T>::set_block_number(now);
+		let elapsed: u128 = now.unique_saturated_into();
+		let cumulative = FixedU128::one().into_inner().wrapping_mul(elapsed);
+		PriceObservations::<T>::insert(
+			&pool_id,
+			BoundedVec::truncate_from(alloc::vec![Observation {
+				block: Zero::zero(),
+				price1_cumulative: 0,
+				price2_cumulative: 0,
+			}]),
+		);
+		// no reserves, so the price stays as seeded until the pool is interacted with.
+		PriceAccumulators::<T>::insert(
+			&pool_id,
+			PriceAccumulator {
+				block: now,
+				price1_cumulative: cumulative,
+				price2_cumulative: cumulative,
+				reserve1: Zero::zero(),
+				reserve2: Zero::zero(),
+			},
+		);
+	}
+}
+
+impl<T, Target, Period> ConversionToAssetBalance<T::Balance, T::AssetKind, T::Balance>
+	for TwapConversion<T, Target, Period>
+where
+	T: Config,
+	Target: Get<T::AssetKind>,
+	Period: Get<BlockNumberFor<T>>,
+{
+	type Error = ();
+
+	fn to_asset_balance(
+		balance: T::Balance,
+		asset_id: T::AssetKind,
+	) -> Result<T::Balance, Self::Error> {
+		let target = Target::get();
+		if asset_id == target {
+			return Ok(balance)
+		}
+		let price = Pallet::<T>::time_weighted_price(target, asset_id, Period::get()).ok_or(())?;
+		Ok(price.saturating_mul_int(balance))
+	}
+}
@@ -24,11 +24,13 @@ use frame_support::{
 		fungible::{Inspect as FungibleInspect, NativeOrWithId},
 		fungibles,
 		fungibles::{Inspect, InspectEnumerable},
+		tokens::{ConversionFromAssetBalance, ConversionToAssetBalance},
 		Get,
 	},
 };
 use sp_arithmetic::Permill;
-use sp_runtime::{DispatchError, TokenError};
+use sp_core::ConstU64;
+use sp_runtime::{DispatchError, FixedPointNumber, FixedU128, TokenError};
 
 fn events() -> Vec<Event<Test>> {
 	let result = System::events()
@@ -2493,3 +2495,758 @@ fn swap_credit_invalid_path() {
 		assert_eq!(error, (expected_credit_in, Error::<Test>::InvalidPath.into()));
 	});
 }
+
+#[test]
+fn stable_swap_amounts_match_curve() {
+	new_test_ext().execute_with(|| {
+		// a balanced StableSwap pool barely moves the price, unlike a constant product one.
+		assert_eq!(
+			AssetConversion::get_stable_swap_amount_out(
+				&100_000,
+				&1_000_000,
+				&1_000_000,
+				100,
+				(1, 1),
+				3
+			),
+			Ok(99_650)
+		);
+		assert_eq!(AssetConversion::get_amount_out(&100_000, &1_000_000, &1_000_000), Ok(90_661));
+
+		assert_eq!(
+			AssetConversion::get_stable_swap_amount_out(&1_000, &10_000, &10_000, 100, (1, 1), 3),
+			Ok(996)
+		);
+		assert_eq!(
+			AssetConversion::get_stable_swap_amount_in(&1_000, &10_000, &10_000, 100, (1, 1), 3),
+			Ok(1_005)
+		);
+
+		// the amount in for an exact amount out always buys at least that amount out.
+		for (reserve_in, reserve_out, amplification) in [
+			(10_000u128, 10_000u128, 1u32),
+			(10_000, 15_000, 100),
+			(u64::MAX as u128, 10_000, 10_000),
+		] {
+			for amount_out in [1, 10, 500, 5_000] {
+				let amount_in = AssetConversion::get_stable_swap_amount_in(
+					&amount_out,
+					&reserve_in,
+					&reserve_out,
+					amplification,
+					(1, 1),
+					3,
+				)
+				.unwrap();
+				let received = AssetConversion::get_stable_swap_amount_out(
+					&amount_in,
+					&reserve_in,
+					&reserve_out,
+					amplification,
+					(1, 1),
+					3,
+				)
+				.unwrap();
+				assert!(received >= amount_out);
+			}
+		}
+
+		assert_noop!(
+			AssetConversion::get_stable_swap_amount_in(&10_000, &10_000, &10_000, 100, (1, 1), 3),
+			Error::<Test>::AmountOutTooHigh
+		);
+		assert_noop!(
+			AssetConversion::get_stable_swap_amount_out(&100, &0, &10_000, 100, (1, 1), 3),
+			Error::<Test>::ZeroLiquidity
+		);
+	});
+}
+
+#[test]
+fn can_create_stable_swap_pool_and_swap() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		let curve = CurveKind::StableSwap { amplification: 100, rates: (1, 1) };
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_noop!(
+			AssetConversion::create_pool_with_curve(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				CurveKind::StableSwap { amplification: 0, rates: (1, 1) },
+			),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			AssetConversion::create_pool_with_curve(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				CurveKind::StableSwap { amplification: 10_001, rates: (1, 1) },
+			),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			curve,
+		));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::CurveSet { pool_id: pool_id.clone(), curve })
+		);
+		assert_eq!(
+			AssetConversion::get_curve(&token_2, &token_1),
+			Ok(Curve::StableSwap { amplification: Amplification::constant(100, 1), rates: (1, 1) })
+		);
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 20000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 20000));
+
+		let liquidity = 10000;
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			liquidity,
+			liquidity,
+			1,
+			1,
+			user,
+		));
+
+		let input_amount = 1000;
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				input_amount,
+				true
+			),
+			Some(996)
+		);
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_2.clone(), token_1.clone()],
+			input_amount,
+			990,
+			user,
+			false,
+		));
+
+		let pool_account = <Test as Config>::PoolLocator::address(&pool_id).unwrap();
+		assert_eq!(balance(user, token_1.clone()), 20000 - liquidity + 996 + ed);
+		assert_eq!(balance(pool_account, token_1.clone()), liquidity - 996);
+		assert_eq!(balance(pool_account, token_2.clone()), liquidity + input_amount);
+	});
+}
+
+#[test]
+fn stable_swap_rates_scale_reserves() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		// an asset of 3 decimals less than its pair trades at parity once scaled by its rate.
+		let (amount_in, amount_out) = (1_001, 996_500);
+		assert_eq!(
+			AssetConversion::get_stable_swap_amount_out(
+				&amount_in,
+				&10_000,
+				&10_000_000,
+				100,
+				(1_000, 1),
+				3
+			),
+			Ok(amount_out)
+		);
+		assert_eq!(
+			AssetConversion::get_stable_swap_amount_in(
+				&amount_out,
+				&10_000,
+				&10_000_000,
+				100,
+				(1_000, 1),
+				3
+			),
+			Ok(amount_in)
+		);
+		// without the rates, the same reserves are far off balance.
+		assert!(
+			AssetConversion::get_stable_swap_amount_out(
+				&amount_in,
+				&10_000,
+				&10_000_000,
+				100,
+				(1, 1),
+				3
+			)
+			.unwrap() < amount_out
+		);
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_noop!(
+			AssetConversion::create_pool_with_curve(
+				RuntimeOrigin::signed(user),
+				Box::new(token_2.clone()),
+				Box::new(token_1.clone()),
+				CurveKind::StableSwap { amplification: 100, rates: (0, 1) },
+			),
+			Error::<Test>::InvalidRate
+		);
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_2.clone()),
+			Box::new(token_1.clone()),
+			CurveKind::StableSwap { amplification: 100, rates: (1_000, 1) },
+		));
+
+		// the rates are kept in the order of the assets' encodings.
+		let pool_id = <Test as Config>::PoolLocator::pool_id(&token_1, &token_2).unwrap();
+		assert_eq!(
+			PoolCurves::<Test>::get(&pool_id),
+			Curve::StableSwap { amplification: Amplification::constant(100, 1), rates: (1, 1_000) }
+		);
+		assert_eq!(
+			AssetConversion::get_curve(&token_2, &token_1),
+			Ok(Curve::StableSwap {
+				amplification: Amplification::constant(100, 1),
+				rates: (1_000, 1)
+			})
+		);
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 20_000_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 20_000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_2.clone()),
+			Box::new(token_1.clone()),
+			10_000,
+			10_000_000,
+			1,
+			1,
+			user,
+		));
+
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				1_000,
+				true
+			),
+			Some(amount_out)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_tokens_for_exact_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				amount_out,
+				true
+			),
+			Some(amount_in)
+		);
+	});
+}
+
+#[test]
+fn stable_swap_quotes_without_fee_use_spot_price() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			CurveKind::StableSwap { amplification: 100, rates: (1, 1) },
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 10000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			10000,
+			10000,
+			1,
+			1,
+			user,
+		));
+
+		// a balanced pool trades at parity, however large the amount ...
+		let amount = 9000;
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				amount,
+				false
+			),
+			Some(amount)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_tokens_for_exact_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				amount,
+				false
+			),
+			Some(amount)
+		);
+		// ... while swapping it slips, even without the fee.
+		assert!(
+			AssetConversion::get_stable_swap_amount_out(&amount, &10000, &10000, 100, (1, 1), 0)
+				.unwrap() < amount
+		);
+	});
+}
+
+#[test]
+fn can_swap_credit_across_mixed_curves() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			CurveKind::StableSwap { amplification: 100, rates: (1, 1) },
+		));
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_3.clone()),
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 20000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 20000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 3, user, 20000));
+
+		for token in [token_2.clone(), token_3.clone()] {
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token),
+				10000,
+				10000,
+				1,
+				1,
+				user,
+			));
+		}
+
+		let amount_in = 1000;
+		let expect_native =
+			AssetConversion::get_stable_swap_amount_out(&amount_in, &10000, &10000, 100, (1, 1), 3)
+				.unwrap();
+		let expect_out = AssetConversion::get_amount_out(&expect_native, &10000, &10000).unwrap();
+
+		let credit_in = NativeAndAssets::issue(token_2.clone(), amount_in);
+		let credit_out = <AssetConversion as SwapCredit<_>>::swap_exact_tokens_for_tokens(
+			vec![token_2.clone(), token_1.clone(), token_3.clone()],
+			credit_in,
+			None,
+		)
+		.unwrap();
+		assert_eq!(credit_out.peek(), expect_out);
+
+		let credit_in = NativeAndAssets::issue(token_2.clone(), amount_in);
+		let (credit_out, credit_change) =
+			<AssetConversion as SwapCredit<_>>::swap_tokens_for_exact_tokens(
+				vec![token_2.clone(), token_1.clone(), token_3.clone()],
+				credit_in,
+				500,
+			)
+			.unwrap();
+		assert_eq!(credit_out.peek(), 500);
+		assert!(credit_change.peek() > 0);
+	});
+}
+
+#[test]
+fn can_ramp_amplification() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+		let pool_id = (token_1.clone(), token_2.clone());
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone()]);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000));
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			CurveKind::StableSwap { amplification: 100, rates: (1, 1) },
+		));
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_3.clone()),
+		));
+
+		assert_noop!(
+			AssetConversion::ramp_amplification(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				200,
+				11,
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			AssetConversion::ramp_amplification(
+				RuntimeOrigin::root(),
+				Box::new(token_1.clone()),
+				Box::new(token_3.clone()),
+				200,
+				11,
+			),
+			Error::<Test>::NotStableSwapPool
+		);
+		assert_noop!(
+			AssetConversion::ramp_amplification(
+				RuntimeOrigin::root(),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				200,
+				10,
+			),
+			Error::<Test>::AmplificationRampTooShort
+		);
+		assert_noop!(
+			AssetConversion::ramp_amplification(
+				RuntimeOrigin::root(),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				1001,
+				11,
+			),
+			Error::<Test>::AmplificationChangeTooLarge
+		);
+		assert_noop!(
+			AssetConversion::ramp_amplification(
+				RuntimeOrigin::root(),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				9,
+				11,
+			),
+			Error::<Test>::AmplificationChangeTooLarge
+		);
+
+		assert_ok!(AssetConversion::ramp_amplification(
+			RuntimeOrigin::root(),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			200,
+			21,
+		));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::AmplificationRampStarted {
+				pool_id: pool_id.clone(),
+				initial: 100,
+				future: 200,
+				initial_block: 1,
+				future_block: 21,
+			})
+		);
+
+		let amplification = |at| match PoolCurves::<Test>::get(&pool_id) {
+			Curve::StableSwap { amplification, .. } => amplification.at(at),
+			Curve::ConstantProduct => unreachable!("the pool uses the StableSwap curve"),
+		};
+		assert_eq!(amplification(1), 100);
+		assert_eq!(amplification(6), 125);
+		assert_eq!(amplification(11), 150);
+		assert_eq!(amplification(21), 200);
+		assert_eq!(amplification(100), 200);
+
+		System::set_block_number(11);
+		assert_ok!(AssetConversion::stop_amplification_ramp(
+			RuntimeOrigin::root(),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+		));
+		assert_eq!(
+			events().last(),
+			Some(&Event::<Test>::AmplificationRampStopped {
+				pool_id: pool_id.clone(),
+				amplification: 150
+			})
+		);
+		assert_eq!(amplification(100), 150);
+
+		// ramping down from the stopped value.
+		assert_ok!(AssetConversion::ramp_amplification(
+			RuntimeOrigin::root(),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			15,
+			31,
+		));
+		assert_eq!(amplification(21), 83);
+		assert_eq!(amplification(31), 15);
+	});
+}
+
+#[test]
+fn time_weighted_price_ignores_intra_block_moves() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 1000));
+
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			10000,
+			200,
+			1,
+			1,
+			user,
+		));
+		// no history yet.
+		assert_eq!(AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 1), None);
+
+		System::set_block_number(11);
+		let price = FixedU128::from_rational(200, 10000);
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 10),
+			Some(price)
+		);
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_2.clone(), token_1.clone(), 10),
+			Some(FixedU128::from_u32(50))
+		);
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 11),
+			None
+		);
+
+		// moving the spot price doesn't move the average within the block.
+		let input_amount = 100;
+		let expect_receive = AssetConversion::get_amount_out(&input_amount, &200, &10000).unwrap();
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_2.clone(), token_1.clone()],
+			input_amount,
+			1,
+			user,
+			false,
+		));
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 10),
+			Some(price)
+		);
+		let latest = PriceAccumulators::<Test>::get((token_1.clone(), token_2.clone())).unwrap();
+		assert_eq!(
+			(latest.reserve1, latest.reserve2),
+			(10000 - expect_receive, 200 + input_amount)
+		);
+
+		// but counts once held across blocks.
+		System::set_block_number(21);
+		let moved_price =
+			FixedU128::checked_from_rational(200 + input_amount, 10000 - expect_receive).unwrap();
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 10),
+			Some(moved_price)
+		);
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 20),
+			Some(FixedU128::from_inner((price.into_inner() + moved_price.into_inner()) / 2))
+		);
+	});
+}
+
+#[test]
+fn price_observations_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 1000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			1000,
+			100,
+			1,
+			1,
+			user,
+		));
+
+		// observations are at least `ObservationPeriod` blocks apart.
+		for block in [5, 11, 21, 25, 31, 41] {
+			System::set_block_number(block);
+			AssetConversion::update_price_oracle(&token_1, &token_2);
+		}
+		let blocks: Vec<u64> = PriceObservations::<Test>::get(&pool_id)
+			.into_iter()
+			.map(|observation| observation.block)
+			.collect();
+		assert_eq!(blocks, vec![11, 21, 31, 41]);
+		assert_eq!(PriceAccumulators::<Test>::get(&pool_id).map(|latest| latest.block), Some(41));
+
+		// the oldest observation was dropped, so the longest window shrank accordingly.
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 40),
+			None
+		);
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 30),
+			Some(FixedU128::from_rational(1, 10))
+		);
+	});
+}
+
+#[test]
+fn time_weighted_price_follows_amplification_ramps() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			CurveKind::StableSwap { amplification: 100, rates: (1, 1) },
+		));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 20000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 5000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			10000,
+			5000,
+			1,
+			1,
+			user,
+		));
+
+		let price_at = |amplification| {
+			let (numerator, denominator) =
+				AssetConversion::stable_swap_marginal_price(&10000, &5000, amplification, (1, 1))
+					.unwrap();
+			FixedU128::checked_from_rational(numerator, denominator).unwrap()
+		};
+		assert_ne!(price_at(100), price_at(1000));
+
+		assert_ok!(AssetConversion::ramp_amplification(
+			RuntimeOrigin::root(),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			1000,
+			11,
+		));
+
+		// the prices held since the last update are accumulated at the amplification of then, ...
+		System::set_block_number(31);
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 30),
+			Some(price_at(100))
+		);
+
+		// ... and changing the ramp closes them out before the new one applies.
+		assert_ok!(AssetConversion::stop_amplification_ramp(
+			RuntimeOrigin::root(),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+		));
+		System::set_block_number(41);
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 10),
+			Some(price_at(1000))
+		);
+		assert_eq!(
+			AssetConversion::time_weighted_price(token_1.clone(), token_2.clone(), 40),
+			Some(FixedU128::from_inner(
+				(price_at(100).into_inner() * 30 + price_at(1000).into_inner() * 10) / 40
+			))
+		);
+	});
+}
+
+#[test]
+fn twap_conversion_converts_at_average_price() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 1000));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			1000,
+			100,
+			1,
+			1,
+			user,
+		));
+
+		type Conversion = TwapConversion<Test, Native, ConstU64<10>>;
+		assert_eq!(Conversion::from_asset_balance(10, token_2.clone()), Err(()));
+
+		System::set_block_number(11);
+		assert_eq!(Conversion::from_asset_balance(10, token_2.clone()), Ok(100));
+		assert_eq!(Conversion::to_asset_balance(100, token_2.clone()), Ok(10));
+		assert_eq!(Conversion::from_asset_balance(10, token_1.clone()), Ok(10));
+		assert_eq!(Conversion::from_asset_balance(10, NativeOrWithId::WithId(3)), Err(()));
+	});
+}
@@ -16,10 +16,13 @@
 // limitations under the License.
 
 use super::*;
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use core::marker::PhantomData;
 use scale_info::TypeInfo;
-use sp_runtime::traits::TryConvert;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, TryConvert, UniqueSaturatedInto},
+	RuntimeDebug,
+};
 
 /// Represents a swap path with associated asset amounts indicating how much of the asset needs to
 /// be deposited to get the following asset's amount withdrawn (this is inclusive of fees).
@@ -41,6 +44,144 @@ pub struct PoolInfo<PoolAssetId> {
 	pub lp_token: PoolAssetId,
 }
 
+/// The pricing curve requested for a pool at its creation.
+#[derive(
+	Decode,
+	DecodeWithMemTracking,
+	Encode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum CurveKind {
+	/// Uniswap V2 constant product invariant, `x * y = k`.
+	ConstantProduct,
+	/// Curve StableSwap invariant, suited to pairs of assets expected to trade close to parity.
+	StableSwap {
+		/// The amplification coefficient `A`. The higher it is, the flatter the curve is around
+		/// the balanced point of the pool.
+		amplification: u32,
+		/// The rates of the first and the second asset of the pool, in the order they are given
+		/// at its creation.
+		///
+		/// The reserves are multiplied by the rate of their asset before being priced along the
+		/// invariant, which brings assets of different decimals to a common precision. An asset
+		/// of 6 decimals paired with one of 12 decimals takes the rates `(1_000_000, 1)`.
+		rates: (u128, u128),
+	},
+}
+
+/// The pricing curve of a pool, as kept in storage.
+#[derive(
+	Decode, Encode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub enum Curve<BlockNumber> {
+	/// Uniswap V2 constant product invariant, `x * y = k`.
+	#[default]
+	ConstantProduct,
+	/// Curve StableSwap invariant with a possibly ramping amplification coefficient.
+	StableSwap {
+		/// The amplification coefficient of the pool.
+		amplification: Amplification<BlockNumber>,
+		/// The rates of the pool's assets, in the order of the assets' encodings.
+		rates: (u128, u128),
+	},
+}
+
+/// Amplification coefficient of a StableSwap pool.
+///
+/// The coefficient moves linearly from `initial` at `initial_block` to `future` at
+/// `future_block`, and stays at `future` afterwards.
+#[derive(Decode, Encode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Amplification<BlockNumber> {
+	/// The coefficient at the start of the ramp.
+	pub initial: u32,
+	/// The coefficient at the end of the ramp.
+	pub future: u32,
+	/// The block the ramp starts at.
+	pub initial_block: BlockNumber,
+	/// The block the ramp ends at.
+	pub future_block: BlockNumber,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> Amplification<BlockNumber> {
+	/// An amplification coefficient fixed at `value`.
+	pub fn constant(value: u32, now: BlockNumber) -> Self {
+		Self { initial: value, future: value, initial_block: now, future_block: now }
+	}
+
+	/// The amplification coefficient in effect at block `now`.
+	pub fn at(&self, now: BlockNumber) -> u32 {
+		if now >= self.future_block {
+			return self.future
+		}
+		if now <= self.initial_block {
+			return self.initial
+		}
+		let elapsed: u128 = (now - self.initial_block).unique_saturated_into();
+		let duration: u128 = (self.future_block - self.initial_block).unique_saturated_into();
+		// `elapsed < duration` here, so the interpolated delta stays below the full delta.
+		let delta = |from: u32, to: u32| (((to - from) as u128) * elapsed / duration) as u32;
+		if self.future > self.initial {
+			self.initial + delta(self.initial, self.future)
+		} else {
+			self.initial - delta(self.future, self.initial)
+		}
+	}
+}
+
+/// Cumulative prices of a pool at a given block.
+///
+/// Each cumulative price is the sum, over every block since the pool's first interaction, of the
+/// spot price in effect during the block, as the inner value of a [`sp_runtime::FixedU128`]. The
+/// sums are expected to overflow and wrap around; only the differences between two of them are
+/// meaningful. `price1_cumulative` tracks the price of the asset whose encoding sorts first, in
+/// units of the other asset, and `price2_cumulative` the inverse.
+#[derive(Decode, Encode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Observation<BlockNumber> {
+	/// The block the cumulative prices are accounted up to.
+	pub block: BlockNumber,
+	/// The cumulative price of the first asset in units of the second.
+	pub price1_cumulative: u128,
+	/// The cumulative price of the second asset in units of the first.
+	pub price2_cumulative: u128,
+}
+
+/// Cumulative prices of a pool along with the reserves the pool was left with at the last block
+/// it was interacted with.
+///
+/// The reserves set the spot prices accumulated from that block on, so a price moved within a
+/// block only counts once it is held across blocks. Like in [`Observation`], `reserve1` is the
+/// reserve of the asset whose encoding sorts first.
+#[derive(Decode, Encode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct PriceAccumulator<BlockNumber, Balance> {
+	/// The block the cumulative prices are accounted up to.
+	pub block: BlockNumber,
+	/// The cumulative price of the first asset in units of the second.
+	pub price1_cumulative: u128,
+	/// The cumulative price of the second asset in units of the first.
+	pub price2_cumulative: u128,
+	/// The reserve of the first asset at the end of the last interaction with the pool.
+	pub reserve1: Balance,
+	/// The reserve of the second asset at the end of the last interaction with the pool.
+	pub reserve2: Balance,
+}
+
+impl<BlockNumber: Copy, Balance> PriceAccumulator<BlockNumber, Balance> {
+	/// The cumulative prices, without the reserves.
+	pub fn observation(&self) -> Observation<BlockNumber> {
+		Observation {
+			block: self.block,
+			price1_cumulative: self.price1_cumulative,
+			price2_cumulative: self.price2_cumulative,
+		}
+	}
+}
+
 /// Provides means to resolve the `PoolId` and `AccountId` from a pair of assets.
 ///
 /// Resulting `PoolId` remains consistent whether the asset pair is presented as (asset1, asset2)
@@ -21,7 +21,9 @@ use super::*;
 
 use alloc::{collections::btree_map::BTreeMap, vec::Vec};
 use assert_matches::assert_matches;
-use frame_benchmarking::v1::{account, benchmarks_instance_pallet, whitelist_account};
+use frame_benchmarking::v1::{
+	account, benchmarks_instance_pallet, whitelist_account, BenchmarkError,
+};
 use frame_support::{
 	dispatch::RawOrigin,
 	traits::{
@@ -36,12 +38,15 @@ use crate::Pallet as ConvictionVoting;
 
 const SEED: u32 = 0;
 
-/// Fill all classes as much as possible up to `MaxVotes` and return the Class with the most votes
-/// ongoing.
+/// Fill all classes in which accounts may vote as much as possible up to `MaxVotes` and return the
+/// Class with the most votes ongoing.
 fn fill_voting<T: Config<I>, I: 'static>(
 ) -> (ClassOf<T, I>, BTreeMap<ClassOf<T, I>, Vec<IndexOf<T, I>>>) {
 	let mut r = BTreeMap::<ClassOf<T, I>, Vec<IndexOf<T, I>>>::new();
 	for class in T::Polls::classes().into_iter() {
+		if T::PersonhoodClasses::contains(&class) {
+			continue
+		}
 		for _ in 0..T::MaxVotes::get() {
 			match T::Polls::create_ongoing(class.clone()) {
 				Ok(i) => r.entry(class.clone()).or_default().push(i),
@@ -59,6 +64,21 @@ fn funded_account<T: Config<I>, I: 'static>(name: &'static str, index: u32) -> T
 	caller
 }
 
+/// Create an ongoing poll of a class which is decided by persons and return its index, along with
+/// an origin of a person in the context of the poll.
+fn personal_poll<T: Config<I>, I: 'static>(
+) -> Result<(IndexOf<T, I>, T::RuntimeOrigin), BenchmarkError> {
+	let class = T::Polls::classes()
+		.into_iter()
+		.find(|class| T::PersonhoodClasses::contains(class))
+		.ok_or(BenchmarkError::Weightless)?;
+	let index = T::Polls::create_ongoing(class).map_err(|_| BenchmarkError::Weightless)?;
+	let origin =
+		T::PersonhoodOrigin::try_successful_origin(&ConvictionVoting::<T, I>::alias_context(index))
+			.map_err(|_| BenchmarkError::Weightless)?;
+	Ok((index, origin))
+}
+
 fn account_vote<T: Config<I>, I: 'static>(b: BalanceOf<T, I>) -> AccountVote<BalanceOf<T, I>> {
 	let v = Vote { aye: true, conviction: Conviction::Locked1x };
 
@@ -287,6 +307,39 @@ benchmarks_instance_pallet! {
 		assert_eq!(orig_usable, <T::Currency as fungible::Inspect<T::AccountId>>::reducible_balance(&caller, Expendable, Polite));
 	}
 
+	vote_as_person {
+		let (index, origin) = personal_poll::<T, I>()?;
+		// Worst case: the person replaces an existing vote.
+		ConvictionVoting::<T, I>::vote_as_person(origin.clone(), index, false)?;
+	}: _<T::RuntimeOrigin>(origin, index, true)
+	verify {
+		assert_matches!(PersonalVotes::<T, I>::iter_prefix_values(index).next(), Some((true, _)));
+	}
+
+	remove_personal_vote {
+		let (index, origin) = personal_poll::<T, I>()?;
+		ConvictionVoting::<T, I>::vote_as_person(origin.clone(), index, true)?;
+	}: _<T::RuntimeOrigin>(origin, index)
+	verify {
+		assert_eq!(PersonalVotes::<T, I>::iter_prefix(index).next(), None);
+	}
+
+	clear_personal_votes {
+		let n in 0 .. 1000;
+
+		let caller = funded_account::<T, I>("caller", 0);
+		whitelist_account!(caller);
+		let (index, _) = personal_poll::<T, I>()?;
+		for i in 0..n {
+			let alias = (i, index).using_encoded(blake2_256);
+			PersonalVotes::<T, I>::insert(index, alias, (true, Delegations::default()));
+		}
+		T::Polls::end_ongoing(index, true).map_err(|_| BenchmarkError::Weightless)?;
+	}: _(RawOrigin::Signed(caller.clone()), index, n)
+	verify {
+		assert_eq!(PersonalVotes::<T, I>::iter_prefix(index).next(), None);
+	}
+
 	impl_benchmark_test_suite!(
 		ConvictionVoting,
 		crate::tests::new_test_ext(),
@@ -23,22 +23,41 @@
 //! ## Overview
 //!
 //! Pallet for managing actual voting in polls.
+//!
+//! ### One person, one vote
+//!
+//! Polls of the classes in [`Config::PersonhoodClasses`] are decided by persons rather than by
+//! token holders. Instead of accounts, persons vote with `vote_as_person`, proving their
+//! personhood through [`Config::PersonhoodOrigin`] with an alias that is scoped to the poll (see
+//! [`Pallet::alias_context`]). Each alias may have a single vote per poll, which counts for
+//! [`Config::VotesPerPerson`] votes in the tally, and no vote can be linked to an account or to the
+//! votes of the same person on other polls.
+//!
+//! The support of such a poll is measured against the number of persons rather than against the
+//! issuance: every person in favour adds their share of [`Config::MaxTurnout`], as counted by the
+//! members of [`Config::PersonhoodOrigin`] at the time of their vote. The share is fixed when the
+//! vote is cast and is not adjusted when the population changes afterwards, so votes cast at
+//! different times may carry slightly different support until they are replaced or removed.
 
 #![recursion_limit = "256"]
 #![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 
+use codec::Encode;
 use frame_support::{
 	dispatch::DispatchResult,
 	ensure,
 	traits::{
-		fungible, Currency, Get, LockIdentifier, LockableCurrency, PollStatus, Polling,
-		ReservableCurrency, WithdrawReasons,
+		fungible,
+		reality::{Alias, Context, CountedMembers},
+		Contains, Currency, EnsureOriginWithArg, Get, LockIdentifier, LockableCurrency,
+		PalletInfoAccess, PollStatus, Polling, ReservableCurrency, WithdrawReasons,
 	},
 };
+use sp_io::hashing::blake2_256;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, Saturating, StaticLookup, Zero},
+	traits::{AtLeast32BitUnsigned, One, Saturating, StaticLookup, Zero},
 	ArithmeticError, DispatchError, Perbill,
 };
 
@@ -97,7 +116,7 @@ pub mod pallet {
 			DispatchResultWithPostInfo, IsType, StorageDoubleMap, StorageMap, ValueQuery,
 		},
 		traits::ClassCountOf,
-		Twox64Concat,
+		Blake2_128Concat, Twox64Concat,
 	};
 	use frame_system::pallet_prelude::{ensure_signed, OriginFor};
 	use sp_runtime::BoundedVec;
@@ -157,6 +176,26 @@ pub mod pallet {
 		/// of the calling function. This means that if the calling function fails, the hook will
 		/// be rolled back without further notice.
 		type VotingHooks: VotingHooks<Self::AccountId, PollIndexOf<Self, I>, BalanceOf<Self, I>>;
+
+		/// The origin of votes that are cast by persons rather than by accounts.
+		///
+		/// It is given the alias context of the poll (see [`Pallet::alias_context`]) and must
+		/// return the alias of the person in that context, e.g.
+		/// `pallet_people::EnsurePersonalAliasInContext`. Its members are the population against
+		/// which the support of persons is measured, as counted at the time of each vote.
+		type PersonhoodOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, Context, Success = Alias>
+			+ CountedMembers
+			+ Default;
+
+		/// The classes of polls which are decided by one person, one vote.
+		///
+		/// Polls of these classes only accept votes of persons, while polls of all other classes
+		/// only accept votes and delegations of accounts.
+		type PersonhoodClasses: Contains<ClassOf<Self, I>>;
+
+		/// The number of votes that the vote of a single person counts for.
+		#[pallet::constant]
+		type VotesPerPerson: Get<BalanceOf<Self, I>>;
 	}
 
 	/// All voting for a particular voter in a particular voting class. We store the balance for the
@@ -184,6 +223,19 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The votes of persons, keyed by the poll and the alias of the person in the context of that
+	/// poll. Each is whether the vote is in favour, and the votes and support it was counted for
+	/// in the tally.
+	#[pallet::storage]
+	pub type PersonalVotes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PollIndexOf<T, I>,
+		Blake2_128Concat,
+		Alias,
+		(bool, Delegations<BalanceOf<T, I>>),
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -197,6 +249,12 @@ pub mod pallet {
 		VoteRemoved { who: T::AccountId, vote: AccountVote<BalanceOf<T, I>> },
 		/// The lockup period of a conviction vote expired, and the funds have been unlocked.
 		VoteUnlocked { who: T::AccountId, class: ClassOf<T, I> },
+		/// A person has voted.
+		PersonVoted { alias: Alias, poll_index: PollIndexOf<T, I>, aye: bool },
+		/// The vote of a person has been removed.
+		PersonalVoteRemoved { alias: Alias, poll_index: PollIndexOf<T, I> },
+		/// Votes of persons have been cleared from a poll which is no longer ongoing.
+		PersonalVotesCleared { poll_index: PollIndexOf<T, I>, removed: u32 },
 	}
 
 	#[pallet::error]
@@ -226,6 +284,10 @@ pub mod pallet {
 		ClassNeeded,
 		/// The class ID supplied is invalid.
 		BadClass,
+		/// Polls of this class only accept votes of persons.
+		PersonsOnly,
+		/// Polls of this class only accept votes of accounts.
+		AccountsOnly,
 	}
 
 	#[pallet::call]
@@ -411,10 +473,132 @@ pub mod pallet {
 			Self::try_remove_vote(&target, index, Some(class), scope)?;
 			Ok(())
 		}
+
+		/// Vote in a poll as a person. If `aye`, the vote is to enact the proposal; otherwise it
+		/// is a vote to keep the status quo.
+		///
+		/// The dispatch origin of this call must prove personhood with the alias of the voter in
+		/// the context of the poll, as given by [`Pallet::alias_context`]. Voting again with the
+		/// same alias replaces the previous vote.
+		///
+		/// - `poll_index`: The index of the poll to vote for. It must be of a personhood class.
+		/// - `aye`: Whether the vote is in favour.
+		///
+		/// Emits `PersonVoted`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::vote_as_person())]
+		pub fn vote_as_person(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_index: PollIndexOf<T, I>,
+			aye: bool,
+		) -> DispatchResult {
+			let alias =
+				T::PersonhoodOrigin::ensure_origin(origin, &Self::alias_context(poll_index))?;
+			Self::try_vote_as_person(alias, poll_index, aye)
+		}
+
+		/// Remove the vote of a person for a poll.
+		///
+		/// The dispatch origin of this call must prove personhood with the alias that voted, in the
+		/// context of the poll.
+		///
+		/// - `poll_index`: The index of the poll of the vote to be removed.
+		///
+		/// Emits `PersonalVoteRemoved`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::remove_personal_vote())]
+		pub fn remove_personal_vote(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_index: PollIndexOf<T, I>,
+		) -> DispatchResult {
+			let alias =
+				T::PersonhoodOrigin::ensure_origin(origin, &Self::alias_context(poll_index))?;
+			let (aye, votes) =
+				PersonalVotes::<T, I>::take(poll_index, alias).ok_or(Error::<T, I>::NotVoter)?;
+			T::Polls::access_poll(poll_index, |poll_status| {
+				if let PollStatus::Ongoing(tally, _) = poll_status {
+					tally.reduce(aye, votes);
+				}
+			});
+			Self::deposit_event(Event::PersonalVoteRemoved { alias, poll_index });
+			Ok(())
+		}
+
+		/// Remove up to `limit` votes of persons from a poll which is no longer ongoing.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_index`: The index of the completed or cancelled poll.
+		/// - `limit`: The maximum number of votes to remove.
+		///
+		/// Emits `PersonalVotesCleared`.
+		///
+		/// Weight: `O(limit)`, refunded to the number of votes removed.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::clear_personal_votes(*limit))]
+		pub fn clear_personal_votes(
+			origin: OriginFor<T>,
+			#[pallet::compact] poll_index: PollIndexOf<T, I>,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(T::Polls::as_ongoing(poll_index).is_none(), Error::<T, I>::NoPermission);
+			let removed =
+				PersonalVotes::<T, I>::drain_prefix(poll_index).take(limit as usize).count() as u32;
+			Self::deposit_event(Event::PersonalVotesCleared { poll_index, removed });
+			Ok(Some(T::WeightInfo::clear_personal_votes(removed)).into())
+		}
 	}
 }
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The context in which a person has their alias for voting on the poll `poll_index`.
+	///
+	/// The context is unique to the poll and to this instance of the pallet, so that each person
+	/// has exactly one alias per poll, and the aliases of a person on different polls cannot be
+	/// linked.
+	pub fn alias_context(poll_index: PollIndexOf<T, I>) -> Context {
+		let pallet_index = <Self as PalletInfoAccess>::index() as u32;
+		(CONVICTION_VOTING_ID, pallet_index, poll_index).using_encoded(blake2_256)
+	}
+
+	/// Actually enact the vote of a person, if legit.
+	fn try_vote_as_person(
+		alias: Alias,
+		poll_index: PollIndexOf<T, I>,
+		aye: bool,
+	) -> DispatchResult {
+		T::Polls::try_access_poll(poll_index, |poll_status| {
+			let (tally, class) = poll_status.ensure_ongoing().ok_or(Error::<T, I>::NotOngoing)?;
+			ensure!(T::PersonhoodClasses::contains(&class), Error::<T, I>::AccountsOnly);
+			// A person has a single alias per poll, so a second vote replaces the first one.
+			if let Some((old_aye, old_votes)) = PersonalVotes::<T, I>::get(poll_index, alias) {
+				tally.reduce(old_aye, old_votes);
+			}
+			let votes = Self::personal_votes();
+			tally.increase(aye, votes);
+			PersonalVotes::<T, I>::insert(poll_index, alias, (aye, votes));
+			Self::deposit_event(Event::PersonVoted { alias, poll_index, aye });
+			Ok(())
+		})
+	}
+
+	/// The votes and support of a single person.
+	///
+	/// The support is the share of a single person in `MaxTurnout`, rounded up, so that the
+	/// support of a poll is the proportion of the population in favour of it.
+	///
+	/// The share is taken from the population at the time of the call and is stored alongside the
+	/// vote, which is later reduced by exactly that amount. Votes cast before the population
+	/// changes therefore keep their original share until they are replaced or removed.
+	fn personal_votes() -> Delegations<BalanceOf<T, I>> {
+		let population: BalanceOf<T, I> =
+			T::PersonhoodOrigin::default().active_count().max(1).into();
+		let capital =
+			T::MaxTurnout::get().saturating_add(population.saturating_sub(One::one())) / population;
+		Delegations { votes: T::VotesPerPerson::get(), capital }
+	}
+
 	/// Actually enact a vote, if legit.
 	fn try_vote(
 		who: &T::AccountId,
@@ -430,6 +614,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		T::Polls::try_access_poll(poll_index, |poll_status| {
 			let (tally, class) = poll_status.ensure_ongoing().ok_or(Error::<T, I>::NotOngoing)?;
+			ensure!(!T::PersonhoodClasses::contains(&class), Error::<T, I>::PersonsOnly);
 			VotingFor::<T, I>::try_mutate(who, &class, |voting| {
 				if let Voting::Casting(Casting { ref mut votes, delegations, .. }) = voting {
 					match votes.binary_search_by_key(&poll_index, |i| i.0) {
@@ -619,6 +804,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	) -> Result<u32, DispatchError> {
 		ensure!(who != target, Error::<T, I>::Nonsense);
 		T::Polls::classes().binary_search(&class).map_err(|_| Error::<T, I>::BadClass)?;
+		ensure!(!T::PersonhoodClasses::contains(&class), Error::<T, I>::PersonsOnly);
 		ensure!(balance <= T::Currency::total_balance(&who), Error::<T, I>::InsufficientFunds);
 		let votes =
 			VotingFor::<T, I>::try_mutate(&who, &class, |voting| -> Result<u32, DispatchError> {
@@ -21,7 +21,7 @@ use std::{cell::RefCell, collections::BTreeMap};
 
 use frame_support::{
 	assert_noop, assert_ok, derive_impl, parameter_types,
-	traits::{ConstU32, ConstU64, Contains, Polling, VoteTally},
+	traits::{ConstU32, ConstU64, Contains, EnsureOrigin, Polling, VoteTally},
 };
 use sp_runtime::BuildStorage;
 
@@ -81,7 +81,7 @@ impl Polling<TallyOf<Test>> for TestPolls {
 	type Moment = u64;
 	type Class = u8;
 	fn classes() -> Vec<u8> {
-		vec![0, 1, 2]
+		vec![0, 1, 2, 3]
 	}
 	fn as_ongoing(index: u8) -> Option<(TallyOf<Test>, Self::Class)> {
 		Polls::get().remove(&index).and_then(|x| {
@@ -146,6 +146,38 @@ impl Polling<TallyOf<Test>> for TestPolls {
 	}
 }
 
+/// Polls of class 3 are decided by persons.
+pub struct PersonhoodClasses;
+impl Contains<u8> for PersonhoodClasses {
+	fn contains(class: &u8) -> bool {
+		*class == 3
+	}
+}
+
+parameter_types! {
+	pub static Population: u32 = 6;
+}
+
+/// Every signed account is a person, whose alias is derived from the account and the context.
+#[derive(Default)]
+pub struct EnsurePerson;
+impl EnsureOriginWithArg<RuntimeOrigin, Context> for EnsurePerson {
+	type Success = Alias;
+	fn try_origin(o: RuntimeOrigin, context: &Context) -> Result<Alias, RuntimeOrigin> {
+		let who = frame_system::EnsureSigned::<u64>::try_origin(o)?;
+		Ok((who, context).using_encoded(blake2_256))
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(_: &Context) -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(1))
+	}
+}
+impl CountedMembers for EnsurePerson {
+	fn active_count(&self) -> u32 {
+		Population::get()
+	}
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = pallet_balances::Pallet<Self>;
@@ -156,6 +188,9 @@ impl Config for Test {
 	type Polls = TestPolls;
 	type BlockNumberProvider = System;
 	type VotingHooks = HooksHandler;
+	type PersonhoodOrigin = EnsurePerson;
+	type PersonhoodClasses = PersonhoodClasses;
+	type VotesPerPerson = ConstU64<10>;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -1051,3 +1086,184 @@ fn voting_hooks_are_called_correctly() {
 		assert_eq!(Balances::usable_balance(1), 5);
 	});
 }
+
+fn alias_of(who: u64, poll_index: u8) -> Alias {
+	(who, Voting::alias_context(poll_index)).using_encoded(blake2_256)
+}
+
+#[test]
+fn personal_voting_works() {
+	new_test_ext().execute_with(|| {
+		Polls::set(vec![(3, Ongoing(Tally::new(3), 3))].into_iter().collect());
+
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(1), 3, true));
+		System::assert_last_event(tests::RuntimeEvent::Voting(Event::PersonVoted {
+			alias: alias_of(1, 3),
+			poll_index: 3,
+			aye: true,
+		}));
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(2), 3, false));
+		// Each person in favour adds their share of the issuance of 210 among 6 persons.
+		assert_eq!(tally(3), Tally::from_parts(10, 10, 35));
+
+		// Votes of persons count the same, regardless of their balance, and lock nothing.
+		assert_eq!(Balances::usable_balance(1), 10);
+		assert_eq!(Balances::usable_balance(2), 20);
+		assert_eq!(
+			PersonalVotes::<Test>::get(3, alias_of(1, 3)),
+			Some((true, Delegations { votes: 10, capital: 35 }))
+		);
+		assert_eq!(
+			PersonalVotes::<Test>::get(3, alias_of(2, 3)),
+			Some((false, Delegations { votes: 10, capital: 35 }))
+		);
+	});
+}
+
+#[test]
+fn personal_vote_can_only_be_cast_once() {
+	new_test_ext().execute_with(|| {
+		Polls::set(
+			vec![(3, Ongoing(Tally::new(3), 3)), (4, Ongoing(Tally::new(3), 3))]
+				.into_iter()
+				.collect(),
+		);
+
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(1), 3, true));
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(1), 3, true));
+		assert_eq!(tally(3), Tally::from_parts(10, 0, 35));
+
+		// Voting again replaces the previous vote.
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(1), 3, false));
+		assert_eq!(tally(3), Tally::from_parts(0, 10, 0));
+		assert_eq!(PersonalVotes::<Test>::iter_prefix(3).count(), 1);
+
+		// The same person has a different, unlinkable alias on another poll.
+		assert_ne!(Voting::alias_context(3), Voting::alias_context(4));
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(1), 4, true));
+		assert_eq!(tally(4), Tally::from_parts(10, 0, 35));
+		assert_eq!(PersonalVotes::<Test>::get(4, alias_of(1, 3)), None);
+		assert!(PersonalVotes::<Test>::contains_key(4, alias_of(1, 4)));
+	});
+}
+
+#[test]
+fn personhood_classes_only_accept_persons() {
+	new_test_ext().execute_with(|| {
+		Polls::set(
+			vec![(3, Ongoing(Tally::new(0), 0)), (4, Ongoing(Tally::new(3), 3))]
+				.into_iter()
+				.collect(),
+		);
+
+		assert_noop!(
+			Voting::vote(RuntimeOrigin::signed(1), 4, aye(10, 0)),
+			Error::<Test>::PersonsOnly
+		);
+		assert_noop!(
+			Voting::delegate(RuntimeOrigin::signed(1), 3, 2, Conviction::Locked1x, 10),
+			Error::<Test>::PersonsOnly
+		);
+		assert_noop!(
+			Voting::vote_as_person(RuntimeOrigin::signed(1), 3, true),
+			Error::<Test>::AccountsOnly
+		);
+		assert_noop!(
+			Voting::vote_as_person(RuntimeOrigin::root(), 4, true),
+			DispatchError::BadOrigin
+		);
+
+		// Delegations on other classes don't count for persons.
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(2), 0, 1, Conviction::Locked1x, 20));
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(1), 4, true));
+		assert_eq!(tally(4), Tally::from_parts(10, 0, 35));
+	});
+}
+
+#[test]
+fn personal_vote_can_be_removed() {
+	new_test_ext().execute_with(|| {
+		Polls::set(vec![(3, Ongoing(Tally::new(3), 3))].into_iter().collect());
+
+		assert_noop!(
+			Voting::remove_personal_vote(RuntimeOrigin::signed(1), 3),
+			Error::<Test>::NotVoter
+		);
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(1), 3, true));
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(2), 3, true));
+		assert_eq!(tally(3), Tally::from_parts(20, 0, 70));
+
+		assert_ok!(Voting::remove_personal_vote(RuntimeOrigin::signed(1), 3));
+		System::assert_last_event(tests::RuntimeEvent::Voting(Event::PersonalVoteRemoved {
+			alias: alias_of(1, 3),
+			poll_index: 3,
+		}));
+		assert_eq!(tally(3), Tally::from_parts(10, 0, 35));
+		assert_eq!(PersonalVotes::<Test>::get(3, alias_of(1, 3)), None);
+
+		// Votes can be removed after the poll has ended too.
+		Polls::set(vec![(3, Completed(1, true))].into_iter().collect());
+		assert_ok!(Voting::remove_personal_vote(RuntimeOrigin::signed(2), 3));
+		assert_eq!(PersonalVotes::<Test>::iter_prefix(3).count(), 0);
+	});
+}
+
+#[test]
+fn personal_votes_can_be_cleared_once_poll_ends() {
+	new_test_ext().execute_with(|| {
+		Polls::set(vec![(3, Ongoing(Tally::new(3), 3))].into_iter().collect());
+		for who in 1..=5 {
+			assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(who), 3, who % 2 == 0));
+		}
+		assert_eq!(tally(3), Tally::from_parts(20, 30, 70));
+
+		assert_noop!(
+			Voting::clear_personal_votes(RuntimeOrigin::signed(6), 3, 10),
+			Error::<Test>::NoPermission
+		);
+
+		Polls::set(vec![(3, Completed(1, false))].into_iter().collect());
+		assert_ok!(Voting::clear_personal_votes(RuntimeOrigin::signed(6), 3, 3));
+		System::assert_last_event(tests::RuntimeEvent::Voting(Event::PersonalVotesCleared {
+			poll_index: 3,
+			removed: 3,
+		}));
+		assert_eq!(PersonalVotes::<Test>::iter_prefix(3).count(), 2);
+
+		assert_ok!(Voting::clear_personal_votes(RuntimeOrigin::signed(6), 3, 3));
+		System::assert_last_event(tests::RuntimeEvent::Voting(Event::PersonalVotesCleared {
+			poll_index: 3,
+			removed: 2,
+		}));
+		assert_eq!(PersonalVotes::<Test>::iter_prefix(3).count(), 0);
+	});
+}
+
+#[test]
+fn personal_support_passes_referendum() {
+	new_test_ext().execute_with(|| {
+		Polls::set(vec![(3, Ongoing(Tally::new(3), 3))].into_iter().collect());
+
+		// Four of the six persons are in favour, though they hold less than half of the issuance.
+		for who in 1..=4 {
+			assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(who), 3, true));
+		}
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(5), 3, false));
+		assert_eq!(tally(3), Tally::from_parts(40, 10, 140));
+
+		// Support is the share of the population in favour, which passes a track requiring the
+		// support of a majority.
+		let min_support = Perbill::from_percent(50);
+		assert_eq!(tally(3).support(3), Perbill::from_rational(2u32, 3u32));
+		assert!(tally(3).support(3) >= min_support);
+		assert_eq!(tally(3).approval(3), Perbill::from_percent(80));
+		assert!(Perbill::from_rational(100u64, Balances::total_issuance()) < min_support);
+
+		// A vote is removed with the support it was counted for, even if the population changed.
+		Population::set(3);
+		assert_ok!(Voting::remove_personal_vote(RuntimeOrigin::signed(1), 3));
+		assert_eq!(tally(3), Tally::from_parts(30, 10, 105));
+		assert_ok!(Voting::vote_as_person(RuntimeOrigin::signed(1), 3, true));
+		assert_eq!(tally(3), Tally::from_parts(40, 10, 175));
+	});
+}
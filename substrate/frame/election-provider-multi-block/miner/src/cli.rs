@@ -0,0 +1,190 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The command line interface of the miner.
+use crate::{
+	client::Client,
+	config::{MinerConfig16, MinerConfig24, SolutionType},
+	miner,
+	snapshot::{Snapshot, SnapshotSource},
+	solver::{set_solver_options, Algorithm, SolverOptions},
+	submit::{self, signer_from_uri, SubmissionOptions, Submitter},
+	LOG_TARGET,
+};
+use clap::Parser;
+use codec::Encode;
+use pallet_election_provider_multi_block::{unsigned::miner::MinerConfig, PageIndex};
+use sp_core::H256;
+use std::path::PathBuf;
+
+// Parsed command instructions from the command line
+#[derive(Parser, Debug)]
+#[clap(author, about, version)]
+pub struct CliCommand {
+	/// The node url to connect to.
+	#[clap(long, global = true, default_value = "ws://127.0.0.1:9944")]
+	pub uri: String,
+
+	/// The solution type of the runtime. Must match the `MinerConfig::Solution` of the runtime.
+	#[clap(long, global = true, value_enum, default_value_t = SolutionType::Npos16)]
+	pub solution_type: SolutionType,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub solver: SolverParams,
+
+	#[allow(missing_docs)]
+	#[clap(subcommand)]
+	pub command: Command,
+}
+
+/// The parameters of the election algorithm.
+#[derive(Parser, Debug, Clone)]
+pub struct SolverParams {
+	/// The election algorithm.
+	#[clap(long, global = true, value_enum, default_value_t = Algorithm::SeqPhragmen)]
+	pub algorithm: Algorithm,
+
+	/// The number of balancing iterations. `0` disables balancing.
+	#[clap(long, global = true, default_value = "10")]
+	pub balancing_iterations: usize,
+
+	/// The tolerance of the balancing algorithm.
+	#[clap(long, global = true, default_value = "0")]
+	pub balancing_tolerance: u128,
+
+	/// Do not reduce the edges of the solution.
+	#[clap(long, global = true)]
+	pub no_reduce: bool,
+}
+
+/// The subcommands of the miner.
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+	/// Mine a solution for the current snapshot and log its score, without submitting it.
+	Mine(MineCmd),
+	/// Follow the chain, and mine and submit a solution in the signed phase of every round.
+	Monitor(MonitorCmd),
+}
+
+/// The `mine` subcommand.
+#[derive(Parser, Debug)]
+pub struct MineCmd {
+	/// The block at which to read the snapshot. Defaults to the latest finalized block.
+	#[clap(long)]
+	pub at: Option<H256>,
+
+	/// Read the state from this snapshot file, instead of the node.
+	#[clap(long, conflicts_with_all = ["at", "save_snapshot"])]
+	pub snapshot: Option<PathBuf>,
+
+	/// Write the state read from the node to this snapshot file.
+	#[clap(long)]
+	pub save_snapshot: Option<PathBuf>,
+
+	/// The number of most significant pages to mine. Defaults to all pages.
+	#[clap(long)]
+	pub pages: Option<PageIndex>,
+
+	/// Write the SCALE encoded solution to this file.
+	#[clap(long)]
+	pub output: Option<PathBuf>,
+}
+
+/// The `monitor` subcommand.
+#[derive(Parser, Debug)]
+pub struct MonitorCmd {
+	/// The secret URI of the submitter, e.g. `//Alice` or a mnemonic.
+	#[clap(long, env = "SEED")]
+	pub suri: String,
+
+	/// The maximum deposit to hold for a submission.
+	#[clap(long)]
+	pub max_deposit: Option<u128>,
+
+	/// The number of failed transactions after which the submission of a round is abandoned.
+	#[clap(long, default_value = "5")]
+	pub retries: u32,
+
+	/// Run all checks, but do not submit any transaction.
+	#[clap(long)]
+	pub dry_run: bool,
+}
+
+/// Run the miner with the given command.
+pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
+	env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+	let SolverParams { algorithm, balancing_iterations, balancing_tolerance, no_reduce } =
+		cmd.solver;
+	set_solver_options(SolverOptions { algorithm, balancing_iterations, balancing_tolerance });
+
+	let tokio_runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+	tokio_runtime.block_on(async {
+		match cmd.solution_type {
+			SolutionType::Npos16 =>
+				run_with::<MinerConfig16>(&cmd.uri, cmd.command, !no_reduce).await,
+			SolutionType::Npos24 =>
+				run_with::<MinerConfig24>(&cmd.uri, cmd.command, !no_reduce).await,
+		}
+	})
+}
+
+async fn run_with<T: MinerConfig>(
+	uri: &str,
+	command: Command,
+	do_reduce: bool,
+) -> anyhow::Result<()> {
+	let client = Client::new(uri).await?;
+	let parameters = client.chain_parameters()?;
+	log::info!(target: LOG_TARGET, "connected to {uri}, election parameters: {parameters:?}");
+	parameters.apply();
+
+	match command {
+		Command::Mine(MineCmd { at, snapshot, save_snapshot, pages, output }) => {
+			let source = match snapshot {
+				Some(path) => SnapshotSource::File(path),
+				None => SnapshotSource::Live { uri: uri.to_string(), at, save: save_snapshot },
+			};
+			let snapshot = Snapshot::<T>::fetch(source, pages.unwrap_or(parameters.pages)).await?;
+			let solution = miner::mine(&snapshot, do_reduce)?;
+			miner::ensure_page_lengths(&solution, parameters.max_page_length)?;
+
+			log::info!(
+				target: LOG_TARGET,
+				"round: {}, score: {:?}",
+				solution.round,
+				solution.score,
+			);
+			for (page, solution_page) in miner::pages_of(&solution) {
+				log::info!(target: LOG_TARGET, "page {page}: {} bytes", solution_page.encoded_size());
+			}
+			if let Some(output) = output {
+				std::fs::write(&output, solution.encode())?;
+				log::info!(target: LOG_TARGET, "solution written to {}", output.display());
+			}
+		},
+		Command::Monitor(MonitorCmd { suri, max_deposit, retries, dry_run }) => {
+			let signer = signer_from_uri(&suri)?;
+			let submitter =
+				Submitter::new(client, signer, SubmissionOptions { max_deposit, retries, dry_run });
+			log::info!(target: LOG_TARGET, "submitting as {}", submitter.account());
+			submit::monitor::<T>(&submitter, do_reduce).await?;
+		},
+	}
+
+	Ok(())
+}
@@ -0,0 +1,333 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A thin client over [`subxt`] to read the state of the election pallets.
+//!
+//! The miner is not tied to a particular runtime, hence it does not use generated metadata types.
+//! Storage is read by its raw key and decoded into the types of this module, which mirror the
+//! encoding of their counterparts in `pallet-election-provider-multi-block`.
+
+use crate::{config::ChainParameters, Error, ELECTION_PALLET, SIGNED_PALLET, VERIFIER_PALLET};
+use codec::{Decode, Encode};
+use frame_support::{
+	storage::storage_prefix, view_functions::ViewFunctionDispatchError, Blake2_128Concat,
+	StorageHasher, Twox64Concat,
+};
+use pallet_election_provider_multi_block::{ElectionScore, PageIndex};
+use sp_core::H256;
+use sp_runtime::{AccountId32, Perbill};
+use subxt::{OnlineClient, PolkadotConfig};
+
+/// The [`subxt::Config`] used by the miner.
+pub type ChainConfig = PolkadotConfig;
+
+/// The ratio of the normal block length that a single solution page may use.
+///
+/// This is the same ratio that runtimes use for the `MinerMaxLength` of the offchain worker miner.
+const PAGE_LENGTH_RATIO: Perbill = Perbill::from_percent(75);
+
+/// The signature of the `deposit_for` view function of the signed pallet.
+///
+/// The id of a view function is derived from its signature, see
+/// `frame_support::view_functions::ViewFunctionId`.
+const DEPOSIT_FOR_SIGNATURE: &str = "deposit_for(T::AccountId,u32) -> BalanceOf<T>";
+
+/// Mirror of `pallet_election_provider_multi_block::Phase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode, Default)]
+pub enum Phase {
+	/// Nothing is happening.
+	#[default]
+	Off,
+	/// The signed phase, with the given number of blocks left.
+	Signed(u32),
+	/// The signed validation phase, with the given number of blocks left.
+	SignedValidation(u32),
+	/// The unsigned phase, with the given number of blocks left.
+	Unsigned(u32),
+	/// The snapshot is being created, with the given number of pages left.
+	Snapshot(PageIndex),
+	/// The snapshot is done.
+	Done,
+	/// The result is being exported, and the given page was the last one received.
+	Export(PageIndex),
+	/// The emergency phase.
+	Emergency,
+}
+
+impl Phase {
+	/// Whether this is the signed phase.
+	pub fn is_signed(&self) -> bool {
+		matches!(self, Phase::Signed(_))
+	}
+}
+
+/// Mirror of `pallet_election_provider_multi_block::signed::SubmissionMetadata`.
+#[derive(Debug, Clone, PartialEq, Eq, Decode)]
+pub struct SubmissionMetadata {
+	/// The amount of deposit that has been held in reserve.
+	pub deposit: u128,
+	/// The amount of transaction fee that this submission has cost so far.
+	pub fee: u128,
+	/// The amount of rewards expected for this submission.
+	pub reward: u128,
+	/// The score that this submission is claiming to achieve.
+	pub claimed_score: ElectionScore,
+	/// The pages that have been submitted so far.
+	pub pages: Vec<bool>,
+}
+
+impl SubmissionMetadata {
+	/// The indices of the pages that have not been submitted yet.
+	pub fn missing_pages(&self) -> Vec<PageIndex> {
+		self.pages
+			.iter()
+			.enumerate()
+			.filter_map(|(page, submitted)| (!submitted).then_some(page as PageIndex))
+			.collect()
+	}
+}
+
+/// The prefix of `frame_system::AccountInfo` and `pallet_balances::AccountData` that the miner
+/// needs.
+#[derive(Decode)]
+struct AccountInfo {
+	_nonce: u32,
+	_consumers: u32,
+	_providers: u32,
+	_sufficients: u32,
+	free: u128,
+	_reserved: u128,
+	frozen: u128,
+}
+
+/// The raw storage key of the given item, with each of `keys` hashed with [`Twox64Concat`].
+pub fn twox_64_concat_key(pallet: &str, item: &str, keys: &[&[u8]]) -> Vec<u8> {
+	let mut key = storage_prefix(pallet.as_bytes(), item.as_bytes()).to_vec();
+	keys.iter().for_each(|k| key.extend(Twox64Concat::hash(k)));
+	key
+}
+
+/// A client to a node of a chain that runs the election pallets.
+#[derive(Clone)]
+pub struct Client {
+	api: OnlineClient<ChainConfig>,
+	uri: String,
+}
+
+impl Client {
+	/// Connect to the node at `uri`.
+	pub async fn new(uri: &str) -> Result<Self, Error> {
+		let api = OnlineClient::<ChainConfig>::from_insecure_url(uri).await?;
+		Ok(Self { api, uri: uri.to_string() })
+	}
+
+	/// The underlying [`subxt`] client.
+	pub fn api(&self) -> &OnlineClient<ChainConfig> {
+		&self.api
+	}
+
+	/// The uri of the node.
+	pub fn uri(&self) -> &str {
+		&self.uri
+	}
+
+	/// Decode the given constant from the metadata.
+	pub fn constant<V: Decode>(
+		&self,
+		pallet: &'static str,
+		name: &'static str,
+	) -> Result<V, Error> {
+		let metadata = self.api.metadata();
+		let constant = metadata
+			.pallet_by_name(pallet)
+			.and_then(|p| p.constant_by_name(name))
+			.ok_or(Error::MissingConstant(pallet, name))?;
+		Ok(V::decode(&mut constant.value())?)
+	}
+
+	/// Read the bounds of the election from the metadata.
+	pub fn chain_parameters(&self) -> Result<ChainParameters, Error> {
+		// `frame_system::limits::BlockLength` is a `PerDispatchClass<u32>`, with `normal` first.
+		let (normal_length, _operational, _mandatory): (u32, u32, u32) =
+			self.constant("System", "BlockLength")?;
+
+		Ok(ChainParameters {
+			pages: self.constant(ELECTION_PALLET, "Pages")?,
+			voter_snapshot_per_block: self.constant(ELECTION_PALLET, "VoterSnapshotPerBlock")?,
+			target_snapshot_per_block: self.constant(ELECTION_PALLET, "TargetSnapshotPerBlock")?,
+			max_winners_per_page: self.constant(VERIFIER_PALLET, "MaxWinnersPerPage")?,
+			max_backers_per_winner: self.constant(VERIFIER_PALLET, "MaxBackersPerWinner")?,
+			max_backers_per_winner_final: self
+				.constant(VERIFIER_PALLET, "MaxBackersPerWinnerFinal")?,
+			max_page_length: PAGE_LENGTH_RATIO * normal_length,
+		})
+	}
+
+	/// The `SolutionImprovementThreshold` of the verifier.
+	pub fn solution_improvement_threshold(&self) -> Result<Perbill, Error> {
+		self.constant(VERIFIER_PALLET, "SolutionImprovementThreshold")
+	}
+
+	/// Read and decode the value at the given raw storage key.
+	pub async fn storage<V: Decode>(&self, at: H256, key: Vec<u8>) -> Result<Option<V>, Error> {
+		match self.api.storage().at(at).fetch_raw(key).await? {
+			Some(raw) => Ok(Some(V::decode(&mut &raw[..])?)),
+			None => Ok(None),
+		}
+	}
+
+	/// The hash of the latest finalized block.
+	pub async fn finalized_head(&self) -> Result<H256, Error> {
+		Ok(self.api.backend().latest_finalized_block_ref().await?.hash())
+	}
+
+	/// The current round of the election.
+	pub async fn round(&self, at: H256) -> Result<u32, Error> {
+		let key = twox_64_concat_key(ELECTION_PALLET, "Round", &[]);
+		Ok(self.storage(at, key).await?.unwrap_or_default())
+	}
+
+	/// The current phase of the election.
+	pub async fn phase(&self, at: H256) -> Result<Phase, Error> {
+		let key = twox_64_concat_key(ELECTION_PALLET, "CurrentPhase", &[]);
+		Ok(self.storage(at, key).await?.unwrap_or_default())
+	}
+
+	/// The signed submissions of `round`, sorted by ascending score.
+	pub async fn sorted_scores(
+		&self,
+		at: H256,
+		round: u32,
+	) -> Result<Vec<(AccountId32, ElectionScore)>, Error> {
+		let key = twox_64_concat_key(SIGNED_PALLET, "SortedScores", &[&round.encode()]);
+		Ok(self.storage(at, key).await?.unwrap_or_default())
+	}
+
+	/// The metadata of the submission of `who` in `round`, if any.
+	pub async fn submission_metadata(
+		&self,
+		at: H256,
+		round: u32,
+		who: &AccountId32,
+	) -> Result<Option<SubmissionMetadata>, Error> {
+		let key = twox_64_concat_key(
+			SIGNED_PALLET,
+			"SubmissionMetadataStorage",
+			&[&round.encode(), &who.encode()],
+		);
+		self.storage(at, key).await
+	}
+
+	/// The score of the solution that is already verified and queued in `round`, if any.
+	pub async fn queued_score(&self, at: H256, round: u32) -> Result<Option<ElectionScore>, Error> {
+		let key = twox_64_concat_key(VERIFIER_PALLET, "QueuedSolutionScore", &[&round.encode()]);
+		self.storage(at, key).await
+	}
+
+	/// The minimum score that any solution must attain.
+	pub async fn minimum_score(&self, at: H256) -> Result<Option<ElectionScore>, Error> {
+		let key = twox_64_concat_key(VERIFIER_PALLET, "MinimumScore", &[]);
+		self.storage(at, key).await
+	}
+
+	/// The balance of `who` that can be held as deposit.
+	pub async fn spendable_balance(&self, at: H256, who: &AccountId32) -> Result<u128, Error> {
+		let mut key = storage_prefix(b"System", b"Account").to_vec();
+		key.extend(Blake2_128Concat::hash(&who.encode()));
+		let existential_deposit: u128 = self.constant("Balances", "ExistentialDeposit")?;
+		Ok(self
+			.storage::<AccountInfo>(at, key)
+			.await?
+			.map(|info| info.free.saturating_sub(info.frozen.max(existential_deposit)))
+			.unwrap_or_default())
+	}
+
+	/// The deposit that `who` has to hold for a submission of `pages` pages, as computed by the
+	/// `deposit_for` view function of the signed pallet.
+	pub async fn deposit_for(
+		&self,
+		at: H256,
+		who: &AccountId32,
+		pages: u32,
+	) -> Result<u128, Error> {
+		let id = (
+			sp_crypto_hashing::twox_128(SIGNED_PALLET.as_bytes()),
+			sp_crypto_hashing::twox_128(DEPOSIT_FOR_SIGNATURE.as_bytes()),
+		);
+		let params = (id, (who, pages).encode()).encode();
+		let result: Result<Vec<u8>, ViewFunctionDispatchError> = self
+			.api
+			.runtime_api()
+			.at(at)
+			.call_raw("RuntimeViewFunction_execute_view_function", Some(&params))
+			.await?;
+		let output = result.map_err(|e| Error::ViewFunction("deposit_for", format!("{e:?}")))?;
+		Ok(u128::decode(&mut &output[..])?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn phase_decodes_like_the_pallet() {
+		// `Signed(5)`, with a `u32` block number.
+		let encoded = (1u8, 5u32).encode();
+		assert_eq!(Phase::decode(&mut &encoded[..]).unwrap(), Phase::Signed(5));
+		assert_eq!(Phase::decode(&mut &[7u8][..]).unwrap(), Phase::Emergency);
+	}
+
+	#[test]
+	fn missing_pages_works() {
+		let metadata = SubmissionMetadata {
+			deposit: 10,
+			fee: 1,
+			reward: 5,
+			claimed_score: Default::default(),
+			pages: vec![true, false, true, false],
+		};
+		let encoded =
+			(10u128, 1u128, 5u128, ElectionScore::default(), vec![true, false, true, false])
+				.encode();
+		assert_eq!(SubmissionMetadata::decode(&mut &encoded[..]).unwrap(), metadata);
+		assert_eq!(metadata.missing_pages(), vec![1, 3]);
+	}
+
+	#[test]
+	fn storage_keys_match_frame_support() {
+		#[frame_support::storage_alias]
+		type SubmissionMetadataStorage = StorageDoubleMap<
+			MultiBlockElectionSigned,
+			Twox64Concat,
+			u32,
+			Twox64Concat,
+			AccountId32,
+			u32,
+		>;
+
+		let who = AccountId32::new([1; 32]);
+		assert_eq!(
+			twox_64_concat_key(
+				SIGNED_PALLET,
+				"SubmissionMetadataStorage",
+				&[&3u32.encode(), &who.encode()]
+			),
+			SubmissionMetadataStorage::hashed_key_for(3u32, who),
+		);
+	}
+}
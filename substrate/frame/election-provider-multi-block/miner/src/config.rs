@@ -0,0 +1,195 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [`MinerConfig`] implementations whose bounds are set at startup.
+//!
+//! The bounds of a [`MinerConfig`] are types implementing [`Get`], yet the miner only learns them
+//! once it has connected to a chain. The parameters of this module are backed by atomics, and are
+//! set once via [`ChainParameters::apply`].
+//!
+//! The solution type, on the other hand, is a compile time property of the runtime. This module
+//! provides one [`MinerConfig`] per supported [`SolutionType`]; the one that is used must match the
+//! `MinerConfig::Solution` of the runtime, otherwise the submitted pages cannot be decoded.
+
+use crate::solver::Solver;
+use frame_election_provider_support::PageIndex;
+use frame_support::traits::{ConstU32, Get};
+use pallet_election_provider_multi_block::unsigned::miner::MinerConfig;
+use sp_core::H256;
+use sp_runtime::{AccountId32, PerU16};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+macro_rules! dynamic_parameter {
+	($( $(#[$attr:meta])* $name:ident ),* $(,)?) => {
+		$(
+			$(#[$attr])*
+			pub struct $name;
+
+			impl $name {
+				fn value() -> &'static AtomicU32 {
+					static VALUE: AtomicU32 = AtomicU32::new(0);
+					&VALUE
+				}
+
+				/// Set the value of this parameter.
+				pub fn set(value: u32) {
+					Self::value().store(value, Ordering::Relaxed);
+				}
+			}
+
+			impl Get<u32> for $name {
+				fn get() -> u32 {
+					Self::value().load(Ordering::Relaxed)
+				}
+			}
+		)*
+	};
+}
+
+dynamic_parameter!(
+	/// Number of pages of the election.
+	Pages,
+	/// Maximum number of voters per snapshot page.
+	VoterSnapshotPerBlock,
+	/// Maximum number of targets per snapshot page.
+	TargetSnapshotPerBlock,
+	/// Maximum number of winners per solution page.
+	MaxWinnersPerPage,
+	/// Maximum number of backers per winner, per solution page.
+	MaxBackersPerWinner,
+	/// Maximum number of backers per winner, across all pages.
+	MaxBackersPerWinnerFinal,
+	/// Maximum encoded length of all pages of a solution.
+	MaxLength,
+);
+
+/// The bounds of the election, as configured in the runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainParameters {
+	/// `MultiBlockElection::Pages`.
+	pub pages: PageIndex,
+	/// `MultiBlockElection::VoterSnapshotPerBlock`.
+	pub voter_snapshot_per_block: u32,
+	/// `MultiBlockElection::TargetSnapshotPerBlock`.
+	pub target_snapshot_per_block: u32,
+	/// `MultiBlockElectionVerifier::MaxWinnersPerPage`.
+	pub max_winners_per_page: u32,
+	/// `MultiBlockElectionVerifier::MaxBackersPerWinner`.
+	pub max_backers_per_winner: u32,
+	/// `MultiBlockElectionVerifier::MaxBackersPerWinnerFinal`.
+	pub max_backers_per_winner_final: u32,
+	/// The maximum encoded length of a single solution page.
+	///
+	/// Each page is submitted in its own transaction, so this is bound by the block length.
+	pub max_page_length: u32,
+}
+
+impl ChainParameters {
+	/// Set the dynamic parameters of this module to these values.
+	pub fn apply(&self) {
+		Pages::set(self.pages);
+		VoterSnapshotPerBlock::set(self.voter_snapshot_per_block);
+		TargetSnapshotPerBlock::set(self.target_snapshot_per_block);
+		MaxWinnersPerPage::set(self.max_winners_per_page);
+		MaxBackersPerWinner::set(self.max_backers_per_winner);
+		MaxBackersPerWinnerFinal::set(self.max_backers_per_winner_final);
+		MaxLength::set(self.max_page_length.saturating_mul(self.pages));
+	}
+
+	/// The parameters used by all tests of this crate.
+	///
+	/// The parameters are global, so tests must not use different values.
+	#[cfg(test)]
+	pub(crate) fn for_tests() -> Self {
+		Self {
+			pages: 3,
+			voter_snapshot_per_block: 4,
+			target_snapshot_per_block: 8,
+			max_winners_per_page: 8,
+			max_backers_per_winner: 4,
+			max_backers_per_winner_final: 12,
+			max_page_length: 1024,
+		}
+	}
+}
+
+/// The solution types supported by the miner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SolutionType {
+	/// 16 votes per voter, as used by Polkadot and Westend.
+	#[value(name = "npos-16")]
+	Npos16,
+	/// 24 votes per voter, as used by Kusama.
+	#[value(name = "npos-24")]
+	Npos24,
+}
+
+frame_election_provider_support::generate_solution_type!(
+	#[compact]
+	pub struct NposSolution16::<
+		VoterIndex = u32,
+		TargetIndex = u16,
+		Accuracy = PerU16,
+		MaxVoters = VoterSnapshotPerBlock,
+	>(16)
+);
+
+frame_election_provider_support::generate_solution_type!(
+	#[compact]
+	pub struct NposSolution24::<
+		VoterIndex = u32,
+		TargetIndex = u16,
+		Accuracy = PerU16,
+		MaxVoters = VoterSnapshotPerBlock,
+	>(24)
+);
+
+macro_rules! impl_miner_config {
+	($(#[$attr:meta])* $name:ident, $solution:ty, $votes:literal) => {
+		$(#[$attr])*
+		pub struct $name;
+
+		impl MinerConfig for $name {
+			type AccountId = AccountId32;
+			type Solution = $solution;
+			type Solver = Solver<AccountId32, PerU16>;
+			type MaxLength = MaxLength;
+			type MaxVotesPerVoter = ConstU32<$votes>;
+			type MaxWinnersPerPage = MaxWinnersPerPage;
+			type MaxBackersPerWinner = MaxBackersPerWinner;
+			type MaxBackersPerWinnerFinal = MaxBackersPerWinnerFinal;
+			type Pages = Pages;
+			type VoterSnapshotPerBlock = VoterSnapshotPerBlock;
+			type TargetSnapshotPerBlock = TargetSnapshotPerBlock;
+			type Hash = H256;
+		}
+	};
+}
+
+impl_miner_config!(
+	/// A [`MinerConfig`] for [`SolutionType::Npos16`].
+	MinerConfig16,
+	NposSolution16,
+	16
+);
+
+impl_miner_config!(
+	/// A [`MinerConfig`] for [`SolutionType::Npos24`].
+	MinerConfig24,
+	NposSolution24,
+	24
+);
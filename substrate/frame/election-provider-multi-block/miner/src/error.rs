@@ -0,0 +1,87 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The error type of the miner.
+
+use pallet_election_provider_multi_block::{ElectionScore, PageIndex};
+
+/// The errors that can happen while mining or submitting a solution.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// A [`subxt::Error`] wrapper error.
+	#[error(transparent)]
+	Subxt(#[from] subxt::Error),
+	/// A SCALE decoding error.
+	#[error(transparent)]
+	Codec(#[from] codec::Error),
+	/// Building the remote externalities failed.
+	#[error("Failed to build remote externalities: {0}")]
+	RemoteExternalities(&'static str),
+	/// A constant of the chain is missing from its metadata.
+	#[error("Constant {0}::{1} is missing from the metadata")]
+	MissingConstant(&'static str, &'static str),
+	/// Some part of the snapshot is missing.
+	#[error("Snapshot is not available: {0}")]
+	SnapshotUnavailable(String),
+	/// The base miner failed.
+	#[error("Failed to mine a solution: {0}")]
+	Miner(String),
+	/// The election is not in the signed phase.
+	#[error("The election is not in the signed phase")]
+	PhaseNotSigned,
+	/// The mined solution is not good enough to be submitted.
+	#[error("Mined score {0:?} is not better than {1:?}")]
+	WeakScore(ElectionScore, ElectionScore),
+	/// The round of the election changed since the solution was mined.
+	#[error("Solution was mined for round {mined}, but the current round is {current}")]
+	RoundChanged {
+		/// The round of the solution.
+		mined: u32,
+		/// The current round.
+		current: u32,
+	},
+	/// The submitter is already registered with another score in this round.
+	#[error("Already registered with score {0:?} in this round")]
+	AlreadyRegistered(ElectionScore),
+	/// The deposit of the submission is more than what the submitter can or wants to pay.
+	#[error("Deposit of {deposit} exceeds the spendable amount of {available}")]
+	InsufficientFunds {
+		/// The deposit required by the chain.
+		deposit: u128,
+		/// The amount that the submitter can spend.
+		available: u128,
+	},
+	/// A page of the solution does not fit in a single transaction.
+	#[error("Page {page} is {size} bytes, more than the limit of {limit}")]
+	PageTooLarge {
+		/// The index of the page.
+		page: PageIndex,
+		/// The encoded size of the page.
+		size: u32,
+		/// The maximum encoded size of the page.
+		limit: u32,
+	},
+	/// The secret URI of the submitter could not be parsed.
+	#[error("Invalid secret URI: {0}")]
+	InvalidSecretUri(String),
+	/// A submission could not be completed after all retries.
+	#[error("Submission not completed after {0} attempts")]
+	RetriesExhausted(u32),
+	/// A view function call failed.
+	#[error("View function {0} failed: {1}")]
+	ViewFunction(&'static str, String),
+}
@@ -0,0 +1,62 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Multi-Block Election Miner
+//!
+//! An off-chain miner for the signed phase of `pallet-election-provider-multi-block`.
+//!
+//! The miner is composed of the following steps, each of which lives in its own module and can be
+//! used on its own:
+//!
+//! 1. [`snapshot`]: fetch the paged voter and target snapshot of the current round, either from a
+//!    live node or from a `remote-externalities` state snapshot file.
+//! 2. [`miner`]: run the same [`BaseMiner`] that the `unsigned` pallet uses in its offchain worker,
+//!    with a [`solver::Solver`] that can be configured at runtime. The base miner already trims the
+//!    solution to the page limits of the chain.
+//! 3. [`submit`]: check the mined score against the queued best and the deposit against the free
+//!    balance of the submitter, then `register` and `submit_page` all pages. Pages that are lost in
+//!    a re-org are submitted again.
+//!
+//! The bounds of the chain are read from its metadata at startup and stored in the types of the
+//! [`config`] module, which in turn provide a [`MinerConfig`] implementation for each supported
+//! solution type.
+//!
+//! The `epmb-miner` binary wires all of these together, see [`cli`].
+//!
+//! [`BaseMiner`]: pallet_election_provider_multi_block::unsigned::miner::BaseMiner
+//! [`MinerConfig`]: pallet_election_provider_multi_block::unsigned::miner::MinerConfig
+
+pub mod cli;
+pub mod client;
+pub mod config;
+pub mod miner;
+pub mod snapshot;
+pub mod solver;
+pub mod submit;
+
+mod error;
+pub use error::Error;
+
+/// The log target of this crate.
+pub const LOG_TARGET: &str = "epmb-miner";
+
+/// The name of the main election pallet in the runtime.
+pub const ELECTION_PALLET: &str = "MultiBlockElection";
+/// The name of the verifier pallet in the runtime.
+pub const VERIFIER_PALLET: &str = "MultiBlockElectionVerifier";
+/// The name of the signed pallet in the runtime.
+pub const SIGNED_PALLET: &str = "MultiBlockElectionSigned";
@@ -0,0 +1,150 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mining a paged solution out of a [`Snapshot`].
+
+use crate::{snapshot::Snapshot, Error, LOG_TARGET};
+use codec::Encode;
+use pallet_election_provider_multi_block::{
+	unsigned::miner::{BaseMiner, MineInput, MinerConfig},
+	PageIndex, PagedRawSolution, Pagify, SolutionOf,
+};
+
+/// Mine a solution for all the voter pages of `snapshot`.
+///
+/// The returned solution is already trimmed to the per page and final backer limits, and to the
+/// `MaxLength` of `T`. Its score is computed and checked for feasibility against the snapshot.
+pub fn mine<T: MinerConfig>(
+	snapshot: &Snapshot<T>,
+	do_reduce: bool,
+) -> Result<PagedRawSolution<T>, Error> {
+	let solution = BaseMiner::<T>::mine_solution(MineInput {
+		desired_targets: snapshot.desired_targets,
+		all_targets: snapshot.targets.clone(),
+		voter_pages: snapshot.voters.clone(),
+		pages: snapshot.voters.len() as PageIndex,
+		do_reduce,
+		round: snapshot.round,
+	})
+	.map_err(|e| Error::Miner(format!("{e:?}")))?;
+
+	log::info!(
+		target: LOG_TARGET,
+		"mined a solution of round {} with score {:?}, {} voters and {} bytes",
+		solution.round,
+		solution.score,
+		solution.voter_count(),
+		solution.encoded_size(),
+	);
+
+	Ok(solution)
+}
+
+/// The pages of `solution`, each with the index of the snapshot page it belongs to.
+///
+/// A solution that does not cover all pages only contains the most significant ones.
+pub fn pages_of<T: MinerConfig>(
+	solution: &PagedRawSolution<T>,
+) -> Vec<(PageIndex, &SolutionOf<T>)> {
+	solution.solution_pages.pagify(T::Pages::get()).collect()
+}
+
+/// Ensure that each page of `solution` fits in a transaction.
+pub fn ensure_page_lengths<T: MinerConfig>(
+	solution: &PagedRawSolution<T>,
+	max_page_length: u32,
+) -> Result<(), Error> {
+	for (page, solution_page) in pages_of(solution) {
+		let size = solution_page.encoded_size() as u32;
+		if size > max_page_length {
+			return Err(Error::PageTooLarge { page, size, limit: max_page_length })
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		config::{ChainParameters, MinerConfig16},
+		solver::{with_solver_options, Algorithm, SolverOptions},
+	};
+	use frame_election_provider_support::NposSolution;
+	use sp_runtime::AccountId32;
+
+	fn account(i: u8) -> AccountId32 {
+		AccountId32::new([i; 32])
+	}
+
+	fn snapshot() -> Snapshot<MinerConfig16> {
+		ChainParameters::for_tests().apply();
+		let targets = (100..104).map(account).collect::<Vec<_>>();
+		let voters = (0..3u8)
+			.map(|page| {
+				(0..4u8)
+					.map(|i| {
+						let who = account(page * 4 + i);
+						let mut votes =
+							vec![targets[i as usize].clone(), targets[page as usize].clone()];
+						votes.dedup();
+						(who, 10 + (page * 4 + i) as u64, votes.try_into().unwrap())
+					})
+					.collect::<Vec<_>>()
+					.try_into()
+					.unwrap()
+			})
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+
+		Snapshot { round: 0, desired_targets: 2, targets: targets.try_into().unwrap(), voters }
+	}
+
+	#[test]
+	fn mines_all_pages_with_both_algorithms() {
+		let snapshot = snapshot();
+		for algorithm in [Algorithm::SeqPhragmen, Algorithm::PhragMMS] {
+			let solution =
+				with_solver_options(SolverOptions { algorithm, ..Default::default() }, || {
+					mine(&snapshot, true).unwrap()
+				});
+
+			assert_eq!(solution.solution_pages.len(), 3);
+			assert!(solution.score.minimal_stake > 0);
+			assert_eq!(solution.winner_count_single_page_target_snapshot(), 2);
+			assert_eq!(
+				pages_of(&solution).iter().map(|(page, _)| *page).collect::<Vec<_>>(),
+				vec![0, 1, 2]
+			);
+			assert!(ensure_page_lengths(&solution, 1024).is_ok());
+		}
+	}
+
+	#[test]
+	fn page_length_is_checked() {
+		let snapshot = snapshot();
+		let solution = with_solver_options(Default::default(), || mine(&snapshot, true).unwrap());
+		let largest = solution.solution_pages.iter().map(|p| p.encoded_size()).max().unwrap();
+		assert!(solution.solution_pages.iter().any(|p| p.voter_count() > 0));
+
+		assert!(matches!(
+			ensure_page_lengths(&solution, largest as u32 - 1),
+			Err(Error::PageTooLarge { limit, .. }) if limit == largest as u32 - 1
+		));
+	}
+}
@@ -0,0 +1,178 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fetching the paged snapshot of an election.
+//!
+//! The state of the election pallet is loaded into a [`remote_externalities`] environment, either
+//! from a live node or from a state snapshot file written by a previous run, and the snapshot is
+//! then read from it by its raw storage keys.
+
+use crate::{client::twox_64_concat_key, Error, ELECTION_PALLET, LOG_TARGET};
+use codec::{Decode, Encode};
+use frame_support::{storage::unhashed, BoundedVec};
+use pallet_election_provider_multi_block::{
+	unsigned::miner::MinerConfig, AllVoterPagesOf, PageIndex, VoterPageOf,
+};
+use remote_externalities::{Builder, Mode, OfflineConfig, OnlineConfig, SnapshotConfig, Transport};
+use sp_core::H256;
+use sp_runtime::{generic, traits::BlakeTwo256, OpaqueExtrinsic};
+use std::path::PathBuf;
+
+/// An opaque block type, only used to build the [`remote_externalities`].
+pub type Block = generic::Block<generic::Header<u32, BlakeTwo256>, OpaqueExtrinsic>;
+
+/// Where to load the state of the election pallet from.
+#[derive(Debug, Clone)]
+pub enum SnapshotSource {
+	/// A live node, at the given block or the latest finalized one.
+	Live {
+		/// The uri of the node.
+		uri: String,
+		/// The block at which to read the state.
+		at: Option<H256>,
+		/// An optional path to write the state to, for later use with [`SnapshotSource::File`].
+		save: Option<PathBuf>,
+	},
+	/// A state snapshot file.
+	File(PathBuf),
+}
+
+/// The snapshot of one round of the election.
+pub struct Snapshot<T: MinerConfig> {
+	/// The round of the election.
+	pub round: u32,
+	/// The number of winners to elect.
+	pub desired_targets: u32,
+	/// All of the targets.
+	pub targets: BoundedVec<T::AccountId, T::TargetSnapshotPerBlock>,
+	/// The voter pages, in the order expected by the base miner. See [`Snapshot::read`].
+	pub voters: AllVoterPagesOf<T>,
+}
+
+impl<T: MinerConfig> Snapshot<T> {
+	/// Fetch the snapshot of the `pages` most significant pages from the given source.
+	pub async fn fetch(source: SnapshotSource, pages: PageIndex) -> Result<Self, Error> {
+		let mode = match source {
+			SnapshotSource::Live { uri, at, save } => Mode::Online(OnlineConfig {
+				at,
+				state_snapshot: save.map(SnapshotConfig::new),
+				pallets: vec![ELECTION_PALLET.to_string()],
+				transport: Transport::from(uri),
+				child_trie: false,
+				..Default::default()
+			}),
+			SnapshotSource::File(path) =>
+				Mode::Offline(OfflineConfig { state_snapshot: SnapshotConfig::new(path) }),
+		};
+
+		let mut ext = Builder::<Block>::new()
+			.mode(mode)
+			.build()
+			.await
+			.map_err(Error::RemoteExternalities)?;
+		ext.execute_with(|| Self::read(pages))
+	}
+
+	/// Read the snapshot from the storage of the current externalities.
+	///
+	/// Like `OffchainWorkerMiner::fetch_snapshot` of the pallet, only the `pages` most significant
+	/// voter pages are read, and they are kept in their original order. For example, with 4 pages
+	/// and `pages = 2` the voters are `[snapshot(2), snapshot(3)]`.
+	pub fn read(pages: PageIndex) -> Result<Self, Error> {
+		let pages = pages.min(T::Pages::get());
+		let round: u32 = get(ELECTION_PALLET, "Round", &[]).unwrap_or_default();
+		let desired_targets: u32 = get(ELECTION_PALLET, "DesiredTargets", &[&round.encode()])
+			.ok_or_else(|| Error::SnapshotUnavailable("desired targets".into()))?;
+
+		let msp = T::Pages::get().saturating_sub(1);
+		let targets: BoundedVec<T::AccountId, T::TargetSnapshotPerBlock> =
+			get(ELECTION_PALLET, "PagedTargetSnapshot", &[&round.encode(), &msp.encode()])
+				.ok_or_else(|| Error::SnapshotUnavailable("targets".into()))?;
+
+		let voters = (T::Pages::get().saturating_sub(pages)..T::Pages::get())
+			.map(|page| {
+				get::<VoterPageOf<T>>(
+					ELECTION_PALLET,
+					"PagedVoterSnapshot",
+					&[&round.encode(), &page.encode()],
+				)
+				.ok_or_else(|| Error::SnapshotUnavailable(format!("voters of page {page}")))
+			})
+			.collect::<Result<Vec<_>, _>>()?
+			.try_into()
+			.expect("at most `T::Pages` pages are read; qed");
+
+		log::info!(
+			target: LOG_TARGET,
+			"fetched snapshot of round {round} with {} targets and {pages} voter pages",
+			targets.len(),
+		);
+
+		Ok(Self { round, desired_targets, targets, voters })
+	}
+}
+
+fn get<V: Decode>(pallet: &str, item: &str, keys: &[&[u8]]) -> Option<V> {
+	unhashed::get(&twox_64_concat_key(pallet, item, keys))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::{ChainParameters, MinerConfig16};
+	use sp_runtime::AccountId32;
+
+	fn put<V: Encode>(item: &str, keys: &[&[u8]], value: V) {
+		unhashed::put(&twox_64_concat_key(ELECTION_PALLET, item, keys), &value);
+	}
+
+	#[test]
+	fn reads_most_significant_pages() {
+		ChainParameters::for_tests().apply();
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			let round = 1u32;
+			let target = AccountId32::new([9; 32]);
+			put("Round", &[], round);
+			put("DesiredTargets", &[&round.encode()], 1u32);
+			put("PagedTargetSnapshot", &[&round.encode(), &2u32.encode()], vec![target.clone()]);
+			for page in 0u32..3 {
+				let voter = (AccountId32::new([page as u8; 32]), 10u64, vec![target.clone()]);
+				put("PagedVoterSnapshot", &[&round.encode(), &page.encode()], vec![voter]);
+			}
+
+			let snapshot = Snapshot::<MinerConfig16>::read(2).unwrap();
+			assert_eq!(snapshot.round, 1);
+			assert_eq!(snapshot.desired_targets, 1);
+			assert_eq!(snapshot.targets.into_inner(), vec![target]);
+			let voters = snapshot
+				.voters
+				.iter()
+				.map(|page| page.iter().map(|(who, _, _)| who.clone()).collect::<Vec<_>>())
+				.collect::<Vec<_>>();
+			assert_eq!(
+				voters,
+				vec![vec![AccountId32::new([1; 32])], vec![AccountId32::new([2; 32])]]
+			);
+
+			assert!(matches!(
+				Snapshot::<MinerConfig16>::read(4),
+				Ok(Snapshot { voters, .. }) if voters.len() == 3
+			));
+		});
+	}
+}
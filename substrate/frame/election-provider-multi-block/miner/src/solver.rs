@@ -0,0 +1,156 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An [`NposSolver`] that can be chosen at runtime.
+//!
+//! [`MinerConfig::Solver`] is a type, yet the operator of the miner wants to pick the algorithm
+//! and its balancing parameters from the command line. [`Solver`] reads the [`SolverOptions`]
+//! set via [`set_solver_options`] or [`with_solver_options`] and dispatches to
+//! [`ParallelSequentialPhragmen`] or [`ParallelPhragMMS`]. These produce the same solutions as
+//! their single-threaded counterparts used on-chain, using as many threads as the
+//! `RAYON_NUM_THREADS` environment variable allows.
+//!
+//! [`MinerConfig::Solver`]: pallet_election_provider_multi_block::unsigned::miner::MinerConfig
+
+use frame_election_provider_support::{
+	BalancingConfig, ElectionResult, ExtendedBalance, Get, IdentifierT, NposSolver,
+	ParallelPhragMMS, ParallelSequentialPhragmen, PerThing128, VoteWeight, Weight, WeightInfo,
+};
+use std::{
+	marker::PhantomData,
+	sync::{Mutex, RwLock},
+};
+
+/// The election algorithm to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Algorithm {
+	/// Sequential Phragmén.
+	SeqPhragmen,
+	/// PhragMMS.
+	PhragMMS,
+}
+
+/// The options of [`Solver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolverOptions {
+	/// The election algorithm.
+	pub algorithm: Algorithm,
+	/// The number of balancing iterations to run after the election algorithm. `0` disables
+	/// balancing.
+	pub balancing_iterations: usize,
+	/// The tolerance of the balancing algorithm.
+	pub balancing_tolerance: ExtendedBalance,
+}
+
+impl SolverOptions {
+	const DEFAULT: Self = Self {
+		algorithm: Algorithm::SeqPhragmen,
+		balancing_iterations: 10,
+		balancing_tolerance: 0,
+	};
+}
+
+impl Default for SolverOptions {
+	fn default() -> Self {
+		Self::DEFAULT
+	}
+}
+
+static SOLVER_OPTIONS: RwLock<SolverOptions> = RwLock::new(SolverOptions::DEFAULT);
+
+/// Serializes the callers of [`with_solver_options`].
+static SCOPED_SOLVER_OPTIONS: Mutex<()> = Mutex::new(());
+
+/// Set the options used by all subsequent calls to [`Solver`].
+pub fn set_solver_options(options: SolverOptions) {
+	*SOLVER_OPTIONS.write().expect("lock is never poisoned; qed") = options;
+}
+
+/// Get the options currently used by [`Solver`].
+pub fn solver_options() -> SolverOptions {
+	*SOLVER_OPTIONS.read().expect("lock is never poisoned; qed")
+}
+
+/// Run `f` with `options` used by [`Solver`], and restore the previous options afterwards.
+///
+/// Concurrent calls are serialized, so that each `f` only ever observes its own `options`. This
+/// is what tests should use, since they run in parallel within the same process.
+pub fn with_solver_options<R>(options: SolverOptions, f: impl FnOnce() -> R) -> R {
+	struct Restore(SolverOptions);
+
+	impl Drop for Restore {
+		fn drop(&mut self) {
+			set_solver_options(self.0);
+		}
+	}
+
+	// A panic in another `f` poisons the lock, yet its options have been restored on unwind.
+	let _lock = SCOPED_SOLVER_OPTIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+	let _restore = Restore(solver_options());
+	set_solver_options(options);
+	f()
+}
+
+/// The balancing configuration derived from [`SolverOptions`].
+pub struct Balancing;
+
+impl Get<Option<BalancingConfig>> for Balancing {
+	fn get() -> Option<BalancingConfig> {
+		let SolverOptions { balancing_iterations, balancing_tolerance, .. } = solver_options();
+		(balancing_iterations > 0).then(|| BalancingConfig {
+			iterations: balancing_iterations,
+			tolerance: balancing_tolerance,
+		})
+	}
+}
+
+/// A [`NposSolver`] that dispatches to the [`Algorithm`] of the current [`SolverOptions`].
+pub struct Solver<AccountId, Accuracy>(PhantomData<(AccountId, Accuracy)>);
+
+impl<AccountId: IdentifierT + Send + Sync, Accuracy: PerThing128> NposSolver
+	for Solver<AccountId, Accuracy>
+{
+	type AccountId = AccountId;
+	type Accuracy = Accuracy;
+	type Error = sp_npos_elections::Error;
+
+	fn solve(
+		to_elect: usize,
+		targets: Vec<Self::AccountId>,
+		voters: Vec<(
+			Self::AccountId,
+			VoteWeight,
+			impl Clone + IntoIterator<Item = Self::AccountId>,
+		)>,
+	) -> Result<ElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
+		match solver_options().algorithm {
+			Algorithm::SeqPhragmen =>
+				ParallelSequentialPhragmen::<AccountId, Accuracy, Balancing>::solve(
+					to_elect, targets, voters,
+				),
+			Algorithm::PhragMMS =>
+				ParallelPhragMMS::<AccountId, Accuracy, Balancing>::solve(to_elect, targets, voters),
+		}
+	}
+
+	fn weight<T: WeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight {
+		match solver_options().algorithm {
+			Algorithm::SeqPhragmen => T::phragmen(voters, targets, vote_degree),
+			Algorithm::PhragMMS => T::phragmms(voters, targets, vote_degree),
+		}
+	}
+}
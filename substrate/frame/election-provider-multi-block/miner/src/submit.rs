@@ -0,0 +1,325 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Submitting a paged solution to the signed pallet.
+//!
+//! A submission consists of one `register` transaction, claiming the score of the solution, and
+//! one `submit_page` transaction per page. All of them are watched until finalized. The state of
+//! the submission is always read back from the latest finalized block, so pages that were lost in
+//! a re-org, or whose transaction was dropped, are simply submitted again.
+
+use crate::{
+	client::{Client, SubmissionMetadata},
+	miner::{self, pages_of},
+	snapshot::{Snapshot, SnapshotSource},
+	Error, LOG_TARGET, SIGNED_PALLET,
+};
+use codec::Encode;
+use futures::StreamExt;
+use pallet_election_provider_multi_block::{
+	unsigned::miner::MinerConfig, ElectionScore, PageIndex, PagedRawSolution,
+};
+use sp_core::H256;
+use sp_runtime::AccountId32;
+use std::str::FromStr;
+use subxt::{
+	ext::subxt_core::{self, error::MetadataError},
+	Metadata,
+};
+use subxt_signer::{sr25519::Keypair, SecretUri};
+
+/// A call of the signed pallet, encoded without generated metadata types.
+struct SignedCall {
+	call: &'static str,
+	args: Vec<u8>,
+}
+
+impl SignedCall {
+	fn register(claimed_score: ElectionScore) -> Self {
+		Self { call: "register", args: claimed_score.encode() }
+	}
+
+	fn submit_page(page: PageIndex, solution_page: &impl Encode) -> Self {
+		Self { call: "submit_page", args: (page, Some(solution_page)).encode() }
+	}
+}
+
+impl subxt::tx::Payload for SignedCall {
+	fn encode_call_data_to(
+		&self,
+		metadata: &Metadata,
+		out: &mut Vec<u8>,
+	) -> Result<(), subxt_core::Error> {
+		let pallet = metadata.pallet_by_name_err(SIGNED_PALLET)?;
+		let call = pallet
+			.call_variant_by_name(self.call)
+			.ok_or_else(|| MetadataError::CallNameNotFound(self.call.to_string()))?;
+		out.push(pallet.index());
+		out.push(call.index);
+		out.extend_from_slice(&self.args);
+		Ok(())
+	}
+}
+
+/// Parse a secret URI, e.g. `//Alice` or a mnemonic, into a signer.
+pub fn signer_from_uri(suri: &str) -> Result<Keypair, Error> {
+	let uri = SecretUri::from_str(suri).map_err(|e| Error::InvalidSecretUri(e.to_string()))?;
+	Keypair::from_uri(&uri).map_err(|e| Error::InvalidSecretUri(e.to_string()))
+}
+
+/// The options of a [`Submitter`].
+#[derive(Debug, Clone)]
+pub struct SubmissionOptions {
+	/// The maximum deposit that the submitter is willing to hold.
+	pub max_deposit: Option<u128>,
+	/// The number of failed transactions after which a submission is abandoned.
+	pub retries: u32,
+	/// Only run the checks, without submitting any transaction.
+	pub dry_run: bool,
+}
+
+/// Submits solutions to the signed pallet on behalf of one account.
+pub struct Submitter {
+	client: Client,
+	signer: Keypair,
+	who: AccountId32,
+	options: SubmissionOptions,
+}
+
+impl Submitter {
+	/// Create a new submitter.
+	pub fn new(client: Client, signer: Keypair, options: SubmissionOptions) -> Self {
+		let who = AccountId32::from(signer.public_key().0);
+		Self { client, signer, who, options }
+	}
+
+	/// The account of the submitter.
+	pub fn account(&self) -> &AccountId32 {
+		&self.who
+	}
+
+	/// Check whether a solution with `score` is worth submitting in `round` at block `at`.
+	///
+	/// If the submitter is already registered with the same score, e.g. because of a previous
+	/// attempt, its submission metadata is returned so that the submission can be resumed.
+	pub async fn check_score(
+		&self,
+		at: H256,
+		round: u32,
+		score: ElectionScore,
+	) -> Result<Option<SubmissionMetadata>, Error> {
+		if let Some(metadata) = self.client.submission_metadata(at, round, &self.who).await? {
+			return if metadata.claimed_score == score {
+				Ok(Some(metadata))
+			} else {
+				Err(Error::AlreadyRegistered(metadata.claimed_score))
+			}
+		}
+
+		if let Some(minimum) = self.client.minimum_score(at).await? {
+			if !score.strict_better(minimum) {
+				return Err(Error::WeakScore(score, minimum))
+			}
+		}
+
+		let threshold = self.client.solution_improvement_threshold()?;
+		let queued = self.client.queued_score(at, round).await?;
+		let leader = self.client.sorted_scores(at, round).await?.last().map(|(_, score)| *score);
+		for best in queued.into_iter().chain(leader) {
+			if !score.strict_threshold_better(best, threshold) {
+				return Err(Error::WeakScore(score, best))
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// Ensure that the submitter can hold the deposit of a solution with `pages` pages.
+	pub async fn check_deposit(&self, at: H256, pages: u32) -> Result<u128, Error> {
+		let deposit = self.client.deposit_for(at, &self.who, pages).await?;
+		let spendable = self.client.spendable_balance(at, &self.who).await?;
+		let available = self.options.max_deposit.map_or(spendable, |max| max.min(spendable));
+		if deposit > available {
+			return Err(Error::InsufficientFunds { deposit, available })
+		}
+		Ok(deposit)
+	}
+
+	/// Register and submit all pages of `solution`.
+	///
+	/// Returns once all pages of the solution are stored on-chain as of the latest finalized block.
+	pub async fn submit<T: MinerConfig>(
+		&self,
+		solution: &PagedRawSolution<T>,
+	) -> Result<(), Error> {
+		let pages = pages_of(solution);
+		let mut failures = 0;
+
+		loop {
+			let at = self.client.finalized_head().await?;
+			if !self.client.phase(at).await?.is_signed() {
+				return Err(Error::PhaseNotSigned)
+			}
+			let round = self.client.round(at).await?;
+			if round != solution.round {
+				return Err(Error::RoundChanged { mined: solution.round, current: round })
+			}
+
+			let result = match self.check_score(at, round, solution.score).await? {
+				None => {
+					let deposit = self.check_deposit(at, pages.len() as u32).await?;
+					log::info!(
+						target: LOG_TARGET,
+						"registering score {:?} in round {round}, holding a deposit of {deposit}",
+						solution.score,
+					);
+					if self.options.dry_run {
+						return Ok(())
+					}
+					self.submit_and_watch(SignedCall::register(solution.score)).await
+				},
+				Some(metadata) => {
+					let missing = pages
+						.iter()
+						.filter(|(page, _)| {
+							!metadata.pages.get(*page as usize).copied().unwrap_or(false)
+						})
+						.collect::<Vec<_>>();
+					if missing.is_empty() {
+						log::info!(
+							target: LOG_TARGET,
+							"all {} pages of round {round} are submitted",
+							pages.len(),
+						);
+						return Ok(())
+					}
+
+					let mut result = Ok(());
+					for (page, solution_page) in missing {
+						log::info!(target: LOG_TARGET, "submitting page {page} of round {round}");
+						result = self
+							.submit_and_watch(SignedCall::submit_page(*page, *solution_page))
+							.await;
+						if result.is_err() {
+							break
+						}
+					}
+					result
+				},
+			};
+
+			if let Err(e) = result {
+				failures += 1;
+				log::warn!(
+					target: LOG_TARGET,
+					"submission attempt {failures} of round {round} failed: {e:?}",
+				);
+				if failures > self.options.retries {
+					return Err(Error::RetriesExhausted(failures))
+				}
+			}
+		}
+	}
+
+	async fn submit_and_watch(&self, call: SignedCall) -> Result<(), Error> {
+		let events = self
+			.client
+			.api()
+			.tx()
+			.sign_and_submit_then_watch_default(&call, &self.signer)
+			.await?
+			.wait_for_finalized_success()
+			.await?;
+		log::debug!(
+			target: LOG_TARGET,
+			"{} finalized as {:?}",
+			call.call,
+			events.extrinsic_hash(),
+		);
+		Ok(())
+	}
+}
+
+/// Follow the finalized blocks of the chain, and mine and submit a solution whenever the signed
+/// phase of a new round is open.
+///
+/// The submission of a round is re-checked at every finalized block of its signed phase, so that
+/// pages lost in a re-org are submitted again.
+pub async fn monitor<T: MinerConfig>(submitter: &Submitter, do_reduce: bool) -> Result<(), Error> {
+	let client = &submitter.client;
+	let mut blocks = client.api().blocks().subscribe_finalized().await?;
+	let mut current: Option<(u32, Option<PagedRawSolution<T>>)> = None;
+
+	while let Some(block) = blocks.next().await {
+		let at = block?.hash();
+		if !client.phase(at).await?.is_signed() {
+			continue
+		}
+
+		let round = client.round(at).await?;
+		if current.as_ref().map_or(true, |(r, _)| *r != round) {
+			log::info!(target: LOG_TARGET, "signed phase of round {round} is open, mining");
+			let solution = Snapshot::<T>::fetch(
+				SnapshotSource::Live { uri: client.uri().to_string(), at: Some(at), save: None },
+				T::Pages::get(),
+			)
+			.await
+			.and_then(|snapshot| miner::mine(&snapshot, do_reduce))
+			.and_then(|solution| {
+				let max_page_length = client.chain_parameters()?.max_page_length;
+				miner::ensure_page_lengths(&solution, max_page_length).map(|_| solution)
+			})
+			.map_err(|e| log::error!(target: LOG_TARGET, "failed to mine round {round}: {e:?}"))
+			.ok();
+			current = Some((round, solution));
+		}
+
+		let Some((_, Some(solution))) = current.as_ref() else { continue };
+		match submitter.submit(solution).await {
+			Ok(()) => {},
+			Err(e @ (Error::WeakScore(..) | Error::AlreadyRegistered(_))) => {
+				log::info!(target: LOG_TARGET, "not submitting in round {round}: {e}");
+				current = Some((round, None));
+			},
+			Err(e) => log::warn!(target: LOG_TARGET, "submission of round {round} failed: {e:?}"),
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn signer_from_uri_works() {
+		use sp_core::Pair;
+
+		let signer = signer_from_uri("//Alice").unwrap();
+		let expected = sp_core::sr25519::Pair::from_string("//Alice", None).unwrap().public();
+		assert_eq!(AccountId32::from(signer.public_key().0), AccountId32::from(expected));
+		assert!(signer_from_uri("not a secret uri").is_err());
+	}
+
+	#[test]
+	fn submit_page_args_are_an_optional_page() {
+		let call = SignedCall::submit_page(2, &vec![1u8, 2, 3]);
+		assert_eq!(call.args, (2u32, Some(vec![1u8, 2, 3])).encode());
+		assert_eq!(SignedCall::register(Default::default()).call, "register");
+	}
+}
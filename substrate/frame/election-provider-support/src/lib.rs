@@ -895,6 +895,78 @@ impl<AccountId: IdentifierT, Accuracy: PerThing128, Balancing: Get<Option<Balanc
 	}
 }
 
+/// A wrapper for [`sp_npos_elections::parallel::seq_phragmen`] that implements [`NposSolver`].
+///
+/// Produces the exact same result as [`SequentialPhragmen`], using all the threads of the current
+/// `rayon` thread pool. Only meant to be used off-chain.
+#[cfg(feature = "parallel")]
+pub struct ParallelSequentialPhragmen<AccountId, Accuracy, Balancing = ()>(
+	core::marker::PhantomData<(AccountId, Accuracy, Balancing)>,
+);
+
+#[cfg(feature = "parallel")]
+impl<
+		AccountId: IdentifierT + Send + Sync,
+		Accuracy: PerThing128,
+		Balancing: Get<Option<BalancingConfig>>,
+	> NposSolver for ParallelSequentialPhragmen<AccountId, Accuracy, Balancing>
+{
+	type AccountId = AccountId;
+	type Accuracy = Accuracy;
+	type Error = sp_npos_elections::Error;
+	fn solve(
+		winners: usize,
+		targets: Vec<Self::AccountId>,
+		voters: Vec<(
+			Self::AccountId,
+			VoteWeight,
+			impl Clone + IntoIterator<Item = Self::AccountId>,
+		)>,
+	) -> Result<ElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
+		sp_npos_elections::parallel::seq_phragmen(winners, targets, voters, Balancing::get())
+	}
+
+	fn weight<T: WeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight {
+		T::phragmen(voters, targets, vote_degree)
+	}
+}
+
+/// A wrapper for [`sp_npos_elections::parallel::phragmms`] that implements [`NposSolver`].
+///
+/// Produces the exact same result as [`PhragMMS`], using all the threads of the current `rayon`
+/// thread pool. Only meant to be used off-chain.
+#[cfg(feature = "parallel")]
+pub struct ParallelPhragMMS<AccountId, Accuracy, Balancing = ()>(
+	core::marker::PhantomData<(AccountId, Accuracy, Balancing)>,
+);
+
+#[cfg(feature = "parallel")]
+impl<
+		AccountId: IdentifierT + Send + Sync,
+		Accuracy: PerThing128,
+		Balancing: Get<Option<BalancingConfig>>,
+	> NposSolver for ParallelPhragMMS<AccountId, Accuracy, Balancing>
+{
+	type AccountId = AccountId;
+	type Accuracy = Accuracy;
+	type Error = sp_npos_elections::Error;
+	fn solve(
+		winners: usize,
+		targets: Vec<Self::AccountId>,
+		voters: Vec<(
+			Self::AccountId,
+			VoteWeight,
+			impl Clone + IntoIterator<Item = Self::AccountId>,
+		)>,
+	) -> Result<ElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
+		sp_npos_elections::parallel::phragmms(winners, targets, voters, Balancing::get())
+	}
+
+	fn weight<T: WeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight {
+		T::phragmms(voters, targets, vote_degree)
+	}
+}
+
 /// A voter, at the level of abstraction of this crate.
 pub type Voter<AccountId, Bound> = (AccountId, VoteWeight, BoundedVec<AccountId, Bound>);
 
@@ -610,3 +610,59 @@ fn supports_sorted_truncate_from_works() {
 	assert_eq!(winners_removed, 1);
 	assert_eq!(backers_removed, 3);
 }
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_solvers_produce_same_results() {
+	use crate::{
+		NposSolver, ParallelPhragMMS, ParallelSequentialPhragmen, PhragMMS, SequentialPhragmen,
+	};
+	use sp_npos_elections::{BalancingConfig, ElectionResult};
+
+	frame_support::parameter_types! {
+		pub static Balancing: Option<BalancingConfig> =
+			Some(BalancingConfig { iterations: 5, tolerance: 0 });
+	}
+
+	fn flatten(
+		result: ElectionResult<AccountId, TestAccuracy>,
+	) -> (Vec<(AccountId, u128)>, Vec<(AccountId, Vec<(AccountId, TestAccuracy)>)>) {
+		let assignments = result.assignments.into_iter().map(|a| (a.who, a.distribution)).collect();
+		(result.winners, assignments)
+	}
+
+	let rng = rand::rngs::SmallRng::seed_from_u64(0);
+	let (voters, _, candidates) = generate_random_votes(100, 1000, rng);
+
+	assert_eq!(
+		flatten(
+			ParallelSequentialPhragmen::<AccountId, TestAccuracy, Balancing>::solve(
+				20,
+				candidates.clone(),
+				voters.clone()
+			)
+			.unwrap()
+		),
+		flatten(
+			SequentialPhragmen::<AccountId, TestAccuracy, Balancing>::solve(
+				20,
+				candidates.clone(),
+				voters.clone()
+			)
+			.unwrap()
+		),
+	);
+	assert_eq!(
+		flatten(
+			ParallelPhragMMS::<AccountId, TestAccuracy, Balancing>::solve(
+				20,
+				candidates.clone(),
+				voters.clone()
+			)
+			.unwrap()
+		),
+		flatten(
+			PhragMMS::<AccountId, TestAccuracy, Balancing>::solve(20, candidates, voters).unwrap()
+		),
+	);
+}
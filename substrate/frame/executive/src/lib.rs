@@ -184,7 +184,7 @@ use ::{
 		traits::{TryDecodeEntireStorage, TryDecodeEntireStorageError, TryState},
 		StorageNoopGuard,
 	},
-	frame_try_runtime::{TryStateSelect, UpgradeCheckSelect},
+	frame_try_runtime::{EmptyBlockOutcome, TryStateSelect, UpgradeCheckSelect},
 	log,
 	sp_runtime::TryRuntimeError,
 };
@@ -414,6 +414,49 @@ where
 		Ok(frame_system::Pallet::<System>::block_weight().total())
 	}
 
+	/// Execute a block without any extrinsics on top of the current state.
+	///
+	/// The block is initialized from the `number`, `parent_hash` and pre-runtime digests of
+	/// `header`. Afterwards, the ongoing multi-block migrations are stepped or `on_poll` and
+	/// `on_idle` are called, exactly as for a normal block without transactions.
+	///
+	/// The `on_finalize` hooks are NOT called, since pallets like `pallet-timestamp` would panic
+	/// because their inherents are missing.
+	///
+	/// Should only be used for testing ONLY.
+	pub fn try_execute_empty_block(
+		header: frame_system::pallet_prelude::HeaderFor<System>,
+	) -> EmptyBlockOutcome<frame_system::pallet_prelude::HeaderFor<System>> {
+		log::info!(
+			target: LOG_TARGET,
+			"try-runtime: executing empty block #{:?}",
+			header.number(),
+		);
+
+		Self::initialize_block(&header);
+		Self::inherents_applied();
+
+		<frame_system::Pallet<System>>::note_finished_extrinsics();
+		<System as frame_system::Config>::PostTransactions::post_transactions();
+		Self::on_idle_hook(*header.number());
+
+		let weight = frame_system::Pallet::<System>::block_weight().total();
+		let migrations_ongoing = <System as frame_system::Config>::MultiBlockMigrator::ongoing();
+		log::info!(
+			target: LOG_TARGET,
+			"try-runtime: empty block #{:?} consumed {:?}, migrations ongoing: {:?}",
+			header.number(),
+			weight,
+			migrations_ongoing,
+		);
+
+		EmptyBlockOutcome {
+			header: <frame_system::Pallet<System>>::finalize(),
+			weight,
+			migrations_ongoing,
+		}
+	}
+
 	/// Execute all Migrations of this runtime.
 	///
 	/// The `checks` param determines whether to execute `pre/post_upgrade` and `try_state` hooks.
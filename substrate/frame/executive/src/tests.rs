@@ -1324,6 +1324,37 @@ fn try_execute_tx_forbidden_errors() {
 	});
 }
 
+#[test]
+#[cfg(feature = "try-runtime")]
+fn try_execute_empty_block_works() {
+	new_test_ext(1).execute_with(|| {
+		MbmActive::set(true);
+		MockedSystemCallbacks::reset();
+
+		let outcome = Executive::try_execute_empty_block(Header::new_from_number(1));
+		assert_eq!(*outcome.header.number(), 1);
+		assert!(outcome.migrations_ongoing);
+		assert!(MockedSystemCallbacks::post_transactions_called());
+		assert!(outcome
+			.weight
+			.all_gte(<Runtime as frame_system::Config>::BlockWeights::get().base_block));
+
+		// The next block can be built on top of the previous one.
+		MbmActive::set(false);
+		let parent_hash = outcome.header.hash();
+		let outcome = Executive::try_execute_empty_block(Header::new(
+			2,
+			H256::default(),
+			H256::default(),
+			parent_hash,
+			Digest::default(),
+		));
+		assert_eq!(*outcome.header.number(), 2);
+		assert!(!outcome.migrations_ongoing);
+		assert_eq!(frame_system::Pallet::<Runtime>::block_hash(1), *outcome.header.parent_hash());
+	});
+}
+
 /// Test if `apply_extrinsics` validates if the inherents are first.
 #[test]
 fn apply_extrinsics_checks_inherents_are_first() {
@@ -141,6 +141,15 @@
 //! You can see the transactional semantics for migration steps as mostly useless, since in the
 //! stuck case the state is already messed up. This just prevents it from becoming even more messed
 //! up, but doesn't prevent it in the first place.
+//!
+//! ### Advice: Testing with try-runtime
+//!
+//! The `pre_upgrade` and `post_upgrade` hooks of a [`SteppedMigration`] are run by this pallet
+//! when the `try-runtime` feature is enabled, and any failure panics with the index of the
+//! migration and its [`SteppedMigrationError`]. To run all migrations against real chain state,
+//! use `frame_try_runtime::multi_block::run_multi_block_migrations` on a `remote-externalities`
+//! snapshot. It applies the upgrade and executes empty blocks until the [`Cursor`] is gone, while
+//! reporting the weight and proof size of every block.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -645,7 +654,7 @@ impl<T: Config> Pallet<T> {
 			log::error!("Ongoing migrations interrupted - chain stuck");
 
 			let maybe_index = cursor.as_active().map(|c| c.index);
-			Self::upgrade_failed(maybe_index);
+			Self::upgrade_failed(maybe_index, None);
 			return T::WeightInfo::onboard_new_mbms()
 		}
 
@@ -741,7 +750,7 @@ impl<T: Config> Pallet<T> {
 		let id = T::Migrations::nth_id(cursor.index).map(TryInto::try_into);
 		let Some(Ok(bounded_id)): Option<Result<IdentifierOf<T>, _>> = id else {
 			defensive!("integrity_test ensures that all identifiers are present and bounde; qed.");
-			Self::upgrade_failed(Some(cursor.index));
+			Self::upgrade_failed(Some(cursor.index), None);
 			return None
 		};
 
@@ -769,7 +778,7 @@ impl<T: Config> Pallet<T> {
 		);
 		let Some((max_steps, next_cursor)) = max_steps.zip(next_cursor) else {
 			defensive!("integrity_test ensures that the tuple is valid; qed");
-			Self::upgrade_failed(Some(cursor.index));
+			Self::upgrade_failed(Some(cursor.index), None);
 			return None
 		};
 
@@ -778,7 +787,7 @@ impl<T: Config> Pallet<T> {
 			Ok(Some(next_cursor)) => {
 				let Ok(bound_next_cursor) = next_cursor.try_into() else {
 					defensive!("The integrity check ensures that all cursors' MEL bound fits into CursorMaxLen; qed");
-					Self::upgrade_failed(Some(cursor.index));
+					Self::upgrade_failed(Some(cursor.index), None);
 					return None
 				};
 
@@ -787,7 +796,7 @@ impl<T: Config> Pallet<T> {
 
 				if max_steps.is_some_and(|max| took > max.into()) {
 					Self::deposit_event(Event::MigrationFailed { index: cursor.index, took });
-					Self::upgrade_failed(Some(cursor.index));
+					Self::upgrade_failed(Some(cursor.index), None);
 					None
 				} else {
 					// A migration cannot progress more than one step per block, we therefore break.
@@ -811,34 +820,42 @@ impl<T: Config> Pallet<T> {
 				cursor.goto_next_migration(System::<T>::block_number());
 				Some(ControlFlow::Continue(cursor))
 			},
-			Err(SteppedMigrationError::InsufficientWeight { required }) => {
+			Err(err @ SteppedMigrationError::InsufficientWeight { required }) => {
 				if is_first || required.any_gt(meter.limit()) {
 					Self::deposit_event(Event::MigrationFailed { index: cursor.index, took });
-					Self::upgrade_failed(Some(cursor.index));
+					Self::upgrade_failed(Some(cursor.index), Some(err));
 					None
 				} else {
 					// Retry and hope that there is more weight in the next block.
 					Some(ControlFlow::Break(cursor))
 				}
 			},
-			Err(SteppedMigrationError::InvalidCursor | SteppedMigrationError::Failed) => {
+			Err(err @ (SteppedMigrationError::InvalidCursor | SteppedMigrationError::Failed)) => {
 				Self::deposit_event(Event::MigrationFailed { index: cursor.index, took });
-				Self::upgrade_failed(Some(cursor.index));
+				Self::upgrade_failed(Some(cursor.index), Some(err));
 				None
 			},
 		}
 	}
 
-	/// Fail the current runtime upgrade, caused by `migration`.
+	/// Fail the current runtime upgrade, caused by `migration` returning `error`.
 	///
-	/// When the `try-runtime` feature is enabled, this function will panic.
+	/// When the `try-runtime` feature is enabled, this function will panic. The panic message
+	/// contains the `error` so that try-runtime tooling can report it.
 	// Allow unreachable code so it can compile without warnings when `try-runtime` is enabled.
-	fn upgrade_failed(migration: Option<u32>) {
+	fn upgrade_failed(migration: Option<u32>, error: Option<SteppedMigrationError>) {
 		use FailedMigrationHandling::*;
 		Self::deposit_event(Event::UpgradeFailed);
 
+		if let Some(ref error) = error {
+			log::error!("Migration with index {:?} failed with {:?}", migration, error);
+		}
+
 		if cfg!(feature = "try-runtime") {
-			panic!("Migration with index {:?} failed.", migration);
+			match error {
+				Some(error) => panic!("Migration with index {:?} failed: {:?}.", migration, error),
+				None => panic!("Migration with index {:?} failed.", migration),
+			}
 		} else {
 			match T::FailedMigrationHandler::failed(migration) {
 				KeepStuck => Cursor::<T>::set(Some(MigrationCursor::Stuck)),
@@ -24,8 +24,13 @@ use crate::{mock_helpers::*, Event, Historic};
 use frame_support::{derive_impl, migrations::*, weights::Weight};
 use frame_system::EventRecord;
 use sp_core::H256;
+#[cfg(feature = "try-runtime")]
+use {
+	frame_system::pallet_prelude::HeaderFor,
+	sp_runtime::traits::{HashingFor, Header as _},
+};
 
-type Block = frame_system::mocking::MockBlock<Test>;
+pub type Block = frame_system::mocking::MockBlock<Test>;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -67,6 +72,40 @@ frame_support::parameter_types! {
 	pub static FailedUpgradeResponse: FailedMigrationHandling = FailedMigrationHandling::KeepStuck;
 }
 
+#[cfg(feature = "try-runtime")]
+type Executive = frame_executive::Executive<
+	Test,
+	Block,
+	frame_system::ChainContext<Test>,
+	Test,
+	AllPalletsWithSystem,
+>;
+
+#[cfg(feature = "try-runtime")]
+impl frame_try_runtime::runtime_decl_for_try_runtime::TryRuntimeV2<Block> for Test {
+	fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
+		use frame_support::traits::Get;
+
+		let weight = Executive::try_runtime_upgrade(checks).unwrap();
+		(weight, <Test as frame_system::Config>::BlockWeights::get().max_block)
+	}
+
+	fn execute_block(
+		block: Block,
+		state_root_check: bool,
+		signature_check: bool,
+		select: frame_try_runtime::TryStateSelect,
+	) -> Weight {
+		Executive::try_execute_block(block, state_root_check, signature_check, select).unwrap()
+	}
+
+	fn execute_empty_block(
+		header: HeaderFor<Test>,
+	) -> frame_try_runtime::EmptyBlockOutcome<HeaderFor<Test>> {
+		Executive::try_execute_empty_block(header)
+	}
+}
+
 /// Records all started and completed upgrades in `UpgradesStarted` and `UpgradesCompleted`.
 pub struct MockedMigrationStatusHandler;
 impl MigrationStatusHandler for MockedMigrationStatusHandler {
@@ -108,6 +147,38 @@ pub fn test_closure<R>(f: impl FnOnce() -> R) -> R {
 	ext.execute_with(f)
 }
 
+/// Run the try-runtime MBM driver with the given `migrations` on top of block 1.
+#[cfg(feature = "try-runtime")]
+pub fn run_try_runtime_driver(
+	migrations: Vec<(MockedMigrationKind, u32)>,
+	max_steps: u32,
+) -> (
+	sp_state_machine::TestExternalities<HashingFor<Block>>,
+	frame_try_runtime::multi_block::MultiBlockMigrationReport,
+) {
+	let mut ext = sp_state_machine::TestExternalities::<HashingFor<Block>>::default();
+	ext.execute_with(|| {
+		MockedMigrations::set(migrations);
+		System::set_block_number(1);
+	});
+
+	let parent = HeaderFor::<Test>::new(
+		1,
+		Default::default(),
+		Default::default(),
+		Default::default(),
+		Default::default(),
+	);
+	let report = frame_try_runtime::multi_block::run_multi_block_migrations::<Block, Test>(
+		&mut ext,
+		parent,
+		frame_try_runtime::UpgradeCheckSelect::PreAndPost,
+		max_steps,
+		|_| Default::default(),
+	);
+	(ext, report)
+}
+
 pub fn run_to_block(n: u64) {
 	System::run_to_block_with::<AllPalletsWithSystem>(
 		n,
@@ -427,7 +427,7 @@ fn try_runtime_post_upgrade_failure() {
 
 #[test]
 #[cfg(feature = "try-runtime")]
-#[should_panic]
+#[should_panic = "Migration with index Some(1) failed: Failed."]
 fn try_runtime_migration_failure() {
 	test_closure(|| {
 		// Add three migrations, it should fail after the second one.
@@ -454,3 +454,44 @@ fn try_runtime_no_migrations() {
 		assert_eq!(System::events().len(), 0);
 	});
 }
+
+#[test]
+#[cfg(feature = "try-runtime")]
+fn try_runtime_driver_steps_migrations_to_completion() {
+	let (mut ext, report) = run_try_runtime_driver(vec![(SucceedAfter, 0), (SucceedAfter, 2)], 10);
+
+	assert!(report.is_completed(), "{:?}", report.outcome);
+	assert_eq!(report.steps.len(), 3);
+	assert!(report.steps.iter().all(|s| s.proof_size > 0));
+	ext.execute_with(|| {
+		assert_eq!(System::block_number(), 4);
+		assert!(Cursor::<T>::get().is_none());
+		assert_eq!(historic(), vec![mocked_id(SucceedAfter, 0), mocked_id(SucceedAfter, 2)]);
+	});
+}
+
+#[test]
+#[cfg(feature = "try-runtime")]
+fn try_runtime_driver_reports_failure() {
+	use frame_try_runtime::multi_block::MultiBlockMigrationOutcome;
+
+	let (_, report) = run_try_runtime_driver(vec![(SucceedAfter, 0), (FailAfter, 2)], 10);
+
+	assert_eq!(
+		report.outcome,
+		MultiBlockMigrationOutcome::Failed("Migration with index Some(1) failed: Failed.".into())
+	);
+	assert_eq!(report.steps.len(), 2);
+}
+
+#[test]
+#[cfg(feature = "try-runtime")]
+fn try_runtime_driver_respects_max_steps() {
+	use frame_try_runtime::multi_block::MultiBlockMigrationOutcome;
+
+	let (mut ext, report) = run_try_runtime_driver(vec![(SucceedAfter, 5)], 3);
+
+	assert_eq!(report.outcome, MultiBlockMigrationOutcome::Unfinished);
+	assert_eq!(report.steps.len(), 3);
+	ext.execute_with(|| assert!(Cursor::<T>::get().is_some()));
+}
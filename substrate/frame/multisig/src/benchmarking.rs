@@ -45,6 +45,23 @@ fn setup_multi<T: Config>(
 	Ok((signatories, Box::new(call)))
 }
 
+fn setup_stateful_multi<T: Config>(
+	s: u32,
+	z: u32,
+) -> Result<(Vec<T::AccountId>, T::AccountId, Box<<T as Config>::RuntimeCall>), &'static str> {
+	let (signatories, call) = setup_multi::<T>(s, z)?;
+	let members = signatories.iter().cloned().map(|who| (who, 1)).collect();
+	let index = NextStatefulMultisigIndex::<T>::get();
+	// Every member is needed, so that the benchmarks control when the call is executed.
+	Multisig::<T>::create_stateful_multisig(
+		RawOrigin::Signed(signatories[0].clone()).into(),
+		members,
+		s,
+	)
+	.map_err(|_| "could not create stateful multisig")?;
+	Ok((signatories, Multisig::<T>::stateful_multi_account_id(index), call))
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -359,5 +376,277 @@ mod benchmarks {
 		Ok(())
 	}
 
+	/// `s`: Members
+	#[benchmark]
+	fn create_stateful_multisig(
+		s: Linear<1, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let (signatories, _) = setup_multi::<T>(s, 0)?;
+		let members = signatories.iter().cloned().map(|who| (who, 1)).collect();
+		let caller = signatories[0].clone();
+		let multisig = Multisig::<T>::stateful_multi_account_id(0);
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), members, s);
+
+		assert!(StatefulMultisigs::<T>::contains_key(multisig));
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Members, need at least 2 people
+	#[benchmark]
+	fn as_multi_stateful_create(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (signatories, multisig, call) = setup_stateful_multi::<T>(s, z)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[0].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		as_multi(RawOrigin::Signed(caller), 0, vec![multisig.clone()], None, call, Weight::zero());
+
+		assert!(StatefulProposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Members, need at least 3 people (so we don't complete the proposal)
+	#[benchmark]
+	fn as_multi_stateful_approve(
+		s: Linear<3, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (signatories, multisig, call) = setup_stateful_multi::<T>(s, z)?;
+		let call_hash = call.using_encoded(blake2_256);
+		// before the call, get the timepoint
+		let timepoint = Multisig::<T>::timepoint();
+		// Create the proposal
+		Multisig::<T>::as_multi(
+			RawOrigin::Signed(signatories[0].clone()).into(),
+			0,
+			vec![multisig.clone()],
+			None,
+			call.clone(),
+			Weight::zero(),
+		)?;
+		let caller2 = signatories[1].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller2);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		as_multi(
+			RawOrigin::Signed(caller2),
+			0,
+			vec![multisig.clone()],
+			Some(timepoint),
+			call,
+			Weight::zero(),
+		);
+
+		let proposal =
+			StatefulProposals::<T>::get(multisig, call_hash).ok_or("proposal not created")?;
+		assert_eq!(proposal.approvals.len(), 2);
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Members, need at least 2 people
+	#[benchmark]
+	fn as_multi_stateful_complete(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (signatories, multisig, call) = setup_stateful_multi::<T>(s, z)?;
+		let call_hash = call.using_encoded(blake2_256);
+		// before the call, get the timepoint
+		let timepoint = Multisig::<T>::timepoint();
+		// Everyone except the last person approves
+		for (i, member) in signatories.iter().take(s as usize - 1).enumerate() {
+			Multisig::<T>::approve_as_multi(
+				RawOrigin::Signed(member.clone()).into(),
+				0,
+				vec![multisig.clone()],
+				(i > 0).then_some(timepoint),
+				call_hash,
+				Weight::zero(),
+			)?;
+		}
+		let caller = signatories[s as usize - 1].clone();
+		assert!(StatefulProposals::<T>::contains_key(&multisig, call_hash));
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		as_multi(
+			RawOrigin::Signed(caller),
+			0,
+			vec![multisig.clone()],
+			Some(timepoint),
+			call,
+			Weight::MAX,
+		);
+
+		assert!(!StatefulProposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `s`: Members, need at least 2 people
+	#[benchmark]
+	fn approve_as_multi_stateful_create(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		// The call is neither in storage or an argument, so just use any:
+		let call_len = 10_000;
+		let (signatories, multisig, call) = setup_stateful_multi::<T>(s, call_len)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[0].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		approve_as_multi(
+			RawOrigin::Signed(caller),
+			0,
+			vec![multisig.clone()],
+			None,
+			call_hash,
+			Weight::zero(),
+		);
+
+		assert!(StatefulProposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `s`: Members, need at least 2 people
+	#[benchmark]
+	fn approve_as_multi_stateful_approve(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		// The call is neither in storage or an argument, so just use any:
+		let call_len = 10_000;
+		let (signatories, multisig, call) = setup_stateful_multi::<T>(s, call_len)?;
+		let call_hash = call.using_encoded(blake2_256);
+		// before the call, get the timepoint
+		let timepoint = Multisig::<T>::timepoint();
+		// Create the proposal
+		Multisig::<T>::approve_as_multi(
+			RawOrigin::Signed(signatories[0].clone()).into(),
+			0,
+			vec![multisig.clone()],
+			None,
+			call_hash,
+			Weight::zero(),
+		)?;
+		let caller2 = signatories[1].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller2);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		approve_as_multi(
+			RawOrigin::Signed(caller2),
+			0,
+			vec![multisig.clone()],
+			Some(timepoint),
+			call_hash,
+			Weight::zero(),
+		);
+
+		let proposal =
+			StatefulProposals::<T>::get(multisig, call_hash).ok_or("proposal not created")?;
+		assert_eq!(proposal.approvals.len(), 2);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_as_multi_stateful() -> Result<(), BenchmarkError> {
+		let s = T::MaxSignatories::get();
+		let (signatories, multisig, call) = setup_stateful_multi::<T>(s, 0)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[0].clone();
+		let timepoint = Multisig::<T>::timepoint();
+		// Create the proposal and approve it by everyone but the last member
+		for (i, member) in signatories.iter().take(s as usize - 1).enumerate() {
+			Multisig::<T>::approve_as_multi(
+				RawOrigin::Signed(member.clone()).into(),
+				0,
+				vec![multisig.clone()],
+				(i > 0).then_some(timepoint),
+				call_hash,
+				Weight::zero(),
+			)?;
+		}
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		cancel_as_multi(RawOrigin::Signed(caller), 0, vec![multisig.clone()], timepoint, call_hash);
+
+		assert!(!StatefulProposals::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `s`: New members
+	#[benchmark]
+	fn set_stateful_multisig_members(
+		s: Linear<1, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let (signatories, multisig, _) = setup_stateful_multi::<T>(T::MaxSignatories::get(), 0)?;
+		T::Currency::make_free_balance_be(&multisig, BalanceOf::<T>::max_value());
+		let members: Vec<_> =
+			signatories.into_iter().take(s as usize).map(|who| (who, 1)).collect();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(multisig.clone()), members, s);
+
+		let details = StatefulMultisigs::<T>::get(&multisig).ok_or("multisig not found")?;
+		assert_eq!(details.members.len(), s as usize);
+		assert_eq!(details.depositor, multisig);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn dissolve_stateful_multisig() -> Result<(), BenchmarkError> {
+		let s = T::MaxSignatories::get();
+		let (signatories, multisig, _) = setup_stateful_multi::<T>(s, 0)?;
+		// The multisig holds its own deposit, which is swept along with the rest of its funds.
+		T::Currency::make_free_balance_be(&multisig, BalanceOf::<T>::max_value());
+		let members = signatories.into_iter().map(|who| (who, 1)).collect();
+		Multisig::<T>::set_stateful_multisig_members(
+			RawOrigin::Signed(multisig.clone()).into(),
+			members,
+			s,
+		)?;
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(multisig.clone()), beneficiary.clone());
+
+		assert!(!StatefulMultisigs::<T>::contains_key(&multisig));
+		assert_eq!(T::Currency::free_balance(&beneficiary), BalanceOf::<T>::max_value());
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Multisig, crate::tests::new_test_ext(), crate::tests::Test);
 }
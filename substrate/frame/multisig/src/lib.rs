@@ -31,6 +31,25 @@
 //! operation. This is useful for multisig wallets where cryptographic threshold signatures are
 //! not available or desired.
 //!
+//! ### Stateful multisigs
+//!
+//! Since the account of a deterministic multisig is derived from its signatories and threshold,
+//! changing any signatory changes the account. A stateful multisig instead has an account that
+//! is assigned once on creation, while its members, their voting weights and the threshold are
+//! kept in storage. They can be changed by the multisig itself, by dispatching
+//! `set_stateful_multisig_members` through one of its own proposals. Without weighted voting,
+//! every member simply has a weight of `1`.
+//!
+//! Proposals of a stateful multisig follow the same flow as deterministic multisig operations:
+//! members approve a call hash, and the call is dispatched once the approving members together
+//! have at least the threshold weight. Only the approvals of current members count. Proposals
+//! expire after [`Config::ProposalLifetime`], after which anyone can remove them.
+//!
+//! Proposals are made through the same `as_multi`, `approve_as_multi` and `cancel_as_multi`
+//! dispatchables, by passing a `threshold` of `0` and the account of the stateful multisig as
+//! the only entry of `other_signatories`. The actual threshold and the members are then read
+//! from storage.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -39,6 +58,9 @@
 //!   number of signed origins.
 //! * `approve_as_multi` - Approve a call from a composite origin.
 //! * `cancel_as_multi` - Cancel a call from a composite origin.
+//! * `create_stateful_multisig` - Create a multisig with a stable account and on-chain members.
+//! * `set_stateful_multisig_members` - Change the members and threshold of a stateful multisig.
+//! * `dissolve_stateful_multisig` - Remove a stateful multisig and sweep its funds.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -51,8 +73,9 @@ pub mod weights;
 extern crate alloc;
 use alloc::{boxed::Box, vec, vec::Vec};
 use frame::{
+	arithmetic::ArithmeticError,
 	prelude::*,
-	traits::{Currency, ReservableCurrency},
+	traits::{Currency, ExistenceRequirement, ReservableCurrency},
 };
 use frame_system::RawOrigin;
 pub use weights::WeightInfo;
@@ -120,6 +143,79 @@ where
 	pub approvals: BoundedVec<AccountId, MaxApprovals>,
 }
 
+/// A multisig whose members and threshold are kept in storage, so that they can change without
+/// its account changing.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxMembers))]
+pub struct StatefulMultisig<Balance, AccountId, MaxMembers>
+where
+	MaxMembers: Get<u32>,
+{
+	/// The members with their voting weights. Always sorted by account.
+	pub members: BoundedVec<(AccountId, u32), MaxMembers>,
+	/// The total weight of approvals needed to dispatch a call.
+	pub threshold: u32,
+	/// The amount held in reserve of the `depositor` for as long as the multisig exists.
+	pub deposit: Balance,
+	/// The account holding the deposit; the creator until the members are first changed, the
+	/// multisig itself afterwards.
+	pub depositor: AccountId,
+	/// The number of proposals that are not yet executed or removed.
+	pub proposals: u32,
+}
+
+impl<Balance, AccountId: Ord, MaxMembers: Get<u32>>
+	StatefulMultisig<Balance, AccountId, MaxMembers>
+{
+	/// The voting weight of `who`, if it is a member.
+	pub fn weight_of(&self, who: &AccountId) -> Option<u32> {
+		self.members
+			.binary_search_by(|(member, _)| member.cmp(who))
+			.ok()
+			.map(|index| self.members[index].1)
+	}
+
+	/// The total voting weight of those `approvals` that are given by current members.
+	pub fn approval_weight(&self, approvals: &[AccountId]) -> u32 {
+		approvals
+			.iter()
+			.filter_map(|who| self.weight_of(who))
+			.fold(0u32, |total, weight| total.saturating_add(weight))
+	}
+}
+
+/// An open proposal of a stateful multisig.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxApprovals))]
+pub struct StatefulProposal<BlockNumber, Balance, AccountId, MaxApprovals>
+where
+	MaxApprovals: Get<u32>,
+{
+	/// The extrinsic when the proposal was opened.
+	pub when: Timepoint<BlockNumber>,
+	/// The amount held in reserve of the `depositor`, to be returned once the proposal ends.
+	pub deposit: Balance,
+	/// The account who opened it (i.e. the first to approve it).
+	pub depositor: AccountId,
+	/// The approvals achieved so far, including the depositor. Always sorted.
+	pub approvals: BoundedVec<AccountId, MaxApprovals>,
+	/// The block number from which on the proposal is expired.
+	pub expiry: BlockNumber,
+}
+
+pub type StatefulMultisigOf<T> = StatefulMultisig<
+	BalanceOf<T>,
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::MaxSignatories,
+>;
+
+pub type StatefulProposalOf<T> = StatefulProposal<
+	BlockNumberFor<T>,
+	BalanceOf<T>,
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::MaxSignatories,
+>;
+
 type CallHash = [u8; 32];
 
 enum CallOrHash<T: Config> {
@@ -191,6 +287,13 @@ pub mod pallet {
 		///     providers can be used. Relay provider can be a bit better in cases where the
 		///     parachain is lagging its block production to avoid clock skew.
 		type BlockNumberProvider: BlockNumberProvider;
+
+		/// The number of blocks for which a proposal of a stateful multisig stays open.
+		///
+		/// Once expired, a proposal can no longer be approved or executed and anyone may remove it,
+		/// returning its deposit.
+		#[pallet::constant]
+		type ProposalLifetime: Get<BlockNumberFor<Self>>;
 	}
 
 	/// The in-code storage version.
@@ -211,6 +314,26 @@ pub mod pallet {
 		Multisig<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxSignatories>,
 	>;
 
+	/// The index used to derive the account of the next stateful multisig.
+	#[pallet::storage]
+	pub type NextStatefulMultisigIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The stateful multisigs, keyed by their account.
+	#[pallet::storage]
+	pub type StatefulMultisigs<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, StatefulMultisigOf<T>>;
+
+	/// The open proposals of stateful multisigs.
+	#[pallet::storage]
+	pub type StatefulProposals<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		[u8; 32],
+		StatefulProposalOf<T>,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Threshold must be 2 or greater.
@@ -230,7 +353,7 @@ pub mod pallet {
 		/// Multisig operation not found in storage.
 		NotFound,
 		/// Only the account that originally created the multisig is able to cancel it or update
-		/// its deposits.
+		/// its deposits, unless it has expired.
 		NotOwner,
 		/// No timepoint was given, yet the multisig operation is already underway.
 		NoTimepoint,
@@ -242,6 +365,18 @@ pub mod pallet {
 		MaxWeightTooLow,
 		/// The data to be stored is already stored.
 		AlreadyStored,
+		/// The sender is not a member of the stateful multisig.
+		NotMember,
+		/// A member was given a voting weight of zero.
+		ZeroWeight,
+		/// The threshold is zero or greater than the total weight of all members.
+		UnreachableThreshold,
+		/// The proposal has expired and can no longer be approved.
+		ProposalExpired,
+		/// The stateful multisig still has open proposals.
+		PendingProposals,
+		/// The stateful multisig still has funds reserved by something other than its deposit.
+		FundsReserved,
 	}
 
 	#[pallet::event]
@@ -278,6 +413,12 @@ pub mod pallet {
 			old_deposit: BalanceOf<T>,
 			new_deposit: BalanceOf<T>,
 		},
+		/// A stateful multisig has been created.
+		StatefulMultisigCreated { creator: T::AccountId, multisig: T::AccountId, threshold: u32 },
+		/// The members or threshold of a stateful multisig have been changed.
+		StatefulMultisigMembersChanged { multisig: T::AccountId, threshold: u32 },
+		/// A stateful multisig has been dissolved.
+		StatefulMultisigDissolved { multisig: T::AccountId, beneficiary: T::AccountId },
 	}
 
 	#[pallet::hooks]
@@ -373,6 +514,10 @@ pub mod pallet {
 		/// transaction index) of the first approval transaction.
 		/// - `call`: The call to be executed.
 		///
+		/// A `threshold` of `0` addresses the stateful multisig whose account is the only entry of
+		/// `other_signatories`. The sender must be one of its members, and the call is dispatched
+		/// once the approving members together have at least its threshold weight.
+		///
 		/// NOTE: Unless this is the final approval, you will generally want to use
 		/// `approve_as_multi` instead, since it only requires a hash of the call.
 		///
@@ -395,12 +540,19 @@ pub mod pallet {
 		///   taken for its lifetime of `DepositBase + threshold * DepositFactor`.
 		#[pallet::call_index(1)]
 		#[pallet::weight({
-			let s = other_signatories.len() as u32;
 			let z = call.using_encoded(|d| d.len()) as u32;
 
-			T::WeightInfo::as_multi_create(s, z)
-			.max(T::WeightInfo::as_multi_approve(s, z))
-			.max(T::WeightInfo::as_multi_complete(s, z))
+			if *threshold == 0 {
+				let s = T::MaxSignatories::get();
+				T::WeightInfo::as_multi_stateful_create(s, z)
+					.max(T::WeightInfo::as_multi_stateful_approve(s, z))
+					.max(T::WeightInfo::as_multi_stateful_complete(s, z))
+			} else {
+				let s = other_signatories.len() as u32;
+				T::WeightInfo::as_multi_create(s, z)
+					.max(T::WeightInfo::as_multi_approve(s, z))
+					.max(T::WeightInfo::as_multi_complete(s, z))
+			}
 			.saturating_add(*max_weight)
 		})]
 		pub fn as_multi(
@@ -439,6 +591,9 @@ pub mod pallet {
 		/// transaction index) of the first approval transaction.
 		/// - `call_hash`: The hash of the call to be executed.
 		///
+		/// A `threshold` of `0` addresses the stateful multisig whose account is the only entry of
+		/// `other_signatories`, see `as_multi`.
+		///
 		/// NOTE: If this is the final approval, you will want to use `as_multi` instead.
 		///
 		/// ## Complexity
@@ -454,11 +609,16 @@ pub mod pallet {
 		///   taken for its lifetime of `DepositBase + threshold * DepositFactor`.
 		#[pallet::call_index(2)]
 		#[pallet::weight({
-			let s = other_signatories.len() as u32;
-
-			T::WeightInfo::approve_as_multi_create(s)
-				.max(T::WeightInfo::approve_as_multi_approve(s))
-				.saturating_add(*max_weight)
+			if *threshold == 0 {
+				let s = T::MaxSignatories::get();
+				T::WeightInfo::approve_as_multi_stateful_create(s)
+					.max(T::WeightInfo::approve_as_multi_stateful_approve(s))
+			} else {
+				let s = other_signatories.len() as u32;
+				T::WeightInfo::approve_as_multi_create(s)
+					.max(T::WeightInfo::approve_as_multi_approve(s))
+			}
+			.saturating_add(*max_weight)
 		})]
 		pub fn approve_as_multi(
 			origin: OriginFor<T>,
@@ -491,6 +651,9 @@ pub mod pallet {
 		/// transaction for this dispatch.
 		/// - `call_hash`: The hash of the call to be executed.
 		///
+		/// A `threshold` of `0` addresses the stateful multisig whose account is the only entry of
+		/// `other_signatories`. Its proposals can be cancelled by anyone once they have expired.
+		///
 		/// ## Complexity
 		/// - `O(S)`.
 		/// - Up to one balance-reserve or unreserve operation.
@@ -501,7 +664,11 @@ pub mod pallet {
 		/// - I/O: 1 read `O(S)`, one remove.
 		/// - Storage: removes one item.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::cancel_as_multi(other_signatories.len() as u32))]
+		#[pallet::weight(if *threshold == 0 {
+			T::WeightInfo::cancel_as_multi_stateful()
+		} else {
+			T::WeightInfo::cancel_as_multi(other_signatories.len() as u32)
+		})]
 		pub fn cancel_as_multi(
 			origin: OriginFor<T>,
 			threshold: u16,
@@ -510,6 +677,10 @@ pub mod pallet {
 			call_hash: [u8; 32],
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			if threshold == 0 {
+				let multisig = Self::stateful_target(other_signatories)?;
+				return Self::cancel_stateful(who, multisig, timepoint, call_hash)
+			}
 			ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
 			let max_sigs = T::MaxSignatories::get() as usize;
 			ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
@@ -612,6 +783,139 @@ pub mod pallet {
 				},
 			)
 		}
+
+		/// Create a multisig with a stable account, whose members and threshold are kept in
+		/// storage.
+		///
+		/// Payment: `DepositBase` plus `DepositFactor` for each member is reserved for as long as
+		/// the multisig exists.
+		///
+		/// The dispatch origin for this call must be _Signed_. The creator does not need to be a
+		/// member.
+		///
+		/// - `members`: The members with their voting weights, sorted by account. May not be empty,
+		///   and all weights must be non-zero.
+		/// - `threshold`: The total weight of approvals needed to dispatch a call.
+		///
+		/// Emits `StatefulMultisigCreated`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::create_stateful_multisig(members.len() as u32))]
+		pub fn create_stateful_multisig(
+			origin: OriginFor<T>,
+			members: Vec<(T::AccountId, u32)>,
+			threshold: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let members = Self::ensure_valid_members(members, threshold)?;
+
+			let index = NextStatefulMultisigIndex::<T>::get();
+			NextStatefulMultisigIndex::<T>::put(
+				index.checked_add(1).ok_or(ArithmeticError::Overflow)?,
+			);
+			let multisig = Self::stateful_multi_account_id(index);
+
+			let deposit = Self::stateful_deposit(members.len() as u32);
+			T::Currency::reserve(&who, deposit)?;
+
+			StatefulMultisigs::<T>::insert(
+				&multisig,
+				StatefulMultisig {
+					members,
+					threshold,
+					deposit,
+					depositor: who.clone(),
+					proposals: 0,
+				},
+			);
+			Self::deposit_event(Event::StatefulMultisigCreated {
+				creator: who,
+				multisig,
+				threshold,
+			});
+			Ok(())
+		}
+
+		/// Change the members and threshold of a stateful multisig.
+		///
+		/// Approvals already given to open proposals only keep counting for accounts that remain
+		/// members, with their new weight.
+		///
+		/// The deposit for storing the multisig is recalculated and from now on reserved from the
+		/// multisig account itself; a previous deposit of the creator is returned.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stateful multisig itself,
+		/// i.e. it must be dispatched through one of its proposals.
+		///
+		/// - `members`: The new members with their voting weights, sorted by account. May not be
+		///   empty, and all weights must be non-zero.
+		/// - `threshold`: The new total weight of approvals needed to dispatch a call.
+		///
+		/// Emits `StatefulMultisigMembersChanged`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_stateful_multisig_members(members.len() as u32))]
+		pub fn set_stateful_multisig_members(
+			origin: OriginFor<T>,
+			members: Vec<(T::AccountId, u32)>,
+			threshold: u32,
+		) -> DispatchResult {
+			let multisig = ensure_signed(origin)?;
+			let members = Self::ensure_valid_members(members, threshold)?;
+
+			StatefulMultisigs::<T>::try_mutate(&multisig, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::NotFound)?;
+
+				let deposit = Self::stateful_deposit(members.len() as u32);
+				T::Currency::unreserve(&details.depositor, details.deposit);
+				T::Currency::reserve(&multisig, deposit)?;
+
+				details.members = members;
+				details.threshold = threshold;
+				details.deposit = deposit;
+				details.depositor = multisig.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::StatefulMultisigMembersChanged { multisig, threshold });
+			Ok(())
+		}
+
+		/// Remove a stateful multisig, return its deposit and sweep its funds to `beneficiary`.
+		///
+		/// The multisig must not have any open proposals left, nor any funds reserved other than
+		/// its own deposit. Once dissolved, its account can no longer dispatch calls, so its whole
+		/// free balance, including a deposit it held itself, is transferred to `beneficiary`.
+		///
+		/// - `beneficiary`: The account that receives the funds of the multisig.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stateful multisig itself,
+		/// i.e. it must be dispatched through one of its proposals.
+		///
+		/// Emits `StatefulMultisigDissolved`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::dissolve_stateful_multisig())]
+		pub fn dissolve_stateful_multisig(
+			origin: OriginFor<T>,
+			beneficiary: T::AccountId,
+		) -> DispatchResult {
+			let multisig = ensure_signed(origin)?;
+			let details = StatefulMultisigs::<T>::get(&multisig).ok_or(Error::<T>::NotFound)?;
+			ensure!(details.proposals == 0, Error::<T>::PendingProposals);
+
+			StatefulMultisigs::<T>::remove(&multisig);
+			let err_amount = T::Currency::unreserve(&details.depositor, details.deposit);
+			debug_assert!(err_amount.is_zero());
+			ensure!(T::Currency::reserved_balance(&multisig).is_zero(), Error::<T>::FundsReserved);
+
+			T::Currency::transfer(
+				&multisig,
+				&beneficiary,
+				T::Currency::free_balance(&multisig),
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			Self::deposit_event(Event::StatefulMultisigDissolved { multisig, beneficiary });
+			Ok(())
+		}
 	}
 }
 
@@ -634,6 +938,10 @@ impl<T: Config> Pallet<T> {
 		call_or_hash: CallOrHash<T>,
 		max_weight: Weight,
 	) -> DispatchResultWithPostInfo {
+		if threshold == 0 {
+			let multisig = Self::stateful_target(other_signatories)?;
+			return Self::operate_stateful(who, multisig, maybe_timepoint, call_or_hash, max_weight)
+		}
 		ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
 		let max_sigs = T::MaxSignatories::get() as usize;
 		ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
@@ -791,6 +1099,228 @@ impl<T: Config> Pallet<T> {
 	pub fn deposit(threshold: u16) -> BalanceOf<T> {
 		T::DepositBase::get() + T::DepositFactor::get() * threshold.into()
 	}
+
+	/// Derive the account of the stateful multisig with the given `index`.
+	pub fn stateful_multi_account_id(index: u32) -> T::AccountId {
+		let entropy = (b"modlpy/statemult", index).using_encoded(blake2_256);
+		Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+
+	fn operate_stateful(
+		who: T::AccountId,
+		multisig: T::AccountId,
+		maybe_timepoint: Option<Timepoint<BlockNumberFor<T>>>,
+		call_or_hash: CallOrHash<T>,
+		max_weight: Weight,
+	) -> DispatchResultWithPostInfo {
+		let mut details = StatefulMultisigs::<T>::get(&multisig).ok_or(Error::<T>::NotFound)?;
+		let weight = details.weight_of(&who).ok_or(Error::<T>::NotMember)?;
+		let threshold = details.threshold;
+		let members_len = details.members.len() as u32;
+
+		let (call_hash, call_len, maybe_call) = match call_or_hash {
+			CallOrHash::Call(call) => {
+				let (call_hash, call_len) = call.using_encoded(|d| (blake2_256(d), d.len()));
+				(call_hash, call_len, Some(call))
+			},
+			CallOrHash::Hash(h) => (h, 0, None),
+		};
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let mut maybe_proposal = StatefulProposals::<T>::get(&multisig, call_hash);
+
+		// An expired proposal can no longer be approved, only be replaced by a new one.
+		if let Some(proposal) = maybe_proposal.clone().filter(|p| p.expiry <= now) {
+			ensure!(maybe_timepoint.is_none(), Error::<T>::ProposalExpired);
+			maybe_proposal = None;
+			Self::remove_stateful_proposal(&multisig, &mut details, call_hash, &proposal);
+			StatefulMultisigs::<T>::insert(&multisig, &details);
+			Self::deposit_event(Event::MultisigCancelled {
+				cancelling: who.clone(),
+				timepoint: proposal.when,
+				multisig: multisig.clone(),
+				call_hash,
+			});
+		}
+
+		// Branch on whether the proposal has already been opened or not.
+		let (timepoint, maybe_pos, approvals) = if let Some(ref mut p) = maybe_proposal {
+			// Yes; ensure that the timepoint exists and agrees.
+			let timepoint = maybe_timepoint.ok_or(Error::<T>::NoTimepoint)?;
+			ensure!(p.when == timepoint, Error::<T>::WrongTimepoint);
+
+			// Approvals of accounts that are no longer members do not count.
+			p.approvals.retain(|a| details.weight_of(a).is_some());
+			let mut approvals = details.approval_weight(&p.approvals);
+			// We only bother with the approval if we're below threshold.
+			let maybe_pos = p.approvals.binary_search(&who).err().filter(|_| approvals < threshold);
+			// Bump approvals if not yet voted and the vote is needed.
+			if maybe_pos.is_some() {
+				approvals = approvals.saturating_add(weight);
+			}
+			(timepoint, maybe_pos, approvals)
+		} else {
+			// Not yet opened; there should be no timepoint given.
+			ensure!(maybe_timepoint.is_none(), Error::<T>::UnexpectedTimepoint);
+			(Self::timepoint(), Some(0), weight)
+		};
+
+		// We only bother fetching/decoding call if we know that we're ready to execute.
+		if let Some(call) = maybe_call.filter(|_| approvals >= threshold) {
+			// verify weight
+			ensure!(
+				call.get_dispatch_info().call_weight.all_lte(max_weight),
+				Error::<T>::MaxWeightTooLow
+			);
+
+			// Clean up storage before executing call to avoid an possibility of reentrancy
+			// attack.
+			if let Some(ref p) = maybe_proposal {
+				Self::remove_stateful_proposal(&multisig, &mut details, call_hash, p);
+				StatefulMultisigs::<T>::insert(&multisig, details);
+			}
+
+			let result = call.dispatch(RawOrigin::Signed(multisig.clone()).into());
+			Self::deposit_event(Event::MultisigExecuted {
+				approving: who,
+				timepoint,
+				multisig,
+				call_hash,
+				result: result.map(|_| ()).map_err(|e| e.error),
+			});
+			return Ok(get_result_weight(result)
+				.map(|actual_weight| {
+					T::WeightInfo::as_multi_stateful_complete(members_len, call_len as u32)
+						.saturating_add(actual_weight)
+				})
+				.into())
+		}
+
+		// We cannot dispatch the call now; either it isn't available, or it is, but we don't have
+		// threshold approvals even with our approval.
+		if let Some(mut p) = maybe_proposal {
+			let pos = maybe_pos.ok_or(Error::<T>::AlreadyApproved)?;
+			// Record approval.
+			p.approvals
+				.try_insert(pos, who.clone())
+				.map_err(|_| Error::<T>::TooManySignatories)?;
+			StatefulProposals::<T>::insert(&multisig, call_hash, p);
+			Self::deposit_event(Event::MultisigApproval {
+				approving: who,
+				timepoint,
+				multisig,
+				call_hash,
+			});
+
+			let final_weight =
+				T::WeightInfo::as_multi_stateful_approve(members_len, call_len as u32);
+			// Call is not made, so the actual weight does not include call
+			Ok(Some(final_weight).into())
+		} else {
+			// Just open the proposal by recording it in storage.
+			let deposit = Self::stateful_deposit(members_len);
+			T::Currency::reserve(&who, deposit)?;
+
+			let initial_approvals =
+				vec![who.clone()].try_into().map_err(|_| Error::<T>::TooManySignatories)?;
+
+			StatefulProposals::<T>::insert(
+				&multisig,
+				call_hash,
+				StatefulProposal {
+					when: timepoint,
+					deposit,
+					depositor: who.clone(),
+					approvals: initial_approvals,
+					expiry: now.saturating_add(T::ProposalLifetime::get()),
+				},
+			);
+			details.proposals.saturating_inc();
+			StatefulMultisigs::<T>::insert(&multisig, details);
+			Self::deposit_event(Event::NewMultisig { approving: who, multisig, call_hash });
+
+			let final_weight =
+				T::WeightInfo::as_multi_stateful_create(members_len, call_len as u32);
+			// Call is not made, so the actual weight does not include call
+			Ok(Some(final_weight).into())
+		}
+	}
+
+	/// The account of the stateful multisig addressed by `other_signatories`, which must contain
+	/// nothing else.
+	fn stateful_target(
+		other_signatories: Vec<T::AccountId>,
+	) -> Result<T::AccountId, DispatchError> {
+		let mut other_signatories = other_signatories.into_iter();
+		let multisig = other_signatories.next().ok_or(Error::<T>::TooFewSignatories)?;
+		ensure!(other_signatories.next().is_none(), Error::<T>::TooManySignatories);
+		Ok(multisig)
+	}
+
+	fn cancel_stateful(
+		who: T::AccountId,
+		multisig: T::AccountId,
+		timepoint: Timepoint<BlockNumberFor<T>>,
+		call_hash: CallHash,
+	) -> DispatchResult {
+		let mut details = StatefulMultisigs::<T>::get(&multisig).ok_or(Error::<T>::NotFound)?;
+		let proposal =
+			StatefulProposals::<T>::get(&multisig, call_hash).ok_or(Error::<T>::NotFound)?;
+		ensure!(proposal.when == timepoint, Error::<T>::WrongTimepoint);
+		let now = T::BlockNumberProvider::current_block_number();
+		ensure!(proposal.depositor == who || proposal.expiry <= now, Error::<T>::NotOwner);
+
+		Self::remove_stateful_proposal(&multisig, &mut details, call_hash, &proposal);
+		StatefulMultisigs::<T>::insert(&multisig, details);
+
+		Self::deposit_event(Event::MultisigCancelled {
+			cancelling: who,
+			timepoint,
+			multisig,
+			call_hash,
+		});
+		Ok(())
+	}
+
+	/// Remove a proposal of a stateful multisig and return its deposit.
+	///
+	/// The caller must write `details` back to storage.
+	fn remove_stateful_proposal(
+		multisig: &T::AccountId,
+		details: &mut StatefulMultisigOf<T>,
+		call_hash: CallHash,
+		proposal: &StatefulProposalOf<T>,
+	) {
+		StatefulProposals::<T>::remove(multisig, call_hash);
+		let err_amount = T::Currency::unreserve(&proposal.depositor, proposal.deposit);
+		debug_assert!(err_amount.is_zero());
+		details.proposals.saturating_dec();
+	}
+
+	/// Check that the members of a stateful multisig are sorted, have non-zero weights and can
+	/// reach `threshold` together.
+	fn ensure_valid_members(
+		members: Vec<(T::AccountId, u32)>,
+		threshold: u32,
+	) -> Result<BoundedVec<(T::AccountId, u32), T::MaxSignatories>, DispatchError> {
+		ensure!(!members.is_empty(), Error::<T>::TooFewSignatories);
+		ensure!(
+			members.windows(2).all(|pair| pair[0].0 < pair[1].0),
+			Error::<T>::SignatoriesOutOfOrder
+		);
+		ensure!(members.iter().all(|(_, weight)| *weight > 0), Error::<T>::ZeroWeight);
+		let total = members.iter().fold(0u32, |total, (_, weight)| total.saturating_add(*weight));
+		ensure!(threshold > 0 && threshold <= total, Error::<T>::UnreachableThreshold);
+		members.try_into().map_err(|_| Error::<T>::TooManySignatories.into())
+	}
+
+	/// Calculate the deposit for a stateful multisig or one of its proposals.
+	///
+	/// The deposit is calculated as `DepositBase + DepositFactor * members`.
+	pub fn stateful_deposit(members: u32) -> BalanceOf<T> {
+		T::DepositBase::get() + T::DepositFactor::get() * members.into()
+	}
 }
 
 /// Return the weight of a dispatch call result as an `Option`.
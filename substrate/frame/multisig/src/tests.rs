@@ -52,6 +52,11 @@ impl Contains<RuntimeCall> for TestBaseCallFilter {
 	fn contains(c: &RuntimeCall) -> bool {
 		match *c {
 			RuntimeCall::Balances(_) => true,
+			// Needed for stateful multisigs to manage themselves
+			RuntimeCall::Multisig(
+				Call::set_stateful_multisig_members { .. } |
+				Call::dissolve_stateful_multisig { .. },
+			) => true,
 			// Needed for benchmarking
 			RuntimeCall::System(frame_system::Call::remark { .. }) => true,
 			_ => false,
@@ -73,6 +78,7 @@ impl Config for Test {
 	type MaxSignatories = ConstU32<3>;
 	type WeightInfo = ();
 	type BlockNumberProvider = frame_system::Pallet<Test>;
+	type ProposalLifetime = ConstU32<10>;
 }
 
 use pallet_balances::{Call as BalancesCall, Error as BalancesError};
@@ -468,6 +474,7 @@ fn multisig_2_of_3_cannot_reissue_same_call() {
 fn minimum_threshold_check_works() {
 	new_test_ext().execute_with(|| {
 		let call = call_transfer(6, 15);
+		// A threshold of zero addresses a stateful multisig, which 2 is not.
 		assert_noop!(
 			Multisig::as_multi(
 				RuntimeOrigin::signed(1),
@@ -477,7 +484,7 @@ fn minimum_threshold_check_works() {
 				call.clone(),
 				Weight::zero()
 			),
-			Error::<Test>::MinimumThreshold,
+			Error::<Test>::NotFound,
 		);
 		assert_noop!(
 			Multisig::as_multi(
@@ -1015,3 +1022,622 @@ fn poke_deposit_handles_insufficient_balance() {
 		);
 	});
 }
+
+fn create_stateful_multisig(members: Vec<(u64, u32)>, threshold: u32) -> u64 {
+	let index = NextStatefulMultisigIndex::<Test>::get();
+	assert_ok!(Multisig::create_stateful_multisig(RuntimeOrigin::signed(4), members, threshold));
+	Multisig::stateful_multi_account_id(index)
+}
+
+#[test]
+fn create_stateful_multisig_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Multisig::create_stateful_multisig(RuntimeOrigin::signed(4), vec![], 1),
+			Error::<Test>::TooFewSignatories,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(RuntimeOrigin::signed(4), vec![(2, 1), (1, 1)], 1),
+			Error::<Test>::SignatoriesOutOfOrder,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(RuntimeOrigin::signed(4), vec![(1, 1), (1, 1)], 1),
+			Error::<Test>::SignatoriesOutOfOrder,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(RuntimeOrigin::signed(4), vec![(1, 1), (2, 0)], 1),
+			Error::<Test>::ZeroWeight,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(RuntimeOrigin::signed(4), vec![(1, 1), (2, 1)], 0),
+			Error::<Test>::UnreachableThreshold,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(RuntimeOrigin::signed(4), vec![(1, 1), (2, 1)], 3),
+			Error::<Test>::UnreachableThreshold,
+		);
+		assert_noop!(
+			Multisig::create_stateful_multisig(
+				RuntimeOrigin::signed(4),
+				vec![(1, 1), (2, 1), (3, 1), (5, 1)],
+				2
+			),
+			Error::<Test>::TooManySignatories,
+		);
+
+		let multi = create_stateful_multisig(vec![(1, 1), (2, 1), (3, 1)], 2);
+		System::assert_last_event(
+			pallet_multisig::Event::StatefulMultisigCreated {
+				creator: 4,
+				multisig: multi,
+				threshold: 2,
+			}
+			.into(),
+		);
+		// The account is derived from the index, not from the members.
+		assert_eq!(multi, Multisig::stateful_multi_account_id(0));
+		assert_ne!(multi, Multisig::multi_account_id(&[1, 2, 3][..], 2));
+		assert_eq!(NextStatefulMultisigIndex::<Test>::get(), 1);
+		assert_eq!(Balances::reserved_balance(4), 4);
+
+		let details = StatefulMultisigs::<Test>::get(multi).unwrap();
+		assert_eq!(details.threshold, 2);
+		assert_eq!(details.depositor, 4);
+		assert_eq!(details.weight_of(&2), Some(1));
+		assert_eq!(details.weight_of(&4), None);
+
+		assert_eq!(Balances::free_balance(4), 1);
+		assert_noop!(
+			Multisig::create_stateful_multisig(RuntimeOrigin::signed(4), vec![(1, 1), (2, 1)], 1),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+		// Creating the same members again gives a different account.
+		assert_ok!(Multisig::create_stateful_multisig(
+			RuntimeOrigin::signed(1),
+			vec![(1, 1), (2, 1), (3, 1)],
+			2
+		));
+		assert!(StatefulMultisigs::<Test>::contains_key(Multisig::stateful_multi_account_id(1)));
+	});
+}
+
+#[test]
+fn stateful_multisig_2_of_3_works() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stateful_multisig(vec![(1, 1), (2, 1), (3, 1)], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let call = call_transfer(6, 10);
+		let call_weight = call.get_dispatch_info().call_weight;
+		let hash = blake2_256(&call.encode());
+
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(5),
+				0,
+				vec![multi],
+				None,
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::NotMember,
+		);
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multi],
+			None,
+			hash,
+			Weight::zero()
+		));
+		System::assert_last_event(
+			pallet_multisig::Event::NewMultisig { approving: 1, multisig: multi, call_hash: hash }
+				.into(),
+		);
+		assert_eq!(Balances::reserved_balance(1), 4);
+		assert_eq!(StatefulMultisigs::<Test>::get(multi).unwrap().proposals, 1);
+		assert_eq!(StatefulProposals::<Test>::get(multi, hash).unwrap().expiry, 11);
+
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(1),
+				0,
+				vec![multi],
+				Some(now()),
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::AlreadyApproved,
+		);
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(2),
+				0,
+				vec![multi],
+				None,
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::NoTimepoint,
+		);
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(2),
+				0,
+				vec![multi],
+				Some(now()),
+				call.clone(),
+				Weight::zero()
+			),
+			Error::<Test>::MaxWeightTooLow,
+		);
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multi],
+			Some(now()),
+			call,
+			call_weight
+		));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigExecuted {
+				approving: 2,
+				timepoint: now(),
+				multisig: multi,
+				call_hash: hash,
+				result: Ok(()),
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(6), 10);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(!StatefulProposals::<Test>::contains_key(multi, hash));
+		assert_eq!(StatefulMultisigs::<Test>::get(multi).unwrap().proposals, 0);
+	});
+}
+
+#[test]
+fn stateful_multisig_must_be_the_only_signatory() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stateful_multisig(vec![(1, 1), (2, 1), (3, 1)], 2);
+		let hash = blake2_256(&call_transfer(6, 10).encode());
+
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(1),
+				0,
+				vec![],
+				None,
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::TooFewSignatories,
+		);
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(1),
+				0,
+				vec![2, multi],
+				None,
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::TooManySignatories,
+		);
+		assert_noop!(
+			Multisig::cancel_as_multi(RuntimeOrigin::signed(1), 0, vec![multi, 2], now(), hash),
+			Error::<Test>::TooManySignatories,
+		);
+
+		// The members of the stateful multisig cannot use it as a deterministic one.
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![multi],
+			None,
+			hash,
+			Weight::zero()
+		));
+		assert!(!StatefulProposals::<Test>::contains_key(multi, hash));
+		assert!(Multisigs::<Test>::contains_key(Multisig::multi_account_id(&[1, multi], 2), hash));
+	});
+}
+
+#[test]
+fn stateful_multisig_weighted_voting_works() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stateful_multisig(vec![(1, 2), (2, 1), (3, 1)], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		// The weight of 1 alone reaches the threshold.
+		let call = call_transfer(6, 3);
+		let call_weight = call.get_dispatch_info().call_weight;
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multi],
+			None,
+			call,
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 3);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// 2 and 3 need each other.
+		let call = call_transfer(6, 4);
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multi],
+			None,
+			call.clone(),
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 3);
+		assert_eq!(Balances::reserved_balance(2), 4);
+		assert!(StatefulProposals::<Test>::contains_key(multi, hash));
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(3),
+			0,
+			vec![multi],
+			Some(now()),
+			call,
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 7);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn stateful_multisig_members_can_be_changed() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stateful_multisig(vec![(1, 1), (2, 1), (3, 1)], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let call = call_transfer(6, 5);
+		let call_weight = call.get_dispatch_info().call_weight;
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multi],
+			None,
+			hash,
+			Weight::zero()
+		));
+
+		// Only the multisig itself can change its members.
+		assert_noop!(
+			Multisig::set_stateful_multisig_members(RuntimeOrigin::signed(1), vec![(1, 1)], 1),
+			Error::<Test>::NotFound,
+		);
+
+		// Replace 1 with 4.
+		let set_members = Box::new(RuntimeCall::Multisig(Call::set_stateful_multisig_members {
+			members: vec![(2, 1), (3, 1), (4, 1)],
+			threshold: 2,
+		}));
+		let set_members_weight = set_members.get_dispatch_info().call_weight;
+		let set_members_hash = blake2_256(&set_members.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multi],
+			None,
+			set_members_hash,
+			Weight::zero()
+		));
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(3),
+			0,
+			vec![multi],
+			Some(now()),
+			set_members,
+			set_members_weight
+		));
+		System::assert_has_event(
+			pallet_multisig::Event::StatefulMultisigMembersChanged {
+				multisig: multi,
+				threshold: 2,
+			}
+			.into(),
+		);
+
+		// The multisig now holds its own deposit.
+		let details = StatefulMultisigs::<Test>::get(multi).unwrap();
+		assert_eq!(details.depositor, multi);
+		assert_eq!(details.weight_of(&1), None);
+		assert_eq!(details.weight_of(&4), Some(1));
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::reserved_balance(multi), 4);
+		assert_eq!(Balances::free_balance(multi), 6);
+
+		// The approval of 1 no longer counts, and 1 can no longer approve.
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(1),
+				0,
+				vec![multi],
+				Some(now()),
+				call.clone(),
+				call_weight
+			),
+			Error::<Test>::NotMember,
+		);
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(4),
+			0,
+			vec![multi],
+			Some(now()),
+			call.clone(),
+			call_weight
+		));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigApproval {
+				approving: 4,
+				timepoint: now(),
+				multisig: multi,
+				call_hash: hash,
+			}
+			.into(),
+		);
+		assert_eq!(
+			StatefulProposals::<Test>::get(multi, hash).unwrap().approvals.to_vec(),
+			vec![4]
+		);
+		assert_eq!(Balances::free_balance(6), 0);
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multi],
+			Some(now()),
+			call,
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 5);
+		// The deposit still goes back to the former member who opened the proposal.
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn stateful_proposals_expire() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stateful_multisig(vec![(1, 1), (2, 1), (3, 1)], 2);
+		let hash = blake2_256(&call_transfer(6, 5).encode());
+
+		let timepoint = now();
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multi],
+			None,
+			hash,
+			Weight::zero()
+		));
+
+		System::set_block_number(10);
+		assert_noop!(
+			Multisig::cancel_as_multi(RuntimeOrigin::signed(3), 0, vec![multi], timepoint, hash),
+			Error::<Test>::NotOwner,
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(2),
+				0,
+				vec![multi],
+				Some(timepoint),
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::ProposalExpired,
+		);
+		// Anyone can remove an expired proposal.
+		assert_ok!(Multisig::cancel_as_multi(
+			RuntimeOrigin::signed(3),
+			0,
+			vec![multi],
+			timepoint,
+			hash
+		));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigCancelled {
+				cancelling: 3,
+				timepoint,
+				multisig: multi,
+				call_hash: hash,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(StatefulMultisigs::<Test>::get(multi).unwrap().proposals, 0);
+
+		// An expired proposal is replaced when the call is proposed again.
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multi],
+			None,
+			hash,
+			Weight::zero()
+		));
+		System::set_block_number(21);
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multi],
+			None,
+			hash,
+			Weight::zero()
+		));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 4);
+		let proposal = StatefulProposals::<Test>::get(multi, hash).unwrap();
+		assert_eq!(proposal.depositor, 2);
+		assert_eq!(proposal.when, now());
+		assert_eq!(proposal.expiry, 31);
+		assert_eq!(StatefulMultisigs::<Test>::get(multi).unwrap().proposals, 1);
+	});
+}
+
+#[test]
+fn dissolve_stateful_multisig_works() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stateful_multisig(vec![(1, 1), (2, 1)], 1);
+		assert_eq!(Balances::reserved_balance(4), 3);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let hash = blake2_256(&call_transfer(6, 5).encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multi],
+			None,
+			hash,
+			Weight::zero()
+		));
+
+		let dissolve =
+			Box::new(RuntimeCall::Multisig(Call::dissolve_stateful_multisig { beneficiary: 6 }));
+		let dissolve_weight = dissolve.get_dispatch_info().call_weight;
+		let dissolve_hash = blake2_256(&dissolve.encode());
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multi],
+			None,
+			dissolve.clone(),
+			dissolve_weight
+		));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigExecuted {
+				approving: 2,
+				timepoint: now(),
+				multisig: multi,
+				call_hash: dissolve_hash,
+				result: Err(Error::<Test>::PendingProposals.into()),
+			}
+			.into(),
+		);
+
+		assert_ok!(Multisig::cancel_as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multi],
+			now(),
+			hash
+		));
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			0,
+			vec![multi],
+			None,
+			dissolve,
+			dissolve_weight
+		));
+		System::assert_has_event(
+			pallet_multisig::Event::StatefulMultisigDissolved { multisig: multi, beneficiary: 6 }
+				.into(),
+		);
+		assert!(!StatefulMultisigs::<Test>::contains_key(multi));
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::free_balance(multi), 0);
+		assert_eq!(Balances::free_balance(6), 5);
+
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(1),
+				0,
+				vec![multi],
+				None,
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::NotFound,
+		);
+	});
+}
+
+#[test]
+fn dissolve_stateful_multisig_sweeps_its_own_deposit() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stateful_multisig(vec![(1, 1), (2, 1)], 1);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 10));
+
+		let set_members = Box::new(RuntimeCall::Multisig(Call::set_stateful_multisig_members {
+			members: vec![(1, 1), (2, 1)],
+			threshold: 1,
+		}));
+		let set_members_weight = set_members.get_dispatch_info().call_weight;
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multi],
+			None,
+			set_members,
+			set_members_weight
+		));
+		assert_eq!(StatefulMultisigs::<Test>::get(multi).unwrap().depositor, multi);
+		assert_eq!(Balances::reserved_balance(multi), 3);
+
+		let dissolve =
+			Box::new(RuntimeCall::Multisig(Call::dissolve_stateful_multisig { beneficiary: 6 }));
+		let dissolve_weight = dissolve.get_dispatch_info().call_weight;
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multi],
+			None,
+			dissolve,
+			dissolve_weight
+		));
+		assert!(!StatefulMultisigs::<Test>::contains_key(multi));
+		assert_eq!(Balances::total_balance(&multi), 0);
+		assert_eq!(Balances::free_balance(6), 10);
+	});
+}
+
+#[test]
+fn dissolve_stateful_multisig_fails_with_other_reserves() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stateful_multisig(vec![(1, 1), (2, 1)], 1);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+		assert_ok!(Balances::reserve(&multi, 2));
+
+		let dissolve =
+			Box::new(RuntimeCall::Multisig(Call::dissolve_stateful_multisig { beneficiary: 6 }));
+		let dissolve_weight = dissolve.get_dispatch_info().call_weight;
+		let dissolve_hash = blake2_256(&dissolve.encode());
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![multi],
+			None,
+			dissolve,
+			dissolve_weight
+		));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigExecuted {
+				approving: 1,
+				timepoint: now(),
+				multisig: multi,
+				call_hash: dissolve_hash,
+				result: Err(Error::<Test>::FundsReserved.into()),
+			}
+			.into(),
+		);
+		assert!(StatefulMultisigs::<Test>::contains_key(multi));
+		assert_eq!(Balances::reserved_balance(4), 3);
+		assert_eq!(Balances::free_balance(multi), 3);
+	});
+}
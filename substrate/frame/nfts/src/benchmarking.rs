@@ -189,6 +189,33 @@ fn add_collection_attribute<T: Config<I>, I: 'static>(
 	(key, caller, caller_lookup)
 }
 
+fn sell_with_royalty<T: Config<I>, I: 'static>(
+	collection: T::CollectionId,
+	item: T::ItemId,
+) -> (T::AccountId, T::AccountId, ItemPrice<T, I>) {
+	let owner = Collection::<T, I>::get(collection).unwrap().owner;
+	let seller: T::AccountId = account("seller", 0, SEED);
+	let buyer: T::AccountId = account("buyer", 0, SEED);
+	let recipient: T::AccountId = account("recipient", 0, SEED);
+	let ed = T::Currency::minimum_balance();
+	let price = ed.saturating_mul(10u32.into());
+	T::Currency::make_free_balance_be(&seller, ed);
+	T::Currency::make_free_balance_be(&recipient, ed);
+	T::Currency::make_free_balance_be(&buyer, price.saturating_mul(2u32.into()));
+	assert_ok!(Nfts::<T, I>::transfer(
+		SystemOrigin::Signed(owner.clone()).into(),
+		collection,
+		item,
+		T::Lookup::unlookup(seller.clone()),
+	));
+	assert_ok!(Nfts::<T, I>::set_collection_royalty(
+		SystemOrigin::Signed(owner).into(),
+		collection,
+		Some(CollectionRoyalty { recipient, percentage: T::MaxRoyalty::get() }),
+	));
+	(seller, buyer, price)
+}
+
 fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -662,14 +689,12 @@ benchmarks_instance_pallet! {
 	}
 
 	buy_item {
-		let (collection, seller, _) = create_collection::<T, I>();
+		let (collection, ..) = create_collection::<T, I>();
 		let (item, ..) = mint_item::<T, I>(0);
-		let buyer: T::AccountId = account("buyer", 0, SEED);
+		let (seller, buyer, price) = sell_with_royalty::<T, I>(collection, item);
 		let buyer_lookup = T::Lookup::unlookup(buyer.clone());
-		let price = ItemPrice::<T, I>::from(0u32);
 		let origin = SystemOrigin::Signed(seller.clone()).into();
 		Nfts::<T, I>::set_price(origin, collection, item, Some(price), Some(buyer_lookup))?;
-		T::Currency::make_free_balance_be(&buyer, DepositBalanceOf::<T, I>::max_value());
 	}: _(SystemOrigin::Signed(buyer.clone()), collection, item, price)
 	verify {
 		assert_last_event::<T, I>(Event::ItemBought {
@@ -753,16 +778,24 @@ benchmarks_instance_pallet! {
 		let (collection, caller, _) = create_collection::<T, I>();
 		let (item1, ..) = mint_item::<T, I>(0);
 		let (item2, ..) = mint_item::<T, I>(1);
-		let price = ItemPrice::<T, I>::from(0u32);
-		let price_direction = PriceDirection::Receive;
+		let ed = T::Currency::minimum_balance();
+		let price = ed.saturating_mul(10u32.into());
+		let price_direction = PriceDirection::Send;
 		let price_with_direction = PriceWithDirection { amount: price, direction: price_direction };
 		let duration = T::MaxDeadlineDuration::get();
 		let target: T::AccountId = account("target", 0, SEED);
 		let target_lookup = T::Lookup::unlookup(target.clone());
-		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		T::Currency::make_free_balance_be(&target, ed);
+		T::Currency::make_free_balance_be(&recipient, ed);
 		let origin = SystemOrigin::Signed(caller.clone());
 		T::BlockNumberProvider::set_block_number(One::one());
 		Nfts::<T, I>::transfer(origin.clone().into(), collection, item2, target_lookup)?;
+		Nfts::<T, I>::set_collection_royalty(
+			origin.clone().into(),
+			collection,
+			Some(CollectionRoyalty { recipient, percentage: T::MaxRoyalty::get() }),
+		)?;
 		Nfts::<T, I>::create_swap(
 			origin.clone().into(),
 			collection,
@@ -878,5 +911,103 @@ benchmarks_instance_pallet! {
 		);
 	}
 
+	set_collection_royalty {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let royalty = CollectionRoyalty { recipient: caller.clone(), percentage: T::MaxRoyalty::get() };
+	}: _(SystemOrigin::Signed(caller.clone()), collection, Some(royalty.clone()))
+	verify {
+		assert_last_event::<T, I>(Event::CollectionRoyaltySet {
+			collection,
+			royalty: Some(royalty),
+		}.into());
+	}
+
+	create_auction {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let kind = AuctionKind::English { min_bid: ItemPrice::<T, I>::from(100u32) };
+		let duration = T::MaxDeadlineDuration::get();
+		T::BlockNumberProvider::set_block_number(One::one());
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item, kind, duration)
+	verify {
+		assert_last_event::<T, I>(Event::AuctionCreated {
+			collection,
+			item,
+			seller: caller,
+			kind,
+			start: One::one(),
+			end: duration.saturating_add(One::one()),
+		}.into());
+	}
+
+	bid {
+		let (collection, ..) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let (seller, buyer, price) = sell_with_royalty::<T, I>(collection, item);
+		let kind = AuctionKind::Dutch { start_price: price, end_price: price };
+		T::BlockNumberProvider::set_block_number(One::one());
+		Nfts::<T, I>::create_auction(
+			SystemOrigin::Signed(seller.clone()).into(),
+			collection,
+			item,
+			kind,
+			T::MaxDeadlineDuration::get(),
+		)?;
+	}: _(SystemOrigin::Signed(buyer.clone()), collection, item, price)
+	verify {
+		assert_last_event::<T, I>(Event::AuctionSettled {
+			collection,
+			item,
+			seller,
+			buyer,
+			price,
+		}.into());
+	}
+
+	settle_auction {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let (seller, buyer, price) = sell_with_royalty::<T, I>(collection, item);
+		let duration = T::MaxDeadlineDuration::get();
+		T::BlockNumberProvider::set_block_number(One::one());
+		Nfts::<T, I>::create_auction(
+			SystemOrigin::Signed(seller.clone()).into(),
+			collection,
+			item,
+			AuctionKind::English { min_bid: price },
+			duration,
+		)?;
+		Nfts::<T, I>::bid(SystemOrigin::Signed(buyer.clone()).into(), collection, item, price)?;
+		T::BlockNumberProvider::set_block_number(
+			duration.saturating_add(T::AuctionExtension::get()).saturating_add(One::one()),
+		);
+	}: _(SystemOrigin::Signed(caller), collection, item)
+	verify {
+		assert_last_event::<T, I>(Event::AuctionSettled {
+			collection,
+			item,
+			seller,
+			buyer,
+			price,
+		}.into());
+	}
+
+	cancel_auction {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let kind = AuctionKind::English { min_bid: ItemPrice::<T, I>::from(100u32) };
+		T::BlockNumberProvider::set_block_number(One::one());
+		Nfts::<T, I>::create_auction(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			item,
+			kind,
+			T::MaxDeadlineDuration::get(),
+		)?;
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item)
+	verify {
+		assert_last_event::<T, I>(Event::AuctionCancelled { collection, item }.into());
+	}
+
 	impl_benchmark_test_suite!(Nfts, crate::mock::new_test_ext(), crate::mock::Test);
 }
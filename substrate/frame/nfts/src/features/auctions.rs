@@ -0,0 +1,304 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains helper methods to run English and Dutch auctions of items in the NFTs
+//! pallet.
+//! The bitflag [`PalletFeature::Auctions`] needs to be set in the [`Config::Features`] for NFTs
+//! to have the functionality defined in this module.
+//!
+//! An auctioned item is locked with the `TransferDisabled` system attribute, the same way
+//! `nonfungibles_v2::Transfer::disable_transfer` locks items, so neither its owner nor its
+//! collection's freezer can move or burn it until the auction is closed.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	storage::with_storage_layer,
+	traits::{tokens::nonfungibles_v2::Transfer, ReservableCurrency},
+};
+use sp_runtime::Perbill;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Puts an item up for auction.
+	///
+	/// - `seller`: The account creating the auction, which must be the owner of the item.
+	/// - `collection`: The identifier of the collection containing the item.
+	/// - `item`: The identifier of the item to auction.
+	/// - `kind`: The pricing mechanism of the auction.
+	/// - `duration`: The number of blocks the auction lasts for.
+	///
+	/// Locks the item and emits the `AuctionCreated` event.
+	pub(crate) fn do_create_auction(
+		seller: T::AccountId,
+		collection: T::CollectionId,
+		item: T::ItemId,
+		kind: AuctionKind<BalanceOf<T, I>>,
+		duration: BlockNumberFor<T, I>,
+	) -> DispatchResult {
+		ensure!(
+			Self::is_pallet_feature_enabled(PalletFeature::Auctions),
+			Error::<T, I>::MethodDisabled
+		);
+		ensure!(
+			!duration.is_zero() && duration <= T::MaxDeadlineDuration::get(),
+			Error::<T, I>::WrongDuration
+		);
+		if let AuctionKind::Dutch { start_price, end_price } = kind {
+			ensure!(start_price >= end_price, Error::<T, I>::IncorrectAuctionPrices);
+		}
+
+		let details = Item::<T, I>::get(&collection, &item).ok_or(Error::<T, I>::UnknownItem)?;
+		ensure!(details.owner == seller, Error::<T, I>::NoPermission);
+
+		let collection_config = Self::get_collection_config(&collection)?;
+		ensure!(
+			collection_config.is_setting_enabled(CollectionSetting::TransferableItems),
+			Error::<T, I>::ItemsNonTransferable
+		);
+
+		let item_config = Self::get_item_config(&collection, &item)?;
+		ensure!(
+			item_config.is_setting_enabled(ItemSetting::Transferable),
+			Error::<T, I>::ItemLocked
+		);
+		ensure!(!T::Locker::is_locked(collection, item), Error::<T, I>::ItemLocked);
+
+		// Fails if the item is already locked, which includes it being auctioned.
+		<Self as Transfer<T::AccountId>>::disable_transfer(&collection, &item)?;
+
+		let start = T::BlockNumberProvider::current_block_number();
+		let end = start.saturating_add(duration);
+		ItemAuctionOf::<T, I>::insert(
+			&collection,
+			&item,
+			Auction {
+				seller: seller.clone(),
+				kind,
+				start,
+				end,
+				highest_bid: None,
+				royalty: CollectionRoyaltyOf::<T, I>::get(&collection),
+			},
+		);
+
+		Self::deposit_event(Event::AuctionCreated { collection, item, seller, kind, start, end });
+		Ok(())
+	}
+
+	/// Bids in the auction of an item.
+	///
+	/// - `bidder`: The account placing the bid.
+	/// - `collection`: The identifier of the collection containing the item.
+	/// - `item`: The identifier of the auctioned item.
+	/// - `amount`: The bid, or the max price the bidder is willing to pay in a Dutch auction.
+	///
+	/// In an English auction, the bid is reserved and the previous highest bid returned, and the
+	/// auction is extended if it's about to end. Emits the `BidPlaced` event.
+	///
+	/// In a Dutch auction, the item is sold to the bidder at the current price. Emits the
+	/// `AuctionSettled` event.
+	pub(crate) fn do_bid(
+		bidder: T::AccountId,
+		collection: T::CollectionId,
+		item: T::ItemId,
+		amount: BalanceOf<T, I>,
+	) -> DispatchResult {
+		ensure!(
+			Self::is_pallet_feature_enabled(PalletFeature::Auctions),
+			Error::<T, I>::MethodDisabled
+		);
+
+		let mut auction =
+			ItemAuctionOf::<T, I>::get(&collection, &item).ok_or(Error::<T, I>::UnknownAuction)?;
+		ensure!(auction.seller != bidder, Error::<T, I>::NoPermission);
+
+		let now = T::BlockNumberProvider::current_block_number();
+		ensure!(now < auction.end, Error::<T, I>::DeadlineExpired);
+
+		match auction.kind {
+			AuctionKind::English { min_bid } => {
+				let min_amount = match auction.highest_bid {
+					Some((_, highest)) => highest.saturating_add(
+						T::MinBidIncrement::get().mul_ceil(highest).max(1u32.into()),
+					),
+					None => min_bid,
+				};
+				ensure!(amount >= min_amount, Error::<T, I>::BidTooLow);
+
+				if let Some((previous_bidder, previous_amount)) = auction.highest_bid.take() {
+					T::Currency::unreserve(&previous_bidder, previous_amount);
+				}
+				T::Currency::reserve(&bidder, amount)?;
+				auction.highest_bid = Some((bidder.clone(), amount));
+
+				let extension = T::AuctionExtension::get();
+				if auction.end.saturating_sub(now) < extension {
+					auction.end = now.saturating_add(extension);
+				}
+				let end = auction.end;
+				ItemAuctionOf::<T, I>::insert(&collection, &item, auction);
+
+				Self::deposit_event(Event::BidPlaced { collection, item, bidder, amount, end });
+			},
+			AuctionKind::Dutch { start_price, end_price } => {
+				let price = Self::dutch_auction_price(
+					start_price,
+					end_price,
+					auction.start,
+					auction.end,
+					now,
+				);
+				ensure!(amount >= price, Error::<T, I>::BidTooLow);
+
+				ItemAuctionOf::<T, I>::remove(&collection, &item);
+				Self::unlock_auctioned_item(&collection, &item)?;
+				Self::do_pay_with_royalty(
+					collection,
+					item,
+					auction.royalty,
+					&bidder,
+					&auction.seller,
+					price,
+				)?;
+				Self::do_transfer(collection, item, bidder.clone(), |_, _| Ok(()))?;
+
+				Self::deposit_event(Event::AuctionSettled {
+					collection,
+					item,
+					seller: auction.seller,
+					buyer: bidder,
+					price,
+				});
+			},
+		}
+		Ok(())
+	}
+
+	/// Settles an auction that has ended.
+	///
+	/// - `collection`: The identifier of the collection containing the item.
+	/// - `item`: The identifier of the auctioned item.
+	///
+	/// Unlocks the item and, if there was a bid, sells it to the highest bidder, paying the seller
+	/// out of the reserved bid. Emits the `AuctionSettled` event. If there was no bid or the sale
+	/// fails, e.g. because the item was made non-transferable in the meantime, the bid is returned
+	/// and the `AuctionCancelled` event is emitted instead.
+	///
+	/// Auctions are not settled automatically: this has to be called once the auction has
+	/// ended, until then the item stays locked and the bid reserved.
+	pub(crate) fn do_settle_auction(
+		collection: T::CollectionId,
+		item: T::ItemId,
+	) -> DispatchResult {
+		let auction =
+			ItemAuctionOf::<T, I>::get(&collection, &item).ok_or(Error::<T, I>::UnknownAuction)?;
+
+		let now = T::BlockNumberProvider::current_block_number();
+		ensure!(now >= auction.end, Error::<T, I>::AuctionNotEnded);
+
+		ItemAuctionOf::<T, I>::remove(&collection, &item);
+		Self::unlock_auctioned_item(&collection, &item)?;
+
+		let Some((buyer, price)) = auction.highest_bid else {
+			Self::deposit_event(Event::AuctionCancelled { collection, item });
+			return Ok(())
+		};
+
+		// Paid out of the reserved bid, so the bidder can't make the sale fail.
+		let sold = with_storage_layer(|| -> DispatchResult {
+			Self::do_pay_reserved_with_royalty(
+				collection,
+				item,
+				auction.royalty,
+				&buyer,
+				&auction.seller,
+				price,
+			)?;
+			Self::do_transfer(collection, item, buyer.clone(), |_, _| Ok(()))
+		});
+
+		match sold {
+			Ok(()) => Self::deposit_event(Event::AuctionSettled {
+				collection,
+				item,
+				seller: auction.seller,
+				buyer,
+				price,
+			}),
+			Err(error) => {
+				T::Currency::unreserve(&buyer, price);
+				log::debug!(
+					target: LOG_TARGET,
+					"Failed to settle the auction of item {:?} of collection {:?}: {:?}",
+					item,
+					collection,
+					error,
+				);
+				Self::deposit_event(Event::AuctionCancelled { collection, item });
+			},
+		}
+		Ok(())
+	}
+
+	/// Cancels an auction that hasn't received a bid.
+	///
+	/// - `caller`: The account cancelling the auction, which must be the seller.
+	/// - `collection`: The identifier of the collection containing the item.
+	/// - `item`: The identifier of the auctioned item.
+	///
+	/// Unlocks the item and emits the `AuctionCancelled` event.
+	pub(crate) fn do_cancel_auction(
+		caller: T::AccountId,
+		collection: T::CollectionId,
+		item: T::ItemId,
+	) -> DispatchResult {
+		let auction =
+			ItemAuctionOf::<T, I>::get(&collection, &item).ok_or(Error::<T, I>::UnknownAuction)?;
+		ensure!(auction.seller == caller, Error::<T, I>::NoPermission);
+		ensure!(auction.highest_bid.is_none(), Error::<T, I>::AuctionHasBids);
+
+		ItemAuctionOf::<T, I>::remove(&collection, &item);
+		Self::unlock_auctioned_item(&collection, &item)?;
+
+		Self::deposit_event(Event::AuctionCancelled { collection, item });
+		Ok(())
+	}
+
+	/// Returns the price of a Dutch auction at block `now`, decreasing linearly from
+	/// `start_price` at `start` to `end_price` at `end`.
+	pub fn dutch_auction_price(
+		start_price: BalanceOf<T, I>,
+		end_price: BalanceOf<T, I>,
+		start: BlockNumberFor<T, I>,
+		end: BlockNumberFor<T, I>,
+		now: BlockNumberFor<T, I>,
+	) -> BalanceOf<T, I> {
+		if now >= end {
+			return end_price
+		}
+		let elapsed = Perbill::from_rational(now.saturating_sub(start), end.saturating_sub(start));
+		start_price.saturating_sub(elapsed.mul_floor(start_price.saturating_sub(end_price)))
+	}
+
+	/// Lifts the lock an auction put on an item, unless it was already lifted by `ForceOrigin`.
+	fn unlock_auctioned_item(collection: &T::CollectionId, item: &T::ItemId) -> DispatchResult {
+		if Self::has_system_attribute(collection, item, PalletAttributes::TransferDisabled)? {
+			<Self as Transfer<T::AccountId>>::enable_transfer(collection, item)?;
+		}
+		Ok(())
+	}
+}
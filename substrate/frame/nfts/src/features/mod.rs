@@ -18,11 +18,13 @@
 pub mod approvals;
 pub mod atomic_swap;
 pub mod attributes;
+pub mod auctions;
 pub mod buy_sell;
 pub mod create_delete_collection;
 pub mod create_delete_item;
 pub mod lock;
 pub mod metadata;
 pub mod roles;
+pub mod royalties;
 pub mod settings;
 pub mod transfer;
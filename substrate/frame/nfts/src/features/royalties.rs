@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains helper methods to configure and pay the creator royalties of collections
+//! in the NFTs pallet.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency},
+};
+use sp_runtime::TokenError;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Sets or removes the royalty of a collection.
+	///
+	/// - `maybe_check_owner`: An optional account ID used to check the ownership of the collection.
+	/// - `collection`: The identifier of the collection.
+	/// - `royalty`: The royalty to set, or `None` to remove it.
+	///
+	/// The royalty percentage may not exceed [`Config::MaxRoyalty`]. Emits
+	/// `CollectionRoyaltySet` event when successful.
+	pub(crate) fn do_set_collection_royalty(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: T::CollectionId,
+		royalty: Option<CollectionRoyaltyFor<T>>,
+	) -> DispatchResult {
+		let details =
+			Collection::<T, I>::get(&collection).ok_or(Error::<T, I>::UnknownCollection)?;
+		if let Some(check_owner) = &maybe_check_owner {
+			ensure!(check_owner == &details.owner, Error::<T, I>::NoPermission);
+		}
+
+		match royalty {
+			Some(ref royalty) => {
+				ensure!(royalty.percentage <= T::MaxRoyalty::get(), Error::<T, I>::RoyaltyTooHigh);
+				CollectionRoyaltyOf::<T, I>::insert(&collection, royalty);
+			},
+			None => CollectionRoyaltyOf::<T, I>::remove(&collection),
+		}
+
+		Self::deposit_event(Event::CollectionRoyaltySet { collection, royalty });
+		Ok(())
+	}
+
+	/// Records the current royalty of a collection as the one the sale of an item pays through
+	/// the listing its owner is creating.
+	///
+	/// - `collection`: The identifier of the collection of the item being listed.
+	/// - `item`: The identifier of the item being listed.
+	///
+	/// Changing the royalty afterwards doesn't affect the listing, so the owner of the collection
+	/// can't raise it on open offers. The snapshot is taken again whenever the price is set.
+	pub(crate) fn snapshot_listing_royalty(collection: &T::CollectionId, item: &T::ItemId) {
+		match CollectionRoyaltyOf::<T, I>::get(collection) {
+			Some(royalty) => ListingRoyaltyOf::<T, I>::insert(collection, item, royalty),
+			None => ListingRoyaltyOf::<T, I>::remove(collection, item),
+		}
+	}
+
+	/// Records the current royalty of `paid_collection` as the one the price of the swap of the
+	/// offered item pays.
+	///
+	/// - `offered_collection`: The identifier of the collection of the item offered in the swap.
+	/// - `offered_item`: The identifier of the item offered in the swap.
+	/// - `paid_collection`: The identifier of the collection of the item the price pays for, if the
+	///   swap has a price.
+	///
+	/// Changing the royalty afterwards doesn't affect the swap, independently of the listing of
+	/// the offered item.
+	pub(crate) fn snapshot_swap_royalty(
+		offered_collection: &T::CollectionId,
+		offered_item: &T::ItemId,
+		paid_collection: Option<&T::CollectionId>,
+	) {
+		match paid_collection.and_then(CollectionRoyaltyOf::<T, I>::get) {
+			Some(royalty) =>
+				SwapRoyaltyOf::<T, I>::insert(offered_collection, offered_item, royalty),
+			None => SwapRoyaltyOf::<T, I>::remove(offered_collection, offered_item),
+		}
+	}
+
+	/// Pays `price` from `buyer` to `seller` for an item, routing `royalty`, if any, to its
+	/// recipient.
+	///
+	/// - `collection`: The identifier of the collection of the item being paid for.
+	/// - `item`: The identifier of the item being paid for.
+	/// - `royalty`: The royalty the item was offered under.
+	/// - `buyer`: The account paying for the item.
+	/// - `seller`: The account selling the item.
+	/// - `price`: The full price of the item, royalty included.
+	///
+	/// A royalty below the existential deposit isn't paid to a recipient without an account,
+	/// as the transfer would fail and revert the sale. It's left to the seller instead.
+	///
+	/// Emits `RoyaltyPaid` event if a royalty was paid.
+	pub(crate) fn do_pay_with_royalty(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		royalty: Option<CollectionRoyaltyFor<T>>,
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		price: BalanceOf<T, I>,
+	) -> DispatchResult {
+		Self::pay_with_royalty(collection, item, royalty, seller, price, |to, amount| {
+			T::Currency::transfer(buyer, to, amount, ExistenceRequirement::KeepAlive)
+		})
+	}
+
+	/// Pays `price` for an item out of the balance `buyer` reserved for it, routing `royalty`, if
+	/// any, to its recipient.
+	///
+	/// Same as [`Self::do_pay_with_royalty`], except that the payment can't be made to fail by
+	/// the buyer, e.g. by spending their free balance.
+	pub(crate) fn do_pay_reserved_with_royalty(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		royalty: Option<CollectionRoyaltyFor<T>>,
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		price: BalanceOf<T, I>,
+	) -> DispatchResult {
+		Self::pay_with_royalty(collection, item, royalty, seller, price, |to, amount| {
+			let missing = T::Currency::repatriate_reserved(buyer, to, amount, BalanceStatus::Free)?;
+			ensure!(missing.is_zero(), TokenError::FundsUnavailable);
+			Ok(())
+		})
+	}
+
+	/// Splits `price` between the recipient of `royalty` and `seller`, paying each share with
+	/// `pay`.
+	fn pay_with_royalty(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		royalty: Option<CollectionRoyaltyFor<T>>,
+		seller: &T::AccountId,
+		price: BalanceOf<T, I>,
+		mut pay: impl FnMut(&T::AccountId, BalanceOf<T, I>) -> DispatchResult,
+	) -> DispatchResult {
+		let mut proceeds = price;
+		if let Some(royalty) = royalty {
+			let amount = royalty.percentage.mul_floor(price);
+			let can_receive = amount >= T::Currency::minimum_balance() ||
+				!T::Currency::total_balance(&royalty.recipient).is_zero();
+			if !amount.is_zero() && royalty.recipient != *seller && can_receive {
+				pay(&royalty.recipient, amount)?;
+				proceeds.saturating_reduce(amount);
+				Self::deposit_event(Event::RoyaltyPaid {
+					collection,
+					item,
+					recipient: royalty.recipient,
+					amount,
+				});
+			}
+		}
+		pay(seller, proceeds)
+	}
+}
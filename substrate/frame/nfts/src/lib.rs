@@ -59,7 +59,7 @@ use frame_support::traits::{
 use frame_system::Config as SystemConfig;
 use sp_runtime::{
 	traits::{BlockNumberProvider, IdentifyAccount, Saturating, StaticLookup, Verify, Zero},
-	RuntimeDebug,
+	Permill, RuntimeDebug,
 };
 
 pub use pallet::*;
@@ -217,6 +217,19 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxAttributesPerCall: Get<u32>;
 
+		/// The max royalty a collection could charge on the sales of its items.
+		#[pallet::constant]
+		type MaxRoyalty: Get<Permill>;
+
+		/// The min share of the highest bid a new bid must exceed it by in an English auction.
+		#[pallet::constant]
+		type MinBidIncrement: Get<Permill>;
+
+		/// The number of blocks an English auction is kept open for after a bid is placed close
+		/// to its end, to prevent last moment bids from going unanswered.
+		#[pallet::constant]
+		type AuctionExtension: Get<BlockNumberFor<Self, I>>;
+
 		/// Disables some of pallet's features.
 		#[pallet::constant]
 		type Features: Get<PalletFeatures>;
@@ -414,6 +427,51 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The royalty of a collection.
+	#[pallet::storage]
+	pub type CollectionRoyaltyOf<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, CollectionRoyaltyFor<T>, OptionQuery>;
+
+	/// The royalty of its collection at the time an item was listed, which its sale through the
+	/// listing pays.
+	#[pallet::storage]
+	pub type ListingRoyaltyOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		CollectionRoyaltyFor<T>,
+		OptionQuery,
+	>;
+
+	/// The royalty the price of the swap an item is offered in pays, keyed by the offered item.
+	///
+	/// It's the royalty at the time the swap was created of the collection of the item the price
+	/// pays for.
+	#[pallet::storage]
+	pub type SwapRoyaltyOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		CollectionRoyaltyFor<T>,
+		OptionQuery,
+	>;
+
+	/// The ongoing auction of an item.
+	#[pallet::storage]
+	pub type ItemAuctionOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		AuctionFor<T, I>,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -594,6 +652,45 @@ pub mod pallet {
 			attribute: PalletAttributes<T::CollectionId>,
 			value: BoundedVec<u8, T::ValueLimit>,
 		},
+		/// The royalty of a `collection` was set or removed.
+		CollectionRoyaltySet {
+			collection: T::CollectionId,
+			royalty: Option<CollectionRoyaltyFor<T>>,
+		},
+		/// A royalty was paid on the sale of an `item`.
+		RoyaltyPaid {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			recipient: T::AccountId,
+			amount: BalanceOf<T, I>,
+		},
+		/// An auction was created for an `item`.
+		AuctionCreated {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			seller: T::AccountId,
+			kind: AuctionKind<BalanceOf<T, I>>,
+			start: BlockNumberFor<T, I>,
+			end: BlockNumberFor<T, I>,
+		},
+		/// A bid was placed in the English auction of an `item`.
+		BidPlaced {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			bidder: T::AccountId,
+			amount: BalanceOf<T, I>,
+			end: BlockNumberFor<T, I>,
+		},
+		/// The auction of an `item` was settled and the item sold.
+		AuctionSettled {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			seller: T::AccountId,
+			buyer: T::AccountId,
+			price: BalanceOf<T, I>,
+		},
+		/// The auction of an `item` was closed without a sale.
+		AuctionCancelled { collection: T::CollectionId, item: T::ItemId },
 	}
 
 	#[pallet::error]
@@ -688,6 +785,16 @@ pub mod pallet {
 		CollectionNotEmpty,
 		/// The witness data should be provided.
 		WitnessRequired,
+		/// The royalty exceeds `MaxRoyalty`.
+		RoyaltyTooHigh,
+		/// The item has no ongoing auction.
+		UnknownAuction,
+		/// The auction hasn't ended yet.
+		AuctionNotEnded,
+		/// The auction can't be cancelled once a bid was placed.
+		AuctionHasBids,
+		/// The prices of a Dutch auction must not increase over time.
+		IncorrectAuctionPrices,
 	}
 
 	#[pallet::call]
@@ -1931,6 +2038,134 @@ pub mod pallet {
 			Self::validate_signature(&Encode::encode(&data), &signature, &signer)?;
 			Self::do_set_attributes_pre_signed(origin, data, signer)
 		}
+
+		/// Set (or remove) the royalty paid to the creators of a collection on the sales of its
+		/// items through `buy_item`, `claim_swap` and auctions.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// - `collection`: The identifier of the collection.
+		/// - `royalty`: The recipient and share of the sale price of the royalty. Pass `None` to
+		///   remove it. The share may not exceed `MaxRoyalty`.
+		///
+		/// Items already listed, offered in a swap or auctioned keep the royalty they were
+		/// offered under.
+		///
+		/// Emits `CollectionRoyaltySet` event when successful.
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::set_collection_royalty())]
+		pub fn set_collection_royalty(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			royalty: Option<CollectionRoyaltyFor<T>>,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			Self::do_set_collection_royalty(maybe_check_owner, collection, royalty)
+		}
+
+		/// Put an item up for auction.
+		///
+		/// The item can't be transferred until the auction is settled or cancelled. Auctions are
+		/// not settled automatically, see [`Pallet::settle_auction`].
+		///
+		/// Origin must be Signed and must be the owner of the `item`.
+		///
+		/// - `collection`: The collection of the item.
+		/// - `item`: The item to auction.
+		/// - `kind`: Whether the auction is an English or a Dutch one, and its prices.
+		/// - `duration`: The number of blocks the auction lasts for, which should be less than or
+		///   equal to `MaxDeadlineDuration`.
+		///
+		/// Emits `AuctionCreated` event when successful.
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+			kind: AuctionKind<BalanceOf<T, I>>,
+			duration: BlockNumberFor<T, I>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::do_create_auction(origin, collection, item, kind, duration)
+		}
+
+		/// Bid in the auction of an item.
+		///
+		/// In an English auction, `amount` is reserved until the bid is outbid or the auction is
+		/// settled, and must exceed the highest bid by at least `MinBidIncrement`. A bid placed
+		/// within `AuctionExtension` blocks of the end pushes the end back to `AuctionExtension`
+		/// blocks after it.
+		///
+		/// In a Dutch auction, the item is sold right away at the current price, provided
+		/// `amount` is at least that.
+		///
+		/// Origin must be Signed and must not be the seller.
+		///
+		/// - `collection`: The collection of the item.
+		/// - `item`: The item being auctioned.
+		/// - `amount`: The bid, or the max price the sender is willing to pay in a Dutch auction.
+		///
+		/// Emits `BidPlaced` in an English auction and `AuctionSettled` in a Dutch one.
+		#[pallet::call_index(41)]
+		#[pallet::weight(T::WeightInfo::bid())]
+		pub fn bid(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+			amount: BalanceOf<T, I>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::do_bid(origin, collection, item, amount)
+		}
+
+		/// Settle an auction that has ended.
+		///
+		/// The item goes to the highest bidder against their reserved bid, royalty included. If
+		/// there was no bid, or the item can't be transferred anymore, the auction is closed
+		/// without a sale and the bid is returned.
+		///
+		/// Auctions are not settled automatically: until this is called, by anyone, the item
+		/// stays locked and the bid reserved.
+		///
+		/// Origin must be Signed.
+		///
+		/// - `collection`: The collection of the item.
+		/// - `item`: The item being auctioned.
+		///
+		/// Emits `AuctionSettled` or `AuctionCancelled` on success.
+		#[pallet::call_index(42)]
+		#[pallet::weight(T::WeightInfo::settle_auction())]
+		pub fn settle_auction(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_settle_auction(collection, item)
+		}
+
+		/// Cancel the auction of an item that hasn't received a bid.
+		///
+		/// Origin must be Signed and must be the seller.
+		///
+		/// - `collection`: The collection of the item.
+		/// - `item`: The item being auctioned.
+		///
+		/// Emits `AuctionCancelled` on success.
+		#[pallet::call_index(43)]
+		#[pallet::weight(T::WeightInfo::cancel_auction())]
+		pub fn cancel_auction(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::do_cancel_auction(origin, collection, item)
+		}
 	}
 }
 
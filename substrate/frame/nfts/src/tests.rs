@@ -3875,3 +3875,856 @@ fn clear_collection_metadata_works() {
 		assert_eq!(Balances::reserved_balance(&account(1)), 10);
 	});
 }
+
+#[test]
+fn collection_royalties_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let recipient = account(4);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+		Balances::make_free_balance_be(&recipient, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_1,
+			user_2.clone(),
+			None
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_2,
+			user_2.clone(),
+			None
+		));
+
+		// only the collection's owner can set the royalty
+		let royalty = CollectionRoyalty {
+			recipient: recipient.clone(),
+			percentage: Permill::from_percent(10),
+		};
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				Some(royalty.clone())
+			),
+			Error::<Test>::NoPermission
+		);
+
+		// the royalty can't exceed the max allowed one
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				Some(CollectionRoyalty {
+					recipient: recipient.clone(),
+					percentage: Permill::from_percent(60)
+				})
+			),
+			Error::<Test>::RoyaltyTooHigh
+		);
+
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(royalty.clone())
+		));
+		assert_eq!(CollectionRoyaltyOf::<Test>::get(collection_id), Some(royalty.clone()));
+		assert!(events().contains(&Event::<Test>::CollectionRoyaltySet {
+			collection: collection_id,
+			royalty: Some(royalty),
+		}));
+
+		// the royalty is taken from a direct sale
+		let price = 100;
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			Some(price),
+			None
+		));
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_1,
+			price
+		));
+		assert_eq!(Balances::total_balance(&user_3), initial_balance - price);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + 90);
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 10);
+		assert!(events().contains(&Event::<Test>::RoyaltyPaid {
+			collection: collection_id,
+			item: item_1,
+			recipient: recipient.clone(),
+			amount: 10,
+		}));
+
+		// and from the price paid within a swap
+		let price = 50;
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Send };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_1,
+			collection_id,
+			Some(item_2),
+			Some(price_with_direction.clone()),
+			2,
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			item_1,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&user_3), initial_balance - 100 - price);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + 90 + 45);
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 10 + 5);
+
+		// the force origin can remove the royalty
+		assert_ok!(Nfts::set_collection_royalty(RuntimeOrigin::root(), collection_id, None));
+		assert_eq!(CollectionRoyaltyOf::<Test>::get(collection_id), None);
+	});
+}
+
+#[test]
+fn royalty_changes_do_not_affect_open_offers() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let user_1 = account(1);
+		let seller = account(2);
+		let buyer = account(3);
+		let recipient = account(4);
+		let collection_id = 0;
+		let (item_1, item_2, item_3, item_4) = (1, 2, 3, 4);
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&seller, initial_balance);
+		Balances::make_free_balance_be(&buyer, initial_balance);
+		Balances::make_free_balance_be(&recipient, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		for (item, owner) in
+			[(item_1, &seller), (item_2, &seller), (item_3, &seller), (item_4, &buyer)]
+		{
+			assert_ok!(Nfts::mint(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item,
+				owner.clone(),
+				None
+			));
+		}
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(CollectionRoyalty {
+				recipient: recipient.clone(),
+				percentage: Permill::from_percent(10)
+			})
+		));
+
+		// the seller lists an item, offers another one in a swap and auctions a third one
+		let price = 100;
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_id,
+			item_1,
+			Some(price),
+			None
+		));
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Receive };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			Some(item_4),
+			Some(price_with_direction.clone()),
+			2,
+		));
+		assert_ok!(Nfts::create_auction(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_id,
+			item_3,
+			AuctionKind::Dutch { start_price: price, end_price: price },
+			10
+		));
+
+		// the collection's owner raises the royalty in front of the sales
+		let raised_royalty = CollectionRoyalty {
+			recipient: recipient.clone(),
+			percentage: Permill::from_percent(50),
+		};
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(raised_royalty)
+		));
+
+		// but the items are sold at the royalty they were offered under
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(buyer.clone()),
+			collection_id,
+			item_1,
+			price
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(buyer.clone()),
+			collection_id,
+			item_4,
+			collection_id,
+			item_2,
+			Some(price_with_direction),
+		));
+		assert_ok!(Nfts::bid(RuntimeOrigin::signed(buyer.clone()), collection_id, item_3, price));
+		assert_eq!(Balances::total_balance(&buyer), initial_balance - 3 * price);
+		assert_eq!(Balances::total_balance(&seller), initial_balance + 3 * 90);
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 3 * 10);
+		assert_eq!(ListingRoyaltyOf::<Test>::get(collection_id, item_1), None);
+		assert_eq!(SwapRoyaltyOf::<Test>::get(collection_id, item_2), None);
+
+		// while new offers pay the raised royalty
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(buyer.clone()),
+			collection_id,
+			item_1,
+			Some(price),
+			None
+		));
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_id,
+			item_1,
+			price
+		));
+		assert_eq!(Balances::total_balance(&buyer), initial_balance - 3 * price + 50);
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 3 * 10 + 50);
+	});
+}
+
+#[test]
+fn royalty_snapshots_are_kept_per_offer() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let seller = account(2);
+		let buyer = account(3);
+		let recipient = account(4);
+		let (collection_1, collection_2) = (0, 1);
+		let (item_1, item_2) = (1, 2);
+		let initial_balance = 1000;
+		let price = 100;
+
+		Balances::make_free_balance_be(&seller, initial_balance);
+		Balances::make_free_balance_be(&buyer, initial_balance);
+		Balances::make_free_balance_be(&recipient, initial_balance);
+
+		for (collection, item, owner) in
+			[(collection_1, item_1, &seller), (collection_2, item_2, &buyer)]
+		{
+			assert_ok!(Nfts::force_create(
+				RuntimeOrigin::root(),
+				user_1.clone(),
+				default_collection_config()
+			));
+			assert_ok!(Nfts::mint(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection,
+				item,
+				owner.clone(),
+				None
+			));
+		}
+		let set_royalty = |collection, percent| {
+			assert_ok!(Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection,
+				Some(CollectionRoyalty {
+					recipient: recipient.clone(),
+					percentage: Permill::from_percent(percent)
+				})
+			));
+		};
+		set_royalty(collection_1, 10);
+		set_royalty(collection_2, 10);
+
+		// the item is listed at 10 %, and offered in a swap at a raised royalty afterwards
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_1,
+			item_1,
+			Some(price),
+			None
+		));
+		set_royalty(collection_1, 50);
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_1,
+			item_1,
+			collection_2,
+			Some(item_2),
+			Some(PriceWithDirection { amount: price, direction: PriceDirection::Receive }),
+			2,
+		));
+
+		// which doesn't change the royalty the listing pays
+		assert_eq!(
+			ListingRoyaltyOf::<Test>::get(collection_1, item_1).map(|r| r.percentage),
+			Some(Permill::from_percent(10))
+		);
+		assert_eq!(
+			SwapRoyaltyOf::<Test>::get(collection_1, item_1).map(|r| r.percentage),
+			Some(Permill::from_percent(50))
+		);
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(buyer.clone()),
+			collection_1,
+			item_1,
+			price
+		));
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 10);
+		assert_eq!(SwapRoyaltyOf::<Test>::get(collection_1, item_1), None);
+
+		// the price sent by the one claiming a swap pays the royalty of the sent item's
+		// collection at the time the swap was created
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Send };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(buyer.clone()),
+			collection_1,
+			item_1,
+			collection_2,
+			Some(item_2),
+			Some(price_with_direction.clone()),
+			2,
+		));
+		set_royalty(collection_2, 50);
+		assert_ok!(Nfts::transfer(
+			RuntimeOrigin::signed(buyer.clone()),
+			collection_2,
+			item_2,
+			seller.clone()
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_2,
+			item_2,
+			collection_1,
+			item_1,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 10 + 10);
+
+		// cancelling a swap removes its snapshot
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_1,
+			item_1,
+			collection_2,
+			None,
+			Some(PriceWithDirection { amount: price, direction: PriceDirection::Receive }),
+			2,
+		));
+		assert!(SwapRoyaltyOf::<Test>::contains_key(collection_1, item_1));
+		assert_ok!(Nfts::cancel_swap(RuntimeOrigin::signed(seller.clone()), collection_1, item_1));
+		assert!(!SwapRoyaltyOf::<Test>::contains_key(collection_1, item_1));
+	});
+}
+
+#[test]
+fn royalty_below_existential_deposit_is_left_to_seller() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let seller = account(2);
+		let buyer = account(3);
+		let recipient = account(4);
+		let collection_id = 0;
+		let (item_1, item_2) = (1, 2);
+		let initial_balance = 1000;
+		let price = 100;
+
+		ExistentialDeposit::set(20);
+		Balances::make_free_balance_be(&seller, initial_balance);
+		Balances::make_free_balance_be(&buyer, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		for item in [item_1, item_2] {
+			assert_ok!(Nfts::mint(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item,
+				seller.clone(),
+				None
+			));
+		}
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(CollectionRoyalty {
+				recipient: recipient.clone(),
+				percentage: Permill::from_percent(10)
+			})
+		));
+		for item in [item_1, item_2] {
+			assert_ok!(Nfts::set_price(
+				RuntimeOrigin::signed(seller.clone()),
+				collection_id,
+				item,
+				Some(price),
+				None
+			));
+		}
+
+		// the royalty can't create the account of its recipient, so the sale doesn't pay it
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(buyer.clone()),
+			collection_id,
+			item_1,
+			price
+		));
+		assert_eq!(Balances::total_balance(&recipient), 0);
+		assert_eq!(Balances::total_balance(&seller), initial_balance + price);
+		assert!(!events().iter().any(|e| matches!(e, Event::<Test>::RoyaltyPaid { .. })));
+
+		// but it's paid once the recipient has an account
+		Balances::make_free_balance_be(&recipient, initial_balance);
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(buyer.clone()),
+			collection_id,
+			item_2,
+			price
+		));
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 10);
+		assert_eq!(Balances::total_balance(&seller), initial_balance + price + 90);
+	});
+}
+
+#[test]
+fn english_auction_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let user_1 = account(1);
+		let seller = account(2);
+		let bidder_1 = account(3);
+		let bidder_2 = account(4);
+		let recipient = account(5);
+		let collection_id = 0;
+		let item = 1;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&seller, initial_balance);
+		Balances::make_free_balance_be(&bidder_1, initial_balance);
+		Balances::make_free_balance_be(&bidder_2, initial_balance);
+		Balances::make_free_balance_be(&recipient, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item,
+			seller.clone(),
+			None
+		));
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(CollectionRoyalty {
+				recipient: recipient.clone(),
+				percentage: Permill::from_percent(10)
+			})
+		));
+
+		let kind = AuctionKind::English { min_bid: 100 };
+		assert_noop!(
+			Nfts::create_auction(
+				RuntimeOrigin::signed(bidder_1.clone()),
+				collection_id,
+				item,
+				kind,
+				20
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nfts::create_auction(
+				RuntimeOrigin::signed(seller.clone()),
+				collection_id,
+				item,
+				kind,
+				0
+			),
+			Error::<Test>::WrongDuration
+		);
+		assert_ok!(Nfts::create_auction(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_id,
+			item,
+			kind,
+			20
+		));
+		assert!(events().contains(&Event::<Test>::AuctionCreated {
+			collection: collection_id,
+			item,
+			seller: seller.clone(),
+			kind,
+			start: 1,
+			end: 21,
+		}));
+
+		// the item is locked while being auctioned
+		assert_noop!(
+			Nfts::transfer(RuntimeOrigin::signed(seller.clone()), collection_id, item, account(6)),
+			Error::<Test>::ItemLocked
+		);
+		assert_noop!(
+			Nfts::create_auction(
+				RuntimeOrigin::signed(seller.clone()),
+				collection_id,
+				item,
+				kind,
+				20
+			),
+			Error::<Test>::ItemLocked
+		);
+
+		assert_noop!(
+			Nfts::bid(RuntimeOrigin::signed(seller.clone()), collection_id, item, 100),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nfts::bid(RuntimeOrigin::signed(bidder_1.clone()), collection_id, item, 99),
+			Error::<Test>::BidTooLow
+		);
+		assert_ok!(Nfts::bid(RuntimeOrigin::signed(bidder_1.clone()), collection_id, item, 100));
+		assert_eq!(Balances::reserved_balance(&bidder_1), 100);
+
+		// the next bid must be higher by at least `MinBidIncrement`
+		assert_noop!(
+			Nfts::bid(RuntimeOrigin::signed(bidder_2.clone()), collection_id, item, 109),
+			Error::<Test>::BidTooLow
+		);
+		assert_ok!(Nfts::bid(RuntimeOrigin::signed(bidder_2.clone()), collection_id, item, 110));
+		assert_eq!(Balances::reserved_balance(&bidder_1), 0);
+		assert_eq!(Balances::reserved_balance(&bidder_2), 110);
+
+		assert_noop!(
+			Nfts::settle_auction(RuntimeOrigin::signed(bidder_2.clone()), collection_id, item),
+			Error::<Test>::AuctionNotEnded
+		);
+		assert_noop!(
+			Nfts::cancel_auction(RuntimeOrigin::signed(seller.clone()), collection_id, item),
+			Error::<Test>::AuctionHasBids
+		);
+
+		// a bid close to the end extends the auction
+		System::set_block_number(18);
+		assert_ok!(Nfts::bid(RuntimeOrigin::signed(bidder_1.clone()), collection_id, item, 121));
+		assert!(events().contains(&Event::<Test>::BidPlaced {
+			collection: collection_id,
+			item,
+			bidder: bidder_1.clone(),
+			amount: 121,
+			end: 23,
+		}));
+		assert_eq!(Balances::reserved_balance(&bidder_2), 0);
+
+		System::set_block_number(22);
+		assert_noop!(
+			Nfts::settle_auction(RuntimeOrigin::signed(bidder_2.clone()), collection_id, item),
+			Error::<Test>::AuctionNotEnded
+		);
+
+		System::set_block_number(23);
+		assert_noop!(
+			Nfts::bid(RuntimeOrigin::signed(bidder_2.clone()), collection_id, item, 200),
+			Error::<Test>::DeadlineExpired
+		);
+
+		// anyone can settle the auction once it has ended
+		assert_ok!(Nfts::settle_auction(
+			RuntimeOrigin::signed(bidder_2.clone()),
+			collection_id,
+			item
+		));
+		assert!(events().contains(&Event::<Test>::AuctionSettled {
+			collection: collection_id,
+			item,
+			seller: seller.clone(),
+			buyer: bidder_1.clone(),
+			price: 121,
+		}));
+		assert_eq!(ItemAuctionOf::<Test>::get(collection_id, item), None);
+		assert_eq!(Item::<Test>::get(collection_id, item).unwrap().owner, bidder_1);
+		assert_eq!(Balances::reserved_balance(&bidder_1), 0);
+		assert_eq!(Balances::total_balance(&bidder_1), initial_balance - 121);
+		assert_eq!(Balances::total_balance(&seller), initial_balance + 109);
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 12);
+
+		// the item is transferable again
+		assert_ok!(Nfts::transfer(
+			RuntimeOrigin::signed(bidder_1.clone()),
+			collection_id,
+			item,
+			account(6)
+		));
+		assert_noop!(
+			Nfts::settle_auction(RuntimeOrigin::signed(bidder_2), collection_id, item),
+			Error::<Test>::UnknownAuction
+		);
+	});
+}
+
+#[test]
+fn dutch_auction_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let user_1 = account(1);
+		let seller = account(2);
+		let buyer = account(3);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&seller, initial_balance);
+		Balances::make_free_balance_be(&buyer, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		for item in [item_1, item_2] {
+			assert_ok!(Nfts::mint(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item,
+				seller.clone(),
+				None
+			));
+		}
+
+		assert_noop!(
+			Nfts::create_auction(
+				RuntimeOrigin::signed(seller.clone()),
+				collection_id,
+				item_1,
+				AuctionKind::Dutch { start_price: 50, end_price: 100 },
+				10
+			),
+			Error::<Test>::IncorrectAuctionPrices
+		);
+
+		let kind = AuctionKind::Dutch { start_price: 100, end_price: 50 };
+		for item in [item_1, item_2] {
+			assert_ok!(Nfts::create_auction(
+				RuntimeOrigin::signed(seller.clone()),
+				collection_id,
+				item,
+				kind,
+				10
+			));
+		}
+
+		// the price decreases linearly over the auction's duration
+		assert_eq!(Nfts::dutch_auction_price(100, 50, 1, 11, 1), 100);
+		assert_eq!(Nfts::dutch_auction_price(100, 50, 1, 11, 6), 75);
+		assert_eq!(Nfts::dutch_auction_price(100, 50, 1, 11, 11), 50);
+
+		// the first bid at or above the current price buys the item
+		System::set_block_number(6);
+		assert_noop!(
+			Nfts::bid(RuntimeOrigin::signed(buyer.clone()), collection_id, item_1, 74),
+			Error::<Test>::BidTooLow
+		);
+		assert_ok!(Nfts::bid(RuntimeOrigin::signed(buyer.clone()), collection_id, item_1, 80));
+		assert!(events().contains(&Event::<Test>::AuctionSettled {
+			collection: collection_id,
+			item: item_1,
+			seller: seller.clone(),
+			buyer: buyer.clone(),
+			price: 75,
+		}));
+		assert_eq!(Item::<Test>::get(collection_id, item_1).unwrap().owner, buyer);
+		assert_eq!(Balances::total_balance(&buyer), initial_balance - 75);
+		assert_eq!(Balances::total_balance(&seller), initial_balance + 75);
+		assert_eq!(ItemAuctionOf::<Test>::get(collection_id, item_1), None);
+
+		// an auction without bids can be cancelled by the seller only
+		assert_noop!(
+			Nfts::cancel_auction(RuntimeOrigin::signed(buyer.clone()), collection_id, item_2),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Nfts::cancel_auction(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_id,
+			item_2
+		));
+		assert!(events().contains(&Event::<Test>::AuctionCancelled {
+			collection: collection_id,
+			item: item_2,
+		}));
+		assert_ok!(Nfts::transfer(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_id,
+			item_2,
+			buyer.clone()
+		));
+	});
+}
+
+#[test]
+fn auction_settlement_refunds_bid_if_sale_fails() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let user_1 = account(1);
+		let seller = account(2);
+		let bidder = account(3);
+		let collection_id = 0;
+		let item = 1;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&seller, initial_balance);
+		Balances::make_free_balance_be(&bidder, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item,
+			seller.clone(),
+			None
+		));
+		assert_ok!(Nfts::create_auction(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_id,
+			item,
+			AuctionKind::English { min_bid: 10 },
+			5
+		));
+		assert_ok!(Nfts::bid(RuntimeOrigin::signed(bidder.clone()), collection_id, item, 10));
+
+		// the collection's items become non-transferable before the auction is settled
+		assert_ok!(Nfts::lock_collection(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			CollectionSettings::from_disabled(CollectionSetting::TransferableItems.into())
+		));
+
+		System::set_block_number(6);
+		assert_ok!(Nfts::settle_auction(
+			RuntimeOrigin::signed(bidder.clone()),
+			collection_id,
+			item
+		));
+		assert!(
+			events().contains(&Event::<Test>::AuctionCancelled { collection: collection_id, item })
+		);
+		assert_eq!(ItemAuctionOf::<Test>::get(collection_id, item), None);
+		assert_eq!(Item::<Test>::get(collection_id, item).unwrap().owner, seller);
+		assert_eq!(Balances::reserved_balance(&bidder), 0);
+		assert_eq!(Balances::total_balance(&bidder), initial_balance);
+		assert_eq!(Balances::total_balance(&seller), initial_balance);
+	});
+}
+
+#[test]
+fn auction_settlement_pays_out_of_reserved_bid() {
+	use frame_support::traits::{LockableCurrency, WithdrawReasons};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let user_1 = account(1);
+		let seller = account(2);
+		let bidder = account(3);
+		let collection_id = 0;
+		let item = 1;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&seller, initial_balance);
+		Balances::make_free_balance_be(&bidder, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item,
+			seller.clone(),
+			None
+		));
+		assert_ok!(Nfts::create_auction(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_id,
+			item,
+			AuctionKind::English { min_bid: 100 },
+			5
+		));
+		assert_ok!(Nfts::bid(RuntimeOrigin::signed(bidder.clone()), collection_id, item, 100));
+
+		// the bidder locks all of their balance, which can't cancel the sale
+		Balances::set_lock(*b"auctions", &bidder, initial_balance, WithdrawReasons::all());
+
+		System::set_block_number(6);
+		assert_ok!(Nfts::settle_auction(
+			RuntimeOrigin::signed(seller.clone()),
+			collection_id,
+			item
+		));
+		assert!(events().contains(&Event::<Test>::AuctionSettled {
+			collection: collection_id,
+			item,
+			seller: seller.clone(),
+			buyer: bidder.clone(),
+			price: 100,
+		}));
+		assert_eq!(Item::<Test>::get(collection_id, item).unwrap().owner, bidder);
+		assert_eq!(Balances::reserved_balance(&bidder), 0);
+		assert_eq!(Balances::total_balance(&bidder), initial_balance - 100);
+		assert_eq!(Balances::total_balance(&seller), initial_balance + 100);
+	});
+}
@@ -70,6 +70,11 @@ pub type ItemTipOf<T, I = ()> = ItemTip<
 	<T as SystemConfig>::AccountId,
 	BalanceOf<T, I>,
 >;
+/// A type alias for the royalty configuration of a collection.
+pub type CollectionRoyaltyFor<T> = CollectionRoyalty<<T as SystemConfig>::AccountId>;
+/// A type alias for the details of an item's auction.
+pub type AuctionFor<T, I = ()> =
+	Auction<<T as SystemConfig>::AccountId, BalanceOf<T, I>, BlockNumberFor<T, I>>;
 /// A type alias for the settings configuration of a collection.
 pub type CollectionConfigFor<T, I = ()> =
 	CollectionConfig<BalanceOf<T, I>, BlockNumberFor<T, I>, <T as Config<I>>::CollectionId>;
@@ -295,6 +300,71 @@ pub struct PriceWithDirection<Amount> {
 	pub direction: PriceDirection,
 }
 
+/// The royalty the creators of a collection receive on every sale of its items.
+#[derive(
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct CollectionRoyalty<AccountId> {
+	/// The account the royalty is paid to.
+	pub recipient: AccountId,
+	/// The share of the sale price paid as the royalty.
+	pub percentage: Permill,
+}
+
+/// The pricing mechanism of an auction.
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum AuctionKind<Amount> {
+	/// Ascending price auction, won by the highest bid placed before the end.
+	English {
+		/// The lowest acceptable first bid.
+		min_bid: Amount,
+	},
+	/// Descending price auction, won by the first bid meeting the current price.
+	Dutch {
+		/// The price at the start of the auction.
+		start_price: Amount,
+		/// The price at the end of the auction.
+		end_price: Amount,
+	},
+}
+
+/// Information about an item's auction.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Auction<AccountId, Amount, BlockNumber> {
+	/// The owner of the item at the time the auction was created.
+	pub seller: AccountId,
+	/// The pricing mechanism of the auction.
+	pub kind: AuctionKind<Amount>,
+	/// The block the auction started at.
+	pub start: BlockNumber,
+	/// The block the auction ends at. Bids close to it may push it back.
+	pub end: BlockNumber,
+	/// The highest bid so far and its bidder, whose funds are reserved.
+	pub highest_bid: Option<(AccountId, Amount)>,
+	/// The royalty of the item's collection at the time the auction was created, which the sale
+	/// pays.
+	pub royalty: Option<CollectionRoyalty<AccountId>>,
+}
+
 /// Support for up to 64 user-enabled features on a collection.
 #[bitflags]
 #[repr(u64)]
@@ -570,6 +640,8 @@ pub enum PalletFeature {
 	Approvals,
 	/// Allow/disallow atomic items swap.
 	Swaps,
+	/// Allow/disallow items auctions.
+	Auctions,
 }
 
 /// Wrapper type for `BitFlags<PalletFeature>` that implements `Codec`.
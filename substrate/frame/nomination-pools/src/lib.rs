@@ -377,7 +377,10 @@ use sp_runtime::{
 	},
 	FixedPointNumber, Perbill,
 };
-use sp_staking::{EraIndex, StakingInterface};
+use sp_staking::{
+	simulation::{DelegatedBalance, DelegatedReward, DelegatedStake},
+	EraIndex, StakingInterface,
+};
 
 #[cfg(any(feature = "try-runtime", feature = "fuzzing", test, debug_assertions))]
 use sp_runtime::TryRuntimeError;
@@ -4205,6 +4208,53 @@ impl<T: Config> Pallet<T> {
 		let reward_account = Self::generate_reward_account(pool_id);
 		(bonded_account, reward_account)
 	}
+
+	/// Splits a staking `reward` paid to the bonded account of a pool among its members.
+	///
+	/// Returns the commission of the pool, and the reward shared by the members as per their
+	/// active points, or `None` if `bonded_account` is not the bonded account of a pool.
+	pub fn api_reward_split(
+		bonded_account: T::AccountId,
+		reward: BalanceOf<T>,
+	) -> Option<DelegatedReward<BalanceOf<T>>> {
+		let pool_id = ReversePoolIdLookup::<T>::get(&bonded_account)?;
+		let bonded_pool = BondedPool::<T>::get(pool_id)?;
+
+		let commission = bonded_pool.commission.current() * reward;
+		Some(DelegatedReward {
+			commission,
+			delegators: reward.saturating_sub(commission),
+			points: bonded_pool.points,
+		})
+	}
+
+	/// The stake of the members of the pool with `bonded_account`, bonded and unbonding per era.
+	///
+	/// Returns `None` if `bonded_account` is not the bonded account of a pool. The unbonding pools
+	/// that were merged into the era agnostic pool are left out, they cannot be slashed anymore.
+	pub fn api_delegated_stake(
+		bonded_account: T::AccountId,
+	) -> Option<DelegatedStake<BalanceOf<T>>> {
+		let pool_id = ReversePoolIdLookup::<T>::get(&bonded_account)?;
+		let bonded_pool = BondedPool::<T>::get(pool_id)?;
+
+		let bonded = DelegatedBalance {
+			points: bonded_pool.points,
+			balance: T::StakeAdapter::active_stake(Pool::from(bonded_account)),
+		};
+		let unbonding = SubPoolsStorage::<T>::get(pool_id)
+			.map(|sub_pools| {
+				sub_pools
+					.with_era
+					.into_iter()
+					.map(|(era, pool)| {
+						(era, DelegatedBalance { points: pool.points, balance: pool.balance })
+					})
+					.collect()
+			})
+			.unwrap_or_default();
+		Some(DelegatedStake { bonded, unbonding })
+	}
 }
 
 impl<T: Config> sp_staking::OnStakingUpdate<T::AccountId, BalanceOf<T>> for Pallet<T> {
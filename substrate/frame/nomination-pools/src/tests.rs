@@ -7520,6 +7520,43 @@ mod slash {
 			assert_eq!(BondedPool::<Runtime>::get(1).unwrap(), bonded(12 + 24, 3));
 		});
 	}
+
+	#[test]
+	fn delegated_stake_tracks_slashed_unbonding_pools() {
+		ExtBuilder::default()
+			.add_members(vec![(40, 40), (550, 550)])
+			.build_and_execute(|| {
+				let stake = |bonded, unbonding| DelegatedStake {
+					bonded: DelegatedBalance { points: 560, balance: bonded },
+					unbonding: vec![(3, DelegatedBalance { points: 40, balance: unbonding })],
+				};
+				assert_ok!(fully_unbond_permissioned(40));
+				assert_eq!(
+					Pools::api_delegated_stake(default_bonded_account()),
+					Some(stake(560, 40))
+				);
+
+				// Given the bonded and the unbonding stake are slashed in half.
+				let bonded_account = default_bonded_account();
+				StakingMock::set_bonded_balance(bonded_account, 280);
+				UnbondingBalanceMap::set(&[(bonded_account, vec![(3, 20)])].into_iter().collect());
+				DelegateMock::on_slash(bonded_account, 300);
+				<Pools as sp_staking::OnStakingUpdate<_, _>>::on_slash(
+					&bonded_account,
+					280,
+					&[(3, 20)].into_iter().collect(),
+					300,
+				);
+
+				// Then
+				assert_eq!(
+					Pools::api_delegated_stake(default_bonded_account()),
+					Some(stake(280, 20))
+				);
+				// not the bonded account of a pool.
+				assert_eq!(Pools::api_delegated_stake(40), None);
+			});
+	}
 }
 
 mod chill {
@@ -151,6 +151,7 @@ use frame_support::{
 	},
 	transactional,
 	weights::WeightMeter,
+	DefaultNoBound,
 };
 use scale_info::TypeInfo;
 use sp_runtime::{
@@ -1891,6 +1892,7 @@ pub mod pallet {
 
 	/// Guard to ensure that the given origin is a person. The underlying identity of the person is
 	/// provided on success.
+	#[derive(DefaultNoBound)]
 	pub struct EnsurePersonalIdentity<T>(PhantomData<T>);
 	impl<T: Config> EnsureOrigin<OriginFor<T>> for EnsurePersonalIdentity<T> {
 		type Success = PersonalId;
@@ -1919,6 +1921,7 @@ pub mod pallet {
 
 	/// Guard to ensure that the given origin is a person. The contextual alias of the person is
 	/// provided on success.
+	#[derive(DefaultNoBound)]
 	pub struct EnsurePersonalAlias<T>(PhantomData<T>);
 	impl<T: Config> EnsureOrigin<OriginFor<T>> for EnsurePersonalAlias<T> {
 		type Success = ContextualAlias;
@@ -1952,6 +1955,7 @@ pub mod pallet {
 
 	/// Guard to ensure that the given origin is a person. The alias of the person within the
 	/// context provided as an argument is returned on success.
+	#[derive(DefaultNoBound)]
 	pub struct EnsurePersonalAliasInContext<T>(PhantomData<T>);
 	impl<T: Config> EnsureOriginWithArg<OriginFor<T>, Context> for EnsurePersonalAliasInContext<T> {
 		type Success = Alias;
@@ -2001,6 +2005,7 @@ pub mod pallet {
 	///
 	/// The revised contextual alias of the person is provided on success. The revision can be used
 	/// to tell in the future if an alias may have been suspended. See [`RevisedContextualAlias`].
+	#[derive(DefaultNoBound)]
 	pub struct EnsureRevisedPersonalAlias<T>(PhantomData<T>);
 	impl<T: Config> EnsureOrigin<OriginFor<T>> for EnsureRevisedPersonalAlias<T> {
 		type Success = RevisedContextualAlias;
@@ -2037,6 +2042,7 @@ pub mod pallet {
 	/// The revised alias of the person within the context provided as an argument is returned on
 	/// success. The revision can be used to tell in the future if an alias may have been suspended.
 	/// See [`RevisedAlias`].
+	#[derive(DefaultNoBound)]
 	pub struct EnsureRevisedPersonalAliasInContext<T>(PhantomData<T>);
 	impl<T: Config> EnsureOriginWithArg<OriginFor<T>, Context>
 		for EnsureRevisedPersonalAliasInContext<T>
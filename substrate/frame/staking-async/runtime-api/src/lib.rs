@@ -20,8 +20,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
+use sp_runtime::Perbill;
+use sp_staking::simulation::{PayoutSimulation, SlashSimulation};
 
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait StakingApi<Balance, AccountId>
 		where
 			Balance: Codec,
@@ -35,5 +38,26 @@ sp_api::decl_runtime_apis! {
 
 		/// Returns true if validator `account` has pages to be claimed for the given era.
 		fn pending_rewards(era: sp_staking::EraIndex, account: AccountId) -> bool;
+
+		/// Simulates the payout of `stash` in `era`, for each exposure page it is part of.
+		///
+		/// `era` can be the active era, in which case the era payout is estimated. Returns `None`
+		/// if `era` is out of history, or `stash` was not exposed in it.
+		#[api_version(2)]
+		fn simulate_payout(
+			era: sp_staking::EraIndex,
+			stash: AccountId,
+		) -> Option<PayoutSimulation<AccountId, Balance>>;
+
+		/// Simulates the slash of validator `stash` and its nominators, for an offence with
+		/// `fraction` committed in `era`.
+		///
+		/// Returns `None` if such an offence would be discarded.
+		#[api_version(2)]
+		fn simulate_slash(
+			era: sp_staking::EraIndex,
+			stash: AccountId,
+			fraction: Perbill,
+		) -> Option<SlashSimulation<AccountId, Balance>>;
 	}
 }
@@ -439,6 +439,11 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type MaxSwapPathLength = ConstU32<3>;
+	type AmplificationOrigin = EnsureRoot<AccountId>;
+	type MaxAmplification = ConstU32<10_000>;
+	type MinAmplificationRampDuration = ConstU32<DAYS>;
+	type MaxObservations = ConstU32<48>;
+	type ObservationPeriod = ConstU32<HOURS>;
 	type MintMinLiquidity = ConstU128<100>;
 	type WeightInfo = weights::pallet_asset_conversion::WeightInfo<Runtime>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -483,6 +488,7 @@ impl pallet_vesting::Config for Runtime {
 	type BlockNumberToBalance = ConvertInto;
 	type Currency = Balances;
 	type MinVestedTransfer = MinVestedTransfer;
+	type RevokeOrigin = EnsureRoot<AccountId>;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::pallet_vesting::WeightInfo<Runtime>;
 	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
@@ -601,6 +607,7 @@ impl pallet_multisig::Config for Runtime {
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 	// TODO add migration.
 	type BlockNumberProvider = RelayChainBlockNumberProvider;
+	type ProposalLifetime = ConstU32<{ 30 * DAYS }>;
 }
 
 impl pallet_utility::Config for Runtime {
@@ -1019,6 +1026,9 @@ impl pallet_nft_fractionalization::Config for Runtime {
 parameter_types! {
 	pub NftsPalletFeatures: PalletFeatures = PalletFeatures::all_enabled();
 	pub const NftsMaxDeadlineDuration: BlockNumber = 12 * 30 * DAYS;
+	pub const NftsMaxRoyalty: Permill = Permill::from_percent(50);
+	pub const NftsMinBidIncrement: Permill = Permill::from_percent(1);
+	pub const NftsAuctionExtension: BlockNumber = 10 * MINUTES;
 	// re-use the Uniques deposits
 	pub const NftsCollectionDeposit: Balance = UniquesCollectionDeposit::get();
 	pub const NftsItemDeposit: Balance = UniquesItemDeposit::get();
@@ -1048,6 +1058,9 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = NftsMaxDeadlineDuration;
 	type MaxAttributesPerCall = ConstU32<10>;
+	type MaxRoyalty = NftsMaxRoyalty;
+	type MinBidIncrement = NftsMinBidIncrement;
+	type AuctionExtension = NftsAuctionExtension;
 	type Features = NftsPalletFeatures;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as Verify>::Signer;
@@ -1089,7 +1102,7 @@ parameter_types! {
 impl pallet_migrations::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	#[cfg(not(feature = "runtime-benchmarks"))]
-	type Migrations = ();
+	type Migrations = pallet_vesting::migrations::v2::LazyMigrationV1ToV2<Runtime>;
 	// Benchmarks need mocked migrations to guarantee that they succeed.
 	#[cfg(feature = "runtime-benchmarks")]
 	type Migrations = pallet_migrations::mock_helpers::MockedMigrations;
@@ -1540,6 +1553,17 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_asset_conversion::TimeWeightedPriceApi<
+		Block,
+		xcm::v5::Location,
+		BlockNumber,
+	> for Runtime
+	{
+		fn time_weighted_price(asset1: xcm::v5::Location, asset2: xcm::v5::Location, period: BlockNumber) -> Option<sp_runtime::FixedU128> {
+			AssetConversion::time_weighted_price(asset1, asset2, period)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -1794,6 +1818,21 @@ impl_runtime_apis! {
 		fn pending_rewards(era: sp_staking::EraIndex, account: AccountId) -> bool {
 			Staking::api_pending_rewards(era, account)
 		}
+
+		fn simulate_payout(
+			era: sp_staking::EraIndex,
+			stash: AccountId,
+		) -> Option<sp_staking::simulation::PayoutSimulation<AccountId, Balance>> {
+			Staking::api_simulate_payout::<crate::staking::PoolMembersSplit>(era, stash)
+		}
+
+		fn simulate_slash(
+			era: sp_staking::EraIndex,
+			stash: AccountId,
+			fraction: Perbill,
+		) -> Option<sp_staking::simulation::SlashSimulation<AccountId, Balance>> {
+			Staking::api_simulate_slash::<crate::staking::PoolMembersSplit>(era, stash, fraction)
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
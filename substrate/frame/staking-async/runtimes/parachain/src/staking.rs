@@ -379,6 +379,24 @@ impl pallet_nomination_pools::Config for Runtime {
 	type BlockNumberProvider = RelayChainBlockNumberProvider;
 }
 
+/// Splits the rewards and slashes of nomination pools among their members, as simulated by the
+/// staking runtime API.
+pub struct PoolMembersSplit;
+impl pallet_staking_async::simulation::DelegatedStakers<AccountId, Balance> for PoolMembersSplit {
+	fn reward_split(
+		staker: &AccountId,
+		reward: Balance,
+	) -> Option<sp_staking::simulation::DelegatedReward<Balance>> {
+		NominationPools::api_reward_split(staker.clone(), reward)
+	}
+
+	fn delegated_stake(
+		staker: &AccountId,
+	) -> Option<sp_staking::simulation::DelegatedStake<Balance>> {
+		NominationPools::api_delegated_stake(staker.clone())
+	}
+}
+
 parameter_types! {
 	pub const DelegatedStakingPalletId: PalletId = PalletId(*b"py/dlstk");
 	pub const SlashRewardFraction: Perbill = Perbill::from_percent(1);
@@ -46,6 +46,10 @@ impl pallet_conviction_voting::Config for Runtime {
 	type Polls = Referenda;
 	type BlockNumberProvider = System;
 	type VotingHooks = ();
+	type PersonhoodOrigin =
+		frame_support::traits::NeverEnsureOrigin<frame_support::traits::reality::Alias>;
+	type PersonhoodClasses = frame_support::traits::Nothing;
+	type VotesPerPerson = frame_support::traits::ConstU128<1>;
 }
 
 parameter_types! {
@@ -65,6 +65,7 @@ pub mod election_size_tracker;
 pub mod ledger;
 mod pallet;
 pub mod session_rotation;
+pub mod simulation;
 pub mod slashing;
 pub mod weights;
 
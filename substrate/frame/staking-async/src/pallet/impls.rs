@@ -22,6 +22,7 @@ use crate::{
 	election_size_tracker::StaticTracker,
 	log,
 	session_rotation::{self, Eras, Rotator},
+	simulation::{self, DelegatedStakers},
 	slashing::OffenceRecord,
 	weights::WeightInfo,
 	BalanceOf, Exposure, Forcing, LedgerIntegrityState, MaxNominationsOf, Nominations,
@@ -51,6 +52,7 @@ use sp_runtime::{
 };
 use sp_staking::{
 	currency_to_vote::CurrencyToVote,
+	simulation::{PayoutSimulation, SlashSimulation},
 	EraIndex, OnStakingUpdate, Page, SessionIndex, Stake,
 	StakingAccount::{self, Controller, Stash},
 	StakingInterface,
@@ -880,6 +882,29 @@ impl<T: Config> Pallet<T> {
 	pub fn api_pending_rewards(era: EraIndex, account: T::AccountId) -> bool {
 		Eras::<T>::pending_rewards(era, &account)
 	}
+
+	/// Simulate the payout of `stash` in `era`, across all exposure pages it is part of.
+	///
+	/// `era` can be the active era, in which case the era payout is estimated. Returns `None` if
+	/// `era` is out of history, or `stash` was not exposed in it.
+	pub fn api_simulate_payout<D: DelegatedStakers<T::AccountId, BalanceOf<T>>>(
+		era: EraIndex,
+		stash: T::AccountId,
+	) -> Option<PayoutSimulation<T::AccountId, BalanceOf<T>>> {
+		simulation::simulate_payout::<T, D>(era, stash)
+	}
+
+	/// Simulate the slash of `validator` and its nominators for an offence of `fraction` in `era`.
+	///
+	/// Returns `None` if the offence would be discarded, e.g. because `era` is not bonded anymore,
+	/// or `validator` is invulnerable or was not exposed in it.
+	pub fn api_simulate_slash<D: DelegatedStakers<T::AccountId, BalanceOf<T>>>(
+		era: EraIndex,
+		validator: T::AccountId,
+		fraction: Perbill,
+	) -> Option<SlashSimulation<T::AccountId, BalanceOf<T>>> {
+		simulation::simulate_slash::<T, D>(era, validator, fraction)
+	}
 }
 
 impl<T: Config> ElectionDataProvider for Pallet<T> {
@@ -0,0 +1,285 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Simulation of era payouts and slashes, without altering any state.
+//!
+//! These are used by the runtime API to let stakers know ahead of time what the payout of an era
+//! would be for a given stash, or what a hypothetical offence of a validator would mean for all of
+//! its stakers. The results follow the exact same computation as
+//! [`Pallet::payout_stakers_by_page`] and the offence processing in [`crate::slashing`] do, and
+//! are returned as the types of [`sp_staking::simulation`].
+//!
+//! Stakers that delegate their stake, such as nomination pools backed by `delegated-staking`,
+//! show up as a single (virtual) staker in the exposures. [`DelegatedStakers`] can be used to
+//! further split their rewards and slashes among their delegators.
+
+use crate::{
+	session_rotation::{Eras, Rotator},
+	slashing::{compute_slash, do_slash, SlashParams},
+	BalanceOf, BondedEras, Config, CurrentEra, ErasStakersOverview, ErasStakersPaged,
+	Invulnerables, NegativeImbalanceOf, Pallet, RewardDestination, SlashRewardFraction,
+	ValidatorSlashInEra,
+};
+use alloc::vec::Vec;
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	traits::{Get, Imbalance},
+};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchError, Perbill,
+};
+use sp_staking::{
+	simulation::{
+		DelegatedReward, DelegatedSlash, DelegatedStake, PagePayout, PageSlash, PayoutSimulation,
+		SlashSimulation, SlashedStaker,
+	},
+	EraIndex, Page, StakingAccount,
+};
+
+/// Splits the rewards and slashes of stakers that are backed by delegators.
+///
+/// Implemented by the runtime, typically on top of `pallet-nomination-pools`. Implementations
+/// must not iterate over all delegators, the simulation only needs the totals they share.
+pub trait DelegatedStakers<AccountId, Balance> {
+	/// Split `reward` paid to `staker` among its delegators, if it has any.
+	fn reward_split(staker: &AccountId, reward: Balance) -> Option<DelegatedReward<Balance>>;
+
+	/// The stake of the delegators of `staker`, if it has any.
+	///
+	/// Read before and after the slash of `staker` is applied, to report how the slash is
+	/// distributed among the bonded and unbonding stake of its delegators.
+	fn delegated_stake(staker: &AccountId) -> Option<DelegatedStake<Balance>>;
+}
+
+impl<AccountId, Balance> DelegatedStakers<AccountId, Balance> for () {
+	fn reward_split(_: &AccountId, _: Balance) -> Option<DelegatedReward<Balance>> {
+		None
+	}
+
+	fn delegated_stake(_: &AccountId) -> Option<DelegatedStake<Balance>> {
+		None
+	}
+}
+
+/// The total payout of a validator in an era, as `(commission, leftover)`.
+///
+/// The commission is paid to the validator across all pages, the leftover to everyone that is
+/// exposed, pro rata to their stake.
+fn validator_payout<T: Config>(
+	era: EraIndex,
+	era_payout: BalanceOf<T>,
+	validator: &T::AccountId,
+) -> (BalanceOf<T>, BalanceOf<T>) {
+	let era_reward_points = Eras::<T>::get_reward_points(era);
+	let validator_reward_points =
+		era_reward_points.individual.get(validator).copied().unwrap_or_else(Zero::zero);
+	if validator_reward_points.is_zero() {
+		return (Zero::zero(), Zero::zero())
+	}
+
+	let validator_total_payout =
+		Perbill::from_rational(validator_reward_points, era_reward_points.total) * era_payout;
+	let commission = Eras::<T>::get_validator_commission(era, validator) * validator_total_payout;
+	(commission, validator_total_payout.saturating_sub(commission))
+}
+
+/// Simulate the payout of `stash` in `era`, see [`Pallet::api_simulate_payout`].
+///
+/// The pages of `stash` as a nominator are looked up in all exposures of `era`, so that they are
+/// found even if `stash` has changed its nominations since. This iterates over every exposure
+/// page of `era` and is only meant to be called off-chain, through the runtime API.
+pub(crate) fn simulate_payout<T: Config, D: DelegatedStakers<T::AccountId, BalanceOf<T>>>(
+	era: EraIndex,
+	stash: T::AccountId,
+) -> Option<PayoutSimulation<T::AccountId, BalanceOf<T>>> {
+	let current_era = CurrentEra::<T>::get()?;
+	let active_era = Rotator::<T>::active_era();
+	if era > active_era || era < current_era.saturating_sub(T::HistoryDepth::get()) {
+		return None
+	}
+
+	let (era_payout, estimated) = match Eras::<T>::get_validators_reward(era) {
+		Some(era_payout) => (era_payout, false),
+		None if era == active_era => (
+			era.checked_sub(1)
+				.and_then(Eras::<T>::get_validators_reward)
+				.unwrap_or_else(Zero::zero),
+			true,
+		),
+		None => return None,
+	};
+
+	let mut pages = Vec::new();
+	let mut page_payout =
+		|validator: T::AccountId, page: Page, commission: BalanceOf<T>, staking: BalanceOf<T>| {
+			let reward = commission.saturating_add(staking);
+			pages.push(PagePayout {
+				claimed: Eras::<T>::is_rewards_claimed(era, &validator, page),
+				delegated: (!reward.is_zero()).then(|| D::reward_split(&stash, reward)).flatten(),
+				validator,
+				page,
+				commission,
+				staking,
+			});
+		};
+
+	// the pages of the stash as a validator.
+	if let Some(overview) = ErasStakersOverview::<T>::get(era, &stash) {
+		let (total_commission, leftover) = validator_payout::<T>(era, era_payout, &stash);
+		for page in 0..Eras::<T>::exposure_page_count(era, &stash) {
+			let Some(exposure) = Eras::<T>::get_paged_exposure(era, &stash, page) else { continue };
+			let staking = Perbill::from_rational(exposure.own(), overview.total) * leftover;
+			let commission =
+				Perbill::from_rational(exposure.page_total(), overview.total) * total_commission;
+			page_payout(stash.clone(), page, commission, staking);
+		}
+	}
+
+	// the pages of the stash as a nominator, wherever it was exposed in `era`, regardless of whom
+	// it nominates now.
+	for ((validator, page), exposure_page) in ErasStakersPaged::<T>::iter_prefix((era,)) {
+		let Some(value) = exposure_page.others.iter().find(|n| n.who == stash).map(|n| n.value)
+		else {
+			continue
+		};
+		let Some(overview) = ErasStakersOverview::<T>::get(era, &validator) else { continue };
+		let (_, leftover) = validator_payout::<T>(era, era_payout, &validator);
+		let staking = Perbill::from_rational(value, overview.total) * leftover;
+		page_payout(validator, page, Zero::zero(), staking);
+	}
+
+	if pages.is_empty() {
+		return None
+	}
+
+	let (mut total, mut pending) = (BalanceOf::<T>::zero(), BalanceOf::<T>::zero());
+	for page in &pages {
+		let reward = page.commission.saturating_add(page.staking);
+		total.saturating_accrue(reward);
+		if !page.claimed {
+			pending.saturating_accrue(reward);
+		}
+	}
+
+	let (payee, restaked) = match Pallet::<T>::payee(StakingAccount::Stash(stash.clone())) {
+		Some(RewardDestination::Staked) => (Some(stash), true),
+		Some(RewardDestination::Stash) => (Some(stash), false),
+		Some(RewardDestination::Account(account)) => (Some(account), false),
+		#[allow(deprecated)]
+		Some(RewardDestination::Controller) => (Pallet::<T>::bonded(&stash), false),
+		Some(RewardDestination::None) | None => (None, false),
+	};
+
+	Some(PayoutSimulation { era, era_payout, estimated, payee, restaked, pages, total, pending })
+}
+
+/// Simulate the slash of `validator` for an offence in `era`, see
+/// [`Pallet::api_simulate_slash`].
+pub(crate) fn simulate_slash<T: Config, D: DelegatedStakers<T::AccountId, BalanceOf<T>>>(
+	era: EraIndex,
+	validator: T::AccountId,
+	fraction: Perbill,
+) -> Option<SlashSimulation<T::AccountId, BalanceOf<T>>> {
+	// offences of eras that are not bonded anymore are discarded.
+	if !BondedEras::<T>::get().iter().any(|(bonded, _)| *bonded == era) {
+		return None
+	}
+	if Invulnerables::<T>::get().contains(&validator) {
+		return None
+	}
+	let overview = ErasStakersOverview::<T>::get(era, &validator)?;
+
+	let prior_fraction =
+		ValidatorSlashInEra::<T>::get(era, &validator).map_or(Zero::zero(), |(f, _)| f);
+	let reward_proportion = SlashRewardFraction::<T>::get();
+	let mut simulation = SlashSimulation {
+		era,
+		validator: validator.clone(),
+		fraction,
+		prior_fraction,
+		apply_era: era.saturating_add(T::SlashDeferDuration::get()),
+		pages: Vec::new(),
+		total: Zero::zero(),
+		reward: Zero::zero(),
+	};
+	if fraction.deconstruct() <= prior_fraction.deconstruct() {
+		return Some(simulation)
+	}
+
+	// pages are processed from the last one to the first, see `slashing::process_offence`.
+	for page in (0..overview.page_count.max(1)).rev() {
+		let Some(exposure) = Eras::<T>::get_paged_exposure(era, &validator, page) else { continue };
+		let Some(unapplied) = compute_slash::<T>(SlashParams {
+			stash: &validator,
+			slash: fraction,
+			prior_slash: prior_fraction,
+			exposure: &exposure,
+			slash_era: era,
+			reward_proportion,
+		}) else {
+			continue
+		};
+
+		let stakers = core::iter::once((unapplied.validator.clone(), unapplied.own))
+			.chain(unapplied.others.iter().cloned())
+			.filter(|(_, value)| !value.is_zero())
+			.filter_map(|(who, value)| slash_staker::<T, D>(who, value, era))
+			.collect::<Vec<_>>();
+		stakers.iter().for_each(|s| simulation.total.saturating_accrue(s.amount));
+		simulation.reward.saturating_accrue(unapplied.payout);
+		simulation.pages.push(PageSlash { page, stakers, reward: unapplied.payout });
+	}
+
+	Some(simulation)
+}
+
+/// Slash `who` by `value`, returning the impact of the slash.
+///
+/// The slash is applied exactly as [`do_slash`] applies it, including its side effects on
+/// [`Config::EventListeners`] such as nomination pools, and then reverted.
+fn slash_staker<T: Config, D: DelegatedStakers<T::AccountId, BalanceOf<T>>>(
+	who: T::AccountId,
+	value: BalanceOf<T>,
+	slash_era: EraIndex,
+) -> Option<SlashedStaker<T::AccountId, BalanceOf<T>>> {
+	let before = Pallet::<T>::ledger(StakingAccount::Stash(who.clone())).ok()?;
+	let delegated_before = D::delegated_stake(&who);
+
+	with_transaction(|| {
+		let mut reward_payout = Zero::zero();
+		let mut slashed = NegativeImbalanceOf::<T>::zero();
+		do_slash::<T>(&who, value, &mut reward_payout, &mut slashed, slash_era);
+		// the imbalance must be dropped before reverting, it alters the total issuance.
+		drop(slashed);
+
+		let after = Pallet::<T>::ledger(StakingAccount::Stash(who.clone())).ok();
+		let delegated = delegated_before
+			.zip(D::delegated_stake(&who))
+			.map(|(before, after)| DelegatedSlash { before, after });
+		let staker = after.map(|after| SlashedStaker {
+			amount: before.total.saturating_sub(after.total),
+			active: after.active,
+			unlocking: after.unlocking.iter().map(|chunk| (chunk.era, chunk.value)).collect(),
+			delegated,
+			who,
+		});
+		TransactionOutcome::Rollback(Ok::<_, DispatchError>(staker))
+	})
+	.ok()
+	.flatten()
+}
@@ -1686,3 +1686,63 @@ fn test_runtime_api_pending_rewards() {
 		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), validator_two, 0));
 	});
 }
+
+#[test]
+fn simulate_payout_matches_actual_payout() {
+	ExtBuilder::default().nominate(true).build_and_execute(|| {
+		Payee::<T>::insert(11, RewardDestination::Account(11));
+		Payee::<T>::insert(101, RewardDestination::Account(101));
+		Eras::<T>::reward_active_era(vec![(11, 100), (21, 50)]);
+
+		// the payout of the active era is not known yet, thus estimated.
+		let estimate = Staking::api_simulate_payout::<()>(active_era(), 101).unwrap();
+		assert!(estimate.estimated);
+		assert_eq!(estimate.pages.len(), 2);
+
+		Session::roll_until_active_era(2);
+		let _ = staking_events_since_last_call();
+
+		let validator = Staking::api_simulate_payout::<()>(1, 11).unwrap();
+		let nominator = Staking::api_simulate_payout::<()>(1, 101).unwrap();
+		assert!(!validator.estimated);
+		assert_eq!(validator.era_payout, ErasValidatorReward::<T>::get(1).unwrap());
+		assert_eq!((nominator.payee, nominator.restaked), (Some(101), false));
+		assert_eq_uvec!(
+			nominator
+				.pages
+				.iter()
+				.map(|p| (p.validator, p.page, p.claimed))
+				.collect::<Vec<_>>(),
+			vec![(11, 0, false), (21, 0, false)]
+		);
+		assert!(nominator.pages.iter().all(|p| p.commission == 0 && p.delegated.is_none()));
+		assert_eq!(nominator.total, nominator.pending);
+
+		// the exposures of the era count, not the current nominations.
+		assert_ok!(Staking::chill(RuntimeOrigin::signed(101)));
+		assert_eq!(Staking::api_simulate_payout::<()>(1, 101).unwrap().total, nominator.total);
+
+		mock::make_all_reward_payment(1);
+		let events = staking_events_since_last_call();
+		let rewarded = |who: AccountId| {
+			events
+				.iter()
+				.filter_map(|e| match e {
+					Event::Rewarded { stash, amount, .. } if *stash == who => Some(*amount),
+					_ => None,
+				})
+				.sum::<Balance>()
+		};
+		assert_eq!(validator.total, rewarded(11));
+		assert_eq!(nominator.total, rewarded(101));
+
+		// all pages are claimed now.
+		let nominator = Staking::api_simulate_payout::<()>(1, 101).unwrap();
+		assert!(nominator.pages.iter().all(|p| p.claimed));
+		assert_eq!(nominator.pending, 0);
+
+		// not exposed, or not an era with rewards.
+		assert!(Staking::api_simulate_payout::<()>(1, 31).is_none());
+		assert!(Staking::api_simulate_payout::<()>(3, 11).is_none());
+	});
+}
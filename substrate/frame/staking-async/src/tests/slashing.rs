@@ -1765,3 +1765,51 @@ mod paged_slashing {
 		});
 	}
 }
+
+#[test]
+fn simulate_slash_matches_applied_slash() {
+	ExtBuilder::default().build_and_execute(|| {
+		// a part of the stake of 101 is unbonding, and slashed as well.
+		assert_ok!(Staking::unbond(RuntimeOrigin::signed(101), 100));
+		let ledger_101 = Staking::ledger(101.into()).unwrap();
+
+		let simulation =
+			Staking::api_simulate_slash::<()>(active_era(), 11, Perbill::from_percent(10)).unwrap();
+		assert_eq!(simulation.prior_fraction, Perbill::zero());
+		assert_eq!(simulation.apply_era, active_era());
+		assert_eq!(simulation.pages.len(), 1);
+		let stakers = simulation.pages[0].stakers.clone();
+		assert_eq!(stakers.iter().map(|s| s.who).collect::<Vec<_>>(), vec![11, 101]);
+		assert_eq!(simulation.total, stakers.iter().map(|s| s.amount).sum::<Balance>());
+
+		// nothing is slashed by the simulation.
+		assert_eq!(Staking::ledger(101.into()).unwrap().active, ledger_101.active);
+		assert_eq!(Staking::ledger(101.into()).unwrap().unlocking, ledger_101.unlocking);
+
+		add_slash(11);
+		Session::roll_next();
+		let slashed = staking_events_since_last_call()
+			.into_iter()
+			.filter_map(|e| match e {
+				Event::Slashed { staker, amount } => Some((staker, amount)),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(slashed, stakers.iter().map(|s| (s.who, s.amount)).collect::<Vec<_>>());
+
+		for staker in stakers {
+			let ledger = Staking::ledger(staker.who.into()).unwrap();
+			assert_eq!(ledger.active, staker.active);
+			assert_eq!(
+				ledger.unlocking.iter().map(|c| (c.era, c.value)).collect::<Vec<_>>(),
+				staker.unlocking,
+			);
+		}
+
+		// the same offence is not slashed again.
+		let simulation =
+			Staking::api_simulate_slash::<()>(active_era(), 11, Perbill::from_percent(10)).unwrap();
+		assert_eq!(simulation.prior_fraction, Perbill::from_percent(10));
+		assert!(simulation.pages.is_empty());
+	});
+}
@@ -221,6 +221,11 @@ macro_rules! impl_ensure_origin_with_arg_ignoring_arg {
 
 /// [`EnsureOrigin`] implementation that always fails.
 pub struct NeverEnsureOrigin<Success>(core::marker::PhantomData<Success>);
+impl<Success> Default for NeverEnsureOrigin<Success> {
+	fn default() -> Self {
+		Self(core::marker::PhantomData)
+	}
+}
 impl<OO, Success> EnsureOrigin<OO> for NeverEnsureOrigin<Success> {
 	type Success = Success;
 	fn try_origin(o: OO) -> Result<Success, OO> {
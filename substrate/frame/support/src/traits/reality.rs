@@ -182,6 +182,13 @@ pub trait CountedMembers {
 	fn active_count(&self) -> u32;
 }
 
+/// No origin ever passes [`NeverEnsureOrigin`], so its set has no members.
+impl<Success> CountedMembers for super::NeverEnsureOrigin<Success> {
+	fn active_count(&self) -> u32 {
+		0
+	}
+}
+
 /// A legitimate verdict on a particular statement.
 #[derive(
 	Clone,
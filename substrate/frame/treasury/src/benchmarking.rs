@@ -110,6 +110,31 @@ fn create_spend_arguments<T: Config<I>, I: 'static>(
 	(asset_kind, 100u32.into(), beneficiary, beneficiary_lookup)
 }
 
+// Approve a stream of `amount` of `asset_kind` which has partly accrued by the current block, if
+// there is a spend origin. Returns whether the stream exists.
+fn create_accrued_stream<T: Config<I>, I: 'static>(
+	asset_kind: T::AssetKind,
+	amount: AssetBalanceOf<T, I>,
+	beneficiary_lookup: BeneficiaryLookupOf<T, I>,
+) -> Result<bool, BenchmarkError> {
+	let Ok(origin) = T::SpendOrigin::try_successful_origin() else { return Ok(false) };
+	let period = One::one();
+	let now = T::BlockNumberProvider::current_block_number();
+	// Start the stream one period ago, so that half of it has accrued by now.
+	Treasury::<T, _>::spend_stream(
+		origin,
+		Box::new(asset_kind),
+		amount,
+		Box::new(beneficiary_lookup),
+		Some(now.saturating_sub(period)),
+		period,
+		2,
+		Accrual::Linear,
+	)?;
+	T::BlockNumberProvider::set_block_number(now.max(period));
+	Ok(true)
+}
+
 #[instance_benchmarks]
 mod benchmarks {
 	use super::*;
@@ -350,6 +375,149 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn spend_stream() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SpendOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (asset_kind, amount, beneficiary, beneficiary_lookup) =
+			create_spend_arguments::<T, _>(SEED);
+		T::BalanceConverter::ensure_successful(asset_kind.clone());
+		let period = 10u32.into();
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(asset_kind.clone()),
+			amount,
+			Box::new(beneficiary_lookup),
+			None,
+			period,
+			12,
+			Accrual::Installments,
+		);
+
+		let start = T::BlockNumberProvider::current_block_number();
+		assert_last_event::<T, I>(
+			Event::StreamApproved {
+				index: 0,
+				asset_kind,
+				amount,
+				beneficiary,
+				start,
+				period,
+				periods: 12,
+				accrual: Accrual::Installments,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_stream() -> Result<(), BenchmarkError> {
+		let (asset_kind, amount, beneficiary, beneficiary_lookup) =
+			create_spend_arguments::<T, _>(SEED);
+		T::BalanceConverter::ensure_successful(asset_kind.clone());
+		let stream_exists =
+			create_accrued_stream::<T, I>(asset_kind.clone(), amount, beneficiary_lookup)?;
+
+		T::Paymaster::ensure_successful(&beneficiary, asset_kind, amount);
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		#[block]
+		{
+			let res =
+				Treasury::<T, _>::claim_stream(RawOrigin::Signed(caller.clone()).into(), 0u32);
+
+			if stream_exists {
+				assert_ok!(res);
+			} else {
+				assert_err!(res, crate::Error::<T, _>::InvalidIndex);
+			}
+		}
+
+		if stream_exists {
+			let stream = Streams::<T, I>::get(0).unwrap();
+			let id = match stream.status {
+				PaymentState::Attempted { id, .. } => {
+					assert_ne!(T::Paymaster::check_payment(id), PaymentStatus::Failure);
+					id
+				},
+				_ => panic!("No payout attempt made"),
+			};
+			assert_last_event::<T, I>(
+				Event::StreamPaid { index: 0, amount: stream.paid, payment_id: id }.into(),
+			);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn check_stream_status() -> Result<(), BenchmarkError> {
+		let (asset_kind, amount, beneficiary, beneficiary_lookup) =
+			create_spend_arguments::<T, _>(SEED);
+
+		T::BalanceConverter::ensure_successful(asset_kind.clone());
+		T::Paymaster::ensure_successful(&beneficiary, asset_kind.clone(), amount);
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let stream_exists = create_accrued_stream::<T, I>(asset_kind, amount, beneficiary_lookup)?;
+		if stream_exists {
+			Treasury::<T, _>::claim_stream(RawOrigin::Signed(caller.clone()).into(), 0u32)?;
+			match Streams::<T, I>::get(0).unwrap().status {
+				PaymentState::Attempted { id, .. } => {
+					T::Paymaster::ensure_concluded(id);
+				},
+				_ => panic!("No payout attempt made"),
+			};
+		}
+
+		#[block]
+		{
+			let res = Treasury::<T, _>::check_stream_status(
+				RawOrigin::Signed(caller.clone()).into(),
+				0u32,
+			);
+
+			if stream_exists {
+				assert_ok!(res);
+			} else {
+				assert_err!(res, crate::Error::<T, _>::InvalidIndex);
+			}
+		}
+
+		if let Some(s) = Streams::<T, I>::get(0) {
+			assert!(!matches!(s.status, PaymentState::Attempted { .. }));
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_stream() -> Result<(), BenchmarkError> {
+		let (asset_kind, amount, _, beneficiary_lookup) = create_spend_arguments::<T, _>(SEED);
+		T::BalanceConverter::ensure_successful(asset_kind.clone());
+		let stream_exists = create_accrued_stream::<T, I>(asset_kind, amount, beneficiary_lookup)?;
+
+		let origin =
+			T::RejectOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[block]
+		{
+			let res = Treasury::<T, _>::cancel_stream(origin as T::RuntimeOrigin, 0u32);
+
+			if stream_exists {
+				assert_ok!(res);
+			} else {
+				assert_err!(res, crate::Error::<T, _>::InvalidIndex);
+			}
+		}
+
+		assert!(Streams::<T, I>::get(0).is_none());
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(
 		Treasury,
 		crate::tests::ExtBuilder::default().build(),
@@ -45,6 +45,8 @@
 //! - **Pot:** Unspent funds accumulated by the treasury pallet.
 //! - **Spend** An approved proposal for transferring a specific amount of funds to a designated
 //!   beneficiary.
+//! - **Stream** An approved spend whose amount accrues to the beneficiary over a number of periods,
+//!   either linearly with every block or in installments at the end of every period.
 //!
 //! ### Example
 //!
@@ -54,6 +56,9 @@
 //! 2. Approve a spend of some asset kind and claim it.
 #![doc = docify::embed!("src/tests.rs", spend_payout_works)]
 //!
+//! 3. Approve a stream of some asset kind and claim it as it accrues.
+#![doc = docify::embed!("src/tests.rs", stream_claim_works)]
+//!
 //! ## Pallet API
 //!
 //! See the [`pallet`] module for more information about the interfaces this pallet exposes,
@@ -69,6 +74,13 @@
 //! [`pallet::Config::Paymaster`]. To claim these spends, the `payout` dispatchable should be called
 //! within some temporal bounds, starting from the moment they become valid and within one
 //! [`pallet::Config::PayoutPeriod`].
+//!
+//! Recurring payments are approved at once with the `spend_stream` dispatchable. The amount of a
+//! stream accrues over its periods and the beneficiary claims whatever has accrued so far with the
+//! `claim_stream` dispatchable, through the same [`pallet::Config::Paymaster`]. Whatever has
+//! accrued must be claimed within one [`pallet::Config::PayoutPeriod`] after the stream has ended.
+//! A stream can be cancelled with the `cancel_stream` dispatchable, in which case nothing more
+//! accrues and the unpaid funds remain in the treasury.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -84,16 +96,16 @@ pub use benchmarking::ArgumentsFactory;
 
 extern crate alloc;
 
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 
 use alloc::{boxed::Box, collections::btree_map::BTreeMap};
 use sp_runtime::{
 	traits::{
-		AccountIdConversion, BlockNumberProvider, CheckedAdd, One, Saturating, StaticLookup,
-		UniqueSaturatedInto, Zero,
+		AccountIdConversion, AtLeast32BitUnsigned, BlockNumberProvider, CheckedAdd, One,
+		Saturating, StaticLookup, UniqueSaturatedFrom, UniqueSaturatedInto, Zero,
 	},
-	PerThing, Permill, RuntimeDebug,
+	PerThing, Permill, Rounding, RuntimeDebug,
 };
 
 use frame_support::{
@@ -196,6 +208,88 @@ pub struct SpendStatus<AssetKind, AssetBalance, Beneficiary, BlockNumber, Paymen
 /// Index of an approved treasury spend.
 pub type SpendIndex = u32;
 
+/// The way in which the amount of a treasury stream accrues to its beneficiary.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	MaxEncodedLen,
+	RuntimeDebug,
+	TypeInfo,
+)]
+pub enum Accrual {
+	/// The amount accrues linearly with every block.
+	Linear,
+	/// The amount accrues in equal installments, each at the end of a period.
+	Installments,
+}
+
+/// Info regarding an approved treasury stream.
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct StreamStatus<AssetKind, AssetBalance, Beneficiary, BlockNumber, PaymentId> {
+	/// The kind of asset to be streamed.
+	asset_kind: AssetKind,
+	/// The total asset amount of the stream.
+	amount: AssetBalance,
+	/// The beneficiary of the stream.
+	beneficiary: Beneficiary,
+	/// The block number from which the amount starts to accrue.
+	start: BlockNumber,
+	/// The length of a single period of the stream.
+	period: BlockNumber,
+	/// The number of periods over which the amount accrues.
+	periods: u32,
+	/// The way in which the amount accrues over the periods.
+	accrual: Accrual,
+	/// The amount paid so far, including the amount of a payment that is still in progress.
+	paid: AssetBalance,
+	/// The amount of the last payment attempt.
+	attempted: AssetBalance,
+	/// The block number by which the accrued amount has to be claimed.
+	expire_at: BlockNumber,
+	/// The status of the last payout/claim.
+	status: PaymentState<PaymentId>,
+}
+
+impl<AssetKind, AssetBalance, Beneficiary, BlockNumber, PaymentId>
+	StreamStatus<AssetKind, AssetBalance, Beneficiary, BlockNumber, PaymentId>
+where
+	AssetBalance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// The amount of the stream which has accrued by the block `now`.
+	pub fn accrued(&self, now: BlockNumber) -> AssetBalance {
+		let elapsed = now.saturating_sub(self.start);
+		let duration = self.period.saturating_mul(self.periods.into());
+		if elapsed >= duration {
+			return self.amount
+		}
+		let (part, total): (u128, u128) = match self.accrual {
+			Accrual::Linear => (elapsed.unique_saturated_into(), duration.unique_saturated_into()),
+			Accrual::Installments =>
+				((elapsed / self.period).unique_saturated_into(), self.periods.into()),
+		};
+		// `part < total`, so the result neither overflows nor loses precision beyond the final
+		// rounding down.
+		self.amount
+			.multiply_rational(
+				AssetBalance::unique_saturated_from(part),
+				AssetBalance::unique_saturated_from(total),
+				Rounding::Down,
+			)
+			.unwrap_or(self.amount)
+	}
+}
+
+/// Index of an approved treasury stream.
+pub type StreamIndex = u32;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -352,6 +446,27 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The count of streams that have been made.
+	#[pallet::storage]
+	pub type StreamCount<T, I = ()> = StorageValue<_, StreamIndex, ValueQuery>;
+
+	/// Streams that have been approved and being paid out.
+	// Hasher: Twox safe since `StreamIndex` is an internal count based index.
+	#[pallet::storage]
+	pub type Streams<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		StreamIndex,
+		StreamStatus<
+			T::AssetKind,
+			AssetBalanceOf<T, I>,
+			T::Beneficiary,
+			BlockNumberFor<T, I>,
+			<T::Paymaster as Pay>::Id,
+		>,
+		OptionQuery,
+	>;
+
 	/// The blocknumber for the last triggered spend period.
 	#[pallet::storage]
 	pub type LastSpendPeriod<T, I = ()> = StorageValue<_, BlockNumberFor<T, I>, OptionQuery>;
@@ -414,6 +529,30 @@ pub mod pallet {
 		/// A spend was processed and removed from the storage. It might have been successfully
 		/// paid or it may have expired.
 		SpendProcessed { index: SpendIndex },
+		/// A new stream has been approved.
+		StreamApproved {
+			index: StreamIndex,
+			asset_kind: T::AssetKind,
+			amount: AssetBalanceOf<T, I>,
+			beneficiary: T::Beneficiary,
+			start: BlockNumberFor<T, I>,
+			period: BlockNumberFor<T, I>,
+			periods: u32,
+			accrual: Accrual,
+		},
+		/// An accrued amount of a stream has been paid.
+		StreamPaid {
+			index: StreamIndex,
+			amount: AssetBalanceOf<T, I>,
+			payment_id: <T::Paymaster as Pay>::Id,
+		},
+		/// A payment of a stream failed and the amount can be claimed again.
+		StreamPaymentFailed { index: StreamIndex, payment_id: <T::Paymaster as Pay>::Id },
+		/// A stream was processed and removed from the storage. It might have been fully paid or
+		/// it may have expired.
+		StreamProcessed { index: StreamIndex },
+		/// A stream was cancelled and its unpaid amount remains in the treasury.
+		StreamCancelled { index: StreamIndex, unpaid: AssetBalanceOf<T, I> },
 	}
 
 	/// Error for the treasury pallet.
@@ -442,6 +581,10 @@ pub mod pallet {
 		NotAttempted,
 		/// The payment has neither failed nor succeeded yet.
 		Inconclusive,
+		/// A stream must have at least one period of at least one block.
+		InvalidStreamSchedule,
+		/// Nothing has accrued to the beneficiary of the stream since the last payout.
+		NothingAccrued,
 	}
 
 	#[pallet::hooks]
@@ -659,29 +802,7 @@ pub mod pallet {
 			let expire_at = valid_from.saturating_add(T::PayoutPeriod::get());
 			ensure!(expire_at > now, Error::<T, I>::SpendExpired);
 
-			let native_amount =
-				T::BalanceConverter::from_asset_balance(amount, *asset_kind.clone())
-					.map_err(|_| Error::<T, I>::FailedToConvertBalance)?;
-
-			ensure!(native_amount <= max_amount, Error::<T, I>::InsufficientPermission);
-
-			with_context::<SpendContext<BalanceOf<T, I>>, _>(|v| {
-				let context = v.or_default();
-				// We group based on `max_amount`, to distinguish between different kind of
-				// origins. (assumes that all origins have different `max_amount`)
-				//
-				// Worst case is that we reject some "valid" request.
-				let spend = context.spend_in_context.entry(max_amount).or_default();
-
-				// Ensure that we don't overflow nor use more than `max_amount`
-				if spend.checked_add(&native_amount).map(|s| s > max_amount).unwrap_or(true) {
-					Err(Error::<T, I>::InsufficientPermission)
-				} else {
-					*spend = spend.saturating_add(native_amount);
-					Ok(())
-				}
-			})
-			.unwrap_or(Ok(()))?;
+			Self::ensure_spend_permitted(max_amount, amount, &asset_kind)?;
 
 			let index = SpendCount::<T, I>::get();
 			Spends::<T, I>::insert(
@@ -839,6 +960,243 @@ pub mod pallet {
 			Self::deposit_event(Event::<T, I>::AssetSpendVoided { index });
 			Ok(())
 		}
+
+		/// Propose and approve a stream of treasury funds.
+		///
+		/// ## Dispatch Origin
+		///
+		/// Must be [`Config::SpendOrigin`] with the `Success` value being at least the total
+		/// `amount` of `asset_kind` in the native asset. The amount of `asset_kind` is converted
+		/// for assertion using the [`Config::BalanceConverter`].
+		///
+		/// ## Details
+		///
+		/// Create an approved stream for transferring a total `amount` of `asset_kind` to a
+		/// designated beneficiary over `periods` periods of `period` blocks each. The accrued
+		/// amount can be claimed at any time using the `claim_stream` dispatchable, and must be
+		/// claimed within the [`Config::PayoutPeriod`] after the stream has ended.
+		///
+		/// ### Parameters
+		/// - `asset_kind`: An indicator of the specific asset class to be spent.
+		/// - `amount`: The total amount to be transferred from the treasury to the `beneficiary`.
+		/// - `beneficiary`: The beneficiary of the stream.
+		/// - `valid_from`: The block number from which the amount starts to accrue. If `None`, the
+		///   amount starts to accrue immediately after approval.
+		/// - `period`: The length of a period in blocks.
+		/// - `periods`: The number of periods over which the amount accrues.
+		/// - `accrual`: Whether the amount accrues linearly with every block, or in equal
+		///   installments at the end of every period.
+		///
+		/// ## Events
+		///
+		/// Emits [`Event::StreamApproved`] if successful.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::spend_stream())]
+		pub fn spend_stream(
+			origin: OriginFor<T>,
+			asset_kind: Box<T::AssetKind>,
+			#[pallet::compact] amount: AssetBalanceOf<T, I>,
+			beneficiary: Box<BeneficiaryLookupOf<T, I>>,
+			valid_from: Option<BlockNumberFor<T, I>>,
+			period: BlockNumberFor<T, I>,
+			periods: u32,
+			accrual: Accrual,
+		) -> DispatchResult {
+			let max_amount = T::SpendOrigin::ensure_origin(origin)?;
+			let beneficiary = T::BeneficiaryLookup::lookup(*beneficiary)?;
+			ensure!(!period.is_zero() && periods > 0, Error::<T, I>::InvalidStreamSchedule);
+
+			let now = T::BlockNumberProvider::current_block_number();
+			let start = valid_from.unwrap_or(now);
+			let expire_at = start
+				.saturating_add(period.saturating_mul(periods.into()))
+				.saturating_add(T::PayoutPeriod::get());
+			ensure!(expire_at > now, Error::<T, I>::SpendExpired);
+
+			Self::ensure_spend_permitted(max_amount, amount, &asset_kind)?;
+
+			let index = StreamCount::<T, I>::get();
+			Streams::<T, I>::insert(
+				index,
+				StreamStatus {
+					asset_kind: *asset_kind.clone(),
+					amount,
+					beneficiary: beneficiary.clone(),
+					start,
+					period,
+					periods,
+					accrual,
+					paid: Zero::zero(),
+					attempted: Zero::zero(),
+					expire_at,
+					status: PaymentState::Pending,
+				},
+			);
+			StreamCount::<T, I>::put(index + 1);
+
+			Self::deposit_event(Event::StreamApproved {
+				index,
+				asset_kind: *asset_kind,
+				amount,
+				beneficiary,
+				start,
+				period,
+				periods,
+				accrual,
+			});
+			Ok(())
+		}
+
+		/// Claim the amount of a stream that has accrued since its last payout.
+		///
+		/// ## Dispatch Origin
+		///
+		/// Must be signed.
+		///
+		/// ## Details
+		///
+		/// The accrued amount is paid through the [`Config::Paymaster`]. The status of the payment
+		/// must be updated with the `check_stream_status` dispatchable before the stream can be
+		/// claimed again. If the payment has failed, its amount can be claimed again.
+		///
+		/// ### Parameters
+		/// - `index`: The stream index.
+		///
+		/// ## Events
+		///
+		/// Emits [`Event::StreamPaid`] if successful.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::claim_stream())]
+		pub fn claim_stream(origin: OriginFor<T>, index: StreamIndex) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut stream = Streams::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			let now = T::BlockNumberProvider::current_block_number();
+			ensure!(now >= stream.start, Error::<T, I>::EarlyPayout);
+			ensure!(stream.expire_at > now, Error::<T, I>::SpendExpired);
+			ensure!(
+				matches!(stream.status, PaymentState::Pending | PaymentState::Failed),
+				Error::<T, I>::AlreadyAttempted
+			);
+
+			let amount = stream.accrued(now).saturating_sub(stream.paid);
+			ensure!(!amount.is_zero(), Error::<T, I>::NothingAccrued);
+
+			let id = T::Paymaster::pay(&stream.beneficiary, stream.asset_kind.clone(), amount)
+				.map_err(|_| Error::<T, I>::PayoutError)?;
+
+			stream.paid = stream.paid.saturating_add(amount);
+			stream.attempted = amount;
+			stream.status = PaymentState::Attempted { id };
+			// Leave time to retry the last payment in case it fails.
+			stream.expire_at = stream.expire_at.max(now.saturating_add(T::PayoutPeriod::get()));
+			Streams::<T, I>::insert(index, stream);
+
+			Self::deposit_event(Event::<T, I>::StreamPaid { index, amount, payment_id: id });
+
+			Ok(())
+		}
+
+		/// Check the status of the last payment of a stream and remove the stream from the storage
+		/// if processed.
+		///
+		/// ## Dispatch Origin
+		///
+		/// Must be signed.
+		///
+		/// ## Details
+		///
+		/// The status check is a prerequisite for claiming a stream again after a payout. If the
+		/// stream has either been paid in full or expired, it is removed from the storage by this
+		/// function. In such instances, transaction fees are refunded.
+		///
+		/// ### Parameters
+		/// - `index`: The stream index.
+		///
+		/// ## Events
+		///
+		/// Emits [`Event::StreamPaymentFailed`] if the last payment has failed.
+		/// Emits [`Event::StreamProcessed`] if the stream has been paid in full or has expired.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::check_stream_status())]
+		pub fn check_stream_status(
+			origin: OriginFor<T>,
+			index: StreamIndex,
+		) -> DispatchResultWithPostInfo {
+			use PaymentState as State;
+			use PaymentStatus as Status;
+
+			ensure_signed(origin)?;
+			let mut stream = Streams::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			let now = T::BlockNumberProvider::current_block_number();
+
+			let payment_id = match stream.status {
+				State::Attempted { id } => id,
+				_ if now > stream.expire_at => {
+					// stream has expired and no further status update is expected.
+					Streams::<T, I>::remove(index);
+					Self::deposit_event(Event::<T, I>::StreamProcessed { index });
+					return Ok(Pays::No.into())
+				},
+				_ => return Err(Error::<T, I>::NotAttempted.into()),
+			};
+
+			match T::Paymaster::check_payment(payment_id) {
+				Status::Failure => {
+					stream.paid = stream.paid.saturating_sub(stream.attempted);
+					stream.attempted = Zero::zero();
+					stream.status = PaymentState::Failed;
+					Streams::<T, I>::insert(index, stream);
+					Self::deposit_event(Event::<T, I>::StreamPaymentFailed { index, payment_id });
+				},
+				Status::Success | Status::Unknown => {
+					if stream.paid >= stream.amount {
+						Streams::<T, I>::remove(index);
+						Self::deposit_event(Event::<T, I>::StreamProcessed { index });
+					} else {
+						stream.attempted = Zero::zero();
+						stream.status = PaymentState::Pending;
+						Streams::<T, I>::insert(index, stream);
+					}
+					return Ok(Pays::No.into())
+				},
+				Status::InProgress => return Err(Error::<T, I>::Inconclusive.into()),
+			}
+			return Ok(Pays::Yes.into())
+		}
+
+		/// Cancel a previously approved stream.
+		///
+		/// ## Dispatch Origin
+		///
+		/// Must be [`Config::RejectOrigin`].
+		///
+		/// ## Details
+		///
+		/// Nothing more accrues to the beneficiary and the unpaid amount, including any accrued
+		/// but unclaimed amount, remains in the treasury. A stream can only be cancelled if there
+		/// is no payment of it in progress.
+		///
+		/// ### Parameters
+		/// - `index`: The stream index.
+		///
+		/// ## Events
+		///
+		/// Emits [`Event::StreamCancelled`] if successful.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::cancel_stream())]
+		pub fn cancel_stream(origin: OriginFor<T>, index: StreamIndex) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+			let stream = Streams::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			ensure!(
+				matches!(stream.status, PaymentState::Pending | PaymentState::Failed),
+				Error::<T, I>::AlreadyAttempted
+			);
+
+			Streams::<T, I>::remove(index);
+			let unpaid = stream.amount.saturating_sub(stream.paid);
+			Self::deposit_event(Event::<T, I>::StreamCancelled { index, unpaid });
+			Ok(())
+		}
 	}
 }
 
@@ -853,6 +1211,39 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		T::PalletId::get().into_account_truncating()
 	}
 
+	// Ensure that the spend origin with the maximum native amount `max_amount` is permitted to
+	// spend `amount` of `asset_kind`, including anything else it has spent in the same dispatch.
+	fn ensure_spend_permitted(
+		max_amount: BalanceOf<T, I>,
+		amount: AssetBalanceOf<T, I>,
+		asset_kind: &T::AssetKind,
+	) -> DispatchResult {
+		let native_amount = T::BalanceConverter::from_asset_balance(amount, asset_kind.clone())
+			.map_err(|_| Error::<T, I>::FailedToConvertBalance)?;
+
+		ensure!(native_amount <= max_amount, Error::<T, I>::InsufficientPermission);
+
+		with_context::<SpendContext<BalanceOf<T, I>>, _>(|v| {
+			let context = v.or_default();
+			// We group based on `max_amount`, to distinguish between different kind of
+			// origins. (assumes that all origins have different `max_amount`)
+			//
+			// Worst case is that we reject some "valid" request.
+			let spend = context.spend_in_context.entry(max_amount).or_default();
+
+			// Ensure that we don't overflow nor use more than `max_amount`
+			if spend.checked_add(&native_amount).map(|s| s > max_amount).unwrap_or(true) {
+				Err(Error::<T, I>::InsufficientPermission)
+			} else {
+				*spend = spend.saturating_add(native_amount);
+				Ok(())
+			}
+		})
+		.unwrap_or(Ok(()))?;
+
+		Ok(())
+	}
+
 	// Backfill the `LastSpendPeriod` storage, assuming that no configuration has changed
 	// since introducing this code. Used specifically for a migration-less switch to populate
 	// `LastSpendPeriod`.
@@ -1003,6 +1394,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
 		Self::try_state_proposals()?;
 		Self::try_state_spends()?;
+		Self::try_state_streams()?;
 
 		Ok(())
 	}
@@ -1076,6 +1468,33 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		Ok(())
 	}
+
+	/// ## Invariants of stream storage items
+	///
+	/// 1. [`StreamCount`] >= Number of elements in [`Streams`].
+	/// 2. Each entry in [`Streams`] should be saved under a key strictly less than current
+	/// [`StreamCount`].
+	/// 3. For each stream entry contained in [`Streams`] we should have stream.paid
+	/// <= stream.amount.
+	#[cfg(any(feature = "try-runtime", test))]
+	fn try_state_streams() -> Result<(), sp_runtime::TryRuntimeError> {
+		let current_stream_count = StreamCount::<T, I>::get();
+		ensure!(
+			current_stream_count as usize >= Streams::<T, I>::iter().count(),
+			"Actual number of streams exceeds `StreamCount`."
+		);
+
+		Streams::<T, I>::iter().try_for_each(|(index, stream)| -> DispatchResult {
+			ensure!(
+				current_stream_count > index,
+				"`StreamCount` should by strictly greater than any StreamIndex used as a key for `Streams`."
+			);
+			ensure!(stream.paid <= stream.amount, "Stream cannot pay more than its amount.");
+			Ok(())
+		})?;
+
+		Ok(())
+	}
 }
 
 impl<T: Config<I>, I: 'static> OnUnbalanced<NegativeImbalanceOf<T, I>> for Pallet<T, I> {
@@ -849,6 +849,338 @@ fn check_status_works() {
 	});
 }
 
+fn get_stream_payment_id(i: StreamIndex) -> Option<u64> {
+	let stream = Streams::<Test, _>::get(i).expect("no stream");
+	match stream.status {
+		PaymentState::Attempted { id } => Some(id),
+		_ => None,
+	}
+}
+
+#[test]
+fn spend_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend_stream(
+			RuntimeOrigin::signed(14),
+			Box::new(1),
+			100,
+			Box::new(6),
+			None,
+			10,
+			2,
+			Accrual::Linear
+		));
+
+		assert_eq!(StreamCount::<Test, _>::get(), 1);
+		assert_eq!(
+			Streams::<Test, _>::get(0).unwrap(),
+			StreamStatus {
+				asset_kind: 1,
+				amount: 100,
+				beneficiary: 6,
+				start: 1,
+				period: 10,
+				periods: 2,
+				accrual: Accrual::Linear,
+				paid: 0,
+				attempted: 0,
+				expire_at: 26,
+				status: PaymentState::Pending,
+			}
+		);
+		System::assert_last_event(
+			Event::<Test, _>::StreamApproved {
+				index: 0,
+				asset_kind: 1,
+				amount: 100,
+				beneficiary: 6,
+				start: 1,
+				period: 10,
+				periods: 2,
+				accrual: Accrual::Linear,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn spend_stream_origin_and_schedule_are_checked() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		// the total amount of the stream is checked against the spend origin.
+		assert_noop!(
+			Treasury::spend_stream(
+				RuntimeOrigin::signed(10),
+				Box::new(1),
+				3,
+				Box::new(6),
+				None,
+				10,
+				2,
+				Accrual::Linear
+			),
+			Error::<Test, _>::InsufficientPermission
+		);
+		assert_noop!(
+			Treasury::spend_stream(
+				RuntimeOrigin::signed(14),
+				Box::new(1),
+				100,
+				Box::new(6),
+				None,
+				0,
+				2,
+				Accrual::Linear
+			),
+			Error::<Test, _>::InvalidStreamSchedule
+		);
+		assert_noop!(
+			Treasury::spend_stream(
+				RuntimeOrigin::signed(14),
+				Box::new(1),
+				100,
+				Box::new(6),
+				None,
+				10,
+				0,
+				Accrual::Installments
+			),
+			Error::<Test, _>::InvalidStreamSchedule
+		);
+
+		// a stream cannot be approved after it would have expired.
+		System::set_block_number(30);
+		assert_noop!(
+			Treasury::spend_stream(
+				RuntimeOrigin::signed(14),
+				Box::new(1),
+				100,
+				Box::new(6),
+				Some(1),
+				10,
+				2,
+				Accrual::Linear
+			),
+			Error::<Test, _>::SpendExpired
+		);
+	});
+}
+
+#[docify::export]
+#[test]
+fn stream_claim_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		// approve a stream of `100` coins of asset `1` to beneficiary `6`, accruing linearly over
+		// two periods of `10` blocks from now.
+		assert_ok!(Treasury::spend_stream(
+			RuntimeOrigin::signed(14),
+			Box::new(1),
+			100,
+			Box::new(6),
+			None,
+			10,
+			2,
+			Accrual::Linear
+		));
+		// nothing has accrued yet.
+		assert_noop!(
+			Treasury::claim_stream(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::NothingAccrued
+		);
+
+		// a quarter of the stream has accrued after `5` blocks.
+		System::set_block_number(6);
+		assert_ok!(Treasury::claim_stream(RuntimeOrigin::signed(1), 0));
+		assert_eq!(paid(6, 1), 25);
+		let payment_id = get_stream_payment_id(0).expect("no payment attempt");
+		System::assert_last_event(
+			Event::<Test, _>::StreamPaid { index: 0, amount: 25, payment_id }.into(),
+		);
+		// the payment must be checked before claiming again.
+		System::set_block_number(11);
+		assert_noop!(
+			Treasury::claim_stream(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::AlreadyAttempted
+		);
+		set_status(payment_id, PaymentStatus::Success);
+		let info = Treasury::check_stream_status(RuntimeOrigin::signed(1), 0).unwrap();
+		assert_eq!(info.pays_fee, Pays::No);
+
+		// the rest of the stream has accrued once it has ended.
+		System::set_block_number(21);
+		assert_ok!(Treasury::claim_stream(RuntimeOrigin::signed(1), 0));
+		assert_eq!(paid(6, 1), 100);
+		let payment_id = get_stream_payment_id(0).expect("no payment attempt");
+		set_status(payment_id, PaymentStatus::Success);
+		// the stream is removed once paid in full.
+		assert_ok!(Treasury::check_stream_status(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(Event::<Test, _>::StreamProcessed { index: 0 }.into());
+		assert_noop!(
+			Treasury::claim_stream(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::InvalidIndex
+		);
+	});
+}
+
+#[test]
+fn stream_installments_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		// a stream of four installments of `25` coins, one every `10` blocks, starting at block
+		// `2`.
+		assert_ok!(Treasury::spend_stream(
+			RuntimeOrigin::signed(14),
+			Box::new(1),
+			100,
+			Box::new(6),
+			Some(2),
+			10,
+			4,
+			Accrual::Installments
+		));
+		assert_noop!(
+			Treasury::claim_stream(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::EarlyPayout
+		);
+		System::set_block_number(11);
+		assert_noop!(
+			Treasury::claim_stream(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::NothingAccrued
+		);
+
+		// the first installment accrues at the end of the first period.
+		System::set_block_number(12);
+		assert_ok!(Treasury::claim_stream(RuntimeOrigin::signed(1), 0));
+		assert_eq!(paid(6, 1), 25);
+		assert_ok!(Treasury::check_stream_status(RuntimeOrigin::signed(1), 0));
+
+		// installments which have accrued meanwhile are paid at once.
+		System::set_block_number(37);
+		assert_ok!(Treasury::claim_stream(RuntimeOrigin::signed(1), 0));
+		assert_eq!(paid(6, 1), 75);
+		assert_ok!(Treasury::check_stream_status(RuntimeOrigin::signed(1), 0));
+
+		System::set_block_number(42);
+		assert_ok!(Treasury::claim_stream(RuntimeOrigin::signed(1), 0));
+		assert_eq!(paid(6, 1), 100);
+		assert_ok!(Treasury::check_stream_status(RuntimeOrigin::signed(1), 0));
+		assert_eq!(Streams::<Test, _>::get(0), None);
+	});
+}
+
+#[test]
+fn stream_claim_retry_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend_stream(
+			RuntimeOrigin::signed(14),
+			Box::new(1),
+			100,
+			Box::new(6),
+			None,
+			10,
+			2,
+			Accrual::Linear
+		));
+		System::set_block_number(11);
+		assert_ok!(Treasury::claim_stream(RuntimeOrigin::signed(1), 0));
+		assert_eq!(paid(6, 1), 50);
+		let payment_id = get_stream_payment_id(0).expect("no payment attempt");
+		// stream payment is failed
+		set_status(payment_id, PaymentStatus::Failure);
+		unpay(6, 1, 50);
+		assert_ok!(Treasury::check_stream_status(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(
+			Event::<Test, _>::StreamPaymentFailed { index: 0, payment_id }.into(),
+		);
+
+		// the failed amount can be claimed again, along with what has accrued meanwhile.
+		System::set_block_number(16);
+		assert_ok!(Treasury::claim_stream(RuntimeOrigin::signed(1), 0));
+		assert_eq!(paid(6, 1), 75);
+	});
+}
+
+#[test]
+fn stream_expires() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(<Test as Config>::PayoutPeriod::get(), 5);
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend_stream(
+			RuntimeOrigin::signed(14),
+			Box::new(1),
+			100,
+			Box::new(6),
+			None,
+			10,
+			2,
+			Accrual::Linear
+		));
+		assert_noop!(
+			Treasury::check_stream_status(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::NotAttempted
+		);
+
+		// the stream ends at block `21` and has to be claimed within the payout period.
+		System::set_block_number(26);
+		assert_noop!(
+			Treasury::claim_stream(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::SpendExpired
+		);
+		System::set_block_number(27);
+		let info = Treasury::check_stream_status(RuntimeOrigin::signed(1), 0).unwrap();
+		assert_eq!(info.pays_fee, Pays::No);
+		System::assert_last_event(Event::<Test, _>::StreamProcessed { index: 0 }.into());
+		assert_eq!(Streams::<Test, _>::get(0), None);
+	});
+}
+
+#[test]
+fn cancel_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend_stream(
+			RuntimeOrigin::signed(14),
+			Box::new(1),
+			100,
+			Box::new(6),
+			None,
+			10,
+			2,
+			Accrual::Linear
+		));
+		System::set_block_number(11);
+		assert_ok!(Treasury::claim_stream(RuntimeOrigin::signed(1), 0));
+		assert_eq!(paid(6, 1), 50);
+
+		assert_noop!(Treasury::cancel_stream(RuntimeOrigin::signed(1), 0), BadOrigin);
+		// stream cannot be cancelled while a payment is in progress.
+		assert_noop!(
+			Treasury::cancel_stream(RuntimeOrigin::root(), 0),
+			Error::<Test, _>::AlreadyAttempted
+		);
+		let payment_id = get_stream_payment_id(0).expect("no payment attempt");
+		set_status(payment_id, PaymentStatus::Success);
+		assert_ok!(Treasury::check_stream_status(RuntimeOrigin::signed(1), 0));
+
+		// the unpaid amount remains in the treasury.
+		assert_ok!(Treasury::cancel_stream(RuntimeOrigin::root(), 0));
+		System::assert_last_event(
+			Event::<Test, _>::StreamCancelled { index: 0, unpaid: 50 }.into(),
+		);
+		assert_eq!(Streams::<Test, _>::get(0), None);
+		System::set_block_number(21);
+		assert_noop!(
+			Treasury::claim_stream(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::InvalidIndex
+		);
+		assert_eq!(paid(6, 1), 50);
+	});
+}
+
 #[test]
 fn try_state_proposals_invariant_1_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1046,3 +1378,34 @@ fn multiple_spend_periods_work() {
 		assert_eq!(LastSpendPeriod::<Test>::get(), Some(8));
 	});
 }
+
+#[test]
+fn try_state_streams_invariants_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		use frame_support::pallet_prelude::DispatchError::Other;
+		assert_ok!(Treasury::spend_stream(
+			RuntimeOrigin::signed(14),
+			Box::new(1),
+			100,
+			Box::new(6),
+			None,
+			10,
+			2,
+			Accrual::Linear
+		));
+		assert_ok!(Treasury::do_try_state());
+
+		// Break invariant 3 by paying more than the amount of the stream
+		let mut stream = Streams::<Test>::get(0).unwrap();
+		stream.paid = 101;
+		Streams::<Test>::insert(0, stream);
+		assert_eq!(Treasury::do_try_state(), Err(Other("Stream cannot pay more than its amount.")));
+
+		// Break invariant 1 by decreasing `StreamCount`
+		StreamCount::<Test>::put(0);
+		assert_eq!(
+			Treasury::do_try_state(),
+			Err(Other("Actual number of streams exceeds `StreamCount`."))
+		);
+	});
+}
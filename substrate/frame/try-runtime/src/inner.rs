@@ -17,8 +17,25 @@
 
 //! Supporting types for try-runtime, testing and dry-running commands.
 
+use codec::{Decode, Encode};
 pub use frame_support::traits::{TryStateSelect, UpgradeCheckSelect};
 use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Block as BlockT, RuntimeDebug};
+
+/// The result of executing a block without any extrinsics, see
+/// [`TryRuntime::execute_empty_block`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct EmptyBlockOutcome<Header> {
+	/// The finalized header of the executed block.
+	///
+	/// Its hash can be used as parent hash of the next block.
+	pub header: Header,
+	/// The total weight that was consumed by the block.
+	pub weight: Weight,
+	/// Whether there are still multi-block migrations ongoing after this block.
+	pub migrations_ongoing: bool,
+}
 
 sp_api::decl_runtime_apis! {
 	/// Runtime api for testing the execution of a runtime upgrade.
@@ -46,5 +63,18 @@ sp_api::decl_runtime_apis! {
 			signature_check: bool,
 			try_state: TryStateSelect,
 		) -> Weight;
+
+		/// Execute a block without any extrinsics on top of the current state.
+		///
+		/// The `number`, `parent_hash` and pre-runtime digests of `header` are used to initialize
+		/// the block. This runs the same hooks as a normal block would, which means that ongoing
+		/// multi-block migrations are stepped with their realistic weight limit. Once these are
+		/// done, `on_poll` receives the remaining block weight instead.
+		///
+		/// This is used to step multi-block migrations to completion on real chain state.
+		#[api_version(2)]
+		fn execute_empty_block(
+			header: <Block as BlockT>::Header,
+		) -> EmptyBlockOutcome<<Block as BlockT>::Header>;
 	}
 }
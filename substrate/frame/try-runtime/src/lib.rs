@@ -24,3 +24,6 @@ pub mod inner;
 
 #[cfg(feature = "try-runtime")]
 pub use inner::*;
+
+#[cfg(all(feature = "try-runtime", feature = "std"))]
+pub mod multi_block;
@@ -0,0 +1,190 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Driver that steps multi-block migrations to completion on top of some externalities.
+//!
+//! The externalities are normally created from a chain snapshot with `remote-externalities`. The
+//! driver first applies the runtime upgrade through [`TryRuntime::on_runtime_upgrade`] and then
+//! executes empty blocks through [`TryRuntime::execute_empty_block`] until no more migrations
+//! are ongoing. Each block is executed while recording a storage proof, such that its proof size
+//! can be reported next to its weight.
+//!
+//! [`TryRuntime::on_runtime_upgrade`]: crate::TryRuntime::on_runtime_upgrade
+//! [`TryRuntime::execute_empty_block`]: crate::TryRuntime::execute_empty_block
+
+use crate::{runtime_decl_for_try_runtime::TryRuntimeV2, UpgradeCheckSelect};
+use codec::Encode;
+use frame_support::weights::Weight;
+use sp_runtime::{
+	generic::Digest,
+	traits::{Block as BlockT, HashingFor, Header as HeaderT, One},
+};
+use sp_state_machine::TestExternalities;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// A single empty block that was executed by [`run_multi_block_migrations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiBlockMigrationStep {
+	/// The total weight that was consumed by the block.
+	pub weight: Weight,
+	/// The size of the compact storage proof of the block.
+	pub proof_size: usize,
+}
+
+/// How the multi-block migrations ended, see [`MultiBlockMigrationReport::outcome`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultiBlockMigrationOutcome {
+	/// No more migrations are ongoing.
+	Completed,
+	/// The runtime upgrade or one of its steps panicked with the given message.
+	///
+	/// For `pallet-migrations` this contains the index of the migration and the
+	/// `SteppedMigrationError` that it returned.
+	Failed(String),
+	/// The migrations were still ongoing after the maximal number of steps.
+	Unfinished,
+}
+
+/// The report of [`run_multi_block_migrations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiBlockMigrationReport {
+	/// The weight that was consumed by the runtime upgrade itself.
+	pub upgrade_weight: Weight,
+	/// The maximal weight of a block, as configured in the runtime.
+	pub max_block_weight: Weight,
+	/// The empty blocks that were executed after the runtime upgrade.
+	pub steps: Vec<MultiBlockMigrationStep>,
+	/// How the migrations ended.
+	pub outcome: MultiBlockMigrationOutcome,
+}
+
+impl MultiBlockMigrationReport {
+	/// Whether all migrations completed.
+	pub fn is_completed(&self) -> bool {
+		self.outcome == MultiBlockMigrationOutcome::Completed
+	}
+
+	/// The step that consumed the most weight, if any.
+	pub fn max_weight(&self) -> Option<Weight> {
+		self.steps.iter().map(|s| s.weight).reduce(|a, b| a.max(b))
+	}
+
+	/// The largest proof size of all steps, if any.
+	pub fn max_proof_size(&self) -> Option<usize> {
+		self.steps.iter().map(|s| s.proof_size).max()
+	}
+}
+
+/// Apply the runtime upgrade of `Runtime` to `ext` and step all multi-block migrations.
+///
+/// The first executed block is the child of `parent`, which should be the header of the block
+/// that `ext` was created from. `pre_digest` is called with the parent of every block and returns
+/// the pre-runtime digests of the new block. This is where consensus specific digests, like the
+/// slot of Aura or BABE, should be provided.
+///
+/// Since the blocks contain no inherents, the `on_finalize` hooks of the pallets are not called.
+///
+/// At most `max_steps` blocks are executed. All changes are committed to `ext`, which therefore
+/// contains the migrated state afterwards.
+pub fn run_multi_block_migrations<Block, Runtime>(
+	ext: &mut TestExternalities<HashingFor<Block>>,
+	mut parent: Block::Header,
+	checks: UpgradeCheckSelect,
+	max_steps: u32,
+	mut pre_digest: impl FnMut(&Block::Header) -> Digest,
+) -> MultiBlockMigrationReport
+where
+	Block: BlockT,
+	Runtime: TryRuntimeV2<Block>,
+{
+	let mut report = MultiBlockMigrationReport {
+		upgrade_weight: Weight::zero(),
+		max_block_weight: Weight::zero(),
+		steps: Vec::new(),
+		outcome: MultiBlockMigrationOutcome::Unfinished,
+	};
+
+	let upgrade = catch_unwind(AssertUnwindSafe(|| {
+		ext.execute_with(|| <Runtime as TryRuntimeV2<Block>>::on_runtime_upgrade(checks))
+	}));
+	match upgrade {
+		Ok((upgrade_weight, max_block_weight)) => {
+			report.upgrade_weight = upgrade_weight;
+			report.max_block_weight = max_block_weight;
+		},
+		Err(err) => {
+			report.outcome = MultiBlockMigrationOutcome::Failed(panic_message(err));
+			return report
+		},
+	}
+	if let Err(err) = ext.commit_all() {
+		report.outcome = MultiBlockMigrationOutcome::Failed(err);
+		return report
+	}
+
+	for _ in 0..max_steps {
+		let header = Block::Header::new(
+			*parent.number() + One::one(),
+			Default::default(),
+			Default::default(),
+			parent.hash(),
+			pre_digest(&parent),
+		);
+
+		let root = *ext.backend.root();
+		let executed = catch_unwind(AssertUnwindSafe(|| {
+			ext.execute_and_prove(|| <Runtime as TryRuntimeV2<Block>>::execute_empty_block(header))
+		}));
+		let (outcome, proof) = match executed {
+			Ok(executed) => executed,
+			Err(err) => {
+				report.outcome = MultiBlockMigrationOutcome::Failed(panic_message(err));
+				return report
+			},
+		};
+		if let Err(err) = ext.commit_all() {
+			report.outcome = MultiBlockMigrationOutcome::Failed(err);
+			return report
+		}
+
+		let encoded_size = proof.encoded_size();
+		let proof_size =
+			proof.encoded_compact_size::<HashingFor<Block>>(root).unwrap_or(encoded_size);
+		report
+			.steps
+			.push(MultiBlockMigrationStep { weight: outcome.weight, proof_size });
+
+		if !outcome.migrations_ongoing {
+			report.outcome = MultiBlockMigrationOutcome::Completed;
+			return report
+		}
+		parent = outcome.header;
+	}
+
+	report
+}
+
+/// Extract the message of a panic payload.
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+	if let Some(message) = payload.downcast_ref::<&str>() {
+		message.to_string()
+	} else if let Some(message) = payload.downcast_ref::<String>() {
+		message.clone()
+	} else {
+		"Unknown panic".into()
+	}
+}
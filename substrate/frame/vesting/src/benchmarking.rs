@@ -435,6 +435,81 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn revoke_vesting_schedule(
+		l: Linear<0, { MaxLocksOf::<T>::get() - 1 }>,
+		s: Linear<1, { T::MAX_VESTING_SCHEDULES }>,
+	) -> Result<(), BenchmarkError> {
+		let origin =
+			T::RevokeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		let source = account::<T::AccountId>("source", 0, SEED);
+		T::Currency::make_free_balance_be(&source, BalanceOf::<T>::max_value());
+
+		let target = account::<T::AccountId>("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+
+		// Give target existing locks.
+		add_locks::<T>(&target, l as u8);
+		add_vesting_schedules::<T>(&target, s - 1)?;
+
+		// The last vesting schedule is the revocable one.
+		let min_transfer = T::MinVestedTransfer::get();
+		let locked = min_transfer.checked_mul(&20_u32.into()).unwrap();
+		let schedule = VestingInfo::new(locked, min_transfer, 1_u32.into()).revocable();
+		assert_ok!(Pallet::<T>::do_vested_transfer(&source, &target, schedule));
+
+		let beneficiary = account::<T::AccountId>("beneficiary", 0, SEED);
+		let beneficiary_lookup = T::Lookup::unlookup(beneficiary.clone());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, target_lookup, s - 1, beneficiary_lookup);
+
+		assert_eq!(
+			T::Currency::free_balance(&beneficiary),
+			locked,
+			"Unvested funds were not clawed back"
+		);
+		assert_eq!(
+			Vesting::<T>::decode_len(&target).unwrap_or_default(),
+			(s - 1) as usize,
+			"Schedule count should reduce by 1"
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn migration_v2_step() -> Result<(), BenchmarkError> {
+		let target = account::<T::AccountId>("target", 0, SEED);
+		let schedule = migrations::v1::VestingInfo {
+			locked: T::MinVestedTransfer::get(),
+			per_block: One::one(),
+			starting_block: One::one(),
+		};
+		migrations::v1::Vesting::<T>::insert(
+			&target,
+			BoundedVec::truncate_from(alloc::vec![schedule; T::MAX_VESTING_SCHEDULES as usize]),
+		);
+		StorageVersion::<T>::put(Releases::V1);
+
+		#[block]
+		{
+			migrations::v2::LazyMigrationV1ToV2::<T>::migrate_account(None);
+		}
+
+		assert_eq!(
+			Vesting::<T>::get(&target).unwrap().to_vec(),
+			alloc::vec![
+				VestingInfo::new(T::MinVestedTransfer::get(), One::one(), One::one());
+				T::MAX_VESTING_SCHEDULES as usize
+			],
+		);
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite! {
 		Pallet,
 		mock::ExtBuilder::default().existential_deposit(256).build(),
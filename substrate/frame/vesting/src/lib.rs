@@ -22,10 +22,11 @@
 //!
 //! ## Overview
 //!
-//! A simple pallet providing a means of placing a linear curve on an account's locked balance. This
-//! pallet ensures that there is a lock in place preventing the balance to drop below the *unvested*
-//! amount for any reason other than the ones specified in `UnvestedFundsAllowedWithdrawReasons`
-//! configuration value.
+//! A simple pallet providing a means of placing a vesting curve on an account's locked balance. A
+//! curve unlocks funds either linearly or in steps, optionally after a cliff. This pallet ensures
+//! that there is a lock in place preventing the balance to drop below the *unvested* amount for any
+//! reason other than the ones specified in `UnvestedFundsAllowedWithdrawReasons` configuration
+//! value.
 //!
 //! As the amount vested increases over time, the amount unvested reduces. However, locks remain in
 //! place and explicit action is needed on behalf of the user to ensure that the amount locked is
@@ -33,6 +34,9 @@
 //! either `vest` (in typical case where the sender is calling on their own behalf) or `vest_other`
 //! in case the sender is calling on another account's behalf.
 //!
+//! Schedules created through `force_vested_transfer` may be revocable, in which case `RevokeOrigin`
+//! can claw back the funds which haven't vested yet.
+//!
 //! ## Interface
 //!
 //! This pallet implements the `VestingSchedule` trait.
@@ -42,6 +46,7 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `revoke_vesting_schedule` - Claw back the unvested funds of a revocable schedule.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -66,8 +71,8 @@ use frame_support::{
 	ensure,
 	storage::bounded_vec::BoundedVec,
 	traits::{
-		Currency, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, VestedTransfer,
-		VestingSchedule, WithdrawReasons,
+		Currency, EnsureOrigin, ExistenceRequirement, Get, LockIdentifier, LockableCurrency,
+		VestedTransfer, VestingSchedule, WithdrawReasons,
 	},
 	weights::Weight,
 };
@@ -99,6 +104,7 @@ const VESTING_ID: LockIdentifier = *b"vesting ";
 pub enum Releases {
 	V0,
 	V1,
+	V2,
 }
 
 impl Default for Releases {
@@ -204,6 +210,9 @@ pub mod pallet {
 		///     parachain is lagging its block production to avoid clock skew.
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
 
+		/// The origin which may revoke revocable vesting schedules.
+		type RevokeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		/// Maximum number of vesting schedules an account may have at a given moment.
 		const MAX_VESTING_SCHEDULES: u32;
 	}
@@ -253,7 +262,7 @@ pub mod pallet {
 			use sp_runtime::traits::Saturating;
 
 			// Genesis uses the latest storage version.
-			StorageVersion::<T>::put(Releases::V1);
+			StorageVersion::<T>::put(Releases::V2);
 
 			// Generate initial vesting configuration
 			// * who - Account which we are generating vesting configuration for
@@ -293,6 +302,13 @@ pub mod pallet {
 		VestingUpdated { account: T::AccountId, unvested: BalanceOf<T> },
 		/// An \[account\] has become fully vested.
 		VestingCompleted { account: T::AccountId },
+		/// A vesting schedule has been revoked and its unvested funds clawed back.
+		VestingRevoked {
+			account: T::AccountId,
+			schedule_index: u32,
+			beneficiary: T::AccountId,
+			clawed_back: BalanceOf<T>,
+		},
 	}
 
 	/// Error for the vesting pallet.
@@ -309,6 +325,10 @@ pub mod pallet {
 		ScheduleIndexOutOfBounds,
 		/// Failed to create a new schedule because some parameter was invalid.
 		InvalidScheduleParams,
+		/// The vesting schedule can't be revoked.
+		NotRevocable,
+		/// Revocable vesting schedules can't be merged.
+		CannotMergeRevocable,
 	}
 
 	#[pallet::call]
@@ -357,7 +377,7 @@ pub mod pallet {
 		/// The dispatch origin for this call must be _Signed_.
 		///
 		/// - `target`: The account receiving the vested funds.
-		/// - `schedule`: The vesting schedule attached to the transfer.
+		/// - `schedule`: The vesting schedule attached to the transfer. Must not be revocable.
 		///
 		/// Emits `VestingCreated`.
 		///
@@ -376,6 +396,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let transactor = ensure_signed(origin)?;
 			let target = T::Lookup::lookup(target)?;
+			ensure!(!schedule.is_revocable(), Error::<T>::InvalidScheduleParams);
 			Self::do_vested_transfer(&transactor, &target, schedule)
 		}
 
@@ -385,7 +406,7 @@ pub mod pallet {
 		///
 		/// - `source`: The account whose funds should be transferred.
 		/// - `target`: The account that should be transferred the vested funds.
-		/// - `schedule`: The vesting schedule attached to the transfer.
+		/// - `schedule`: The vesting schedule attached to the transfer, which may be revocable.
 		///
 		/// Emits `VestingCreated`.
 		///
@@ -419,12 +440,16 @@ pub mod pallet {
 		/// NOTE: This will unlock all schedules through the current block prior to merging.
 		/// NOTE: If both schedules have ended by the current block, no new schedule will be created
 		/// and both will be removed.
+		/// NOTE: Revocable schedules can't be merged.
 		///
 		/// Merged schedule attributes:
 		/// - `starting_block`: `MAX(schedule1.starting_block, scheduled2.starting_block,
 		///   current_block)`.
-		/// - `ending_block`: `MAX(schedule1.ending_block, schedule2.ending_block)`.
+		/// - `ending_block`: `MAX(schedule1.ending_block, schedule2.ending_block)`, rounded up to a
+		///   whole number of periods.
 		/// - `locked`: `schedule1.locked_at(current_block) + schedule2.locked_at(current_block)`.
+		/// - `period`: `MAX(schedule1.period, schedule2.period)`.
+		/// - `cliff`: Lasts until both `schedule1` and `schedule2` are past their cliff.
 		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
@@ -488,6 +513,34 @@ pub mod pallet {
 			))
 			.into())
 		}
+
+		/// Revoke a revocable vesting schedule, clawing back the funds which haven't vested yet.
+		///
+		/// The dispatch origin for this call must be `RevokeOrigin`.
+		///
+		/// - `target`: An account that has a revocable vesting schedule.
+		/// - `schedule_index`: The index of the vesting schedule to revoke.
+		/// - `beneficiary`: The account receiving the unvested funds.
+		///
+		/// The funds vested so far stay with `target`. Fails if the unvested funds are subject to
+		/// other locks, e.g. when staked.
+		///
+		/// Emits `VestingRevoked`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			T::WeightInfo::revoke_vesting_schedule(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
+		)]
+		pub fn revoke_vesting_schedule(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			schedule_index: u32,
+			beneficiary: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			T::RevokeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(target)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			Self::do_revoke_vesting_schedule(&who, schedule_index, &beneficiary)
+		}
 	}
 }
 
@@ -535,15 +588,28 @@ impl<T: Config> Pallet<T> {
 
 		let ending_block = schedule1_ending_block.max(schedule2_ending_block);
 		let starting_block = now.max(schedule1.starting_block()).max(schedule2.starting_block());
-
-		let per_block = {
+		// Nothing is unlocked before both cliffs have passed, and funds are unlocked as rarely as
+		// in the schedule with the longest period.
+		let cliff = schedule1
+			.starting_block()
+			.saturating_add(schedule1.cliff())
+			.max(schedule2.starting_block().saturating_add(schedule2.cliff()))
+			.saturating_sub(starting_block);
+		let period = schedule1.period().max(schedule2.period());
+
+		let per_period = {
 			let duration = ending_block
 				.saturating_sub(T::BlockNumberToBalance::convert(starting_block))
 				.max(One::one());
-			(locked / duration).max(One::one())
+			let period = T::BlockNumberToBalance::convert(period);
+			// Round up so the merged schedule doesn't end before the given ones.
+			let period_count = duration / period +
+				if (duration % period).is_zero() { Zero::zero() } else { One::one() };
+			(locked / period_count.max(One::one())).max(One::one())
 		};
 
-		let schedule = VestingInfo::new(locked, per_block, starting_block);
+		let schedule =
+			VestingInfo::new_stepped(locked, per_period, period, starting_block).with_cliff(cliff);
 		debug_assert!(schedule.is_valid(), "merge_vesting_info schedule validation check failed");
 
 		Some(schedule)
@@ -562,29 +628,111 @@ impl<T: Config> Pallet<T> {
 		};
 
 		// Check we can add to this account prior to any storage writes.
-		Self::can_add_vesting_schedule(
-			target,
-			schedule.locked(),
-			schedule.per_block(),
-			schedule.starting_block(),
-		)?;
+		Self::can_add_vesting_info(target, &schedule)?;
 
 		T::Currency::transfer(source, target, schedule.locked(), ExistenceRequirement::AllowDeath)?;
 
 		// We can't let this fail because the currency transfer has already happened.
 		// Must be successful as it has been checked before.
 		// Better to return error on failure anyway.
-		let res = Self::add_vesting_schedule(
-			target,
-			schedule.locked(),
-			schedule.per_block(),
-			schedule.starting_block(),
-		);
+		let res = Self::add_vesting_info(target, schedule);
 		debug_assert!(res.is_ok(), "Failed to add a schedule when we had to succeed.");
 
 		Ok(())
 	}
 
+	/// Ensure we can call `add_vesting_info` without error. This should always
+	/// be called prior to `add_vesting_info`.
+	fn can_add_vesting_info(
+		who: &T::AccountId,
+		schedule: &VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+	) -> DispatchResult {
+		// Check for `per_period`, `period` or `locked` of 0.
+		if !schedule.is_valid() {
+			return Err(Error::<T>::InvalidScheduleParams.into())
+		}
+
+		ensure!(
+			(Vesting::<T>::decode_len(who).unwrap_or_default() as u32) < T::MAX_VESTING_SCHEDULES,
+			Error::<T>::AtMaxVestingSchedules
+		);
+
+		Ok(())
+	}
+
+	/// Add a vesting schedule to `who` and update their lock accordingly.
+	///
+	/// If the account has `MaxVestingSchedules`, an Error is returned and nothing
+	/// is updated.
+	///
+	/// NOTE: This doesn't alter the free balance of the account.
+	fn add_vesting_info(
+		who: &T::AccountId,
+		schedule: VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+	) -> DispatchResult {
+		// Check for `per_period`, `period` or `locked` of 0.
+		if !schedule.is_valid() {
+			return Err(Error::<T>::InvalidScheduleParams.into())
+		};
+
+		let mut schedules = Vesting::<T>::get(who).unwrap_or_default();
+
+		// NOTE: we must push the new schedule so that `exec_action`
+		// will give the correct new locked amount.
+		ensure!(schedules.try_push(schedule).is_ok(), Error::<T>::AtMaxVestingSchedules);
+
+		debug_assert!(schedules.len() > 0, "schedules cannot be empty after insertion");
+		let schedule_index = schedules.len() - 1;
+		Self::deposit_event(Event::<T>::VestingCreated {
+			account: who.clone(),
+			schedule_index: schedule_index as u32,
+		});
+
+		let (schedules, locked_now) =
+			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
+
+		Self::write_vesting(who, schedules)?;
+		Self::write_lock(who, locked_now);
+
+		Ok(())
+	}
+
+	/// Revoke the revocable schedule of `who` at `schedule_index`, transferring its unvested funds
+	/// to `beneficiary`.
+	fn do_revoke_vesting_schedule(
+		who: &T::AccountId,
+		schedule_index: u32,
+		beneficiary: &T::AccountId,
+	) -> DispatchResult {
+		let schedules = Vesting::<T>::get(who).ok_or(Error::<T>::NotVesting)?;
+		let schedule = *schedules
+			.get(schedule_index as usize)
+			.ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+		ensure!(schedule.is_revocable(), Error::<T>::NotRevocable);
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let unvested = schedule.locked_at::<T::BlockNumberToBalance>(now);
+
+		let remove_action = VestingAction::Remove { index: schedule_index as usize };
+		let (schedules, locked_now) = Self::exec_action(schedules.to_vec(), remove_action)?;
+
+		Self::write_vesting(who, schedules)?;
+		Self::write_lock(who, locked_now);
+
+		// Whatever the account managed to spend despite the lock, e.g. on fees, is lost.
+		let clawed_back = unvested.min(T::Currency::free_balance(who));
+		T::Currency::transfer(who, beneficiary, clawed_back, ExistenceRequirement::AllowDeath)?;
+
+		Self::deposit_event(Event::<T>::VestingRevoked {
+			account: who.clone(),
+			schedule_index,
+			beneficiary: beneficiary.clone(),
+			clawed_back,
+		});
+
+		Ok(())
+	}
+
 	/// Iterate through the schedules to track the current locked amount and
 	/// filter out completed and specified schedules.
 	///
@@ -676,6 +824,11 @@ impl<T: Config> Pallet<T> {
 				// any schedules that may be ending at this block.
 				let schedule1 = *schedules.get(idx1).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
 				let schedule2 = *schedules.get(idx2).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+				// Merging would change what can be clawed back from a revocable schedule.
+				ensure!(
+					!schedule1.is_revocable() && !schedule2.is_revocable(),
+					Error::<T>::CannotMergeRevocable
+				);
 
 				// The length of `schedules` decreases by 2 here since we filter out 2 schedules.
 				// Thus we know below that we can push the new merged schedule without error
@@ -751,32 +904,7 @@ where
 			return Ok(())
 		}
 
-		let vesting_schedule = VestingInfo::new(locked, per_block, starting_block);
-		// Check for `per_block` or `locked` of 0.
-		if !vesting_schedule.is_valid() {
-			return Err(Error::<T>::InvalidScheduleParams.into())
-		};
-
-		let mut schedules = Vesting::<T>::get(who).unwrap_or_default();
-
-		// NOTE: we must push the new schedule so that `exec_action`
-		// will give the correct new locked amount.
-		ensure!(schedules.try_push(vesting_schedule).is_ok(), Error::<T>::AtMaxVestingSchedules);
-
-		debug_assert!(schedules.len() > 0, "schedules cannot be empty after insertion");
-		let schedule_index = schedules.len() - 1;
-		Self::deposit_event(Event::<T>::VestingCreated {
-			account: who.clone(),
-			schedule_index: schedule_index as u32,
-		});
-
-		let (schedules, locked_now) =
-			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
-
-		Self::write_vesting(who, schedules)?;
-		Self::write_lock(who, locked_now);
-
-		Ok(())
+		Self::add_vesting_info(who, VestingInfo::new(locked, per_block, starting_block))
 	}
 
 	/// Ensure we can call `add_vesting_schedule` without error. This should always
@@ -787,17 +915,7 @@ where
 		per_block: BalanceOf<T>,
 		starting_block: BlockNumberFor<T>,
 	) -> DispatchResult {
-		// Check for `per_block` or `locked` of 0.
-		if !VestingInfo::new(locked, per_block, starting_block).is_valid() {
-			return Err(Error::<T>::InvalidScheduleParams.into())
-		}
-
-		ensure!(
-			(Vesting::<T>::decode_len(who).unwrap_or_default() as u32) < T::MAX_VESTING_SCHEDULES,
-			Error::<T>::AtMaxVestingSchedules
-		);
-
-		Ok(())
+		Self::can_add_vesting_info(who, &VestingInfo::new(locked, per_block, starting_block))
 	}
 
 	/// Remove a vesting schedule for a given account.
@@ -20,9 +20,33 @@
 use super::*;
 use alloc::vec;
 
+/// The identifier of this pallet's multi-block migrations.
+const PALLET_MIGRATIONS_ID: &[u8; 14] = b"pallet-vesting";
+
 // Migration from single schedule to multiple schedules.
 pub mod v1 {
 	use super::*;
+	use frame_support::{storage_alias, Blake2_128Concat};
+
+	/// A vesting schedule as stored up to `Releases::V1`, which can only unlock linearly.
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct VestingInfo<Balance, BlockNumber> {
+		/// Locked amount at genesis.
+		pub locked: Balance,
+		/// Amount that gets unlocked every block after `starting_block`.
+		pub per_block: Balance,
+		/// Starting block for unlocking(vesting).
+		pub starting_block: BlockNumber,
+	}
+
+	/// The `Vesting` storage as of `Releases::V1`.
+	#[storage_alias]
+	pub type Vesting<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>, MaxVestingSchedulesGet<T>>,
+	>;
 
 	#[cfg(feature = "try-runtime")]
 	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
@@ -78,7 +102,7 @@ pub mod v1 {
 			for s in schedules {
 				// It is ok if this does not pass, but ideally pre-existing schedules would pass
 				// this validation logic so we can be more confident about edge cases.
-				if !s.is_valid() {
+				if s.locked.is_zero() || s.per_block.is_zero() {
 					log::warn!(
 						target: "runtime::vesting",
 						"migration: A schedule does not pass new validation logic.",
@@ -94,3 +118,117 @@ pub mod v1 {
 		Ok(())
 	}
 }
+
+/// Migration from linear schedules to the generalized ones supporting cliffs, steps and
+/// revocation.
+pub mod v2 {
+	use super::*;
+	use frame_support::{
+		migrations::{MigrationId, SteppedMigration, SteppedMigrationError},
+		weights::WeightMeter,
+	};
+
+	/// Translates every linear schedule in `Vesting` into an equivalent `VestingInfo` without
+	/// cliff, unlocking every block and not revocable.
+	///
+	/// Accounts are migrated one by one, the cursor being the last migrated account. The pallet's
+	/// calls must not be used before the migration completes, which the multi-block migrations
+	/// framework ensures by suspending transactions.
+	pub struct LazyMigrationV1ToV2<T: Config>(PhantomData<T>);
+
+	impl<T: Config> LazyMigrationV1ToV2<T> {
+		/// Migrate the schedules of the account following `cursor`, returning that account or
+		/// `None` if there was none left to migrate.
+		pub(crate) fn migrate_account(cursor: Option<T::AccountId>) -> Option<T::AccountId> {
+			let mut iter = match cursor {
+				Some(last_key) =>
+					v1::Vesting::<T>::iter_from(v1::Vesting::<T>::hashed_key_for(last_key)),
+				None => v1::Vesting::<T>::iter(),
+			};
+
+			let (who, schedules) = iter.next()?;
+			let schedules = schedules
+				.into_iter()
+				.map(|s| VestingInfo::new(s.locked, s.per_block, s.starting_block))
+				.collect::<Vec<_>>();
+			// The number of schedules is unchanged, so they fit the same bound.
+			Vesting::<T>::insert(&who, BoundedVec::truncate_from(schedules));
+
+			Some(who)
+		}
+	}
+
+	impl<T: Config> SteppedMigration for LazyMigrationV1ToV2<T> {
+		type Cursor = T::AccountId;
+		type Identifier = MigrationId<14>;
+
+		fn id() -> Self::Identifier {
+			MigrationId { pallet_id: *PALLET_MIGRATIONS_ID, version_from: 1, version_to: 2 }
+		}
+
+		fn step(
+			mut cursor: Option<Self::Cursor>,
+			meter: &mut WeightMeter,
+		) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+			if StorageVersion::<T>::get() != Releases::V1 {
+				return Ok(None)
+			}
+
+			let required = T::WeightInfo::migration_v2_step();
+			if meter.remaining().any_lt(required) {
+				return Err(SteppedMigrationError::InsufficientWeight { required })
+			}
+
+			// Migrate as many accounts as possible within this step.
+			loop {
+				if meter.try_consume(required).is_err() {
+					break
+				}
+
+				cursor = Self::migrate_account(cursor);
+				if cursor.is_none() {
+					StorageVersion::<T>::put(Releases::V2);
+					log::info!(target: "runtime::vesting", "migration: all schedules migrated to v2");
+					break
+				}
+			}
+
+			Ok(cursor)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			ensure!(StorageVersion::<T>::get() == Releases::V1, "Wrong storage version.");
+			Ok(v1::Vesting::<T>::iter().collect::<Vec<_>>().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let prev: Vec<(
+				T::AccountId,
+				BoundedVec<
+					v1::VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+					MaxVestingSchedulesGet<T>,
+				>,
+			)> = Decode::decode(&mut &state[..])
+				.map_err(|_| "Failed to decode the state from pre-upgrade.")?;
+
+			ensure!(StorageVersion::<T>::get() == Releases::V2, "Wrong storage version.");
+			ensure!(
+				Vesting::<T>::iter().count() == prev.len(),
+				"The number of vesting accounts changed."
+			);
+			for (who, old) in prev {
+				let new = Vesting::<T>::get(&who).ok_or("Vesting schedules are missing.")?;
+				ensure!(
+					old.iter()
+						.map(|s| VestingInfo::new(s.locked, s.per_block, s.starting_block))
+						.eq(new.into_iter()),
+					"Vesting schedules changed."
+				);
+			}
+
+			Ok(())
+		}
+	}
+}
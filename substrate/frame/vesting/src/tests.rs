@@ -130,7 +130,7 @@ fn check_vesting_status_for_multi_schedule_account() {
 		// The most recently added schedule exists.
 		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched1]);
 		// sched1 has free funds at block #1, but nothing else.
-		assert_eq!(Vesting::vesting_balance(&2), Some(free_balance - sched1.per_block()));
+		assert_eq!(Vesting::vesting_balance(&2), Some(free_balance - sched1.per_period()));
 
 		// Add a 3rd schedule.
 		let sched2 = VestingInfo::new(
@@ -147,7 +147,7 @@ fn check_vesting_status_for_multi_schedule_account() {
 		// sched1 and sched2 are freeing funds at block #9.
 		assert_eq!(
 			Vesting::vesting_balance(&2),
-			Some(free_balance - sched1.per_block() * 9 - sched2.per_block() * 4)
+			Some(free_balance - sched1.per_period() * 9 - sched2.per_period() * 4)
 		);
 
 		System::set_block_number(20);
@@ -155,7 +155,9 @@ fn check_vesting_status_for_multi_schedule_account() {
 		assert_eq!(
 			Vesting::vesting_balance(&2),
 			Some(
-				free_balance - sched1.locked() - sched2.per_block() * 15 - sched0.per_block() * 10
+				free_balance -
+					sched1.locked() - sched2.per_period() * 15 -
+					sched0.per_period() * 10
 			)
 		);
 
@@ -163,7 +165,7 @@ fn check_vesting_status_for_multi_schedule_account() {
 		// At block #30 sched0 and sched1 are fully unlocked while sched2 is partially unlocked.
 		assert_eq!(
 			Vesting::vesting_balance(&2),
-			Some(free_balance - sched1.locked() - sched2.per_block() * 25 - sched0.locked())
+			Some(free_balance - sched1.locked() - sched2.per_period() * 25 - sched0.locked())
 		);
 
 		// At block #35 sched2 fully unlocks and thus all schedules funds are unlocked.
@@ -627,7 +629,7 @@ fn merge_schedules_that_have_not_started() {
 		// time as the original, just with double the amount.
 		let sched1 = VestingInfo::new(
 			sched0.locked() * 2,
-			sched0.per_block() * 2,
+			sched0.per_period() * 2,
 			10, // Starts at the block the schedules are merged/
 		);
 		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched1]);
@@ -670,8 +672,8 @@ fn merge_ongoing_schedules() {
 
 		// Merging schedules un-vests all pre-existing schedules prior to merging, which is
 		// reflected in account 2's updated usable balance.
-		let sched0_vested_now = sched0.per_block() * (cur_block - sched0.starting_block());
-		let sched1_vested_now = sched1.per_block() * (cur_block - sched1.starting_block());
+		let sched0_vested_now = sched0.per_period() * (cur_block - sched0.starting_block());
+		let sched1_vested_now = sched1.per_period() * (cur_block - sched1.starting_block());
 		assert_eq!(Balances::usable_balance(&2), sched0_vested_now + sched1_vested_now);
 
 		// The locked amount is the sum of what both schedules have locked at the current block.
@@ -806,7 +808,7 @@ fn merge_ongoing_and_yet_to_be_started_schedules() {
 		// After merging, the usable balance only changes by the amount sched0 vested since we
 		// last called `vest` (which is just 1 block). The usable balance is not affected by
 		// sched1 because it has not started yet.
-		usable_balance += sched0.per_block();
+		usable_balance += sched0.per_period();
 		assert_eq!(Balances::usable_balance(&2), usable_balance);
 
 		// The resulting schedule will have the later starting block of the two,
@@ -1066,9 +1068,9 @@ fn multiple_schedules_from_genesis_config_errors() {
 }
 
 #[test]
-fn build_genesis_has_storage_version_v1() {
+fn build_genesis_has_storage_version_v2() {
 	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
-		assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
+		assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
 	});
 }
 
@@ -1140,14 +1142,14 @@ fn vesting_info_ending_block_as_balance_works() {
 }
 
 #[test]
-fn per_block_works() {
+fn per_period_works() {
 	let per_block_0 = VestingInfo::new(256u32, 0u32, 10u32);
-	assert_eq!(per_block_0.per_block(), 1u32);
-	assert_eq!(per_block_0.raw_per_block(), 0u32);
+	assert_eq!(per_block_0.per_period(), 1u32);
+	assert_eq!(per_block_0.raw_per_period(), 0u32);
 
 	let per_block_1 = VestingInfo::new(256u32, 1u32, 10u32);
-	assert_eq!(per_block_1.per_block(), 1u32);
-	assert_eq!(per_block_1.raw_per_block(), 1u32);
+	assert_eq!(per_block_1.per_period(), 1u32);
+	assert_eq!(per_block_1.raw_per_period(), 1u32);
 }
 
 // When an accounts free balance + schedule.locked is less than ED, the vested transfer will fail.
@@ -1262,3 +1264,203 @@ fn vested_transfer_impl_works() {
 		);
 	});
 }
+
+#[test]
+fn vesting_info_cliff_and_steps_work() {
+	// Unlocks 250 every 10 blocks, starting at block 10.
+	let stepped = VestingInfo::new_stepped(1_000u32, 250u32, 10u32, 10u32);
+	assert_eq!(stepped.locked_at::<Identity>(19), 1_000);
+	assert_eq!(stepped.locked_at::<Identity>(20), 750);
+	assert_eq!(stepped.locked_at::<Identity>(29), 750);
+	assert_eq!(stepped.locked_at::<Identity>(30), 500);
+	assert_eq!(stepped.ending_block_as_balance::<Identity>(), 50);
+	assert_eq!(stepped.locked_at::<Identity>(50), 0);
+
+	// What vested during the cliff is unlocked at once when it ends.
+	let cliff = VestingInfo::new(1_000u32, 10u32, 10u32).with_cliff(30);
+	assert_eq!(cliff.locked_at::<Identity>(39), 1_000);
+	assert_eq!(cliff.locked_at::<Identity>(40), 700);
+	assert_eq!(cliff.locked_at::<Identity>(41), 690);
+	assert_eq!(cliff.ending_block_as_balance::<Identity>(), 110);
+
+	// A cliff outlasting the unlocks delays the end of the schedule.
+	let long_cliff = VestingInfo::new(100u32, 10u32, 10u32).with_cliff(20);
+	assert_eq!(long_cliff.ending_block_as_balance::<Identity>(), 30);
+	assert_eq!(long_cliff.locked_at::<Identity>(29), 100);
+	assert_eq!(long_cliff.locked_at::<Identity>(30), 0);
+
+	// `period` cannot be 0.
+	assert_eq!(VestingInfo::new_stepped(1_000u32, 250u32, 0u32, 10u32).is_valid(), false);
+	assert_eq!(VestingInfo::new_stepped(1_000u32, 250u32, 0u32, 10u32).period(), 1);
+}
+
+#[test]
+fn stepped_vested_transfer_with_cliff_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Unlocks ED every 10 blocks after a cliff of 20 blocks.
+		let schedule = VestingInfo::new_stepped(ED * 4, ED, 10, 1).with_cliff(20);
+		assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, schedule));
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![schedule]);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 4));
+
+		System::set_block_number(20);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 4));
+
+		// Both periods elapsed during the cliff are unlocked at once.
+		System::set_block_number(21);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 2));
+
+		System::set_block_number(30);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 2));
+
+		System::set_block_number(31);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED));
+		assert_ok!(Vesting::vest(Some(4).into()));
+		System::assert_last_event(Event::VestingUpdated { account: 4, unvested: ED }.into());
+
+		System::set_block_number(41);
+		assert_eq!(Vesting::vesting_balance(&4), Some(0));
+		vest_and_assert_no_vesting::<Test>(4);
+	});
+}
+
+#[test]
+fn revoke_vesting_schedule_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let revocable = VestingInfo::new(ED * 20, ED, 10).revocable();
+		// Revocable schedules can only be created by `force_vested_transfer`.
+		assert_noop!(
+			Vesting::vested_transfer(Some(13).into(), 4, revocable),
+			Error::<Test>::InvalidScheduleParams
+		);
+		assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 13, 4, revocable));
+
+		let irrevocable = VestingInfo::new(ED * 20, ED, 10);
+		assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, irrevocable));
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![revocable, irrevocable]);
+
+		assert_noop!(Vesting::revoke_vesting_schedule(Some(4).into(), 4, 0, 5), BadOrigin);
+		assert_noop!(
+			Vesting::revoke_vesting_schedule(RawOrigin::Root.into(), 5, 0, 5),
+			Error::<Test>::NotVesting
+		);
+		assert_noop!(
+			Vesting::revoke_vesting_schedule(RawOrigin::Root.into(), 4, 2, 5),
+			Error::<Test>::ScheduleIndexOutOfBounds
+		);
+		assert_noop!(
+			Vesting::revoke_vesting_schedule(RawOrigin::Root.into(), 4, 1, 5),
+			Error::<Test>::NotRevocable
+		);
+		// Revocable schedules can't be merged either.
+		assert_noop!(
+			Vesting::merge_schedules(Some(4).into(), 0, 1),
+			Error::<Test>::CannotMergeRevocable
+		);
+
+		// Half way through, the unvested half is clawed back.
+		System::set_block_number(20);
+		let free_balance = Balances::free_balance(&4);
+		assert_ok!(Vesting::revoke_vesting_schedule(RawOrigin::Root.into(), 4, 0, 5));
+		System::assert_last_event(
+			Event::VestingRevoked {
+				account: 4,
+				schedule_index: 0,
+				beneficiary: 5,
+				clawed_back: ED * 10,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&5), ED * 10);
+		assert_eq!(Balances::free_balance(&4), free_balance - ED * 10);
+
+		// The other schedule is left untouched and the lock reflects it only.
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![irrevocable]);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 10));
+		assert_ok!(Balances::transfer_allow_death(Some(4).into(), 3, free_balance - ED * 20));
+	});
+}
+
+#[test]
+fn merge_schedules_with_cliff_and_steps_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Ends at block 20.
+		let sched0 = VestingInfo::new(ED * 10, ED, 10);
+		// Unlocks every 5 blocks after a cliff ending at block 22, and ends at block 30.
+		let sched1 = VestingInfo::new_stepped(ED * 12, ED * 3, 5, 10).with_cliff(12);
+		assert_eq!(sched1.ending_block_as_balance::<Identity>(), 30);
+		assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, sched0));
+		assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, sched1));
+
+		assert_ok!(Vesting::merge_schedules(Some(4).into(), 0, 1));
+
+		// The merged schedule keeps the longest period and cliff, over the 4 periods from block 10
+		// to block 30.
+		let merged = VestingInfo::new_stepped(ED * 22, ED * 22 / 4, 5, 10).with_cliff(12);
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![merged]);
+		assert_eq!(merged.ending_block_as_balance::<Identity>(), 30);
+
+		System::set_block_number(21);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 22));
+		System::set_block_number(22);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 22 - ED * 22 / 4 * 2));
+	});
+}
+
+#[test]
+fn migration_v2_works() {
+	use frame_support::{migrations::SteppedMigration, weights::WeightMeter};
+	use migrations::v2::LazyMigrationV1ToV2;
+
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Put the linear schedules back into the v1 format.
+		let v1_schedule = |locked, per_block, starting_block| migrations::v1::VestingInfo {
+			locked,
+			per_block,
+			starting_block,
+		};
+		let _ = VestingStorage::<Test>::clear(u32::MAX, None);
+		migrations::v1::Vesting::<Test>::insert(
+			1,
+			BoundedVec::truncate_from(vec![v1_schedule(ED * 5, 128, 10)]),
+		);
+		migrations::v1::Vesting::<Test>::insert(
+			2,
+			BoundedVec::truncate_from(vec![v1_schedule(ED, 64, 0), v1_schedule(ED * 2, 64, 5)]),
+		);
+		StorageVersion::<Test>::put(Releases::V1);
+
+		// Not enough weight to migrate a single account.
+		let mut meter = WeightMeter::with_limit(Weight::zero());
+		assert!(LazyMigrationV1ToV2::<Test>::step(None, &mut meter).is_err());
+
+		// Migrate a single account per step.
+		let mut cursor = None;
+		let mut steps = 0;
+		loop {
+			let mut meter = WeightMeter::with_limit(<() as WeightInfo>::migration_v2_step());
+			cursor = LazyMigrationV1ToV2::<Test>::step(cursor, &mut meter).unwrap();
+			steps += 1;
+			if cursor.is_none() {
+				break
+			}
+		}
+		// One step per account, plus the one finding there are none left.
+		assert_eq!(steps, 3);
+
+		assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
+		assert_eq!(
+			VestingStorage::<Test>::get(1).unwrap(),
+			vec![VestingInfo::new(ED * 5, 128, 10)]
+		);
+		assert_eq!(
+			VestingStorage::<Test>::get(2).unwrap(),
+			vec![VestingInfo::new(ED, 64, 0), VestingInfo::new(ED * 2, 64, 5)]
+		);
+
+		// The migration doesn't run again.
+		let mut meter = WeightMeter::new();
+		assert!(matches!(LazyMigrationV1ToV2::<Test>::step(None, &mut meter), Ok(None)));
+		assert_eq!(meter.consumed(), Weight::zero());
+	});
+}
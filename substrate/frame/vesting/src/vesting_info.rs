@@ -20,6 +20,10 @@
 use super::*;
 
 /// Struct to encode the vesting schedule of an individual account.
+///
+/// A schedule unlocks `per_period` every `period` blocks after `starting_block`, which covers
+/// linear unlocks (a `period` of one block) as well as step releases. Nothing is unlocked before
+/// the `cliff` has passed, at which point everything that vested meanwhile unlocks at once.
 #[derive(
 	Encode,
 	Decode,
@@ -35,10 +39,16 @@ use super::*;
 pub struct VestingInfo<Balance, BlockNumber> {
 	/// Locked amount at genesis.
 	locked: Balance,
-	/// Amount that gets unlocked every block after `starting_block`.
-	per_block: Balance,
+	/// Amount that gets unlocked every `period` blocks after `starting_block`.
+	per_period: Balance,
 	/// Starting block for unlocking(vesting).
 	starting_block: BlockNumber,
+	/// Number of blocks after `starting_block` during which nothing is unlocked.
+	cliff: BlockNumber,
+	/// Number of blocks between two unlocks.
+	period: BlockNumber,
+	/// Whether the unvested funds can be clawed back by `RevokeOrigin`.
+	revocable: bool,
 }
 
 impl<Balance, BlockNumber> VestingInfo<Balance, BlockNumber>
@@ -46,19 +56,46 @@ where
 	Balance: AtLeast32BitUnsigned + Copy,
 	BlockNumber: AtLeast32BitUnsigned + Copy + Bounded,
 {
-	/// Instantiate a new `VestingInfo`.
+	/// Instantiate a new `VestingInfo` unlocking linearly, every block.
 	pub fn new(
 		locked: Balance,
 		per_block: Balance,
 		starting_block: BlockNumber,
 	) -> VestingInfo<Balance, BlockNumber> {
-		VestingInfo { locked, per_block, starting_block }
+		VestingInfo {
+			locked,
+			per_period: per_block,
+			starting_block,
+			cliff: Zero::zero(),
+			period: One::one(),
+			revocable: false,
+		}
+	}
+
+	/// Instantiate a new `VestingInfo` unlocking `per_period` every `period` blocks.
+	pub fn new_stepped(
+		locked: Balance,
+		per_period: Balance,
+		period: BlockNumber,
+		starting_block: BlockNumber,
+	) -> VestingInfo<Balance, BlockNumber> {
+		VestingInfo { period, ..Self::new(locked, per_period, starting_block) }
+	}
+
+	/// Delay any unlock until `cliff` blocks after `starting_block`.
+	pub fn with_cliff(self, cliff: BlockNumber) -> VestingInfo<Balance, BlockNumber> {
+		VestingInfo { cliff, ..self }
+	}
+
+	/// Allow `RevokeOrigin` to claw back the funds which haven't vested yet.
+	pub fn revocable(self) -> VestingInfo<Balance, BlockNumber> {
+		VestingInfo { revocable: true, ..self }
 	}
 
 	/// Validate parameters for `VestingInfo`. Note that this does not check
 	/// against `MinVestedTransfer`.
 	pub fn is_valid(&self) -> bool {
-		!self.locked.is_zero() && !self.raw_per_block().is_zero()
+		!self.locked.is_zero() && !self.raw_per_period().is_zero() && !self.period.is_zero()
 	}
 
 	/// Locked amount at schedule creation.
@@ -66,17 +103,18 @@ where
 		self.locked
 	}
 
-	/// Amount that gets unlocked every block after `starting_block`. Corrects for `per_block` of 0.
-	/// We don't let `per_block` be less than 1, or else the vesting will never end.
-	/// This should be used whenever accessing `per_block` unless explicitly checking for 0 values.
-	pub fn per_block(&self) -> Balance {
-		self.per_block.max(One::one())
+	/// Amount that gets unlocked every `period` blocks after `starting_block`. Corrects for
+	/// `per_period` of 0. We don't let `per_period` be less than 1, or else the vesting will never
+	/// end. This should be used whenever accessing `per_period` unless explicitly checking for 0
+	/// values.
+	pub fn per_period(&self) -> Balance {
+		self.per_period.max(One::one())
 	}
 
-	/// Get the unmodified `per_block`. Generally should not be used, but is useful for
-	/// validating `per_block`.
-	pub(crate) fn raw_per_block(&self) -> Balance {
-		self.per_block
+	/// Get the unmodified `per_period`. Generally should not be used, but is useful for
+	/// validating `per_period`.
+	pub(crate) fn raw_per_period(&self) -> Balance {
+		self.per_period
 	}
 
 	/// Starting block for unlocking(vesting).
@@ -84,18 +122,37 @@ where
 		self.starting_block
 	}
 
+	/// Number of blocks after `starting_block` during which nothing is unlocked.
+	pub fn cliff(&self) -> BlockNumber {
+		self.cliff
+	}
+
+	/// Number of blocks between two unlocks, corrected for a `period` of 0.
+	pub fn period(&self) -> BlockNumber {
+		self.period.max(One::one())
+	}
+
+	/// Whether the unvested funds can be clawed back by `RevokeOrigin`.
+	pub fn is_revocable(&self) -> bool {
+		self.revocable
+	}
+
 	/// Amount locked at block `n`.
 	pub fn locked_at<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
 		&self,
 		n: BlockNumber,
 	) -> Balance {
-		// Number of blocks that count toward vesting;
+		// Nothing is unlocked until the cliff has passed.
+		if n < self.starting_block.saturating_add(self.cliff) {
+			return self.locked
+		}
+		// Number of whole periods that count toward vesting;
 		// saturating to 0 when n < starting_block.
-		let vested_block_count = n.saturating_sub(self.starting_block);
-		let vested_block_count = BlockNumberToBalance::convert(vested_block_count);
+		let vested_period_count = n.saturating_sub(self.starting_block) / self.period();
+		let vested_period_count = BlockNumberToBalance::convert(vested_period_count);
 		// Return amount that is still locked in vesting.
-		vested_block_count
-			.checked_mul(&self.per_block()) // `per_block` accessor guarantees at least 1.
+		vested_period_count
+			.checked_mul(&self.per_period()) // `per_period` accessor guarantees at least 1.
 			.map(|to_unlock| self.locked.saturating_sub(to_unlock))
 			.unwrap_or(Zero::zero())
 	}
@@ -105,20 +162,24 @@ where
 		&self,
 	) -> Balance {
 		let starting_block = BlockNumberToBalance::convert(self.starting_block);
-		let duration = if self.per_block() >= self.locked {
-			// If `per_block` is bigger than `locked`, the schedule will end
-			// the block after starting.
+		let period_count = if self.per_period() >= self.locked {
+			// If `per_period` is bigger than `locked`, the schedule will end
+			// one period after starting.
 			One::one()
 		} else {
-			self.locked / self.per_block() +
-				if (self.locked % self.per_block()).is_zero() {
+			self.locked / self.per_period() +
+				if (self.locked % self.per_period()).is_zero() {
 					Zero::zero()
 				} else {
-					// `per_block` does not perfectly divide `locked`, so we need an extra block to
-					// unlock some amount less than `per_block`.
+					// `per_period` does not perfectly divide `locked`, so we need an extra period
+					// to unlock some amount less than `per_period`.
 					One::one()
 				}
 		};
+		let duration = period_count
+			.saturating_mul(BlockNumberToBalance::convert(self.period()))
+			// A cliff outlasting the unlocks delays the end of the schedule.
+			.max(BlockNumberToBalance::convert(self.cliff));
 
 		starting_block.saturating_add(duration)
 	}
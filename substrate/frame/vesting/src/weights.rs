@@ -81,13 +81,15 @@ pub trait WeightInfo {
 	fn not_unlocking_merge_schedules(l: u32, s: u32, ) -> Weight;
 	fn unlocking_merge_schedules(l: u32, s: u32, ) -> Weight;
 	fn force_remove_vesting_schedule(l: u32, s: u32, ) -> Weight;
+	fn revoke_vesting_schedule(l: u32, s: u32, ) -> Weight;
+	fn migration_v2_step() -> Weight;
 }
 
 /// Weights for `pallet_vesting` using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -96,10 +98,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// The range of component `s` is `[1, 28]`.
 	fn vest_locked(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `123 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `123 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 29_108_000 picoseconds.
-		Weight::from_parts(28_662_441, 4764)
+		Weight::from_parts(28_662_441, 4774)
 			// Standard Error: 954
 			.saturating_add(Weight::from_parts(30_514, 0).saturating_mul(l.into()))
 			// Standard Error: 1_698
@@ -108,7 +110,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -117,10 +119,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// The range of component `s` is `[1, 28]`.
 	fn vest_unlocked(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `123 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `123 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 30_994_000 picoseconds.
-		Weight::from_parts(30_464_494, 4764)
+		Weight::from_parts(30_464_494, 4774)
 			// Standard Error: 1_481
 			.saturating_add(Weight::from_parts(28_285, 0).saturating_mul(l.into()))
 			// Standard Error: 2_636
@@ -129,7 +131,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -140,10 +142,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// The range of component `s` is `[1, 28]`.
 	fn vest_other_locked(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `225 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `225 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 31_696_000 picoseconds.
-		Weight::from_parts(31_191_832, 4764)
+		Weight::from_parts(31_191_832, 4774)
 			// Standard Error: 1_199
 			.saturating_add(Weight::from_parts(29_734, 0).saturating_mul(l.into()))
 			// Standard Error: 2_133
@@ -152,7 +154,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -163,10 +165,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// The range of component `s` is `[1, 28]`.
 	fn vest_other_unlocked(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `225 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `225 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 33_388_000 picoseconds.
-		Weight::from_parts(32_877_344, 4764)
+		Weight::from_parts(32_877_344, 4774)
 			// Standard Error: 1_245
 			.saturating_add(Weight::from_parts(34_308, 0).saturating_mul(l.into()))
 			// Standard Error: 2_216
@@ -175,7 +177,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
@@ -186,10 +188,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// The range of component `s` is `[0, 27]`.
 	fn vested_transfer(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `225 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `225 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 67_470_000 picoseconds.
-		Weight::from_parts(68_202_160, 4764)
+		Weight::from_parts(68_202_160, 4774)
 			// Standard Error: 2_799
 			.saturating_add(Weight::from_parts(48_357, 0).saturating_mul(l.into()))
 			// Standard Error: 4_979
@@ -198,7 +200,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
@@ -209,7 +211,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// The range of component `s` is `[0, 27]`.
 	fn force_vested_transfer(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `328 + l * (25 ±0) + s * (36 ±0)`
+		//  Measured:  `328 + l * (25 ±0) + s * (45 ±0)`
 		//  Estimated: `6196`
 		// Minimum execution time: 69_510_000 picoseconds.
 		Weight::from_parts(69_862_164, 6196)
@@ -221,7 +223,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -230,10 +232,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// The range of component `s` is `[2, 28]`.
 	fn not_unlocking_merge_schedules(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `123 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `123 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 29_865_000 picoseconds.
-		Weight::from_parts(29_471_812, 4764)
+		Weight::from_parts(29_471_812, 4774)
 			// Standard Error: 1_177
 			.saturating_add(Weight::from_parts(30_158, 0).saturating_mul(l.into()))
 			// Standard Error: 2_174
@@ -242,7 +244,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -251,10 +253,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// The range of component `s` is `[2, 28]`.
 	fn unlocking_merge_schedules(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `123 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `123 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 32_137_000 picoseconds.
-		Weight::from_parts(31_237_719, 4764)
+		Weight::from_parts(31_237_719, 4774)
 			// Standard Error: 1_135
 			.saturating_add(Weight::from_parts(35_827, 0).saturating_mul(l.into()))
 			// Standard Error: 2_097
@@ -263,7 +265,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -274,10 +276,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// The range of component `s` is `[2, 28]`.
 	fn force_remove_vesting_schedule(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `225 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `225 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 35_493_000 picoseconds.
-		Weight::from_parts(34_502_101, 4764)
+		Weight::from_parts(34_502_101, 4774)
 			// Standard Error: 1_833
 			.saturating_add(Weight::from_parts(40_786, 0).saturating_mul(l.into()))
 			// Standard Error: 3_385
@@ -285,12 +287,46 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	/// Storage: `Vesting::Vesting` (r:1 w:1)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Freezes` (r:1 w:0)
+	/// Proof: `Balances::Freezes` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[0, 49]`.
+	/// The range of component `s` is `[1, 28]`.
+	fn revoke_vesting_schedule(l: u32, s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `557 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `6196`
+		// Minimum execution time: 71_812_000 picoseconds.
+		Weight::from_parts(70_394_551, 6196)
+			// Standard Error: 2_217
+			.saturating_add(Weight::from_parts(38_412, 0).saturating_mul(l.into()))
+			// Standard Error: 4_096
+			.saturating_add(Weight::from_parts(95_307, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Vesting::Vesting` (r:2 w:1)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
+	fn migration_v2_step() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1291`
+		//  Estimated: `8558`
+		// Minimum execution time: 24_106_000 picoseconds.
+		Weight::from_parts(25_018_000, 8558)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests.
 impl WeightInfo for () {
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -299,10 +335,10 @@ impl WeightInfo for () {
 	/// The range of component `s` is `[1, 28]`.
 	fn vest_locked(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `123 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `123 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 29_108_000 picoseconds.
-		Weight::from_parts(28_662_441, 4764)
+		Weight::from_parts(28_662_441, 4774)
 			// Standard Error: 954
 			.saturating_add(Weight::from_parts(30_514, 0).saturating_mul(l.into()))
 			// Standard Error: 1_698
@@ -311,7 +347,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -320,10 +356,10 @@ impl WeightInfo for () {
 	/// The range of component `s` is `[1, 28]`.
 	fn vest_unlocked(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `123 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `123 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 30_994_000 picoseconds.
-		Weight::from_parts(30_464_494, 4764)
+		Weight::from_parts(30_464_494, 4774)
 			// Standard Error: 1_481
 			.saturating_add(Weight::from_parts(28_285, 0).saturating_mul(l.into()))
 			// Standard Error: 2_636
@@ -332,7 +368,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -343,10 +379,10 @@ impl WeightInfo for () {
 	/// The range of component `s` is `[1, 28]`.
 	fn vest_other_locked(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `225 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `225 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 31_696_000 picoseconds.
-		Weight::from_parts(31_191_832, 4764)
+		Weight::from_parts(31_191_832, 4774)
 			// Standard Error: 1_199
 			.saturating_add(Weight::from_parts(29_734, 0).saturating_mul(l.into()))
 			// Standard Error: 2_133
@@ -355,7 +391,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -366,10 +402,10 @@ impl WeightInfo for () {
 	/// The range of component `s` is `[1, 28]`.
 	fn vest_other_unlocked(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `225 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `225 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 33_388_000 picoseconds.
-		Weight::from_parts(32_877_344, 4764)
+		Weight::from_parts(32_877_344, 4774)
 			// Standard Error: 1_245
 			.saturating_add(Weight::from_parts(34_308, 0).saturating_mul(l.into()))
 			// Standard Error: 2_216
@@ -378,7 +414,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
@@ -389,10 +425,10 @@ impl WeightInfo for () {
 	/// The range of component `s` is `[0, 27]`.
 	fn vested_transfer(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `225 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `225 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 67_470_000 picoseconds.
-		Weight::from_parts(68_202_160, 4764)
+		Weight::from_parts(68_202_160, 4774)
 			// Standard Error: 2_799
 			.saturating_add(Weight::from_parts(48_357, 0).saturating_mul(l.into()))
 			// Standard Error: 4_979
@@ -401,7 +437,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
@@ -412,7 +448,7 @@ impl WeightInfo for () {
 	/// The range of component `s` is `[0, 27]`.
 	fn force_vested_transfer(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `328 + l * (25 ±0) + s * (36 ±0)`
+		//  Measured:  `328 + l * (25 ±0) + s * (45 ±0)`
 		//  Estimated: `6196`
 		// Minimum execution time: 69_510_000 picoseconds.
 		Weight::from_parts(69_862_164, 6196)
@@ -424,7 +460,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -433,10 +469,10 @@ impl WeightInfo for () {
 	/// The range of component `s` is `[2, 28]`.
 	fn not_unlocking_merge_schedules(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `123 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `123 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 29_865_000 picoseconds.
-		Weight::from_parts(29_471_812, 4764)
+		Weight::from_parts(29_471_812, 4774)
 			// Standard Error: 1_177
 			.saturating_add(Weight::from_parts(30_158, 0).saturating_mul(l.into()))
 			// Standard Error: 2_174
@@ -445,7 +481,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -454,10 +490,10 @@ impl WeightInfo for () {
 	/// The range of component `s` is `[2, 28]`.
 	fn unlocking_merge_schedules(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `123 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `123 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 32_137_000 picoseconds.
-		Weight::from_parts(31_237_719, 4764)
+		Weight::from_parts(31_237_719, 4774)
 			// Standard Error: 1_135
 			.saturating_add(Weight::from_parts(35_827, 0).saturating_mul(l.into()))
 			// Standard Error: 2_097
@@ -466,7 +502,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Vesting::Vesting` (r:1 w:1)
-	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Locks` (r:1 w:1)
 	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Freezes` (r:1 w:0)
@@ -477,10 +513,10 @@ impl WeightInfo for () {
 	/// The range of component `s` is `[2, 28]`.
 	fn force_remove_vesting_schedule(l: u32, s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `225 + l * (25 ±0) + s * (36 ±0)`
-		//  Estimated: `4764`
+		//  Measured:  `225 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `4774`
 		// Minimum execution time: 35_493_000 picoseconds.
-		Weight::from_parts(34_502_101, 4764)
+		Weight::from_parts(34_502_101, 4774)
 			// Standard Error: 1_833
 			.saturating_add(Weight::from_parts(40_786, 0).saturating_mul(l.into()))
 			// Standard Error: 3_385
@@ -488,4 +524,38 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	/// Storage: `Vesting::Vesting` (r:1 w:1)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Freezes` (r:1 w:0)
+	/// Proof: `Balances::Freezes` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[0, 49]`.
+	/// The range of component `s` is `[1, 28]`.
+	fn revoke_vesting_schedule(l: u32, s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `557 + l * (25 ±0) + s * (45 ±0)`
+		//  Estimated: `6196`
+		// Minimum execution time: 71_812_000 picoseconds.
+		Weight::from_parts(70_394_551, 6196)
+			// Standard Error: 2_217
+			.saturating_add(Weight::from_parts(38_412, 0).saturating_mul(l.into()))
+			// Standard Error: 4_096
+			.saturating_add(Weight::from_parts(95_307, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Vesting::Vesting` (r:2 w:1)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1309), added: 3784, mode: `MaxEncodedLen`)
+	fn migration_v2_step() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1291`
+		//  Estimated: `8558`
+		// Minimum execution time: 24_106_000 picoseconds.
+		Weight::from_parts(25_018_000, 8558)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -128,6 +128,12 @@ pub enum HttpError {
 	/// The ID of the request is invalid in this context.
 	#[codec(index = 3)]
 	Invalid = 2_isize,
+	/// The request was denied by the HTTP policy of the node.
+	///
+	/// Only reported by the second version of the HTTP host functions. Runtimes that use the
+	/// first version get an [`HttpError::IoError`] instead, as they cannot decode this variant.
+	#[codec(index = 4)]
+	Denied = 3_isize,
 }
 
 impl TryFrom<u32> for HttpError {
@@ -138,6 +144,7 @@ impl TryFrom<u32> for HttpError {
 			e if e == HttpError::DeadlineReached as u8 as u32 => Ok(HttpError::DeadlineReached),
 			e if e == HttpError::IoError as u8 as u32 => Ok(HttpError::IoError),
 			e if e == HttpError::Invalid as u8 as u32 => Ok(HttpError::Invalid),
+			e if e == HttpError::Denied as u8 as u32 => Ok(HttpError::Denied),
 			_ => Err(()),
 		}
 	}
@@ -792,6 +799,19 @@ mod tests {
 		assert_eq!(t.diff(&Timestamp(3)), Duration(2));
 	}
 
+	#[test]
+	fn http_error_conversions() {
+		for error in
+			[HttpError::DeadlineReached, HttpError::IoError, HttpError::Invalid, HttpError::Denied]
+		{
+			assert_eq!(HttpError::try_from(u32::from(error)), Ok(error));
+			assert_eq!(HttpError::decode(&mut &error.encode()[..]), Ok(error));
+		}
+		assert_eq!(u32::from(HttpError::Denied), 3);
+		assert_eq!(HttpError::Denied.encode(), vec![4]);
+		assert_eq!(HttpError::try_from(4), Err(()));
+	}
+
 	#[test]
 	fn capabilities() {
 		let none = Capabilities::empty();
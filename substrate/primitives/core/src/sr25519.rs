@@ -316,16 +316,60 @@ pub mod vrf {
 
 	/// Transcript ready to be used for VRF related operations.
 	#[derive(Clone)]
-	pub struct VrfTranscript(pub merlin::Transcript);
+	pub struct VrfTranscript {
+		transcript: merlin::Transcript,
+		parts: Option<VrfTranscriptParts>,
+	}
+
+	/// The label and the `(domain, message)` elements a [`VrfTranscript`] was built from.
+	#[derive(Clone, Debug, PartialEq, Eq)]
+	pub struct VrfTranscriptParts {
+		/// The label of the transcript.
+		pub label: &'static [u8],
+		/// The `(domain, message)` elements appended to the transcript.
+		pub data: Vec<(&'static [u8], Vec<u8>)>,
+	}
 
 	impl VrfTranscript {
 		/// Build a new transcript instance.
 		///
 		/// Each `data` element is a tuple `(domain, message)` used to build the transcript.
 		pub fn new(label: &'static [u8], data: &[(&'static [u8], &[u8])]) -> Self {
-			let mut transcript = merlin::Transcript::new(label);
-			data.iter().for_each(|(l, b)| transcript.append_message(l, b));
-			VrfTranscript(transcript)
+			Self::from_parts(VrfTranscriptParts {
+				label,
+				data: data.iter().map(|(domain, message)| (*domain, message.to_vec())).collect(),
+			})
+		}
+
+		/// Build a transcript instance from the parts of another one.
+		pub fn from_parts(parts: VrfTranscriptParts) -> Self {
+			let mut transcript = merlin::Transcript::new(parts.label);
+			parts.data.iter().for_each(|(l, b)| transcript.append_message(l, b));
+			VrfTranscript { transcript, parts: Some(parts) }
+		}
+
+		/// Wrap a transcript built by other means.
+		///
+		/// Its parts are unknown, so it cannot be built again elsewhere.
+		pub fn from_transcript(transcript: merlin::Transcript) -> Self {
+			VrfTranscript { transcript, parts: None }
+		}
+
+		/// The parts the transcript was built from, unless built with [`Self::from_transcript`].
+		///
+		/// Allows to build the same transcript elsewhere, e.g. in a remote signer.
+		pub fn parts(&self) -> Option<&VrfTranscriptParts> {
+			self.parts.as_ref()
+		}
+
+		/// The underlying merlin transcript.
+		pub fn transcript(&self) -> &merlin::Transcript {
+			&self.transcript
+		}
+
+		/// Take the underlying merlin transcript, e.g. to append further messages to it.
+		pub fn into_transcript(self) -> merlin::Transcript {
+			self.transcript
 		}
 
 		/// Map transcript to `VrfSignData`.
@@ -334,6 +378,18 @@ pub mod vrf {
 		}
 	}
 
+	impl From<merlin::Transcript> for VrfTranscript {
+		fn from(transcript: merlin::Transcript) -> Self {
+			Self::from_transcript(transcript)
+		}
+	}
+
+	impl From<VrfTranscript> for merlin::Transcript {
+		fn from(transcript: VrfTranscript) -> Self {
+			transcript.into_transcript()
+		}
+	}
+
 	/// VRF input.
 	///
 	/// Technically a transcript used by the Fiat-Shamir transform.
@@ -376,6 +432,11 @@ pub mod vrf {
 			self.extra = Some(extra);
 			self
 		}
+
+		/// The extra data to be signed, if any.
+		pub fn extra(&self) -> Option<&VrfTranscript> {
+			self.extra.as_ref()
+		}
 	}
 
 	/// VRF signature data
@@ -460,12 +521,12 @@ pub mod vrf {
 	#[cfg(feature = "full_crypto")]
 	impl VrfSecret for Pair {
 		fn vrf_sign(&self, data: &Self::VrfSignData) -> Self::VrfSignature {
-			let inout = self.0.vrf_create_hash(data.transcript.0.clone());
+			let inout = self.0.vrf_create_hash(data.transcript.transcript().clone());
 
 			let extra = data
 				.extra
 				.as_ref()
-				.map(|e| e.0.clone())
+				.map(|e| e.transcript().clone())
 				.unwrap_or_else(|| merlin::Transcript::new(DEFAULT_EXTRA_DATA_LABEL));
 
 			let proof = self.0.dleq_proove(extra, &inout, true).0;
@@ -474,7 +535,7 @@ pub mod vrf {
 		}
 
 		fn vrf_pre_output(&self, input: &Self::VrfInput) -> Self::VrfPreOutput {
-			let pre_output = self.0.vrf_create_hash(input.0.clone()).to_preout();
+			let pre_output = self.0.vrf_create_hash(input.transcript().clone()).to_preout();
 			VrfPreOutput(pre_output)
 		}
 	}
@@ -491,13 +552,15 @@ pub mod vrf {
 			let do_verify = || {
 				let public = schnorrkel::PublicKey::from_bytes(&self.0)?;
 
-				let inout =
-					signature.pre_output.0.attach_input_hash(&public, data.transcript.0.clone())?;
+				let inout = signature
+					.pre_output
+					.0
+					.attach_input_hash(&public, data.transcript.transcript().clone())?;
 
 				let extra = data
 					.extra
 					.as_ref()
-					.map(|e| e.0.clone())
+					.map(|e| e.transcript().clone())
 					.unwrap_or_else(|| merlin::Transcript::new(DEFAULT_EXTRA_DATA_LABEL));
 
 				public.dleq_verify(extra, &inout, &signature.proof.0, true)
@@ -545,7 +608,7 @@ pub mod vrf {
 		where
 			[u8; N]: Default,
 		{
-			let inout = self.0.vrf_create_hash(input.0.clone());
+			let inout = self.0.vrf_create_hash(input.transcript().clone());
 			inout.make_bytes::<[u8; N]>(context)
 		}
 	}
@@ -564,7 +627,7 @@ pub mod vrf {
 			let pubkey = schnorrkel::PublicKey::from_bytes(&self.0).map_err(convert_error)?;
 			let inout = pre_output
 				.0
-				.attach_input_hash(&pubkey, input.0.clone())
+				.attach_input_hash(&pubkey, input.transcript().clone())
 				.map_err(convert_error)?;
 			Ok(inout.make_bytes::<[u8; N]>(context))
 		}
@@ -865,6 +928,30 @@ mod tests {
 		assert!(public.vrf_verify(&data, &signature));
 	}
 
+	#[test]
+	fn vrf_transcript_from_parts_matches() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let public = pair.public();
+
+		let extra = VrfTranscript::new(b"extra", &[(b"domain2", b"data2")]);
+		let data = VrfTranscript::new(b"label", &[(b"domain1", b"data1")])
+			.into_sign_data()
+			.with_extra(extra);
+		let signature = pair.vrf_sign(&data);
+
+		let rebuilt = VrfTranscript::from_parts(data.as_ref().parts().unwrap().clone())
+			.into_sign_data()
+			.with_extra(VrfTranscript::from_parts(data.extra().unwrap().parts().unwrap().clone()));
+		// signatures are randomized, but the pre-output only depends on the input.
+		assert_eq!(pair.vrf_pre_output(rebuilt.as_ref()), signature.pre_output);
+		assert!(public.vrf_verify(&rebuilt, &signature));
+
+		let transcript = merlin::Transcript::new(b"label");
+		assert!(VrfTranscript::from_transcript(transcript.clone()).parts().is_none());
+		let wrapped: VrfTranscript = transcript.into();
+		assert!(wrapped.parts().is_none());
+	}
+
 	#[test]
 	fn vrf_make_bytes_matches() {
 		let pair = Pair::from_seed(b"12345678901234567890123456789012");
@@ -908,10 +995,10 @@ mod tests {
 		// Direct call to backend version of sign after check with extra params
 		let (inout, proof, _) = pair
 			.0
-			.vrf_sign_extra_after_check(input.0.clone(), |inout| {
+			.vrf_sign_extra_after_check(input.transcript().clone(), |inout| {
 				let out3 = inout.make_bytes::<[u8; 32]>(ctx);
 				assert_eq!(out2, out3);
-				Some(extra.0.clone())
+				Some(extra.transcript().clone())
 			})
 			.unwrap();
 		let signature2 =
@@ -1523,6 +1523,18 @@ sp_externalities::decl_extension! {
 	struct VerificationExtDeprecated(bool);
 }
 
+/// The error reported by the first version of the HTTP host functions.
+///
+/// Runtimes using them predate [`HttpError::Denied`] and cannot decode it, so denied requests are
+/// reported to them as if the connection failed.
+#[cfg(not(substrate_runtime))]
+fn legacy_http_error(error: HttpError) -> HttpError {
+	match error {
+		HttpError::Denied => HttpError::IoError,
+		error => error,
+	}
+}
+
 /// Interface that provides functions to access the offchain functionality.
 ///
 /// These functions are being made available to the runtime and are called by the runtime.
@@ -1694,6 +1706,28 @@ pub trait Offchain {
 	/// Passing `None` as deadline blocks forever.
 	///
 	/// Returns an error in case deadline is reached or the chunk couldn't be written.
+	///
+	/// Requests denied by the HTTP policy of the node are reported as [`HttpError::IoError`].
+	fn http_request_write_body(
+		&mut self,
+		request_id: PassAs<HttpRequestId, u16>,
+		chunk: PassFatPointerAndRead<&[u8]>,
+		deadline: PassFatPointerAndDecode<Option<Timestamp>>,
+	) -> AllocateAndReturnByCodec<Result<(), HttpError>> {
+		self.extension::<OffchainWorkerExt>()
+			.expect("http_request_write_body can be called only in the offchain worker context")
+			.http_request_write_body(request_id, chunk, deadline)
+			.map_err(legacy_http_error)
+	}
+
+	/// Write a chunk of request body.
+	///
+	/// Writing an empty chunks finalizes the request.
+	/// Passing `None` as deadline blocks forever.
+	///
+	/// Returns an error in case deadline is reached or the chunk couldn't be written, and
+	/// [`HttpError::Denied`] if the request was denied by the HTTP policy of the node.
+	#[version(2, register_only)]
 	fn http_request_write_body(
 		&mut self,
 		request_id: PassAs<HttpRequestId, u16>,
@@ -1743,6 +1777,31 @@ pub trait Offchain {
 	/// and the `request_id` is now invalid.
 	/// NOTE: this implies that response headers must be read before draining the body.
 	/// Passing `None` as a deadline blocks forever.
+	///
+	/// Requests denied by the HTTP policy of the node are reported as [`HttpError::IoError`].
+	fn http_response_read_body(
+		&mut self,
+		request_id: PassAs<HttpRequestId, u16>,
+		buffer: PassFatPointerAndReadWrite<&mut [u8]>,
+		deadline: PassFatPointerAndDecode<Option<Timestamp>>,
+	) -> AllocateAndReturnByCodec<Result<u32, HttpError>> {
+		self.extension::<OffchainWorkerExt>()
+			.expect("http_response_read_body can be called only in the offchain worker context")
+			.http_response_read_body(request_id, buffer, deadline)
+			.map(|r| r as u32)
+			.map_err(legacy_http_error)
+	}
+
+	/// Read a chunk of body response to given buffer.
+	///
+	/// Returns the number of bytes written or an error in case a deadline
+	/// is reached or server closed the connection, and [`HttpError::Denied`] if the request was
+	/// denied by the HTTP policy of the node.
+	/// If `0` is returned it means that the response has been fully consumed
+	/// and the `request_id` is now invalid.
+	/// NOTE: this implies that response headers must be read before draining the body.
+	/// Passing `None` as a deadline blocks forever.
+	#[version(2, register_only)]
 	fn http_response_read_body(
 		&mut self,
 		request_id: PassAs<HttpRequestId, u16>,
@@ -2154,4 +2213,12 @@ mod tests {
 			));
 		});
 	}
+
+	#[test]
+	fn legacy_http_functions_do_not_report_denied() {
+		assert_eq!(legacy_http_error(HttpError::Denied), HttpError::IoError);
+		for error in [HttpError::DeadlineReached, HttpError::IoError, HttpError::Invalid] {
+			assert_eq!(legacy_http_error(error), error);
+		}
+	}
 }
@@ -92,6 +92,26 @@ pub trait Keystore: Send + Sync {
 		msg: &[u8],
 	) -> Result<Option<sr25519::Signature>, Error>;
 
+	/// Hashes the `message` using blake2_256 and then signs the hash using sr25519.
+	///
+	/// Receives [`KeyTypeId`] and an [`sr25519::Public`] key to be able to map
+	/// them to a private key that exists in the keystore.
+	///
+	/// Defaults to [`Self::sr25519_sign`] of the hash, keystores that need to see the message
+	/// itself, e.g. to protect against equivocation, can override it.
+	///
+	/// Returns an [`sr25519::Signature`] or `None` in case the given `key_type`
+	/// and `public` combination doesn't exist in the keystore.
+	/// An `Err` will be returned if generating the signature itself failed.
+	fn sr25519_sign_with_blake2_256(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> Result<Option<sr25519::Signature>, Error> {
+		self.sr25519_sign(key_type, public, &sp_crypto_hashing::blake2_256(msg))
+	}
+
 	/// Generate an sr25519 VRF signature for the given data.
 	///
 	/// Receives [`KeyTypeId`] and an [`sr25519::Public`] key to be able to map
@@ -191,6 +211,26 @@ pub trait Keystore: Send + Sync {
 		msg: &[u8; 32],
 	) -> Result<Option<ecdsa::Signature>, Error>;
 
+	/// Hashes the `message` using keccak256 and then signs it using ECDSA algorithm.
+	///
+	/// Receives [`KeyTypeId`] and an [`ecdsa::Public`] key to be able to map
+	/// them to a private key that exists in the keystore.
+	///
+	/// Defaults to [`Self::ecdsa_sign_prehashed`], keystores that need to see the message
+	/// itself, e.g. to protect against equivocation, can override it.
+	///
+	/// Returns an [`ecdsa::Signature`] or `None` in case the given `key_type`
+	/// and `public` combination doesn't exist in the keystore.
+	/// An `Err` will be returned if generating the signature itself failed.
+	fn ecdsa_sign_with_keccak256(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		self.ecdsa_sign_prehashed(key_type, public, &sp_crypto_hashing::keccak_256(msg))
+	}
+
 	/// Returns all the bandersnatch public keys for the given key type.
 	#[cfg(feature = "bandersnatch-experimental")]
 	fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public>;
@@ -473,6 +513,15 @@ impl<T: Keystore + ?Sized> Keystore for Arc<T> {
 		(**self).sr25519_sign(key_type, public, msg)
 	}
 
+	fn sr25519_sign_with_blake2_256(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> Result<Option<sr25519::Signature>, Error> {
+		(**self).sr25519_sign_with_blake2_256(key_type, public, msg)
+	}
+
 	fn sr25519_vrf_sign(
 		&self,
 		key_type: KeyTypeId,
@@ -542,6 +591,15 @@ impl<T: Keystore + ?Sized> Keystore for Arc<T> {
 		(**self).ecdsa_sign_prehashed(key_type, public, msg)
 	}
 
+	fn ecdsa_sign_with_keccak256(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> Result<Option<ecdsa::Signature>, Error> {
+		(**self).ecdsa_sign_with_keccak256(key_type, public, msg)
+	}
+
 	#[cfg(feature = "bandersnatch-experimental")]
 	fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public> {
 		(**self).bandersnatch_public_keys(key_type)
@@ -25,6 +25,9 @@
 //! - [`balance`]: Implements the star balancing algorithm. This iterative process can push a
 //!   solution toward being more "balanced", which in turn can increase its score.
 //!
+//! With the `parallel` feature, which requires `std`, multi-threaded implementations of
+//! `seq_phragmen` and `phragmms` with the exact same output are provided in the `parallel` module.
+//!
 //! ### Terminology
 //!
 //! This crate uses context-independent words, not to be confused with staking. This is because the
@@ -94,6 +97,8 @@ mod assignments;
 pub mod balancing;
 pub mod helpers;
 pub mod node;
+#[cfg(feature = "parallel")]
+pub mod parallel;
 pub mod phragmen;
 pub mod phragmms;
 pub mod pjr;
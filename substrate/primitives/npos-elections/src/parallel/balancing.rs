@@ -0,0 +1,205 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parallel implementation of [`crate::balance`].
+
+use super::{for_each_in_waves, Candidate, Edge, Voter};
+use crate::{BalancingConfig, ExtendedBalance, IdentifierT};
+use alloc::vec::Vec;
+use sp_arithmetic::traits::Zero;
+
+/// Same as [`crate::balance`].
+///
+/// Voters that share an elected candidate are balanced in the same order as [`crate::balance`]
+/// does, voters that do not are balanced in parallel.
+pub(super) fn balance<AccountId: IdentifierT + Send + Sync>(
+	voters: &mut [Voter<AccountId>],
+	candidates: &mut [Candidate<AccountId>],
+	config: &BalancingConfig,
+) -> usize {
+	if config.iterations == 0 {
+		return 0
+	}
+
+	let mut iter = 0;
+	loop {
+		let mut max_diff = 0;
+		for_each_in_waves(
+			voters,
+			candidates,
+			|voter, candidates| {
+				voter
+					.edges
+					.iter()
+					.filter(|e| candidates[e.candidate].elected)
+					.map(|e| e.candidate)
+					.collect::<Vec<_>>()
+			},
+			|voter, candidates| balance_voter(voter, candidates, config.tolerance),
+			|(diff, backed_stakes), candidates| {
+				if diff > max_diff {
+					max_diff = diff;
+				}
+				for (candidate, backed_stake) in backed_stakes {
+					candidates[candidate].backed_stake = backed_stake;
+				}
+			},
+		);
+
+		iter += 1;
+		if max_diff <= config.tolerance || iter >= config.iterations {
+			break iter
+		}
+	}
+}
+
+/// Same as [`crate::balancing::balance_voter`], except that the backed stake of the candidates is
+/// not updated.
+///
+/// Instead, the new backed stake of each elected candidate of the voter is returned, next to the
+/// difference.
+fn balance_voter<AccountId: IdentifierT>(
+	voter: &mut Voter<AccountId>,
+	candidates: &[Candidate<AccountId>],
+	tolerance: ExtendedBalance,
+) -> (ExtendedBalance, Vec<(usize, ExtendedBalance)>) {
+	// create a shallow copy of the elected ones. The original one will not be used henceforth.
+	let mut elected_edges = voter
+		.edges
+		.iter_mut()
+		.filter(|e| candidates[e.candidate].elected)
+		.collect::<Vec<&mut Edge>>();
+
+	// Either empty, or a self vote. Not much to do in either case.
+	if elected_edges.len() <= 1 {
+		return (Zero::zero(), Vec::new())
+	}
+
+	// local copy of the backed stake of the elected candidates, updated as we go.
+	let mut backed_stakes = elected_edges
+		.iter()
+		.map(|e| (e.candidate, candidates[e.candidate].backed_stake))
+		.collect::<Vec<_>>();
+	let stake_of = |backed_stakes: &[(usize, ExtendedBalance)], candidate: usize| {
+		backed_stakes
+			.iter()
+			.find_map(|(c, stake)| (*c == candidate).then_some(*stake))
+			.expect("all elected candidates of the voter are in backed_stakes; qed")
+	};
+	let stake_mut = |backed_stakes: &mut Vec<(usize, ExtendedBalance)>, candidate: usize| {
+		backed_stakes
+			.iter_mut()
+			.find_map(|(c, stake)| (*c == candidate).then_some(stake))
+			.expect("all elected candidates of the voter are in backed_stakes; qed")
+	};
+
+	// amount of stake from this voter that is used in edges.
+	let stake_used =
+		elected_edges.iter().fold(0, |a: ExtendedBalance, e| a.saturating_add(e.weight));
+
+	// backed stake of all the edges for whom we've spent some stake.
+	let backing_backed_stake = elected_edges
+		.iter()
+		.filter_map(|e| (e.weight > 0).then(|| stake_of(&backed_stakes, e.candidate)))
+		.collect::<Vec<_>>();
+
+	let difference = if backing_backed_stake.len() > 0 {
+		let max_stake = backing_backed_stake
+			.iter()
+			.max()
+			.expect("vector with positive length will have a max; qed");
+		let min_stake = backed_stakes
+			.iter()
+			.map(|(_, stake)| stake)
+			.min()
+			.expect("iterator with positive length will have a min; qed");
+		let mut difference = max_stake.saturating_sub(*min_stake);
+		difference = difference.saturating_add(voter.budget.saturating_sub(stake_used));
+		if difference < tolerance {
+			return (difference, Vec::new())
+		}
+		difference
+	} else {
+		voter.budget
+	};
+
+	// remove all backings.
+	for edge in elected_edges.iter_mut() {
+		let stake = stake_mut(&mut backed_stakes, edge.candidate);
+		*stake = stake.saturating_sub(edge.weight);
+		edge.weight = 0;
+	}
+
+	elected_edges.sort_by_key(|e| stake_of(&backed_stakes, e.candidate));
+
+	let mut cumulative_backed_stake = Zero::zero();
+	let mut last_index = elected_edges.len() - 1;
+
+	for (index, edge) in elected_edges.iter().enumerate() {
+		let index = index as ExtendedBalance;
+		let backed_stake = stake_of(&backed_stakes, edge.candidate);
+		let temp = backed_stake.saturating_mul(index);
+		if temp.saturating_sub(cumulative_backed_stake) > voter.budget {
+			// defensive only. length of elected_edges is checked to be above 1.
+			last_index = index.saturating_sub(1) as usize;
+			break
+		}
+		cumulative_backed_stake = cumulative_backed_stake.saturating_add(backed_stake);
+	}
+
+	let last_stake = stake_of(
+		&backed_stakes,
+		elected_edges
+			.get(last_index)
+			.expect(
+				"length of elected_edges is greater than or equal 2; last_index index is at the \
+				 minimum elected_edges.len() - 1; index is within range; qed",
+			)
+			.candidate,
+	);
+	let ways_to_split = last_index + 1;
+	let excess = voter
+		.budget
+		.saturating_add(cumulative_backed_stake)
+		.saturating_sub(last_stake.saturating_mul(ways_to_split as ExtendedBalance));
+
+	// Do the final update.
+	for edge in elected_edges.into_iter().take(ways_to_split) {
+		let stake = stake_mut(&mut backed_stakes, edge.candidate);
+		let new_edge_weight = (excess / ways_to_split as ExtendedBalance)
+			.saturating_add(last_stake)
+			.saturating_sub(*stake);
+
+		// write the new edge weight
+		edge.weight = new_edge_weight;
+
+		// write the new candidate stake
+		*stake = stake.saturating_add(new_edge_weight);
+	}
+
+	// excess / ways_to_split can cause a small un-normalized voters to be created.
+	// We won't `expect` here because even a result which is not normalized is not corrupt;
+	if let Ok(normalized) = voter.try_normalize_elected(candidates) {
+		for (candidate, previous, corrected) in normalized {
+			let stake = stake_mut(&mut backed_stakes, candidate);
+			// first, subtract the incorrect weight, then add the correct one again.
+			*stake = stake.saturating_sub(previous).saturating_add(corrected);
+		}
+	}
+
+	(difference, backed_stakes)
+}
@@ -0,0 +1,397 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Multi-threaded implementations of the election algorithms of this crate.
+//!
+//! The algorithms of this module produce exactly the same [`ElectionResult`] as their sequential
+//! counterparts, i.e. [`crate::seq_phragmen`] and [`crate::phragmms()`], for the same input. They
+//! are only available with the `parallel` feature, which requires `std`, and are meant to be used
+//! off-chain, e.g. by miners and in benchmarks.
+//!
+//! The sequential implementations share candidates between voters through [`CandidatePtr`]s, which
+//! can not cross threads. Instead, the types of this module refer to candidates by index. Then:
+//!
+//! - The score of each candidate is computed on its own thread, folding over the voters that back
+//!   it in the same order as the sequential implementation, since the arithmetic of scores is not
+//!   associative.
+//! - The work of each voter that only touches its own edges, e.g. updating loads and weights, is
+//!   spread across threads as well.
+//! - Balancing is inherently sequential: the outcome of balancing a voter depends on the backed
+//!   stake of its candidates, as left by all previous voters. Voters are therefore processed in
+//!   waves, where a voter only joins a wave after all previous voters that share a candidate with
+//!   it. The voters of a wave are balanced in parallel, each seeing the exact same state it would
+//!   see sequentially. How much this gains depends on how much the votes of the voters overlap.
+//!
+//! All work is executed in the current [`rayon`] thread pool. To limit the number of threads, run
+//! the election within [`rayon::ThreadPool::install`].
+//!
+//! [`CandidatePtr`]: crate::CandidatePtr
+
+use crate::{ElectionResult, ExtendedBalance, IdentifierT, PerThing128, VoteWeight};
+use alloc::{collections::btree_map::BTreeMap, vec::Vec};
+use rayon::prelude::*;
+use sp_arithmetic::{Normalizable, PerThing, Rational128};
+
+mod balancing;
+mod phragmen;
+mod phragmms;
+
+pub use phragmen::seq_phragmen;
+pub use phragmms::phragmms;
+
+/// A candidate of the election, see [`crate::Candidate`].
+#[derive(Clone, Debug)]
+struct Candidate<AccountId> {
+	who: AccountId,
+	score: Rational128,
+	approval_stake: ExtendedBalance,
+	backed_stake: ExtendedBalance,
+	elected: bool,
+	round: usize,
+}
+
+/// An edge from a voter to the candidate at index `candidate`, see [`crate::Edge`].
+#[derive(Clone, Debug, Default)]
+struct Edge {
+	candidate: usize,
+	load: Rational128,
+	weight: ExtendedBalance,
+}
+
+/// A voter of the election, see [`crate::Voter`].
+#[derive(Clone, Debug)]
+struct Voter<AccountId> {
+	who: AccountId,
+	edges: Vec<Edge>,
+	budget: ExtendedBalance,
+	load: Rational128,
+}
+
+impl<AccountId: IdentifierT> Voter<AccountId> {
+	/// Same as [`crate::Voter::into_assignment`].
+	fn into_assignment<P: PerThing>(
+		self,
+		candidates: &[Candidate<AccountId>],
+	) -> Option<crate::Assignment<AccountId, P>> {
+		let who = self.who;
+		let budget = self.budget;
+		let distribution = self
+			.edges
+			.into_iter()
+			.filter_map(|e| {
+				let per_thing = P::from_rational(e.weight, budget);
+				// trim zero edges.
+				if per_thing.is_zero() {
+					None
+				} else {
+					Some((candidates[e.candidate].who.clone(), per_thing))
+				}
+			})
+			.collect::<Vec<_>>();
+
+		if distribution.len() > 0 {
+			Some(crate::Assignment { who, distribution })
+		} else {
+			None
+		}
+	}
+
+	/// Same as [`crate::Voter::try_normalize_elected`], except that the backed stake of the
+	/// candidates is not updated.
+	///
+	/// Instead, the candidate index, the previous weight and the normalized weight of each elected
+	/// edge are returned, in order.
+	fn try_normalize_elected(
+		&mut self,
+		candidates: &[Candidate<AccountId>],
+	) -> Result<Vec<(usize, ExtendedBalance, ExtendedBalance)>, &'static str> {
+		let elected_edge_weights = self
+			.edges
+			.iter()
+			.filter_map(|e| candidates[e.candidate].elected.then_some(e.weight))
+			.collect::<Vec<_>>();
+		elected_edge_weights.normalize(self.budget).map(|normalized| {
+			self.edges
+				.iter_mut()
+				.filter(|e| candidates[e.candidate].elected)
+				.zip(normalized.into_iter())
+				.map(|(edge, corrected)| {
+					let previous = core::mem::replace(&mut edge.weight, corrected);
+					(edge.candidate, previous, corrected)
+				})
+				.collect()
+		})
+	}
+}
+
+/// The state of an election.
+struct Election<AccountId> {
+	candidates: Vec<Candidate<AccountId>>,
+	voters: Vec<Voter<AccountId>>,
+	/// The indices of the voters that back each candidate, in order.
+	backers: Vec<Vec<usize>>,
+}
+
+impl<AccountId: IdentifierT + Send + Sync> Election<AccountId> {
+	/// Same as [`crate::setup_inputs`].
+	fn setup(
+		initial_candidates: Vec<AccountId>,
+		initial_voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
+	) -> Self {
+		// used to cache and access candidates index.
+		let mut c_idx_cache = BTreeMap::<AccountId, usize>::new();
+
+		let mut candidates = initial_candidates
+			.into_iter()
+			.enumerate()
+			.map(|(idx, who)| {
+				c_idx_cache.insert(who.clone(), idx);
+				Candidate {
+					who,
+					score: Default::default(),
+					approval_stake: Default::default(),
+					backed_stake: Default::default(),
+					elected: Default::default(),
+					round: Default::default(),
+				}
+			})
+			.collect::<Vec<_>>();
+		let mut backers = alloc::vec![Vec::new(); candidates.len()];
+
+		let voters = initial_voters
+			.into_iter()
+			.filter_map(|(who, voter_stake, votes)| {
+				let mut edges: Vec<Edge> = Vec::new();
+				for v in votes {
+					if let Some(idx) = c_idx_cache.get(&v) {
+						if edges.iter().any(|e| e.candidate == *idx) {
+							// duplicate edge.
+							continue
+						}
+						let candidate = &mut candidates[*idx];
+						candidate.approval_stake =
+							candidate.approval_stake.saturating_add(voter_stake.into());
+						edges.push(Edge { candidate: *idx, ..Default::default() });
+					} // else {} would be wrong votes. We don't really care about it.
+				}
+				if edges.is_empty() {
+					None
+				} else {
+					Some(Voter {
+						who,
+						edges,
+						budget: voter_stake.into(),
+						load: Rational128::zero(),
+					})
+				}
+			})
+			.collect::<Vec<_>>();
+
+		for (index, voter) in voters.iter().enumerate() {
+			for edge in &voter.edges {
+				backers[edge.candidate].push(index);
+			}
+		}
+
+		Self { candidates, voters, backers }
+	}
+
+	/// Convert the state into an [`ElectionResult`], with `winners` in the given order.
+	fn into_result<P: PerThing128>(
+		self,
+		winners: Vec<usize>,
+	) -> Result<ElectionResult<AccountId, P>, crate::Error> {
+		let Self { candidates, voters, .. } = self;
+		let mut assignments = voters
+			.into_par_iter()
+			.filter_map(|v| v.into_assignment(&candidates))
+			.collect::<Vec<_>>();
+		assignments
+			.par_iter_mut()
+			.try_for_each(|a| a.try_normalize().map_err(|_| crate::Error::ArithmeticError))?;
+		let winners = winners
+			.into_iter()
+			.map(|w| (candidates[w].who.clone(), candidates[w].backed_stake))
+			.collect();
+
+		Ok(ElectionResult { winners, assignments })
+	}
+}
+
+/// Run some work on each voter, in waves of voters that do not touch the same candidates.
+///
+/// The work of a voter may only read the candidates that it declares in `touches`, and returns
+/// some output that is then applied to the candidates. The outputs of a wave are applied after
+/// the whole wave is done, and the wave of a voter is after the wave of all previous voters that
+/// touch one of its candidates. Thus, each voter observes the same candidates as if all voters were
+/// processed sequentially, in order.
+fn for_each_in_waves<AccountId, I, R>(
+	voters: &mut [Voter<AccountId>],
+	candidates: &mut [Candidate<AccountId>],
+	touches: impl Fn(&Voter<AccountId>, &[Candidate<AccountId>]) -> I,
+	work: impl Fn(&mut Voter<AccountId>, &[Candidate<AccountId>]) -> R + Send + Sync,
+	mut apply: impl FnMut(R, &mut [Candidate<AccountId>]),
+) where
+	AccountId: IdentifierT + Send + Sync,
+	I: IntoIterator<Item = usize>,
+	R: Send,
+{
+	// the wave after the last wave in which each candidate is touched.
+	let mut next_wave = alloc::vec![0usize; candidates.len()];
+	let view: &[Candidate<AccountId>] = candidates;
+	let mut waves = voters
+		.iter_mut()
+		.map(|voter| {
+			let touched = touches(voter, view).into_iter().collect::<Vec<_>>();
+			let wave = touched.iter().map(|c| next_wave[*c]).max().unwrap_or_default();
+			touched.into_iter().for_each(|c| next_wave[c] = wave + 1);
+			(wave, voter)
+		})
+		.collect::<Vec<_>>();
+	// stable, the order of voters within a wave does not matter anyways.
+	waves.sort_by_key(|(wave, _)| *wave);
+
+	let mut rest = &mut waves[..];
+	while let Some(wave) = rest.first().map(|(wave, _)| *wave) {
+		let len = rest.iter().take_while(|(w, _)| *w == wave).count();
+		let (current, next) = core::mem::take(&mut rest).split_at_mut(len);
+		let view: &[Candidate<AccountId>] = candidates;
+		let outputs =
+			current.par_iter_mut().map(|(_, voter)| work(voter, view)).collect::<Vec<_>>();
+		outputs.into_iter().for_each(|output| apply(output, candidates));
+		rest = next;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		mock::AccountId, phragmms as sequential_phragmms, seq_phragmen as sequential_phragmen,
+		BalancingConfig, ElectionResult, PerThing128, VoteWeight,
+	};
+	use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+	use sp_runtime::{Perbill, Percent};
+
+	type Voters = Vec<(AccountId, VoteWeight, Vec<AccountId>)>;
+
+	fn random_input(
+		seed: u64,
+		targets: u64,
+		voters: u64,
+		votes: usize,
+	) -> (Vec<AccountId>, Voters) {
+		let mut rng = SmallRng::seed_from_u64(seed);
+		let candidates = (0..targets).collect::<Vec<_>>();
+		let voters = (0..voters)
+			.map(|v| {
+				let stake = rng.gen_range(1..1_000_000_000_000);
+				let votes =
+					candidates.choose_multiple(&mut rng, votes).copied().collect::<Vec<_>>();
+				(targets + v, stake, votes)
+			})
+			.collect::<Vec<_>>();
+		(candidates, voters)
+	}
+
+	fn flatten<P: PerThing128>(
+		result: ElectionResult<AccountId, P>,
+	) -> (Vec<(AccountId, u128)>, Vec<(AccountId, Vec<(AccountId, P)>)>) {
+		let assignments = result.assignments.into_iter().map(|a| (a.who, a.distribution)).collect();
+		(result.winners, assignments)
+	}
+
+	fn with_threads<R: Send>(threads: usize, f: impl FnOnce() -> R + Send) -> R {
+		rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap().install(f)
+	}
+
+	#[test]
+	fn seq_phragmen_is_identical_to_sequential() {
+		for seed in 0..8 {
+			let (candidates, voters) = random_input(seed, 40, 400, 8);
+			for balancing in [None, Some(BalancingConfig { iterations: 10, tolerance: 0 })] {
+				let expected = sequential_phragmen::<_, Perbill>(
+					10,
+					candidates.clone(),
+					voters.clone(),
+					balancing,
+				)
+				.unwrap();
+				let actual = with_threads(4, || {
+					super::seq_phragmen::<_, Perbill>(
+						10,
+						candidates.clone(),
+						voters.clone(),
+						balancing,
+					)
+					.unwrap()
+				});
+				assert_eq!(flatten(actual), flatten(expected));
+			}
+		}
+	}
+
+	#[test]
+	fn phragmms_is_identical_to_sequential() {
+		for seed in 0..8 {
+			let (candidates, voters) = random_input(seed, 40, 400, 8);
+			for balancing in [None, Some(BalancingConfig { iterations: 4, tolerance: 10 })] {
+				let expected = sequential_phragmms::<_, Percent>(
+					10,
+					candidates.clone(),
+					voters.clone(),
+					balancing,
+				)
+				.unwrap();
+				let actual = with_threads(4, || {
+					super::phragmms::<_, Percent>(10, candidates.clone(), voters.clone(), balancing)
+						.unwrap()
+				});
+				assert_eq!(flatten(actual), flatten(expected));
+			}
+		}
+	}
+
+	#[test]
+	fn handles_invalid_and_duplicate_votes() {
+		let candidates = vec![1, 2, 3, 4];
+		let voters = vec![
+			(10, 10, vec![1, 1, 2, 99]),
+			(20, 20, vec![99]),
+			(30, 30, vec![2, 3, 3]),
+			(40, 0, vec![4]),
+		];
+		let balancing = Some(BalancingConfig { iterations: 2, tolerance: 0 });
+
+		assert_eq!(
+			flatten(
+				super::seq_phragmen::<_, Perbill>(3, candidates.clone(), voters.clone(), None)
+					.unwrap()
+			),
+			flatten(
+				sequential_phragmen::<_, Perbill>(3, candidates.clone(), voters.clone(), None)
+					.unwrap()
+			),
+		);
+		assert_eq!(
+			flatten(
+				super::phragmms::<_, Perbill>(3, candidates.clone(), voters.clone(), balancing)
+					.unwrap()
+			),
+			flatten(sequential_phragmms::<_, Perbill>(3, candidates, voters, balancing).unwrap()),
+		);
+	}
+}
@@ -0,0 +1,183 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parallel implementation of the sequential-phragmen election method.
+
+use super::{balancing, Election};
+use crate::{
+	BalancingConfig, ElectionResult, ExtendedBalance, IdentifierT, PerThing128, VoteWeight,
+};
+use alloc::vec::Vec;
+use rayon::prelude::*;
+use sp_arithmetic::{
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{Bounded, Zero},
+	Rational128, Rounding,
+};
+
+/// The denominator used for loads, see [`crate::phragmen`].
+const DEN: ExtendedBalance = ExtendedBalance::max_value();
+
+/// Same as [`crate::seq_phragmen`], executed on the current [`rayon`] thread pool.
+///
+/// The output is exactly the same as that of [`crate::seq_phragmen`] for the same input.
+pub fn seq_phragmen<AccountId: IdentifierT + Send + Sync, P: PerThing128>(
+	to_elect: usize,
+	candidates: Vec<AccountId>,
+	voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
+	balancing: Option<BalancingConfig>,
+) -> Result<ElectionResult<AccountId, P>, crate::Error> {
+	let mut election = Election::setup(candidates, voters);
+
+	seq_phragmen_core(to_elect, &mut election)?;
+
+	if let Some(ref config) = balancing {
+		// NOTE: might create zero-edges, but we will strip them again when we convert voter into
+		// assignment.
+		let _iters = balancing::balance(&mut election.voters, &mut election.candidates, config);
+	}
+
+	let mut winners = election
+		.candidates
+		.iter()
+		.enumerate()
+		.filter(|(_, c)| c.elected)
+		// defensive only: seq-phragmen-core returns only up to rounds.
+		.take(to_elect)
+		.map(|(index, _)| index)
+		.collect::<Vec<_>>();
+
+	// sort winners based on desirability.
+	winners.sort_by_key(|w| election.candidates[*w].round);
+
+	election.into_result(winners)
+}
+
+/// Same as [`crate::seq_phragmen_core`].
+fn seq_phragmen_core<AccountId: IdentifierT + Send + Sync>(
+	to_elect: usize,
+	election: &mut Election<AccountId>,
+) -> Result<(), crate::Error> {
+	let Election { candidates, voters, backers } = election;
+
+	// we have already checked that we have more candidates than minimum_candidate_count.
+	let to_elect = to_elect.min(candidates.len());
+
+	// main election loop
+	for round in 0..to_elect {
+		// loop 1 and 2, per candidate: initialize and increment score, in the order of voters.
+		candidates
+			.par_iter_mut()
+			.zip(backers.par_iter())
+			.for_each(|(candidate, backers)| {
+				if candidate.elected {
+					return
+				}
+				// 1 / approval_stake == (DEN / approval_stake) / DEN. If approval_stake is zero,
+				// then the ratio should be as large as possible, essentially `infinity`.
+				if candidate.approval_stake.is_zero() {
+					candidate.score = Bounded::max_value();
+					return
+				}
+				candidate.score = Rational128::from(DEN / candidate.approval_stake, DEN);
+				for voter in backers.iter().map(|v| &voters[*v]) {
+					let temp_n = multiply_by_rational_with_rounding(
+						voter.load.n(),
+						voter.budget,
+						candidate.approval_stake,
+						Rounding::Down,
+					)
+					.unwrap_or(Bounded::max_value());
+					let temp_d = voter.load.d();
+					let temp = Rational128::from(temp_n, temp_d);
+					candidate.score = candidate.score.lazy_saturating_add(temp);
+				}
+			});
+
+		// loop 3: find the best
+		if let Some((winner_index, _)) = candidates
+			.iter()
+			.enumerate()
+			.filter(|(_, c)| !c.elected)
+			.min_by_key(|(_, c)| c.score)
+		{
+			let winner = &mut candidates[winner_index];
+			// loop 3: update voter and edge load
+			winner.elected = true;
+			winner.round = round;
+			let (winner_who, winner_score) = (winner.who.clone(), winner.score);
+			let candidates = &*candidates;
+			voters.par_iter_mut().for_each(|voter| {
+				for edge in &mut voter.edges {
+					if candidates[edge.candidate].who == winner_who {
+						edge.load = winner_score.lazy_saturating_sub(voter.load);
+						voter.load = winner_score;
+					}
+				}
+			});
+		} else {
+			break
+		}
+	}
+
+	// update the weights of voters, then the backing stake of candidates in the order of voters.
+	let weights = {
+		let candidates = &*candidates;
+		voters
+			.par_iter_mut()
+			.map(|voter| {
+				for edge in &mut voter.edges {
+					edge.weight = if candidates[edge.candidate].elected {
+						multiply_by_rational_with_rounding(
+							voter.budget,
+							edge.load.n(),
+							voter.load.n(),
+							Rounding::Down,
+						)
+						// If result cannot fit in u128. Not much we can do about it.
+						.unwrap_or(Bounded::max_value())
+					} else {
+						0
+					};
+				}
+
+				// remove all zero edges. These can become phantom edges during normalization.
+				voter.edges.retain(|e| e.weight > 0);
+				// edge of all candidates that eventually have a non-zero weight must be elected.
+				debug_assert!(voter.edges.iter().all(|e| candidates[e.candidate].elected));
+				// inc budget to sum the budget.
+				voter.try_normalize_elected(candidates)
+			})
+			.collect::<Vec<_>>()
+	};
+
+	for normalized in weights {
+		let normalized = normalized.map_err(|_| crate::Error::ArithmeticError)?;
+		for (candidate, weight, _) in &normalized {
+			let candidate = &mut candidates[*candidate];
+			candidate.backed_stake = candidate.backed_stake.saturating_add(*weight);
+		}
+		for (candidate, previous, corrected) in normalized {
+			let candidate = &mut candidates[candidate];
+			// first, subtract the incorrect weight, then add the correct one again.
+			candidate.backed_stake =
+				candidate.backed_stake.saturating_sub(previous).saturating_add(corrected);
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,202 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parallel implementation of the PhragMMS method.
+
+use super::{balancing, for_each_in_waves, Candidate, Election, Voter};
+use crate::{
+	BalancingConfig, ElectionResult, ExtendedBalance, IdentifierT, PerThing128, VoteWeight,
+};
+use alloc::vec::Vec;
+use rayon::prelude::*;
+use sp_arithmetic::{traits::Bounded, PerThing, Rational128};
+
+/// Same as [`crate::phragmms()`], executed on the current [`rayon`] thread pool.
+///
+/// The output is exactly the same as that of [`crate::phragmms()`] for the same input.
+pub fn phragmms<AccountId: IdentifierT + Send + Sync, P: PerThing128>(
+	to_elect: usize,
+	candidates: Vec<AccountId>,
+	voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
+	balancing: Option<BalancingConfig>,
+) -> Result<ElectionResult<AccountId, P>, crate::Error> {
+	let mut election = Election::setup(candidates, voters);
+
+	let mut winners = Vec::new();
+	for round in 0..to_elect {
+		if let Some(round_winner) = calculate_max_score::<AccountId, P>(&mut election) {
+			apply_elected(&mut election.voters, &mut election.candidates, round_winner);
+
+			election.candidates[round_winner].round = round;
+			election.candidates[round_winner].elected = true;
+			winners.push(round_winner);
+
+			if let Some(ref config) = balancing {
+				balancing::balance(&mut election.voters, &mut election.candidates, config);
+			}
+		} else {
+			break
+		}
+	}
+
+	election.into_result(winners)
+}
+
+/// Same as [`crate::phragmms::calculate_max_score`], returning the index of the winner.
+fn calculate_max_score<AccountId: IdentifierT + Send + Sync, P: PerThing>(
+	election: &mut Election<AccountId>,
+) -> Option<usize> {
+	let Election { candidates, voters, backers } = election;
+
+	// gather the contribution of each voter from all of its elected edges.
+	let contributions = {
+		let candidates = &*candidates;
+		voters
+			.par_iter()
+			.map(|voter| {
+				let mut denominator_contribution: ExtendedBalance = 0;
+				for edge in voter.edges.iter() {
+					let edge_candidate = &candidates[edge.candidate];
+					if edge_candidate.elected {
+						let edge_contribution: ExtendedBalance =
+							P::from_rational(edge.weight, edge_candidate.backed_stake)
+								.deconstruct()
+								.into();
+						denominator_contribution += edge_contribution;
+					}
+				}
+				denominator_contribution
+			})
+			.collect::<Vec<_>>()
+	};
+
+	// distribute to all _unelected_ candidates, in the order of voters, and finalise the score.
+	candidates
+		.par_iter_mut()
+		.zip(backers.par_iter())
+		.for_each(|(candidate, backers)| {
+			if !candidate.elected {
+				candidate.score = Rational128::from(1, P::ACCURACY.into());
+				for voter in backers {
+					let prev_d = candidate.score.d();
+					candidate.score = Rational128::from(1, contributions[*voter] + prev_d);
+				}
+			}
+
+			if candidate.approval_stake > 0 {
+				// finalise the score value. See `crate::phragmms::calculate_max_score` for notes on
+				// its accuracy.
+				let score_d = candidate.score.d();
+				let one: ExtendedBalance = P::ACCURACY.into();
+				let score_n =
+					candidate.approval_stake.checked_mul(one).unwrap_or_else(Bounded::max_value);
+				candidate.score = Rational128::from(score_n, score_d);
+			} else {
+				candidate.score = Rational128::zero();
+			}
+		});
+
+	// find the best.
+	let mut best_score = Rational128::zero();
+	let mut best_candidate = None;
+	for (index, candidate) in candidates.iter().enumerate() {
+		if candidate.approval_stake > 0 && !candidate.elected && candidate.score > best_score {
+			best_score = candidate.score;
+			best_candidate = Some(index);
+		}
+	}
+
+	best_candidate
+}
+
+/// Same as [`crate::phragmms::apply_elected`], with `elected` being the index of the candidate.
+///
+/// Voters that take stake from the same candidates are processed in the same order as
+/// [`crate::phragmms::apply_elected`] does, others in parallel.
+fn apply_elected<AccountId: IdentifierT + Send + Sync>(
+	voters: &mut [Voter<AccountId>],
+	candidates: &mut [Candidate<AccountId>],
+	elected: usize,
+) {
+	let elected_who = candidates[elected].who.clone();
+	let cutoff = candidates[elected]
+		.score
+		.to_den(1)
+		.expect("(n / d) < u128::MAX and (n' / 1) == (n / d), thus n' < u128::MAX'; qed.")
+		.n();
+	let new_edge_index = |voter: &Voter<AccountId>, candidates: &[Candidate<AccountId>]| {
+		voter.edges.iter().position(|e| candidates[e.candidate].who == elected_who)
+	};
+
+	let mut elected_backed_stake = candidates[elected].backed_stake;
+	for_each_in_waves(
+		voters,
+		candidates,
+		|voter, candidates| {
+			let Some(new_edge_index) = new_edge_index(voter, candidates) else { return Vec::new() };
+			voter
+				.edges
+				.iter()
+				.enumerate()
+				.filter(|(edge_index, edge)| *edge_index != new_edge_index && edge.weight > 0)
+				.map(|(_, edge)| edge.candidate)
+				.collect::<Vec<_>>()
+		},
+		|voter, candidates| {
+			let new_edge_index = new_edge_index(voter, candidates)?;
+			let used_budget: ExtendedBalance = voter.edges.iter().map(|e| e.weight).sum();
+
+			let mut new_edge_weight = voter.budget.saturating_sub(used_budget);
+			let mut added_stake = new_edge_weight;
+			let mut backed_stakes = Vec::new();
+
+			// Iterate over all other edges.
+			for (_, edge) in
+				voter.edges.iter_mut().enumerate().filter(|(edge_index, edge_inner)| {
+					*edge_index != new_edge_index && edge_inner.weight > 0
+				}) {
+				let backed_stake = candidates[edge.candidate].backed_stake;
+				if backed_stake > cutoff {
+					let stake_to_take = edge.weight.saturating_mul(cutoff) / backed_stake.max(1);
+
+					// subtract this amount from this edge.
+					edge.weight = edge.weight.saturating_sub(stake_to_take);
+					backed_stakes
+						.push((edge.candidate, backed_stake.saturating_sub(stake_to_take)));
+
+					// inject it into the outer loop's edge.
+					added_stake = added_stake.saturating_add(stake_to_take);
+					new_edge_weight = new_edge_weight.saturating_add(stake_to_take);
+				}
+			}
+
+			voter.edges[new_edge_index].weight = new_edge_weight;
+			Some((added_stake, backed_stakes))
+		},
+		|output, candidates| {
+			let Some((added_stake, backed_stakes)) = output else { return };
+			// saturating additions of unsigned values, the order does not matter.
+			elected_backed_stake = elected_backed_stake.saturating_add(added_stake);
+			for (candidate, backed_stake) in backed_stakes {
+				candidates[candidate].backed_stake = backed_stake;
+			}
+		},
+	);
+
+	// final update.
+	candidates[elected].backed_stake = elected_backed_stake;
+}
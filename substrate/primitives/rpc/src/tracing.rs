@@ -90,6 +90,83 @@ pub struct TraceError {
 	pub error: String,
 }
 
+/// Output format requested from the `state_traceBlock` RPC.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceFormat {
+	/// Flat lists of spans and events, see [`BlockTrace`].
+	#[default]
+	Spans,
+	/// A timeline in the Chrome trace event format, see [`ChromeTrace`].
+	Chrome,
+}
+
+/// Block trace in the Chrome trace event format.
+///
+/// Can be opened directly in `chrome://tracing` or <https://ui.perfetto.dev>.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChromeTrace {
+	/// Time unit used by viewers to display timestamps.
+	pub display_time_unit: String,
+	/// Timeline of the block execution.
+	pub trace_events: Vec<ChromeEvent>,
+}
+
+/// Single entry in a [`ChromeTrace`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChromeEvent {
+	/// Name of the span, event or counter
+	pub name: String,
+	/// Category, the tracing target
+	pub cat: String,
+	/// Kind of the entry
+	pub ph: ChromePhase,
+	/// Timestamp in microseconds since the start of the block execution
+	pub ts: u64,
+	/// Process id
+	pub pid: u32,
+	/// Thread id
+	pub tid: u32,
+	/// Scope of an instant event
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub s: Option<String>,
+	/// Values recorded with the span or event, or the counter values
+	#[serde(default, skip_serializing_if = "FxHashMap::is_empty")]
+	pub args: FxHashMap<String, ChromeArg>,
+}
+
+/// Kind of a [`ChromeEvent`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChromePhase {
+	/// Span was entered
+	#[serde(rename = "B")]
+	Begin,
+	/// Span was exited
+	#[serde(rename = "E")]
+	End,
+	/// Event without a duration
+	#[serde(rename = "i")]
+	Instant,
+	/// Counter sample
+	#[serde(rename = "C")]
+	Counter,
+}
+
+/// Value attached to a [`ChromeEvent`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ChromeArg {
+	/// Boolean value
+	Bool(bool),
+	/// Unsigned integer value
+	U64(u64),
+	/// Signed integer value
+	I64(i64),
+	/// String value
+	String(String),
+}
+
 /// Response for the `state_traceBlock` RPC.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -98,4 +175,6 @@ pub enum TraceBlockResponse {
 	TraceError(TraceError),
 	/// Successful block tracing response
 	BlockTrace(BlockTrace),
+	/// Successful block tracing response in the Chrome trace event format
+	ChromeTrace(ChromeTrace),
 }
@@ -36,6 +36,8 @@ pub mod offence;
 
 pub mod currency_to_vote;
 
+pub mod simulation;
+
 /// Simple index type with which we can count sessions.
 pub type SessionIndex = u32;
 
@@ -0,0 +1,165 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Results of the simulation of era payouts and slashes, as returned by the runtime APIs of
+//! staking pallets.
+//!
+//! Stakers that delegate their stake, such as nomination pools, show up as a single staker. Their
+//! delegators hold points of the delegated stake, and their share of a reward or slash is pro rata
+//! to their points.
+
+use crate::{EraIndex, Page};
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{Perbill, RuntimeDebug};
+
+/// The simulated payout of a stash in an era.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PayoutSimulation<AccountId, Balance> {
+	/// The era of the payout.
+	pub era: EraIndex,
+	/// The total payout of the era, shared by all validators and their nominators.
+	pub era_payout: Balance,
+	/// Whether `era_payout` is estimated, because the era is still active.
+	///
+	/// The payout of the active era is only known once it ends. Until then, the payout of the
+	/// previous era and the reward points collected so far are used.
+	pub estimated: bool,
+	/// The account the rewards of the stash are paid to, `None` if they are not paid out.
+	pub payee: Option<AccountId>,
+	/// Whether the rewards are added to the stake of the stash.
+	pub restaked: bool,
+	/// The payout of the stash per exposure page it is part of.
+	pub pages: Vec<PagePayout<AccountId, Balance>>,
+	/// The sum of the payouts of all pages.
+	pub total: Balance,
+	/// The sum of the payouts of the pages that are not claimed yet.
+	pub pending: Balance,
+}
+
+/// The payout of a stash in a single exposure page of a validator.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PagePayout<AccountId, Balance> {
+	/// The validator of the exposure.
+	pub validator: AccountId,
+	/// The exposure page.
+	pub page: Page,
+	/// Whether the page is already claimed.
+	pub claimed: bool,
+	/// The commission of the validator paid with this page. Only non-zero for the validator.
+	pub commission: Balance,
+	/// The reward for the stake exposed in this page.
+	pub staking: Balance,
+	/// The split of `commission + staking` among the delegators of the stash, if any.
+	pub delegated: Option<DelegatedReward<Balance>>,
+}
+
+/// The split of the reward of a staker among its delegators.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DelegatedReward<Balance> {
+	/// The commission taken by the operator of the staker, e.g. a pool's commission.
+	pub commission: Balance,
+	/// The reward shared by the delegators, pro rata to their points.
+	pub delegators: Balance,
+	/// The points of all delegators that share the reward.
+	pub points: Balance,
+}
+
+/// The simulated slash of a validator and its nominators for an offence in an era.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SlashSimulation<AccountId, Balance> {
+	/// The era in which the offence happened.
+	pub era: EraIndex,
+	/// The slashed validator.
+	pub validator: AccountId,
+	/// The simulated slash fraction.
+	pub fraction: Perbill,
+	/// The slash fraction the validator was already reported for in `era`.
+	///
+	/// Only the difference to this fraction is slashed. If it is not lower than `fraction`, the
+	/// offence is ignored and nothing is slashed.
+	pub prior_fraction: Perbill,
+	/// The era at whose start the slash would be applied.
+	pub apply_era: EraIndex,
+	/// The slash per exposure page, in the order in which they are processed.
+	pub pages: Vec<PageSlash<AccountId, Balance>>,
+	/// The total amount slashed.
+	pub total: Balance,
+	/// The total amount paid to reporters out of the slash.
+	pub reward: Balance,
+}
+
+/// The slash of a single exposure page of a validator.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PageSlash<AccountId, Balance> {
+	/// The exposure page.
+	pub page: Page,
+	/// The slashed stakers of the page.
+	pub stakers: Vec<SlashedStaker<AccountId, Balance>>,
+	/// The amount paid to reporters out of the slash of this page.
+	pub reward: Balance,
+}
+
+/// The slash of a single staker, and its impact on the staker's ledger.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SlashedStaker<AccountId, Balance> {
+	/// The stash of the staker.
+	pub who: AccountId,
+	/// The amount slashed.
+	///
+	/// Might be less than the slash due according to the exposure, if the staker unbonded in the
+	/// meantime.
+	pub amount: Balance,
+	/// The active stake of the staker after the slash.
+	pub active: Balance,
+	/// The unlocking chunks of the staker after the slash, as `(era, value)`.
+	pub unlocking: Vec<(EraIndex, Balance)>,
+	/// The stake of the delegators of the staker before and after the slash, if it has any.
+	pub delegated: Option<DelegatedSlash<Balance>>,
+}
+
+/// The slash of the delegators of a staker.
+///
+/// The slash of a delegator is its share of the difference between `before` and `after`, in the
+/// bonded stake and in each unbonding era it holds points of.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DelegatedSlash<Balance> {
+	/// The stake of the delegators before the slash.
+	pub before: DelegatedStake<Balance>,
+	/// The stake of the delegators after the slash.
+	pub after: DelegatedStake<Balance>,
+}
+
+/// The stake of the delegators of a staker, bonded and unbonding.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DelegatedStake<Balance> {
+	/// The bonded stake.
+	pub bonded: DelegatedBalance<Balance>,
+	/// The stake unbonding per era, as `(era, balance)`, where `era` is the era in which the
+	/// stake can be withdrawn.
+	pub unbonding: Vec<(EraIndex, DelegatedBalance<Balance>)>,
+}
+
+/// A balance shared by delegators, pro rata to their points.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DelegatedBalance<Balance> {
+	/// The points of all delegators sharing `balance`.
+	pub points: Balance,
+	/// The shared balance.
+	pub balance: Balance,
+}
@@ -0,0 +1,396 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains the [`CompareCmd`] as entry point for the CLI to compare two benchmark runs.
+
+use super::formula::{self, Formulas, Metric, BASE_TERM};
+use clap::{Parser, ValueEnum};
+use comfy_table::Table;
+use sc_cli::{CliConfiguration, Result, SharedParams};
+use std::{collections::BTreeSet, fmt::Write, fs, path::PathBuf};
+
+/// Compare the weights of two benchmark runs.
+///
+/// Both runs must be given in the same format: either as JSON results that were written with
+/// `--json-file`, or as weight files that were written with `--output`. A directory can be passed
+/// to compare all `.json` or `.rs` files in it at once. Example:
+///
+/// $ frame-omni-bencher v1 benchmark compare old/weights new/weights --threshold 5
+///
+/// Every base weight and component slope is compared in its ref time, proof size, storage reads
+/// and storage writes. The command fails if any of them increased by more than the threshold.
+///
+/// Note that proof sizes of JSON results are the recorded ones, while those of weight files are
+/// the estimated ones.
+#[derive(Debug, Parser)]
+pub struct CompareCmd {
+	/// The results of the old run.
+	#[arg(value_name = "OLD")]
+	pub old: PathBuf,
+
+	/// The results of the new run.
+	#[arg(value_name = "NEW")]
+	pub new: PathBuf,
+
+	/// Maximal relative increase of any value before the command fails.
+	///
+	/// Changes of this size or less are also not reported, unless `--all` is given.
+	#[arg(long, default_value_t = 10.0, value_name = "PERCENT")]
+	pub threshold: f64,
+
+	/// Report all changes, including those within the threshold.
+	#[arg(long)]
+	pub all: bool,
+
+	/// The format of the report.
+	#[arg(long, value_enum, ignore_case = true, default_value_t = CompareFormat::Text)]
+	pub format: CompareFormat,
+
+	/// Write the report into this file instead of printing it.
+	#[arg(long)]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+}
+
+/// The format of the comparison report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompareFormat {
+	/// A table for the console.
+	Text,
+	/// A Markdown table, for example for a PR comment.
+	Markdown,
+}
+
+/// A single value of a weight formula that differs between the two runs.
+#[derive(Debug, Clone, PartialEq)]
+struct Change {
+	pallet: String,
+	extrinsic: String,
+	/// Either [`BASE_TERM`] or the name of a component.
+	term: String,
+	metric: Metric,
+	old: u128,
+	new: u128,
+}
+
+impl Change {
+	/// The relative change in percent, or `None` if the old value was zero.
+	fn percent(&self) -> Option<f64> {
+		(self.old != 0).then(|| (self.new as f64 - self.old as f64) / self.old as f64 * 100.0)
+	}
+
+	/// Whether the absolute change is larger than `threshold` percent.
+	fn is_significant(&self, threshold: f64) -> bool {
+		self.percent().map_or(true, |percent| percent.abs() > threshold)
+	}
+
+	/// Whether the value increased by more than `threshold` percent.
+	fn is_regression(&self, threshold: f64) -> bool {
+		self.new > self.old && self.is_significant(threshold)
+	}
+
+	fn format_percent(&self) -> String {
+		match self.percent() {
+			Some(percent) => format!("{percent:+.2} %"),
+			None => "new".into(),
+		}
+	}
+}
+
+/// The differences between the formulas of two runs.
+#[derive(Debug, Default)]
+struct Comparison {
+	changes: Vec<Change>,
+	/// Benchmarks that only exist in the new run.
+	added: Vec<(String, String)>,
+	/// Benchmarks that only exist in the old run.
+	removed: Vec<(String, String)>,
+}
+
+impl Comparison {
+	fn new(old: &Formulas, new: &Formulas) -> Self {
+		let mut comparison = Self::default();
+		for ((pallet, extrinsic), old_formula) in old {
+			let Some(new_formula) = new.get(&(pallet.clone(), extrinsic.clone())) else {
+				comparison.removed.push((pallet.clone(), extrinsic.clone()));
+				continue
+			};
+
+			let components = old_formula.slopes.keys().chain(new_formula.slopes.keys());
+			let terms = std::iter::once(BASE_TERM)
+				.chain(components.map(String::as_str).collect::<BTreeSet<_>>());
+			for term in terms {
+				let (old_term, new_term) = (old_formula.term(term), new_formula.term(term));
+				for metric in Metric::ALL {
+					let (old, new) = (old_term.get(metric), new_term.get(metric));
+					if old != new {
+						comparison.changes.push(Change {
+							pallet: pallet.clone(),
+							extrinsic: extrinsic.clone(),
+							term: term.to_string(),
+							metric,
+							old,
+							new,
+						});
+					}
+				}
+			}
+		}
+		comparison.added =
+			new.keys().filter(|key| !old.contains_key(*key)).cloned().collect::<Vec<_>>();
+
+		comparison
+	}
+}
+
+impl CompareCmd {
+	/// Compare the two runs and report the differences.
+	///
+	/// Returns an error if any value increased by more than the threshold.
+	pub fn run(&self) -> Result<()> {
+		if !self.threshold.is_finite() || self.threshold < 0.0 {
+			return Err("The threshold must be a non-negative percentage".into())
+		}
+
+		let (old_kind, old) = formula::load(&self.old)?;
+		let (new_kind, new) = formula::load(&self.new)?;
+		if old_kind != new_kind {
+			return Err("Cannot compare JSON results with weight files".into())
+		}
+
+		let comparison = Comparison::new(&old, &new);
+		let report = self.report(&comparison);
+		match &self.output {
+			Some(path) => fs::write(path, report)?,
+			None => print!("{report}"),
+		}
+
+		let regressions =
+			comparison.changes.iter().filter(|c| c.is_regression(self.threshold)).count();
+		if regressions > 0 {
+			return Err(
+				format!("{regressions} values increased by more than {} %", self.threshold).into()
+			)
+		}
+
+		Ok(())
+	}
+
+	/// Render the report in the configured format.
+	fn report(&self, comparison: &Comparison) -> String {
+		let changes = comparison
+			.changes
+			.iter()
+			.filter(|c| self.all || c.is_significant(self.threshold))
+			.collect::<Vec<_>>();
+		let regressions = changes.iter().filter(|c| c.is_regression(self.threshold)).count();
+		let summary = format!(
+			"{} changed values, {} of them above the threshold of {} %. {} benchmarks added, {} \
+			 removed.",
+			changes.len(),
+			regressions,
+			self.threshold,
+			comparison.added.len(),
+			comparison.removed.len(),
+		);
+
+		match self.format {
+			CompareFormat::Text => self.text_report(comparison, &changes, &summary),
+			CompareFormat::Markdown => self.markdown_report(comparison, &changes, &summary),
+		}
+	}
+
+	fn text_report(&self, comparison: &Comparison, changes: &[&Change], summary: &str) -> String {
+		let mut table = Table::new();
+		table.set_header(["Pallet", "Extrinsic", "Term", "Metric", "Old", "New", "Change"]);
+		for change in changes {
+			table.add_row([
+				change.pallet.clone(),
+				change.extrinsic.clone(),
+				change.term.clone(),
+				change.metric.name().to_string(),
+				change.old.to_string(),
+				change.new.to_string(),
+				self.format_change(change),
+			]);
+		}
+
+		let mut report = String::new();
+		if !changes.is_empty() {
+			let _ = writeln!(report, "{table}");
+		}
+		for (pallet, extrinsic) in &comparison.added {
+			let _ = writeln!(report, "Added: {pallet}::{extrinsic}");
+		}
+		for (pallet, extrinsic) in &comparison.removed {
+			let _ = writeln!(report, "Removed: {pallet}::{extrinsic}");
+		}
+		let _ = writeln!(report, "{summary}");
+		report
+	}
+
+	fn markdown_report(
+		&self,
+		comparison: &Comparison,
+		changes: &[&Change],
+		summary: &str,
+	) -> String {
+		let mut report = String::from("### Weight comparison\n\n");
+		if !changes.is_empty() {
+			report.push_str("| Pallet | Extrinsic | Term | Metric | Old | New | Change |\n");
+			report.push_str("|---|---|---|---|--:|--:|--:|\n");
+			for change in changes {
+				let _ = writeln!(
+					report,
+					"| `{}` | `{}` | `{}` | {} | {} | {} | {} |",
+					change.pallet,
+					change.extrinsic,
+					change.term,
+					change.metric.name(),
+					change.old,
+					change.new,
+					self.format_change(change),
+				);
+			}
+			report.push('\n');
+		}
+		for (pallet, extrinsic) in &comparison.added {
+			let _ = writeln!(report, "- Added `{pallet}::{extrinsic}`");
+		}
+		for (pallet, extrinsic) in &comparison.removed {
+			let _ = writeln!(report, "- Removed `{pallet}::{extrinsic}`");
+		}
+		if !comparison.added.is_empty() || !comparison.removed.is_empty() {
+			report.push('\n');
+		}
+		let _ = writeln!(report, "{summary}");
+		report
+	}
+
+	/// Format the relative change and mark it if it exceeds the threshold.
+	fn format_change(&self, change: &Change) -> String {
+		if change.is_regression(self.threshold) {
+			format!("❌ {}", change.format_percent())
+		} else {
+			change.format_percent()
+		}
+	}
+}
+
+// Boilerplate
+impl CliConfiguration for CompareCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::compare::formula::{Formula, Resources};
+	use std::collections::BTreeMap;
+
+	fn cmd(threshold: f64, all: bool) -> CompareCmd {
+		let mut cmd = CompareCmd::parse_from(["compare", "old", "new"]);
+		cmd.threshold = threshold;
+		cmd.all = all;
+		cmd
+	}
+
+	fn formulas(entries: &[(&str, u128, u128)]) -> Formulas {
+		entries
+			.iter()
+			.map(|(extrinsic, base, slope)| {
+				let formula = Formula {
+					base: Resources { ref_time: *base, reads: 1, ..Default::default() },
+					slopes: BTreeMap::from([(
+						"n".to_string(),
+						Resources { ref_time: *slope, ..Default::default() },
+					)]),
+				};
+				(("pallet".to_string(), extrinsic.to_string()), formula)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn comparison_reports_changed_terms() {
+		let old = formulas(&[("a", 100, 10), ("b", 100, 10), ("c", 100, 10)]);
+		let new = formulas(&[("a", 100, 10), ("b", 105, 20), ("d", 100, 10)]);
+
+		let comparison = Comparison::new(&old, &new);
+
+		assert_eq!(comparison.added, vec![("pallet".to_string(), "d".to_string())]);
+		assert_eq!(comparison.removed, vec![("pallet".to_string(), "c".to_string())]);
+		let changes = comparison
+			.changes
+			.iter()
+			.map(|c| (c.extrinsic.as_str(), c.term.as_str(), c.metric, c.old, c.new))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			changes,
+			vec![("b", BASE_TERM, Metric::RefTime, 100, 105), ("b", "n", Metric::RefTime, 10, 20)]
+		);
+	}
+
+	#[test]
+	fn threshold_is_respected() {
+		let old = formulas(&[("a", 100, 0)]);
+		let new = formulas(&[("a", 105, 1)]);
+		let comparison = Comparison::new(&old, &new);
+		let [base, slope] = &comparison.changes[..] else { panic!("expected two changes") };
+
+		// A new slope always exceeds the threshold.
+		assert!(!base.is_regression(10.0));
+		assert!(slope.is_regression(10.0));
+		assert!(base.is_regression(4.0));
+		// Decreases are reported but are no regressions.
+		assert!(!Comparison::new(&new, &old).changes[0].is_regression(1.0));
+
+		let report = cmd(10.0, false).report(&comparison);
+		assert!(!report.contains("+5.00 %"));
+		assert!(report.contains("❌ new"));
+		assert!(cmd(10.0, true).report(&comparison).contains("+5.00 %"));
+	}
+
+	#[test]
+	fn negative_threshold_is_rejected() {
+		let err = cmd(-1.0, false).run().unwrap_err();
+		assert!(err.to_string().contains("non-negative percentage"));
+	}
+
+	#[test]
+	fn markdown_report_works() {
+		let comparison =
+			Comparison::new(&formulas(&[("a", 100, 10)]), &formulas(&[("a", 150, 10)]));
+		let mut cmd = cmd(10.0, false);
+		cmd.format = CompareFormat::Markdown;
+
+		assert_eq!(
+			cmd.report(&comparison),
+			"### Weight comparison\n\n\
+			 | Pallet | Extrinsic | Term | Metric | Old | New | Change |\n\
+			 |---|---|---|---|--:|--:|--:|\n\
+			 | `pallet` | `a` | `base` | ref_time | 100 | 150 | ❌ +50.00 % |\n\n\
+			 1 changed values, 1 of them above the threshold of 10 %. 0 benchmarks added, 0 \
+			 removed.\n"
+		);
+	}
+}
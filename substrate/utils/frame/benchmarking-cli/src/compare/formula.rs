@@ -0,0 +1,418 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loads the weight formulas of benchmarks from JSON results or generated weight files.
+
+use frame_benchmarking::{Analysis, BenchmarkBatchSplitResults, BenchmarkSelector};
+use regex::Regex;
+use sc_cli::Result;
+use std::{
+	collections::BTreeMap,
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// Name of the term that holds the base weight of a [`Formula`].
+pub(crate) const BASE_TERM: &str = "base";
+
+/// A single dimension of a weight formula.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Metric {
+	RefTime,
+	ProofSize,
+	Reads,
+	Writes,
+}
+
+impl Metric {
+	/// All metrics in the order that they are reported in.
+	pub(crate) const ALL: [Metric; 4] =
+		[Metric::RefTime, Metric::ProofSize, Metric::Reads, Metric::Writes];
+
+	/// Human readable name of the metric.
+	pub(crate) fn name(&self) -> &'static str {
+		match self {
+			Metric::RefTime => "ref_time",
+			Metric::ProofSize => "proof_size",
+			Metric::Reads => "reads",
+			Metric::Writes => "writes",
+		}
+	}
+}
+
+/// The resources of a single term of a [`Formula`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Resources {
+	/// Execution time in picoseconds.
+	pub ref_time: u128,
+	/// Proof size in bytes.
+	pub proof_size: u128,
+	/// Number of storage reads.
+	pub reads: u128,
+	/// Number of storage writes.
+	pub writes: u128,
+}
+
+impl Resources {
+	/// The value of `metric`.
+	pub(crate) fn get(&self, metric: Metric) -> u128 {
+		match metric {
+			Metric::RefTime => self.ref_time,
+			Metric::ProofSize => self.proof_size,
+			Metric::Reads => self.reads,
+			Metric::Writes => self.writes,
+		}
+	}
+
+	fn get_mut(&mut self, metric: Metric) -> &mut u128 {
+		match metric {
+			Metric::RefTime => &mut self.ref_time,
+			Metric::ProofSize => &mut self.proof_size,
+			Metric::Reads => &mut self.reads,
+			Metric::Writes => &mut self.writes,
+		}
+	}
+}
+
+/// The weight formula of a single benchmark.
+///
+/// The weight is `base + sum(slope * component)` over all components.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Formula {
+	/// The base weight that does not depend on any component.
+	pub base: Resources,
+	/// The slope per component. Components that have no effect are omitted.
+	pub slopes: BTreeMap<String, Resources>,
+}
+
+impl Formula {
+	/// The resources of a term, which is either [`BASE_TERM`] or the name of a component.
+	pub(crate) fn term(&self, term: &str) -> Resources {
+		if term == BASE_TERM {
+			self.base
+		} else {
+			self.slopes.get(term).copied().unwrap_or_default()
+		}
+	}
+
+	fn term_mut(&mut self, component: Option<String>) -> &mut Resources {
+		match component {
+			Some(component) => self.slopes.entry(component).or_default(),
+			None => &mut self.base,
+		}
+	}
+}
+
+/// The formulas of all benchmarks, keyed by pallet and extrinsic name.
+pub(crate) type Formulas = BTreeMap<(String, String), Formula>;
+
+/// The format of the files that the formulas were loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InputKind {
+	/// Raw results as written by `--json-file`.
+	Json,
+	/// Weight files as written by `--output`.
+	Weights,
+}
+
+impl InputKind {
+	fn from_path(path: &Path) -> Option<Self> {
+		match path.extension()?.to_str()? {
+			"json" => Some(InputKind::Json),
+			"rs" => Some(InputKind::Weights),
+			_ => None,
+		}
+	}
+}
+
+/// Load the formulas from a single file or from all files in a directory.
+pub(crate) fn load(path: &Path) -> Result<(InputKind, Formulas)> {
+	let files = if path.is_dir() {
+		let mut files = fs::read_dir(path)?
+			.map(|entry| entry.map(|e| e.path()))
+			.collect::<std::result::Result<Vec<PathBuf>, _>>()?;
+		files.retain(|file| file.is_file() && InputKind::from_path(file).is_some());
+		files.sort();
+		files
+	} else {
+		vec![path.to_path_buf()]
+	};
+
+	let mut kind = None;
+	let mut formulas = Formulas::new();
+	for file in files {
+		let file_kind = InputKind::from_path(&file).ok_or_else(|| {
+			format!("Expected a `.json` or `.rs` file, but got {}", file.display())
+		})?;
+		if kind.is_some_and(|kind| kind != file_kind) {
+			return Err(format!("{} mixes JSON results and weight files", path.display()).into())
+		}
+		kind = Some(file_kind);
+
+		match file_kind {
+			InputKind::Json => formulas_from_json(&file, &mut formulas)?,
+			InputKind::Weights => formulas_from_weights(&file, &mut formulas)?,
+		}
+	}
+
+	let kind = kind.ok_or_else(|| format!("No results found in {}", path.display()))?;
+	Ok((kind, formulas))
+}
+
+/// Analyze the raw results of a JSON file in the same way as the weight file writer does.
+///
+/// Proof sizes are the recorded ones, since the storage info that is needed to estimate them is
+/// not part of the JSON output.
+fn formulas_from_json(path: &Path, formulas: &mut Formulas) -> Result<()> {
+	let raw_data = fs::read(path)?;
+	let batches: Vec<BenchmarkBatchSplitResults> = serde_json::from_slice(&raw_data)
+		.map_err(|e| format!("Failed to deserialize {}: {}", path.display(), e))?;
+
+	for batch in batches {
+		let pallet = String::from_utf8_lossy(&batch.pallet);
+		let instance = String::from_utf8_lossy(&batch.instance);
+		let benchmark = String::from_utf8_lossy(&batch.benchmark).into_owned();
+		let formula = formula_from_batch(&batch).ok_or_else(|| {
+			format!("Could not analyze `{pallet}::{benchmark}` in {}", path.display())
+		})?;
+
+		formulas.insert((format!("{pallet}::{instance}"), benchmark), formula);
+	}
+
+	Ok(())
+}
+
+fn formula_from_batch(batch: &BenchmarkBatchSplitResults) -> Option<Formula> {
+	let mut formula = Formula::default();
+	for (metric, results, selector) in [
+		(Metric::RefTime, &batch.time_results, BenchmarkSelector::ExtrinsicTime),
+		(Metric::ProofSize, &batch.db_results, BenchmarkSelector::ProofSize),
+		(Metric::Reads, &batch.db_results, BenchmarkSelector::Reads),
+		(Metric::Writes, &batch.db_results, BenchmarkSelector::Writes),
+	] {
+		if results.is_empty() {
+			return None
+		}
+		let analysis = Analysis::min_squares_iqr(results, selector)?;
+
+		*formula.base.get_mut(metric) = analysis.base;
+		for (name, slope) in analysis.names.into_iter().zip(analysis.slopes) {
+			if slope != 0 {
+				*formula.term_mut(Some(name)).get_mut(metric) = slope;
+			}
+		}
+	}
+
+	Some(formula)
+}
+
+/// Parse the weight functions of a weight file.
+///
+/// The pallet is identified by the file name, since instances of the same pallet share their
+/// pallet name. Only the first implementation of every function is used.
+fn formulas_from_weights(path: &Path, formulas: &mut Formulas) -> Result<()> {
+	let content = fs::read_to_string(path)?;
+	let pallet = path
+		.file_stem()
+		.map(|stem| stem.to_string_lossy().into_owned())
+		.ok_or_else(|| format!("Invalid weight file name: {}", path.display()))?;
+
+	let weights = parse_weights(&content)
+		.map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+	for (extrinsic, formula) in weights {
+		formulas.entry((pallet.clone(), extrinsic)).or_insert(formula);
+	}
+
+	Ok(())
+}
+
+/// Parse all functions of the form `fn name(..) -> Weight { .. }`.
+///
+/// This expects the layout of the weight templates, where every term of the formula is on its own
+/// line. Signatures may be wrapped over multiple lines. A function that mentions `Weight` in its
+/// signature but cannot be parsed is an error, so that it is not silently left out of the
+/// comparison.
+fn parse_weights(content: &str) -> Result<Vec<(String, Formula)>> {
+	let function = Regex::new(r"^fn\s+(\w+)\s*\(.*\)\s*->\s*Weight\s*\{$").expect("valid regex");
+	let component = Regex::new(r"saturating_mul\((\w+)\.into\(\)\)").expect("valid regex");
+	let parts = Regex::new(r"Weight::from_parts\(([\d_]+)(?:u64)?,\s*([\d_]+)(?:u64)?\)")
+		.expect("valid regex");
+	let reads_writes =
+		Regex::new(r"\.reads_writes\(\(?([\d_]+)(?:u64)?\)?,\s*\(?([\d_]+)(?:u64)?\)?\)")
+			.expect("valid regex");
+	let reads = Regex::new(r"\.reads\(\(?([\d_]+)(?:u64)?\)?").expect("valid regex");
+	let writes = Regex::new(r"\.writes\(\(?([\d_]+)(?:u64)?\)?").expect("valid regex");
+
+	let mut result = Vec::new();
+	let mut current: Option<(String, Formula)> = None;
+	let mut signature: Option<String> = None;
+	for line in content.lines().map(str::trim) {
+		if line.starts_with("//") {
+			continue
+		}
+		if current.is_none() && (signature.is_some() || line.starts_with("fn ")) {
+			let signature = signature.get_or_insert_with(String::new);
+			if !signature.is_empty() {
+				signature.push(' ');
+			}
+			signature.push_str(line);
+		}
+		if let Some(sig) = signature.as_deref() {
+			if !sig.ends_with('{') && !sig.ends_with(';') {
+				continue
+			}
+			if let Some(captures) = function.captures(sig) {
+				current = Some((captures[1].to_string(), Formula::default()));
+			} else if sig.ends_with('{') && sig.contains("Weight") {
+				return Err(format!("Could not parse the weight function `{sig}`").into())
+			}
+			signature = None;
+			continue
+		}
+		let Some((_, formula)) = current.as_mut() else { continue };
+		if line == "}" {
+			result.extend(current.take());
+			continue
+		}
+
+		let term = formula.term_mut(component.captures(line).map(|c| c[1].to_string()));
+		if let Some(captures) = parts.captures(line) {
+			term.ref_time += parse_number(&captures[1]);
+			term.proof_size += parse_number(&captures[2]);
+		}
+		if let Some(captures) = reads_writes.captures(line) {
+			term.reads += parse_number(&captures[1]);
+			term.writes += parse_number(&captures[2]);
+		} else if let Some(captures) = reads.captures(line) {
+			term.reads += parse_number(&captures[1]);
+		} else if let Some(captures) = writes.captures(line) {
+			term.writes += parse_number(&captures[1]);
+		}
+	}
+
+	Ok(result)
+}
+
+/// Parse a number literal like `1_000_u64`.
+fn parse_number(literal: &str) -> u128 {
+	literal.replace('_', "").parse().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const WEIGHTS: &str = r#"
+impl<T: frame_system::Config> pallet_example::WeightInfo for WeightInfo<T> {
+	/// Storage: `Example::Value` (r:1 w:1)
+	/// Proof: `Example::Value` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn set_value() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1489`
+		// Minimum execution time: 4_000_000 picoseconds.
+		Weight::from_parts(4_263_000, 0)
+			.saturating_add(Weight::from_parts(0, 1489))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// The range of component `n` is `[0, 100]`.
+	fn accumulate(n: u32, _m: u32, ) -> Weight {
+		Weight::from_parts(7_000_000_u64, 1_000_u64)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(1_500_000_u64, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2503).saturating_mul(n.into()))
+	}
+	fn both() -> Weight {
+		Weight::from_parts(1, 2).saturating_add(T::DbWeight::get().reads_writes(3, 4))
+	}
+}
+"#;
+
+	fn resources(ref_time: u128, proof_size: u128, reads: u128, writes: u128) -> Resources {
+		Resources { ref_time, proof_size, reads, writes }
+	}
+
+	#[test]
+	fn parse_weights_works() {
+		let formulas = parse_weights(WEIGHTS).unwrap().into_iter().collect::<BTreeMap<_, _>>();
+		assert_eq!(formulas.len(), 3);
+
+		let set_value = &formulas["set_value"];
+		assert_eq!(set_value.base, resources(4_263_000, 1489, 1, 1));
+		assert!(set_value.slopes.is_empty());
+
+		let accumulate = &formulas["accumulate"];
+		assert_eq!(accumulate.base, resources(7_000_000, 1_000, 2, 0));
+		assert_eq!(
+			accumulate.slopes,
+			BTreeMap::from([("n".to_string(), resources(1_500_000, 2503, 1, 1))])
+		);
+
+		assert_eq!(formulas["both"].base, resources(1, 2, 3, 4));
+	}
+
+	#[test]
+	fn parse_weights_handles_wrapped_signatures() {
+		let content = r#"
+impl<T: frame_system::Config> pallet_example::WeightInfo for WeightInfo<T> {
+	fn wrapped(
+		n: u32,
+		m: u32,
+	) -> Weight {
+		Weight::from_parts(10, 20)
+			.saturating_add(Weight::from_parts(3, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(5, 0).saturating_mul(m.into()))
+	}
+}
+"#;
+		let formulas = parse_weights(content).unwrap().into_iter().collect::<BTreeMap<_, _>>();
+		assert_eq!(formulas.len(), 1);
+
+		let wrapped = &formulas["wrapped"];
+		assert_eq!(wrapped.base, resources(10, 20, 0, 0));
+		assert_eq!(
+			wrapped.slopes,
+			BTreeMap::from([
+				("m".to_string(), resources(5, 0, 0, 0)),
+				("n".to_string(), resources(3, 0, 0, 0)),
+			])
+		);
+	}
+
+	#[test]
+	fn parse_weights_rejects_unparsable_functions() {
+		let content = "fn broken<const N: u32>() -> Weight {\n\tWeight::zero()\n}\n";
+		assert!(parse_weights(content).is_err());
+	}
+
+	#[test]
+	fn only_first_implementation_is_used() {
+		let path = std::env::temp_dir().join("compare_only_first_implementation_is_used.rs");
+		fs::write(&path, format!("{WEIGHTS}\n{}", WEIGHTS.replace("4_263_000", "1"))).unwrap();
+
+		let (kind, formulas) = load(&path).unwrap();
+		fs::remove_file(&path).unwrap();
+
+		assert_eq!(kind, InputKind::Weights);
+		let key = ("compare_only_first_implementation_is_used".to_string(), "set_value".into());
+		assert_eq!(formulas[&key].base.ref_time, 4_263_000);
+	}
+}
@@ -34,6 +34,7 @@ use frame_benchmarking::{
 };
 use frame_support::traits::StorageInfo;
 use linked_hash_map::LinkedHashMap;
+use remote_externalities::RawSnapshot;
 use sc_cli::{execution_method_from_cli, ChainSpec, CliConfiguration, Result, SharedParams};
 use sc_client_db::BenchmarkingState;
 use sc_executor::{HeapAllocStrategy, WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY};
@@ -42,6 +43,7 @@ use sp_core::{
 		testing::{TestOffchainExt, TestTransactionPoolExt},
 		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
 	},
+	storage::Storage,
 	traits::{CallContext, CodeExecutor, ReadRuntimeVersionExt, WrappedRuntimeCode},
 	Hasher,
 };
@@ -152,6 +154,42 @@ fn combine_batches(
 		.collect::<Vec<_>>()
 }
 
+/// The state that every benchmark starts from.
+enum BaseState<H: Hash> {
+	/// A genesis state, built from a chain spec or runtime.
+	Genesis(Storage),
+	/// The raw trie of a remote-externalities snapshot.
+	Snapshot(RawSnapshot<H::Output>),
+}
+
+impl<H: Hash> BaseState<H> {
+	/// Create a fresh benchmarking state on top of this base state.
+	fn benchmarking_state(
+		&self,
+		cache_size: Option<usize>,
+		record_proof: bool,
+		enable_tracking: bool,
+	) -> Result<BenchmarkingState<H>> {
+		let state = match self {
+			Self::Genesis(storage) => BenchmarkingState::<H>::new(
+				storage.clone(),
+				cache_size,
+				record_proof,
+				enable_tracking,
+			)?,
+			Self::Snapshot(snapshot) => BenchmarkingState::<H>::from_raw_snapshot(
+				snapshot.raw_storage.clone(),
+				snapshot.storage_root,
+				cache_size,
+				record_proof,
+				enable_tracking,
+			)?,
+		};
+
+		Ok(state)
+	}
+}
+
 /// Explains possible reasons why the metadata for the benchmarking could not be found.
 const ERROR_API_NOT_FOUND: &'static str = "Did not find the benchmarking runtime api. \
 This could mean that you either did not build the node correctly with the \
@@ -253,23 +291,29 @@ impl PalletCmd {
 			return self.output_from_results(&batches)
 		}
 
-		let state_handler =
-			self.state_handler_from_cli::<SubstrateAndExtraHF<ExtraHostFunctions>>(chain_spec)?;
-		let genesis_storage =
-			state_handler.build_storage::<SubstrateAndExtraHF<ExtraHostFunctions>>(None)?;
+		let base_state = match &self.snapshot {
+			Some(snapshot_path) => {
+				log::info!(target: LOG_TARGET, "Loading base state from snapshot {:?}", snapshot_path);
+				let snapshot = RawSnapshot::<Hasher::Output>::load(snapshot_path)
+					.map_err(|e| format!("Failed to load snapshot {:?}: {}", snapshot_path, e))?;
+				BaseState::Snapshot(snapshot)
+			},
+			None => {
+				let state_handler = self
+					.state_handler_from_cli::<SubstrateAndExtraHF<ExtraHostFunctions>>(
+						chain_spec,
+					)?;
+				let genesis_storage =
+					state_handler.build_storage::<SubstrateAndExtraHF<ExtraHostFunctions>>(None)?;
+				BaseState::Genesis(genesis_storage)
+			},
+		};
 
 		let cache_size = Some(self.database_cache_size as usize);
-		let state_with_tracking = BenchmarkingState::<Hasher>::new(
-			genesis_storage.clone(),
-			cache_size,
-			// Record proof size
-			true,
-			// Enable storage tracking
-			true,
-		)?;
+		// Record proof size and enable storage tracking.
+		let state_with_tracking = base_state.benchmarking_state(cache_size, true, true)?;
 
-		let state_without_tracking = BenchmarkingState::<Hasher>::new(
-			genesis_storage,
+		let state_without_tracking = base_state.benchmarking_state(
 			cache_size,
 			// Proof recording depends on CLI settings
 			!self.disable_proof_recording,
@@ -708,8 +752,8 @@ impl PalletCmd {
 
 	/// Load the runtime blob for this benchmark.
 	///
-	/// The blob will either be loaded from the `:code` key out of the chain spec, or from a file
-	/// when specified with `--runtime`.
+	/// The blob will either be loaded from the `:code` key out of the chain spec or snapshot, or
+	/// from a file when specified with `--runtime`.
 	fn runtime_blob<'a, H: Hash>(
 		&self,
 		state: &'a BenchmarkingState<H>,
@@ -1015,14 +1059,28 @@ impl PalletCmd {
 			unreachable!("Clap should not allow both `--runtime` and `--chain` to be provided.")
 		}
 
-		if chain_spec.is_none() && self.runtime.is_none() && self.shared_params.chain.is_none() {
+		if chain_spec.is_none() &&
+			self.runtime.is_none() &&
+			self.shared_params.chain.is_none() &&
+			self.snapshot.is_none()
+		{
 			return Err((
 				ErrorKind::MissingRequiredArgument,
-				"Provide either a runtime via `--runtime` or a chain spec via `--chain`"
+				"Provide either a runtime via `--runtime`, a chain spec via `--chain` or a snapshot \
+				 via `--snapshot`"
 					.to_string(),
 			))
 		}
 
+		if let Some(snapshot) = &self.snapshot {
+			if !snapshot.is_file() {
+				return Err((
+					ErrorKind::InvalidValue,
+					format!("Snapshot file could not be found: {snapshot:?}"),
+				));
+			}
+		}
+
 		match self.genesis_builder {
 			Some(GenesisBuilderPolicy::SpecGenesis | GenesisBuilderPolicy::SpecRuntime) =>
 				if chain_spec.is_none() && self.shared_params.chain.is_none() {
@@ -1206,6 +1264,55 @@ mod tests {
 		]);
 		cli_fail(&["test", "--runtime", "path/to/spec", "--genesis-builder", "spec-genesis"]);
 
+		// Snapshot tests
+		let snapshot = "../remote-externalities/test_data/test.snap";
+		cli_succeed(&["test", "--extrinsic", "", "--pallet", "", "--snapshot", snapshot])?;
+		cli_succeed(&[
+			"test",
+			"--extrinsic",
+			"",
+			"--pallet",
+			"",
+			"--snapshot",
+			snapshot,
+			"--runtime",
+			"path/to/runtime",
+		])?;
+		cli_fail(&["test", "--extrinsic", "", "--pallet", "", "--snapshot", "path/to/snapshot"]);
+		cli_fail(&[
+			"test",
+			"--extrinsic",
+			"",
+			"--pallet",
+			"",
+			"--snapshot",
+			snapshot,
+			"--chain",
+			"path/to/spec",
+		]);
+		cli_fail(&[
+			"test",
+			"--extrinsic",
+			"",
+			"--pallet",
+			"",
+			"--snapshot",
+			snapshot,
+			"--genesis-builder",
+			"none",
+		]);
+		cli_fail(&[
+			"test",
+			"--extrinsic",
+			"",
+			"--pallet",
+			"",
+			"--snapshot",
+			snapshot,
+			"--genesis-builder-preset",
+			"preset",
+		]);
+
 		// Spec tests
 		cli_succeed(&["test", "--extrinsic", "", "--pallet", "", "--chain", "path/to/spec"])?;
 		cli_succeed(&[
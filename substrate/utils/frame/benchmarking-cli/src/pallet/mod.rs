@@ -204,6 +204,18 @@ pub struct PalletCmd {
 	#[arg(long, default_value = sp_genesis_builder::DEV_RUNTIME_PRESET)]
 	pub genesis_builder_preset: String,
 
+	/// Use the state of a remote-externalities snapshot as the base state of all benchmarks.
+	///
+	/// No genesis state is built. Instead, the trie of the snapshot is loaded as-is and the setup
+	/// of every benchmark runs on top of it. Storage reads and recorded proof sizes therefore
+	/// reflect the trie depth and storage sizes of the chain that the snapshot was taken from.
+	/// Consider lowering `--additional-trie-layers` accordingly.
+	///
+	/// The runtime is loaded from the snapshot, unless `--runtime` is given. Snapshots can be
+	/// created with `try-runtime create-snapshot`.
+	#[arg(long, value_name = "PATH", conflicts_with_all = ["chain", "genesis_builder", "genesis_builder_preset"])]
+	pub snapshot: Option<PathBuf>,
+
 	/// DEPRECATED: This argument has no effect.
 	#[arg(long = "execution")]
 	pub execution: Option<String>,
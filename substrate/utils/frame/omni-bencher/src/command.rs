@@ -138,9 +138,10 @@ impl V1SubCommand {
 				},
 				BenchmarkCmd::Overhead(overhead_cmd) =>
 					overhead_cmd.run_with_default_builder_and_spec::<OpaqueBlock, HostFunctions>(None),
+				BenchmarkCmd::Compare(compare_cmd) => compare_cmd.run(),
 				_ =>
 					return Err(
-						"Only the `v1 benchmark pallet` and `v1 benchmark overhead` command is currently supported".into()
+						"Only the `v1 benchmark pallet`, `v1 benchmark overhead` and `v1 benchmark compare` commands are currently supported".into()
 					),
 			},
 		}
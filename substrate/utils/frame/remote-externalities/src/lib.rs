@@ -92,19 +92,57 @@ impl<B: BlockT> Snapshot<B> {
 
 	fn load(path: &PathBuf) -> Result<Snapshot<B>> {
 		let bytes = fs::read(path).map_err(|_| "fs::read failed.")?;
-		// The first item in the SCALE encoded struct bytes is the snapshot version. We decode and
-		// check that first, before proceeding to decode the rest of the snapshot.
-		let snapshot_version = SnapshotVersion::decode(&mut &*bytes)
-			.map_err(|_| "Failed to decode snapshot version")?;
-
-		if snapshot_version != SNAPSHOT_VERSION {
-			return Err("Unsupported snapshot version detected. Please create a new snapshot.")
-		}
+		ensure_snapshot_version(&bytes)?;
 
 		Decode::decode(&mut &*bytes).map_err(|_| "Decode failed")
 	}
 }
 
+/// Check the snapshot version of the SCALE encoded snapshot `bytes`.
+///
+/// The first item in the SCALE encoded struct bytes is the snapshot version. We decode and check
+/// that first, before proceeding to decode the rest of the snapshot.
+fn ensure_snapshot_version(bytes: &[u8]) -> Result<()> {
+	let snapshot_version =
+		SnapshotVersion::decode(&mut &*bytes).map_err(|_| "Failed to decode snapshot version")?;
+
+	if snapshot_version != SNAPSHOT_VERSION {
+		return Err("Unsupported snapshot version detected. Please create a new snapshot.")
+	}
+
+	Ok(())
+}
+
+/// The raw trie of a snapshot, without the header of the block it was taken at.
+///
+/// In contrast to [`Builder`], this can be loaded without knowing the block type of the chain. It
+/// is meant for tools that operate directly on the trie database, such as the benchmarking CLI.
+pub struct RawSnapshot<Hash> {
+	/// The state version of the snapshot.
+	pub state_version: StateVersion,
+	/// The trie nodes, keyed by their prefixed hash, together with their reference count.
+	pub raw_storage: Vec<(Vec<u8>, (Vec<u8>, i32))>,
+	/// The storage root of the trie.
+	pub storage_root: Hash,
+}
+
+impl<Hash: Decode> RawSnapshot<Hash> {
+	/// Load the raw trie of the snapshot file at `path`.
+	pub fn load(path: &Path) -> Result<Self> {
+		let bytes = fs::read(path).map_err(|_| "fs::read failed.")?;
+		ensure_snapshot_version(&bytes)?;
+
+		// Same field order as `Snapshot`, but the trailing header is never decoded.
+		let (_, state_version, raw_storage, storage_root) =
+			<(SnapshotVersion, StateVersion, Vec<(Vec<u8>, (Vec<u8>, i32))>, Hash)>::decode(
+				&mut &*bytes,
+			)
+			.map_err(|_| "Decode failed")?;
+
+		Ok(Self { state_version, raw_storage, storage_root })
+	}
+}
+
 /// An externalities that acts exactly the same as [`sp_io::TestExternalities`] but has a few extra
 /// bits and pieces to it, and can be loaded remotely.
 pub struct RemoteExternalities<B: BlockT> {
@@ -1268,6 +1306,25 @@ mod tests {
 			.execute_with(|| {});
 	}
 
+	#[tokio::test]
+	async fn can_load_raw_snapshot() {
+		init_logger();
+		let ext = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new("test_data/test.snap"),
+			}))
+			.build()
+			.await
+			.unwrap();
+
+		let raw =
+			RawSnapshot::<<Block as BlockT>::Hash>::load(Path::new("test_data/test.snap")).unwrap();
+
+		assert!(!raw.raw_storage.is_empty());
+		assert_eq!(raw.state_version, ext.state_version);
+		assert_eq!(&raw.storage_root, ext.backend.root());
+	}
+
 	#[tokio::test]
 	async fn can_exclude_from_snapshot() {
 		init_logger();
@@ -137,6 +137,9 @@ pub fn run() -> Result<()> {
 				Ok(cmd.run(components.client, config.chain_spec))
 			})
 		},
+		Some(Subcommand::ExportSnapshot(cmd)) => {
+			construct_async_run!(|components, cli, cmd, config| Ok(cmd.run(components.client)))
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			construct_async_run!(|components, cli, cmd, config| {
 				Ok(cmd.run(components.client, components.import_queue))
@@ -214,6 +217,7 @@ pub fn run() -> Result<()> {
 				}),
 				BenchmarkCmd::Machine(cmd) =>
 					runner.sync_run(|config| cmd.run(&config, SUBSTRATE_REFERENCE_HARDWARE.clone())),
+				BenchmarkCmd::Compare(cmd) => cmd.run(),
 				// NOTE: this allows the Client to leniently implement
 				// new benchmark commands without requiring a companion MR.
 				#[allow(unreachable_patterns)]
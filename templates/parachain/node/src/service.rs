@@ -316,6 +316,8 @@ pub async fn start_parachain_node(
 				network_provider: Arc::new(network.clone()),
 				is_validator: parachain_config.role.is_authority(),
 				enable_http_requests: false,
+				http_policy: parachain_config.offchain_worker.http_policy.clone(),
+				prometheus_registry: parachain_config.prometheus_registry().cloned(),
 				custom_extensions: move |_| vec![],
 			})?;
 		task_manager.spawn_handle().spawn(
@@ -463,6 +463,10 @@ pub use pallet_dummy_dim;
 #[cfg(feature = "pallet-election-provider-multi-block")]
 pub use pallet_election_provider_multi_block;
 
+/// An off-chain miner for the signed phase of pallet-election-provider-multi-block.
+#[cfg(feature = "pallet-election-provider-multi-block-miner")]
+pub use pallet_election_provider_multi_block_miner;
+
 /// PALLET two phase election providers.
 #[cfg(feature = "pallet-election-provider-multi-phase")]
 pub use pallet_election_provider_multi_phase;